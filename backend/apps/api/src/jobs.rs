@@ -0,0 +1,175 @@
+//! # Background Jobs
+//!
+//! Periodic maintenance tasks spawned at startup.
+//!
+//! - **Contact metadata retention** - strips IP addresses and user agents
+//!   from contact submissions older than `CONTACT_METADATA_RETENTION_DAYS`
+//!   (default 90), once a day.
+//! - **Invoice reminders** - marks issued invoices past their due date as
+//!   overdue and emails the customer a reminder every
+//!   `INVOICE_REMINDER_INTERVAL_DAYS` (default 7), once a day.
+//!
+//! Each job runs once per tenant, as that tenant.
+
+use lib_core::core_config;
+use lib_core::email::{email_service, EmailMessage};
+use lib_core::model::email_log::{EmailLogBmc, EmailLogForCreate};
+use lib_core::model::gdpr::GdprBmc;
+use lib_core::model::invoice::{InvoiceBmc, InvoiceReminder};
+use lib_core::model::tenant::TenantBmc;
+use lib_core::model::ModelManager;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often the retention job runs.
+const RETENTION_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How often overdue invoices are checked.
+const INVOICE_REMINDER_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Spawns all background jobs.
+pub fn spawn(mm: ModelManager) {
+    tokio::spawn(contact_metadata_retention(mm.clone()));
+    tokio::spawn(invoice_reminders(mm));
+}
+
+/// Purges expired contact submission metadata once a day.
+async fn contact_metadata_retention(mm: ModelManager) {
+    let retention_days = core_config().CONTACT_METADATA_RETENTION_DAYS;
+    let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        // Suspended tenants' customers are still owed the retention limit
+        for (tenant_id, mm) in tenant_mms(&mm, false).await {
+            match GdprBmc::purge_contact_metadata(&mm, retention_days).await {
+                Ok(0) => {}
+                Ok(purged) => info!(
+                    "Stripped IP/user agent from {} contact submissions older than {} days \
+                     for tenant {}",
+                    purged, retention_days, tenant_id
+                ),
+                Err(e) => error!(
+                    "Contact metadata retention job failed for tenant {}: {}",
+                    tenant_id, e
+                ),
+            }
+        }
+    }
+}
+
+/// Marks overdue invoices and sends payment reminders once a day.
+async fn invoice_reminders(mm: ModelManager) {
+    let reminder_interval_days = core_config().INVOICE_REMINDER_INTERVAL_DAYS;
+    let mut interval = tokio::time::interval(INVOICE_REMINDER_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        for (tenant_id, mm) in tenant_mms(&mm, true).await {
+            match InvoiceBmc::mark_overdue(&mm).await {
+                Ok(0) => {}
+                Ok(overdue) => info!(
+                    "Marked {} invoices overdue for tenant {}",
+                    overdue, tenant_id
+                ),
+                Err(e) => error!(
+                    "Invoice overdue check failed for tenant {}: {}",
+                    tenant_id, e
+                ),
+            }
+
+            let reminders = match InvoiceBmc::list_reminders_due(&mm, reminder_interval_days).await
+            {
+                Ok(reminders) => reminders,
+                Err(e) => {
+                    error!(
+                        "Failed to list invoice reminders for tenant {}: {}",
+                        tenant_id, e
+                    );
+                    continue;
+                }
+            };
+            for reminder in reminders {
+                send_invoice_reminder(&mm, reminder).await;
+            }
+        }
+    }
+}
+
+/// A ModelManager for each tenant, optionally only active ones. Tenants
+/// that can't be listed or connected to are logged and skipped.
+async fn tenant_mms(mm: &ModelManager, active_only: bool) -> Vec<(i32, ModelManager)> {
+    let tenants = match TenantBmc::list(mm, active_only).await {
+        Ok(tenants) => tenants,
+        Err(e) => {
+            error!("Failed to list tenants: {}", e);
+            return Vec::new();
+        }
+    };
+
+    tenants
+        .into_iter()
+        .filter_map(|tenant| match mm.for_tenant(tenant.id) {
+            Ok(tenant_mm) => Some((tenant.id, tenant_mm)),
+            Err(e) => {
+                error!("Failed to connect as tenant {}: {}", tenant.id, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Emails one overdue reminder and records it in the email log.
+async fn send_invoice_reminder(mm: &ModelManager, reminder: InvoiceReminder) {
+    let subject = format!("Payment reminder: invoice {}", reminder.number);
+    let message = EmailMessage {
+        to: reminder.customer_email.clone(),
+        subject: subject.clone(),
+        body: format!(
+            "Hello {},\n\nOur records show that invoice {} for \u{a3}{}.{:02} was due on {} \
+             and is still outstanding. If you have already paid, please ignore this email.\n\n\
+             Thank you.",
+            reminder.customer_name,
+            reminder.number,
+            reminder.balance_cents / 100,
+            reminder.balance_cents % 100,
+            reminder.due_date,
+        ),
+        content_type: "text/plain; charset=utf8".to_string(),
+    };
+
+    let status = match email_service().as_ref() {
+        Ok(service) => match service.send_email(message).await {
+            Ok(()) => "sent",
+            Err(e) => {
+                error!(
+                    "Failed to send reminder for invoice {}: {}",
+                    reminder.number, e
+                );
+                "failed"
+            }
+        },
+        Err(e) => {
+            error!("Email service not configured: {}", e);
+            return;
+        }
+    };
+
+    let entry = EmailLogForCreate {
+        customer_id: Some(reminder.customer_id),
+        to_email: reminder.customer_email,
+        subject,
+        kind: "invoice_reminder".to_string(),
+        related_email: None,
+        status: status.to_string(),
+    };
+    if let Err(e) = EmailLogBmc::create(mm, entry).await {
+        error!("Failed to record invoice reminder email: {}", e);
+    }
+
+    if status == "sent" {
+        if let Err(e) = InvoiceBmc::mark_reminded(mm, reminder.id).await {
+            error!("Failed to mark invoice {} reminded: {}", reminder.number, e);
+        }
+    }
+}
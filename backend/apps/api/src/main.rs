@@ -17,6 +17,17 @@
 //!   ```bash
 //!   cargo run -p api -- --test-db
 //!   ```
+//! - `--import-postcodes <CSV>` - Import postcode district locations from an open postcode
+//!   dataset (e.g. ONS Postcode Directory or Code-Point Open exported as CSV) and exit
+//!   ```bash
+//!   cargo run -p api -- --import-postcodes data/postcodes.csv
+//!   ```
+//! - `--issue-admin-token <USERNAME>` - Print an admin access token for an existing user and exit;
+//!   `--tenant <ID>` picks the user's tenant (default: 1)
+//!   ```bash
+//!   cargo run -p api -- --issue-admin-token sam
+//!   ```
+//! - `--revoke-admin-tokens <USERNAME>` - Revoke every admin token the user holds and exit
 //!
 //! ## Environment Variables
 //!
@@ -25,6 +36,49 @@
 //! - `APP_SERVER__PORT` - Server port (default: 8080)
 //! - `RUST_LOG` - Log level: debug, info, warn, error
 //! - `SMTP_*` - Email configuration
+//! - `CONTACT_METADATA_RETENTION_DAYS` - Days to keep contact IPs/user agents (default: 90)
+//! - `VAT_REGISTERED` - Charge VAT on invoices (default: false)
+//! - `VAT_RATE_BP` - VAT rate in basis points (default: 2000 = 20%)
+//! - `INVOICE_PAYMENT_TERMS_DAYS` - Days from issue until an invoice is due (default: 14)
+//! - `INVOICE_REMINDER_INTERVAL_DAYS` - Days between overdue reminders (default: 7)
+//! - `BUSINESS_NAME`, `BUSINESS_ADDRESS` (`|`-separated), `BUSINESS_PHONE`, `BUSINESS_EMAIL`,
+//!   `VAT_NUMBER` - Business details printed on quotes and invoices
+//! - `BUSINESS_LOGO_PATH` - JPEG logo for document headers
+//! - `QUOTE_TERMS`, `INVOICE_TERMS` - Terms printed on documents
+//! - `DOCUMENT_SIGNING_KEY` - Secret for signed public PDF links
+//! - `DOCUMENT_LINK_TTL_HOURS` - Signed link lifetime (default: 720)
+//! - `PUBLIC_BASE_URL` - Base URL for links sent to customers (default: https://xftradesman.com)
+//! - `ADMIN_TOKEN_KEY` - Secret for signing admin access tokens; `/api/admin/*` refuses every
+//!   request without it
+//! - `ADMIN_TOKEN_TTL_HOURS` - Admin token lifetime (default: 720)
+//! - `STORAGE_BACKEND` - Attachment storage: `local` or `s3` (default: local)
+//! - `STORAGE_LOCAL_PATH` - Attachment directory for local storage (default: ./data/attachments)
+//! - `S3_ENDPOINT`, `S3_BUCKET`, `S3_REGION`, `S3_ACCESS_KEY_ID`, `S3_SECRET_ACCESS_KEY` -
+//!   S3-compatible storage settings
+//! - `ATTACHMENT_MAX_BYTES` - Largest accepted upload (default: 10485760 = 10 MB)
+//! - `ATTACHMENT_LINK_TTL_MINUTES` - Signed attachment link lifetime (default: 60)
+//! - `LABOUR_COST_PER_HOUR_CENTS` - Labour cost used in margin reports (default: 2500)
+//! - `SERVICE_CENTRE_LATITUDE`, `SERVICE_CENTRE_LONGITUDE` - Where coverage distances are measured
+//!   from (default: Coventry city centre)
+//! - `TRAVEL_BANDS_KM` - Comma-separated outer edges of travel bands 1, 2, 3... (default: 10,20,30)
+//!
+//! ## Tenants
+//!
+//! One deployment serves several tradesman sites. Each request is served as
+//! the tenant whose domains include its `Host`, or the `X-Forwarded-Host`
+//! set by a trusted proxy, and the database only shows that tenant's rows.
+//! Hosts no tenant serves get 404 Not Found. Tenants are managed at
+//! `/api/admin/tenants` on the default tenant's hosts, with an admin token
+//! issued for the default tenant. The database user needs to be able to
+//! create roles when migrating.
+//!
+//! - `DEFAULT_TENANT_HOSTS` - Hosts the original site (tenant 1) serves besides its own domains
+//!   (default: localhost,127.0.0.1)
+//! - `TRUSTED_PROXIES` - IP addresses allowed to set `X-Forwarded-Host` and
+//!   `X-Forwarded-For`, such as the SSR server (default: 127.0.0.1,::1)
+//! - `TENANT_POOL_MAX` - Most tenant connection pools open at once; the least recently used
+//!   is closed to make room (default: 50)
+//! - `TENANT_POOL_CONNECTIONS` - Connections in each tenant's pool (default: 5)
 //!
 //! ## Development
 //!
@@ -44,6 +98,7 @@
 //! - RUST_LOG=info or warn
 
 mod config;
+mod jobs;
 mod middleware;
 mod web;
 
@@ -51,6 +106,7 @@ use crate::config::app_config;
 use clap::Parser;
 use lib_core::model::ModelManager;
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use tokio::net::TcpListener;
 
 /// Handyman Marketplace API Server
@@ -65,6 +121,22 @@ struct Args {
     /// Test database connection and exit
     #[arg(long)]
     test_db: bool,
+
+    /// Import postcode district locations from a CSV file and exit
+    #[arg(long, value_name = "CSV")]
+    import_postcodes: Option<PathBuf>,
+
+    /// Print an admin access token for an existing user and exit
+    #[arg(long, value_name = "USERNAME")]
+    issue_admin_token: Option<String>,
+
+    /// Revoke every admin access token a user holds and exit
+    #[arg(long, value_name = "USERNAME")]
+    revoke_admin_tokens: Option<String>,
+
+    /// Tenant of the user named by the admin token flags
+    #[arg(long, value_name = "ID", default_value_t = lib_core::model::tenant::DEFAULT_TENANT_ID)]
+    tenant: i32,
 }
 
 /// Main application entry point.
@@ -109,6 +181,19 @@ async fn main() -> anyhow::Result<()> {
         return run_migrations().await;
     }
 
+    // Handle --import-postcodes flag
+    if let Some(path) = &args.import_postcodes {
+        return import_postcodes(path).await;
+    }
+
+    // Handle --issue-admin-token and --revoke-admin-tokens flags
+    if let Some(username) = &args.issue_admin_token {
+        return issue_admin_token(username, args.tenant).await;
+    }
+    if let Some(username) = &args.revoke_admin_tokens {
+        return revoke_admin_tokens(username, args.tenant).await;
+    }
+
     let config = app_config();
     println!(
         "Server config: {}:{}",
@@ -134,6 +219,8 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    jobs::spawn(mm.clone());
+
     let mut app = middleware::apply_middleware(web::routes(mm));
 
     // Add Request ID and Tracing Middleware
@@ -154,7 +241,12 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("listening on {}", addr);
     let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // Peer addresses are recorded with quote acceptances
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -206,3 +298,84 @@ async fn run_migrations() -> anyhow::Result<()> {
     tracing::info!("Migrations completed successfully");
     Ok(())
 }
+
+/// Import postcode district locations and exit.
+///
+/// Used with `--import-postcodes` to load the coverage map from an open
+/// postcode dataset. Full postcodes are averaged into district centres;
+/// re-importing replaces the districts in the file and keeps the rest.
+///
+/// # Exit Codes
+///
+/// - 0 - Import successful
+/// - 1 - Unreadable file, missing columns or database error
+async fn import_postcodes(path: &Path) -> anyhow::Result<()> {
+    use lib_core::model::postcode_district::PostcodeDistrictBmc;
+
+    tracing::info!("Importing postcode districts from {}...", path.display());
+
+    let csv = std::fs::read_to_string(path)?;
+    let parsed = lib_core::postcode::parse_districts_csv(&csv)?;
+    if parsed.skipped > 0 {
+        tracing::warn!(
+            "Skipped {} rows without a valid postcode and location",
+            parsed.skipped
+        );
+    }
+
+    let mm = ModelManager::new().await?;
+    let imported = PostcodeDistrictBmc::import(&mm, &parsed.districts).await?;
+
+    tracing::info!("Imported {} postcode districts", imported);
+    Ok(())
+}
+
+/// Print an admin access token and exit.
+///
+/// Used with `--issue-admin-token` to let a user call `/api/admin/*` on
+/// their tenant's hosts, until the token expires or is revoked.
+///
+/// # Exit Codes
+///
+/// - 0 - Token printed
+/// - 1 - `ADMIN_TOKEN_KEY` unset, unknown user or database error
+async fn issue_admin_token(username: &str, tenant_id: i32) -> anyhow::Result<()> {
+    use lib_core::model::user::UserBmc;
+
+    let key = lib_core::auth::token_key()
+        .ok_or_else(|| anyhow::anyhow!("ADMIN_TOKEN_KEY must be set to issue admin tokens"))?;
+    let mm = ModelManager::new().await?.for_tenant(tenant_id)?;
+    let user = UserBmc::first_by_username(&mm, username)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No user {username} in tenant {tenant_id}"))?;
+    let token_salt = UserBmc::ensure_token_salt(&mm, user.id).await?;
+    let expires = time::OffsetDateTime::now_utc().unix_timestamp()
+        + lib_core::core_config().ADMIN_TOKEN_TTL_HOURS * 60 * 60;
+
+    println!(
+        "{}",
+        lib_core::auth::issue(key, user.id, tenant_id, token_salt, expires)
+    );
+    Ok(())
+}
+
+/// Revoke a user's admin access tokens and exit.
+///
+/// Used with `--revoke-admin-tokens` when a token leaks or someone leaves.
+///
+/// # Exit Codes
+///
+/// - 0 - Tokens revoked
+/// - 1 - Unknown user or database error
+async fn revoke_admin_tokens(username: &str, tenant_id: i32) -> anyhow::Result<()> {
+    use lib_core::model::user::UserBmc;
+
+    let mm = ModelManager::new().await?.for_tenant(tenant_id)?;
+    let user = UserBmc::first_by_username(&mm, username)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No user {username} in tenant {tenant_id}"))?;
+    UserBmc::reset_token_salt(&mm, user.id).await?;
+
+    tracing::info!("Revoked admin tokens of {}", username);
+    Ok(())
+}
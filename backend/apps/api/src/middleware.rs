@@ -95,8 +95,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::http::Request;
     use axum::{body::Body, routing::get, Router};
-    use http::Request;
     use tower::ServiceExt;
 
     async fn test_handler() -> &'static str {
@@ -112,7 +112,7 @@ mod tests {
         let request = Request::builder().uri("/test").body(Body::empty()).unwrap();
 
         let response = app_with_middleware.oneshot(request).await.unwrap();
-        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
     }
 
     #[tokio::test]
@@ -0,0 +1,286 @@
+//! Attachment handlers.
+//!
+//! Photo and document uploads on bookings and quotes, and the signed-link
+//! download that serves them. Admin listings return short-lived signed URLs
+//! rather than storage keys, so the files themselves are never public.
+
+use super::document::SignedLinkParams;
+use axum::extract::multipart::MultipartError;
+use axum::extract::{Json, Multipart, Path, Query};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use lib_core::document::link::{self, AttachmentVariant, LinkError};
+use lib_core::model::attachment::{
+    Attachment, AttachmentBmc, AttachmentForCreate, AttachmentOwner,
+};
+use lib_core::model::ModelManager;
+use lib_core::storage::{blob_store, BlobStore};
+use lib_web::{Error, TenantMm};
+use serde::Serialize;
+use serde_json::{json, Value};
+use shared::ApiResponse;
+use tracing::info;
+
+/// Multipart form accepted by the upload endpoints.
+#[derive(Debug, utoipa::ToSchema)]
+#[allow(dead_code)]
+pub struct AttachmentUpload {
+    /// JPEG, PNG, WebP or PDF file
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+    /// before, after, receipt or other (default other)
+    pub category: Option<String>,
+}
+
+/// An attachment with signed links to its files.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AttachmentView {
+    #[serde(flatten)]
+    pub attachment: Attachment,
+    /// Signed URL of the file
+    pub url: String,
+    /// Signed URL of the JPEG thumbnail, for images
+    pub thumbnail_url: Option<String>,
+    /// When the URLs stop working, as a Unix timestamp
+    pub expires: i64,
+}
+
+/// Uploads a photo or document to a booking.
+#[utoipa::path(
+    post,
+    path = "/api/admin/bookings/{id}/attachments",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    request_body(content = AttachmentUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Attachment stored", body = AttachmentView),
+        (status = 400, description = "Missing, empty, oversized or unsupported file", body = Value),
+        (status = 404, description = "Booking not found", body = Value)
+    )
+)]
+pub async fn upload_booking_attachment_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    multipart: Multipart,
+) -> Result<Json<ApiResponse<AttachmentView>>, Error> {
+    upload(&mm, AttachmentOwner::Booking(id), multipart).await
+}
+
+/// Uploads a photo or document to a quote.
+#[utoipa::path(
+    post,
+    path = "/api/admin/quotes/{id}/attachments",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Quote ID")),
+    request_body(content = AttachmentUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Attachment stored", body = AttachmentView),
+        (status = 400, description = "Missing, empty, oversized or unsupported file", body = Value),
+        (status = 404, description = "Quote not found", body = Value)
+    )
+)]
+pub async fn upload_quote_attachment_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    multipart: Multipart,
+) -> Result<Json<ApiResponse<AttachmentView>>, Error> {
+    upload(&mm, AttachmentOwner::Quote(id), multipart).await
+}
+
+/// Lists a booking's attachments with signed links.
+#[utoipa::path(
+    get,
+    path = "/api/admin/bookings/{id}/attachments",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    responses(
+        (status = 200, description = "Attachments, oldest first", body = [AttachmentView]),
+        (status = 404, description = "Booking not found", body = Value)
+    )
+)]
+pub async fn list_booking_attachments_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<AttachmentView>>>, Error> {
+    let attachments = AttachmentBmc::list(&mm, AttachmentOwner::Booking(id)).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Attachments retrieved",
+        attachments.into_iter().map(view).collect(),
+    )))
+}
+
+/// Lists a quote's attachments with signed links.
+#[utoipa::path(
+    get,
+    path = "/api/admin/quotes/{id}/attachments",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Quote ID")),
+    responses(
+        (status = 200, description = "Attachments, oldest first", body = [AttachmentView]),
+        (status = 404, description = "Quote not found", body = Value)
+    )
+)]
+pub async fn list_quote_attachments_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<AttachmentView>>>, Error> {
+    let attachments = AttachmentBmc::list(&mm, AttachmentOwner::Quote(id)).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Attachments retrieved",
+        attachments.into_iter().map(view).collect(),
+    )))
+}
+
+/// Deletes an attachment and its files.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/attachments/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Attachment ID")),
+    responses(
+        (status = 200, description = "Attachment deleted", body = Value),
+        (status = 404, description = "Attachment not found", body = Value)
+    )
+)]
+pub async fn delete_attachment_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    AttachmentBmc::delete(&mm, store()?, id).await?;
+
+    info!("Attachment {} deleted", id);
+    Ok(Json(ApiResponse::success(
+        "Attachment deleted",
+        json!({ "id": id }),
+    )))
+}
+
+/// Public attachment download authorised by a signed link.
+#[utoipa::path(
+    get,
+    path = "/api/attachments/{id}/{variant}",
+    tag = "documents",
+    params(
+        ("id" = i32, Path, description = "Attachment ID"),
+        ("variant" = AttachmentVariant, Path, description = "original or thumbnail"),
+        SignedLinkParams
+    ),
+    responses(
+        (status = 200, description = "The file, with its sniffed content type"),
+        (status = 401, description = "Invalid or expired link", body = Value),
+        (status = 404, description = "Attachment not found", body = Value)
+    )
+)]
+pub async fn public_attachment_handler(
+    TenantMm(mm): TenantMm,
+    Path((id, variant)): Path<(i32, AttachmentVariant)>,
+    Query(params): Query<SignedLinkParams>,
+) -> Result<impl IntoResponse, Error> {
+    link::verify_attachment(
+        link::signing_key(),
+        id,
+        variant,
+        params.expires,
+        &params.signature,
+        now(),
+    )
+    .map_err(|e| match e {
+        LinkError::InvalidSignature => Error::InvalidToken,
+        LinkError::Expired => Error::TokenExpired,
+    })?;
+
+    let attachment = AttachmentBmc::get(&mm, id).await?;
+    let (bytes, content_type) = AttachmentBmc::read(store()?, &attachment, variant).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}\"", attachment.filename),
+            ),
+            (header::CACHE_CONTROL, "private, no-store".to_string()),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        bytes,
+    ))
+}
+
+// region:    --- Helpers
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn store() -> Result<&'static dyn BlobStore, Error> {
+    blob_store().map_err(|e| Error::Model(e.into()))
+}
+
+fn view(attachment: Attachment) -> AttachmentView {
+    let now = now();
+    let original = link::attachment_link(attachment.id, AttachmentVariant::Original, now);
+    let thumbnail_url = attachment
+        .thumbnail_key
+        .as_ref()
+        .map(|_| link::attachment_link(attachment.id, AttachmentVariant::Thumbnail, now).url);
+
+    AttachmentView {
+        attachment,
+        url: original.url,
+        thumbnail_url,
+        expires: original.expires,
+    }
+}
+
+/// Reads the `file` and `category` fields and stores the file.
+async fn upload(
+    mm: &ModelManager,
+    owner: AttachmentOwner,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<AttachmentView>>, Error> {
+    let invalid = |e: MultipartError| {
+        if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            Error::ValidationError("The file is larger than the upload limit".into())
+        } else {
+            Error::ValidationError(e.body_text().into())
+        }
+    };
+    let mut file = None;
+    let mut category = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        match field.name() {
+            Some("file") => {
+                let filename = field.file_name().map(str::to_string);
+                file = Some((filename, field.bytes().await.map_err(invalid)?.to_vec()));
+            }
+            Some("category") => category = Some(field.text().await.map_err(invalid)?),
+            _ => {}
+        }
+    }
+    let (filename, bytes) =
+        file.ok_or_else(|| Error::ValidationError("A file field is required".into()))?;
+
+    let id = AttachmentBmc::create(
+        mm,
+        store()?,
+        AttachmentForCreate {
+            owner,
+            category: category.filter(|c| !c.trim().is_empty()),
+            filename,
+            bytes,
+        },
+    )
+    .await?;
+    let attachment = AttachmentBmc::get(mm, id).await?;
+
+    info!("Attachment {} stored for {:?}", id, owner);
+    Ok(Json(ApiResponse::success(
+        "Attachment stored",
+        view(attachment),
+    )))
+}
+
+// endregion: --- Helpers
@@ -0,0 +1,182 @@
+//! Blog handlers.
+//!
+//! Public endpoints serve the posts that are live — published, or scheduled
+//! with a publish time that has passed — to the Leptos site; admin endpoints
+//! draft, schedule and edit them.
+
+use axum::extract::{Json, Path, Query};
+use lib_core::model::blog_post::{
+    BlogPostBmc, BlogPostForCreate, BlogPostForUpdate, BlogPostRecord,
+};
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::{ApiResponse, BlogPost};
+use tracing::info;
+
+/// Query parameters for `GET /api/blog`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct BlogListParams {
+    /// Only posts in this category, e.g. `local-seo`
+    pub category: Option<String>,
+}
+
+/// Query parameters for `GET /api/admin/blog`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct BlogAdminListParams {
+    /// Only posts with this status: draft, scheduled or published
+    pub status: Option<String>,
+}
+
+// region:    --- Public Posts
+
+/// Lists the live posts, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/blog",
+    tag = "pages",
+    params(BlogListParams),
+    responses((status = 200, description = "Live posts, newest first", body = [BlogPost]))
+)]
+pub async fn list_posts_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<BlogListParams>,
+) -> Result<Json<ApiResponse<Vec<BlogPost>>>, Error> {
+    let posts = BlogPostBmc::list_published(&mm, params.category.as_deref()).await?;
+
+    Ok(Json(ApiResponse::success("Posts retrieved", posts)))
+}
+
+/// A live post.
+#[utoipa::path(
+    get,
+    path = "/api/blog/{slug}",
+    tag = "pages",
+    params(("slug" = String, Path, description = "Post slug, e.g. local-seo-guide")),
+    responses(
+        (status = 200, description = "Post", body = BlogPost),
+        (status = 404, description = "No live post with that slug", body = Value)
+    )
+)]
+pub async fn get_post_handler(
+    TenantMm(mm): TenantMm,
+    Path(slug): Path<String>,
+) -> Result<Json<ApiResponse<BlogPost>>, Error> {
+    let post = BlogPostBmc::get_published(&mm, &slug).await?;
+
+    Ok(Json(ApiResponse::success("Post retrieved", post)))
+}
+
+// endregion: --- Public Posts
+
+// region:    --- Admin Posts
+
+/// Drafts, schedules or publishes a post.
+#[utoipa::path(
+    post,
+    path = "/api/admin/blog",
+    tag = "admin",
+    request_body = BlogPostForCreate,
+    responses(
+        (status = 200, description = "Post created", body = BlogPostRecord),
+        (status = 400, description = "Invalid, or duplicates another post's slug", body = Value)
+    )
+)]
+pub async fn create_post_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<BlogPostForCreate>,
+) -> Result<Json<ApiResponse<BlogPostRecord>>, Error> {
+    let id = BlogPostBmc::create(&mm, req).await?;
+    let post = BlogPostBmc::get(&mm, id).await?;
+
+    info!("Blog post {} created as {}", id, post.status);
+    Ok(Json(ApiResponse::success("Post created", post)))
+}
+
+/// Lists every post, drafts included, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/admin/blog",
+    tag = "admin",
+    params(BlogAdminListParams),
+    responses((status = 200, description = "Posts, newest first", body = [BlogPostRecord]))
+)]
+pub async fn list_admin_posts_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<BlogAdminListParams>,
+) -> Result<Json<ApiResponse<Vec<BlogPostRecord>>>, Error> {
+    let posts = BlogPostBmc::list(&mm, params.status.as_deref()).await?;
+
+    Ok(Json(ApiResponse::success("Posts retrieved", posts)))
+}
+
+/// Gets a post, whatever its status.
+#[utoipa::path(
+    get,
+    path = "/api/admin/blog/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Post ID")),
+    responses(
+        (status = 200, description = "Post", body = BlogPostRecord),
+        (status = 404, description = "Post not found", body = Value)
+    )
+)]
+pub async fn get_admin_post_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<BlogPostRecord>>, Error> {
+    let post = BlogPostBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success("Post retrieved", post)))
+}
+
+/// Edits, schedules or publishes a post.
+#[utoipa::path(
+    patch,
+    path = "/api/admin/blog/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Post ID")),
+    request_body = BlogPostForUpdate,
+    responses(
+        (status = 200, description = "Post updated", body = BlogPostRecord),
+        (status = 400, description = "Invalid, or duplicates another post's slug", body = Value),
+        (status = 404, description = "Post not found", body = Value)
+    )
+)]
+pub async fn update_post_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<BlogPostForUpdate>,
+) -> Result<Json<ApiResponse<BlogPostRecord>>, Error> {
+    BlogPostBmc::update(&mm, id, req).await?;
+    let post = BlogPostBmc::get(&mm, id).await?;
+
+    info!("Blog post {} updated", id);
+    Ok(Json(ApiResponse::success("Post updated", post)))
+}
+
+/// Deletes a post.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/blog/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Post ID")),
+    responses(
+        (status = 200, description = "Post deleted", body = Value),
+        (status = 404, description = "Post not found", body = Value)
+    )
+)]
+pub async fn delete_post_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    BlogPostBmc::delete(&mm, id).await?;
+
+    info!("Blog post {} deleted", id);
+    Ok(Json(ApiResponse::success(
+        "Post deleted",
+        json!({ "id": id }),
+    )))
+}
+
+// endregion: --- Admin Posts
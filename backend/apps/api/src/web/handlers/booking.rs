@@ -0,0 +1,55 @@
+//! Admin booking handlers.
+//!
+//! Viewing a booking and rescheduling it: date, time, estimated duration
+//! and the site postcode used to match technicians.
+
+use axum::extract::{Json, Path};
+use lib_core::model::booking::{Booking, BookingBmc, BookingForUpdate};
+use lib_web::{Error, TenantMm};
+use shared::ApiResponse;
+use tracing::info;
+
+/// Gets a booking.
+#[utoipa::path(
+    get,
+    path = "/api/admin/bookings/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    responses(
+        (status = 200, description = "Booking", body = Booking),
+        (status = 404, description = "Booking not found", body = serde_json::Value)
+    )
+)]
+pub async fn get_booking_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Booking>>, Error> {
+    let booking = BookingBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success("Booking retrieved", booking)))
+}
+
+/// Updates a booking's schedule, site or status.
+#[utoipa::path(
+    patch,
+    path = "/api/admin/bookings/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    request_body = BookingForUpdate,
+    responses(
+        (status = 200, description = "Booking updated", body = Booking),
+        (status = 400, description = "Invalid date, time, postcode or status", body = serde_json::Value),
+        (status = 404, description = "Booking not found", body = serde_json::Value)
+    )
+)]
+pub async fn update_booking_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<BookingForUpdate>,
+) -> Result<Json<ApiResponse<Booking>>, Error> {
+    BookingBmc::update(&mm, id, req).await?;
+    let booking = BookingBmc::get(&mm, id).await?;
+
+    info!("Booking {} updated", id);
+    Ok(Json(ApiResponse::success("Booking updated", booking)))
+}
@@ -9,11 +9,12 @@
 //! - Email sent asynchronously to prevent blocking
 //! - Database errors logged but not exposed to client
 
-use axum::extract::{Json, State};
+use axum::extract::Json;
 use lib_core::email::email_service;
 use lib_core::model::contact::{ContactBmc, ContactForCreate};
+use lib_core::model::email_log::{EmailLogBmc, EmailLogForCreate};
 use lib_core::model::ModelManager;
-use lib_web::{Error, ValidatedJson};
+use lib_web::{Error, TenantMm, ValidatedJson};
 use serde_json::{json, Value};
 use shared::{ApiResponse, ContactForm};
 use tracing::{error, info};
@@ -30,7 +31,7 @@ use tracing::{error, info};
     )
 )]
 pub async fn api_contact_handler(
-    State(mm): State<ModelManager>,
+    TenantMm(mm): TenantMm,
     ValidatedJson(payload): ValidatedJson<ContactForm>,
 ) -> Result<Json<ApiResponse<Value>>, Error> {
     let contact = ContactForCreate {
@@ -52,10 +53,16 @@ pub async fn api_contact_handler(
 
     // Send notification email asynchronously (non-blocking)
     let email_payload = contact.clone();
+    let email_mm = mm.clone();
     tokio::spawn(async move {
-        if let Err(e) = send_contact_email(&email_payload).await {
-            error!("Failed to send contact notification email: {}", e);
-        }
+        let status = match send_contact_email(&email_payload).await {
+            Ok(()) => "sent",
+            Err(e) => {
+                error!("Failed to send contact notification email: {}", e);
+                "failed"
+            }
+        };
+        record_contact_email(&email_mm, &email_payload, status).await;
     });
 
     info!("Contact form submitted successfully, id: {}", id);
@@ -88,3 +95,25 @@ async fn send_contact_email(
     info!("Contact notification email sent for: {}", contact.email);
     Ok(())
 }
+
+/// Records the contact notification in the email log so it shows on the
+/// customer's timeline.
+async fn record_contact_email(mm: &ModelManager, contact: &ContactForCreate, status: &str) {
+    let entry = EmailLogForCreate {
+        customer_id: None,
+        to_email: std::env::var("CONTACT_EMAIL")
+            .unwrap_or_else(|_| "admin@xftradesman.com".to_string()),
+        subject: contact
+            .subject
+            .as_deref()
+            .map(|s| format!("Contact Form: {}", s))
+            .unwrap_or_else(|| "New Contact Form Submission".to_string()),
+        kind: "contact_notification".to_string(),
+        related_email: Some(contact.email.clone()),
+        status: status.to_string(),
+    };
+
+    if let Err(e) = EmailLogBmc::create(mm, entry).await {
+        error!("Failed to record contact notification email: {}", e);
+    }
+}
@@ -0,0 +1,40 @@
+//! Service coverage handler.
+//!
+//! Answers "do you cover me?" for the booking and quote forms (public
+//! endpoint).
+
+use axum::extract::{Json, Query};
+use lib_core::model::postcode_district::PostcodeDistrictBmc;
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use shared::{ApiResponse, Coverage};
+
+/// Query parameters for `/api/coverage`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct CoverageParams {
+    /// Full postcode or district, e.g. `CV1 1AA` or `CV1`
+    pub postcode: String,
+}
+
+/// Whether we cover a postcode, how far it is and which travel band it's in.
+#[utoipa::path(
+    get,
+    path = "/api/coverage",
+    tag = "coverage",
+    params(CoverageParams),
+    responses(
+        (status = 200, description = "Coverage for the postcode", body = Coverage),
+        (status = 400, description = "Not a UK postcode", body = serde_json::Value)
+    )
+)]
+pub async fn coverage_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<CoverageParams>,
+) -> Result<Json<ApiResponse<Coverage>>, Error> {
+    let coverage = PostcodeDistrictBmc::coverage(&mm, &params.postcode).await?;
+
+    Ok(Json(ApiResponse::success(
+        coverage.message.clone(),
+        coverage,
+    )))
+}
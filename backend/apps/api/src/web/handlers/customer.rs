@@ -0,0 +1,47 @@
+//! Admin customer handlers.
+//!
+//! Everything about a single customer, for when they call.
+
+use axum::extract::{Json, Path, Query};
+use lib_core::model::pagination::{PaginatedResult, Pagination};
+use lib_core::model::timeline::{TimelineBmc, TimelineEvent};
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use shared::ApiResponse;
+
+/// Query parameters for paginated admin lists.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct PageParams {
+    /// Page number, starting at 1 (default 1)
+    pub page: Option<u32>,
+    /// Items per page (default 20, max 100)
+    pub per_page: Option<u32>,
+}
+
+/// Lists a customer's activity timeline, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/admin/customers/{id}/timeline",
+    tag = "admin",
+    params(
+        ("id" = i32, Path, description = "Customer ID"),
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Paginated timeline events", body = serde_json::Value),
+        (status = 404, description = "Customer not found", body = serde_json::Value)
+    )
+)]
+pub async fn customer_timeline_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Query(params): Query<PageParams>,
+) -> Result<Json<ApiResponse<PaginatedResult<TimelineEvent>>>, Error> {
+    let pagination = Pagination::new(params.page.unwrap_or(1), params.per_page.unwrap_or(20));
+    let timeline = TimelineBmc::list_for_customer(&mm, id, pagination).await?;
+
+    Ok(Json(ApiResponse::success(
+        format!("{} events", timeline.total_items),
+        timeline,
+    )))
+}
@@ -0,0 +1,317 @@
+//! Document handlers.
+//!
+//! PDF downloads for quotes and invoices: admin downloads, signed public
+//! links for customers, and emailing PDFs as attachments.
+
+use axum::extract::{Json, Path, Query};
+use axum::http::header;
+use axum::response::IntoResponse;
+use lib_core::document::link::{self, LinkError, LinkedDocument, SignedLink};
+use lib_core::document::Branding;
+use lib_core::email::{email_service, EmailAttachment, EmailMessage};
+use lib_core::model::document::{DocumentBmc, RenderedDocument};
+use lib_core::model::email_log::{EmailLogBmc, EmailLogForCreate};
+use lib_core::model::quote::QuoteBmc;
+use lib_core::model::ModelManager;
+use lib_web::{Error, TenantMm};
+use serde::{Deserialize, Serialize};
+use shared::ApiResponse;
+use tracing::{error, info};
+
+/// Signature query parameters on a public document link.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct SignedLinkParams {
+    /// Expiry as a Unix timestamp
+    pub expires: i64,
+    /// URL-safe base64 HMAC signature
+    pub signature: String,
+}
+
+/// Outcome of emailing a document to its customer.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DocumentEmailResult {
+    /// Recipient address
+    pub to: String,
+    /// Delivery status: sent, failed
+    pub status: String,
+    /// Signed link included in the email
+    pub link: SignedLink,
+}
+
+/// Downloads a quote as a PDF.
+#[utoipa::path(
+    get,
+    path = "/api/admin/quotes/{id}/pdf",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Quote ID")),
+    responses(
+        (status = 200, description = "Quote PDF", content_type = "application/pdf"),
+        (status = 401, description = "Admin token missing or invalid", body = serde_json::Value),
+        (status = 404, description = "Quote not found", body = serde_json::Value)
+    )
+)]
+pub async fn admin_quote_pdf_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, Error> {
+    let doc = DocumentBmc::quote_pdf(&mm, id, &Branding::load(&mm).await?).await?;
+
+    Ok(pdf_response(doc))
+}
+
+/// Downloads an issued invoice or credit note as a PDF.
+#[utoipa::path(
+    get,
+    path = "/api/admin/invoices/{id}/pdf",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Invoice ID")),
+    responses(
+        (status = 200, description = "Invoice PDF", content_type = "application/pdf"),
+        (status = 401, description = "Admin token missing or invalid", body = serde_json::Value),
+        (status = 400, description = "Invoice is still a draft", body = serde_json::Value),
+        (status = 404, description = "Invoice not found", body = serde_json::Value)
+    )
+)]
+pub async fn admin_invoice_pdf_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, Error> {
+    let doc = DocumentBmc::invoice_pdf(&mm, id, &Branding::load(&mm).await?).await?;
+
+    Ok(pdf_response(doc))
+}
+
+/// Creates a signed public link to a quote PDF.
+#[utoipa::path(
+    post,
+    path = "/api/admin/quotes/{id}/pdf-link",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Quote ID")),
+    responses(
+        (status = 200, description = "Signed link", body = SignedLink),
+        (status = 404, description = "Quote not found", body = serde_json::Value)
+    )
+)]
+pub async fn quote_pdf_link_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<SignedLink>>, Error> {
+    QuoteBmc::get(&mm, id).await?;
+    let link = link::signed_link(LinkedDocument::Quotes, id, now());
+
+    Ok(Json(ApiResponse::success("Link created", link)))
+}
+
+/// Creates a signed public link to an invoice PDF.
+#[utoipa::path(
+    post,
+    path = "/api/admin/invoices/{id}/pdf-link",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Invoice ID")),
+    responses(
+        (status = 200, description = "Signed link", body = SignedLink),
+        (status = 400, description = "Invoice is still a draft", body = serde_json::Value),
+        (status = 404, description = "Invoice not found", body = serde_json::Value)
+    )
+)]
+pub async fn invoice_pdf_link_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<SignedLink>>, Error> {
+    // Renders once so drafts are rejected before a link is handed out
+    DocumentBmc::invoice_pdf(&mm, id, &Branding::load(&mm).await?).await?;
+    let link = link::signed_link(LinkedDocument::Invoices, id, now());
+
+    Ok(Json(ApiResponse::success("Link created", link)))
+}
+
+/// Public PDF download authorised by a signed link.
+#[utoipa::path(
+    get,
+    path = "/api/documents/{document}/{id}/pdf",
+    tag = "documents",
+    params(
+        ("document" = LinkedDocument, Path, description = "quotes or invoices"),
+        ("id" = i32, Path, description = "Document ID"),
+        SignedLinkParams
+    ),
+    responses(
+        (status = 200, description = "Document PDF", content_type = "application/pdf"),
+        (status = 401, description = "Invalid or expired link", body = serde_json::Value),
+        (status = 404, description = "Document not found", body = serde_json::Value)
+    )
+)]
+pub async fn public_document_pdf_handler(
+    TenantMm(mm): TenantMm,
+    Path((document, id)): Path<(LinkedDocument, i32)>,
+    Query(params): Query<SignedLinkParams>,
+) -> Result<impl IntoResponse, Error> {
+    link::verify(
+        link::signing_key(),
+        document,
+        id,
+        params.expires,
+        &params.signature,
+        now(),
+    )
+    .map_err(|e| match e {
+        LinkError::InvalidSignature => Error::InvalidToken,
+        LinkError::Expired => Error::TokenExpired,
+    })?;
+
+    let branding = &Branding::load(&mm).await?;
+    let doc = match document {
+        LinkedDocument::Quotes => DocumentBmc::quote_pdf(&mm, id, branding).await?,
+        LinkedDocument::Invoices => DocumentBmc::invoice_pdf(&mm, id, branding).await?,
+    };
+
+    Ok(pdf_response(doc))
+}
+
+/// Emails a quote PDF to its customer and marks a draft quote as sent.
+#[utoipa::path(
+    post,
+    path = "/api/admin/quotes/{id}/email",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Quote ID")),
+    responses(
+        (status = 200, description = "Email attempted", body = DocumentEmailResult),
+        (status = 400, description = "Customer has no email address, or quote already answered", body = serde_json::Value),
+        (status = 404, description = "Quote not found", body = serde_json::Value)
+    )
+)]
+pub async fn email_quote_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<DocumentEmailResult>>, Error> {
+    let doc = DocumentBmc::quote_pdf(&mm, id, &Branding::load(&mm).await?).await?;
+    let link = link::signed_link(LinkedDocument::Quotes, id, now());
+    let result = email_document(&mm, doc, "Your quote", "quote_pdf", link, None).await?;
+
+    if result.status == "sent" && QuoteBmc::get(&mm, id).await?.status == "draft" {
+        QuoteBmc::send(&mm, id).await?;
+    }
+
+    Ok(Json(ApiResponse::success("Quote emailed", result)))
+}
+
+/// Emails an invoice or credit note PDF to its customer.
+#[utoipa::path(
+    post,
+    path = "/api/admin/invoices/{id}/email",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Invoice ID")),
+    responses(
+        (status = 200, description = "Email attempted", body = DocumentEmailResult),
+        (status = 400, description = "Draft invoice or customer has no email address", body = serde_json::Value),
+        (status = 404, description = "Invoice not found", body = serde_json::Value)
+    )
+)]
+pub async fn email_invoice_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<DocumentEmailResult>>, Error> {
+    let doc = DocumentBmc::invoice_pdf(&mm, id, &Branding::load(&mm).await?).await?;
+    let link = link::signed_link(LinkedDocument::Invoices, id, now());
+    let result = email_document(&mm, doc, "Your invoice", "invoice_pdf", link, None).await?;
+
+    Ok(Json(ApiResponse::success("Invoice emailed", result)))
+}
+
+// region:    --- Helpers
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn pdf_response(doc: RenderedDocument) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}\"", doc.filename),
+            ),
+            (header::CACHE_CONTROL, "private, no-store".to_string()),
+        ],
+        doc.bytes,
+    )
+}
+
+/// Sends a rendered document to its customer and records it in the email
+/// log so it shows on the customer's timeline.
+///
+/// `details` is an extra paragraph printed after the link.
+pub(crate) async fn email_document(
+    mm: &ModelManager,
+    doc: RenderedDocument,
+    subject_prefix: &str,
+    kind: &str,
+    link: SignedLink,
+    details: Option<&str>,
+) -> Result<DocumentEmailResult, Error> {
+    let customer = doc.customer.as_ref();
+    let Some(to) = customer.and_then(|c| c.email.clone()) else {
+        return Err(Error::ValidationError(
+            "Customer has no email address".into(),
+        ));
+    };
+    let name = customer.map(|c| c.name.as_str()).unwrap_or_default();
+    let number = &doc.number;
+    let subject = format!("{subject_prefix} {number}");
+
+    let message = EmailMessage {
+        to: to.clone(),
+        subject: subject.clone(),
+        body: format!(
+            "Hello {name},\n\nPlease find {number} attached. You can also view it online:\n\n{}\n\n\
+             {}Thank you.",
+            link.url,
+            details.map(|d| format!("{d}\n\n")).unwrap_or_default()
+        ),
+        content_type: "text/plain; charset=utf8".to_string(),
+    };
+    let attachment = EmailAttachment {
+        filename: doc.filename.clone(),
+        content_type: "application/pdf".to_string(),
+        data: doc.bytes,
+    };
+
+    let status = match email_service().as_ref() {
+        Ok(service) => match service
+            .send_email_with_attachments(message, vec![attachment])
+            .await
+        {
+            Ok(()) => "sent",
+            Err(e) => {
+                error!("Failed to email {}: {}", doc.filename, e);
+                "failed"
+            }
+        },
+        Err(e) => {
+            error!("Email service not configured: {}", e);
+            "failed"
+        }
+    };
+
+    let entry = EmailLogForCreate {
+        customer_id: customer.map(|c| c.id),
+        to_email: to.clone(),
+        subject,
+        kind: kind.to_string(),
+        related_email: None,
+        status: status.to_string(),
+    };
+    if let Err(e) = EmailLogBmc::create(mm, entry).await {
+        error!("Failed to record document email: {}", e);
+    }
+
+    info!("Emailed {} ({})", doc.filename, status);
+    Ok(DocumentEmailResult {
+        to,
+        status: status.to_string(),
+        link,
+    })
+}
+
+// endregion: --- Helpers
@@ -0,0 +1,452 @@
+//! RSS and Atom feed handlers for the blog.
+//!
+//! `/blog/rss.xml` and `/blog/atom.xml` carry the latest live posts, and
+//! `/blog/category/{category}/rss.xml` and `.../atom.xml` those in one
+//! category. Feeds are built per request, so a scheduled post joins them
+//! once it goes live. Caches may keep a feed for [`FEED_MAX_AGE`] seconds,
+//! and a reader sending `If-Modified-Since` gets `304 Not Modified` until a
+//! post in the feed changes.
+//!
+//! Each tenant's feeds link to its own site (see [`Tenant::origin`]) and
+//! are described by its tagline.
+
+use axum::extract::Path;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, FixedOffset, Utc};
+use lib_core::model::blog_post::BlogPostBmc;
+use lib_core::model::tenant::{Tenant, TenantBmc, DEFAULT_TENANT_ID};
+use lib_core::model::{Error as ModelError, ModelManager};
+use lib_web::{Error, TenantMm};
+use shared::{BlogFeed, BlogPost, BLOG_PATH};
+
+/// Seconds caches may keep a feed.
+pub const FEED_MAX_AGE: u32 = 3600;
+
+/// Most posts in a feed, newest first.
+pub const FEED_LIMIT: usize = 20;
+
+/// What the platform's blog is about, for its feeds' description.
+const FEED_DESCRIPTION: &str =
+    "Expert marketing guides, SEO strategies, and website tips for UK tradesmen.";
+
+/// The site a feed belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FeedSite {
+    /// Origin the feed's links are on, e.g. `https://jonesplumbing.co.uk`
+    origin: String,
+    /// What the blog is about
+    description: String,
+}
+
+impl FeedSite {
+    fn for_tenant(tenant: &Tenant) -> Self {
+        let tagline = tenant.tagline.as_deref().map(str::trim);
+        let description = match tagline {
+            _ if tenant.id == DEFAULT_TENANT_ID => FEED_DESCRIPTION.to_string(),
+            Some(tagline) if !tagline.is_empty() => tagline.to_string(),
+            _ => format!("The latest from {}.", tenant.business_name),
+        };
+        Self {
+            origin: tenant.origin(),
+            description,
+        }
+    }
+
+    /// The blog's home page.
+    fn blog_url(&self) -> String {
+        format!("{}{}", self.origin, BLOG_PATH)
+    }
+}
+
+/// Feed formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+// region:    --- Feed Handlers
+
+/// Handler for /blog/rss.xml
+pub async fn rss_handler(TenantMm(mm): TenantMm, headers: HeaderMap) -> Result<Response, Error> {
+    feed_response(&mm, BlogFeed::ALL, FeedFormat::Rss, &headers).await
+}
+
+/// Handler for /blog/atom.xml
+pub async fn atom_handler(TenantMm(mm): TenantMm, headers: HeaderMap) -> Result<Response, Error> {
+    feed_response(&mm, BlogFeed::ALL, FeedFormat::Atom, &headers).await
+}
+
+/// Handler for /blog/category/{category}/rss.xml
+pub async fn category_rss_handler(
+    TenantMm(mm): TenantMm,
+    Path(category): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, Error> {
+    let feed = category_feed(&category)?;
+    feed_response(&mm, feed, FeedFormat::Rss, &headers).await
+}
+
+/// Handler for /blog/category/{category}/atom.xml
+pub async fn category_atom_handler(
+    TenantMm(mm): TenantMm,
+    Path(category): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, Error> {
+    let feed = category_feed(&category)?;
+    feed_response(&mm, feed, FeedFormat::Atom, &headers).await
+}
+
+// endregion: --- Feed Handlers
+
+// region:    --- Feed Helpers
+
+fn category_feed(category: &str) -> Result<BlogFeed, Error> {
+    BlogFeed::for_category(category).ok_or_else(|| {
+        ModelError::SlugNotFound {
+            entity: "BlogFeed",
+            slug: category.to_string(),
+        }
+        .into()
+    })
+}
+
+async fn feed_response(
+    mm: &ModelManager,
+    feed: BlogFeed,
+    format: FeedFormat,
+    headers: &HeaderMap,
+) -> Result<Response, Error> {
+    let site = FeedSite::for_tenant(&TenantBmc::get(mm, mm.tenant_id()).await?);
+    let mut posts = BlogPostBmc::list_published(mm, feed.category()).await?;
+    posts.truncate(FEED_LIMIT);
+    let updated = last_updated(&posts);
+
+    let cache_control = format!("public, max-age={}", FEED_MAX_AGE);
+    let last_modified = updated.map(http_date).unwrap_or_default();
+    if is_not_modified(headers, updated) {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [
+                (header::CACHE_CONTROL, cache_control),
+                (header::LAST_MODIFIED, last_modified),
+            ],
+        )
+            .into_response());
+    }
+
+    let (content_type, body) = match format {
+        FeedFormat::Rss => (
+            "application/rss+xml; charset=utf-8",
+            rss_xml(&site, feed, &posts),
+        ),
+        FeedFormat::Atom => (
+            "application/atom+xml; charset=utf-8",
+            atom_xml(&site, feed, &posts),
+        ),
+    };
+    let mut response = (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        body,
+    )
+        .into_response();
+    if updated.is_some() {
+        if let Ok(value) = last_modified.parse() {
+            response.headers_mut().insert(header::LAST_MODIFIED, value);
+        }
+    }
+
+    Ok(response)
+}
+
+/// Builds an RSS 2.0 feed of `posts`, newest first.
+fn rss_xml(site: &FeedSite, feed: BlogFeed, posts: &[BlogPost]) -> String {
+    let mut items = String::new();
+    for post in posts {
+        let url = post.canonical_url(&site.origin);
+        items.push_str(&format!(
+            r#"    <item>
+      <title>{}</title>
+      <link>{}</link>
+      <guid isPermaLink="true">{}</guid>
+      <description>{}</description>
+      <category>{}</category>
+      <dc:creator>{}</dc:creator>
+      <pubDate>{}</pubDate>
+    </item>
+"#,
+            escape_xml(&post.title),
+            escape_xml(&url),
+            escape_xml(&url),
+            escape_xml(&post.description),
+            escape_xml(post.category_name()),
+            escape_xml(&post.author),
+            parse_date(&post.published_at)
+                .map(|date| date.to_rfc2822())
+                .unwrap_or_default()
+        ));
+    }
+
+    let last_build = last_updated(posts).unwrap_or_else(|| Utc::now().fixed_offset());
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>{}</title>
+    <link>{}</link>
+    <description>{}</description>
+    <language>en-gb</language>
+    <lastBuildDate>{}</lastBuildDate>
+    <atom:link href="{}" rel="self" type="application/rss+xml"/>
+{}  </channel>
+</rss>
+"#,
+        escape_xml(&feed.title()),
+        escape_xml(&site.blog_url()),
+        escape_xml(&site.description),
+        last_build.to_rfc2822(),
+        escape_xml(&feed.rss_url(&site.origin)),
+        items
+    )
+}
+
+/// Builds an Atom feed of `posts`, newest first.
+fn atom_xml(site: &FeedSite, feed: BlogFeed, posts: &[BlogPost]) -> String {
+    let mut entries = String::new();
+    for post in posts {
+        let url = post.canonical_url(&site.origin);
+        let published = parse_date(&post.published_at);
+        entries.push_str(&format!(
+            r#"  <entry>
+    <title>{}</title>
+    <id>{}</id>
+    <link rel="alternate" type="text/html" href="{}"/>
+    <published>{}</published>
+    <updated>{}</updated>
+    <author><name>{}</name></author>
+    <category term="{}" label="{}"/>
+    <summary>{}</summary>
+  </entry>
+"#,
+            escape_xml(&post.title),
+            escape_xml(&url),
+            escape_xml(&url),
+            published.map(|date| date.to_rfc3339()).unwrap_or_default(),
+            post_updated(post)
+                .map(|date| date.to_rfc3339())
+                .unwrap_or_default(),
+            escape_xml(&post.author),
+            escape_xml(&post.category),
+            escape_xml(post.category_name()),
+            escape_xml(&post.description)
+        ));
+    }
+
+    let updated = last_updated(posts).unwrap_or_else(|| Utc::now().fixed_offset());
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="en-gb">
+  <title>{}</title>
+  <subtitle>{}</subtitle>
+  <id>{}</id>
+  <link rel="self" type="application/atom+xml" href="{}"/>
+  <link rel="alternate" type="text/html" href="{}"/>
+  <updated>{}</updated>
+{}</feed>
+"#,
+        escape_xml(&feed.title()),
+        escape_xml(&site.description),
+        escape_xml(&feed.atom_url(&site.origin)),
+        escape_xml(&feed.atom_url(&site.origin)),
+        escape_xml(&site.blog_url()),
+        updated.to_rfc3339(),
+        entries
+    )
+}
+
+fn parse_date(rfc3339: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(rfc3339).ok()
+}
+
+/// When a post last changed: its last edit, or going live if later.
+fn post_updated(post: &BlogPost) -> Option<DateTime<FixedOffset>> {
+    let published = parse_date(&post.published_at);
+    let edited = post.updated_at.as_deref().and_then(parse_date);
+    published.max(edited)
+}
+
+/// When any of `posts` last changed.
+fn last_updated(posts: &[BlogPost]) -> Option<DateTime<FixedOffset>> {
+    posts.iter().filter_map(post_updated).max()
+}
+
+/// `date` as an HTTP date, e.g. "Sun, 15 Dec 2024 09:00:00 GMT".
+fn http_date(date: DateTime<FixedOffset>) -> String {
+    date.with_timezone(&Utc)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+/// Whether the reader's copy, per `If-Modified-Since`, is still current.
+fn is_not_modified(headers: &HeaderMap, updated: Option<DateTime<FixedOffset>>) -> bool {
+    let since = headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok());
+
+    match (since, updated) {
+        (Some(since), Some(updated)) => updated.timestamp() <= since.timestamp(),
+        _ => false,
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// endregion: --- Feed Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn fx_post(slug: &str, published_at: &str, updated_at: Option<&str>) -> BlogPost {
+        BlogPost {
+            slug: slug.to_string(),
+            title: "Boilers & Radiators".to_string(),
+            description: "Five <quick> checks".to_string(),
+            category: "local-seo".to_string(),
+            keywords: vec![],
+            author: "XF Tradesmen".to_string(),
+            content: String::new(),
+            toc: vec![],
+            read_minutes: 1,
+            images: vec![],
+            published_at: published_at.to_string(),
+            updated_at: updated_at.map(str::to_string),
+        }
+    }
+
+    fn fx_site() -> FeedSite {
+        FeedSite {
+            origin: "https://jonesplumbing.co.uk".to_string(),
+            description: "Plumbing & heating in Leeds".to_string(),
+        }
+    }
+
+    fn fx_posts() -> Vec<BlogPost> {
+        vec![
+            fx_post(
+                "boilers",
+                "2024-12-15T09:00:00Z",
+                Some("2024-12-01T09:00:00Z"),
+            ),
+            fx_post(
+                "radiators",
+                "2024-12-10T09:00:00Z",
+                Some("2024-12-20T12:30:00Z"),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_rss_xml() {
+        let feed = BlogFeed::for_category("local-seo").unwrap();
+        let content = rss_xml(&fx_site(), feed, &fx_posts());
+
+        assert!(content.contains("<title>XF Tradesmen Blog: Local SEO</title>"));
+        assert!(content.contains(
+            r#"<atom:link href="https://jonesplumbing.co.uk/blog/category/local-seo/rss.xml" rel="self""#
+        ));
+        assert!(content.contains("<link>https://jonesplumbing.co.uk/blog</link>"));
+        assert!(content.contains("<description>Plumbing &amp; heating in Leeds</description>"));
+        assert!(content.contains("<link>https://jonesplumbing.co.uk/blog/boilers</link>"));
+        assert!(content.contains(
+            r#"<guid isPermaLink="true">https://jonesplumbing.co.uk/blog/boilers</guid>"#
+        ));
+        assert!(content.contains("<title>Boilers &amp; Radiators</title>"));
+        assert!(content.contains("<description>Five &lt;quick&gt; checks</description>"));
+        assert!(content.contains("<category>Local SEO</category>"));
+        assert!(content.contains("<pubDate>Sun, 15 Dec 2024 09:00:00 +0000</pubDate>"));
+        assert!(content.contains("<lastBuildDate>Fri, 20 Dec 2024 12:30:00 +0000</lastBuildDate>"));
+    }
+
+    #[test]
+    fn test_atom_xml() {
+        let content = atom_xml(&fx_site(), BlogFeed::ALL, &fx_posts());
+
+        assert!(content.contains("<id>https://jonesplumbing.co.uk/blog/atom.xml</id>"));
+        assert!(content.contains("<subtitle>Plumbing &amp; heating in Leeds</subtitle>"));
+        assert!(content.contains("<id>https://jonesplumbing.co.uk/blog/radiators</id>"));
+        assert!(content.contains("<updated>2024-12-20T12:30:00+00:00</updated>"));
+        assert!(content.contains("<published>2024-12-15T09:00:00+00:00</published>"));
+        // Edited before it went live, so updated when it went live
+        assert!(content.contains("<updated>2024-12-15T09:00:00+00:00</updated>"));
+        assert!(content.contains(r#"<category term="local-seo" label="Local SEO"/>"#));
+        assert!(content.contains("<author><name>XF Tradesmen</name></author>"));
+    }
+
+    #[test]
+    fn test_feed_site_for_tenant() {
+        let mut tenant: Tenant = serde_json::from_value(serde_json::json!({
+            "id": 2,
+            "slug": "jones",
+            "business_name": "Jones Plumbing",
+            "domains": ["jonesplumbing.co.uk"],
+            "tagline": " Plumbing in Leeds ",
+            "opening_hours": [],
+            "accreditations": [],
+            "testimonials": [],
+            "noindex": false,
+            "active": true
+        }))
+        .unwrap();
+
+        let site = FeedSite::for_tenant(&tenant);
+        assert_eq!(site.blog_url(), "https://jonesplumbing.co.uk/blog");
+        assert_eq!(site.description, "Plumbing in Leeds");
+
+        tenant.tagline = None;
+        assert_eq!(
+            FeedSite::for_tenant(&tenant).description,
+            "The latest from Jones Plumbing."
+        );
+
+        tenant.id = DEFAULT_TENANT_ID;
+        assert_eq!(FeedSite::for_tenant(&tenant).description, FEED_DESCRIPTION);
+    }
+
+    #[test]
+    fn test_is_not_modified() {
+        let updated = parse_date("2024-12-20T12:30:00Z");
+        let mut headers = HeaderMap::new();
+
+        assert!(!is_not_modified(&headers, updated));
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_str(&http_date(updated.unwrap())).unwrap(),
+        );
+        assert_eq!(
+            headers[header::IF_MODIFIED_SINCE],
+            "Fri, 20 Dec 2024 12:30:00 GMT"
+        );
+        assert!(is_not_modified(&headers, updated));
+        assert!(!is_not_modified(
+            &headers,
+            parse_date("2024-12-20T12:30:01Z")
+        ));
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,75 @@
+//! Admin GDPR handlers.
+//!
+//! Subject access exports and right-to-erasure requests.
+
+use axum::extract::{Extension, Json, Query};
+use axum::http::header;
+use axum::response::IntoResponse;
+use lib_core::model::gdpr::{ErasureReport, GdprBmc};
+use lib_core::Ctx;
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use shared::ApiResponse;
+use tracing::info;
+
+/// Identifies the data subject of a GDPR request.
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+pub struct SubjectRequest {
+    /// Data subject's email address
+    pub email: String,
+}
+
+/// Exports everything held about an email address as a JSON download.
+#[utoipa::path(
+    get,
+    path = "/api/admin/gdpr/export",
+    tag = "admin",
+    params(SubjectRequest),
+    responses(
+        (status = 200, description = "Subject access export", body = lib_core::model::gdpr::SubjectAccessExport),
+        (status = 400, description = "Invalid email", body = serde_json::Value),
+        (status = 401, description = "Admin token missing or invalid", body = serde_json::Value)
+    )
+)]
+pub async fn gdpr_export_handler(
+    TenantMm(mm): TenantMm,
+    Extension(ctx): Extension<Ctx>,
+    Query(req): Query<SubjectRequest>,
+) -> Result<impl IntoResponse, Error> {
+    let export = GdprBmc::export(&mm, &req.email, ctx.username()).await?;
+    let filename = format!(
+        "subject-access-{}.json",
+        export.generated_at.unix_timestamp()
+    );
+
+    info!("GDPR export generated");
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{filename}\""),
+        )],
+        Json(export),
+    ))
+}
+
+/// Anonymises everything held about an email address.
+#[utoipa::path(
+    post,
+    path = "/api/admin/gdpr/erase",
+    tag = "admin",
+    request_body = SubjectRequest,
+    responses(
+        (status = 200, description = "Rows anonymised", body = serde_json::Value),
+        (status = 400, description = "Invalid email", body = serde_json::Value),
+        (status = 401, description = "Admin token missing or invalid", body = serde_json::Value)
+    )
+)]
+pub async fn gdpr_erase_handler(
+    TenantMm(mm): TenantMm,
+    Extension(ctx): Extension<Ctx>,
+    Json(req): Json<SubjectRequest>,
+) -> Result<Json<ApiResponse<ErasureReport>>, Error> {
+    let report = GdprBmc::erase(&mm, &req.email, ctx.username()).await?;
+
+    Ok(Json(ApiResponse::success("Personal data erased", report)))
+}
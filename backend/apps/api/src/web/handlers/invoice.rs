@@ -0,0 +1,196 @@
+//! Admin invoice handlers.
+//!
+//! Generating invoices from completed bookings, issuing them, recording
+//! payments and raising credit notes.
+
+use axum::extract::{Json, Path};
+use lib_core::model::invoice::{Invoice, InvoiceBmc, InvoicePaymentForCreate, InvoiceSettings};
+use lib_core::model::quote::QuoteItem;
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::ApiResponse;
+use tracing::info;
+
+/// Request body for generating an invoice from a booking.
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct GenerateInvoiceRequest {
+    /// Line items, required when the booking has no accepted quote
+    pub items: Option<Vec<QuoteItem>>,
+}
+
+/// Request body for raising a credit note.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreditNoteRequest {
+    /// Gross amount to credit in cents, at most the outstanding balance
+    pub amount_cents: i32,
+    /// Reason printed on the credit note
+    pub reason: String,
+}
+
+/// Generates a draft invoice for a completed booking.
+#[utoipa::path(
+    post,
+    path = "/api/admin/bookings/{id}/invoice",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    request_body = GenerateInvoiceRequest,
+    responses(
+        (status = 200, description = "Draft invoice created", body = Invoice),
+        (status = 400, description = "Booking not completed, already invoiced or no line items", body = Value),
+        (status = 404, description = "Booking not found", body = Value)
+    )
+)]
+pub async fn generate_invoice_handler(
+    TenantMm(mm): TenantMm,
+    Path(booking_id): Path<i32>,
+    body: Option<Json<GenerateInvoiceRequest>>,
+) -> Result<Json<ApiResponse<Invoice>>, Error> {
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+    let settings = InvoiceSettings::from_config();
+    let id = InvoiceBmc::generate_from_booking(&mm, booking_id, req.items, &settings).await?;
+    let invoice = InvoiceBmc::get(&mm, id).await?;
+
+    info!("Draft invoice {} generated for booking {}", id, booking_id);
+    Ok(Json(ApiResponse::success("Draft invoice created", invoice)))
+}
+
+/// Lists all invoices and credit notes.
+#[utoipa::path(
+    get,
+    path = "/api/admin/invoices",
+    tag = "admin",
+    responses((status = 200, description = "Invoices, newest first", body = [Invoice]))
+)]
+pub async fn list_invoices_handler(
+    TenantMm(mm): TenantMm,
+) -> Result<Json<ApiResponse<Vec<Invoice>>>, Error> {
+    let invoices = InvoiceBmc::list(&mm).await?;
+
+    Ok(Json(ApiResponse::success("Invoices retrieved", invoices)))
+}
+
+/// Gets an invoice or credit note.
+#[utoipa::path(
+    get,
+    path = "/api/admin/invoices/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Invoice ID")),
+    responses(
+        (status = 200, description = "Invoice", body = Invoice),
+        (status = 404, description = "Invoice not found", body = Value)
+    )
+)]
+pub async fn get_invoice_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Invoice>>, Error> {
+    let invoice = InvoiceBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success("Invoice retrieved", invoice)))
+}
+
+/// Issues a draft invoice, assigning its number and due date.
+#[utoipa::path(
+    post,
+    path = "/api/admin/invoices/{id}/issue",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Invoice ID")),
+    responses(
+        (status = 200, description = "Invoice issued", body = Invoice),
+        (status = 400, description = "Invoice is not a draft", body = Value),
+        (status = 404, description = "Invoice not found", body = Value)
+    )
+)]
+pub async fn issue_invoice_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Invoice>>, Error> {
+    let number = InvoiceBmc::issue(&mm, id, &InvoiceSettings::from_config()).await?;
+    let invoice = InvoiceBmc::get(&mm, id).await?;
+
+    info!("Invoice {} issued as {}", id, number);
+    Ok(Json(ApiResponse::success("Invoice issued", invoice)))
+}
+
+/// Records a full or partial payment against an invoice.
+#[utoipa::path(
+    post,
+    path = "/api/admin/invoices/{id}/payments",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Invoice ID")),
+    request_body = InvoicePaymentForCreate,
+    responses(
+        (status = 200, description = "Payment recorded", body = Invoice),
+        (status = 400, description = "Invoice not awaiting payment or invalid amount", body = Value),
+        (status = 404, description = "Invoice not found", body = Value)
+    )
+)]
+pub async fn record_invoice_payment_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(payment): Json<InvoicePaymentForCreate>,
+) -> Result<Json<ApiResponse<Invoice>>, Error> {
+    let payment_id = InvoiceBmc::record_payment(&mm, id, payment).await?;
+    let invoice = InvoiceBmc::get(&mm, id).await?;
+
+    info!("Payment {} recorded against invoice {}", payment_id, id);
+    Ok(Json(ApiResponse::success("Payment recorded", invoice)))
+}
+
+/// Raises a credit note against an issued invoice.
+#[utoipa::path(
+    post,
+    path = "/api/admin/invoices/{id}/credit-notes",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Invoice ID")),
+    request_body = CreditNoteRequest,
+    responses(
+        (status = 200, description = "Credit note issued", body = Invoice),
+        (status = 400, description = "Invoice not issued or amount over the outstanding balance", body = Value),
+        (status = 404, description = "Invoice not found", body = Value)
+    )
+)]
+pub async fn create_credit_note_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<CreditNoteRequest>,
+) -> Result<Json<ApiResponse<Invoice>>, Error> {
+    let credit_note_id =
+        InvoiceBmc::create_credit_note(&mm, id, req.amount_cents, &req.reason).await?;
+    let credit_note = InvoiceBmc::get(&mm, credit_note_id).await?;
+
+    info!(
+        "Credit note {} raised against invoice {}",
+        credit_note_id, id
+    );
+    Ok(Json(ApiResponse::success(
+        "Credit note issued",
+        credit_note,
+    )))
+}
+
+/// Voids an invoice with nothing paid or credited against it.
+#[utoipa::path(
+    post,
+    path = "/api/admin/invoices/{id}/void",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Invoice ID")),
+    responses(
+        (status = 200, description = "Invoice voided", body = Value),
+        (status = 400, description = "Invoice has payments or credits", body = Value),
+        (status = 404, description = "Invoice not found", body = Value)
+    )
+)]
+pub async fn void_invoice_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    InvoiceBmc::void(&mm, id).await?;
+
+    info!("Invoice {} voided", id);
+    Ok(Json(ApiResponse::success(
+        "Invoice voided",
+        json!({ "id": id }),
+    )))
+}
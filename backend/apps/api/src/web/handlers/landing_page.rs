@@ -0,0 +1,418 @@
+//! Area and service landing page handlers.
+//!
+//! Public endpoints assemble each landing page from the database for the
+//! Leptos site; admin endpoints manage the areas, services and the
+//! service-in-town introductions that publish `/services/{service}/{town}`
+//! pages.
+
+use axum::extract::{Json, Path, Query};
+use lib_core::model::area::{Area, AreaBmc, AreaForCreate, AreaForUpdate};
+use lib_core::model::landing_page::{LandingPageBmc, ServiceAreaIntro};
+use lib_core::model::service::{Service, ServiceBmc, ServiceForCreate, ServiceForUpdate};
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::{ApiResponse, AreaPage, PageLink, ServiceAreaPage, SitemapUrl};
+use tracing::info;
+
+/// Query parameters for the admin area and service lists.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct LandingListParams {
+    /// Include unpublished entries (default false)
+    pub include_inactive: Option<bool>,
+}
+
+/// Body for `PUT /api/admin/services/{id}/areas/{area_id}`.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct IntroRequest {
+    /// Copy written for this service in this town
+    pub intro: String,
+}
+
+// region:    --- Public Pages
+
+/// Lists the published areas, for navigation and the service area page.
+#[utoipa::path(
+    get,
+    path = "/api/areas",
+    tag = "pages",
+    responses((status = 200, description = "Areas, in display order", body = [PageLink]))
+)]
+pub async fn list_areas_handler(
+    TenantMm(mm): TenantMm,
+) -> Result<Json<ApiResponse<Vec<PageLink>>>, Error> {
+    let areas = AreaBmc::list(&mm, true)
+        .await?
+        .into_iter()
+        .map(|a| PageLink {
+            slug: a.slug,
+            name: a.name,
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success("Areas retrieved", areas)))
+}
+
+/// The landing page for an area.
+#[utoipa::path(
+    get,
+    path = "/api/areas/{slug}",
+    tag = "pages",
+    params(("slug" = String, Path, description = "Area slug, e.g. coventry")),
+    responses(
+        (status = 200, description = "Area page", body = AreaPage),
+        (status = 404, description = "No published area with that slug", body = Value)
+    )
+)]
+pub async fn area_page_handler(
+    TenantMm(mm): TenantMm,
+    Path(slug): Path<String>,
+) -> Result<Json<ApiResponse<AreaPage>>, Error> {
+    let page = LandingPageBmc::area_page(&mm, &slug).await?;
+
+    Ok(Json(ApiResponse::success("Area page retrieved", page)))
+}
+
+/// The landing page for a service in a town.
+#[utoipa::path(
+    get,
+    path = "/api/services/{service}/{town}",
+    tag = "pages",
+    params(
+        ("service" = String, Path, description = "Service slug, e.g. plumbing"),
+        ("town" = String, Path, description = "Area slug, e.g. coventry")
+    ),
+    responses(
+        (status = 200, description = "Service-in-town page", body = ServiceAreaPage),
+        (status = 404, description = "Service, area or page not published", body = Value)
+    )
+)]
+pub async fn service_area_page_handler(
+    TenantMm(mm): TenantMm,
+    Path((service, town)): Path<(String, String)>,
+) -> Result<Json<ApiResponse<ServiceAreaPage>>, Error> {
+    let page = LandingPageBmc::service_area_page(&mm, &service, &town).await?;
+
+    Ok(Json(ApiResponse::success("Service page retrieved", page)))
+}
+
+/// Every published landing page, relative to the handyman site's root, for
+/// the frontend's sitemap.
+#[utoipa::path(
+    get,
+    path = "/api/landing-pages",
+    tag = "pages",
+    responses((status = 200, description = "Area pages, then service pages", body = [SitemapUrl]))
+)]
+pub async fn landing_paths_handler(
+    TenantMm(mm): TenantMm,
+) -> Result<Json<ApiResponse<Vec<SitemapUrl>>>, Error> {
+    let urls = LandingPageBmc::sitemap_urls(&mm).await?;
+
+    Ok(Json(ApiResponse::success("Landing pages retrieved", urls)))
+}
+
+// endregion: --- Public Pages
+
+// region:    --- Admin Areas
+
+/// Adds an area.
+#[utoipa::path(
+    post,
+    path = "/api/admin/areas",
+    tag = "admin",
+    request_body = AreaForCreate,
+    responses(
+        (status = 200, description = "Area created", body = Area),
+        (status = 400, description = "Invalid, or duplicates another area", body = Value)
+    )
+)]
+pub async fn create_area_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<AreaForCreate>,
+) -> Result<Json<ApiResponse<Area>>, Error> {
+    let id = AreaBmc::create(&mm, req).await?;
+    let area = AreaBmc::get(&mm, id).await?;
+
+    info!("Area {} created", id);
+    Ok(Json(ApiResponse::success("Area created", area)))
+}
+
+/// Lists areas.
+#[utoipa::path(
+    get,
+    path = "/api/admin/areas",
+    tag = "admin",
+    params(LandingListParams),
+    responses((status = 200, description = "Areas, in display order", body = [Area]))
+)]
+pub async fn list_admin_areas_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<LandingListParams>,
+) -> Result<Json<ApiResponse<Vec<Area>>>, Error> {
+    let areas = AreaBmc::list(&mm, !params.include_inactive.unwrap_or(false)).await?;
+
+    Ok(Json(ApiResponse::success("Areas retrieved", areas)))
+}
+
+/// Gets an area.
+#[utoipa::path(
+    get,
+    path = "/api/admin/areas/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Area ID")),
+    responses(
+        (status = 200, description = "Area", body = Area),
+        (status = 404, description = "Area not found", body = Value)
+    )
+)]
+pub async fn get_area_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Area>>, Error> {
+    let area = AreaBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success("Area retrieved", area)))
+}
+
+/// Updates an area.
+#[utoipa::path(
+    patch,
+    path = "/api/admin/areas/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Area ID")),
+    request_body = AreaForUpdate,
+    responses(
+        (status = 200, description = "Area updated", body = Area),
+        (status = 400, description = "Invalid, or duplicates another area", body = Value),
+        (status = 404, description = "Area not found", body = Value)
+    )
+)]
+pub async fn update_area_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<AreaForUpdate>,
+) -> Result<Json<ApiResponse<Area>>, Error> {
+    AreaBmc::update(&mm, id, req).await?;
+    let area = AreaBmc::get(&mm, id).await?;
+
+    info!("Area {} updated", id);
+    Ok(Json(ApiResponse::success("Area updated", area)))
+}
+
+/// Deletes an area and its service pages.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/areas/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Area ID")),
+    responses(
+        (status = 200, description = "Area deleted", body = Value),
+        (status = 404, description = "Area not found", body = Value)
+    )
+)]
+pub async fn delete_area_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    AreaBmc::delete(&mm, id).await?;
+
+    info!("Area {} deleted", id);
+    Ok(Json(ApiResponse::success(
+        "Area deleted",
+        json!({ "id": id }),
+    )))
+}
+
+// endregion: --- Admin Areas
+
+// region:    --- Admin Services
+
+/// Adds a service.
+#[utoipa::path(
+    post,
+    path = "/api/admin/services",
+    tag = "admin",
+    request_body = ServiceForCreate,
+    responses(
+        (status = 200, description = "Service created", body = Service),
+        (status = 400, description = "Invalid, or duplicates another service", body = Value)
+    )
+)]
+pub async fn create_service_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<ServiceForCreate>,
+) -> Result<Json<ApiResponse<Service>>, Error> {
+    let id = ServiceBmc::create(&mm, req).await?;
+    let service = ServiceBmc::get(&mm, id).await?;
+
+    info!("Service {} created", id);
+    Ok(Json(ApiResponse::success("Service created", service)))
+}
+
+/// Lists services.
+#[utoipa::path(
+    get,
+    path = "/api/admin/services",
+    tag = "admin",
+    params(LandingListParams),
+    responses((status = 200, description = "Services, in display order", body = [Service]))
+)]
+pub async fn list_admin_services_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<LandingListParams>,
+) -> Result<Json<ApiResponse<Vec<Service>>>, Error> {
+    let services = ServiceBmc::list(&mm, !params.include_inactive.unwrap_or(false)).await?;
+
+    Ok(Json(ApiResponse::success("Services retrieved", services)))
+}
+
+/// Gets a service.
+#[utoipa::path(
+    get,
+    path = "/api/admin/services/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Service ID")),
+    responses(
+        (status = 200, description = "Service", body = Service),
+        (status = 404, description = "Service not found", body = Value)
+    )
+)]
+pub async fn get_service_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Service>>, Error> {
+    let service = ServiceBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success("Service retrieved", service)))
+}
+
+/// Updates a service.
+#[utoipa::path(
+    patch,
+    path = "/api/admin/services/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Service ID")),
+    request_body = ServiceForUpdate,
+    responses(
+        (status = 200, description = "Service updated", body = Service),
+        (status = 400, description = "Invalid, or duplicates another service", body = Value),
+        (status = 404, description = "Service not found", body = Value)
+    )
+)]
+pub async fn update_service_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<ServiceForUpdate>,
+) -> Result<Json<ApiResponse<Service>>, Error> {
+    ServiceBmc::update(&mm, id, req).await?;
+    let service = ServiceBmc::get(&mm, id).await?;
+
+    info!("Service {} updated", id);
+    Ok(Json(ApiResponse::success("Service updated", service)))
+}
+
+/// Deletes a service and its area pages.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/services/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Service ID")),
+    responses(
+        (status = 200, description = "Service deleted", body = Value),
+        (status = 404, description = "Service not found", body = Value)
+    )
+)]
+pub async fn delete_service_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    ServiceBmc::delete(&mm, id).await?;
+
+    info!("Service {} deleted", id);
+    Ok(Json(ApiResponse::success(
+        "Service deleted",
+        json!({ "id": id }),
+    )))
+}
+
+// endregion: --- Admin Services
+
+// region:    --- Admin Introductions
+
+/// Lists every service-in-town introduction.
+#[utoipa::path(
+    get,
+    path = "/api/admin/landing-intros",
+    tag = "admin",
+    responses((status = 200, description = "Introductions", body = [ServiceAreaIntro]))
+)]
+pub async fn list_intros_handler(
+    TenantMm(mm): TenantMm,
+) -> Result<Json<ApiResponse<Vec<ServiceAreaIntro>>>, Error> {
+    let intros = LandingPageBmc::list(&mm).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Introductions retrieved",
+        intros,
+    )))
+}
+
+/// Writes the introduction for a service in an area, publishing its page.
+#[utoipa::path(
+    put,
+    path = "/api/admin/services/{id}/areas/{area_id}",
+    tag = "admin",
+    params(
+        ("id" = i32, Path, description = "Service ID"),
+        ("area_id" = i32, Path, description = "Area ID")
+    ),
+    request_body = IntroRequest,
+    responses(
+        (status = 200, description = "Page published", body = Value),
+        (status = 400, description = "Too short, or too like other copy", body = Value),
+        (status = 404, description = "Service or area not found", body = Value)
+    )
+)]
+pub async fn set_intro_handler(
+    TenantMm(mm): TenantMm,
+    Path((service_id, area_id)): Path<(i32, i32)>,
+    Json(req): Json<IntroRequest>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    LandingPageBmc::set_intro(&mm, service_id, area_id, &req.intro).await?;
+
+    info!("Service {} page for area {} published", service_id, area_id);
+    Ok(Json(ApiResponse::success(
+        "Page published",
+        json!({ "service_id": service_id, "area_id": area_id }),
+    )))
+}
+
+/// Removes the introduction for a service in an area, taking its page down.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/services/{id}/areas/{area_id}",
+    tag = "admin",
+    params(
+        ("id" = i32, Path, description = "Service ID"),
+        ("area_id" = i32, Path, description = "Area ID")
+    ),
+    responses(
+        (status = 200, description = "Page removed", body = Value),
+        (status = 404, description = "No introduction for the pair", body = Value)
+    )
+)]
+pub async fn remove_intro_handler(
+    TenantMm(mm): TenantMm,
+    Path((service_id, area_id)): Path<(i32, i32)>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    LandingPageBmc::remove_intro(&mm, service_id, area_id).await?;
+
+    info!("Service {} page for area {} removed", service_id, area_id);
+    Ok(Json(ApiResponse::success(
+        "Page removed",
+        json!({ "service_id": service_id, "area_id": area_id }),
+    )))
+}
+
+// endregion: --- Admin Introductions
@@ -0,0 +1,180 @@
+//! Admin material handlers.
+//!
+//! The materials catalogue, and recording the materials used on a
+//! booking so they can be invoiced and costed.
+
+use axum::extract::{Json, Path, Query};
+use lib_core::model::material::{
+    BookingMaterial, BookingMaterialForCreate, Material, MaterialBmc, MaterialForCreate,
+    MaterialForUpdate,
+};
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::ApiResponse;
+use tracing::info;
+
+/// Query parameters for `/api/admin/materials`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct MaterialListParams {
+    /// Include retired entries (default false)
+    pub include_inactive: Option<bool>,
+}
+
+/// Adds a catalogue entry.
+#[utoipa::path(
+    post,
+    path = "/api/admin/materials",
+    tag = "admin",
+    request_body = MaterialForCreate,
+    responses(
+        (status = 200, description = "Material created", body = Material),
+        (status = 400, description = "Blank name or invalid prices", body = Value)
+    )
+)]
+pub async fn create_material_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<MaterialForCreate>,
+) -> Result<Json<ApiResponse<Material>>, Error> {
+    let id = MaterialBmc::create(&mm, req).await?;
+    let material = MaterialBmc::get(&mm, id).await?;
+
+    info!("Material {} created", id);
+    Ok(Json(ApiResponse::success("Material created", material)))
+}
+
+/// Lists the materials catalogue.
+#[utoipa::path(
+    get,
+    path = "/api/admin/materials",
+    tag = "admin",
+    params(MaterialListParams),
+    responses((status = 200, description = "Materials, by name", body = [Material]))
+)]
+pub async fn list_materials_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<MaterialListParams>,
+) -> Result<Json<ApiResponse<Vec<Material>>>, Error> {
+    let materials = MaterialBmc::list(&mm, !params.include_inactive.unwrap_or(false)).await?;
+
+    Ok(Json(ApiResponse::success("Materials retrieved", materials)))
+}
+
+/// Updates a catalogue entry. Usage already recorded keeps its prices.
+#[utoipa::path(
+    patch,
+    path = "/api/admin/materials/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Material ID")),
+    request_body = MaterialForUpdate,
+    responses(
+        (status = 200, description = "Material updated", body = Material),
+        (status = 400, description = "Blank name or invalid prices", body = Value),
+        (status = 404, description = "Material not found", body = Value)
+    )
+)]
+pub async fn update_material_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<MaterialForUpdate>,
+) -> Result<Json<ApiResponse<Material>>, Error> {
+    MaterialBmc::update(&mm, id, req).await?;
+    let material = MaterialBmc::get(&mm, id).await?;
+
+    info!("Material {} updated", id);
+    Ok(Json(ApiResponse::success("Material updated", material)))
+}
+
+/// Deletes a catalogue entry. Recorded usage keeps its own details.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/materials/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Material ID")),
+    responses(
+        (status = 200, description = "Material deleted", body = Value),
+        (status = 404, description = "Material not found", body = Value)
+    )
+)]
+pub async fn delete_material_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    MaterialBmc::delete(&mm, id).await?;
+
+    info!("Material {} deleted", id);
+    Ok(Json(ApiResponse::success(
+        "Material deleted",
+        json!({ "id": id }),
+    )))
+}
+
+/// Records material used on a booking.
+#[utoipa::path(
+    post,
+    path = "/api/admin/bookings/{id}/materials",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    request_body = BookingMaterialForCreate,
+    responses(
+        (status = 200, description = "Usage recorded", body = [BookingMaterial]),
+        (status = 400, description = "Invalid usage, or booking already invoiced", body = Value),
+        (status = 404, description = "Booking or material not found", body = Value)
+    )
+)]
+pub async fn add_booking_material_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<BookingMaterialForCreate>,
+) -> Result<Json<ApiResponse<Vec<BookingMaterial>>>, Error> {
+    let line_id = MaterialBmc::add_to_booking(&mm, id, req).await?;
+    let lines = MaterialBmc::list_for_booking(&mm, id).await?;
+
+    info!("Material line {} recorded on booking {}", line_id, id);
+    Ok(Json(ApiResponse::success("Usage recorded", lines)))
+}
+
+/// Lists the materials used on a booking.
+#[utoipa::path(
+    get,
+    path = "/api/admin/bookings/{id}/materials",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    responses((status = 200, description = "Usage, oldest first", body = [BookingMaterial]))
+)]
+pub async fn list_booking_materials_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<BookingMaterial>>>, Error> {
+    let lines = MaterialBmc::list_for_booking(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success("Usage retrieved", lines)))
+}
+
+/// Removes a material line from a booking.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/bookings/{id}/materials/{line_id}",
+    tag = "admin",
+    params(
+        ("id" = i32, Path, description = "Booking ID"),
+        ("line_id" = i32, Path, description = "Usage line ID")
+    ),
+    responses(
+        (status = 200, description = "Usage removed", body = Value),
+        (status = 400, description = "Booking already invoiced", body = Value),
+        (status = 404, description = "Line not found on the booking", body = Value)
+    )
+)]
+pub async fn remove_booking_material_handler(
+    TenantMm(mm): TenantMm,
+    Path((id, line_id)): Path<(i32, i32)>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    MaterialBmc::remove_from_booking(&mm, id, line_id).await?;
+
+    info!("Material line {} removed from booking {}", line_id, id);
+    Ok(Json(ApiResponse::success(
+        "Usage removed",
+        json!({ "id": line_id }),
+    )))
+}
@@ -1,12 +1,56 @@
 //! API request handlers.
 //!
 //! This module contains all HTTP request handlers organized by domain:
+//! - `attachment`: Booking and quote photo/document uploads and signed downloads
+//! - `blog`: Published blog posts, and drafting and scheduling them
+//! - `booking`: Admin booking view and rescheduling
 //! - `contact`: Contact form submissions
 //! - `static_content`: Health checks, version info, config
-//! - `seo`: Robots.txt and sitemap generation
+//! - `seo`: The API host's robots.txt
+//! - `coverage`: Service coverage by postcode
+//! - `customer`: Admin customer timeline
+//! - `document`: Quote and invoice PDFs, signed links and emails
+//! - `feed`: RSS and Atom feeds of the blog and its categories
+//! - `gdpr`: Subject access exports and erasure
+//! - `invoice`: Invoices, payments and credit notes
+//! - `landing_page`: Data-driven area and service-in-town landing pages
+//! - `material`: Materials catalogue and per-booking material usage
+//! - `onboarding`: New client onboarding wizard submissions, review and publishing
+//! - `pricing_rule`: Instant quote pricing rule administration
+//! - `promo_code`: Promo code administration
 //! - `quote`: Quote management and instant quotes
+//! - `quote_revision`: Admin quote edits, revision history and acceptance records
+//! - `quote_template`: Quote template administration
+//! - `report`: Per-job and per-service margin reports
+//! - `route_plan`: Daily technician routes as GeoJSON
+//! - `search`: Admin search across customers, bookings and quotes
+//! - `technician`: Technicians, job assignment and suggested assignees
+//! - `tenant`: Site configuration for the request's host and tenant administration
+//! - `time_entry`: Job time tracking and technician timesheets
 
+pub mod attachment;
+pub mod blog;
+pub mod booking;
 pub mod contact;
+pub mod coverage;
+pub mod customer;
+pub mod document;
+pub mod feed;
+pub mod gdpr;
+pub mod invoice;
+pub mod landing_page;
+pub mod material;
+pub mod onboarding;
+pub mod pricing_rule;
+pub mod promo_code;
 pub mod quote;
+pub mod quote_revision;
+pub mod quote_template;
+pub mod report;
+pub mod route_plan;
+pub mod search;
 pub mod seo;
 pub mod static_content;
+pub mod technician;
+pub mod tenant;
+pub mod time_entry;
@@ -0,0 +1,203 @@
+//! Client onboarding handlers.
+//!
+//! New clients submit their business details from the onboarding wizard
+//! (public endpoint); admins review the drafts, correct them and publish
+//! them as live sites. Onboarding is part of the platform's own site, so
+//! these endpoints are only served on the default tenant's hosts.
+
+use crate::web::handlers::tenant::require_default_tenant;
+use axum::extract::{Json, Path, Query};
+use lib_core::email::email_service;
+use lib_core::model::onboarding::{OnboardingBmc, OnboardingDraft, OnboardingForPublish};
+use lib_core::model::tenant::{Tenant, TenantBmc};
+use lib_web::{Error, TenantMm, ValidatedJson};
+use serde::Deserialize;
+use shared::{ApiResponse, OnboardingForm, OnboardingReceipt};
+use tracing::{error, info};
+
+/// Query parameters for listing onboarding drafts.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct OnboardingListParams {
+    /// Only drafts with this status: `submitted` or `published`
+    pub status: Option<String>,
+}
+
+// region:    --- Public Wizard
+
+/// Submits a new client's details for review.
+#[utoipa::path(
+    post,
+    path = "/api/onboarding",
+    tag = "onboarding",
+    request_body = OnboardingForm,
+    responses(
+        (status = 200, description = "Details received", body = OnboardingReceipt),
+        (status = 400, description = "Invalid details", body = serde_json::Value),
+        (status = 403, description = "Not the platform's own site", body = serde_json::Value)
+    )
+)]
+pub async fn submit_onboarding_handler(
+    TenantMm(mm): TenantMm,
+    ValidatedJson(form): ValidatedJson<OnboardingForm>,
+) -> Result<Json<ApiResponse<OnboardingReceipt>>, Error> {
+    require_default_tenant(&mm)?;
+    let id = OnboardingBmc::create(&mm, form.clone()).await?;
+
+    tokio::spawn(async move {
+        if let Err(e) = send_onboarding_notification(id, &form).await {
+            error!("Failed to send onboarding notification email: {}", e);
+        }
+    });
+
+    info!("Onboarding draft {} submitted", id);
+    Ok(Json(ApiResponse::success(
+        "Thanks! We'll review your details and be in touch",
+        OnboardingReceipt { id },
+    )))
+}
+
+/// Tells the admin a draft is waiting for review.
+async fn send_onboarding_notification(
+    id: i32,
+    form: &OnboardingForm,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let email_service = match email_service().as_ref() {
+        Ok(service) => service,
+        Err(e) => {
+            error!("Email service not configured: {}", e);
+            return Ok(());
+        }
+    };
+
+    let message = format!(
+        "Onboarding draft {} is ready for review: {}, {} in {}, with {} services.",
+        id,
+        form.business_name,
+        form.trade,
+        form.town,
+        form.services.len()
+    );
+    email_service
+        .send_contact_notification(
+            &form.contact_name,
+            &form.email,
+            Some(&format!("Onboarding: {}", form.business_name)),
+            &message,
+        )
+        .await?;
+
+    Ok(())
+}
+
+// endregion: --- Public Wizard
+
+// region:    --- Admin Review
+
+/// Lists onboarding drafts, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/admin/onboarding",
+    tag = "admin",
+    params(OnboardingListParams),
+    responses(
+        (status = 200, description = "Onboarding drafts", body = [OnboardingDraft]),
+        (status = 400, description = "Unknown status", body = serde_json::Value),
+        (status = 403, description = "Not the platform's own site", body = serde_json::Value)
+    )
+)]
+pub async fn list_onboarding_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<OnboardingListParams>,
+) -> Result<Json<ApiResponse<Vec<OnboardingDraft>>>, Error> {
+    require_default_tenant(&mm)?;
+    let drafts = OnboardingBmc::list(&mm, params.status.as_deref()).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Onboarding drafts retrieved",
+        drafts,
+    )))
+}
+
+/// Gets an onboarding draft.
+#[utoipa::path(
+    get,
+    path = "/api/admin/onboarding/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Draft ID")),
+    responses(
+        (status = 200, description = "Onboarding draft", body = OnboardingDraft),
+        (status = 403, description = "Not the platform's own site", body = serde_json::Value),
+        (status = 404, description = "Draft not found", body = serde_json::Value)
+    )
+)]
+pub async fn get_onboarding_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<OnboardingDraft>>, Error> {
+    require_default_tenant(&mm)?;
+    let draft = OnboardingBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Onboarding draft retrieved",
+        draft,
+    )))
+}
+
+/// Replaces a draft's details with the admin's corrections.
+#[utoipa::path(
+    put,
+    path = "/api/admin/onboarding/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Draft ID")),
+    request_body = OnboardingForm,
+    responses(
+        (status = 200, description = "Draft updated", body = OnboardingDraft),
+        (status = 400, description = "Invalid details, or already published", body = serde_json::Value),
+        (status = 403, description = "Not the platform's own site", body = serde_json::Value),
+        (status = 404, description = "Draft not found", body = serde_json::Value)
+    )
+)]
+pub async fn update_onboarding_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    ValidatedJson(form): ValidatedJson<OnboardingForm>,
+) -> Result<Json<ApiResponse<OnboardingDraft>>, Error> {
+    require_default_tenant(&mm)?;
+    OnboardingBmc::update(&mm, id, form).await?;
+    let draft = OnboardingBmc::get(&mm, id).await?;
+
+    info!("Onboarding draft {} updated", id);
+    Ok(Json(ApiResponse::success(
+        "Onboarding draft updated",
+        draft,
+    )))
+}
+
+/// Publishes a draft as a live site, creating the client's tenant.
+#[utoipa::path(
+    post,
+    path = "/api/admin/onboarding/{id}/publish",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Draft ID")),
+    request_body = OnboardingForPublish,
+    responses(
+        (status = 200, description = "Site published", body = Tenant),
+        (status = 400, description = "Already published, or the site can't be set up", body = serde_json::Value),
+        (status = 403, description = "Not the platform's own site", body = serde_json::Value),
+        (status = 404, description = "Draft not found", body = serde_json::Value)
+    )
+)]
+pub async fn publish_onboarding_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<OnboardingForPublish>,
+) -> Result<Json<ApiResponse<Tenant>>, Error> {
+    require_default_tenant(&mm)?;
+    let tenant_id = OnboardingBmc::publish(&mm, id, req).await?;
+    let tenant = TenantBmc::get(&mm, tenant_id).await?;
+
+    info!("Onboarding draft {} published as tenant {}", id, tenant_id);
+    Ok(Json(ApiResponse::success("Site published", tenant)))
+}
+
+// endregion: --- Admin Review
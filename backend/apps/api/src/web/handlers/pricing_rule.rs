@@ -0,0 +1,121 @@
+//! Admin pricing rule handlers.
+//!
+//! Editing the service base ranges and pricing rules behind the instant
+//! quote calculator.
+
+use axum::extract::{Json, Path};
+use lib_core::model::pricing_rule::{PricingRuleBmc, PricingRuleForCreate};
+use lib_web::{Error, TenantMm};
+use serde_json::{json, Value};
+use shared::{ApiResponse, PricingCatalog, PricingRule, ServiceRate};
+use tracing::info;
+
+/// Gets every service and rule, including inactive ones.
+#[utoipa::path(
+    get,
+    path = "/api/admin/pricing",
+    tag = "admin",
+    responses((status = 200, description = "Services and rules", body = PricingCatalog))
+)]
+pub async fn get_pricing_catalog_handler(
+    TenantMm(mm): TenantMm,
+) -> Result<Json<ApiResponse<PricingCatalog>>, Error> {
+    let catalog = PricingRuleBmc::catalog(&mm, false).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Pricing catalog retrieved",
+        catalog,
+    )))
+}
+
+/// Creates or replaces a service's base price range.
+#[utoipa::path(
+    put,
+    path = "/api/admin/pricing/services",
+    tag = "admin",
+    request_body = ServiceRate,
+    responses(
+        (status = 200, description = "Service saved", body = ServiceRate),
+        (status = 400, description = "Invalid range", body = Value)
+    )
+)]
+pub async fn save_service_rate_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<ServiceRate>,
+) -> Result<Json<ApiResponse<ServiceRate>>, Error> {
+    PricingRuleBmc::save_service(&mm, req.clone()).await?;
+
+    info!("Service rate {} saved", req.service_type);
+    Ok(Json(ApiResponse::success("Service saved", req)))
+}
+
+/// Creates a pricing rule.
+#[utoipa::path(
+    post,
+    path = "/api/admin/pricing/rules",
+    tag = "admin",
+    request_body = PricingRuleForCreate,
+    responses(
+        (status = 200, description = "Rule created", body = PricingRule),
+        (status = 400, description = "Invalid rule", body = Value)
+    )
+)]
+pub async fn create_pricing_rule_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<PricingRuleForCreate>,
+) -> Result<Json<ApiResponse<PricingRule>>, Error> {
+    let id = PricingRuleBmc::create(&mm, req).await?;
+    let rule = PricingRuleBmc::get(&mm, id).await?;
+
+    info!("Pricing rule {} created", id);
+    Ok(Json(ApiResponse::success("Pricing rule created", rule)))
+}
+
+/// Replaces a pricing rule.
+#[utoipa::path(
+    put,
+    path = "/api/admin/pricing/rules/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Rule ID")),
+    request_body = PricingRuleForCreate,
+    responses(
+        (status = 200, description = "Rule updated", body = PricingRule),
+        (status = 400, description = "Invalid rule", body = Value),
+        (status = 404, description = "Rule not found", body = Value)
+    )
+)]
+pub async fn update_pricing_rule_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<PricingRuleForCreate>,
+) -> Result<Json<ApiResponse<PricingRule>>, Error> {
+    PricingRuleBmc::update(&mm, id, req).await?;
+    let rule = PricingRuleBmc::get(&mm, id).await?;
+
+    info!("Pricing rule {} updated", id);
+    Ok(Json(ApiResponse::success("Pricing rule updated", rule)))
+}
+
+/// Deletes a pricing rule.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/pricing/rules/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Rule ID")),
+    responses(
+        (status = 200, description = "Rule deleted", body = Value),
+        (status = 404, description = "Rule not found", body = Value)
+    )
+)]
+pub async fn delete_pricing_rule_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    PricingRuleBmc::delete(&mm, id).await?;
+
+    info!("Pricing rule {} deleted", id);
+    Ok(Json(ApiResponse::success(
+        "Pricing rule deleted",
+        json!({ "id": id }),
+    )))
+}
@@ -0,0 +1,72 @@
+//! Admin promo code handlers.
+//!
+//! Creating, listing and retiring promo codes customers can apply to
+//! quotes.
+
+use axum::extract::{Json, Path};
+use lib_core::model::promo_code::{PromoCode, PromoCodeBmc, PromoCodeForCreate};
+use lib_web::{Error, TenantMm};
+use serde_json::{json, Value};
+use shared::ApiResponse;
+use tracing::info;
+
+/// Creates a promo code.
+#[utoipa::path(
+    post,
+    path = "/api/admin/promo-codes",
+    tag = "admin",
+    request_body = PromoCodeForCreate,
+    responses(
+        (status = 200, description = "Promo code created", body = PromoCode),
+        (status = 400, description = "Invalid code or discount", body = Value)
+    )
+)]
+pub async fn create_promo_code_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<PromoCodeForCreate>,
+) -> Result<Json<ApiResponse<PromoCode>>, Error> {
+    let id = PromoCodeBmc::create(&mm, req).await?;
+    let promo = PromoCodeBmc::get(&mm, id).await?;
+
+    info!("Promo code {} created", promo.code);
+    Ok(Json(ApiResponse::success("Promo code created", promo)))
+}
+
+/// Lists all promo codes with their usage.
+#[utoipa::path(
+    get,
+    path = "/api/admin/promo-codes",
+    tag = "admin",
+    responses((status = 200, description = "Promo codes, newest first", body = [PromoCode]))
+)]
+pub async fn list_promo_codes_handler(
+    TenantMm(mm): TenantMm,
+) -> Result<Json<ApiResponse<Vec<PromoCode>>>, Error> {
+    let promos = PromoCodeBmc::list(&mm).await?;
+
+    Ok(Json(ApiResponse::success("Promo codes retrieved", promos)))
+}
+
+/// Deactivates a promo code so it can no longer be redeemed.
+#[utoipa::path(
+    post,
+    path = "/api/admin/promo-codes/{id}/deactivate",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Promo code ID")),
+    responses(
+        (status = 200, description = "Promo code deactivated", body = Value),
+        (status = 404, description = "Promo code not found", body = Value)
+    )
+)]
+pub async fn deactivate_promo_code_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    PromoCodeBmc::deactivate(&mm, id).await?;
+
+    info!("Promo code {} deactivated", id);
+    Ok(Json(ApiResponse::success(
+        "Promo code deactivated",
+        json!({ "id": id }),
+    )))
+}
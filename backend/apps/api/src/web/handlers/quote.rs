@@ -2,13 +2,33 @@
 //!
 //! Handles quote creation, retrieval, and management.
 
+use super::document::{email_document, SignedLinkParams};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::Json,
 };
-use lib_core::model::ModelManager;
+use lib_core::document::link::{self, LinkError, LinkedDocument};
+use lib_core::document::{format_timestamp, Branding};
+use lib_core::model::document::DocumentBmc;
+use lib_core::model::postcode_district::PostcodeDistrictBmc;
+use lib_core::model::pricing_rule::PricingRuleBmc;
+use lib_core::model::promo_code::PromoCodeBmc;
+use lib_core::model::quote::{
+    describe_selection, QuoteBmc, QuoteItem as CoreQuoteItem, QuoteOption, QuoteSelection,
+};
+use lib_core::model::quote_acceptance::{
+    QuoteAcceptance, QuoteAcceptanceBmc, QuoteAcceptanceForCreate,
+};
+use lib_core::model::quote_revision::QuoteRevisionBmc;
+use lib_core::model::quote_template::{QuoteTemplate, QuoteTemplateBmc};
+use lib_core::model::{Error as ModelError, ModelManager};
+use lib_core::pricing::{self, VatSettings};
+use lib_web::{client_ip, TenantMm};
 use serde::{Deserialize, Serialize};
+use shared::{InstantQuoteRequest, InstantQuoteResponse, PricingCatalog};
+use std::net::SocketAddr;
+use tracing::error;
 use uuid::Uuid;
 
 /// Line item in a quote
@@ -45,15 +65,6 @@ pub struct QuoteResponse {
     pub accept_url: String,
 }
 
-/// Quote templates response
-#[derive(Debug, Serialize)]
-pub struct QuoteTemplateResponse {
-    pub id: i32,
-    pub name: String,
-    pub service_type: String,
-    pub items: Vec<QuoteItem>,
-}
-
 /// Create a new quote
 pub async fn create_quote(
     State(_mm): State<ModelManager>,
@@ -88,196 +99,362 @@ pub async fn create_quote(
     Ok(Json(response))
 }
 
-/// Get quote by ID
+/// A sent quote as the customer sees it: the latest revision and what
+/// changed since the one before.
+#[derive(Debug, Serialize)]
+pub struct PublicQuoteResponse {
+    pub id: i32,
+    /// Revision shown; send this back when accepting
+    pub revision: i32,
+    pub title: String,
+    /// Items common to every option
+    pub items: Vec<CoreQuoteItem>,
+    /// Options to choose one of, each with its own items and total
+    pub options: Vec<QuoteOption>,
+    /// Optional extras the customer can tick
+    pub add_ons: Vec<CoreQuoteItem>,
+    /// Totals for the first option with no add-ons until accepted, then for
+    /// the selection
+    pub subtotal_cents: i32,
+    pub discount_cents: i32,
+    pub vat_cents: i32,
+    pub total_cents: i32,
+    pub status: String,
+    pub valid_until: Option<String>,
+    pub notes: Option<String>,
+    /// When this revision was made
+    pub revised_at: Option<String>,
+    /// "What changed" lines since the previous revision (empty for the first)
+    pub changes: Vec<String>,
+    /// What the customer chose, once accepted
+    pub selection: Option<QuoteSelection>,
+}
+
+/// Get a sent quote (public endpoint, authorised by a signed link)
+///
+/// Uses the same `expires`/`signature` parameters as the quote's PDF link.
+/// Viewing marks a sent quote as viewed.
 pub async fn get_quote(
-    State(_mm): State<ModelManager>,
-    Path(quote_id): Path<String>,
-) -> Result<Json<QuoteResponse>, (StatusCode, String)> {
-    // TODO: Fetch from database
-    // For now, return a mock response
-    let response = QuoteResponse {
-        id: quote_id.clone(),
-        title: "Sample Quote".to_string(),
-        items: vec![
-            QuoteItem {
-                description: "Call-out fee".to_string(),
-                quantity: 1,
-                unit_price: 3000,
-            },
-            QuoteItem {
-                description: "Labour (1 hour)".to_string(),
-                quantity: 1,
-                unit_price: 4500,
-            },
-        ],
-        subtotal: 7500,
-        total: 7500,
-        status: "sent".to_string(),
-        valid_until: Some("2025-01-22".to_string()),
-        created_at: chrono::Utc::now().to_rfc3339(),
-        accept_url: format!("/quote/{}/accept", quote_id),
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Query(params): Query<SignedLinkParams>,
+) -> Result<Json<PublicQuoteResponse>, (StatusCode, String)> {
+    verify_quote_link(id, &params)?;
+
+    let quote = QuoteBmc::get(&mm, id).await.map_err(error_response)?;
+    if quote.status == "draft" {
+        return Err((StatusCode::NOT_FOUND, "Quote not found".to_string()));
+    }
+    let revision = QuoteRevisionBmc::latest(&mm, id)
+        .await
+        .map_err(error_response)?;
+    QuoteBmc::mark_viewed(&mm, id)
+        .await
+        .map_err(error_response)?;
+
+    // Accepting reprices the quote for the customer's selection
+    let (subtotal_cents, discount_cents, vat_cents, total_cents, selection) = match quote.selection
+    {
+        Some(_) => (
+            quote.subtotal_cents,
+            quote.discount_cents,
+            quote.vat_cents,
+            quote.total_cents,
+            Some(quote.quote_selection().map_err(error_response)?),
+        ),
+        None => (
+            revision.subtotal_cents,
+            revision.discount_cents,
+            revision.vat_cents,
+            revision.total_cents,
+            None,
+        ),
+    };
+
+    let response = PublicQuoteResponse {
+        id,
+        revision: revision.revision,
+        items: revision.quote_items().map_err(error_response)?,
+        options: revision.quote_options().map_err(error_response)?,
+        add_ons: revision.quote_add_ons().map_err(error_response)?,
+        changes: revision.change_summary(),
+        selection,
+        title: revision.title,
+        subtotal_cents,
+        discount_cents,
+        vat_cents,
+        total_cents,
+        status: if quote.status == "sent" {
+            "viewed".to_string()
+        } else {
+            quote.status
+        },
+        valid_until: revision.valid_until.map(|d| d.to_string()),
+        notes: revision.notes,
+        revised_at: revision.created_at.map(|t| t.to_string()),
     };
 
     Ok(Json(response))
 }
 
-/// Get all quote templates
+/// Get all active quote templates
 pub async fn get_quote_templates(
-    State(_mm): State<ModelManager>,
-) -> Result<Json<Vec<QuoteTemplateResponse>>, (StatusCode, String)> {
-    // TODO: Fetch from database
-    // For now, return mock templates
-    let templates = vec![
-        QuoteTemplateResponse {
-            id: 1,
-            name: "Leaky Tap Repair".to_string(),
-            service_type: "plumbing".to_string(),
-            items: vec![
-                QuoteItem {
-                    description: "Call-out fee".to_string(),
-                    quantity: 1,
-                    unit_price: 3000,
-                },
-                QuoteItem {
-                    description: "Labour (1 hour)".to_string(),
-                    quantity: 1,
-                    unit_price: 4500,
-                },
-                QuoteItem {
-                    description: "Materials".to_string(),
-                    quantity: 1,
-                    unit_price: 500,
-                },
-            ],
-        },
-        QuoteTemplateResponse {
-            id: 2,
-            name: "Light Fitting Installation".to_string(),
-            service_type: "electrical".to_string(),
-            items: vec![
-                QuoteItem {
-                    description: "Call-out fee".to_string(),
-                    quantity: 1,
-                    unit_price: 3000,
-                },
-                QuoteItem {
-                    description: "Labour (1 hour)".to_string(),
-                    quantity: 1,
-                    unit_price: 5000,
-                },
-                QuoteItem {
-                    description: "Standard light fitting".to_string(),
-                    quantity: 1,
-                    unit_price: 2500,
-                },
-            ],
-        },
-        QuoteTemplateResponse {
-            id: 3,
-            name: "IKEA Furniture Assembly (Small)".to_string(),
-            service_type: "assembly".to_string(),
-            items: vec![QuoteItem {
-                description: "Assembly service".to_string(),
-                quantity: 1,
-                unit_price: 4500,
-            }],
-        },
-        QuoteTemplateResponse {
-            id: 4,
-            name: "TV Wall Mount".to_string(),
-            service_type: "general".to_string(),
-            items: vec![QuoteItem {
-                description: "TV mounting service".to_string(),
-                quantity: 1,
-                unit_price: 6500,
-            }],
-        },
-        QuoteTemplateResponse {
-            id: 5,
-            name: "Door Hanging".to_string(),
-            service_type: "carpentry".to_string(),
-            items: vec![
-                QuoteItem {
-                    description: "Call-out fee".to_string(),
-                    quantity: 1,
-                    unit_price: 3000,
-                },
-                QuoteItem {
-                    description: "Door hanging labour".to_string(),
-                    quantity: 1,
-                    unit_price: 7500,
-                },
-            ],
-        },
-    ];
+    TenantMm(mm): TenantMm,
+) -> Result<Json<Vec<QuoteTemplate>>, (StatusCode, String)> {
+    let templates = QuoteTemplateBmc::list(&mm, true)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     Ok(Json(templates))
 }
 
-/// Accept a quote (convert to booking)
+/// Quote acceptance request
+#[derive(Debug, Deserialize)]
+pub struct AcceptQuoteRequest {
+    /// Revision the customer was shown
+    pub revision: i32,
+    /// Full name typed by the customer as their signature
+    pub signer_name: String,
+    /// Drawn signature as a `data:image/jpeg;base64,...` URL
+    #[serde(default)]
+    pub signature_image: Option<String>,
+    pub customer_notes: Option<String>,
+    /// Option and add-ons chosen (leave empty if the quote has no choices)
+    #[serde(default)]
+    pub selection: QuoteSelection,
+}
+
+/// Accept a quote (public endpoint, authorised by a signed link)
+///
+/// Acceptance is tied to the revision the customer saw; if the quote has
+/// been revised since, it is refused and the customer is asked to review
+/// the new revision. The signer's name and signature, the request's IP
+/// address and user agent and a hash of the accepted revision are stored
+/// as an immutable acceptance record, and the signed PDF is emailed to the
+/// customer. For a quote with options, the chosen option and add-ons set
+/// the final price and open a pending booking for that work.
 pub async fn accept_quote(
-    State(_mm): State<ModelManager>,
-    Path(quote_id): Path<String>,
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Query(params): Query<SignedLinkParams>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(req): Json<AcceptQuoteRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    // TODO: Update quote status and create booking
+    verify_quote_link(id, &params)?;
+
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|ua| ua.to_str().ok())
+        .map(str::to_string);
+    let acceptance = QuoteAcceptanceBmc::record(
+        &mm,
+        id,
+        QuoteAcceptanceForCreate {
+            revision: req.revision,
+            signer_name: req.signer_name,
+            signature_image: req.signature_image,
+            customer_notes: req.customer_notes,
+            ip_address: Some(client_ip(&headers, peer).to_string()),
+            user_agent,
+            selection: req.selection,
+        },
+    )
+    .await
+    .map_err(error_response)?;
+    let quote = QuoteBmc::get(&mm, id).await.map_err(error_response)?;
+    let selection = quote.quote_selection().map_err(error_response)?;
+    let chosen = describe_selection(
+        &quote.quote_options().map_err(error_response)?,
+        &quote.quote_add_ons().map_err(error_response)?,
+        &selection,
+    );
+
+    send_acceptance_confirmation(&mm, &acceptance).await;
+
     Ok(Json(serde_json::json!({
         "success": true,
-        "quote_id": quote_id,
-        "message": "Quote accepted! We'll be in touch to confirm your booking.",
-        "booking_id": Uuid::new_v4().to_string()
+        "quote_id": id,
+        "revision": acceptance.revision,
+        "signer_name": acceptance.signer_name,
+        "accepted_at": format_timestamp(acceptance.accepted_at),
+        "content_hash": acceptance.content_hash,
+        "selection": selection,
+        "chosen": chosen,
+        "total_cents": quote.total_cents,
+        "booking_id": quote.booking_id,
+        "message": "Quote accepted! We'll be in touch to confirm your booking."
     })))
 }
 
-/// Instant quote calculator
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct InstantQuoteRequest {
-    pub service_type: String,
-    pub description: Option<String>,
-    pub urgency: Option<String>, // "same_day", "within_3_days", "flexible"
-}
+/// Services and pricing rules behind the instant quote calculator (public
+/// endpoint).
+///
+/// The Leptos calculator builds its form from this, so the services and
+/// options it offers always match what the estimate endpoint prices.
+pub async fn get_pricing_catalog(
+    TenantMm(mm): TenantMm,
+) -> Result<Json<PricingCatalog>, (StatusCode, String)> {
+    let catalog = PricingRuleBmc::catalog(&mm, true)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-#[derive(Debug, Serialize)]
-pub struct InstantQuoteResponse {
-    pub estimate_low: i32,
-    pub estimate_high: i32,
-    pub urgency_fee: i32,
-    pub message: String,
+    Ok(Json(catalog))
 }
 
 /// Get instant quote estimate (public endpoint)
+///
+/// The pricing rules engine turns the service's base range into an estimate
+/// range; both ends then go through the same pricing function as real
+/// quotes, so promo codes and VAT are applied the same way.
+///
+/// A postcode, when given, must be one we cover, and its travel band
+/// replaces any `distance_zone` the form sent.
 pub async fn get_instant_quote(
-    Json(req): Json<InstantQuoteRequest>,
+    TenantMm(mm): TenantMm,
+    Json(mut req): Json<InstantQuoteRequest>,
 ) -> Result<Json<InstantQuoteResponse>, (StatusCode, String)> {
-    // Base prices by service type (in cents)
-    let (base_low, base_high) = match req.service_type.to_lowercase().as_str() {
-        "plumbing" => (4500, 12000),
-        "electrical" => (5000, 15000),
-        "carpentry" => (6000, 18000),
-        "assembly" => (3500, 8500),
-        "painting" => (8000, 25000),
-        "general" | _ => (4000, 10000),
+    req.service_type = req.service_type.trim().to_lowercase();
+
+    if let Some(postcode) = req.postcode.as_deref().filter(|p| !p.trim().is_empty()) {
+        let coverage = PostcodeDistrictBmc::coverage(&mm, postcode)
+            .await
+            .map_err(error_response)?;
+        match coverage.distance_zone() {
+            Some(zone) => req.distance_zone = Some(zone),
+            None => return Err((StatusCode::BAD_REQUEST, coverage.message)),
+        }
+    }
+
+    let catalog = PricingRuleBmc::catalog(&mm, true)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let estimate = pricing::estimate(&catalog, &req).map_err(error_response)?;
+    let label = catalog
+        .services
+        .iter()
+        .find(|s| s.service_type == req.service_type)
+        .map_or(req.service_type.clone(), |s| s.label.clone());
+
+    let mut discounts = Vec::new();
+    let promo = match req.promo_code.as_deref().filter(|c| !c.trim().is_empty()) {
+        Some(code) => {
+            let promo =
+                PromoCodeBmc::validate(&mm, code, Some(&req.service_type), estimate.low_cents)
+                    .await
+                    .map_err(error_response)?;
+            discounts.push(promo.discount());
+            Some(promo.code)
+        }
+        None => None,
     };
 
-    // Urgency fee
-    let urgency_fee = match req.urgency.as_deref() {
-        Some("same_day") => 1500,   // £15 extra
-        Some("within_3_days") => 0, // No extra
-        Some("flexible") => -500,   // £5 discount
-        _ => 0,
+    let vat = VatSettings::from_config();
+    let total = |amount: i32| {
+        let items = [CoreQuoteItem {
+            description: format!("{label} work"),
+            quantity: 1,
+            unit_price: amount,
+            discount: None,
+            vat_rate_bp: None,
+        }];
+        pricing::price(&items, &discounts, &vat)
+            .map(|p| p.total_cents)
+            .map_err(error_response)
     };
+    let estimate_low = total(estimate.low_cents)?;
+    let estimate_high = total(estimate.high_cents)?;
 
     let response = InstantQuoteResponse {
-        estimate_low: base_low + urgency_fee,
-        estimate_high: base_high + urgency_fee,
-        urgency_fee,
+        estimate_low,
+        estimate_high,
+        adjustments: estimate.adjustments,
+        vat_included: vat.registered,
+        promo_code: promo,
         message: format!(
             "Estimated cost for {} work: £{:.2} - £{:.2}",
-            req.service_type,
-            (base_low + urgency_fee) as f64 / 100.0,
-            (base_high + urgency_fee) as f64 / 100.0
+            label,
+            estimate_low as f64 / 100.0,
+            estimate_high as f64 / 100.0
         ),
     };
 
     Ok(Json(response))
 }
+
+/// Emails the signed quote PDF to the customer. Failures are logged; the
+/// acceptance itself is already recorded.
+async fn send_acceptance_confirmation(mm: &ModelManager, acceptance: &QuoteAcceptance) {
+    let rendered = async {
+        let branding = Branding::load(mm).await?;
+        DocumentBmc::quote_pdf(mm, acceptance.quote_id, &branding).await
+    };
+    let doc = match rendered.await {
+        Ok(doc) => doc,
+        Err(e) => {
+            error!(
+                "Failed to render accepted quote {}: {}",
+                acceptance.quote_id, e
+            );
+            return;
+        }
+    };
+    let link = link::signed_link(
+        LinkedDocument::Quotes,
+        acceptance.quote_id,
+        chrono::Utc::now().timestamp(),
+    );
+    let details = format!(
+        "Thank you for accepting this quote. Your acceptance has been recorded:\n\n\
+         Signed by: {}\nAccepted at: {}\nRevision: {}\nIP address: {}\nBrowser: {}\n\
+         Quote SHA-256: {}",
+        acceptance.signer_name,
+        format_timestamp(acceptance.accepted_at),
+        acceptance.revision,
+        acceptance.ip_address.as_deref().unwrap_or("unknown"),
+        acceptance.user_agent.as_deref().unwrap_or("unknown"),
+        acceptance.content_hash
+    );
+
+    if let Err(e) = email_document(
+        mm,
+        doc,
+        "Quote accepted:",
+        "quote_acceptance",
+        link,
+        Some(&details),
+    )
+    .await
+    {
+        error!(
+            "Failed to send acceptance confirmation for quote {}: {}",
+            acceptance.quote_id, e
+        );
+    }
+}
+
+fn verify_quote_link(id: i32, params: &SignedLinkParams) -> Result<(), (StatusCode, String)> {
+    link::verify(
+        link::signing_key(),
+        LinkedDocument::Quotes,
+        id,
+        params.expires,
+        &params.signature,
+        chrono::Utc::now().timestamp(),
+    )
+    .map_err(|e| match e {
+        LinkError::InvalidSignature => (StatusCode::UNAUTHORIZED, "Invalid link".to_string()),
+        LinkError::Expired => (
+            StatusCode::UNAUTHORIZED,
+            "This link has expired".to_string(),
+        ),
+    })
+}
+
+fn error_response(e: ModelError) -> (StatusCode, String) {
+    match e {
+        ModelError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg.into_owned()),
+        ModelError::EntityNotFound { .. } => (StatusCode::NOT_FOUND, "Quote not found".to_string()),
+        other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+    }
+}
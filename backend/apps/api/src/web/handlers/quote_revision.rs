@@ -0,0 +1,80 @@
+//! Admin quote editing and revision history handlers.
+//!
+//! Editing a sent quote creates a new revision; earlier revisions stay
+//! exactly as the customer saw them. Once accepted, the signed acceptance
+//! record shows which revision the customer agreed to.
+
+use axum::extract::{Json, Path};
+use lib_core::model::quote::{Quote, QuoteBmc, QuoteForUpdate};
+use lib_core::model::quote_acceptance::{QuoteAcceptance, QuoteAcceptanceBmc};
+use lib_core::model::quote_revision::{QuoteRevision, QuoteRevisionBmc};
+use lib_web::{Error, TenantMm};
+use shared::ApiResponse;
+use tracing::info;
+
+/// Edits a quote, creating a new revision if it has been sent.
+#[utoipa::path(
+    patch,
+    path = "/api/admin/quotes/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Quote ID")),
+    request_body = QuoteForUpdate,
+    responses(
+        (status = 200, description = "Quote updated", body = Quote),
+        (status = 400, description = "Invalid change, or quote already answered", body = Value),
+        (status = 404, description = "Quote not found", body = Value)
+    )
+)]
+pub async fn update_quote_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<QuoteForUpdate>,
+) -> Result<Json<ApiResponse<Quote>>, Error> {
+    let quote = QuoteBmc::update(&mm, id, req).await?;
+
+    info!("Quote {} updated (revision {})", id, quote.revision);
+    Ok(Json(ApiResponse::success("Quote updated", quote)))
+}
+
+/// Lists a quote's revisions, oldest first.
+#[utoipa::path(
+    get,
+    path = "/api/admin/quotes/{id}/revisions",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Quote ID")),
+    responses((status = 200, description = "Revisions, oldest first", body = [QuoteRevision]))
+)]
+pub async fn list_quote_revisions_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<QuoteRevision>>>, Error> {
+    let revisions = QuoteRevisionBmc::list(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Quote revisions retrieved",
+        revisions,
+    )))
+}
+
+/// Gets the signed acceptance record for a quote.
+#[utoipa::path(
+    get,
+    path = "/api/admin/quotes/{id}/acceptance",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Quote ID")),
+    responses(
+        (status = 200, description = "Acceptance record", body = QuoteAcceptance),
+        (status = 404, description = "Quote has not been accepted", body = Value)
+    )
+)]
+pub async fn get_quote_acceptance_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<QuoteAcceptance>>, Error> {
+    let acceptance = QuoteAcceptanceBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Quote acceptance retrieved",
+        acceptance,
+    )))
+}
@@ -0,0 +1,145 @@
+//! Admin quote template handlers.
+//!
+//! Managing reusable quote templates and creating draft quotes from them.
+
+use axum::extract::{Json, Path};
+use lib_core::model::quote::{Quote, QuoteBmc};
+use lib_core::model::quote_template::{
+    QuoteFromTemplate, QuoteTemplate, QuoteTemplateBmc, QuoteTemplateForCreate,
+    QuoteTemplateForUpdate,
+};
+use lib_web::{Error, TenantMm};
+use serde_json::{json, Value};
+use shared::ApiResponse;
+use tracing::info;
+
+/// Creates a quote template.
+#[utoipa::path(
+    post,
+    path = "/api/admin/quote-templates",
+    tag = "admin",
+    request_body = QuoteTemplateForCreate,
+    responses(
+        (status = 200, description = "Template created", body = QuoteTemplate),
+        (status = 400, description = "Items refer to undeclared variables", body = Value)
+    )
+)]
+pub async fn create_quote_template_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<QuoteTemplateForCreate>,
+) -> Result<Json<ApiResponse<QuoteTemplate>>, Error> {
+    let id = QuoteTemplateBmc::create(&mm, req).await?;
+    let template = QuoteTemplateBmc::get(&mm, id).await?;
+
+    info!("Quote template {} created", id);
+    Ok(Json(ApiResponse::success("Template created", template)))
+}
+
+/// Lists all quote templates, including inactive ones.
+#[utoipa::path(
+    get,
+    path = "/api/admin/quote-templates",
+    tag = "admin",
+    responses((status = 200, description = "Templates by service and name", body = [QuoteTemplate]))
+)]
+pub async fn list_quote_templates_handler(
+    TenantMm(mm): TenantMm,
+) -> Result<Json<ApiResponse<Vec<QuoteTemplate>>>, Error> {
+    let templates = QuoteTemplateBmc::list(&mm, false).await?;
+
+    Ok(Json(ApiResponse::success("Templates retrieved", templates)))
+}
+
+/// Gets a quote template.
+#[utoipa::path(
+    get,
+    path = "/api/admin/quote-templates/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Template ID")),
+    responses(
+        (status = 200, description = "Template", body = QuoteTemplate),
+        (status = 404, description = "Template not found", body = Value)
+    )
+)]
+pub async fn get_quote_template_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<QuoteTemplate>>, Error> {
+    let template = QuoteTemplateBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success("Template retrieved", template)))
+}
+
+/// Updates a quote template.
+#[utoipa::path(
+    patch,
+    path = "/api/admin/quote-templates/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Template ID")),
+    request_body = QuoteTemplateForUpdate,
+    responses(
+        (status = 200, description = "Template updated", body = QuoteTemplate),
+        (status = 400, description = "Items refer to undeclared variables", body = Value),
+        (status = 404, description = "Template not found", body = Value)
+    )
+)]
+pub async fn update_quote_template_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<QuoteTemplateForUpdate>,
+) -> Result<Json<ApiResponse<QuoteTemplate>>, Error> {
+    QuoteTemplateBmc::update(&mm, id, req).await?;
+    let template = QuoteTemplateBmc::get(&mm, id).await?;
+
+    info!("Quote template {} updated", id);
+    Ok(Json(ApiResponse::success("Template updated", template)))
+}
+
+/// Deletes a quote template. Quotes already created from it are kept.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/quote-templates/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Template ID")),
+    responses(
+        (status = 200, description = "Template deleted", body = Value),
+        (status = 404, description = "Template not found", body = Value)
+    )
+)]
+pub async fn delete_quote_template_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    QuoteTemplateBmc::delete(&mm, id).await?;
+
+    info!("Quote template {} deleted", id);
+    Ok(Json(ApiResponse::success(
+        "Template deleted",
+        json!({ "id": id }),
+    )))
+}
+
+/// Creates a draft quote for a customer from a template.
+#[utoipa::path(
+    post,
+    path = "/api/admin/quote-templates/{id}/quotes",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Template ID")),
+    request_body = QuoteFromTemplate,
+    responses(
+        (status = 200, description = "Draft quote created", body = Quote),
+        (status = 400, description = "Inactive template, invalid variable or promo code", body = Value),
+        (status = 404, description = "Template not found", body = Value)
+    )
+)]
+pub async fn create_quote_from_template_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<QuoteFromTemplate>,
+) -> Result<Json<ApiResponse<Quote>>, Error> {
+    let quote_id = QuoteTemplateBmc::create_quote(&mm, id, req).await?;
+    let quote = QuoteBmc::get(&mm, quote_id).await?;
+
+    info!("Draft quote {} created from template {}", quote_id, id);
+    Ok(Json(ApiResponse::success("Draft quote created", quote)))
+}
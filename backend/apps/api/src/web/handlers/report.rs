@@ -0,0 +1,49 @@
+//! Admin report handlers.
+//!
+//! Margin reporting on completed jobs: what was charged against labour
+//! time and material cost, per job and per service type.
+
+use axum::extract::{Json, Query};
+use lib_core::model::margin::{MarginBmc, MarginFilter, MarginReport};
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use shared::ApiResponse;
+
+/// Query parameters for `/api/admin/reports/margins`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct MarginParams {
+    /// First completion date to include (YYYY-MM-DD)
+    pub from: Option<String>,
+    /// Last completion date to include (YYYY-MM-DD)
+    pub to: Option<String>,
+    /// Only jobs of this service type
+    pub service_type: Option<String>,
+}
+
+/// Reports margins on completed jobs.
+#[utoipa::path(
+    get,
+    path = "/api/admin/reports/margins",
+    tag = "admin",
+    params(MarginParams),
+    responses(
+        (status = 200, description = "Margins per job and service", body = MarginReport),
+        (status = 400, description = "Invalid date", body = serde_json::Value)
+    )
+)]
+pub async fn margin_report_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<MarginParams>,
+) -> Result<Json<ApiResponse<MarginReport>>, Error> {
+    let report = MarginBmc::report(
+        &mm,
+        MarginFilter {
+            from: params.from,
+            to: params.to,
+            service_type: params.service_type,
+        },
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::success("Margin report ready", report)))
+}
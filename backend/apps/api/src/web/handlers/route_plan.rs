@@ -0,0 +1,47 @@
+//! Admin route planning handlers.
+//!
+//! Technicians' routes for a day as GeoJSON, ready for the dashboard map.
+
+use axum::extract::Query;
+use axum::http::header;
+use axum::response::IntoResponse;
+use lib_core::model::route_plan::{to_geojson, RoutePlanBmc};
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+
+/// Query parameters for `/api/admin/route`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct RouteParams {
+    /// Day to plan (YYYY-MM-DD)
+    pub date: String,
+    /// Only this technician (default: everyone with jobs that day)
+    pub user_id: Option<i32>,
+    /// When the day starts, HH:MM (default 08:00)
+    pub start: Option<String>,
+}
+
+/// Plans the day's routes and returns them as a GeoJSON FeatureCollection.
+#[utoipa::path(
+    get,
+    path = "/api/admin/route",
+    tag = "admin",
+    params(RouteParams),
+    responses(
+        (status = 200, description = "Stops, routes and unrouted jobs", body = serde_json::Value,
+            content_type = "application/geo+json"),
+        (status = 400, description = "Invalid date or start time", body = serde_json::Value),
+        (status = 404, description = "Technician not found", body = serde_json::Value)
+    )
+)]
+pub async fn route_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<RouteParams>,
+) -> Result<impl IntoResponse, Error> {
+    let routes =
+        RoutePlanBmc::plan_day(&mm, &params.date, params.user_id, params.start.as_deref()).await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/geo+json")],
+        to_geojson(&routes).to_string(),
+    ))
+}
@@ -0,0 +1,45 @@
+//! Admin search handler.
+//!
+//! Backs the admin search box with ranked hits across customers,
+//! bookings and quotes.
+
+use axum::extract::{Json, Query};
+use lib_core::model::search::{SearchBmc, SearchHit};
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use shared::ApiResponse;
+
+/// Default number of hits returned when `limit` is omitted.
+const DEFAULT_LIMIT: i64 = 20;
+
+/// Query parameters for `/api/admin/search`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct SearchParams {
+    /// Free-text query (name, email, phone, postcode, notes, quote items...)
+    pub q: String,
+    /// Maximum number of hits (default 20, max 100)
+    pub limit: Option<i64>,
+}
+
+/// Searches customers, bookings and quotes.
+#[utoipa::path(
+    get,
+    path = "/api/admin/search",
+    tag = "admin",
+    params(SearchParams),
+    responses(
+        (status = 200, description = "Ranked search hits", body = serde_json::Value),
+        (status = 400, description = "Query too short", body = serde_json::Value)
+    )
+)]
+pub async fn admin_search_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<ApiResponse<Vec<SearchHit>>>, Error> {
+    let hits = SearchBmc::search(&mm, &params.q, params.limit.unwrap_or(DEFAULT_LIMIT)).await?;
+
+    Ok(Json(ApiResponse::success(
+        format!("{} results", hits.len()),
+        hits,
+    )))
+}
@@ -0,0 +1,277 @@
+//! Admin technician handlers.
+//!
+//! Technician profiles (skills and working areas), assigning technicians
+//! to bookings, suggested assignees, and each technician's job list.
+
+use axum::extract::{Json, Path, Query};
+use lib_core::model::booking::Booking;
+use lib_core::model::technician::{
+    BookingAssignment, Technician, TechnicianBmc, TechnicianForCreate, TechnicianForUpdate,
+    TechnicianSuggestion,
+};
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::ApiResponse;
+use tracing::info;
+
+/// Query parameters for `/api/admin/technicians`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct TechnicianListParams {
+    /// Include technicians not taking work (default false)
+    pub include_inactive: Option<bool>,
+}
+
+/// Query parameters for a technician's job list.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct TechnicianBookingsParams {
+    /// First scheduled date to include (YYYY-MM-DD)
+    pub from: Option<String>,
+    /// Last scheduled date to include (YYYY-MM-DD)
+    pub to: Option<String>,
+}
+
+/// Technicians to assign to a booking.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AssignRequest {
+    /// User IDs of the technicians
+    pub user_ids: Vec<i32>,
+    /// Assign even if their schedules clash (default false)
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Makes a user a technician.
+#[utoipa::path(
+    post,
+    path = "/api/admin/technicians",
+    tag = "admin",
+    request_body = TechnicianForCreate,
+    responses(
+        (status = 200, description = "Technician created", body = Technician),
+        (status = 400, description = "Invalid profile or already a technician", body = Value),
+        (status = 404, description = "User not found", body = Value)
+    )
+)]
+pub async fn create_technician_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<TechnicianForCreate>,
+) -> Result<Json<ApiResponse<Technician>>, Error> {
+    let user_id = TechnicianBmc::create(&mm, req).await?;
+    let technician = TechnicianBmc::get(&mm, user_id).await?;
+
+    info!("User {} made a technician", user_id);
+    Ok(Json(ApiResponse::success("Technician created", technician)))
+}
+
+/// Lists technicians by name.
+#[utoipa::path(
+    get,
+    path = "/api/admin/technicians",
+    tag = "admin",
+    params(TechnicianListParams),
+    responses((status = 200, description = "Technicians, by name", body = [Technician]))
+)]
+pub async fn list_technicians_handler(
+    TenantMm(mm): TenantMm,
+    Query(params): Query<TechnicianListParams>,
+) -> Result<Json<ApiResponse<Vec<Technician>>>, Error> {
+    let technicians = TechnicianBmc::list(&mm, !params.include_inactive.unwrap_or(false)).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Technicians retrieved",
+        technicians,
+    )))
+}
+
+/// Gets a technician.
+#[utoipa::path(
+    get,
+    path = "/api/admin/technicians/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Technician", body = Technician),
+        (status = 404, description = "Technician not found", body = Value)
+    )
+)]
+pub async fn get_technician_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Technician>>, Error> {
+    let technician = TechnicianBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Technician retrieved",
+        technician,
+    )))
+}
+
+/// Updates a technician's profile.
+#[utoipa::path(
+    patch,
+    path = "/api/admin/technicians/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "User ID")),
+    request_body = TechnicianForUpdate,
+    responses(
+        (status = 200, description = "Technician updated", body = Technician),
+        (status = 400, description = "Blank name or invalid area", body = Value),
+        (status = 404, description = "Technician not found", body = Value)
+    )
+)]
+pub async fn update_technician_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<TechnicianForUpdate>,
+) -> Result<Json<ApiResponse<Technician>>, Error> {
+    TechnicianBmc::update(&mm, id, req).await?;
+    let technician = TechnicianBmc::get(&mm, id).await?;
+
+    info!("Technician {} updated", id);
+    Ok(Json(ApiResponse::success("Technician updated", technician)))
+}
+
+/// Removes a technician profile and their assignments.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/technicians/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Technician removed", body = Value),
+        (status = 404, description = "Technician not found", body = Value)
+    )
+)]
+pub async fn delete_technician_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    TechnicianBmc::delete(&mm, id).await?;
+
+    info!("Technician {} removed", id);
+    Ok(Json(ApiResponse::success(
+        "Technician removed",
+        json!({ "id": id }),
+    )))
+}
+
+/// Lists a technician's jobs by schedule.
+#[utoipa::path(
+    get,
+    path = "/api/admin/technicians/{id}/bookings",
+    tag = "admin",
+    params(("id" = i32, Path, description = "User ID"), TechnicianBookingsParams),
+    responses(
+        (status = 200, description = "Assigned bookings, soonest first", body = [Booking]),
+        (status = 400, description = "Invalid date", body = Value),
+        (status = 404, description = "Technician not found", body = Value)
+    )
+)]
+pub async fn list_technician_bookings_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Query(params): Query<TechnicianBookingsParams>,
+) -> Result<Json<ApiResponse<Vec<Booking>>>, Error> {
+    let bookings =
+        TechnicianBmc::list_bookings(&mm, id, params.from.as_deref(), params.to.as_deref()).await?;
+
+    Ok(Json(ApiResponse::success("Bookings retrieved", bookings)))
+}
+
+/// Assigns technicians to a booking.
+#[utoipa::path(
+    post,
+    path = "/api/admin/bookings/{id}/assignments",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    request_body = AssignRequest,
+    responses(
+        (status = 200, description = "Everyone now assigned", body = [BookingAssignment]),
+        (status = 400, description = "Booking closed, technician inactive or busy", body = Value),
+        (status = 404, description = "Booking or technician not found", body = Value)
+    )
+)]
+pub async fn assign_technicians_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<AssignRequest>,
+) -> Result<Json<ApiResponse<Vec<BookingAssignment>>>, Error> {
+    let assignments = TechnicianBmc::assign(&mm, id, &req.user_ids, req.force).await?;
+
+    info!(
+        "Booking {} assigned to {:?} (force: {})",
+        id, req.user_ids, req.force
+    );
+    Ok(Json(ApiResponse::success(
+        "Technicians assigned",
+        assignments,
+    )))
+}
+
+/// Lists the technicians assigned to a booking.
+#[utoipa::path(
+    get,
+    path = "/api/admin/bookings/{id}/assignments",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    responses((status = 200, description = "Assigned technicians", body = [BookingAssignment]))
+)]
+pub async fn list_assignments_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<BookingAssignment>>>, Error> {
+    let assignments = TechnicianBmc::list_assignments(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Assignments retrieved",
+        assignments,
+    )))
+}
+
+/// Removes a technician from a booking.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/bookings/{id}/assignments/{user_id}",
+    tag = "admin",
+    params(
+        ("id" = i32, Path, description = "Booking ID"),
+        ("user_id" = i32, Path, description = "Technician's user ID")
+    ),
+    responses(
+        (status = 200, description = "Technician unassigned", body = Value),
+        (status = 404, description = "Technician not on the booking", body = Value)
+    )
+)]
+pub async fn unassign_technician_handler(
+    TenantMm(mm): TenantMm,
+    Path((id, user_id)): Path<(i32, i32)>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    TechnicianBmc::unassign(&mm, id, user_id).await?;
+
+    info!("Technician {} unassigned from booking {}", user_id, id);
+    Ok(Json(ApiResponse::success(
+        "Technician unassigned",
+        json!({ "booking_id": id, "user_id": user_id }),
+    )))
+}
+
+/// Ranks technicians to send to a booking.
+#[utoipa::path(
+    get,
+    path = "/api/admin/bookings/{id}/technician-suggestions",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    responses(
+        (status = 200, description = "Candidates, best first", body = [TechnicianSuggestion]),
+        (status = 404, description = "Booking not found", body = Value)
+    )
+)]
+pub async fn suggest_technicians_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<TechnicianSuggestion>>>, Error> {
+    let suggestions = TechnicianBmc::suggest(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success("Suggestions ready", suggestions)))
+}
@@ -0,0 +1,178 @@
+//! Site and tenant handlers.
+//!
+//! `GET /api/site` tells the Leptos site which business it is rendering for
+//! the request's host. The admin tenant endpoints add and edit the client
+//! businesses hosted from this deployment; they need an admin token and
+//! are only served on the default tenant's hosts, so one client's admin
+//! can't see another's.
+
+use axum::extract::{Json, Path};
+use lib_core::model::area::AreaBmc;
+use lib_core::model::service::ServiceBmc;
+use lib_core::model::tenant::{
+    Tenant, TenantBmc, TenantForCreate, TenantForUpdate, DEFAULT_TENANT_ID,
+};
+use lib_core::model::ModelManager;
+use lib_web::{Error, TenantMm};
+use shared::{ApiResponse, PageLink, SiteConfig, HANDYMAN_SITE_PATH};
+use tracing::info;
+
+// region:    --- Public Site
+
+/// The business, branding and navigation for the site on this host.
+#[utoipa::path(
+    get,
+    path = "/api/site",
+    tag = "site",
+    responses((status = 200, description = "Site configuration", body = SiteConfig))
+)]
+pub async fn site_handler(TenantMm(mm): TenantMm) -> Result<Json<ApiResponse<SiteConfig>>, Error> {
+    let tenant = TenantBmc::get(&mm, mm.tenant_id()).await?;
+    let services = ServiceBmc::list(&mm, true)
+        .await?
+        .into_iter()
+        .map(|s| PageLink {
+            slug: s.slug,
+            name: s.name,
+        })
+        .collect();
+    let areas = AreaBmc::list(&mm, true)
+        .await?
+        .into_iter()
+        .map(|a| PageLink {
+            slug: a.slug,
+            name: a.name,
+        })
+        .collect();
+
+    let opening_hours = tenant.opening_hours_list()?;
+    let testimonials = tenant.testimonial_list()?;
+
+    let site = SiteConfig {
+        business_name: tenant.business_name,
+        trade: tenant.trade,
+        phone: tenant.phone,
+        email: tenant.email,
+        tagline: tenant.tagline,
+        primary_color: tenant.primary_color,
+        accent_color: tenant.accent_color,
+        logo_url: tenant.logo_url,
+        services,
+        areas,
+        opening_hours,
+        accreditations: tenant.accreditations,
+        testimonials,
+        client_site: tenant.id != DEFAULT_TENANT_ID,
+        noindex: tenant.noindex,
+        site_path: HANDYMAN_SITE_PATH.to_string(),
+    };
+
+    Ok(Json(ApiResponse::success("Site retrieved", site)))
+}
+
+// endregion: --- Public Site
+
+// region:    --- Admin Tenants
+
+/// Lists tenants, including suspended ones.
+#[utoipa::path(
+    get,
+    path = "/api/admin/tenants",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Tenants, by ID", body = [Tenant]),
+        (status = 403, description = "Not the platform's own site", body = serde_json::Value)
+    )
+)]
+pub async fn list_tenants_handler(
+    TenantMm(mm): TenantMm,
+) -> Result<Json<ApiResponse<Vec<Tenant>>>, Error> {
+    require_default_tenant(&mm)?;
+    let tenants = TenantBmc::list(&mm, false).await?;
+
+    Ok(Json(ApiResponse::success("Tenants retrieved", tenants)))
+}
+
+/// Adds a tenant.
+#[utoipa::path(
+    post,
+    path = "/api/admin/tenants",
+    tag = "admin",
+    request_body = TenantForCreate,
+    responses(
+        (status = 200, description = "Tenant created", body = Tenant),
+        (status = 400, description = "Invalid, or claims another tenant's domain", body = serde_json::Value),
+        (status = 403, description = "Not the platform's own site", body = serde_json::Value)
+    )
+)]
+pub async fn create_tenant_handler(
+    TenantMm(mm): TenantMm,
+    Json(req): Json<TenantForCreate>,
+) -> Result<Json<ApiResponse<Tenant>>, Error> {
+    require_default_tenant(&mm)?;
+    let id = TenantBmc::create(&mm, req).await?;
+    let tenant = TenantBmc::get(&mm, id).await?;
+
+    info!("Tenant {} created", id);
+    Ok(Json(ApiResponse::success("Tenant created", tenant)))
+}
+
+/// Gets a tenant.
+#[utoipa::path(
+    get,
+    path = "/api/admin/tenants/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Tenant ID")),
+    responses(
+        (status = 200, description = "Tenant", body = Tenant),
+        (status = 403, description = "Not the platform's own site", body = serde_json::Value),
+        (status = 404, description = "Tenant not found", body = serde_json::Value)
+    )
+)]
+pub async fn get_tenant_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Tenant>>, Error> {
+    require_default_tenant(&mm)?;
+    let tenant = TenantBmc::get(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success("Tenant retrieved", tenant)))
+}
+
+/// Updates a tenant's details, domains, branding or indexing, or suspends it.
+#[utoipa::path(
+    patch,
+    path = "/api/admin/tenants/{id}",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Tenant ID")),
+    request_body = TenantForUpdate,
+    responses(
+        (status = 200, description = "Tenant updated", body = Tenant),
+        (status = 400, description = "Invalid, or claims another tenant's domain", body = serde_json::Value),
+        (status = 403, description = "Not the platform's own site", body = serde_json::Value),
+        (status = 404, description = "Tenant not found", body = serde_json::Value)
+    )
+)]
+pub async fn update_tenant_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<TenantForUpdate>,
+) -> Result<Json<ApiResponse<Tenant>>, Error> {
+    require_default_tenant(&mm)?;
+    TenantBmc::update(&mm, id, req).await?;
+    let tenant = TenantBmc::get(&mm, id).await?;
+
+    info!("Tenant {} updated", id);
+    Ok(Json(ApiResponse::success("Tenant updated", tenant)))
+}
+
+// endregion: --- Admin Tenants
+
+/// Tenants are managed from the platform's own site only.
+pub(super) fn require_default_tenant(mm: &ModelManager) -> Result<(), Error> {
+    if mm.tenant_id() == DEFAULT_TENANT_ID {
+        Ok(())
+    } else {
+        Err(Error::InsufficientPermissions)
+    }
+}
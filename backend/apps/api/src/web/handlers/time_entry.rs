@@ -0,0 +1,206 @@
+//! Admin time tracking handlers.
+//!
+//! Start, pause, resume and stop the clock on a job, list the time
+//! recorded against it, and produce weekly timesheets per technician as
+//! JSON or CSV.
+
+use axum::extract::{Json, Path, Query};
+use axum::http::header;
+use axum::response::IntoResponse;
+use lib_core::model::time_entry::{TimeEntry, TimeEntryBmc, Timesheet};
+use lib_web::{Error, TenantMm};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::ApiResponse;
+use time::OffsetDateTime;
+use tracing::info;
+
+/// Technician performing a clock action.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ClockRequest {
+    /// User ID of the technician
+    pub user_id: i32,
+}
+
+/// Query parameters for the timesheet endpoints.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct TimesheetParams {
+    /// Any date in the week, YYYY-MM-DD (default: this week)
+    pub week: Option<String>,
+}
+
+/// Starts the clock for a technician on a booking.
+#[utoipa::path(
+    post,
+    path = "/api/admin/bookings/{id}/time/start",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    request_body = ClockRequest,
+    responses(
+        (status = 200, description = "Clock started", body = TimeEntry),
+        (status = 400, description = "Booking closed or technician busy", body = Value),
+        (status = 404, description = "Booking or user not found", body = Value)
+    )
+)]
+pub async fn start_time_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<ClockRequest>,
+) -> Result<Json<ApiResponse<TimeEntry>>, Error> {
+    let entry = TimeEntryBmc::start(&mm, id, req.user_id).await?;
+
+    info!("User {} started booking {}", req.user_id, id);
+    Ok(Json(ApiResponse::success("Clock started", entry)))
+}
+
+/// Stops the clock for a technician, leaving the job open.
+#[utoipa::path(
+    post,
+    path = "/api/admin/bookings/{id}/time/pause",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    request_body = ClockRequest,
+    responses(
+        (status = 200, description = "Clock paused", body = TimeEntry),
+        (status = 400, description = "Technician not clocked in on the booking", body = Value),
+        (status = 404, description = "User not found", body = Value)
+    )
+)]
+pub async fn pause_time_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<ClockRequest>,
+) -> Result<Json<ApiResponse<TimeEntry>>, Error> {
+    let entry = TimeEntryBmc::pause(&mm, id, req.user_id).await?;
+
+    info!("User {} paused booking {}", req.user_id, id);
+    Ok(Json(ApiResponse::success("Clock paused", entry)))
+}
+
+/// Restarts the clock for a technician after a pause.
+#[utoipa::path(
+    post,
+    path = "/api/admin/bookings/{id}/time/resume",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    request_body = ClockRequest,
+    responses(
+        (status = 200, description = "Clock resumed", body = TimeEntry),
+        (status = 400, description = "Nothing to resume, or technician busy", body = Value),
+        (status = 404, description = "Booking or user not found", body = Value)
+    )
+)]
+pub async fn resume_time_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Json(req): Json<ClockRequest>,
+) -> Result<Json<ApiResponse<TimeEntry>>, Error> {
+    let entry = TimeEntryBmc::resume(&mm, id, req.user_id).await?;
+
+    info!("User {} resumed booking {}", req.user_id, id);
+    Ok(Json(ApiResponse::success("Clock resumed", entry)))
+}
+
+/// Stops the job: closes all running entries and completes the booking.
+#[utoipa::path(
+    post,
+    path = "/api/admin/bookings/{id}/time/stop",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    responses(
+        (status = 200, description = "Job completed with its recorded minutes", body = Value),
+        (status = 400, description = "Booking closed or no time recorded", body = Value),
+        (status = 404, description = "Booking not found", body = Value)
+    )
+)]
+pub async fn stop_time_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, Error> {
+    let minutes = TimeEntryBmc::stop(&mm, id).await?;
+
+    info!("Booking {} completed after {} minutes", id, minutes);
+    Ok(Json(ApiResponse::success(
+        "Job completed",
+        json!({ "id": id, "actual_duration": minutes }),
+    )))
+}
+
+/// Lists the time recorded on a booking.
+#[utoipa::path(
+    get,
+    path = "/api/admin/bookings/{id}/time-entries",
+    tag = "admin",
+    params(("id" = i32, Path, description = "Booking ID")),
+    responses((status = 200, description = "Time entries, oldest first", body = [TimeEntry]))
+)]
+pub async fn list_time_entries_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<TimeEntry>>>, Error> {
+    let entries = TimeEntryBmc::list_for_booking(&mm, id).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Time entries retrieved",
+        entries,
+    )))
+}
+
+/// A technician's weekly timesheet.
+#[utoipa::path(
+    get,
+    path = "/api/admin/users/{id}/timesheet",
+    tag = "admin",
+    params(("id" = i32, Path, description = "User ID"), TimesheetParams),
+    responses(
+        (status = 200, description = "Timesheet for the week", body = Timesheet),
+        (status = 400, description = "Invalid week", body = Value),
+        (status = 404, description = "User not found", body = Value)
+    )
+)]
+pub async fn timesheet_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Query(params): Query<TimesheetParams>,
+) -> Result<Json<ApiResponse<Timesheet>>, Error> {
+    let sheet =
+        TimeEntryBmc::timesheet(&mm, id, params.week.as_deref(), OffsetDateTime::now_utc()).await?;
+
+    Ok(Json(ApiResponse::success("Timesheet retrieved", sheet)))
+}
+
+/// A technician's weekly timesheet as a CSV download.
+#[utoipa::path(
+    get,
+    path = "/api/admin/users/{id}/timesheet.csv",
+    tag = "admin",
+    params(("id" = i32, Path, description = "User ID"), TimesheetParams),
+    responses(
+        (status = 200, description = "Timesheet CSV", content_type = "text/csv"),
+        (status = 400, description = "Invalid week", body = Value),
+        (status = 404, description = "User not found", body = Value)
+    )
+)]
+pub async fn timesheet_csv_handler(
+    TenantMm(mm): TenantMm,
+    Path(id): Path<i32>,
+    Query(params): Query<TimesheetParams>,
+) -> Result<impl IntoResponse, Error> {
+    let sheet =
+        TimeEntryBmc::timesheet(&mm, id, params.week.as_deref(), OffsetDateTime::now_utc()).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"timesheet-{}-{}.csv\"",
+                    sheet.user_id, sheet.week_start
+                ),
+            ),
+            (header::CACHE_CONTROL, "private, no-store".to_string()),
+        ],
+        sheet.to_csv(),
+    ))
+}
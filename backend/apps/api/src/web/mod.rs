@@ -4,26 +4,130 @@
 
 pub mod handlers;
 pub mod openapi;
+pub mod routes_attachment;
+pub mod routes_blog;
 pub mod routes_contact;
+pub mod routes_coverage;
+pub mod routes_customer;
+pub mod routes_document;
+pub mod routes_gdpr;
 pub mod routes_health;
+pub mod routes_invoice;
+pub mod routes_landing_page;
+pub mod routes_material;
+pub mod routes_onboarding;
 pub mod routes_payment;
+pub mod routes_pricing_rule;
+pub mod routes_promo_code;
 pub mod routes_quote;
+pub mod routes_quote_revision;
+pub mod routes_quote_template;
+pub mod routes_report;
+pub mod routes_route_plan;
+pub mod routes_search;
 pub mod routes_seo;
 pub mod routes_static;
+pub mod routes_technician;
+pub mod routes_tenant;
+pub mod routes_time_entry;
 
+use axum::middleware;
 use axum::Router;
 use lib_core::model::ModelManager;
 
 pub fn routes(mm: ModelManager) -> Router {
     let api_routes = Router::new()
+        .merge(routes_attachment::routes(mm.clone()))
+        .merge(routes_blog::routes(mm.clone()))
         .merge(routes_contact::routes(mm.clone()))
+        .merge(routes_coverage::routes(mm.clone()))
+        .merge(routes_customer::routes(mm.clone()))
+        .merge(routes_document::routes(mm.clone()))
+        .merge(routes_gdpr::routes(mm.clone()))
+        .merge(routes_invoice::routes(mm.clone()))
+        .merge(routes_landing_page::routes(mm.clone()))
+        .merge(routes_material::routes(mm.clone()))
+        .merge(routes_onboarding::routes(mm.clone()))
         .merge(routes_payment::routes(mm.clone()))
-        .merge(routes_quote::routes(mm.clone()));
+        .merge(routes_pricing_rule::routes(mm.clone()))
+        .merge(routes_promo_code::routes(mm.clone()))
+        .merge(routes_quote::routes(mm.clone()))
+        .merge(routes_quote_revision::routes(mm.clone()))
+        .merge(routes_quote_template::routes(mm.clone()))
+        .merge(routes_report::routes(mm.clone()))
+        .merge(routes_route_plan::routes(mm.clone()))
+        .merge(routes_search::routes(mm.clone()))
+        .merge(routes_technician::routes(mm.clone()))
+        .merge(routes_tenant::routes(mm.clone()))
+        .merge(routes_time_entry::routes(mm.clone()));
 
     let system_routes = Router::new()
         .merge(routes_static::routes())
-        .merge(routes_seo::routes())
+        .merge(routes_seo::routes(mm.clone()))
         .merge(routes_health::routes(mm.clone()));
 
-    Router::new().nest("/api", api_routes).merge(system_routes)
+    Router::new()
+        .nest("/api", api_routes)
+        .merge(system_routes)
+        // Everything under /api/admin/ needs an admin token
+        .layer(middleware::from_fn_with_state(mm, lib_web::mw_admin_auth))
 }
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use lib_core::_dev_utils;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_admin_routes_need_token() {
+        let app = routes(_dev_utils::init_test().await);
+
+        for uri in [
+            "/api/admin/quotes/1/pdf",
+            "/api/admin/invoices/1/pdf",
+            "/api/admin/gdpr/export?email=john@example.com",
+            "/api/admin/search?q=boiler",
+            "/api/admin/tenants",
+        ] {
+            let request = Request::builder()
+                .uri(uri)
+                .header(header::HOST, "localhost")
+                .body(Body::empty())
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_host_not_found() {
+        let app = routes(_dev_utils::init_test().await);
+        let request = |host: &str| {
+            Request::builder()
+                .uri("/api/site")
+                .header(header::HOST, host)
+                .header("x-forwarded-host", "localhost")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let unknown = app
+            .clone()
+            .oneshot(request("unknown.example.com"))
+            .await
+            .unwrap();
+        let default = app.oneshot(request("localhost")).await.unwrap();
+
+        // The forwarded host is ignored: the request isn't from a proxy
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+        assert_eq!(default.status(), StatusCode::OK);
+    }
+}
+
+// endregion: --- Tests
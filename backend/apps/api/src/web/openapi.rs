@@ -2,25 +2,273 @@
 //!
 //! Provides Swagger UI and OpenAPI spec generation.
 
+use crate::web::handlers::attachment::{AttachmentUpload, AttachmentView};
+use crate::web::handlers::document::DocumentEmailResult;
+use crate::web::handlers::invoice::{CreditNoteRequest, GenerateInvoiceRequest};
+use crate::web::handlers::landing_page::IntroRequest;
+use crate::web::handlers::technician::AssignRequest;
+use crate::web::handlers::time_entry::ClockRequest;
+use lib_core::document::link::{AttachmentVariant, LinkedDocument, SignedLink};
+use lib_core::model::area::{Area, AreaForCreate, AreaForUpdate};
+use lib_core::model::attachment::Attachment;
+use lib_core::model::blog_post::{BlogPostForCreate, BlogPostForUpdate, BlogPostRecord};
+use lib_core::model::booking::{Booking, BookingForUpdate};
 use lib_core::model::contact::ContactForCreate;
+use lib_core::model::gdpr::{ErasureReport, SubjectAccessExport};
+use lib_core::model::invoice::{Invoice, InvoicePaymentForCreate};
+use lib_core::model::landing_page::ServiceAreaIntro;
+use lib_core::model::margin::{JobMargin, MarginReport, ServiceMargin};
+use lib_core::model::material::{
+    BookingMaterial, BookingMaterialForCreate, Material, MaterialForCreate, MaterialForUpdate,
+};
+use lib_core::model::onboarding::{OnboardingDraft, OnboardingForPublish};
+use lib_core::model::pricing_rule::PricingRuleForCreate;
+use lib_core::model::promo_code::{PromoCode, PromoCodeForCreate};
+use lib_core::model::quote::{Quote, QuoteForUpdate, QuoteOption, QuoteSelection};
+use lib_core::model::quote_acceptance::QuoteAcceptance;
+use lib_core::model::quote_revision::{ItemChange, QuoteChanges, QuoteRevision, TotalChange};
+use lib_core::model::quote_template::{
+    QuoteFromTemplate, QuoteTemplate, QuoteTemplateForCreate, QuoteTemplateForUpdate, TemplateItem,
+    TemplateVariable,
+};
+use lib_core::model::search::{SearchHit, SearchHitKind};
+use lib_core::model::service::{Service, ServiceForCreate, ServiceForUpdate};
+use lib_core::model::technician::{
+    AreaMatch, BookingAssignment, ScheduleConflict, Technician, TechnicianForCreate,
+    TechnicianForUpdate, TechnicianSuggestion,
+};
+use lib_core::model::tenant::{Tenant, TenantForCreate, TenantForUpdate};
+use lib_core::model::time_entry::{TimeEntry, Timesheet, TimesheetDay, TimesheetEntry};
+use lib_core::model::timeline::{TimelineEvent, TimelineEventKind};
+use lib_core::pricing::{Discount, VatBand};
+use shared::{
+    AreaPage, AreaService, BlogPost, Coverage, OnboardingForm, OnboardingReceipt,
+    OnboardingService, OpeningHours, PageLink, PricingCatalog, PricingRule, RuleAdjustment,
+    RuleCondition, ServiceAreaPage, ServiceRate, SiteConfig, SitemapUrl, Testimonial, TocEntry,
+};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
+        crate::web::handlers::attachment::upload_booking_attachment_handler,
+        crate::web::handlers::attachment::upload_quote_attachment_handler,
+        crate::web::handlers::attachment::list_booking_attachments_handler,
+        crate::web::handlers::attachment::list_quote_attachments_handler,
+        crate::web::handlers::attachment::delete_attachment_handler,
+        crate::web::handlers::attachment::public_attachment_handler,
+        crate::web::handlers::blog::list_posts_handler,
+        crate::web::handlers::blog::get_post_handler,
+        crate::web::handlers::blog::create_post_handler,
+        crate::web::handlers::blog::list_admin_posts_handler,
+        crate::web::handlers::blog::get_admin_post_handler,
+        crate::web::handlers::blog::update_post_handler,
+        crate::web::handlers::blog::delete_post_handler,
         crate::web::handlers::contact::api_contact_handler,
+        crate::web::handlers::coverage::coverage_handler,
+        crate::web::handlers::customer::customer_timeline_handler,
+        crate::web::handlers::document::admin_quote_pdf_handler,
+        crate::web::handlers::document::admin_invoice_pdf_handler,
+        crate::web::handlers::document::quote_pdf_link_handler,
+        crate::web::handlers::document::invoice_pdf_link_handler,
+        crate::web::handlers::document::public_document_pdf_handler,
+        crate::web::handlers::document::email_quote_handler,
+        crate::web::handlers::document::email_invoice_handler,
+        crate::web::handlers::gdpr::gdpr_export_handler,
+        crate::web::handlers::gdpr::gdpr_erase_handler,
+        crate::web::handlers::invoice::generate_invoice_handler,
+        crate::web::handlers::invoice::list_invoices_handler,
+        crate::web::handlers::invoice::get_invoice_handler,
+        crate::web::handlers::invoice::issue_invoice_handler,
+        crate::web::handlers::invoice::record_invoice_payment_handler,
+        crate::web::handlers::invoice::create_credit_note_handler,
+        crate::web::handlers::invoice::void_invoice_handler,
+        crate::web::handlers::landing_page::list_areas_handler,
+        crate::web::handlers::landing_page::area_page_handler,
+        crate::web::handlers::landing_page::service_area_page_handler,
+        crate::web::handlers::landing_page::landing_paths_handler,
+        crate::web::handlers::landing_page::create_area_handler,
+        crate::web::handlers::landing_page::list_admin_areas_handler,
+        crate::web::handlers::landing_page::get_area_handler,
+        crate::web::handlers::landing_page::update_area_handler,
+        crate::web::handlers::landing_page::delete_area_handler,
+        crate::web::handlers::landing_page::create_service_handler,
+        crate::web::handlers::landing_page::list_admin_services_handler,
+        crate::web::handlers::landing_page::get_service_handler,
+        crate::web::handlers::landing_page::update_service_handler,
+        crate::web::handlers::landing_page::delete_service_handler,
+        crate::web::handlers::landing_page::list_intros_handler,
+        crate::web::handlers::landing_page::set_intro_handler,
+        crate::web::handlers::landing_page::remove_intro_handler,
+        crate::web::handlers::material::create_material_handler,
+        crate::web::handlers::material::list_materials_handler,
+        crate::web::handlers::material::update_material_handler,
+        crate::web::handlers::material::delete_material_handler,
+        crate::web::handlers::material::add_booking_material_handler,
+        crate::web::handlers::material::list_booking_materials_handler,
+        crate::web::handlers::material::remove_booking_material_handler,
+        crate::web::handlers::onboarding::submit_onboarding_handler,
+        crate::web::handlers::onboarding::list_onboarding_handler,
+        crate::web::handlers::onboarding::get_onboarding_handler,
+        crate::web::handlers::onboarding::update_onboarding_handler,
+        crate::web::handlers::onboarding::publish_onboarding_handler,
+        crate::web::handlers::pricing_rule::get_pricing_catalog_handler,
+        crate::web::handlers::pricing_rule::save_service_rate_handler,
+        crate::web::handlers::pricing_rule::create_pricing_rule_handler,
+        crate::web::handlers::pricing_rule::update_pricing_rule_handler,
+        crate::web::handlers::pricing_rule::delete_pricing_rule_handler,
+        crate::web::handlers::promo_code::create_promo_code_handler,
+        crate::web::handlers::promo_code::list_promo_codes_handler,
+        crate::web::handlers::promo_code::deactivate_promo_code_handler,
+        crate::web::handlers::quote_revision::update_quote_handler,
+        crate::web::handlers::quote_revision::list_quote_revisions_handler,
+        crate::web::handlers::quote_revision::get_quote_acceptance_handler,
+        crate::web::handlers::quote_template::create_quote_template_handler,
+        crate::web::handlers::quote_template::list_quote_templates_handler,
+        crate::web::handlers::quote_template::get_quote_template_handler,
+        crate::web::handlers::quote_template::update_quote_template_handler,
+        crate::web::handlers::quote_template::delete_quote_template_handler,
+        crate::web::handlers::quote_template::create_quote_from_template_handler,
+        crate::web::handlers::report::margin_report_handler,
+        crate::web::handlers::route_plan::route_handler,
+        crate::web::handlers::search::admin_search_handler,
         crate::web::handlers::static_content::version_handler,
+        crate::web::handlers::booking::get_booking_handler,
+        crate::web::handlers::booking::update_booking_handler,
+        crate::web::handlers::technician::create_technician_handler,
+        crate::web::handlers::technician::list_technicians_handler,
+        crate::web::handlers::technician::get_technician_handler,
+        crate::web::handlers::technician::update_technician_handler,
+        crate::web::handlers::technician::delete_technician_handler,
+        crate::web::handlers::technician::list_technician_bookings_handler,
+        crate::web::handlers::technician::assign_technicians_handler,
+        crate::web::handlers::technician::list_assignments_handler,
+        crate::web::handlers::technician::unassign_technician_handler,
+        crate::web::handlers::technician::suggest_technicians_handler,
+        crate::web::handlers::tenant::site_handler,
+        crate::web::handlers::tenant::list_tenants_handler,
+        crate::web::handlers::tenant::create_tenant_handler,
+        crate::web::handlers::tenant::get_tenant_handler,
+        crate::web::handlers::tenant::update_tenant_handler,
+        crate::web::handlers::time_entry::start_time_handler,
+        crate::web::handlers::time_entry::pause_time_handler,
+        crate::web::handlers::time_entry::resume_time_handler,
+        crate::web::handlers::time_entry::stop_time_handler,
+        crate::web::handlers::time_entry::list_time_entries_handler,
+        crate::web::handlers::time_entry::timesheet_handler,
+        crate::web::handlers::time_entry::timesheet_csv_handler,
         crate::web::routes_health::api_health_handler
     ),
     components(
         schemas(
-            ContactForCreate
+            Area,
+            AreaForCreate,
+            AreaForUpdate,
+            AreaMatch,
+            AreaPage,
+            AreaService,
+            AssignRequest,
+            Attachment,
+            AttachmentUpload,
+            AttachmentVariant,
+            AttachmentView,
+            BlogPost,
+            BlogPostForCreate,
+            BlogPostForUpdate,
+            BlogPostRecord,
+            Booking,
+            BookingAssignment,
+            BookingForUpdate,
+            BookingMaterial,
+            BookingMaterialForCreate,
+            ClockRequest,
+            ContactForCreate,
+            Coverage,
+            CreditNoteRequest,
+            Discount,
+            DocumentEmailResult,
+            ErasureReport,
+            GenerateInvoiceRequest,
+            IntroRequest,
+            Invoice,
+            InvoicePaymentForCreate,
+            ItemChange,
+            JobMargin,
+            LinkedDocument,
+            MarginReport,
+            Material,
+            MaterialForCreate,
+            MaterialForUpdate,
+            OnboardingDraft,
+            OnboardingForPublish,
+            OnboardingForm,
+            OnboardingReceipt,
+            OnboardingService,
+            OpeningHours,
+            PageLink,
+            PricingCatalog,
+            PricingRule,
+            PricingRuleForCreate,
+            PromoCode,
+            PromoCodeForCreate,
+            Quote,
+            QuoteAcceptance,
+            QuoteChanges,
+            QuoteForUpdate,
+            QuoteFromTemplate,
+            QuoteOption,
+            QuoteRevision,
+            QuoteSelection,
+            QuoteTemplate,
+            QuoteTemplateForCreate,
+            QuoteTemplateForUpdate,
+            RuleAdjustment,
+            RuleCondition,
+            ScheduleConflict,
+            SearchHit,
+            SearchHitKind,
+            Service,
+            ServiceAreaIntro,
+            ServiceAreaPage,
+            ServiceForCreate,
+            ServiceForUpdate,
+            ServiceMargin,
+            ServiceRate,
+            SignedLink,
+            SiteConfig,
+            SitemapUrl,
+            SubjectAccessExport,
+            Technician,
+            TechnicianForCreate,
+            TechnicianForUpdate,
+            TechnicianSuggestion,
+            TemplateItem,
+            TemplateVariable,
+            Tenant,
+            TenantForCreate,
+            TenantForUpdate,
+            Testimonial,
+            TocEntry,
+            TimeEntry,
+            TimelineEvent,
+            TimelineEventKind,
+            Timesheet,
+            TimesheetDay,
+            TimesheetEntry,
+            TotalChange,
+            VatBand
         )
     ),
     tags(
+        (name = "admin", description = "Admin back-office endpoints"),
         (name = "contact", description = "Contact form endpoints"),
-        (name = "health", description = "Health check endpoints")
+        (name = "coverage", description = "Service coverage by postcode"),
+        (name = "documents", description = "Signed public document and attachment downloads"),
+        (name = "health", description = "Health check endpoints"),
+        (name = "onboarding", description = "New client onboarding wizard"),
+        (name = "pages", description = "Area and service landing pages, and the blog"),
+        (name = "site", description = "Business and branding for the request's host")
     )
 )]
 #[allow(dead_code)]
@@ -0,0 +1,44 @@
+//! Attachment routes.
+//!
+//! Admin uploads, listings and deletion of booking and quote attachments,
+//! plus the public signed-link download.
+
+use crate::web::handlers::attachment::{
+    delete_attachment_handler, list_booking_attachments_handler, list_quote_attachments_handler,
+    public_attachment_handler, upload_booking_attachment_handler, upload_quote_attachment_handler,
+};
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{delete, get};
+use axum::Router;
+use lib_core::core_config;
+use lib_core::model::ModelManager;
+
+/// Room for the multipart boundaries and the other form fields.
+const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+
+/// Creates the attachment routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    let uploads = Router::new()
+        .route(
+            "/admin/bookings/{id}/attachments",
+            get(list_booking_attachments_handler).post(upload_booking_attachment_handler),
+        )
+        .route(
+            "/admin/quotes/{id}/attachments",
+            get(list_quote_attachments_handler).post(upload_quote_attachment_handler),
+        )
+        // Oversized uploads are refused while streaming, before they are buffered
+        .layer(DefaultBodyLimit::max(
+            core_config().ATTACHMENT_MAX_BYTES + MULTIPART_OVERHEAD_BYTES,
+        ));
+
+    Router::new()
+        .merge(uploads)
+        .route("/admin/attachments/{id}", delete(delete_attachment_handler))
+        // Public, authorised by the link signature
+        .route(
+            "/attachments/{id}/{variant}",
+            get(public_attachment_handler),
+        )
+        .with_state(mm)
+}
@@ -0,0 +1,27 @@
+//! Blog routes.
+
+use crate::web::handlers::blog::{
+    create_post_handler, delete_post_handler, get_admin_post_handler, get_post_handler,
+    list_admin_posts_handler, list_posts_handler, update_post_handler,
+};
+use axum::routing::get;
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the blog routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/blog", get(list_posts_handler))
+        .route("/blog/{slug}", get(get_post_handler))
+        .route(
+            "/admin/blog",
+            get(list_admin_posts_handler).post(create_post_handler),
+        )
+        .route(
+            "/admin/blog/{id}",
+            get(get_admin_post_handler)
+                .patch(update_post_handler)
+                .delete(delete_post_handler),
+        )
+        .with_state(mm)
+}
@@ -0,0 +1,13 @@
+//! Service coverage routes.
+
+use crate::web::handlers::coverage::coverage_handler;
+use axum::routing::get;
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the service coverage routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/coverage", get(coverage_handler))
+        .with_state(mm)
+}
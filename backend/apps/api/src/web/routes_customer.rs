@@ -0,0 +1,18 @@
+//! Admin customer routes.
+//!
+//! Customer activity timeline for the admin back office.
+
+use crate::web::handlers::customer::customer_timeline_handler;
+use axum::routing::get;
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin customer routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route(
+            "/admin/customers/{id}/timeline",
+            get(customer_timeline_handler),
+        )
+        .with_state(mm)
+}
@@ -0,0 +1,33 @@
+//! Document routes.
+//!
+//! Admin PDF downloads and emails for quotes and invoices, plus the public
+//! signed-link download customers use.
+
+use crate::web::handlers::document::{
+    admin_invoice_pdf_handler, admin_quote_pdf_handler, email_invoice_handler, email_quote_handler,
+    invoice_pdf_link_handler, public_document_pdf_handler, quote_pdf_link_handler,
+};
+use axum::routing::{get, post};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the document routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        // Admin
+        .route("/admin/quotes/{id}/pdf", get(admin_quote_pdf_handler))
+        .route("/admin/quotes/{id}/pdf-link", post(quote_pdf_link_handler))
+        .route("/admin/quotes/{id}/email", post(email_quote_handler))
+        .route("/admin/invoices/{id}/pdf", get(admin_invoice_pdf_handler))
+        .route(
+            "/admin/invoices/{id}/pdf-link",
+            post(invoice_pdf_link_handler),
+        )
+        .route("/admin/invoices/{id}/email", post(email_invoice_handler))
+        // Public, authorised by the link signature
+        .route(
+            "/documents/{document}/{id}/pdf",
+            get(public_document_pdf_handler),
+        )
+        .with_state(mm)
+}
@@ -0,0 +1,16 @@
+//! Admin GDPR routes.
+//!
+//! Subject access exports and right-to-erasure tooling.
+
+use crate::web::handlers::gdpr::{gdpr_erase_handler, gdpr_export_handler};
+use axum::routing::{get, post};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin GDPR routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/admin/gdpr/export", get(gdpr_export_handler))
+        .route("/admin/gdpr/erase", post(gdpr_erase_handler))
+        .with_state(mm)
+}
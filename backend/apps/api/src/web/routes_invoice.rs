@@ -0,0 +1,34 @@
+//! Admin invoice routes.
+//!
+//! Invoice generation, issuing, payments and credit notes.
+
+use crate::web::handlers::invoice::{
+    create_credit_note_handler, generate_invoice_handler, get_invoice_handler,
+    issue_invoice_handler, list_invoices_handler, record_invoice_payment_handler,
+    void_invoice_handler,
+};
+use axum::routing::{get, post};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin invoice routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route(
+            "/admin/bookings/{id}/invoice",
+            post(generate_invoice_handler),
+        )
+        .route("/admin/invoices", get(list_invoices_handler))
+        .route("/admin/invoices/{id}", get(get_invoice_handler))
+        .route("/admin/invoices/{id}/issue", post(issue_invoice_handler))
+        .route(
+            "/admin/invoices/{id}/payments",
+            post(record_invoice_payment_handler),
+        )
+        .route(
+            "/admin/invoices/{id}/credit-notes",
+            post(create_credit_note_handler),
+        )
+        .route("/admin/invoices/{id}/void", post(void_invoice_handler))
+        .with_state(mm)
+}
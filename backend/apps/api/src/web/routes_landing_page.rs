@@ -0,0 +1,47 @@
+//! Area and service landing page routes.
+
+use crate::web::handlers::landing_page::{
+    area_page_handler, create_area_handler, create_service_handler, delete_area_handler,
+    delete_service_handler, get_area_handler, get_service_handler, landing_paths_handler,
+    list_admin_areas_handler, list_admin_services_handler, list_areas_handler, list_intros_handler,
+    remove_intro_handler, service_area_page_handler, set_intro_handler, update_area_handler,
+    update_service_handler,
+};
+use axum::routing::{get, put};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the landing page routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/areas", get(list_areas_handler))
+        .route("/areas/{slug}", get(area_page_handler))
+        .route("/services/{service}/{town}", get(service_area_page_handler))
+        .route("/landing-pages", get(landing_paths_handler))
+        .route(
+            "/admin/areas",
+            get(list_admin_areas_handler).post(create_area_handler),
+        )
+        .route(
+            "/admin/areas/{id}",
+            get(get_area_handler)
+                .patch(update_area_handler)
+                .delete(delete_area_handler),
+        )
+        .route(
+            "/admin/services",
+            get(list_admin_services_handler).post(create_service_handler),
+        )
+        .route(
+            "/admin/services/{id}",
+            get(get_service_handler)
+                .patch(update_service_handler)
+                .delete(delete_service_handler),
+        )
+        .route(
+            "/admin/services/{id}/areas/{area_id}",
+            put(set_intro_handler).delete(remove_intro_handler),
+        )
+        .route("/admin/landing-intros", get(list_intros_handler))
+        .with_state(mm)
+}
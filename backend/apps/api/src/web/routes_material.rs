@@ -0,0 +1,32 @@
+//! Admin material routes.
+
+use crate::web::handlers::material::{
+    add_booking_material_handler, create_material_handler, delete_material_handler,
+    list_booking_materials_handler, list_materials_handler, remove_booking_material_handler,
+    update_material_handler,
+};
+use axum::routing::{delete, get, patch};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin material routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route(
+            "/admin/materials",
+            get(list_materials_handler).post(create_material_handler),
+        )
+        .route(
+            "/admin/materials/{id}",
+            patch(update_material_handler).delete(delete_material_handler),
+        )
+        .route(
+            "/admin/bookings/{id}/materials",
+            get(list_booking_materials_handler).post(add_booking_material_handler),
+        )
+        .route(
+            "/admin/bookings/{id}/materials/{line_id}",
+            delete(remove_booking_material_handler),
+        )
+        .with_state(mm)
+}
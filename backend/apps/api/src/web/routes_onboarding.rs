@@ -0,0 +1,25 @@
+//! Client onboarding routes.
+
+use crate::web::handlers::onboarding::{
+    get_onboarding_handler, list_onboarding_handler, publish_onboarding_handler,
+    submit_onboarding_handler, update_onboarding_handler,
+};
+use axum::routing::{get, post};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the onboarding wizard and review routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/onboarding", post(submit_onboarding_handler))
+        .route("/admin/onboarding", get(list_onboarding_handler))
+        .route(
+            "/admin/onboarding/{id}",
+            get(get_onboarding_handler).put(update_onboarding_handler),
+        )
+        .route(
+            "/admin/onboarding/{id}/publish",
+            post(publish_onboarding_handler),
+        )
+        .with_state(mm)
+}
@@ -0,0 +1,22 @@
+//! Admin pricing rule routes.
+
+use crate::web::handlers::pricing_rule::{
+    create_pricing_rule_handler, delete_pricing_rule_handler, get_pricing_catalog_handler,
+    save_service_rate_handler, update_pricing_rule_handler,
+};
+use axum::routing::{get, post, put};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin pricing rule routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/admin/pricing", get(get_pricing_catalog_handler))
+        .route("/admin/pricing/services", put(save_service_rate_handler))
+        .route("/admin/pricing/rules", post(create_pricing_rule_handler))
+        .route(
+            "/admin/pricing/rules/{id}",
+            put(update_pricing_rule_handler).delete(delete_pricing_rule_handler),
+        )
+        .with_state(mm)
+}
@@ -0,0 +1,22 @@
+//! Admin promo code routes.
+
+use crate::web::handlers::promo_code::{
+    create_promo_code_handler, deactivate_promo_code_handler, list_promo_codes_handler,
+};
+use axum::routing::{get, post};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin promo code routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route(
+            "/admin/promo-codes",
+            get(list_promo_codes_handler).post(create_promo_code_handler),
+        )
+        .route(
+            "/admin/promo-codes/{id}/deactivate",
+            post(deactivate_promo_code_handler),
+        )
+        .with_state(mm)
+}
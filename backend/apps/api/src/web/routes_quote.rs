@@ -19,6 +19,7 @@ pub fn routes(mm: ModelManager) -> Router {
         // Templates
         .route("/quotes/templates", get(quote::get_quote_templates))
         // Public instant quote
+        .route("/quote/catalog", get(quote::get_pricing_catalog))
         .route("/quote/instant", post(quote::get_instant_quote))
         .with_state(mm)
 }
@@ -0,0 +1,23 @@
+//! Admin quote editing and revision history routes.
+
+use crate::web::handlers::quote_revision::{
+    get_quote_acceptance_handler, list_quote_revisions_handler, update_quote_handler,
+};
+use axum::routing::{get, patch};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin quote revision routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/admin/quotes/{id}", patch(update_quote_handler))
+        .route(
+            "/admin/quotes/{id}/revisions",
+            get(list_quote_revisions_handler),
+        )
+        .route(
+            "/admin/quotes/{id}/acceptance",
+            get(get_quote_acceptance_handler),
+        )
+        .with_state(mm)
+}
@@ -0,0 +1,30 @@
+//! Admin quote template routes.
+
+use crate::web::handlers::quote_template::{
+    create_quote_from_template_handler, create_quote_template_handler,
+    delete_quote_template_handler, get_quote_template_handler, list_quote_templates_handler,
+    update_quote_template_handler,
+};
+use axum::routing::{get, post};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin quote template routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route(
+            "/admin/quote-templates",
+            get(list_quote_templates_handler).post(create_quote_template_handler),
+        )
+        .route(
+            "/admin/quote-templates/{id}",
+            get(get_quote_template_handler)
+                .patch(update_quote_template_handler)
+                .delete(delete_quote_template_handler),
+        )
+        .route(
+            "/admin/quote-templates/{id}/quotes",
+            post(create_quote_from_template_handler),
+        )
+        .with_state(mm)
+}
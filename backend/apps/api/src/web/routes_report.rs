@@ -0,0 +1,13 @@
+//! Admin report routes.
+
+use crate::web::handlers::report::margin_report_handler;
+use axum::routing::get;
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin report routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/admin/reports/margins", get(margin_report_handler))
+        .with_state(mm)
+}
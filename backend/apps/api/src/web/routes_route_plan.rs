@@ -0,0 +1,13 @@
+//! Admin route planning routes.
+
+use crate::web::handlers::route_plan::route_handler;
+use axum::routing::get;
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin route planning routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/admin/route", get(route_handler))
+        .with_state(mm)
+}
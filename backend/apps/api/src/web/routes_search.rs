@@ -0,0 +1,15 @@
+//! Admin search routes.
+//!
+//! Unified search across customers, bookings and quotes.
+
+use crate::web::handlers::search::admin_search_handler;
+use axum::routing::get;
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin search routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/admin/search", get(admin_search_handler))
+        .with_state(mm)
+}
@@ -1,13 +1,28 @@
-//! SEO routes for robots.txt and sitemap.xml.
+//! SEO routes for robots.txt and the blog feeds.
 //!
-//! Provides static SEO files for search engine crawlers.
+//! Provides robots.txt for search engine crawlers. The RSS and Atom feeds
+//! of the blog sit beside it at the paths the Leptos site links to.
 
-use crate::web::handlers::seo::{robots_txt_handler, sitemap_xml_handler};
+use crate::web::handlers::feed::{
+    atom_handler, category_atom_handler, category_rss_handler, rss_handler,
+};
+use crate::web::handlers::seo::robots_txt_handler;
 use axum::routing::get;
 use axum::Router;
+use lib_core::model::ModelManager;
 
-pub fn routes() -> Router {
+pub fn routes(mm: ModelManager) -> Router {
     Router::new()
         .route("/robots.txt", get(robots_txt_handler))
-        .route("/sitemap.xml", get(sitemap_xml_handler))
+        .route("/blog/rss.xml", get(rss_handler))
+        .route("/blog/atom.xml", get(atom_handler))
+        .route(
+            "/blog/category/{category}/rss.xml",
+            get(category_rss_handler),
+        )
+        .route(
+            "/blog/category/{category}/atom.xml",
+            get(category_atom_handler),
+        )
+        .with_state(mm)
 }
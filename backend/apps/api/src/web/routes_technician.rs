@@ -0,0 +1,48 @@
+//! Admin technician and booking assignment routes.
+
+use crate::web::handlers::booking::{get_booking_handler, update_booking_handler};
+use crate::web::handlers::technician::{
+    assign_technicians_handler, create_technician_handler, delete_technician_handler,
+    get_technician_handler, list_assignments_handler, list_technician_bookings_handler,
+    list_technicians_handler, suggest_technicians_handler, unassign_technician_handler,
+    update_technician_handler,
+};
+use axum::routing::{delete, get};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin technician routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route(
+            "/admin/technicians",
+            get(list_technicians_handler).post(create_technician_handler),
+        )
+        .route(
+            "/admin/technicians/{id}",
+            get(get_technician_handler)
+                .patch(update_technician_handler)
+                .delete(delete_technician_handler),
+        )
+        .route(
+            "/admin/technicians/{id}/bookings",
+            get(list_technician_bookings_handler),
+        )
+        .route(
+            "/admin/bookings/{id}",
+            get(get_booking_handler).patch(update_booking_handler),
+        )
+        .route(
+            "/admin/bookings/{id}/assignments",
+            get(list_assignments_handler).post(assign_technicians_handler),
+        )
+        .route(
+            "/admin/bookings/{id}/assignments/{user_id}",
+            delete(unassign_technician_handler),
+        )
+        .route(
+            "/admin/bookings/{id}/technician-suggestions",
+            get(suggest_technicians_handler),
+        )
+        .with_state(mm)
+}
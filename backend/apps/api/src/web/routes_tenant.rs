@@ -0,0 +1,24 @@
+//! Site configuration and tenant administration routes.
+
+use crate::web::handlers::tenant::{
+    create_tenant_handler, get_tenant_handler, list_tenants_handler, site_handler,
+    update_tenant_handler,
+};
+use axum::routing::get;
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the site and tenant routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/site", get(site_handler))
+        .route(
+            "/admin/tenants",
+            get(list_tenants_handler).post(create_tenant_handler),
+        )
+        .route(
+            "/admin/tenants/{id}",
+            get(get_tenant_handler).patch(update_tenant_handler),
+        )
+        .with_state(mm)
+}
@@ -0,0 +1,31 @@
+//! Admin time tracking routes.
+
+use crate::web::handlers::time_entry::{
+    list_time_entries_handler, pause_time_handler, resume_time_handler, start_time_handler,
+    stop_time_handler, timesheet_csv_handler, timesheet_handler,
+};
+use axum::routing::{get, post};
+use axum::Router;
+use lib_core::model::ModelManager;
+
+/// Creates the admin time tracking routes for the API.
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/admin/bookings/{id}/time/start", post(start_time_handler))
+        .route("/admin/bookings/{id}/time/pause", post(pause_time_handler))
+        .route(
+            "/admin/bookings/{id}/time/resume",
+            post(resume_time_handler),
+        )
+        .route("/admin/bookings/{id}/time/stop", post(stop_time_handler))
+        .route(
+            "/admin/bookings/{id}/time-entries",
+            get(list_time_entries_handler),
+        )
+        .route("/admin/users/{id}/timesheet", get(timesheet_handler))
+        .route(
+            "/admin/users/{id}/timesheet.csv",
+            get(timesheet_csv_handler),
+        )
+        .with_state(mm)
+}
@@ -0,0 +1,179 @@
+//! Signed, expiring admin access tokens.
+//!
+//! Admin endpoints take a bearer token naming a user, the tenant they
+//! administer and an expiry, with an HMAC-SHA256 over all three keyed with
+//! `ADMIN_TOKEN_KEY` and the user's `token_salt`. Changing the salt revokes
+//! every token the user holds.
+//!
+//! ```text
+//! Authorization: Bearer 12.1.1792339200.<b64u>
+//! ```
+//!
+//! Tokens are issued from the command line with
+//! `api --issue-admin-token <USERNAME>`.
+
+use crate::core_config;
+use hmac::{Hmac, Mac};
+use lib_utils::b64::{b64u_decode, b64u_encode};
+use sha2::Sha256;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Why an admin token was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    /// Not of the form `user.tenant.expires.signature`
+    Malformed,
+    /// Signature does not match
+    InvalidSignature,
+    /// Token is past its expiry time
+    Expired,
+}
+
+/// The claims of an admin token, before its signature is checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminToken {
+    /// User the token was issued to
+    pub user_id: i32,
+    /// Tenant the user administers
+    pub tenant_id: i32,
+    /// Expiry as a Unix timestamp
+    pub expires: i64,
+    signature: String,
+}
+
+impl AdminToken {
+    /// Splits a token into its claims.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TokenError::Malformed`] if the token isn't four
+    /// dot-separated parts with numeric claims.
+    pub fn parse(token: &str) -> Result<Self, TokenError> {
+        let mut parts = token.trim().splitn(4, '.');
+        let mut claim = || parts.next().ok_or(TokenError::Malformed);
+        let user_id = claim()?.parse().map_err(|_| TokenError::Malformed)?;
+        let tenant_id = claim()?.parse().map_err(|_| TokenError::Malformed)?;
+        let expires = claim()?.parse().map_err(|_| TokenError::Malformed)?;
+        let signature = claim()?.to_string();
+
+        Ok(Self {
+            user_id,
+            tenant_id,
+            expires,
+            signature,
+        })
+    }
+
+    /// Checks the token's signature against the user's current
+    /// `token_salt` and its expiry against the Unix time `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TokenError::InvalidSignature`] for a tampered, foreign or
+    /// revoked token and [`TokenError::Expired`] for a genuine token past
+    /// its expiry.
+    pub fn verify(&self, key: &[u8], token_salt: Uuid, now: i64) -> Result<(), TokenError> {
+        let signature = b64u_decode(&self.signature).map_err(|_| TokenError::InvalidSignature)?;
+        mac(key, self.user_id, self.tenant_id, self.expires, token_salt)
+            .verify_slice(&signature)
+            .map_err(|_| TokenError::InvalidSignature)?;
+
+        if now > self.expires {
+            return Err(TokenError::Expired);
+        }
+
+        Ok(())
+    }
+}
+
+/// Issues a token for `user_id` to administer `tenant_id` until the Unix
+/// timestamp `expires`.
+#[must_use]
+pub fn issue(key: &[u8], user_id: i32, tenant_id: i32, token_salt: Uuid, expires: i64) -> String {
+    let signature = mac(key, user_id, tenant_id, expires, token_salt)
+        .finalize()
+        .into_bytes();
+
+    format!("{user_id}.{tenant_id}.{expires}.{}", b64u_encode(signature))
+}
+
+/// Key for signing admin tokens, if `ADMIN_TOKEN_KEY` is set.
+///
+/// There's no fallback: tokens are issued by another process, so without
+/// a configured key no token can be checked and admin endpoints stay shut.
+#[must_use]
+pub fn token_key() -> Option<&'static [u8]> {
+    core_config().ADMIN_TOKEN_KEY.as_deref().map(str::as_bytes)
+}
+
+fn mac(key: &[u8], user_id: i32, tenant_id: i32, expires: i64, token_salt: Uuid) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(format!("admin:{user_id}:{tenant_id}:{expires}:{token_salt}").as_bytes());
+    mac
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test-admin-key";
+
+    fn salt() -> Uuid {
+        Uuid::from_u128(0x5eed)
+    }
+
+    #[test]
+    fn test_issue_verify_roundtrip() {
+        let token = AdminToken::parse(&issue(KEY, 12, 1, salt(), 2_000)).unwrap();
+
+        assert_eq!(
+            (token.user_id, token.tenant_id, token.expires),
+            (12, 1, 2_000)
+        );
+        assert_eq!(token.verify(KEY, salt(), 1_000), Ok(()));
+        assert_eq!(token.verify(KEY, salt(), 2_001), Err(TokenError::Expired));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let issued = issue(KEY, 12, 1, salt(), 2_000);
+        let signature = issued.rsplit('.').next().unwrap();
+
+        for forged in [
+            format!("13.1.2000.{signature}"),
+            format!("12.2.2000.{signature}"),
+            format!("12.1.9000.{signature}"),
+            "12.1.2000.not base64!".to_string(),
+        ] {
+            assert_eq!(
+                AdminToken::parse(&forged)
+                    .unwrap()
+                    .verify(KEY, salt(), 1_000),
+                Err(TokenError::InvalidSignature)
+            );
+        }
+        let token = AdminToken::parse(&issued).unwrap();
+        // A rotated salt revokes the token, as does another deployment's key
+        assert_eq!(
+            token.verify(KEY, Uuid::from_u128(1), 1_000),
+            Err(TokenError::InvalidSignature)
+        );
+        assert_eq!(
+            token.verify(b"other-key", salt(), 1_000),
+            Err(TokenError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        for token in ["", "12.1.2000", "admin.1.2000.sig", "12.1.soon.sig"] {
+            assert_eq!(AdminToken::parse(token), Err(TokenError::Malformed));
+        }
+    }
+}
+
+// endregion: --- Tests
@@ -2,7 +2,8 @@
 //!
 //! Provides centralized configuration loaded from environment variables.
 
-use lib_utils::envs::get_env;
+use lib_utils::envs::{get_env, get_env_opt, get_env_or, get_env_parse};
+use std::net::IpAddr;
 use std::sync::OnceLock;
 
 pub fn core_config() -> &'static CoreConfig {
@@ -15,6 +16,97 @@ pub fn core_config() -> &'static CoreConfig {
 pub struct CoreConfig {
     // -- Db
     pub DB_URL: String,
+
+    // -- GDPR
+    /// Days to keep IP addresses and user agents on contact submissions
+    pub CONTACT_METADATA_RETENTION_DAYS: u32,
+
+    // -- Invoicing
+    /// Whether the business is VAT registered (charges VAT on invoices)
+    pub VAT_REGISTERED: bool,
+    /// VAT rate in basis points (2000 = 20%)
+    pub VAT_RATE_BP: i32,
+    /// Days between issue and due date
+    pub INVOICE_PAYMENT_TERMS_DAYS: i32,
+    /// Days between overdue reminders
+    pub INVOICE_REMINDER_INTERVAL_DAYS: i32,
+
+    // -- Documents
+    /// Trading name printed on quotes and invoices
+    pub BUSINESS_NAME: String,
+    /// Postal address lines (`BUSINESS_ADDRESS` is `|`-separated)
+    pub BUSINESS_ADDRESS: Vec<String>,
+    /// Contact phone number printed on documents
+    pub BUSINESS_PHONE: Option<String>,
+    /// Contact email printed on documents
+    pub BUSINESS_EMAIL: Option<String>,
+    /// Path to a JPEG logo for document headers
+    pub BUSINESS_LOGO_PATH: Option<String>,
+    /// VAT registration number printed on documents
+    pub VAT_NUMBER: Option<String>,
+    /// Terms printed on quotes
+    pub QUOTE_TERMS: String,
+    /// Terms printed on invoices and credit notes
+    pub INVOICE_TERMS: String,
+    /// Secret for signing public document links
+    pub DOCUMENT_SIGNING_KEY: Option<String>,
+    /// Hours a signed document link stays valid
+    pub DOCUMENT_LINK_TTL_HOURS: i64,
+    /// Public base URL used in links sent to customers
+    pub PUBLIC_BASE_URL: String,
+
+    // -- Admin
+    /// Secret for signing admin access tokens; admin endpoints refuse every
+    /// request without it
+    pub ADMIN_TOKEN_KEY: Option<String>,
+    /// Hours an issued admin token stays valid
+    pub ADMIN_TOKEN_TTL_HOURS: i64,
+
+    // -- Attachments
+    /// Where attachment bytes are kept: `local` or `s3`
+    pub STORAGE_BACKEND: String,
+    /// Directory for the local storage backend
+    pub STORAGE_LOCAL_PATH: String,
+    /// S3-compatible endpoint URL
+    pub S3_ENDPOINT: Option<String>,
+    /// Bucket for attachments
+    pub S3_BUCKET: Option<String>,
+    /// Signing region for the S3 endpoint
+    pub S3_REGION: String,
+    /// S3 access key ID
+    pub S3_ACCESS_KEY_ID: Option<String>,
+    /// S3 secret access key
+    pub S3_SECRET_ACCESS_KEY: Option<String>,
+    /// Largest accepted upload, in bytes
+    pub ATTACHMENT_MAX_BYTES: usize,
+    /// Minutes a signed attachment link stays valid
+    pub ATTACHMENT_LINK_TTL_MINUTES: i64,
+
+    // -- Reporting
+    /// What an hour of labour costs the business, in cents
+    pub LABOUR_COST_PER_HOUR_CENTS: i64,
+
+    // -- Coverage
+    /// Latitude of the point travel distances are measured from
+    pub SERVICE_CENTRE_LATITUDE: f64,
+    /// Longitude of the point travel distances are measured from
+    pub SERVICE_CENTRE_LONGITUDE: f64,
+    /// Outer edge of each travel band in km, nearest first
+    /// (`TRAVEL_BANDS_KM` is comma-separated); band 1 is pricing zone `1`
+    pub TRAVEL_BANDS_KM: Vec<f64>,
+
+    // -- Tenants
+    /// Hosts the default tenant serves besides its own domains
+    /// (`DEFAULT_TENANT_HOSTS` is comma-separated)
+    pub DEFAULT_TENANT_HOSTS: Vec<String>,
+    /// Peers whose `X-Forwarded-Host` and `X-Forwarded-For` are believed,
+    /// such as the site's SSR server (`TRUSTED_PROXIES` is comma-separated
+    /// IP addresses)
+    pub TRUSTED_PROXIES: Vec<IpAddr>,
+    /// Most tenant connection pools kept open at once
+    pub TENANT_POOL_MAX: usize,
+    /// Connections in each tenant's pool
+    pub TENANT_POOL_CONNECTIONS: u32,
 }
 
 impl CoreConfig {
@@ -22,6 +114,85 @@ impl CoreConfig {
         CoreConfig {
             // -- Db
             DB_URL: get_env("DATABASE_URL"),
+
+            // -- GDPR
+            CONTACT_METADATA_RETENTION_DAYS: get_env_parse("CONTACT_METADATA_RETENTION_DAYS")
+                .unwrap_or(90),
+
+            // -- Invoicing
+            VAT_REGISTERED: get_env_parse("VAT_REGISTERED").unwrap_or(false),
+            VAT_RATE_BP: get_env_parse("VAT_RATE_BP").unwrap_or(2000),
+            INVOICE_PAYMENT_TERMS_DAYS: get_env_parse("INVOICE_PAYMENT_TERMS_DAYS").unwrap_or(14),
+            INVOICE_REMINDER_INTERVAL_DAYS: get_env_parse("INVOICE_REMINDER_INTERVAL_DAYS")
+                .unwrap_or(7),
+
+            // -- Documents
+            BUSINESS_NAME: get_env_or("BUSINESS_NAME", "XF Tradesman"),
+            BUSINESS_ADDRESS: get_env_opt("BUSINESS_ADDRESS")
+                .map(|a| {
+                    a.split('|')
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            BUSINESS_PHONE: get_env_opt("BUSINESS_PHONE"),
+            BUSINESS_EMAIL: get_env_opt("BUSINESS_EMAIL"),
+            BUSINESS_LOGO_PATH: get_env_opt("BUSINESS_LOGO_PATH"),
+            VAT_NUMBER: get_env_opt("VAT_NUMBER"),
+            QUOTE_TERMS: get_env_or(
+                "QUOTE_TERMS",
+                "This quote is valid until the date shown. Prices cover the work described; \
+                 any additional work will be agreed with you before it starts.",
+            ),
+            INVOICE_TERMS: get_env_or(
+                "INVOICE_TERMS",
+                "Payment is due by the date shown. Please quote the invoice number as your \
+                 payment reference.",
+            ),
+            DOCUMENT_SIGNING_KEY: get_env_opt("DOCUMENT_SIGNING_KEY"),
+            DOCUMENT_LINK_TTL_HOURS: get_env_parse("DOCUMENT_LINK_TTL_HOURS").unwrap_or(24 * 30),
+            PUBLIC_BASE_URL: get_env_or("PUBLIC_BASE_URL", "https://xftradesman.com"),
+
+            // -- Admin
+            ADMIN_TOKEN_KEY: get_env_opt("ADMIN_TOKEN_KEY"),
+            ADMIN_TOKEN_TTL_HOURS: get_env_parse("ADMIN_TOKEN_TTL_HOURS").unwrap_or(24 * 30),
+
+            // -- Attachments
+            STORAGE_BACKEND: get_env_or("STORAGE_BACKEND", "local"),
+            STORAGE_LOCAL_PATH: get_env_or("STORAGE_LOCAL_PATH", "./data/attachments"),
+            S3_ENDPOINT: get_env_opt("S3_ENDPOINT"),
+            S3_BUCKET: get_env_opt("S3_BUCKET"),
+            S3_REGION: get_env_or("S3_REGION", "us-east-1"),
+            S3_ACCESS_KEY_ID: get_env_opt("S3_ACCESS_KEY_ID"),
+            S3_SECRET_ACCESS_KEY: get_env_opt("S3_SECRET_ACCESS_KEY"),
+            ATTACHMENT_MAX_BYTES: get_env_parse("ATTACHMENT_MAX_BYTES").unwrap_or(10 * 1024 * 1024),
+            ATTACHMENT_LINK_TTL_MINUTES: get_env_parse("ATTACHMENT_LINK_TTL_MINUTES").unwrap_or(60),
+
+            // -- Reporting
+            LABOUR_COST_PER_HOUR_CENTS: get_env_parse("LABOUR_COST_PER_HOUR_CENTS").unwrap_or(2500),
+
+            // -- Coverage
+            SERVICE_CENTRE_LATITUDE: get_env_parse("SERVICE_CENTRE_LATITUDE").unwrap_or(52.4081),
+            SERVICE_CENTRE_LONGITUDE: get_env_parse("SERVICE_CENTRE_LONGITUDE").unwrap_or(-1.5106),
+            TRAVEL_BANDS_KM: get_env_or("TRAVEL_BANDS_KM", "10,20,30")
+                .split(',')
+                .filter_map(|km| km.trim().parse().ok())
+                .collect(),
+
+            // -- Tenants
+            DEFAULT_TENANT_HOSTS: get_env_or("DEFAULT_TENANT_HOSTS", "localhost,127.0.0.1")
+                .split(',')
+                .map(|host| host.trim().to_ascii_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect(),
+            TRUSTED_PROXIES: get_env_or("TRUSTED_PROXIES", "127.0.0.1,::1")
+                .split(',')
+                .filter_map(|ip| ip.trim().parse().ok())
+                .collect(),
+            TENANT_POOL_MAX: get_env_parse("TENANT_POOL_MAX").unwrap_or(50),
+            TENANT_POOL_CONNECTIONS: get_env_parse("TENANT_POOL_CONNECTIONS").unwrap_or(5),
         }
     }
 }
@@ -0,0 +1,427 @@
+//! Markdown content pipeline for published pages.
+//!
+//! Blog posts and service descriptions are written in Markdown and stored as
+//! written; [`render`] turns them into HTML for the site. Besides
+//! CommonMark, authors get:
+//!
+//! - **Tables** - GitHub-style pipe tables
+//! - **Callouts** - a blockquote starting `[!NOTE]`, `[!TIP]`, `[!IMPORTANT]`,
+//!   `[!WARNING]` or `[!CAUTION]` renders as an `<aside class="callout ...">`
+//! - **Image captions** - an image on its own line with a title,
+//!   `![alt](src "Caption")`, renders as a `<figure>` with a `<figcaption>`
+//!
+//! Every heading gets an `id` anchor from its text, and the `##` and `###`
+//! headings make up the table of contents. `#` headings render as `<h2>`:
+//! the page title is the page's only `<h1>`.
+//!
+//! Content comes from the database, so the HTML is always passed through an
+//! allow-list sanitiser ([`sanitize`]): inline HTML in the Markdown is
+//! allowed, but anything outside the list (scripts, styles, event handlers,
+//! `javascript:` links) is dropped.
+
+use crate::text::slugify;
+use pulldown_cmark::{BlockQuoteKind, CowStr, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use shared::TocEntry;
+use std::collections::{HashMap, HashSet};
+
+/// Words read per minute, for [`Rendered::read_minutes`].
+pub const WORDS_PER_MINUTE: usize = 200;
+
+/// Tags the sanitiser keeps.
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "aside",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "em",
+    "figcaption",
+    "figure",
+    "h2",
+    "h3",
+    "h4",
+    "hr",
+    "img",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "strong",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// Callout kinds as `(class, title)`.
+const CALLOUTS: &[(&str, &str)] = &[
+    ("callout-note", "Note"),
+    ("callout-tip", "Tip"),
+    ("callout-important", "Important"),
+    ("callout-warning", "Warning"),
+    ("callout-caution", "Caution"),
+];
+
+/// Markdown rendered for the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendered {
+    /// Sanitised HTML
+    pub html: String,
+    /// The `##` and `###` headings, in order
+    pub toc: Vec<TocEntry>,
+    /// Words of text, for the reading time
+    pub words: usize,
+    /// URLs of the Markdown images, for the sitemap
+    pub images: Vec<String>,
+}
+
+impl Rendered {
+    /// Estimated reading time in whole minutes, at least one.
+    pub fn read_minutes(&self) -> u32 {
+        self.words.div_ceil(WORDS_PER_MINUTE).max(1) as u32
+    }
+}
+
+/// Renders Markdown to sanitised HTML, with heading anchors and a table of
+/// contents.
+///
+/// # Examples
+///
+/// ```
+/// use lib_core::content::render;
+///
+/// let page = render("## Getting started\n\nCall us <script>alert(1)</script>first.");
+///
+/// assert!(page.html.contains(r#"<h2 id="getting-started">"#));
+/// assert!(!page.html.contains("<script>"));
+/// assert_eq!(page.toc[0].id, "getting-started");
+/// ```
+#[must_use]
+pub fn render(markdown: &str) -> Rendered {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_GFM | Options::ENABLE_STRIKETHROUGH;
+    let events: Vec<Event> = Parser::new_ext(markdown, options).collect();
+
+    let images = images(&events);
+    let (events, toc) = anchor_headings(events);
+    let events = figures(callouts(events));
+
+    let mut html = String::with_capacity(markdown.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, events.into_iter());
+
+    let html = sanitize(&html);
+    let words = count_words(&html);
+
+    Rendered {
+        html,
+        toc,
+        words,
+        images,
+    }
+}
+
+/// Cleans HTML against the content allow-list.
+#[must_use]
+pub fn sanitize(html: &str) -> String {
+    let heading_attrs = HashSet::from(["id"]);
+    let tag_attributes = HashMap::from([
+        ("a", HashSet::from(["href", "title"])),
+        ("img", HashSet::from(["src", "alt", "title"])),
+        ("h2", heading_attrs.clone()),
+        ("h3", heading_attrs.clone()),
+        ("h4", heading_attrs),
+    ]);
+    let mut callout_classes: HashSet<&str> = CALLOUTS.iter().map(|(class, _)| *class).collect();
+    callout_classes.insert("callout");
+    let allowed_classes = HashMap::from([
+        ("aside", callout_classes),
+        ("p", HashSet::from(["callout-title"])),
+    ]);
+
+    ammonia::Builder::empty()
+        .tags(ALLOWED_TAGS.iter().copied().collect())
+        .tag_attributes(tag_attributes)
+        .generic_attributes(HashSet::new())
+        .allowed_classes(allowed_classes)
+        .url_schemes(HashSet::from(["http", "https", "mailto", "tel"]))
+        .link_rel(Some("noopener noreferrer"))
+        .strip_comments(true)
+        .clean(html)
+        .to_string()
+}
+
+// region:    --- Content Helpers
+
+/// Gives every heading a unique `id` from its text, demotes `#` to `##`,
+/// and collects the `##` and `###` headings.
+fn anchor_headings(mut events: Vec<Event>) -> (Vec<Event>, Vec<TocEntry>) {
+    let mut toc = Vec::new();
+    let mut used = HashSet::new();
+    let mut i = 0;
+
+    while i < events.len() {
+        let Event::Start(Tag::Heading { level, .. }) = events[i] else {
+            i += 1;
+            continue;
+        };
+        let level = level.max(HeadingLevel::H2);
+        let end = events[i..]
+            .iter()
+            .position(|e| matches!(e, Event::End(TagEnd::Heading(_))))
+            .map_or(events.len(), |offset| i + offset);
+        let title: String = events[i + 1..end].iter().filter_map(event_text).collect();
+
+        let base = match slugify(&title) {
+            slug if slug.is_empty() => "section".to_string(),
+            slug => slug,
+        };
+        let mut id = base.clone();
+        let mut n = 1;
+        while !used.insert(id.clone()) {
+            n += 1;
+            id = format!("{}-{}", base, n);
+        }
+
+        if matches!(level, HeadingLevel::H2 | HeadingLevel::H3) {
+            toc.push(TocEntry {
+                id: id.clone(),
+                title: title.trim().to_string(),
+                level: level as u8,
+            });
+        }
+        events[i] = Event::Start(Tag::Heading {
+            level,
+            id: Some(CowStr::from(id)),
+            classes: Vec::new(),
+            attrs: Vec::new(),
+        });
+        if let Some(event) = events.get_mut(end) {
+            *event = Event::End(TagEnd::Heading(level));
+        }
+        i = end + 1;
+    }
+
+    (events, toc)
+}
+
+/// The image URLs the sanitiser keeps: web and site-relative ones.
+fn images(events: &[Event]) -> Vec<String> {
+    let mut images: Vec<String> = Vec::new();
+    for event in events {
+        let Event::Start(Tag::Image { dest_url, .. }) = event else {
+            continue;
+        };
+        let kept = ["/", "http://", "https://"]
+            .iter()
+            .any(|prefix| dest_url.starts_with(prefix));
+        if kept && !images.iter().any(|image| image == dest_url.as_ref()) {
+            images.push(dest_url.to_string());
+        }
+    }
+    images
+}
+
+/// Turns `[!NOTE]`-style blockquotes into callouts.
+fn callouts(events: Vec<Event>) -> Vec<Event> {
+    events
+        .into_iter()
+        .map(|event| match event {
+            Event::Start(Tag::BlockQuote(Some(kind))) => {
+                let (class, title) = CALLOUTS[callout_index(kind)];
+                Event::Html(
+                    format!(
+                        "<aside class=\"callout {}\"><p class=\"callout-title\">{}</p>\n",
+                        class, title
+                    )
+                    .into(),
+                )
+            }
+            Event::End(TagEnd::BlockQuote(Some(_))) => Event::Html("</aside>\n".into()),
+            event => event,
+        })
+        .collect()
+}
+
+fn callout_index(kind: BlockQuoteKind) -> usize {
+    match kind {
+        BlockQuoteKind::Note => 0,
+        BlockQuoteKind::Tip => 1,
+        BlockQuoteKind::Important => 2,
+        BlockQuoteKind::Warning => 3,
+        BlockQuoteKind::Caution => 4,
+    }
+}
+
+/// Turns paragraphs holding only an image into figures, captioned with the
+/// image's title.
+fn figures(events: Vec<Event>) -> Vec<Event> {
+    let mut out = Vec::with_capacity(events.len());
+    let mut i = 0;
+
+    while i < events.len() {
+        match lone_image(&events[i..]) {
+            Some((figure, replaced)) => {
+                out.push(figure);
+                i += replaced;
+            }
+            None => {
+                out.push(events[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// If `events` starts with a paragraph holding only an image, the image as a
+/// figure and how many events it replaces.
+fn lone_image<'a>(events: &[Event<'a>]) -> Option<(Event<'a>, usize)> {
+    let [Event::Start(Tag::Paragraph), Event::Start(Tag::Image {
+        dest_url, title, ..
+    }), rest @ ..] = events
+    else {
+        return None;
+    };
+    let alt_len = rest
+        .iter()
+        .position(|e| matches!(e, Event::End(TagEnd::Image)))?;
+    if !matches!(rest.get(alt_len + 1), Some(Event::End(TagEnd::Paragraph))) {
+        return None;
+    }
+
+    let alt: String = rest[..alt_len].iter().filter_map(event_text).collect();
+    let caption = if title.is_empty() {
+        String::new()
+    } else {
+        format!("<figcaption>{}</figcaption>", escape(title))
+    };
+    let figure = format!(
+        "<figure><img src=\"{}\" alt=\"{}\">{}</figure>\n",
+        escape(dest_url),
+        escape(&alt),
+        caption
+    );
+
+    Some((Event::Html(figure.into()), alt_len + 4))
+}
+
+/// The text of a text or inline code event.
+fn event_text<'e>(event: &'e Event) -> Option<&'e str> {
+    match event {
+        Event::Text(text) | Event::Code(text) => Some(text),
+        _ => None,
+    }
+}
+
+/// Words in the text of some HTML.
+fn count_words(html: &str) -> usize {
+    let mut words = 0;
+    let mut in_tag = false;
+    let mut in_word = false;
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                in_word = false;
+            }
+            '>' if in_tag => in_tag = false,
+            _ if in_tag => {}
+            c if c.is_whitespace() => in_word = false,
+            _ if !in_word => {
+                in_word = true;
+                words += 1;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Escapes text for an HTML attribute or element.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// endregion: --- Content Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_headings_and_toc() {
+        let page = render("# Intro\n\n## Costs\n\n### Costs\n\n#### Detail\n\n## `cargo` & more\n");
+
+        assert!(page.html.contains(r#"<h2 id="intro">Intro</h2>"#));
+        assert!(page.html.contains(r#"<h3 id="costs-2">Costs</h3>"#));
+        assert!(page.html.contains(r#"<h4 id="detail">"#));
+        let ids: Vec<&str> = page.toc.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["intro", "costs", "costs-2", "cargo-more"]);
+        assert_eq!(page.toc[2].level, 3);
+        assert_eq!(page.toc[3].title, "cargo & more");
+    }
+
+    #[test]
+    fn test_render_extensions() {
+        let page = render(
+            "> [!TIP]\n> Book early.\n\n\
+             ![A new boiler](/img/boiler.jpg \"Fitted in Coventry\")\n\n\
+             | Job | Price |\n|-----|------:|\n| Tap | £60 |\n",
+        );
+
+        assert!(page
+            .html
+            .contains(r#"<aside class="callout callout-tip"><p class="callout-title">Tip</p>"#));
+        assert!(page.html.contains(
+            r#"<figure><img src="/img/boiler.jpg" alt="A new boiler"><figcaption>Fitted in Coventry</figcaption></figure>"#
+        ));
+        assert!(page.html.contains("<table>"));
+        assert!(page.html.contains("<td>£60</td>"));
+    }
+
+    #[test]
+    fn test_render_images() {
+        let page = render(
+            "![Van](/img/van.jpg) ![Tap](https://cdn.example.com/tap.jpg)\n\n\
+             ![Van again](/img/van.jpg) ![x](javascript:alert(1)) ![y](data:image/png,x)\n",
+        );
+
+        assert_eq!(
+            page.images,
+            ["/img/van.jpg", "https://cdn.example.com/tap.jpg"]
+        );
+    }
+
+    #[test]
+    fn test_render_sanitises() {
+        let page = render(
+            "<script>alert(1)</script>\n\n\
+             [Call](javascript:alert(1)) <img src=x onerror=alert(1)> \
+             <p class=\"lead\" style=\"color:red\">Hi</p>",
+        );
+
+        assert!(!page.html.contains("script"));
+        assert!(!page.html.contains("javascript:"));
+        assert!(!page.html.contains("onerror"));
+        assert!(!page.html.contains("lead"));
+        assert!(!page.html.contains("style"));
+    }
+
+    #[test]
+    fn test_read_minutes() {
+        assert_eq!(render("").read_minutes(), 1);
+        assert_eq!(render(&"word ".repeat(401)).read_minutes(), 3);
+        assert_eq!(render("*one* `two` three <b>four</b>").words, 4);
+    }
+}
+
+// endregion: --- Tests
@@ -9,14 +9,23 @@ use serde::{Deserialize, Serialize};
 #[must_use = "Context should be used for authorization"]
 pub struct Ctx {
     user_id: i64,
+    username: String,
 }
 
 impl Ctx {
-    pub fn new(user_id: i64) -> Self {
-        Self { user_id }
+    pub fn new(user_id: i64, username: impl Into<String>) -> Self {
+        Self {
+            user_id,
+            username: username.into(),
+        }
     }
 
     pub fn user_id(&self) -> i64 {
         self.user_id
     }
+
+    /// Who the user is in audit logs.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
 }
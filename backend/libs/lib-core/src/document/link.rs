@@ -0,0 +1,320 @@
+//! Signed, expiring public links to documents.
+//!
+//! Customers open their quote or invoice PDF without logging in, so the
+//! URL itself is the credential: an HMAC-SHA256 over the document and an
+//! expiry time, keyed with `DOCUMENT_SIGNING_KEY`.
+//!
+//! ```text
+//! /api/documents/invoices/42/pdf?expires=1792339200&signature=<b64u>
+//! ```
+//!
+//! Attachment links work the same way but are short-lived
+//! (`ATTACHMENT_LINK_TTL_MINUTES`), since they are only handed to the admin
+//! UI for display:
+//!
+//! ```text
+//! /api/attachments/7/thumbnail?expires=1792339200&signature=<b64u>
+//! ```
+
+use crate::core_config;
+use hmac::{Hmac, Mac};
+use lib_utils::b64::{b64u_decode, b64u_encode};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::OnceLock;
+use tracing::warn;
+use utoipa::ToSchema;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Table a public document link points at.
+///
+/// Credit notes are stored with invoices, so they use [`Self::Invoices`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkedDocument {
+    Quotes,
+    Invoices,
+}
+
+impl LinkedDocument {
+    /// Path segment used in public URLs.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LinkedDocument::Quotes => "quotes",
+            LinkedDocument::Invoices => "invoices",
+        }
+    }
+}
+
+/// Which file of an attachment a link serves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentVariant {
+    /// The uploaded file, with metadata stripped
+    Original,
+    /// The JPEG preview of an image
+    Thumbnail,
+}
+
+impl AttachmentVariant {
+    /// Path segment used in public URLs.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AttachmentVariant::Original => "original",
+            AttachmentVariant::Thumbnail => "thumbnail",
+        }
+    }
+
+    /// Resource name covered by the signature.
+    fn resource(self) -> &'static str {
+        match self {
+            AttachmentVariant::Original => "attachments/original",
+            AttachmentVariant::Thumbnail => "attachments/thumbnail",
+        }
+    }
+}
+
+/// Why a signed link was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkError {
+    /// Signature does not match
+    InvalidSignature,
+    /// Link is past its expiry time
+    Expired,
+}
+
+/// A signed link ready to hand to a customer.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SignedLink {
+    /// Absolute URL of the file
+    pub url: String,
+    /// Expiry as a Unix timestamp
+    pub expires: i64,
+}
+
+/// Signs a link to `id` valid until the Unix timestamp `expires`.
+#[must_use]
+pub fn sign(key: &[u8], document: LinkedDocument, id: i32, expires: i64) -> String {
+    b64u_encode(
+        mac(key, document.as_str(), id, expires)
+            .finalize()
+            .into_bytes(),
+    )
+}
+
+/// Checks a link's signature and expiry against the Unix time `now`.
+///
+/// # Errors
+///
+/// Returns [`LinkError::InvalidSignature`] for a tampered or foreign link
+/// and [`LinkError::Expired`] for a genuine link past its expiry.
+pub fn verify(
+    key: &[u8],
+    document: LinkedDocument,
+    id: i32,
+    expires: i64,
+    signature: &str,
+    now: i64,
+) -> Result<(), LinkError> {
+    check(
+        mac(key, document.as_str(), id, expires),
+        expires,
+        signature,
+        now,
+    )
+}
+
+/// Builds a signed absolute URL using the configured key, TTL and
+/// `PUBLIC_BASE_URL`.
+#[must_use]
+pub fn signed_link(document: LinkedDocument, id: i32, now: i64) -> SignedLink {
+    let config = core_config();
+    let expires = now + config.DOCUMENT_LINK_TTL_HOURS * 60 * 60;
+    let signature = sign(signing_key(), document, id, expires);
+
+    SignedLink {
+        url: format!(
+            "{}/api/documents/{}/{}/pdf?expires={}&signature={}",
+            config.PUBLIC_BASE_URL.trim_end_matches('/'),
+            document.as_str(),
+            id,
+            expires,
+            signature
+        ),
+        expires,
+    }
+}
+
+/// Signs a link to an attachment file valid until the Unix timestamp
+/// `expires`.
+#[must_use]
+pub fn sign_attachment(key: &[u8], id: i32, variant: AttachmentVariant, expires: i64) -> String {
+    b64u_encode(
+        mac(key, variant.resource(), id, expires)
+            .finalize()
+            .into_bytes(),
+    )
+}
+
+/// Checks an attachment link's signature and expiry against the Unix time
+/// `now`.
+///
+/// # Errors
+///
+/// As [`verify`].
+pub fn verify_attachment(
+    key: &[u8],
+    id: i32,
+    variant: AttachmentVariant,
+    expires: i64,
+    signature: &str,
+    now: i64,
+) -> Result<(), LinkError> {
+    check(
+        mac(key, variant.resource(), id, expires),
+        expires,
+        signature,
+        now,
+    )
+}
+
+/// Builds a signed absolute URL to an attachment file using the configured
+/// key, `ATTACHMENT_LINK_TTL_MINUTES` and `PUBLIC_BASE_URL`.
+#[must_use]
+pub fn attachment_link(id: i32, variant: AttachmentVariant, now: i64) -> SignedLink {
+    let config = core_config();
+    let expires = now + config.ATTACHMENT_LINK_TTL_MINUTES * 60;
+    let signature = sign_attachment(signing_key(), id, variant, expires);
+
+    SignedLink {
+        url: format!(
+            "{}/api/attachments/{}/{}?expires={}&signature={}",
+            config.PUBLIC_BASE_URL.trim_end_matches('/'),
+            id,
+            variant.as_str(),
+            expires,
+            signature
+        ),
+        expires,
+    }
+}
+
+/// Key for signing links.
+///
+/// Falls back to a per-process random key when `DOCUMENT_SIGNING_KEY` is
+/// unset, so links still work but do not survive a restart.
+#[must_use]
+pub fn signing_key() -> &'static [u8] {
+    static INSTANCE: OnceLock<Vec<u8>> = OnceLock::new();
+
+    INSTANCE.get_or_init(|| match &core_config().DOCUMENT_SIGNING_KEY {
+        Some(key) => key.as_bytes().to_vec(),
+        None => {
+            warn!("DOCUMENT_SIGNING_KEY not set - document links will expire on restart");
+            uuid::Uuid::new_v4().as_bytes().to_vec()
+        }
+    })
+}
+
+fn mac(key: &[u8], resource: &str, id: i32, expires: i64) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(format!("{resource}:{id}:{expires}").as_bytes());
+    mac
+}
+
+fn check(mac: HmacSha256, expires: i64, signature: &str, now: i64) -> Result<(), LinkError> {
+    let signature = b64u_decode(signature).map_err(|_| LinkError::InvalidSignature)?;
+    mac.verify_slice(&signature)
+        .map_err(|_| LinkError::InvalidSignature)?;
+
+    if now > expires {
+        return Err(LinkError::Expired);
+    }
+
+    Ok(())
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test-signing-key";
+
+    #[test]
+    fn test_sign_verify_roundtrip() {
+        let sig = sign(KEY, LinkedDocument::Invoices, 42, 2_000);
+
+        assert_eq!(
+            verify(KEY, LinkedDocument::Invoices, 42, 2_000, &sig, 1_000),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let sig = sign(KEY, LinkedDocument::Invoices, 42, 2_000);
+
+        for (document, id, expires, key) in [
+            (LinkedDocument::Quotes, 42, 2_000, KEY),
+            (LinkedDocument::Invoices, 43, 2_000, KEY),
+            (LinkedDocument::Invoices, 42, 9_000, KEY),
+            (LinkedDocument::Invoices, 42, 2_000, b"other-key".as_slice()),
+        ] {
+            assert_eq!(
+                verify(key, document, id, expires, &sig, 1_000),
+                Err(LinkError::InvalidSignature)
+            );
+        }
+        assert_eq!(
+            verify(
+                KEY,
+                LinkedDocument::Invoices,
+                42,
+                2_000,
+                "not base64!",
+                1_000
+            ),
+            Err(LinkError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_expired() {
+        let sig = sign(KEY, LinkedDocument::Quotes, 7, 2_000);
+
+        assert_eq!(
+            verify(KEY, LinkedDocument::Quotes, 7, 2_000, &sig, 2_001),
+            Err(LinkError::Expired)
+        );
+    }
+
+    #[test]
+    fn test_attachment_links_are_scoped() {
+        let sig = sign_attachment(KEY, 7, AttachmentVariant::Thumbnail, 2_000);
+
+        assert_eq!(
+            verify_attachment(KEY, 7, AttachmentVariant::Thumbnail, 2_000, &sig, 1_000),
+            Ok(())
+        );
+        // A thumbnail link does not open the original, nor a document with the same ID
+        assert_eq!(
+            verify_attachment(KEY, 7, AttachmentVariant::Original, 2_000, &sig, 1_000),
+            Err(LinkError::InvalidSignature)
+        );
+        assert_eq!(
+            verify(KEY, LinkedDocument::Quotes, 7, 2_000, &sig, 1_000),
+            Err(LinkError::InvalidSignature)
+        );
+        assert_eq!(
+            verify_attachment(KEY, 7, AttachmentVariant::Thumbnail, 2_000, &sig, 2_001),
+            Err(LinkError::Expired)
+        );
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,566 @@
+//! # Document Rendering
+//!
+//! Branded A4 PDFs for quotes, invoices and credit notes, rendered in pure
+//! Rust.
+//!
+//! Rendering is deterministic: the same [`Branding`] and [`DocumentData`]
+//! always produce byte-identical output, so documents can be snapshot
+//! tested and cached by content.
+//!
+//! ## Modules
+//!
+//! - [`pdf`] - Minimal PDF writer (pages, text, rules, JPEG images)
+//! - [`link`] - Signed, expiring public links to documents
+//!
+//! ## Structures
+//!
+//! - [`Branding`] - Business details, logo and terms printed on documents
+//! - [`DocumentKind`] - Quote, invoice or credit note
+//! - [`DocumentData`] - Everything printed on one document
+//! - [`DocumentLine`] - One line item
+//! - [`DocumentAcceptance`] - Signature block on an accepted quote
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::document::{render_pdf, Branding, DocumentData};
+//! use lib_core::model::quote::QuoteBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn quote_pdf(mm: &ModelManager, id: i32) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+//!     let branding = Branding::from_config();
+//!     let quote = QuoteBmc::get(mm, id).await?;
+//!     let data = DocumentData::from_quote(&quote, None, branding)?;
+//!     Ok(render_pdf(branding, &data))
+//! }
+//! ```
+
+// region:    --- Modules
+
+pub mod link;
+pub mod pdf;
+mod render;
+
+pub use self::render::render_pdf;
+
+// endregion: --- Modules
+
+use self::pdf::JpegImage;
+use crate::core_config;
+use crate::model::customer::Customer;
+use crate::model::invoice::Invoice;
+use crate::model::quote::{Quote, QuoteItem};
+use crate::model::quote_acceptance::QuoteAcceptance;
+use crate::model::tenant::{Tenant, TenantBmc, DEFAULT_TENANT_ID};
+use crate::model::{Error, ModelManager, Result};
+use crate::pricing::VatBand;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use time::{OffsetDateTime, UtcOffset};
+use tracing::warn;
+use utoipa::ToSchema;
+
+/// Business details, logo and terms printed on every document.
+#[derive(Debug, Clone, Default)]
+pub struct Branding {
+    /// Trading name
+    pub business_name: String,
+    /// Postal address, one entry per line
+    pub address_lines: Vec<String>,
+    /// Contact phone number
+    pub phone: Option<String>,
+    /// Contact email address
+    pub email: Option<String>,
+    /// VAT registration number, printed when set
+    pub vat_number: Option<String>,
+    /// Logo drawn in the header instead of the business name
+    pub logo: Option<JpegImage>,
+    /// Terms printed on quotes
+    pub quote_terms: String,
+    /// Terms printed on invoices and credit notes
+    pub invoice_terms: String,
+}
+
+impl Branding {
+    /// Branding from the `BUSINESS_*` and `*_TERMS` settings, loaded once.
+    ///
+    /// A logo that cannot be read, or is not a JPEG, is logged and skipped.
+    #[must_use]
+    pub fn from_config() -> &'static Branding {
+        static INSTANCE: OnceLock<Branding> = OnceLock::new();
+
+        INSTANCE.get_or_init(|| {
+            let config = core_config();
+            let logo =
+                config
+                    .BUSINESS_LOGO_PATH
+                    .as_deref()
+                    .and_then(|path| match std::fs::read(path) {
+                        Ok(bytes) => {
+                            let logo = JpegImage::from_bytes(bytes);
+                            if logo.is_none() {
+                                warn!("Business logo {} is not a JPEG; skipping", path);
+                            }
+                            logo
+                        }
+                        Err(e) => {
+                            warn!("Failed to read business logo {}: {}", path, e);
+                            None
+                        }
+                    });
+
+            Branding {
+                business_name: config.BUSINESS_NAME.clone(),
+                address_lines: config.BUSINESS_ADDRESS.clone(),
+                phone: config.BUSINESS_PHONE.clone(),
+                email: config.BUSINESS_EMAIL.clone(),
+                vat_number: config.VAT_NUMBER.clone(),
+                logo,
+                quote_terms: config.QUOTE_TERMS.clone(),
+                invoice_terms: config.INVOICE_TERMS.clone(),
+            }
+        })
+    }
+
+    /// Branding for a tenant's documents. The default tenant gets the
+    /// settings above with its own name and contact details over them;
+    /// other tenants get their own details and the standard terms, without
+    /// the address, VAT number or logo from the settings.
+    #[must_use]
+    pub fn for_tenant(tenant: &Tenant) -> Branding {
+        let config = Self::from_config();
+        let base = if tenant.id == DEFAULT_TENANT_ID {
+            config.clone()
+        } else {
+            Branding {
+                quote_terms: config.quote_terms.clone(),
+                invoice_terms: config.invoice_terms.clone(),
+                ..Default::default()
+            }
+        };
+
+        Branding {
+            business_name: tenant.business_name.clone(),
+            phone: tenant.phone.clone().or(base.phone.clone()),
+            email: tenant.email.clone().or(base.email.clone()),
+            ..base
+        }
+    }
+
+    /// Branding for the model manager's tenant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the tenant no longer exists.
+    pub async fn load(mm: &ModelManager) -> Result<Branding> {
+        let tenant = TenantBmc::get(mm, mm.tenant_id()).await?;
+
+        Ok(Self::for_tenant(&tenant))
+    }
+}
+
+/// Type of document being rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentKind {
+    Quote,
+    Invoice,
+    CreditNote,
+}
+
+impl DocumentKind {
+    /// Heading printed at the top of the document.
+    #[must_use]
+    pub fn heading(self) -> &'static str {
+        match self {
+            DocumentKind::Quote => "QUOTE",
+            DocumentKind::Invoice => "INVOICE",
+            DocumentKind::CreditNote => "CREDIT NOTE",
+        }
+    }
+
+    /// Lowercase name used in file names and URLs.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DocumentKind::Quote => "quote",
+            DocumentKind::Invoice => "invoice",
+            DocumentKind::CreditNote => "credit_note",
+        }
+    }
+}
+
+/// One line item on a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentLine {
+    /// Description of the work or part
+    pub description: String,
+    /// Quantity
+    pub quantity: i32,
+    /// Unit price in cents
+    pub unit_price_cents: i32,
+}
+
+impl From<&QuoteItem> for DocumentLine {
+    fn from(item: &QuoteItem) -> Self {
+        Self {
+            description: item.description.clone(),
+            quantity: item.quantity,
+            unit_price_cents: item.unit_price,
+        }
+    }
+}
+
+/// Who accepted a quote, printed at the end of the accepted quote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentAcceptance {
+    /// Name the customer typed to sign
+    pub signer_name: String,
+    /// When they accepted, e.g. `18 October 2026 14:05:09 UTC`
+    pub accepted_at: String,
+    /// Revision accepted
+    pub revision: i32,
+    /// IP address the acceptance came from
+    pub ip_address: Option<String>,
+    /// Browser user agent
+    pub user_agent: Option<String>,
+    /// Hex SHA-256 of the accepted revision
+    pub content_hash: String,
+    /// Drawn signature
+    pub signature: Option<JpegImage>,
+}
+
+impl From<&QuoteAcceptance> for DocumentAcceptance {
+    fn from(acceptance: &QuoteAcceptance) -> Self {
+        Self {
+            signer_name: acceptance.signer_name.clone(),
+            accepted_at: format_timestamp(acceptance.accepted_at),
+            revision: acceptance.revision,
+            ip_address: acceptance.ip_address.clone(),
+            user_agent: acceptance.user_agent.clone(),
+            content_hash: acceptance.content_hash.clone(),
+            signature: acceptance
+                .signature_jpeg
+                .clone()
+                .and_then(JpegImage::from_bytes),
+        }
+    }
+}
+
+/// Everything printed on one document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentData {
+    /// Quote, invoice or credit note
+    pub kind: DocumentKind,
+    /// Document number, e.g. `Q-000042`, `INV-000001`
+    pub number: String,
+    /// Optional title under the customer block (quote title)
+    pub title: Option<String>,
+    /// Label/value rows under the heading (date, due date, ...)
+    pub meta: Vec<(String, String)>,
+    /// Customer name and address lines
+    pub customer: Option<Vec<String>>,
+    /// Line items
+    pub lines: Vec<DocumentLine>,
+    /// Sum of line items in cents
+    pub subtotal_cents: i32,
+    /// Discount in cents
+    pub discount_cents: i32,
+    /// VAT by rate, one totals row each
+    pub vat_bands: Vec<VatBand>,
+    /// VAT in cents
+    pub vat_cents: i32,
+    /// Total in cents
+    pub total_cents: i32,
+    /// Amount paid in cents (invoices only)
+    pub paid_cents: Option<i32>,
+    /// Amount credited in cents (invoices only)
+    pub credited_cents: Option<i32>,
+    /// Free-text notes
+    pub notes: Option<String>,
+    /// Terms and conditions
+    pub terms: String,
+    /// Other options and optional extras, one line each (quotes awaiting a
+    /// response only)
+    pub alternatives: Vec<String>,
+    /// Signature block (accepted quotes only)
+    pub acceptance: Option<DocumentAcceptance>,
+}
+
+impl DocumentData {
+    /// Builds the printable view of a quote.
+    ///
+    /// The lines and totals are for the option and add-ons the customer
+    /// chose, or before acceptance the first option with the others and
+    /// the add-ons listed under "Also available".
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stored line items, options or selection are
+    /// malformed.
+    pub fn from_quote(
+        quote: &Quote,
+        customer: Option<&Customer>,
+        branding: &Branding,
+    ) -> Result<Self> {
+        let items = quote.selected_items()?;
+        let options = quote.quote_options()?;
+        let add_ons = quote.quote_add_ons()?;
+        let selection = quote.quote_selection()?;
+
+        let mut meta = Vec::new();
+        if let Some(created_at) = quote.created_at {
+            meta.push(("Date".to_string(), format_date(created_at.date())));
+        }
+        if let Some(valid_until) = quote.valid_until {
+            meta.push(("Valid until".to_string(), format_date(valid_until)));
+        }
+        if let Some(option) = selection.option.and_then(|i| options.get(i)) {
+            meta.push(("Option".to_string(), option.name.clone()));
+        }
+
+        let mut alternatives = Vec::new();
+        if quote.selection.is_none() {
+            for (i, option) in options.iter().enumerate() {
+                if selection.option != Some(i) {
+                    alternatives.push(format!(
+                        "{}: {}",
+                        option.name,
+                        format_money(i64::from(option.total_cents))
+                    ));
+                }
+            }
+            for add_on in &add_ons {
+                alternatives.push(format!(
+                    "Optional extra: {}, {} × {}",
+                    add_on.description,
+                    add_on.quantity,
+                    format_money(i64::from(add_on.unit_price))
+                ));
+            }
+        }
+
+        Ok(Self {
+            kind: DocumentKind::Quote,
+            number: format!("Q-{:06}", quote.id),
+            title: Some(quote.title.clone()),
+            meta,
+            customer: customer.map(customer_lines),
+            lines: items.iter().map(DocumentLine::from).collect(),
+            subtotal_cents: quote.subtotal_cents,
+            discount_cents: quote.discount_cents,
+            vat_bands: parse_vat_bands(&quote.vat_breakdown),
+            vat_cents: quote.vat_cents,
+            total_cents: quote.total_cents,
+            paid_cents: None,
+            credited_cents: None,
+            notes: quote.notes.clone(),
+            terms: branding.quote_terms.clone(),
+            alternatives,
+            acceptance: None,
+        })
+    }
+
+    /// Adds the customer's acceptance to a quote.
+    #[must_use]
+    pub fn with_acceptance(mut self, acceptance: &QuoteAcceptance) -> Self {
+        self.meta.push((
+            "Accepted".to_string(),
+            format_date(acceptance.accepted_at.to_offset(UtcOffset::UTC).date()),
+        ));
+        self.acceptance = Some(acceptance.into());
+        self
+    }
+
+    /// Builds the printable view of an invoice or credit note.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the invoice is still a draft (drafts
+    /// have no number) or the stored line items are malformed.
+    pub fn from_invoice(
+        invoice: &Invoice,
+        customer: Option<&Customer>,
+        credited_number: Option<&str>,
+        branding: &Branding,
+    ) -> Result<Self> {
+        let Some(number) = invoice.number.clone() else {
+            return Err(Error::ValidationError(
+                "Draft invoices have no number; issue the invoice first".into(),
+            ));
+        };
+        let items = parse_items(&invoice.items)?;
+        let kind = if invoice.kind == "credit_note" {
+            DocumentKind::CreditNote
+        } else {
+            DocumentKind::Invoice
+        };
+
+        let mut meta = Vec::new();
+        if let Some(issue_date) = invoice.issue_date {
+            meta.push(("Date".to_string(), format_date(issue_date)));
+        }
+        if let Some(due_date) = invoice.due_date {
+            meta.push(("Due date".to_string(), format_date(due_date)));
+        }
+        if let Some(credited_number) = credited_number {
+            meta.push(("Against invoice".to_string(), credited_number.to_string()));
+        }
+        if invoice.status == "void" {
+            meta.push(("Status".to_string(), "VOID".to_string()));
+        }
+
+        // Invoices issued before per-line VAT have no breakdown
+        let mut vat_bands = parse_vat_bands(&invoice.vat_breakdown);
+        if vat_bands.is_empty() && invoice.vat_registered {
+            vat_bands.push(VatBand {
+                rate_bp: invoice.vat_rate_bp,
+                net_cents: invoice.subtotal_cents - invoice.discount_cents,
+                vat_cents: invoice.vat_cents,
+            });
+        }
+
+        let is_invoice = kind == DocumentKind::Invoice;
+        Ok(Self {
+            kind,
+            number,
+            title: None,
+            meta,
+            customer: customer.map(customer_lines),
+            lines: items.iter().map(DocumentLine::from).collect(),
+            subtotal_cents: invoice.subtotal_cents,
+            discount_cents: invoice.discount_cents,
+            vat_bands,
+            vat_cents: invoice.vat_cents,
+            total_cents: invoice.total_cents,
+            paid_cents: is_invoice.then_some(invoice.paid_cents),
+            credited_cents: (is_invoice && invoice.credited_cents > 0)
+                .then_some(invoice.credited_cents),
+            notes: invoice.notes.clone(),
+            terms: branding.invoice_terms.clone(),
+            alternatives: Vec::new(),
+            acceptance: None,
+        })
+    }
+
+    /// Download file name, e.g. `invoice-INV-000001.pdf`.
+    #[must_use]
+    pub fn filename(&self) -> String {
+        format!(
+            "{}-{}.pdf",
+            self.kind.as_str().replace('_', "-"),
+            self.number
+        )
+    }
+}
+
+// region:    --- Formatting Helpers
+
+/// Formats cents as pounds with thousands separators: `£1,234.50`.
+#[must_use]
+pub fn format_money(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.unsigned_abs();
+    let pounds = (cents / 100).to_string();
+
+    let mut grouped = String::with_capacity(pounds.len() + pounds.len() / 3);
+    for (i, digit) in pounds.chars().enumerate() {
+        if i > 0 && (pounds.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    format!("{sign}£{grouped}.{:02}", cents % 100)
+}
+
+/// Formats a date as `18 October 2026`.
+#[must_use]
+pub fn format_date(date: time::Date) -> String {
+    format!("{} {} {}", date.day(), date.month(), date.year())
+}
+
+/// Formats an instant in UTC as `18 October 2026 14:05:09 UTC`.
+#[must_use]
+pub fn format_timestamp(at: OffsetDateTime) -> String {
+    let at = at.to_offset(UtcOffset::UTC);
+    format!(
+        "{} {:02}:{:02}:{:02} UTC",
+        format_date(at.date()),
+        at.hour(),
+        at.minute(),
+        at.second()
+    )
+}
+
+fn parse_items(items: &serde_json::Value) -> Result<Vec<QuoteItem>> {
+    serde_json::from_value(items.clone())
+        .map_err(|e| Error::ValidationError(format!("Malformed line items: {e}").into()))
+}
+
+fn parse_vat_bands(breakdown: &serde_json::Value) -> Vec<VatBand> {
+    serde_json::from_value(breakdown.clone()).unwrap_or_default()
+}
+
+/// Name, email and first address of a customer.
+fn customer_lines(customer: &Customer) -> Vec<String> {
+    let mut lines = vec![customer.name.clone()];
+    if let Some(address) = customer
+        .addresses
+        .as_ref()
+        .and_then(|a| a.as_array())
+        .and_then(|a| a.first())
+    {
+        match address {
+            serde_json::Value::String(s) => lines.extend(s.lines().map(str::to_string)),
+            serde_json::Value::Object(fields) => lines.extend(
+                ["line1", "line2", "street", "city", "town", "postcode"]
+                    .iter()
+                    .filter_map(|key| fields.get(*key).and_then(|v| v.as_str()))
+                    .map(str::to_string),
+            ),
+            _ => {}
+        }
+    }
+    if let Some(email) = &customer.email {
+        lines.push(email.clone());
+    }
+    lines
+}
+
+// endregion: --- Formatting Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_money() {
+        assert_eq!(format_money(0), "£0.00");
+        assert_eq!(format_money(7500), "£75.00");
+        assert_eq!(format_money(123_456_789), "£1,234,567.89");
+        assert_eq!(format_money(-1050), "-£10.50");
+    }
+
+    #[test]
+    fn test_format_date() {
+        let date = time::Date::from_calendar_date(2026, time::Month::October, 8).unwrap();
+
+        assert_eq!(format_date(date), "8 October 2026");
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        let at = time::Date::from_calendar_date(2026, time::Month::October, 18)
+            .unwrap()
+            .with_hms(15, 5, 9)
+            .unwrap()
+            .assume_offset(UtcOffset::from_hms(1, 0, 0).unwrap());
+
+        assert_eq!(format_timestamp(at), "18 October 2026 14:05:09 UTC");
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,559 @@
+//! Minimal PDF 1.4 writer.
+//!
+//! Just enough PDF to lay out business documents: pages of text in the
+//! standard Helvetica faces, rules, filled boxes and JPEG images. Fonts are
+//! the PDF base-14 faces, so nothing is embedded, and no timestamps or file
+//! IDs are written - the same input always produces the same bytes.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+/// A4 page width in points.
+pub const PAGE_WIDTH: f32 = 595.28;
+/// A4 page height in points.
+pub const PAGE_HEIGHT: f32 = 841.89;
+
+/// Standard font face.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource_name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    /// Width of `text` in points at `size`.
+    #[must_use]
+    pub fn text_width(self, text: &str, size: f32) -> f32 {
+        let widths = match self {
+            Font::Regular => &HELVETICA_WIDTHS,
+            Font::Bold => &HELVETICA_BOLD_WIDTHS,
+        };
+        let units: u32 = text
+            .chars()
+            .map(|c| match c as u32 {
+                code @ 32..=126 => u32::from(widths[(code - 32) as usize]),
+                _ => 556,
+            })
+            .sum();
+        units as f32 * size / 1000.0
+    }
+}
+
+/// RGB colour with components in `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color(pub f32, pub f32, pub f32);
+
+impl Color {
+    pub const BLACK: Color = Color(0.0, 0.0, 0.0);
+}
+
+/// A baseline JPEG, passed through to the PDF untouched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JpegImage {
+    data: Vec<u8>,
+    width: u16,
+    height: u16,
+    components: u8,
+}
+
+impl JpegImage {
+    /// Reads the dimensions from a JPEG's start-of-frame segment.
+    ///
+    /// Returns `None` if the data is not a JPEG this writer can embed.
+    #[must_use]
+    pub fn from_bytes(data: Vec<u8>) -> Option<Self> {
+        if data.get(..2) != Some(&[0xFF, 0xD8]) {
+            return None;
+        }
+
+        let mut pos = 2;
+        while pos + 4 <= data.len() {
+            if data[pos] != 0xFF {
+                return None;
+            }
+            let marker = data[pos + 1];
+            let len = usize::from(u16::from_be_bytes([data[pos + 2], data[pos + 3]]));
+            let is_sof = matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+            if is_sof {
+                let seg = data.get(pos + 4..pos + 10)?;
+                let height = u16::from_be_bytes([seg[1], seg[2]]);
+                let width = u16::from_be_bytes([seg[3], seg[4]]);
+                let components = seg[5];
+                if width == 0 || height == 0 || !matches!(components, 1 | 3 | 4) {
+                    return None;
+                }
+                return Some(Self {
+                    data,
+                    width,
+                    height,
+                    components,
+                });
+            }
+            pos += 2 + len;
+        }
+
+        None
+    }
+
+    /// Image width in pixels.
+    #[must_use]
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Image height in pixels.
+    #[must_use]
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// The JPEG file.
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// Handle for an image added with [`PdfWriter::add_image`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageId(usize);
+
+impl ImageId {
+    fn resource_name(self) -> String {
+        // Im1 is the document image set with `set_image`
+        format!("Im{}", self.0 + 2)
+    }
+}
+
+/// Drawing operations for one page.
+///
+/// Coordinates are in points from the top-left corner, which is flipped to
+/// PDF's bottom-left origin when written.
+#[derive(Debug, Default)]
+pub struct Page {
+    ops: Vec<u8>,
+}
+
+impl Page {
+    /// Draws `text` with its baseline at `y`, starting at `x`.
+    pub fn text(&mut self, x: f32, y: f32, font: Font, size: f32, color: Color, text: &str) {
+        let _ = write!(
+            self.ops,
+            "BT {} {} {} rg /{} {} Tf {} {} Td (",
+            num(color.0),
+            num(color.1),
+            num(color.2),
+            font.resource_name(),
+            num(size),
+            num(x),
+            num(PAGE_HEIGHT - y),
+        );
+        self.ops.extend_from_slice(&escape_text(text));
+        self.ops.extend_from_slice(b") Tj ET\n");
+    }
+
+    /// Draws `text` so it ends at `right`.
+    pub fn text_right(
+        &mut self,
+        right: f32,
+        y: f32,
+        font: Font,
+        size: f32,
+        color: Color,
+        text: &str,
+    ) {
+        let x = right - font.text_width(text, size);
+        self.text(x, y, font, size, color, text);
+    }
+
+    /// Draws a horizontal rule.
+    pub fn rule(&mut self, x1: f32, x2: f32, y: f32, width: f32, color: Color) {
+        let _ = writeln!(
+            self.ops,
+            "{} {} {} RG {} w {} {} m {} {} l S",
+            num(color.0),
+            num(color.1),
+            num(color.2),
+            num(width),
+            num(x1),
+            num(PAGE_HEIGHT - y),
+            num(x2),
+            num(PAGE_HEIGHT - y)
+        );
+    }
+
+    /// Fills a rectangle whose top-left corner is at (`x`, `y`).
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let _ = writeln!(
+            self.ops,
+            "{} {} {} rg {} {} {} {} re f",
+            num(color.0),
+            num(color.1),
+            num(color.2),
+            num(x),
+            num(PAGE_HEIGHT - y - height),
+            num(width),
+            num(height)
+        );
+    }
+
+    /// Draws the document's image with its top-left corner at (`x`, `y`).
+    pub fn image(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.draw_xobject("Im1", x, y, width, height);
+    }
+
+    /// Draws an image added with [`PdfWriter::add_image`] with its top-left
+    /// corner at (`x`, `y`).
+    pub fn place_image(&mut self, image: ImageId, x: f32, y: f32, width: f32, height: f32) {
+        self.draw_xobject(&image.resource_name(), x, y, width, height);
+    }
+
+    fn draw_xobject(&mut self, name: &str, x: f32, y: f32, width: f32, height: f32) {
+        let _ = writeln!(
+            self.ops,
+            "q {} 0 0 {} {} {} cm /{} Do Q",
+            num(width),
+            num(height),
+            num(x),
+            num(PAGE_HEIGHT - y - height),
+            name
+        );
+    }
+}
+
+/// Assembles pages into a PDF file.
+#[derive(Debug, Default)]
+pub struct PdfWriter {
+    pages: Vec<Page>,
+    image: Option<JpegImage>,
+    extra_images: Vec<JpegImage>,
+    title: Option<String>,
+}
+
+impl PdfWriter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the document title shown by PDF viewers.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = Some(title.into());
+    }
+
+    /// Sets the image drawn by [`Page::image`].
+    pub fn set_image(&mut self, image: JpegImage) {
+        self.image = Some(image);
+    }
+
+    /// Adds an image to draw with [`Page::place_image`], such as a
+    /// signature.
+    pub fn add_image(&mut self, image: JpegImage) -> ImageId {
+        self.extra_images.push(image);
+        ImageId(self.extra_images.len() - 1)
+    }
+
+    /// Appends a page.
+    pub fn push_page(&mut self, page: Page) {
+        self.pages.push(page);
+    }
+
+    /// Mutable access to the pages added so far.
+    pub fn pages_mut(&mut self) -> &mut [Page] {
+        &mut self.pages
+    }
+
+    /// Serialises the document.
+    #[must_use]
+    pub fn finish(self) -> Vec<u8> {
+        // Object layout: 1 catalog, 2 page tree, 3-4 fonts, 5 info,
+        // 6 image (optional), one per added image, then a page and a
+        // content stream per page.
+        let first_page_obj = 7 + self.extra_images.len();
+        let page_count = self.pages.len().max(1);
+        let mut pages = self.pages;
+        if pages.is_empty() {
+            pages.push(Page::default());
+        }
+
+        let mut out = Vec::new();
+        let mut offsets = Vec::new();
+        out.extend_from_slice(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+
+        let kids: Vec<String> = (0..page_count)
+            .map(|i| format!("{} 0 R", first_page_obj + i * 2))
+            .collect();
+
+        write_obj(&mut out, &mut offsets, b"<< /Type /Catalog /Pages 2 0 R >>");
+        write_obj(
+            &mut out,
+            &mut offsets,
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                page_count
+            )
+            .as_bytes(),
+        );
+        write_obj(
+            &mut out,
+            &mut offsets,
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>",
+        );
+        write_obj(
+            &mut out,
+            &mut offsets,
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>",
+        );
+
+        let mut info = b"<< /Producer (lib-core) ".to_vec();
+        if let Some(title) = &self.title {
+            info.extend_from_slice(b"/Title (");
+            info.extend_from_slice(&escape_text(title));
+            info.extend_from_slice(b") ");
+        }
+        info.extend_from_slice(b">>");
+        write_obj(&mut out, &mut offsets, &info);
+
+        match &self.image {
+            Some(image) => write_obj(&mut out, &mut offsets, &image_obj(image)),
+            // Keep object numbers stable whether or not there is a logo
+            None => write_obj(&mut out, &mut offsets, b"null"),
+        }
+        for image in &self.extra_images {
+            write_obj(&mut out, &mut offsets, &image_obj(image));
+        }
+
+        let mut xobjects: Vec<String> = self
+            .image
+            .iter()
+            .map(|_| "/Im1 6 0 R".to_string())
+            .collect();
+        for i in 0..self.extra_images.len() {
+            xobjects.push(format!("/{} {} 0 R", ImageId(i).resource_name(), 7 + i));
+        }
+        let xobjects = if xobjects.is_empty() {
+            String::new()
+        } else {
+            format!(" /XObject << {} >>", xobjects.join(" "))
+        };
+        for (i, page) in pages.iter().enumerate() {
+            let content_obj = first_page_obj + i * 2 + 1;
+            write_obj(
+                &mut out,
+                &mut offsets,
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >>{} >> /Contents {} 0 R >>",
+                    num(PAGE_WIDTH),
+                    num(PAGE_HEIGHT),
+                    xobjects,
+                    content_obj
+                )
+                .as_bytes(),
+            );
+
+            let mut stream = format!("<< /Length {} >>\nstream\n", page.ops.len()).into_bytes();
+            stream.extend_from_slice(&page.ops);
+            stream.extend_from_slice(b"endstream");
+            write_obj(&mut out, &mut offsets, &stream);
+        }
+
+        let xref_offset = out.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1);
+        for offset in &offsets {
+            let _ = writeln!(trailer, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            trailer,
+            "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+            offsets.len() + 1,
+            xref_offset
+        );
+        out.extend_from_slice(trailer.as_bytes());
+
+        out
+    }
+}
+
+fn image_obj(image: &JpegImage) -> Vec<u8> {
+    let color_space = match image.components {
+        1 => "/DeviceGray",
+        4 => "/DeviceCMYK",
+        _ => "/DeviceRGB",
+    };
+    let mut obj = format!(
+        "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace {} \
+         /BitsPerComponent 8 /Filter /DCTDecode /Length {} >>\nstream\n",
+        image.width,
+        image.height,
+        color_space,
+        image.data.len()
+    )
+    .into_bytes();
+    obj.extend_from_slice(&image.data);
+    obj.extend_from_slice(b"\nendstream");
+    obj
+}
+
+fn write_obj(out: &mut Vec<u8>, offsets: &mut Vec<usize>, body: &[u8]) {
+    offsets.push(out.len());
+    out.extend_from_slice(format!("{} 0 obj\n", offsets.len()).as_bytes());
+    out.extend_from_slice(body);
+    out.extend_from_slice(b"\nendobj\n");
+}
+
+/// Formats a coordinate with at most two decimals.
+fn num(value: f32) -> String {
+    let s = format!("{value:.2}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// Encodes text as a WinAnsi PDF string body, escaping delimiters.
+///
+/// Characters outside WinAnsi are replaced with `?`.
+fn escape_text(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                c as u8
+            }
+            ' '..='~' => c as u8,
+            '\u{A0}'..='\u{FF}' => c as u32 as u8,
+            '€' => 0x80,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        };
+        out.push(byte);
+    }
+    out
+}
+
+// region:    --- Font Metrics
+
+/// Helvetica advance widths for ASCII 32..=126, in 1/1000 em.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0..?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P.._
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // `..o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p..~
+];
+
+/// Helvetica-Bold advance widths for ASCII 32..=126, in 1/1000 em.
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, // 0..?
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, // P.._
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, // `..o
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584, // p..~
+];
+
+// endregion: --- Font Metrics
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_width() {
+        assert_eq!(Font::Regular.text_width("", 10.0), 0.0);
+        assert!((Font::Regular.text_width("Hello", 10.0) - 22.78).abs() < 0.01);
+        assert!(Font::Bold.text_width("Hello", 10.0) > Font::Regular.text_width("Hello", 10.0));
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a(b)c\\"), b"a\\(b\\)c\\\\".to_vec());
+        assert_eq!(escape_text("£5"), vec![0xA3, b'5']);
+        assert_eq!(escape_text("€ ✓"), vec![0x80, b' ', b'?']);
+    }
+
+    #[test]
+    fn test_jpeg_dimensions() {
+        // SOI, APP0 (empty), SOF0 with 2x3 px, 3 components
+        let data = vec![
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x02, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x03, 0x00,
+            0x02, 0x03,
+        ];
+        let image = JpegImage::from_bytes(data).expect("valid JPEG header");
+
+        assert_eq!((image.width(), image.height()), (2, 3));
+        assert!(JpegImage::from_bytes(b"\x89PNG".to_vec()).is_none());
+    }
+
+    #[test]
+    fn test_added_images_get_own_objects() {
+        let jpeg = vec![
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x02, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x03, 0x00,
+            0x02, 0x03,
+        ];
+        let mut writer = PdfWriter::new();
+        let signature = writer.add_image(JpegImage::from_bytes(jpeg).unwrap());
+        let mut page = Page::default();
+        page.place_image(signature, 50.0, 50.0, 20.0, 30.0);
+        writer.push_page(page);
+        let pdf = writer.finish();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.contains("6 0 obj\nnull"));
+        assert!(text.contains("7 0 obj\n<< /Type /XObject"));
+        assert!(text.contains("/XObject << /Im2 7 0 R >>"));
+        assert!(text.contains("/Im2 Do"));
+        assert!(text.contains("/Kids [8 0 R]"));
+    }
+
+    #[test]
+    fn test_finish_is_well_formed_and_deterministic() {
+        let build = || {
+            let mut writer = PdfWriter::new();
+            writer.set_title("Test");
+            let mut page = Page::default();
+            page.text(50.0, 50.0, Font::Bold, 12.0, Color::BLACK, "Hello");
+            page.rule(50.0, 100.0, 60.0, 0.5, Color::BLACK);
+            writer.push_page(page);
+            writer.finish()
+        };
+        let pdf = build();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert!(text.contains("(Hello) Tj"));
+        assert!(text.contains("/Count 1"));
+        assert_eq!(pdf, build());
+
+        // Every xref entry must point at its object header
+        let xref_at = text.rfind("xref\n").unwrap();
+        for (i, line) in text[xref_at..].lines().skip(3).take(7).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,626 @@
+//! A4 layout for quotes, invoices and credit notes.
+//!
+//! ```text
+//! ┌───────────────────────────────────────────┐
+//! │ Logo / business name           HEADING    │
+//! │ Address, phone, email, VAT no  Number ... │
+//! │                                           │
+//! │ Bill to                                   │
+//! │ ┌ Description ──── Qty ── Unit ─ Amount ┐ │
+//! │ │ ...line items, wrapped, paginated...   │ │
+//! │                         Subtotal / VAT    │
+//! │                         Total             │
+//! │ Also available, Notes, Terms              │
+//! │ Acceptance and signature (accepted quotes)│
+//! │ footer                        Page 1 of 1 │
+//! └───────────────────────────────────────────┘
+//! ```
+
+use super::pdf::{Color, Font, ImageId, Page, PdfWriter, PAGE_HEIGHT, PAGE_WIDTH};
+use super::{format_money, Branding, DocumentAcceptance, DocumentData, DocumentKind};
+
+const LEFT: f32 = 50.0;
+const RIGHT: f32 = PAGE_WIDTH - 50.0;
+const TOP: f32 = 50.0;
+/// Lowest baseline for body content; the footer sits below it.
+const BOTTOM: f32 = PAGE_HEIGHT - 70.0;
+
+const COL_QTY: f32 = 380.0;
+const COL_UNIT: f32 = 465.0;
+const DESCRIPTION_WIDTH: f32 = 300.0;
+
+const ACCENT: Color = Color(0.13, 0.33, 0.55);
+const MUTED: Color = Color(0.4, 0.4, 0.4);
+const RULE: Color = Color(0.8, 0.8, 0.8);
+const SHADE: Color = Color(0.94, 0.95, 0.97);
+
+const LOGO_MAX_WIDTH: f32 = 150.0;
+const LOGO_MAX_HEIGHT: f32 = 60.0;
+const SIGNATURE_MAX_WIDTH: f32 = 180.0;
+const SIGNATURE_MAX_HEIGHT: f32 = 60.0;
+/// Indent of values in the acceptance block.
+const ACCEPTANCE_VALUE_X: f32 = LEFT + 100.0;
+
+/// Renders a document to PDF bytes.
+#[must_use]
+pub fn render_pdf(branding: &Branding, doc: &DocumentData) -> Vec<u8> {
+    let mut writer = PdfWriter::new();
+    let mut layout = Layout::new();
+
+    layout.header(branding, doc);
+    layout.customer(doc);
+    layout.line_items(doc);
+    layout.totals(doc);
+    if !doc.alternatives.is_empty() {
+        layout.paragraphs("Also available", &doc.alternatives.join("\n"));
+    }
+    if let Some(notes) = doc.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        layout.paragraphs("Notes", notes);
+    }
+    if !doc.terms.trim().is_empty() {
+        layout.paragraphs("Terms", &doc.terms);
+    }
+    if let Some(acceptance) = &doc.acceptance {
+        let signature = acceptance
+            .signature
+            .clone()
+            .map(|image| writer.add_image(image));
+        layout.acceptance(acceptance, signature);
+    }
+
+    writer.set_title(format!(
+        "{} {} - {}",
+        title_case(doc.kind.heading()),
+        doc.number,
+        branding.business_name
+    ));
+    if let Some(logo) = &branding.logo {
+        writer.set_image(logo.clone());
+    }
+
+    let page_count = layout.pages.len();
+    for (i, mut page) in layout.pages.into_iter().enumerate() {
+        footer(&mut page, branding, i + 1, page_count);
+        writer.push_page(page);
+    }
+
+    writer.finish()
+}
+
+/// Page-by-page cursor.
+struct Layout {
+    pages: Vec<Page>,
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self {
+            pages: vec![Page::default()],
+            y: TOP,
+        }
+    }
+
+    fn page(&mut self) -> &mut Page {
+        self.pages.last_mut().expect("layout always has a page")
+    }
+
+    /// Starts a new page if `height` does not fit below the cursor.
+    fn ensure_space(&mut self, height: f32) -> bool {
+        if self.y + height <= BOTTOM {
+            return false;
+        }
+        self.pages.push(Page::default());
+        self.y = TOP;
+        true
+    }
+
+    fn header(&mut self, branding: &Branding, doc: &DocumentData) {
+        // Left: logo or name, then contact details
+        let mut left_y = TOP;
+        match &branding.logo {
+            Some(logo) => {
+                let (w, h) = fit(
+                    f32::from(logo.width()),
+                    f32::from(logo.height()),
+                    LOGO_MAX_WIDTH,
+                    LOGO_MAX_HEIGHT,
+                );
+                self.page().image(LEFT, left_y - 10.0, w, h);
+                left_y += h + 8.0;
+            }
+            None => {
+                left_y += 8.0;
+                let name = &branding.business_name;
+                self.page()
+                    .text(LEFT, left_y, Font::Bold, 16.0, ACCENT, name);
+                left_y += 18.0;
+            }
+        }
+        let details = branding
+            .address_lines
+            .iter()
+            .cloned()
+            .chain(branding.phone.clone())
+            .chain(branding.email.clone())
+            .chain(branding.vat_number.as_ref().map(|n| format!("VAT No: {n}")));
+        for line in details {
+            self.page()
+                .text(LEFT, left_y, Font::Regular, 9.0, MUTED, &line);
+            left_y += 12.0;
+        }
+
+        // Right: heading, number and dates
+        let mut right_y = TOP + 12.0;
+        self.page()
+            .text_right(RIGHT, right_y, Font::Bold, 22.0, ACCENT, doc.kind.heading());
+        right_y += 20.0;
+        let number_row = ("Number".to_string(), doc.number.clone());
+        for (label, value) in std::iter::once(&number_row).chain(&doc.meta) {
+            let page = self.page();
+            page.text_right(RIGHT - 100.0, right_y, Font::Regular, 9.0, MUTED, label);
+            page.text_right(RIGHT, right_y, Font::Bold, 9.0, Color::BLACK, value);
+            right_y += 13.0;
+        }
+
+        self.y = left_y.max(right_y) + 20.0;
+    }
+
+    fn customer(&mut self, doc: &DocumentData) {
+        if let Some(lines) = &doc.customer {
+            let label = match doc.kind {
+                DocumentKind::Quote => "Prepared for",
+                DocumentKind::Invoice | DocumentKind::CreditNote => "Bill to",
+            };
+            let y = self.y;
+            self.page().text(LEFT, y, Font::Bold, 10.0, ACCENT, label);
+            self.y += 15.0;
+            for (i, line) in lines.iter().enumerate() {
+                let font = if i == 0 { Font::Bold } else { Font::Regular };
+                let y = self.y;
+                self.page().text(LEFT, y, font, 10.0, Color::BLACK, line);
+                self.y += 13.0;
+            }
+            self.y += 12.0;
+        }
+
+        if let Some(title) = doc.title.as_deref().filter(|t| !t.is_empty()) {
+            for line in wrap(title, Font::Bold, 12.0, RIGHT - LEFT) {
+                let y = self.y;
+                self.page()
+                    .text(LEFT, y, Font::Bold, 12.0, Color::BLACK, &line);
+                self.y += 15.0;
+            }
+            self.y += 6.0;
+        }
+    }
+
+    fn table_header(&mut self) {
+        let y = self.y;
+        let page = self.page();
+        page.fill_rect(LEFT, y, RIGHT - LEFT, 20.0, SHADE);
+        let baseline = y + 13.5;
+        page.text(LEFT + 6.0, baseline, Font::Bold, 9.0, ACCENT, "Description");
+        page.text_right(COL_QTY, baseline, Font::Bold, 9.0, ACCENT, "Qty");
+        page.text_right(COL_UNIT, baseline, Font::Bold, 9.0, ACCENT, "Unit price");
+        page.text_right(RIGHT - 6.0, baseline, Font::Bold, 9.0, ACCENT, "Amount");
+        self.y += 20.0;
+    }
+
+    fn line_items(&mut self, doc: &DocumentData) {
+        self.ensure_space(60.0);
+        self.table_header();
+
+        for line in &doc.lines {
+            let wrapped = wrap(&line.description, Font::Regular, 10.0, DESCRIPTION_WIDTH);
+            let height = wrapped.len() as f32 * 13.0 + 9.0;
+            if self.ensure_space(height) {
+                self.table_header();
+            }
+
+            let amount = i64::from(line.quantity) * i64::from(line.unit_price_cents);
+            let baseline = self.y + 15.0;
+            let page = self.page();
+            for (i, text) in wrapped.iter().enumerate() {
+                let y = baseline + i as f32 * 13.0;
+                page.text(LEFT + 6.0, y, Font::Regular, 10.0, Color::BLACK, text);
+            }
+            let qty = line.quantity.to_string();
+            let unit = format_money(i64::from(line.unit_price_cents));
+            let total = format_money(amount);
+            page.text_right(COL_QTY, baseline, Font::Regular, 10.0, Color::BLACK, &qty);
+            page.text_right(COL_UNIT, baseline, Font::Regular, 10.0, Color::BLACK, &unit);
+            page.text_right(
+                RIGHT - 6.0,
+                baseline,
+                Font::Regular,
+                10.0,
+                Color::BLACK,
+                &total,
+            );
+            self.y += height;
+            let y = self.y;
+            self.page().rule(LEFT, RIGHT, y, 0.5, RULE);
+        }
+
+        self.y += 12.0;
+    }
+
+    fn totals(&mut self, doc: &DocumentData) {
+        let mut rows: Vec<(String, String, bool)> = vec![(
+            "Subtotal".to_string(),
+            format_money(doc.subtotal_cents.into()),
+            false,
+        )];
+        if doc.discount_cents > 0 {
+            rows.push((
+                "Discount".to_string(),
+                format_money(-i64::from(doc.discount_cents)),
+                false,
+            ));
+        }
+        for band in &doc.vat_bands {
+            rows.push((
+                format!("VAT ({})", format_rate(band.rate_bp)),
+                format_money(band.vat_cents.into()),
+                false,
+            ));
+        }
+        rows.push((
+            "Total".to_string(),
+            format_money(doc.total_cents.into()),
+            true,
+        ));
+        if let Some(paid) = doc.paid_cents {
+            if let Some(credited) = doc.credited_cents {
+                rows.push((
+                    "Credited".to_string(),
+                    format_money(-i64::from(credited)),
+                    false,
+                ));
+            }
+            rows.push(("Paid".to_string(), format_money(-i64::from(paid)), false));
+            let balance = i64::from(doc.total_cents)
+                - i64::from(paid)
+                - i64::from(doc.credited_cents.unwrap_or(0));
+            rows.push(("Balance due".to_string(), format_money(balance), true));
+        }
+
+        self.ensure_space(rows.len() as f32 * 18.0 + 10.0);
+        for (label, value, strong) in rows {
+            let y = self.y;
+            let page = self.page();
+            let font = if strong { Font::Bold } else { Font::Regular };
+            let size = if strong { 11.0 } else { 10.0 };
+            if strong {
+                page.fill_rect(COL_QTY - 20.0, y, RIGHT - COL_QTY + 20.0, 18.0, SHADE);
+            }
+            page.text_right(COL_UNIT, y + 12.5, font, size, Color::BLACK, &label);
+            page.text_right(RIGHT - 6.0, y + 12.5, font, size, Color::BLACK, &value);
+            self.y += 18.0;
+        }
+
+        self.y += 20.0;
+    }
+
+    fn paragraphs(&mut self, heading: &str, text: &str) {
+        self.ensure_space(40.0);
+        let y = self.y;
+        self.page().text(LEFT, y, Font::Bold, 10.0, ACCENT, heading);
+        self.y += 14.0;
+
+        for paragraph in text.lines() {
+            for line in wrap(paragraph, Font::Regular, 9.0, RIGHT - LEFT) {
+                self.ensure_space(12.0);
+                let y = self.y;
+                self.page().text(LEFT, y, Font::Regular, 9.0, MUTED, &line);
+                self.y += 12.0;
+            }
+        }
+
+        self.y += 12.0;
+    }
+
+    fn acceptance(&mut self, acceptance: &DocumentAcceptance, signature: Option<ImageId>) {
+        let mut rows = vec![
+            ("Signed by", acceptance.signer_name.clone()),
+            ("Accepted at", acceptance.accepted_at.clone()),
+            ("Revision", acceptance.revision.to_string()),
+        ];
+        rows.extend(acceptance.ip_address.clone().map(|ip| ("IP address", ip)));
+        rows.extend(acceptance.user_agent.clone().map(|ua| ("Browser", ua)));
+        rows.push(("SHA-256", acceptance.content_hash.clone()));
+
+        self.ensure_space(40.0 + rows.len() as f32 * 12.0);
+        let y = self.y;
+        self.page()
+            .text(LEFT, y, Font::Bold, 10.0, ACCENT, "Acceptance");
+        self.y += 14.0;
+
+        for (label, value) in rows {
+            let width = RIGHT - ACCEPTANCE_VALUE_X;
+            for (i, line) in wrap(&value, Font::Regular, 9.0, width).iter().enumerate() {
+                self.ensure_space(12.0);
+                let y = self.y;
+                let page = self.page();
+                if i == 0 {
+                    page.text(LEFT, y, Font::Regular, 9.0, MUTED, label);
+                }
+                page.text(
+                    ACCEPTANCE_VALUE_X,
+                    y,
+                    Font::Regular,
+                    9.0,
+                    Color::BLACK,
+                    line,
+                );
+                self.y += 12.0;
+            }
+        }
+
+        if let (Some(id), Some(image)) = (signature, &acceptance.signature) {
+            let (w, h) = fit(
+                f32::from(image.width()),
+                f32::from(image.height()),
+                SIGNATURE_MAX_WIDTH,
+                SIGNATURE_MAX_HEIGHT,
+            );
+            self.y += 4.0;
+            self.ensure_space(h + 12.0);
+            let y = self.y;
+            let page = self.page();
+            page.place_image(id, ACCEPTANCE_VALUE_X, y, w, h);
+            page.rule(
+                ACCEPTANCE_VALUE_X,
+                ACCEPTANCE_VALUE_X + SIGNATURE_MAX_WIDTH,
+                y + h + 2.0,
+                0.5,
+                RULE,
+            );
+            page.text(LEFT, y + h, Font::Regular, 9.0, MUTED, "Signature");
+            self.y += h + 14.0;
+        }
+
+        self.y += 12.0;
+    }
+}
+
+fn footer(page: &mut Page, branding: &Branding, number: usize, count: usize) {
+    let y = PAGE_HEIGHT - 35.0;
+    page.rule(LEFT, RIGHT, y - 12.0, 0.5, RULE);
+    let contact = std::iter::once(branding.business_name.as_str())
+        .chain(branding.phone.as_deref())
+        .chain(branding.email.as_deref())
+        .collect::<Vec<_>>()
+        .join("  •  ");
+    page.text(LEFT, y, Font::Regular, 8.0, MUTED, &contact);
+    let pages = format!("Page {number} of {count}");
+    page.text_right(RIGHT, y, Font::Regular, 8.0, MUTED, &pages);
+}
+
+/// Scales `w`×`h` down to fit the box, keeping the aspect ratio.
+fn fit(w: f32, h: f32, max_w: f32, max_h: f32) -> (f32, f32) {
+    let scale = (max_w / w).min(max_h / h).min(1.0);
+    (w * scale, h * scale)
+}
+
+/// Formats a basis-point rate as a percentage: `20%`, `12.5%`.
+fn format_rate(rate_bp: i32) -> String {
+    let whole = rate_bp / 100;
+    let frac = rate_bp % 100;
+    if frac == 0 {
+        format!("{whole}%")
+    } else {
+        let frac = format!("{frac:02}");
+        format!("{whole}.{}%", frac.trim_end_matches('0'))
+    }
+}
+
+fn title_case(heading: &str) -> String {
+    heading
+        .split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_string() + &chars.as_str().to_lowercase())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Greedy word wrap. Words longer than a line are broken by character.
+fn wrap(text: &str, font: Font, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+        if font.text_width(&candidate, size) <= max_width {
+            current = candidate;
+            continue;
+        }
+
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        for c in word.chars() {
+            current.push(c);
+            if font.text_width(&current, size) > max_width {
+                current.pop();
+                lines.push(std::mem::take(&mut current));
+                current.push(c);
+            }
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::DocumentLine;
+    use crate::pricing::VatBand;
+
+    fn branding() -> Branding {
+        Branding {
+            business_name: "XF Tradesman".to_string(),
+            address_lines: vec!["1 High Street".to_string(), "Leeds LS1 1AA".to_string()],
+            phone: Some("0113 000 0000".to_string()),
+            email: Some("hello@xftradesman.com".to_string()),
+            vat_number: Some("GB123456789".to_string()),
+            logo: None,
+            quote_terms: "Valid for 30 days.".to_string(),
+            invoice_terms: "Payment due within 14 days.".to_string(),
+        }
+    }
+
+    fn invoice(lines: usize) -> DocumentData {
+        DocumentData {
+            kind: DocumentKind::Invoice,
+            number: "INV-000001".to_string(),
+            title: None,
+            meta: vec![("Date".to_string(), "18 October 2026".to_string())],
+            customer: Some(vec![
+                "Jane Smith".to_string(),
+                "jane@example.com".to_string(),
+            ]),
+            lines: (0..lines)
+                .map(|i| DocumentLine {
+                    description: format!("Replace radiator valve (room {i})"),
+                    quantity: 1,
+                    unit_price_cents: 4500,
+                })
+                .collect(),
+            subtotal_cents: 4500 * lines as i32,
+            discount_cents: 0,
+            vat_bands: vec![VatBand {
+                rate_bp: 2000,
+                net_cents: 4500 * lines as i32,
+                vat_cents: 900 * lines as i32,
+            }],
+            vat_cents: 900 * lines as i32,
+            total_cents: 5400 * lines as i32,
+            paid_cents: Some(0),
+            credited_cents: None,
+            notes: None,
+            terms: "Payment due within 14 days.".to_string(),
+            alternatives: Vec::new(),
+            acceptance: None,
+        }
+    }
+
+    #[test]
+    fn test_render_is_deterministic() {
+        let first = render_pdf(&branding(), &invoice(3));
+        let second = render_pdf(&branding(), &invoice(3));
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_render_contents() {
+        let pdf = render_pdf(&branding(), &invoice(2));
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.contains("(INVOICE) Tj"));
+        assert!(text.contains("(INV-000001) Tj"));
+        assert!(text.contains("(VAT \\(20%\\)) Tj"));
+        assert!(text.contains("(Balance due) Tj"));
+        assert!(text.contains("(VAT No: GB123456789) Tj"));
+        assert!(text.contains("(Page 1 of 1) Tj"));
+        assert!(!text.contains("(Also available) Tj"));
+    }
+
+    #[test]
+    fn test_render_alternatives() {
+        let mut quote = invoice(1);
+        quote.kind = DocumentKind::Quote;
+        quote.alternatives = vec![
+            "Replace mixer: £120.00".to_string(),
+            "Optional extra: Isolation valve, 1 × £15.00".to_string(),
+        ];
+
+        let pdf = render_pdf(&branding(), &quote);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.contains("(Also available) Tj"));
+        assert!(text.contains("(Replace mixer: "));
+        assert!(text.contains("(Optional extra: Isolation valve, 1 "));
+    }
+
+    #[test]
+    fn test_render_acceptance_block() {
+        let signature = vec![
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x02, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x03, 0x00,
+            0x02, 0x03,
+        ];
+        let mut quote = invoice(1);
+        quote.kind = DocumentKind::Quote;
+        quote.paid_cents = None;
+        quote.acceptance = Some(DocumentAcceptance {
+            signer_name: "Jane Smith".to_string(),
+            accepted_at: "18 October 2026 14:05:09 UTC".to_string(),
+            revision: 2,
+            ip_address: Some("203.0.113.7".to_string()),
+            user_agent: Some("Mozilla/5.0 (test)".to_string()),
+            content_hash: "ab".repeat(32),
+            signature: crate::document::pdf::JpegImage::from_bytes(signature),
+        });
+
+        let pdf = render_pdf(&branding(), &quote);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.contains("(Acceptance) Tj"));
+        assert!(text.contains("(Jane Smith) Tj"));
+        assert!(text.contains("(203.0.113.7) Tj"));
+        assert!(text.contains(&format!("({}) Tj", "ab".repeat(32))));
+        assert!(text.contains("/Im2 Do"));
+        assert_eq!(pdf, render_pdf(&branding(), &quote));
+    }
+
+    #[test]
+    fn test_render_paginates_long_documents() {
+        let pdf = render_pdf(&branding(), &invoice(80));
+        let text = String::from_utf8_lossy(&pdf);
+
+        let count_at = text.find("/Count ").unwrap() + "/Count ".len();
+        let count: usize = text[count_at..]
+            .split(' ')
+            .next()
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+
+        assert!(count >= 3);
+        assert!(text.contains(&format!("(Page {count} of {count}) Tj")));
+    }
+
+    #[test]
+    fn test_wrap() {
+        let lines = wrap("one two three four five", Font::Regular, 10.0, 60.0);
+
+        assert!(lines.len() > 1);
+        assert_eq!(lines.join(" "), "one two three four five");
+        assert_eq!(wrap("", Font::Regular, 10.0, 60.0), vec![String::new()]);
+    }
+
+    #[test]
+    fn test_format_rate() {
+        assert_eq!(format_rate(2000), "20%");
+        assert_eq!(format_rate(1250), "12.5%");
+        assert_eq!(format_rate(0), "0%");
+    }
+}
+
+// endregion: --- Tests
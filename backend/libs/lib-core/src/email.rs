@@ -8,6 +8,7 @@
 //! - Automatic retry with exponential backoff
 //! - Graceful error handling with detailed logging
 //! - Support for plain text and HTML emails
+//! - File attachments (quote and invoice PDFs)
 //! - Global singleton pattern for efficient resource usage
 //!
 //! ## Supported Templates
@@ -64,6 +65,7 @@
 //! or other operations succeed even if email delivery is temporarily unavailable.
 
 use lettre::message::header::ContentType;
+use lettre::message::{Attachment, MultiPart, SinglePart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use serde::{Deserialize, Serialize};
@@ -109,6 +111,17 @@ pub struct EmailMessage {
     pub content_type: String,
 }
 
+/// File attached to an email.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailAttachment {
+    /// File name shown to the recipient
+    pub filename: String,
+    /// MIME type (e.g., "application/pdf")
+    pub content_type: String,
+    /// File contents
+    pub data: Vec<u8>,
+}
+
 impl EmailService {
     /// Create a new email service from environment variables.
     ///
@@ -219,6 +232,54 @@ impl EmailService {
         self.send_with_retry(email, 3).await
     }
 
+    /// Send an email with file attachments, with automatic retry.
+    ///
+    /// The message body becomes the first MIME part and each attachment
+    /// follows it. With no attachments this behaves like [`Self::send_email`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::send_email`], plus `EmailError::MessageError` for an
+    /// attachment with an invalid content type.
+    pub async fn send_email_with_attachments(
+        &self,
+        message: EmailMessage,
+        attachments: Vec<EmailAttachment>,
+    ) -> Result<(), EmailError> {
+        if attachments.is_empty() {
+            return self.send_email(message).await;
+        }
+
+        let body_type = ContentType::parse(&message.content_type)
+            .map_err(|e| EmailError::MessageError(format!("Invalid content type: {}", e)))?;
+        let mut multipart = MultiPart::mixed()
+            .singlepart(SinglePart::builder().header(body_type).body(message.body));
+        for attachment in attachments {
+            let content_type = ContentType::parse(&attachment.content_type).map_err(|e| {
+                EmailError::MessageError(format!("Invalid attachment content type: {}", e))
+            })?;
+            multipart = multipart.singlepart(
+                Attachment::new(attachment.filename).body(attachment.data, content_type),
+            );
+        }
+
+        let email = Message::builder()
+            .from(
+                self.from_email
+                    .parse()
+                    .map_err(|e| EmailError::MessageError(format!("Invalid from email: {}", e)))?,
+            )
+            .to(message
+                .to
+                .parse()
+                .map_err(|e| EmailError::MessageError(format!("Invalid to email: {}", e)))?)
+            .subject(message.subject)
+            .multipart(multipart)
+            .map_err(|e| EmailError::MessageError(format!("Failed to build email: {}", e)))?;
+
+        self.send_with_retry(email, 3).await
+    }
+
     /// Send email with exponential backoff retry.
     ///
     /// Internal method that implements retry logic with exponential backoff.
@@ -9,7 +9,16 @@
 //!
 //! - **[`model`]** - Data access layer with ModelManager and BMC pattern
 //! - **[`ctx`]** - Request context for authentication and authorization
+//! - **[`auth`]** - Signed admin access tokens
 //! - **[`email`]** - Email service for notifications
+//! - **[`document`]** - PDF rendering and signed links for quotes and invoices
+//! - **[`pricing`]** - Discounts, VAT and totals shared by quotes and invoices
+//! - **[`storage`]** - Blob storage (local disk or S3) for uploaded files
+//! - **[`media`]** - Type sniffing, metadata stripping and thumbnails for uploads
+//! - **[`postcode`]** - UK postcode normalisation, districts and areas
+//! - **[`routing`]** - Daily visiting order and arrival estimates for jobs
+//! - **[`text`]** - URL slugs and near-duplicate detection for page copy
+//! - **[`content`]** - Markdown to sanitised HTML for blog posts and service pages
 //! - **[`config`]** - Configuration management
 //!
 //! ## Design Principles
@@ -110,12 +119,21 @@
 
 // region:    --- Modules
 
+pub mod auth;
 pub mod cache;
 pub mod config;
+pub mod content;
 pub mod ctx;
+pub mod document;
 pub mod email;
+pub mod media;
 pub mod model;
+pub mod postcode;
 pub mod prelude;
+pub mod pricing;
+pub mod routing;
+pub mod storage;
+pub mod text;
 
 // Re-export commonly used types for convenience
 pub use config::core_config;
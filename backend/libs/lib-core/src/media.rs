@@ -0,0 +1,519 @@
+//! # Media
+//!
+//! Checks and cleans uploaded files before they are stored as attachments.
+//!
+//! ## What happens to an upload
+//!
+//! 1. The type is sniffed from the leading bytes; the filename and the
+//!    client's `Content-Type` are never trusted. Only JPEG, PNG, WebP and
+//!    PDF are accepted.
+//! 2. Images are decoded (bounded by [`MAX_IMAGE_DIMENSION`]) to prove they
+//!    are well formed and to read their size.
+//! 3. Metadata is stripped. Job photos taken on a phone carry the GPS
+//!    position of the customer's home, so EXIF, XMP and text chunks never
+//!    reach storage. If the photo relies on an EXIF orientation, the
+//!    rotation is applied to the pixels and the image re-encoded; otherwise
+//!    the metadata segments are cut out and the pixels left untouched.
+//! 4. A JPEG thumbnail that fits in [`THUMBNAIL_SIZE`] is generated.
+//!
+//! PDFs are stored as uploaded, without a thumbnail.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::media::{process, MediaType};
+//!
+//! let bytes = std::fs::read("before.jpg").unwrap();
+//! let upload = process(&bytes).unwrap();
+//!
+//! assert_eq!(upload.media_type, MediaType::Jpeg);
+//! assert!(upload.thumbnail.is_some());
+//! ```
+
+use image::codecs::jpeg::JpegEncoder;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use utoipa::ToSchema;
+
+/// Largest width or height accepted for an image, in pixels.
+pub const MAX_IMAGE_DIMENSION: u32 = 12_000;
+
+/// Thumbnails fit within this many pixels on each side.
+pub const THUMBNAIL_SIZE: u32 = 320;
+
+/// JPEG quality used when an image has to be re-encoded.
+const JPEG_QUALITY: u8 = 90;
+
+/// JPEG quality used for thumbnails.
+const THUMBNAIL_QUALITY: u8 = 80;
+
+/// Media processing result type.
+pub type Result<T> = core::result::Result<T, MediaError>;
+
+/// Why an upload was refused.
+#[derive(Debug, thiserror::Error)]
+pub enum MediaError {
+    /// Not one of the accepted file types
+    #[error("Unsupported file type; upload a JPEG, PNG, WebP or PDF")]
+    Unsupported,
+
+    /// Claims to be a supported type but does not decode
+    #[error("The file appears to be damaged: {0}")]
+    Corrupt(String),
+}
+
+/// The accepted upload types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaType {
+    Jpeg,
+    Png,
+    Webp,
+    Pdf,
+}
+
+impl MediaType {
+    /// Identifies a file from its leading bytes.
+    #[must_use]
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(Self::Webp)
+        } else if bytes.starts_with(b"%PDF-") {
+            Some(Self::Pdf)
+        } else {
+            None
+        }
+    }
+
+    /// MIME type to serve the file with.
+    #[must_use]
+    pub fn mime(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::Webp => "image/webp",
+            Self::Pdf => "application/pdf",
+        }
+    }
+
+    /// Conventional file extension, without the dot.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::Webp => "webp",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    /// Whether the file is a picture (and so gets a thumbnail).
+    #[must_use]
+    pub fn is_image(self) -> bool {
+        !matches!(self, Self::Pdf)
+    }
+
+    fn image_format(self) -> Option<ImageFormat> {
+        match self {
+            Self::Jpeg => Some(ImageFormat::Jpeg),
+            Self::Png => Some(ImageFormat::Png),
+            Self::Webp => Some(ImageFormat::WebP),
+            Self::Pdf => None,
+        }
+    }
+}
+
+/// An upload that is safe to store.
+#[derive(Debug, Clone)]
+pub struct ProcessedUpload {
+    pub media_type: MediaType,
+    /// The file with metadata removed
+    pub bytes: Vec<u8>,
+    /// JPEG thumbnail, for images
+    pub thumbnail: Option<Vec<u8>>,
+    /// Width in pixels after orientation, for images
+    pub width: Option<u32>,
+    /// Height in pixels after orientation, for images
+    pub height: Option<u32>,
+}
+
+/// Sniffs, validates and cleans an upload (see the module docs).
+///
+/// This decodes the whole image, so call it from a blocking task.
+///
+/// # Errors
+///
+/// Returns [`MediaError::Unsupported`] for anything but JPEG, PNG, WebP and
+/// PDF, and [`MediaError::Corrupt`] for images that fail to decode or are
+/// larger than [`MAX_IMAGE_DIMENSION`].
+pub fn process(bytes: &[u8]) -> Result<ProcessedUpload> {
+    let media_type = MediaType::sniff(bytes).ok_or(MediaError::Unsupported)?;
+    let Some(format) = media_type.image_format() else {
+        return Ok(ProcessedUpload {
+            media_type,
+            bytes: bytes.to_vec(),
+            thumbnail: None,
+            width: None,
+            height: None,
+        });
+    };
+
+    let (image, orientation) = decode(bytes, format)?;
+    let (image, bytes) = if orientation == Orientation::NoTransforms {
+        (image, strip_metadata(media_type, bytes)?)
+    } else {
+        let mut image = image;
+        image.apply_orientation(orientation);
+        let bytes = encode(&image, media_type)?;
+        (image, bytes)
+    };
+
+    Ok(ProcessedUpload {
+        media_type,
+        thumbnail: Some(thumbnail(&image)?),
+        width: Some(image.width()),
+        height: Some(image.height()),
+        bytes,
+    })
+}
+
+// region:    --- Media Helpers
+
+fn corrupt(e: impl std::fmt::Display) -> MediaError {
+    MediaError::Corrupt(e.to_string())
+}
+
+fn decode(bytes: &[u8], format: ImageFormat) -> Result<(DynamicImage, Orientation)> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+    let mut decoder = reader.into_decoder().map_err(corrupt)?;
+    // A malformed EXIF block should not sink an otherwise good photo
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let image = DynamicImage::from_decoder(decoder).map_err(corrupt)?;
+
+    Ok((image, orientation))
+}
+
+/// Re-encodes an image in its own format. The encoders write no metadata.
+fn encode(image: &DynamicImage, media_type: MediaType) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    match media_type {
+        MediaType::Jpeg => JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY)
+            .encode_image(&DynamicImage::ImageRgb8(image.to_rgb8()))
+            .map_err(corrupt)?,
+        MediaType::Png => image
+            .write_to(&mut Cursor::new(&mut out), ImageFormat::Png)
+            .map_err(corrupt)?,
+        MediaType::Webp => DynamicImage::ImageRgba8(image.to_rgba8())
+            .write_to(&mut Cursor::new(&mut out), ImageFormat::WebP)
+            .map_err(corrupt)?,
+        MediaType::Pdf => return Err(MediaError::Unsupported),
+    }
+
+    Ok(out)
+}
+
+fn thumbnail(image: &DynamicImage) -> Result<Vec<u8>> {
+    // Small images are used as they are rather than scaled up
+    let thumb = if image.width() > THUMBNAIL_SIZE || image.height() > THUMBNAIL_SIZE {
+        image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8()
+    } else {
+        image.to_rgb8()
+    };
+    let mut out = Vec::new();
+    JpegEncoder::new_with_quality(&mut out, THUMBNAIL_QUALITY)
+        .encode_image(&thumb)
+        .map_err(corrupt)?;
+
+    Ok(out)
+}
+
+/// Removes metadata segments without touching the encoded pixels.
+fn strip_metadata(media_type: MediaType, bytes: &[u8]) -> Result<Vec<u8>> {
+    match media_type {
+        MediaType::Jpeg => strip_jpeg(bytes),
+        MediaType::Png => strip_png(bytes),
+        MediaType::Webp => strip_webp(bytes),
+        MediaType::Pdf => Ok(bytes.to_vec()),
+    }
+}
+
+/// Keeps JFIF (APP0), Adobe colour (APP14) and ICC profile (APP2) segments;
+/// drops the other APPn segments (EXIF, XMP, ...) and comments.
+fn strip_jpeg(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut out = bytes[..2].to_vec();
+    let mut pos = 2;
+
+    loop {
+        // Markers may be preceded by any number of 0xFF fill bytes
+        while bytes.get(pos) == Some(&0xFF) && bytes.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        let (Some(&0xFF), Some(&marker)) = (bytes.get(pos), bytes.get(pos + 1)) else {
+            return Err(corrupt("JPEG marker expected"));
+        };
+        if marker == 0xDA || marker == 0xD9 {
+            // Start of scan: the rest is entropy-coded data
+            out.extend_from_slice(&bytes[pos..]);
+            return Ok(out);
+        }
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            out.extend_from_slice(&bytes[pos..pos + 2]);
+            pos += 2;
+            continue;
+        }
+
+        let len = bytes
+            .get(pos + 2..pos + 4)
+            .map(|l| usize::from(u16::from_be_bytes([l[0], l[1]])))
+            .filter(|&len| len >= 2 && pos + 2 + len <= bytes.len())
+            .ok_or_else(|| corrupt("JPEG segment overruns the file"))?;
+        let segment = &bytes[pos..pos + 2 + len];
+        let keep = match marker {
+            0xE0 | 0xEE => true,
+            0xE2 => segment[4..].starts_with(b"ICC_PROFILE\0"),
+            0xE1..=0xEF | 0xFE => false,
+            _ => true,
+        };
+        if keep {
+            out.extend_from_slice(segment);
+        }
+        pos += 2 + len;
+    }
+}
+
+/// Drops the EXIF, text and timestamp chunks.
+fn strip_png(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut out = bytes[..8].to_vec();
+    let mut pos = 8;
+
+    while pos < bytes.len() {
+        let header = bytes
+            .get(pos..pos + 8)
+            .ok_or_else(|| corrupt("PNG chunk header truncated"))?;
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let end = pos
+            .checked_add(12 + len)
+            .filter(|&end| end <= bytes.len())
+            .ok_or_else(|| corrupt("PNG chunk overruns the file"))?;
+
+        if !matches!(
+            &header[4..8],
+            b"eXIf" | b"tEXt" | b"zTXt" | b"iTXt" | b"tIME"
+        ) {
+            out.extend_from_slice(&bytes[pos..end]);
+        }
+        pos = end;
+    }
+
+    Ok(out)
+}
+
+/// Drops the EXIF and XMP chunks and clears their flags in the extended
+/// header.
+fn strip_webp(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut out = bytes[..12].to_vec();
+    let mut pos = 12;
+
+    while pos < bytes.len() {
+        let header = bytes
+            .get(pos..pos + 8)
+            .ok_or_else(|| corrupt("WebP chunk header truncated"))?;
+        let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        // Chunks are padded to an even length
+        let end = pos
+            .checked_add(8 + len + len % 2)
+            .filter(|&end| end <= bytes.len())
+            .ok_or_else(|| corrupt("WebP chunk overruns the file"))?;
+
+        match &header[..4] {
+            b"EXIF" | b"XMP " => {}
+            b"VP8X" if len >= 1 => {
+                let start = out.len();
+                out.extend_from_slice(&bytes[pos..end]);
+                out[start + 8] &= !(0x08 | 0x04);
+            }
+            _ => out.extend_from_slice(&bytes[pos..end]),
+        }
+        pos = end;
+    }
+
+    let riff_size = u32::try_from(out.len() - 8).map_err(corrupt)?;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+
+    Ok(out)
+}
+
+// endregion: --- Media Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    /// A 40×20 JPEG, left half red and right half blue.
+    fn fx_jpeg() -> Vec<u8> {
+        let image = RgbImage::from_fn(40, 20, |x, _| {
+            if x < 20 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        });
+        let mut out = Vec::new();
+        JpegEncoder::new_with_quality(&mut out, 95)
+            .encode_image(&image)
+            .unwrap();
+        out
+    }
+
+    /// APP1 segment with an EXIF block holding only an orientation tag.
+    fn fx_exif_segment(orientation: u16) -> Vec<u8> {
+        let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01".to_vec();
+        exif.extend_from_slice(&orientation.to_be_bytes());
+        exif.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        let mut segment = vec![0xFF, 0xE1];
+        segment.extend_from_slice(&(exif.len() as u16 + 2).to_be_bytes());
+        segment.extend_from_slice(&exif);
+        segment
+    }
+
+    fn fx_jpeg_with(segments: &[Vec<u8>]) -> Vec<u8> {
+        let jpeg = fx_jpeg();
+        let mut out = jpeg[..2].to_vec();
+        for segment in segments {
+            out.extend_from_slice(segment);
+        }
+        out.extend_from_slice(&jpeg[2..]);
+        out
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(MediaType::sniff(&fx_jpeg()), Some(MediaType::Jpeg));
+        assert_eq!(MediaType::sniff(b"%PDF-1.7\n"), Some(MediaType::Pdf));
+        assert_eq!(
+            MediaType::sniff(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some(MediaType::Webp)
+        );
+        assert_eq!(MediaType::sniff(b"RIFF\0\0\0\0WAVEfmt "), None);
+        assert_eq!(MediaType::sniff(b"<svg xmlns="), None);
+        assert!(matches!(process(b"GIF89a"), Err(MediaError::Unsupported)));
+    }
+
+    #[test]
+    fn test_process_jpeg_strips_exif_keeping_pixels() {
+        let comment = [&[0xFF, 0xFE, 0x00, 0x09][..], b"secret!"].concat();
+        let upload = fx_jpeg_with(&[fx_exif_segment(1), comment]);
+
+        let processed = process(&upload).unwrap();
+
+        assert_eq!(processed.media_type, MediaType::Jpeg);
+        assert_eq!((processed.width, processed.height), (Some(40), Some(20)));
+        assert!(!contains(&processed.bytes, b"Exif"));
+        assert!(!contains(&processed.bytes, b"secret!"));
+        // Only the metadata was cut; the encoded image is byte-for-byte the same
+        assert!(processed.bytes.ends_with(&fx_jpeg()[2..]));
+        let thumb = image::load_from_memory(&processed.thumbnail.unwrap()).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (40, 20));
+    }
+
+    #[test]
+    fn test_process_jpeg_applies_orientation() {
+        // 6 = rotate 90° clockwise to display
+        let upload = fx_jpeg_with(&[fx_exif_segment(6)]);
+
+        let processed = process(&upload).unwrap();
+
+        assert_eq!((processed.width, processed.height), (Some(20), Some(40)));
+        assert!(!contains(&processed.bytes, b"Exif"));
+        let image = image::load_from_memory(&processed.bytes).unwrap().to_rgb8();
+        assert_eq!((image.width(), image.height()), (20, 40));
+        // The red left half is now on top
+        assert!(image.get_pixel(10, 5)[0] > 200);
+        assert!(image.get_pixel(10, 35)[2] > 200);
+    }
+
+    #[test]
+    fn test_process_png_strips_text_chunks() {
+        let mut png = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(8, 8))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        // Insert a tEXt chunk straight after IHDR (8 + 25 bytes)
+        let text = b"tEXtComment\0home address";
+        let mut chunk = ((text.len() - 4) as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(text);
+        chunk.extend_from_slice(&[0, 0, 0, 0]);
+        let upload = [&png[..33], &chunk, &png[33..]].concat();
+
+        let processed = process(&upload).unwrap();
+
+        assert!(!contains(&processed.bytes, b"home address"));
+        assert_eq!(processed.bytes, png);
+    }
+
+    #[test]
+    fn test_process_webp_strips_exif_chunk() {
+        let mut plain = Vec::new();
+        DynamicImage::ImageRgba8(image::RgbaImage::new(8, 4))
+            .write_to(&mut Cursor::new(&mut plain), ImageFormat::WebP)
+            .unwrap();
+        // Rebuild as an extended WebP: VP8X (EXIF flag) + image + EXIF
+        let mut vp8x = b"VP8X\x0a\0\0\0\x08\0\0\0".to_vec();
+        vp8x.extend_from_slice(&[7, 0, 0, 3, 0, 0]);
+        let exif = [&b"EXIF\x04\0\0\0"[..], b"gps!"].concat();
+        let body = [&b"WEBP"[..], &vp8x, &plain[12..], &exif].concat();
+        let upload = [&b"RIFF"[..], &(body.len() as u32).to_le_bytes(), &body].concat();
+
+        let processed = process(&upload).unwrap();
+
+        assert!(!contains(&processed.bytes, b"gps!"));
+        assert_eq!(processed.bytes[20] & 0x08, 0);
+        let size = u32::from_le_bytes(processed.bytes[4..8].try_into().unwrap()) as usize;
+        assert_eq!(size, processed.bytes.len() - 8);
+        assert_eq!((processed.width, processed.height), (Some(8), Some(4)));
+        assert!(image::load_from_memory(&processed.bytes).is_ok());
+    }
+
+    #[test]
+    fn test_process_rejects_corrupt_and_oversized() {
+        let truncated = &fx_jpeg()[..60];
+        assert!(matches!(process(truncated), Err(MediaError::Corrupt(_))));
+
+        let mut huge = Vec::new();
+        DynamicImage::ImageLuma8(image::GrayImage::new(MAX_IMAGE_DIMENSION + 1, 1))
+            .write_to(&mut Cursor::new(&mut huge), ImageFormat::Png)
+            .unwrap();
+        assert!(matches!(process(&huge), Err(MediaError::Corrupt(_))));
+    }
+
+    #[test]
+    fn test_process_pdf_passes_through() {
+        let pdf = b"%PDF-1.4\n1 0 obj\n<<>>\nendobj\n%%EOF\n";
+
+        let processed = process(pdf).unwrap();
+
+        assert_eq!(processed.media_type, MediaType::Pdf);
+        assert_eq!(processed.bytes, pdf);
+        assert!(processed.thumbnail.is_none());
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,509 @@
+//! # Area Model
+//!
+//! The towns we cover, each rendered as an `/areas/{slug}` landing page.
+//!
+//! Areas are what make landing pages distinct, so two areas may not claim
+//! the same postcode district, and an area's description may not be a
+//! near-copy of another's (see [`crate::text::is_near_duplicate`]).
+//!
+//! ## Structures
+//!
+//! - [`Area`] - Area record from database
+//! - [`AreaForCreate`] - Data required to add an area
+//! - [`AreaForUpdate`] - Data for updating an area
+//! - [`AreaBmc`] - Business Model Controller for areas
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::area::{AreaBmc, AreaForCreate};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn add_kenilworth(mm: &ModelManager) -> Result<i32, Box<dyn std::error::Error>> {
+//!     let area = AreaForCreate {
+//!         slug: None,
+//!         name: "Kenilworth".to_string(),
+//!         description: "Castle town a short hop down the A46.".to_string(),
+//!         postcodes: vec!["CV8".to_string()],
+//!         travel_time: Some("15-20 mins".to_string()),
+//!         testimonial_quote: None,
+//!         testimonial_author: None,
+//!         sort_order: 40,
+//!         active: true,
+//!     };
+//!     let id = AreaBmc::create(mm, area).await?;
+//!     Ok(id)
+//! }
+//! ```
+
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::postcode;
+use crate::text::{is_near_duplicate, is_slug, slugify};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Select list for [`Area`].
+const AREA_SELECT: &str = "SELECT id, slug, name, description, postcodes, travel_time, \
+     testimonial_quote, testimonial_author, sort_order, active, created_at, updated_at \
+     FROM service_areas";
+
+/// Area record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Area {
+    /// Area ID
+    pub id: i32,
+    /// URL slug, e.g. `leamington-spa`
+    pub slug: String,
+    /// Town name
+    pub name: String,
+    /// About working in the area
+    pub description: String,
+    /// Postcode districts covered
+    pub postcodes: Vec<String>,
+    /// Typical travel time, e.g. "Under 20 mins"
+    pub travel_time: Option<String>,
+    /// Local testimonial
+    pub testimonial_quote: Option<String>,
+    /// Who gave the testimonial
+    pub testimonial_author: Option<String>,
+    /// Display order (lowest first)
+    pub sort_order: i32,
+    /// Whether the area's pages are published
+    pub active: bool,
+    /// When the area was added
+    pub created_at: Option<OffsetDateTime>,
+    /// When the area was last updated
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+/// Data required to add an area.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AreaForCreate {
+    /// URL slug (default: from the name)
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Town name
+    pub name: String,
+    /// About working in the area
+    pub description: String,
+    /// Postcode districts, e.g. `CV8`
+    #[serde(default)]
+    pub postcodes: Vec<String>,
+    /// Typical travel time
+    #[serde(default)]
+    pub travel_time: Option<String>,
+    /// Local testimonial
+    #[serde(default)]
+    pub testimonial_quote: Option<String>,
+    /// Who gave the testimonial
+    #[serde(default)]
+    pub testimonial_author: Option<String>,
+    /// Display order (lowest first)
+    #[serde(default)]
+    pub sort_order: i32,
+    /// Whether to publish the area's pages
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Data for updating an area. Omitted fields are left unchanged; postcodes
+/// are replaced whole.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct AreaForUpdate {
+    /// New URL slug
+    pub slug: Option<String>,
+    /// New name
+    pub name: Option<String>,
+    /// New description
+    pub description: Option<String>,
+    /// New postcode districts
+    pub postcodes: Option<Vec<String>>,
+    /// New travel time
+    pub travel_time: Option<String>,
+    /// New testimonial
+    pub testimonial_quote: Option<String>,
+    /// New testimonial author
+    pub testimonial_author: Option<String>,
+    /// New display order
+    pub sort_order: Option<i32>,
+    /// Publish or unpublish
+    pub active: Option<bool>,
+}
+
+/// Business Model Controller for areas.
+pub struct AreaBmc;
+
+impl AreaBmc {
+    /// Adds an area.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `area` - Area to add
+    ///
+    /// # Returns
+    ///
+    /// The new area's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for a blank name or description, an
+    /// invalid slug or postcode, a slug already in use, a postcode another
+    /// area covers, or a description too like another area's.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm, area), fields(name = %area.name))]
+    pub async fn create(mm: &ModelManager, area: AreaForCreate) -> Result<i32> {
+        let slug = area.slug.clone().unwrap_or_else(|| slugify(&area.name));
+        let postcodes = normalize_postcodes(&area.postcodes)?;
+        let others = Self::list(mm, false).await?;
+        check_area(
+            None,
+            &slug,
+            &area.name,
+            &area.description,
+            &postcodes,
+            &others,
+        )?;
+
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO service_areas
+                (slug, name, description, postcodes, travel_time, testimonial_quote,
+                 testimonial_author, sort_order, active)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(&slug)
+        .bind(area.name.trim())
+        .bind(area.description.trim())
+        .bind(&postcodes)
+        .bind(&area.travel_time)
+        .bind(&area.testimonial_quote)
+        .bind(&area.testimonial_author)
+        .bind(area.sort_order)
+        .bind(area.active)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Gets an area by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the area does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<Area> {
+        sqlx::query_as(&format!("{AREA_SELECT} WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "Area",
+                id: id as i64,
+            })
+    }
+
+    /// Gets a published area by slug.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SlugNotFound` if no active area has the slug.
+    #[instrument(skip(mm))]
+    pub async fn get_by_slug(mm: &ModelManager, slug: &str) -> Result<Area> {
+        sqlx::query_as(&format!("{AREA_SELECT} WHERE slug = $1 AND active"))
+            .bind(slug)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or_else(|| Error::SlugNotFound {
+                entity: "Area",
+                slug: slug.to_string(),
+            })
+    }
+
+    /// Lists areas in display order, optionally only published ones.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, active_only: bool) -> Result<Vec<Area>> {
+        let areas = sqlx::query_as(&format!(
+            "{AREA_SELECT} WHERE active OR NOT $1 ORDER BY sort_order, name"
+        ))
+        .bind(active_only)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(areas)
+    }
+
+    /// Updates an area.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the area does not exist, or
+    /// `ValidationError` as for [`AreaBmc::create`].
+    #[instrument(skip(mm, data))]
+    pub async fn update(mm: &ModelManager, id: i32, data: AreaForUpdate) -> Result<()> {
+        let current = Self::get(mm, id).await?;
+        let postcodes = data
+            .postcodes
+            .as_deref()
+            .map(normalize_postcodes)
+            .transpose()?;
+        let others = Self::list(mm, false).await?;
+        check_area(
+            Some(id),
+            data.slug.as_deref().unwrap_or(&current.slug),
+            data.name.as_deref().unwrap_or(&current.name),
+            data.description.as_deref().unwrap_or(&current.description),
+            postcodes.as_deref().unwrap_or(&current.postcodes),
+            &others,
+        )?;
+
+        sqlx::query(
+            r#"
+            UPDATE service_areas
+            SET slug = COALESCE($2, slug),
+                name = COALESCE($3, name),
+                description = COALESCE($4, description),
+                postcodes = COALESCE($5, postcodes),
+                travel_time = COALESCE($6, travel_time),
+                testimonial_quote = COALESCE($7, testimonial_quote),
+                testimonial_author = COALESCE($8, testimonial_author),
+                sort_order = COALESCE($9, sort_order),
+                active = COALESCE($10, active)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(&data.slug)
+        .bind(data.name.as_deref().map(str::trim))
+        .bind(data.description.as_deref().map(str::trim))
+        .bind(&postcodes)
+        .bind(&data.travel_time)
+        .bind(&data.testimonial_quote)
+        .bind(&data.testimonial_author)
+        .bind(data.sort_order)
+        .bind(data.active)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes an area and its service pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the area does not exist.
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM service_areas WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "Area",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+// region:    --- Area Helpers
+
+/// Canonical postcode districts, without repeats.
+fn normalize_postcodes(postcodes: &[String]) -> Result<Vec<String>> {
+    let mut districts: Vec<String> = Vec::with_capacity(postcodes.len());
+    for code in postcodes {
+        let district = postcode::outward(code)
+            .filter(|d| d.chars().any(|c| c.is_ascii_digit()))
+            .ok_or_else(|| {
+                Error::ValidationError(format!("{code} is not a postcode district").into())
+            })?;
+        if !districts.contains(&district) {
+            districts.push(district);
+        }
+    }
+
+    Ok(districts)
+}
+
+/// Checks an area's fields, and that it doesn't duplicate another area.
+fn check_area(
+    id: Option<i32>,
+    slug: &str,
+    name: &str,
+    description: &str,
+    postcodes: &[String],
+    areas: &[Area],
+) -> Result<()> {
+    if name.trim().is_empty() || description.trim().is_empty() {
+        return Err(Error::ValidationError(
+            "An area needs a name and a description".into(),
+        ));
+    }
+    if !is_slug(slug) {
+        return Err(Error::ValidationError(
+            format!(
+                "Slug must be lowercase words joined by hyphens, like {}",
+                slugify(name)
+            )
+            .into(),
+        ));
+    }
+
+    for other in areas.iter().filter(|a| Some(a.id) != id) {
+        if other.slug == slug {
+            return Err(Error::ValidationError(
+                format!("Slug {slug} is already used by {}", other.name).into(),
+            ));
+        }
+        if let Some(code) = postcodes.iter().find(|p| other.postcodes.contains(p)) {
+            return Err(Error::ValidationError(
+                format!("{code} is already covered by {}", other.name).into(),
+            ));
+        }
+        if is_near_duplicate(description, &other.description) {
+            return Err(Error::ValidationError(
+                format!(
+                    "Description is too like {}'s; write one about this area",
+                    other.name
+                )
+                .into(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// endregion: --- Area Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+
+    fn fx_area(id: i32, slug: &str, postcodes: &[&str], description: &str) -> Area {
+        Area {
+            id,
+            slug: slug.to_string(),
+            name: slug.to_uppercase(),
+            description: description.to_string(),
+            postcodes: postcodes.iter().map(|p| p.to_string()).collect(),
+            travel_time: None,
+            testimonial_quote: None,
+            testimonial_author: None,
+            sort_order: 0,
+            active: true,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_postcodes() {
+        let codes = ["cv8", "CV8 1AB", " cv32 "].map(String::from);
+
+        assert_eq!(normalize_postcodes(&codes).unwrap(), ["CV8", "CV32"]);
+        assert!(normalize_postcodes(&["CV".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_check_area() {
+        let warwick = fx_area(
+            1,
+            "warwick",
+            &["CV34", "CV35"],
+            "We look after Warwick's listed town houses and the new estates at Chase Meadow.",
+        );
+        let areas = [warwick];
+        let check = |id, slug, postcodes: &[&str], description| {
+            let postcodes: Vec<String> = postcodes.iter().map(|p| p.to_string()).collect();
+            check_area(id, slug, "Somewhere", description, &postcodes, &areas)
+        };
+
+        assert!(check(
+            None,
+            "kenilworth",
+            &["CV8"],
+            "Castle town a short hop down the A46."
+        )
+        .is_ok());
+        assert!(check(None, "Kenilworth", &["CV8"], "Castle town.").is_err());
+        assert!(check(None, "warwick", &["CV8"], "Castle town.").is_err());
+        assert!(check(None, "kenilworth", &["CV35"], "Castle town.").is_err());
+        assert!(check(
+            None,
+            "kenilworth",
+            &["CV8"],
+            "We look after Kenilworth's listed town houses and the new estates at Chase Meadow."
+        )
+        .is_err());
+        // An area may keep its own slug, postcodes and description
+        assert!(check(Some(1), "warwick", &["CV34"], &areas[0].description.clone()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_get_update_delete() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let suffix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let area = AreaForCreate {
+            slug: Some(format!("test-area-{suffix}")),
+            name: "Test Area".to_string(),
+            description: format!("A place that exists only for test {suffix}."),
+            postcodes: vec![],
+            travel_time: Some("5 mins".to_string()),
+            testimonial_quote: None,
+            testimonial_author: None,
+            sort_order: 999,
+            active: true,
+        };
+
+        // Execute
+        let id = AreaBmc::create(&mm, area.clone()).await?;
+        let duplicate = AreaBmc::create(&mm, area.clone()).await;
+        AreaBmc::update(
+            &mm,
+            id,
+            AreaForUpdate {
+                active: Some(false),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        // Check
+        assert!(duplicate.is_err(), "Slug must be unique");
+        assert_eq!(
+            AreaBmc::get(&mm, id).await?.travel_time.as_deref(),
+            Some("5 mins")
+        );
+        let hidden = AreaBmc::get_by_slug(&mm, area.slug.as_deref().unwrap()).await;
+        assert!(matches!(hidden, Err(Error::SlugNotFound { .. })));
+
+        // Cleanup
+        AreaBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,642 @@
+//! # Attachment Model
+//!
+//! Photos and documents attached to a booking or a quote: before and after
+//! shots of the job, receipts for materials, photos a customer sent to be
+//! priced.
+//!
+//! Uploads go through [`crate::media::process`] before anything is stored,
+//! so the stored file is a sniffed JPEG, PNG, WebP or PDF with its metadata
+//! (including GPS position) stripped. The bytes live in a [`BlobStore`];
+//! the table only keeps metadata and the storage keys, which are never sent
+//! to clients. Files are served through short-lived signed links (see
+//! [`crate::document::link::attachment_link`]).
+//!
+//! Deleting a booking or quote removes its attachment rows; delete
+//! attachments through [`AttachmentBmc::delete`] first to remove the files
+//! as well.
+//!
+//! ## Structures
+//!
+//! - [`Attachment`] - Complete attachment record from database
+//! - [`AttachmentOwner`] - Booking or quote an attachment belongs to
+//! - [`AttachmentForCreate`] - An upload to store
+//! - [`AttachmentBmc`] - Business Model Controller for attachment operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::attachment::{AttachmentBmc, AttachmentForCreate, AttachmentOwner};
+//! use lib_core::model::ModelManager;
+//! use lib_core::storage::blob_store;
+//!
+//! async fn attach_photo(mm: &ModelManager) -> Result<i32, Box<dyn std::error::Error>> {
+//!     let upload = AttachmentForCreate {
+//!         owner: AttachmentOwner::Booking(42),
+//!         category: Some("before".to_string()),
+//!         filename: Some("IMG_0001.jpg".to_string()),
+//!         bytes: std::fs::read("IMG_0001.jpg")?,
+//!     };
+//!     let id = AttachmentBmc::create(mm, blob_store()?, upload).await?;
+//!     Ok(id)
+//! }
+//! ```
+
+use crate::core_config;
+use crate::document::link::AttachmentVariant;
+use crate::media::{self, MediaError};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::storage::BlobStore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::{instrument, warn};
+use utoipa::ToSchema;
+
+/// Allowed values of [`Attachment::category`].
+pub const ATTACHMENT_CATEGORIES: &[&str] = &["before", "after", "receipt", "other"];
+
+/// Columns selected for [`Attachment`].
+const ATTACHMENT_COLUMNS: &str = "id, booking_id, quote_id, category, filename, content_type, \
+     size_bytes, sha256, width, height, storage_key, thumbnail_key, uploaded_at";
+
+/// Complete attachment record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Attachment {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Booking the file is attached to
+    pub booking_id: Option<i32>,
+    /// Quote the file is attached to
+    pub quote_id: Option<i32>,
+    /// One of [`ATTACHMENT_CATEGORIES`]
+    pub category: String,
+    /// Sanitised filename, with an extension matching the content
+    pub filename: String,
+    /// Sniffed MIME type
+    pub content_type: String,
+    /// Size of the stored file in bytes
+    pub size_bytes: i32,
+    /// Hex SHA-256 of the stored file
+    pub sha256: String,
+    /// Width in pixels, for images
+    pub width: Option<i32>,
+    /// Height in pixels, for images
+    pub height: Option<i32>,
+    /// Blob store key of the file
+    #[serde(skip)]
+    pub storage_key: String,
+    /// Blob store key of the thumbnail, for images
+    #[serde(skip)]
+    pub thumbnail_key: Option<String>,
+    /// When the file was uploaded
+    pub uploaded_at: OffsetDateTime,
+}
+
+/// Booking or quote an attachment belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentOwner {
+    Booking(i32),
+    Quote(i32),
+}
+
+impl AttachmentOwner {
+    fn entity(self) -> &'static str {
+        match self {
+            AttachmentOwner::Booking(_) => "Booking",
+            AttachmentOwner::Quote(_) => "Quote",
+        }
+    }
+
+    /// Table name, also used as the storage key prefix.
+    fn table(self) -> &'static str {
+        match self {
+            AttachmentOwner::Booking(_) => "bookings",
+            AttachmentOwner::Quote(_) => "quotes",
+        }
+    }
+
+    fn id(self) -> i32 {
+        match self {
+            AttachmentOwner::Booking(id) | AttachmentOwner::Quote(id) => id,
+        }
+    }
+}
+
+/// An upload to store.
+#[derive(Clone)]
+pub struct AttachmentForCreate {
+    /// What the file is attached to
+    pub owner: AttachmentOwner,
+    /// One of [`ATTACHMENT_CATEGORIES`] (default `other`)
+    pub category: Option<String>,
+    /// Filename given by the client
+    pub filename: Option<String>,
+    /// File contents as uploaded
+    pub bytes: Vec<u8>,
+}
+
+/// Business Model Controller for attachment operations.
+pub struct AttachmentBmc;
+
+impl AttachmentBmc {
+    /// Validates, cleans and stores an upload.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `store` - Where to keep the file and its thumbnail
+    /// * `upload` - The uploaded file and what it belongs to
+    ///
+    /// # Returns
+    ///
+    /// The auto-generated ID of the new attachment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for empty, oversized, unsupported or
+    /// corrupt files and unknown categories, `EntityNotFound` if the
+    /// booking or quote does not exist, or `Storage` if the blob store
+    /// fails.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm, store, upload), fields(owner = ?upload.owner))]
+    pub async fn create(
+        mm: &ModelManager,
+        store: &dyn BlobStore,
+        upload: AttachmentForCreate,
+    ) -> Result<i32> {
+        let max_bytes = core_config().ATTACHMENT_MAX_BYTES;
+        if upload.bytes.is_empty() {
+            return Err(Error::ValidationError("The file is empty".into()));
+        }
+        if upload.bytes.len() > max_bytes {
+            return Err(Error::ValidationError(
+                format!("Files must be no larger than {}", format_size(max_bytes)).into(),
+            ));
+        }
+        let category = upload.category.as_deref().unwrap_or("other");
+        if !ATTACHMENT_CATEGORIES.contains(&category) {
+            return Err(Error::ValidationError(
+                format!(
+                    "Category must be one of: {}",
+                    ATTACHMENT_CATEGORIES.join(", ")
+                )
+                .into(),
+            ));
+        }
+        let owner = upload.owner;
+        ensure_owner_exists(mm, owner).await?;
+
+        // Decoding a large photo takes long enough to stall the runtime
+        let bytes = upload.bytes;
+        let processed = tokio::task::spawn_blocking(move || media::process(&bytes))
+            .await
+            .unwrap_or_else(|_| Err(MediaError::Corrupt("decoding failed".into())))
+            .map_err(|e| Error::ValidationError(e.to_string().into()))?;
+
+        let extension = processed.media_type.extension();
+        let filename = sanitize_filename(upload.filename.as_deref(), extension);
+        let sha256: String = Sha256::digest(&processed.bytes)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        let stem = format!("{}/{}/{}", owner.table(), owner.id(), uuid::Uuid::new_v4());
+        let storage_key = format!("{stem}.{extension}");
+        let thumbnail_key = processed
+            .thumbnail
+            .as_ref()
+            .map(|_| format!("{stem}_thumb.jpg"));
+        let size_bytes = i32::try_from(processed.bytes.len())
+            .map_err(|_| Error::ValidationError("The file is too large".into()))?;
+
+        store
+            .put(&storage_key, processed.bytes, processed.media_type.mime())
+            .await?;
+        if let (Some(key), Some(thumbnail)) = (&thumbnail_key, processed.thumbnail) {
+            if let Err(e) = store.put(key, thumbnail, "image/jpeg").await {
+                discard(store, &storage_key, None).await;
+                return Err(e.into());
+            }
+        }
+
+        let inserted: std::result::Result<(i32,), sqlx::Error> = sqlx::query_as(
+            r#"
+            INSERT INTO attachments (booking_id, quote_id, category, filename, content_type,
+                                     size_bytes, sha256, width, height, storage_key,
+                                     thumbnail_key)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING id
+            "#,
+        )
+        .bind(match owner {
+            AttachmentOwner::Booking(id) => Some(id),
+            AttachmentOwner::Quote(_) => None,
+        })
+        .bind(match owner {
+            AttachmentOwner::Quote(id) => Some(id),
+            AttachmentOwner::Booking(_) => None,
+        })
+        .bind(category)
+        .bind(&filename)
+        .bind(processed.media_type.mime())
+        .bind(size_bytes)
+        .bind(&sha256)
+        .bind(processed.width.map(|w| w as i32))
+        .bind(processed.height.map(|h| h as i32))
+        .bind(&storage_key)
+        .bind(&thumbnail_key)
+        .fetch_one(mm.dbx().db())
+        .await;
+
+        match inserted {
+            Ok((id,)) => Ok(id),
+            Err(e) => {
+                discard(store, &storage_key, thumbnail_key.as_deref()).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Gets an attachment by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the attachment does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<Attachment> {
+        sqlx::query_as(&format!(
+            "SELECT {ATTACHMENT_COLUMNS} FROM attachments WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "Attachment",
+            id: id as i64,
+        })
+    }
+
+    /// Lists a booking's or quote's attachments, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the booking or quote does not exist.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, owner: AttachmentOwner) -> Result<Vec<Attachment>> {
+        ensure_owner_exists(mm, owner).await?;
+
+        let column = match owner {
+            AttachmentOwner::Booking(_) => "booking_id",
+            AttachmentOwner::Quote(_) => "quote_id",
+        };
+        let attachments = sqlx::query_as(&format!(
+            "SELECT {ATTACHMENT_COLUMNS} FROM attachments WHERE {column} = $1 \
+             ORDER BY uploaded_at ASC, id ASC"
+        ))
+        .bind(owner.id())
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(attachments)
+    }
+
+    /// Lists the attachments of any of the given bookings and quotes.
+    #[instrument(skip(mm))]
+    pub async fn list_for_owners(
+        mm: &ModelManager,
+        booking_ids: &[i32],
+        quote_ids: &[i32],
+    ) -> Result<Vec<Attachment>> {
+        let attachments = sqlx::query_as(&format!(
+            "SELECT {ATTACHMENT_COLUMNS} FROM attachments \
+             WHERE booking_id = ANY($1) OR quote_id = ANY($2) ORDER BY id ASC"
+        ))
+        .bind(booking_ids)
+        .bind(quote_ids)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(attachments)
+    }
+
+    /// Reads a stored file.
+    ///
+    /// # Returns
+    ///
+    /// The bytes and the MIME type to serve them with.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` when asking for the thumbnail of a PDF, or
+    /// `Storage` if the blob store fails.
+    #[instrument(skip(store, attachment), fields(id = attachment.id))]
+    pub async fn read(
+        store: &dyn BlobStore,
+        attachment: &Attachment,
+        variant: AttachmentVariant,
+    ) -> Result<(Vec<u8>, String)> {
+        match variant {
+            AttachmentVariant::Original => Ok((
+                store.get(&attachment.storage_key).await?,
+                attachment.content_type.clone(),
+            )),
+            AttachmentVariant::Thumbnail => {
+                let key = attachment
+                    .thumbnail_key
+                    .as_deref()
+                    .ok_or(Error::EntityNotFound {
+                        entity: "AttachmentThumbnail",
+                        id: attachment.id as i64,
+                    })?;
+                Ok((store.get(key).await?, "image/jpeg".to_string()))
+            }
+        }
+    }
+
+    /// Deletes an attachment and its files.
+    ///
+    /// The row goes first; if removing a file then fails it is logged and
+    /// left behind rather than leaving a row that points at nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the attachment does not exist.
+    #[instrument(skip(mm, store))]
+    pub async fn delete(mm: &ModelManager, store: &dyn BlobStore, id: i32) -> Result<()> {
+        let attachment: Attachment = sqlx::query_as(&format!(
+            "DELETE FROM attachments WHERE id = $1 RETURNING {ATTACHMENT_COLUMNS}"
+        ))
+        .bind(id)
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "Attachment",
+            id: id as i64,
+        })?;
+
+        discard(
+            store,
+            &attachment.storage_key,
+            attachment.thumbnail_key.as_deref(),
+        )
+        .await;
+
+        Ok(())
+    }
+}
+
+// region:    --- Attachment Helpers
+
+async fn ensure_owner_exists(mm: &ModelManager, owner: AttachmentOwner) -> Result<()> {
+    let (exists,): (bool,) = sqlx::query_as(&format!(
+        "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1)",
+        owner.table()
+    ))
+    .bind(owner.id())
+    .fetch_one(mm.dbx().db())
+    .await?;
+
+    if !exists {
+        return Err(Error::EntityNotFound {
+            entity: owner.entity(),
+            id: owner.id() as i64,
+        });
+    }
+
+    Ok(())
+}
+
+/// Best-effort removal of stored files that no row refers to.
+async fn discard(store: &dyn BlobStore, storage_key: &str, thumbnail_key: Option<&str>) {
+    for key in std::iter::once(storage_key).chain(thumbnail_key) {
+        if let Err(e) = store.delete(key).await {
+            warn!("Failed to delete attachment blob {}: {}", key, e);
+        }
+    }
+}
+
+/// Makes a client filename safe to store and show: no directories, only
+/// plain characters, at most 100 characters, and an extension matching
+/// the sniffed content.
+fn sanitize_filename(filename: Option<&str>, extension: &str) -> String {
+    let name = filename
+        .and_then(|f| f.rsplit(['/', '\\']).next())
+        .unwrap_or_default();
+    let stem = match name.rsplit_once('.') {
+        Some((stem, _)) => stem,
+        None => name,
+    };
+    let stem: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '(' | ')') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(100)
+        .collect();
+    let stem = stem.trim_matches(|c: char| c == ' ' || c == '_');
+
+    if stem.is_empty() {
+        format!("attachment.{extension}")
+    } else {
+        format!("{stem}.{extension}")
+    }
+}
+
+/// `10485760` -> `10 MB`
+fn format_size(bytes: usize) -> String {
+    const MB: usize = 1024 * 1024;
+    if bytes >= MB && bytes.is_multiple_of(MB) {
+        format!("{} MB", bytes / MB)
+    } else if bytes >= 1024 {
+        format!("{} KB", bytes / 1024)
+    } else {
+        format!("{bytes} bytes")
+    }
+}
+
+// endregion: --- Attachment Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::booking::{BookingBmc, BookingForCreate};
+    use crate::storage::LocalBlobStore;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use std::io::Cursor;
+
+    fn fx_png(width: u32, height: u32) -> Vec<u8> {
+        let mut png = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(width, height))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        png
+    }
+
+    async fn fx_booking(mm: &ModelManager) -> Result<i32> {
+        BookingBmc::create(
+            mm,
+            BookingForCreate {
+                customer_id: None,
+                service_type: "plumbing".to_string(),
+                scheduled_date: None,
+                scheduled_time: None,
+                notes: Some("attachment test".to_string()),
+            },
+        )
+        .await
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(
+            sanitize_filename(Some("IMG_0001.JPG"), "jpg"),
+            "IMG_0001.jpg"
+        );
+        assert_eq!(
+            sanitize_filename(Some("C:\\Users\\me\\kitchen tap.png"), "png"),
+            "kitchen tap.png"
+        );
+        assert_eq!(
+            sanitize_filename(Some("../../etc/passwd"), "pdf"),
+            "passwd.pdf"
+        );
+        assert_eq!(
+            sanitize_filename(Some("invoice.pdf.exe"), "pdf"),
+            "invoice_pdf.pdf"
+        );
+        assert_eq!(sanitize_filename(Some("<script>"), "png"), "script.png");
+        assert_eq!(sanitize_filename(None, "webp"), "attachment.webp");
+        assert_eq!(sanitize_filename(Some(".jpg"), "jpg"), "attachment.jpg");
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(10 * 1024 * 1024), "10 MB");
+        assert_eq!(format_size(1536 * 1024), "1536 KB");
+        assert_eq!(format_size(500), "500 bytes");
+    }
+
+    #[tokio::test]
+    async fn test_attachment_create_read_delete() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let root = std::env::temp_dir().join(format!("attachments-{}", uuid::Uuid::new_v4()));
+        let store = LocalBlobStore::new(&root);
+        let booking_id = fx_booking(&mm).await?;
+
+        // Execute
+        let id = AttachmentBmc::create(
+            &mm,
+            &store,
+            AttachmentForCreate {
+                owner: AttachmentOwner::Booking(booking_id),
+                category: Some("before".to_string()),
+                filename: Some("leak.jpeg".to_string()),
+                bytes: fx_png(600, 300),
+            },
+        )
+        .await?;
+        let attachment = AttachmentBmc::get(&mm, id).await?;
+        let listed = AttachmentBmc::list(&mm, AttachmentOwner::Booking(booking_id)).await?;
+        let (original, original_type) =
+            AttachmentBmc::read(&store, &attachment, AttachmentVariant::Original).await?;
+        let (thumbnail, thumbnail_type) =
+            AttachmentBmc::read(&store, &attachment, AttachmentVariant::Thumbnail).await?;
+
+        // Check
+        assert_eq!(attachment.booking_id, Some(booking_id));
+        assert_eq!(
+            attachment.filename, "leak.png",
+            "extension follows the content"
+        );
+        assert_eq!(attachment.content_type, "image/png");
+        assert_eq!(
+            (attachment.width, attachment.height),
+            (Some(600), Some(300))
+        );
+        assert_eq!(attachment.size_bytes as usize, original.len());
+        assert_eq!(listed.len(), 1);
+        assert_eq!(original_type, "image/png");
+        assert_eq!(thumbnail_type, "image/jpeg");
+        let thumb = image::load_from_memory(&thumbnail).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (320, 160));
+
+        AttachmentBmc::delete(&mm, &store, id).await?;
+        assert!(matches!(
+            AttachmentBmc::get(&mm, id).await,
+            Err(Error::EntityNotFound { .. })
+        ));
+        assert!(store.get(&attachment.storage_key).await.is_err());
+
+        // Cleanup
+        sqlx::query("DELETE FROM bookings WHERE id = $1")
+            .bind(booking_id)
+            .execute(mm.dbx().db())
+            .await?;
+        let _ = std::fs::remove_dir_all(root);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_attachment_create_rejects_bad_uploads() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let root = std::env::temp_dir().join(format!("attachments-{}", uuid::Uuid::new_v4()));
+        let store = LocalBlobStore::new(&root);
+        let booking_id = fx_booking(&mm).await?;
+        let upload = |owner, category: &str, bytes: Vec<u8>| AttachmentForCreate {
+            owner,
+            category: Some(category.to_string()),
+            filename: None,
+            bytes,
+        };
+
+        // Execute & Check
+        for bad in [
+            upload(AttachmentOwner::Booking(booking_id), "other", vec![]),
+            upload(
+                AttachmentOwner::Booking(booking_id),
+                "other",
+                b"<html>".to_vec(),
+            ),
+            upload(AttachmentOwner::Booking(booking_id), "selfie", fx_png(4, 4)),
+        ] {
+            assert!(matches!(
+                AttachmentBmc::create(&mm, &store, bad).await,
+                Err(Error::ValidationError(_))
+            ));
+        }
+        assert!(matches!(
+            AttachmentBmc::create(
+                &mm,
+                &store,
+                upload(AttachmentOwner::Quote(-1), "other", fx_png(4, 4))
+            )
+            .await,
+            Err(Error::EntityNotFound {
+                entity: "Quote",
+                ..
+            })
+        ));
+        assert!(!root.exists(), "nothing is stored for rejected uploads");
+
+        // Cleanup
+        sqlx::query("DELETE FROM bookings WHERE id = $1")
+            .bind(booking_id)
+            .execute(mm.dbx().db())
+            .await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,130 @@
+//! # Audit Log Model
+//!
+//! Append-only record of sensitive admin operations such as subject access
+//! exports and erasures.
+//!
+//! ## Structures
+//!
+//! - [`AuditLogEntry`] - Complete audit log record from database
+//! - [`AuditLogForCreate`] - Data required to record an action
+//! - [`AuditLogBmc`] - Business Model Controller for audit log operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::audit_log::{AuditLogBmc, AuditLogForCreate};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn record(mm: &ModelManager) -> Result<i32, Box<dyn std::error::Error>> {
+//!     let entry = AuditLogForCreate {
+//!         action: "gdpr_export".to_string(),
+//!         actor: "admin".to_string(),
+//!         subject_email: Some("john@example.com".to_string()),
+//!         details: None,
+//!     };
+//!     let id = AuditLogBmc::create(mm, entry).await?;
+//!     Ok(id)
+//! }
+//! ```
+
+use crate::model::ModelManager;
+use crate::model::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Complete audit log record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct AuditLogEntry {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Action performed, e.g. 'gdpr_export', 'gdpr_erasure'
+    pub action: String,
+    /// Who performed the action
+    pub actor: String,
+    /// Email of the data subject (NULL once erased)
+    pub subject_email: Option<String>,
+    /// Action-specific details
+    pub details: Option<serde_json::Value>,
+    /// When the action was performed
+    pub created_at: Option<OffsetDateTime>,
+}
+
+/// Data required to record an action.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AuditLogForCreate {
+    /// Action performed
+    pub action: String,
+    /// Who performed the action
+    pub actor: String,
+    /// Email of the data subject
+    pub subject_email: Option<String>,
+    /// Action-specific details
+    pub details: Option<serde_json::Value>,
+}
+
+/// Business Model Controller for audit log operations.
+pub struct AuditLogBmc;
+
+impl AuditLogBmc {
+    /// Records an action.
+    ///
+    /// Runs on the model manager's transaction when it has one, so the entry
+    /// is only kept if the audited operation commits.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `entry` - Action to record
+    ///
+    /// # Returns
+    ///
+    /// The auto-generated ID of the log entry.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm), fields(action = %entry.action))]
+    pub async fn create(mm: &ModelManager, entry: AuditLogForCreate) -> Result<i32> {
+        let row: (i32,) = mm
+            .dbx()
+            .fetch_one(
+                sqlx::query_as(
+                    r#"
+                    INSERT INTO audit_log (action, actor, subject_email, details)
+                    VALUES ($1, $2, $3, $4)
+                    RETURNING id
+                    "#,
+                )
+                .bind(&entry.action)
+                .bind(&entry.actor)
+                .bind(&entry.subject_email)
+                .bind(&entry.details),
+            )
+            .await?;
+
+        Ok(row.0)
+    }
+
+    /// Lists actions concerning a data subject, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `email` - Subject email (case-insensitive)
+    #[instrument(skip(mm))]
+    pub async fn list_by_subject(mm: &ModelManager, email: &str) -> Result<Vec<AuditLogEntry>> {
+        let entries = sqlx::query_as(
+            r#"
+            SELECT id, action, actor, subject_email, details, created_at
+            FROM audit_log
+            WHERE lower(subject_email) = lower($1)
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(email)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(entries)
+    }
+}
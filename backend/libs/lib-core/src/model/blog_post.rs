@@ -0,0 +1,586 @@
+//! # Blog Post Model
+//!
+//! Blog posts, written and scheduled from the admin API instead of being
+//! compiled into the site.
+//!
+//! A post is a `draft` until it is `published` or `scheduled`. Readers see
+//! it once it is no longer a draft and its `publish_at` has passed, so a
+//! scheduled post goes live on its own, with no redeploy or background
+//! job. Publishing without a date publishes now.
+//!
+//! Bodies are stored as the Markdown they were written in and rendered to
+//! sanitised HTML (see [`crate::content`]) each time a post is read for the
+//! site, so a change to the allow-list applies to every post.
+//!
+//! ## Structures
+//!
+//! - [`BlogPostRecord`] - Blog post record from database
+//! - [`BlogPostForCreate`] - Data required to write a post
+//! - [`BlogPostForUpdate`] - Data for updating a post
+//! - [`BlogPostBmc`] - Business Model Controller for blog posts
+//!
+//! The published post type itself ([`BlogPost`]) lives in the `shared`
+//! crate so the Leptos site can render it.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::blog_post::{BlogPostBmc, BlogPostForCreate};
+//! use lib_core::model::ModelManager;
+//! use time::{Duration, OffsetDateTime};
+//!
+//! async fn schedule(mm: &ModelManager) -> Result<i32, Box<dyn std::error::Error>> {
+//!     let post = BlogPostForCreate {
+//!         slug: None,
+//!         title: "Winter Boiler Checks".to_string(),
+//!         description: "Five checks before the cold sets in.".to_string(),
+//!         category: "trust-protocols".to_string(),
+//!         keywords: vec!["boiler service".to_string()],
+//!         author: "XF Tradesmen".to_string(),
+//!         body: "## Bleed your radiators\n\nStart at the top floor.".to_string(),
+//!         status: "scheduled".to_string(),
+//!         publish_at: Some(OffsetDateTime::now_utc() + Duration::days(7)),
+//!     };
+//!     let id = BlogPostBmc::create(mm, post).await?;
+//!     Ok(id)
+//! }
+//! ```
+
+use crate::content;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::text::{is_slug, slugify};
+use serde::{Deserialize, Serialize};
+use shared::types::blog::BLOG_CATEGORIES;
+use shared::BlogPost;
+use sqlx::FromRow;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Allowed values of [`BlogPostRecord::status`].
+pub const BLOG_STATUSES: &[&str] = &["draft", "scheduled", "published"];
+
+/// Select list for [`BlogPostRecord`].
+const BLOG_POST_SELECT: &str = "SELECT id, slug, title, description, category, keywords, \
+     author, body, status, publish_at, created_at, updated_at FROM blog_posts";
+
+/// Condition for posts readers can see.
+const VISIBLE: &str = "status <> 'draft' AND publish_at <= NOW()";
+
+/// Blog post record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct BlogPostRecord {
+    /// Post ID
+    pub id: i32,
+    /// URL slug, e.g. `local-seo-guide`
+    pub slug: String,
+    /// Headline
+    pub title: String,
+    /// Summary for cards and meta descriptions
+    pub description: String,
+    /// Category slug, one of `shared::BLOG_CATEGORIES`
+    pub category: String,
+    /// Search keywords
+    pub keywords: Vec<String>,
+    /// Who wrote it
+    pub author: String,
+    /// Article body as Markdown
+    pub body: String,
+    /// `draft`, `scheduled` or `published`
+    pub status: String,
+    /// When the post goes (or went) live
+    pub publish_at: Option<OffsetDateTime>,
+    /// When the post was written
+    pub created_at: Option<OffsetDateTime>,
+    /// When the post was last changed
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl BlogPostRecord {
+    /// Whether readers can see the post at `now`.
+    pub fn is_visible(&self, now: OffsetDateTime) -> bool {
+        self.status != "draft" && self.publish_at.is_some_and(|at| at <= now)
+    }
+}
+
+impl From<BlogPostRecord> for BlogPost {
+    fn from(post: BlogPostRecord) -> Self {
+        let rfc3339 = |at: OffsetDateTime| at.format(&Rfc3339).unwrap_or_default();
+        let body = content::render(&post.body);
+        Self {
+            slug: post.slug,
+            title: post.title,
+            description: post.description,
+            category: post.category,
+            keywords: post.keywords,
+            author: post.author,
+            read_minutes: body.read_minutes(),
+            content: body.html,
+            toc: body.toc,
+            images: body.images,
+            published_at: post.publish_at.map(rfc3339).unwrap_or_default(),
+            updated_at: post.updated_at.map(rfc3339),
+        }
+    }
+}
+
+/// Data required to write a post.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct BlogPostForCreate {
+    /// URL slug (default: from the title)
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Headline
+    pub title: String,
+    /// Summary for cards and meta descriptions
+    pub description: String,
+    /// Category slug
+    pub category: String,
+    /// Search keywords
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Who wrote it
+    pub author: String,
+    /// Article body as Markdown
+    pub body: String,
+    /// `draft` (default), `scheduled` or `published`
+    #[serde(default = "default_status")]
+    pub status: String,
+    /// When to go live; required when scheduled (default when published: now)
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub publish_at: Option<OffsetDateTime>,
+}
+
+fn default_status() -> String {
+    "draft".to_string()
+}
+
+/// Data for updating a post. Omitted fields are left unchanged; keywords
+/// are replaced whole.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct BlogPostForUpdate {
+    /// New URL slug
+    pub slug: Option<String>,
+    /// New headline
+    pub title: Option<String>,
+    /// New summary
+    pub description: Option<String>,
+    /// New category slug
+    pub category: Option<String>,
+    /// New keywords
+    pub keywords: Option<Vec<String>>,
+    /// New author
+    pub author: Option<String>,
+    /// New body
+    pub body: Option<String>,
+    /// New status
+    pub status: Option<String>,
+    /// New publication time
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub publish_at: Option<OffsetDateTime>,
+}
+
+/// Business Model Controller for blog posts.
+pub struct BlogPostBmc;
+
+impl BlogPostBmc {
+    /// Writes a post.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `post` - Post to write
+    ///
+    /// # Returns
+    ///
+    /// The new post's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for a blank title, description, author or
+    /// body, an invalid or taken slug, an unknown category or status, or a
+    /// scheduled post without a publication time.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm, post), fields(title = %post.title))]
+    pub async fn create(mm: &ModelManager, post: BlogPostForCreate) -> Result<i32> {
+        let slug = post.slug.clone().unwrap_or_else(|| slugify(&post.title));
+        check_post(
+            &slug,
+            &[&post.title, &post.description, &post.author, &post.body],
+            &post.category,
+            &post.status,
+            post.publish_at,
+        )?;
+        check_slug_free(mm, None, &slug).await?;
+
+        let (id,): (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO blog_posts
+                (slug, title, description, category, keywords, author, body, status, publish_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8,
+                    CASE WHEN $8 = 'published' THEN COALESCE($9, NOW()) ELSE $9 END)
+            RETURNING id
+            "#,
+        )
+        .bind(&slug)
+        .bind(post.title.trim())
+        .bind(post.description.trim())
+        .bind(&post.category)
+        .bind(&post.keywords)
+        .bind(post.author.trim())
+        .bind(&post.body)
+        .bind(&post.status)
+        .bind(post.publish_at)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Gets a post by ID, whatever its status.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the post does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<BlogPostRecord> {
+        sqlx::query_as(&format!("{BLOG_POST_SELECT} WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "BlogPost",
+                id: id as i64,
+            })
+    }
+
+    /// Lists posts, newest first, optionally only those with a status.
+    /// Drafts without a publication time come first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for an unknown status.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, status: Option<&str>) -> Result<Vec<BlogPostRecord>> {
+        if let Some(status) = status.filter(|s| !BLOG_STATUSES.contains(s)) {
+            return Err(Error::ValidationError(
+                format!("Unknown blog post status {status}").into(),
+            ));
+        }
+
+        let posts = sqlx::query_as(&format!(
+            "{BLOG_POST_SELECT} WHERE $1::TEXT IS NULL OR status = $1 \
+             ORDER BY publish_at DESC NULLS FIRST, id DESC"
+        ))
+        .bind(status)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(posts)
+    }
+
+    /// Gets a post readers can see, by slug.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SlugNotFound` if no visible post has the slug.
+    #[instrument(skip(mm))]
+    pub async fn get_published(mm: &ModelManager, slug: &str) -> Result<BlogPost> {
+        let post: BlogPostRecord =
+            sqlx::query_as(&format!("{BLOG_POST_SELECT} WHERE slug = $1 AND {VISIBLE}"))
+                .bind(slug)
+                .fetch_optional(mm.dbx().db())
+                .await?
+                .ok_or_else(|| Error::SlugNotFound {
+                    entity: "BlogPost",
+                    slug: slug.to_string(),
+                })?;
+
+        Ok(post.into())
+    }
+
+    /// Lists the posts readers can see, newest first, optionally only
+    /// those in a category.
+    #[instrument(skip(mm))]
+    pub async fn list_published(
+        mm: &ModelManager,
+        category: Option<&str>,
+    ) -> Result<Vec<BlogPost>> {
+        let posts: Vec<BlogPostRecord> = sqlx::query_as(&format!(
+            "{BLOG_POST_SELECT} WHERE {VISIBLE} AND ($1::TEXT IS NULL OR category = $1) \
+             ORDER BY publish_at DESC, id DESC"
+        ))
+        .bind(category)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(posts.into_iter().map(BlogPost::from).collect())
+    }
+
+    /// Updates a post.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the post does not exist, or
+    /// `ValidationError` as for [`BlogPostBmc::create`].
+    #[instrument(skip(mm, data))]
+    pub async fn update(mm: &ModelManager, id: i32, data: BlogPostForUpdate) -> Result<()> {
+        let current = Self::get(mm, id).await?;
+        let slug = data.slug.as_deref().unwrap_or(&current.slug);
+        check_post(
+            slug,
+            &[
+                data.title.as_deref().unwrap_or(&current.title),
+                data.description.as_deref().unwrap_or(&current.description),
+                data.author.as_deref().unwrap_or(&current.author),
+                data.body.as_deref().unwrap_or(&current.body),
+            ],
+            data.category.as_deref().unwrap_or(&current.category),
+            data.status.as_deref().unwrap_or(&current.status),
+            data.publish_at.or(current.publish_at),
+        )?;
+        check_slug_free(mm, Some(id), slug).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE blog_posts
+            SET slug = COALESCE($2, slug),
+                title = COALESCE($3, title),
+                description = COALESCE($4, description),
+                category = COALESCE($5, category),
+                keywords = COALESCE($6, keywords),
+                author = COALESCE($7, author),
+                body = COALESCE($8, body),
+                status = COALESCE($9, status),
+                publish_at = CASE WHEN COALESCE($9, status) = 'published'
+                                  THEN COALESCE($10, publish_at, NOW())
+                                  ELSE COALESCE($10, publish_at) END
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(&data.slug)
+        .bind(data.title.as_deref().map(str::trim))
+        .bind(data.description.as_deref().map(str::trim))
+        .bind(&data.category)
+        .bind(&data.keywords)
+        .bind(data.author.as_deref().map(str::trim))
+        .bind(&data.body)
+        .bind(&data.status)
+        .bind(data.publish_at)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes a post.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the post does not exist.
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM blog_posts WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "BlogPost",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+// region:    --- Blog Post Helpers
+
+/// Checks a post's fields. `required` are the title, description, author
+/// and body.
+fn check_post(
+    slug: &str,
+    required: &[&str],
+    category: &str,
+    status: &str,
+    publish_at: Option<OffsetDateTime>,
+) -> Result<()> {
+    if required.iter().any(|field| field.trim().is_empty()) {
+        return Err(Error::ValidationError(
+            "A post needs a title, description, author and body".into(),
+        ));
+    }
+    if !is_slug(slug) {
+        return Err(Error::ValidationError(
+            format!(
+                "Slug must be lowercase words joined by hyphens, like {}",
+                slugify(slug)
+            )
+            .into(),
+        ));
+    }
+    if !BLOG_CATEGORIES.iter().any(|(c, _)| *c == category) {
+        return Err(Error::ValidationError(
+            format!("Unknown blog category {category}").into(),
+        ));
+    }
+    if !BLOG_STATUSES.contains(&status) {
+        return Err(Error::ValidationError(
+            format!("Unknown blog post status {status}").into(),
+        ));
+    }
+    if status == "scheduled" && publish_at.is_none() {
+        return Err(Error::ValidationError(
+            "A scheduled post needs a publication time".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks no other post uses `slug`.
+async fn check_slug_free(mm: &ModelManager, id: Option<i32>, slug: &str) -> Result<()> {
+    let (taken,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM blog_posts WHERE slug = $1 AND id IS DISTINCT FROM $2)",
+    )
+    .bind(slug)
+    .bind(id)
+    .fetch_one(mm.dbx().db())
+    .await?;
+
+    if taken {
+        return Err(Error::ValidationError(
+            format!("Slug {slug} is already used by another post").into(),
+        ));
+    }
+
+    Ok(())
+}
+
+// endregion: --- Blog Post Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use time::Duration;
+
+    fn fx_post(slug: &str, status: &str, publish_at: Option<OffsetDateTime>) -> BlogPostForCreate {
+        BlogPostForCreate {
+            slug: Some(slug.to_string()),
+            title: "Test Post".to_string(),
+            description: "A post that exists only for tests.".to_string(),
+            category: "local-seo".to_string(),
+            keywords: vec!["testing".to_string()],
+            author: "Test Author".to_string(),
+            body: "Hello".to_string(),
+            status: status.to_string(),
+            publish_at,
+        }
+    }
+
+    #[test]
+    fn test_check_post() {
+        let check = |slug, category, status, publish_at| {
+            check_post(
+                slug,
+                &["Title", "About", "Me", "Body"],
+                category,
+                status,
+                publish_at,
+            )
+        };
+        let now = Some(OffsetDateTime::now_utc());
+
+        assert!(check("winter-boilers", "local-seo", "draft", None).is_ok());
+        assert!(check("winter-boilers", "local-seo", "scheduled", now).is_ok());
+        assert!(check("winter-boilers", "local-seo", "scheduled", None).is_err());
+        assert!(check("Winter Boilers", "local-seo", "draft", None).is_err());
+        assert!(check("winter-boilers", "gossip", "draft", None).is_err());
+        assert!(check("winter-boilers", "local-seo", "live", None).is_err());
+        assert!(check_post(
+            "winter-boilers",
+            &["Title", " "],
+            "local-seo",
+            "draft",
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_is_visible() {
+        let now = OffsetDateTime::now_utc();
+        let post = |status: &str, publish_at| BlogPostRecord {
+            id: 1,
+            slug: "post".to_string(),
+            title: String::new(),
+            description: String::new(),
+            category: "local-seo".to_string(),
+            keywords: vec![],
+            author: String::new(),
+            body: String::new(),
+            status: status.to_string(),
+            publish_at,
+            created_at: None,
+            updated_at: None,
+        };
+
+        assert!(post("published", Some(now)).is_visible(now));
+        assert!(post("scheduled", Some(now - Duration::minutes(1))).is_visible(now));
+        assert!(!post("scheduled", Some(now + Duration::minutes(1))).is_visible(now));
+        assert!(!post("draft", Some(now - Duration::days(1))).is_visible(now));
+    }
+
+    #[tokio::test]
+    async fn test_schedule_and_publish() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let suffix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let slug = format!("test-post-{suffix}");
+        let tomorrow = OffsetDateTime::now_utc() + Duration::days(1);
+
+        // Execute
+        let id = BlogPostBmc::create(&mm, fx_post(&slug, "scheduled", Some(tomorrow))).await?;
+        let duplicate = BlogPostBmc::create(&mm, fx_post(&slug, "draft", None)).await;
+        let hidden = BlogPostBmc::get_published(&mm, &slug).await;
+        BlogPostBmc::update(
+            &mm,
+            id,
+            BlogPostForUpdate {
+                status: Some("published".to_string()),
+                publish_at: Some(OffsetDateTime::now_utc() - Duration::minutes(1)),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        // Check
+        assert!(duplicate.is_err(), "Slug must be unique");
+        assert!(matches!(hidden, Err(Error::SlugNotFound { .. })));
+        let post = BlogPostBmc::get_published(&mm, &slug).await?;
+        assert_eq!(post.title, "Test Post");
+        assert_eq!(post.content, "<p>Hello</p>\n");
+        assert!(BlogPostBmc::list_published(&mm, Some("local-seo"))
+            .await?
+            .iter()
+            .any(|p| p.slug == slug));
+
+        // Cleanup
+        BlogPostBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
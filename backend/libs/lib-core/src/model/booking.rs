@@ -28,14 +28,26 @@
 //! }
 //! ```
 
+use crate::model::postcode_district::PostcodeDistrictBmc;
 use crate::model::ModelManager;
-use crate::model::Result;
+use crate::model::{Error, Result};
+use crate::postcode;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use time::OffsetDateTime;
+use time::format_description::well_known::Iso8601;
+use time::{Date, OffsetDateTime, Time};
 use tracing::instrument;
 use utoipa::ToSchema;
 
+/// Columns selected for [`Booking`].
+pub(crate) const BOOKING_COLUMNS: &str = "id, customer_id, service_type, scheduled_date, \
+     scheduled_time, status, quote_id, estimated_duration, actual_duration, started_at, \
+     completed_at, customer_rating, customer_review, notes, postcode, latitude, longitude, \
+     created_at, updated_at";
+
+/// Statuses a booking can be in.
+const BOOKING_STATUSES: [&str; 4] = ["pending", "confirmed", "completed", "cancelled"];
+
 /// Complete booking record from the database.
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
 pub struct Booking {
@@ -65,6 +77,14 @@ pub struct Booking {
     pub customer_rating: Option<i32>,
     /// Customer review text
     pub customer_review: Option<String>,
+    /// Notes captured with the booking request
+    pub notes: Option<String>,
+    /// Postcode of the job site
+    pub postcode: Option<String>,
+    /// Latitude of the job site
+    pub latitude: Option<f64>,
+    /// Longitude of the job site
+    pub longitude: Option<f64>,
     /// When the booking was created
     pub created_at: Option<OffsetDateTime>,
     /// When the booking was last updated
@@ -86,8 +106,9 @@ pub struct BookingForCreate {
     pub notes: Option<String>,
 }
 
-/// Data for updating an existing booking.
-#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+/// Data for updating an existing booking. Omitted fields are left
+/// unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
 pub struct BookingForUpdate {
     /// New status
     pub status: Option<String>,
@@ -101,6 +122,12 @@ pub struct BookingForUpdate {
     pub customer_rating: Option<i32>,
     /// Customer review text
     pub customer_review: Option<String>,
+    /// Postcode of the job site
+    pub postcode: Option<String>,
+    /// Latitude of the job site (set together with `longitude`)
+    pub latitude: Option<f64>,
+    /// Longitude of the job site (set together with `latitude`)
+    pub longitude: Option<f64>,
 }
 
 /// Business Model Controller for booking operations.
@@ -126,13 +153,14 @@ impl BookingBmc {
     pub async fn create(mm: &ModelManager, booking: BookingForCreate) -> Result<i32> {
         let row: (i32,) = sqlx::query_as(
             r#"
-            INSERT INTO bookings (customer_id, service_type, status)
-            VALUES ($1, $2, 'pending')
+            INSERT INTO bookings (customer_id, service_type, notes, status)
+            VALUES ($1, $2, $3, 'pending')
             RETURNING id
             "#,
         )
         .bind(booking.customer_id)
         .bind(&booking.service_type)
+        .bind(&booking.notes)
         .fetch_one(mm.dbx().db())
         .await?;
 
@@ -152,24 +180,19 @@ impl BookingBmc {
     ///
     /// # Errors
     ///
-    /// Returns an error if the database query fails or booking not found.
+    /// Returns `EntityNotFound` if the booking does not exist.
     #[instrument(skip(mm))]
     pub async fn get(mm: &ModelManager, id: i32) -> Result<Booking> {
-        let booking = sqlx::query_as::<_, Booking>(
-            r#"
-            SELECT id, customer_id, service_type, scheduled_date, scheduled_time,
-                   status, quote_id, estimated_duration, actual_duration,
-                   started_at, completed_at, customer_rating, customer_review,
-                   created_at, updated_at
-            FROM bookings
-            WHERE id = $1
-            "#,
-        )
+        sqlx::query_as::<_, Booking>(&format!(
+            "SELECT {BOOKING_COLUMNS} FROM bookings WHERE id = $1"
+        ))
         .bind(id)
-        .fetch_one(mm.dbx().db())
-        .await?;
-
-        Ok(booking)
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "Booking",
+            id: id as i64,
+        })
     }
 
     /// Lists all bookings, ordered by creation date (newest first).
@@ -183,16 +206,9 @@ impl BookingBmc {
     /// A vector of all bookings.
     #[instrument(skip(mm))]
     pub async fn list(mm: &ModelManager) -> Result<Vec<Booking>> {
-        let bookings = sqlx::query_as(
-            r#"
-            SELECT id, customer_id, service_type, scheduled_date, scheduled_time,
-                   status, quote_id, estimated_duration, actual_duration,
-                   started_at, completed_at, customer_rating, customer_review,
-                   created_at, updated_at
-            FROM bookings
-            ORDER BY created_at DESC
-            "#,
-        )
+        let bookings = sqlx::query_as(&format!(
+            "SELECT {BOOKING_COLUMNS} FROM bookings ORDER BY created_at DESC"
+        ))
         .fetch_all(mm.dbx().db())
         .await?;
 
@@ -207,17 +223,10 @@ impl BookingBmc {
     /// * `status` - Status to filter by (pending, confirmed, completed, cancelled)
     #[instrument(skip(mm))]
     pub async fn list_by_status(mm: &ModelManager, status: &str) -> Result<Vec<Booking>> {
-        let bookings = sqlx::query_as(
-            r#"
-            SELECT id, customer_id, service_type, scheduled_date, scheduled_time,
-                   status, quote_id, estimated_duration, actual_duration,
-                   started_at, completed_at, customer_rating, customer_review,
-                   created_at, updated_at
-            FROM bookings
-            WHERE status = $1
-            ORDER BY scheduled_date ASC, scheduled_time ASC
-            "#,
-        )
+        let bookings = sqlx::query_as(&format!(
+            "SELECT {BOOKING_COLUMNS} FROM bookings \
+             WHERE status = $1 ORDER BY scheduled_date ASC, scheduled_time ASC"
+        ))
         .bind(status)
         .fetch_all(mm.dbx().db())
         .await?;
@@ -225,6 +234,116 @@ impl BookingBmc {
         Ok(bookings)
     }
 
+    /// Updates a booking's schedule, status, site or review.
+    ///
+    /// A postcode given without coordinates is located from the imported
+    /// postcode districts, when its district is known.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Booking ID to update
+    /// * `data` - Fields to change
+    ///
+    /// # Errors
+    ///
+    /// - `EntityNotFound` if the booking does not exist
+    /// - `ValidationError` for an unknown status, a rating outside 1-5, a
+    ///   non-positive duration, a malformed date, time or postcode, or a
+    ///   site location missing a coordinate or off the map
+    #[instrument(skip(mm))]
+    pub async fn update(mm: &ModelManager, id: i32, data: BookingForUpdate) -> Result<()> {
+        if let Some(status) = &data.status {
+            if !BOOKING_STATUSES.contains(&status.as_str()) {
+                return Err(Error::ValidationError(
+                    format!("Unknown booking status: {status}").into(),
+                ));
+            }
+        }
+        if data.customer_rating.is_some_and(|r| !(1..=5).contains(&r)) {
+            return Err(Error::ValidationError("Rating must be 1 to 5".into()));
+        }
+        if data.estimated_duration.is_some_and(|d| d <= 0) {
+            return Err(Error::ValidationError(
+                "Estimated duration must be positive".into(),
+            ));
+        }
+        validate_location(data.latitude, data.longitude)?;
+        let postcode = data
+            .postcode
+            .as_deref()
+            .map(|p| {
+                postcode::normalize(p)
+                    .ok_or_else(|| Error::ValidationError(format!("Invalid postcode: {p}").into()))
+            })
+            .transpose()?;
+        // A new postcode without a pin is placed at its district's centre,
+        // which is close enough for route planning.
+        let (latitude, longitude) = match (&postcode, data.latitude) {
+            (Some(postcode), None) => PostcodeDistrictBmc::find(mm, postcode)
+                .await?
+                .map_or((None, None), |d| (Some(d.latitude), Some(d.longitude))),
+            _ => (data.latitude, data.longitude),
+        };
+        let scheduled_date = data
+            .scheduled_date
+            .as_deref()
+            .map(|d| {
+                Date::parse(d, &Iso8601::DATE).map_err(|_| {
+                    Error::ValidationError("scheduled_date must be a YYYY-MM-DD date".into())
+                })
+            })
+            .transpose()?;
+        let scheduled_time = data
+            .scheduled_time
+            .as_deref()
+            .map(|t| {
+                parse_time(t).ok_or_else(|| {
+                    Error::ValidationError("scheduled_time must be an HH:MM time".into())
+                })
+            })
+            .transpose()?;
+
+        let rows_affected = sqlx::query(
+            r#"
+            UPDATE bookings
+            SET status = COALESCE($2, status),
+                scheduled_date = COALESCE($3, scheduled_date),
+                scheduled_time = COALESCE($4, scheduled_time),
+                estimated_duration = COALESCE($5, estimated_duration),
+                customer_rating = COALESCE($6, customer_rating),
+                customer_review = COALESCE($7, customer_review),
+                postcode = COALESCE($8, postcode),
+                latitude = COALESCE($9, latitude),
+                longitude = COALESCE($10, longitude),
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(&data.status)
+        .bind(scheduled_date)
+        .bind(scheduled_time)
+        .bind(data.estimated_duration)
+        .bind(data.customer_rating)
+        .bind(&data.customer_review)
+        .bind(postcode)
+        .bind(latitude)
+        .bind(longitude)
+        .execute(mm.dbx().db())
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "Booking",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Updates a booking's status.
     ///
     /// # Arguments
@@ -309,6 +428,38 @@ impl BookingBmc {
     }
 }
 
+// region:    --- Booking Helpers
+
+/// Checks an optional location has both coordinates, each on the map.
+pub(crate) fn validate_location(latitude: Option<f64>, longitude: Option<f64>) -> Result<()> {
+    match (latitude, longitude) {
+        (None, None) => Ok(()),
+        (Some(lat), Some(lng))
+            if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng) =>
+        {
+            Ok(())
+        }
+        (Some(_), Some(_)) => Err(Error::ValidationError(
+            "Latitude must be -90 to 90 and longitude -180 to 180".into(),
+        )),
+        _ => Err(Error::ValidationError(
+            "Latitude and longitude must be set together".into(),
+        )),
+    }
+}
+
+/// Parses an `HH:MM` time of day.
+pub(crate) fn parse_time(value: &str) -> Option<Time> {
+    let (hour, minute) = value.trim().split_once(':')?;
+    if hour.len() != 2 || minute.len() != 2 {
+        return None;
+    }
+
+    Time::from_hms(hour.parse().ok()?, minute.parse().ok()?, 0).ok()
+}
+
+// endregion: --- Booking Helpers
+
 // region:    --- Tests
 
 #[cfg(test)]
@@ -510,6 +661,77 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_booking_update_schedule() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let booking = BookingForCreate {
+            customer_id: None,
+            service_type: "test_update".to_string(),
+            scheduled_date: None,
+            scheduled_time: None,
+            notes: None,
+        };
+        let id = BookingBmc::create(&mm, booking).await?;
+
+        // Execute
+        BookingBmc::update(
+            &mm,
+            id,
+            BookingForUpdate {
+                status: Some("confirmed".to_string()),
+                scheduled_date: Some("2026-11-02".to_string()),
+                scheduled_time: Some("09:30".to_string()),
+                estimated_duration: Some(90),
+                postcode: Some("sw1a1aa".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let bad_postcode = BookingBmc::update(
+            &mm,
+            id,
+            BookingForUpdate {
+                postcode: Some("nowhere".to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        // Check
+        let booking = BookingBmc::get(&mm, id).await?;
+        assert_eq!(booking.status, "confirmed");
+        assert_eq!(
+            booking.scheduled_time,
+            Some(Time::from_hms(9, 30, 0).unwrap())
+        );
+        assert_eq!(booking.estimated_duration, Some(90));
+        assert_eq!(booking.postcode.as_deref(), Some("SW1A 1AA"));
+        assert!(matches!(bad_postcode, Err(Error::ValidationError(_))));
+
+        // Cleanup
+        BookingBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("09:30"), Some(Time::from_hms(9, 30, 0).unwrap()));
+        assert_eq!(parse_time("24:00"), None);
+        assert_eq!(parse_time("9:30"), None);
+        assert_eq!(parse_time("noon"), None);
+    }
+
+    #[test]
+    fn test_validate_location() {
+        assert!(validate_location(None, None).is_ok());
+        assert!(validate_location(Some(52.4068), Some(-1.5197)).is_ok());
+        assert!(validate_location(Some(52.4068), None).is_err());
+        assert!(validate_location(Some(91.0), Some(0.0)).is_err());
+        assert!(validate_location(Some(0.0), Some(-181.0)).is_err());
+    }
+
     #[tokio::test]
     async fn test_booking_delete_ok() -> Result<()> {
         // Setup
@@ -0,0 +1,187 @@
+//! # Document Model
+//!
+//! Loads quotes and invoices with their customer and renders them as PDFs
+//! with the configured [`Branding`].
+//!
+//! ## Structures
+//!
+//! - [`RenderedDocument`] - A rendered PDF with its file name and recipient
+//! - [`DocumentBmc`] - Business Model Controller for document rendering
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::document::Branding;
+//! use lib_core::model::document::DocumentBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn save_invoice(mm: &ModelManager) -> Result<(), Box<dyn std::error::Error>> {
+//!     let doc = DocumentBmc::invoice_pdf(mm, 1, Branding::from_config()).await?;
+//!     std::fs::write(&doc.filename, &doc.bytes)?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::document::{render_pdf, Branding, DocumentData};
+use crate::model::customer::{Customer, CustomerBmc};
+use crate::model::invoice::InvoiceBmc;
+use crate::model::quote::QuoteBmc;
+use crate::model::quote_acceptance::QuoteAcceptanceBmc;
+use crate::model::ModelManager;
+use crate::model::Result;
+use tracing::instrument;
+
+/// A rendered PDF with its file name and recipient.
+#[derive(Debug, Clone)]
+pub struct RenderedDocument {
+    /// Document number, e.g. `Q-000042`
+    pub number: String,
+    /// Download file name, e.g. `quote-Q-000042.pdf`
+    pub filename: String,
+    /// PDF bytes
+    pub bytes: Vec<u8>,
+    /// Customer the document is addressed to
+    pub customer: Option<Customer>,
+}
+
+/// Business Model Controller for document rendering.
+pub struct DocumentBmc;
+
+impl DocumentBmc {
+    /// Renders a quote as a PDF, with the signature block once it has been
+    /// accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Quote ID to render
+    /// * `branding` - Business details printed on the document
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the quote does not exist.
+    #[instrument(skip(mm, branding))]
+    pub async fn quote_pdf(
+        mm: &ModelManager,
+        id: i32,
+        branding: &Branding,
+    ) -> Result<RenderedDocument> {
+        let quote = QuoteBmc::get(mm, id).await?;
+        let customer = Self::customer(mm, quote.customer_id).await?;
+        let mut data = DocumentData::from_quote(&quote, customer.as_ref(), branding)?;
+        if let Some(acceptance) = QuoteAcceptanceBmc::get_for_quote(mm, id).await? {
+            data = data.with_acceptance(&acceptance);
+        }
+
+        Ok(RenderedDocument {
+            number: data.number.clone(),
+            filename: data.filename(),
+            bytes: render_pdf(branding, &data),
+            customer,
+        })
+    }
+
+    /// Renders an issued invoice or credit note as a PDF.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Invoice or credit note ID to render
+    /// * `branding` - Business details printed on the document
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the invoice does not exist, or
+    /// `ValidationError` if it is still a draft.
+    #[instrument(skip(mm, branding))]
+    pub async fn invoice_pdf(
+        mm: &ModelManager,
+        id: i32,
+        branding: &Branding,
+    ) -> Result<RenderedDocument> {
+        let invoice = InvoiceBmc::get(mm, id).await?;
+        let customer = Self::customer(mm, invoice.customer_id).await?;
+        let credited_number = match invoice.credited_invoice_id {
+            Some(credited_id) => InvoiceBmc::get(mm, credited_id).await?.number,
+            None => None,
+        };
+        let data = DocumentData::from_invoice(
+            &invoice,
+            customer.as_ref(),
+            credited_number.as_deref(),
+            branding,
+        )?;
+
+        Ok(RenderedDocument {
+            number: data.number.clone(),
+            filename: data.filename(),
+            bytes: render_pdf(branding, &data),
+            customer,
+        })
+    }
+
+    async fn customer(mm: &ModelManager, customer_id: Option<i32>) -> Result<Option<Customer>> {
+        match customer_id {
+            Some(id) => Ok(Some(CustomerBmc::get(mm, id).await?)),
+            None => Ok(None),
+        }
+    }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::quote::{QuoteForCreate, QuoteItem};
+
+    #[tokio::test]
+    async fn test_quote_pdf_ok() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let id = QuoteBmc::create(
+            &mm,
+            QuoteForCreate {
+                customer_id: None,
+                title: "test_quote_pdf_ok".to_string(),
+                items: vec![QuoteItem {
+                    description: "Call-out fee".to_string(),
+                    quantity: 1,
+                    unit_price: 3000,
+                    discount: None,
+                    vat_rate_bp: None,
+                }],
+                valid_days: Some(30),
+                service_type: None,
+                discount: None,
+                promo_code: None,
+                notes: None,
+                template_id: None,
+                options: vec![],
+                add_ons: vec![],
+            },
+        )
+        .await?;
+        let branding = Branding {
+            business_name: "Test Trades".to_string(),
+            ..Branding::default()
+        };
+
+        // Execute
+        let first = DocumentBmc::quote_pdf(&mm, id, &branding).await?;
+        let second = DocumentBmc::quote_pdf(&mm, id, &branding).await?;
+
+        // Check
+        assert_eq!(first.filename, format!("quote-Q-{id:06}.pdf"));
+        assert!(first.bytes.starts_with(b"%PDF-"));
+        assert_eq!(first.bytes, second.bytes);
+
+        // Cleanup
+        QuoteBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,160 @@
+//! # Email Log Model
+//!
+//! Record of every email the application sends, so customer history shows
+//! what was sent to (or about) them and when.
+//!
+//! ## Structures
+//!
+//! - [`EmailLog`] - Complete email log record from database
+//! - [`EmailLogForCreate`] - Data required to record a sent email
+//! - [`EmailLogBmc`] - Business Model Controller for email log operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::email_log::{EmailLogBmc, EmailLogForCreate};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn record(mm: &ModelManager) -> Result<i32, Box<dyn std::error::Error>> {
+//!     let entry = EmailLogForCreate {
+//!         customer_id: None,
+//!         to_email: "admin@xftradesman.com".to_string(),
+//!         subject: "New contact form submission".to_string(),
+//!         kind: "contact_notification".to_string(),
+//!         related_email: Some("john@example.com".to_string()),
+//!         status: "sent".to_string(),
+//!     };
+//!     let id = EmailLogBmc::create(mm, entry).await?;
+//!     Ok(id)
+//! }
+//! ```
+
+use crate::model::ModelManager;
+use crate::model::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Complete email log record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct EmailLog {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Customer ID if the recipient is a known customer
+    pub customer_id: Option<i32>,
+    /// Recipient email address
+    pub to_email: String,
+    /// Email subject line
+    pub subject: String,
+    /// Kind of email, e.g. 'contact_notification'
+    pub kind: String,
+    /// Email of the customer the message is about, if not the recipient
+    pub related_email: Option<String>,
+    /// Delivery status: sent, failed
+    pub status: String,
+    /// When the email was sent
+    pub sent_at: Option<OffsetDateTime>,
+}
+
+/// Data required to record a sent email.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct EmailLogForCreate {
+    /// Customer ID if the recipient is a known customer
+    pub customer_id: Option<i32>,
+    /// Recipient email address
+    pub to_email: String,
+    /// Email subject line
+    pub subject: String,
+    /// Kind of email, e.g. 'contact_notification'
+    pub kind: String,
+    /// Email of the customer the message is about, if not the recipient
+    pub related_email: Option<String>,
+    /// Delivery status: sent, failed
+    pub status: String,
+}
+
+/// Business Model Controller for email log operations.
+pub struct EmailLogBmc;
+
+impl EmailLogBmc {
+    /// Records a sent (or failed) email.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `entry` - Email details to record
+    ///
+    /// # Returns
+    ///
+    /// The auto-generated ID of the log entry.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm), fields(kind = %entry.kind))]
+    pub async fn create(mm: &ModelManager, entry: EmailLogForCreate) -> Result<i32> {
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO email_log (customer_id, to_email, subject, kind, related_email, status)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(entry.customer_id)
+        .bind(&entry.to_email)
+        .bind(&entry.subject)
+        .bind(&entry.kind)
+        .bind(&entry.related_email)
+        .bind(&entry.status)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Lists emails sent to or about an email address, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `email` - Recipient or related email address (case-insensitive)
+    #[instrument(skip(mm))]
+    pub async fn list_by_email(mm: &ModelManager, email: &str) -> Result<Vec<EmailLog>> {
+        let entries = sqlx::query_as(
+            r#"
+            SELECT id, customer_id, to_email, subject, kind, related_email, status, sent_at
+            FROM email_log
+            WHERE lower(to_email) = lower($1) OR lower(related_email) = lower($1)
+            ORDER BY sent_at DESC
+            "#,
+        )
+        .bind(email)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Deletes an email log entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Log entry ID to delete
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM email_log WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(crate::model::Error::EntityNotFound {
+                entity: "EmailLog",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@
 //! Defines error types for model operations and database access.
 
 use crate::model::store::dbx;
+use crate::storage;
 use derive_more::From;
 use serde::Serialize;
 use serde_with::{serde_as, DisplayFromStr};
@@ -16,6 +17,12 @@ pub enum Error {
         entity: &'static str,
         id: i64,
     },
+    /// Lookup by URL slug (areas, services, pages) or host name found
+    /// nothing
+    SlugNotFound {
+        entity: &'static str,
+        slug: String,
+    },
 
     // -- DB
     UserAlreadyExists {
@@ -35,6 +42,8 @@ pub enum Error {
     // -- Modules
     #[from]
     Dbx(dbx::Error),
+    #[from]
+    Storage(#[serde_as(as = "DisplayFromStr")] storage::Error),
 
     // -- External
     #[from]
@@ -0,0 +1,598 @@
+//! # GDPR Model
+//!
+//! Subject access exports, right-to-erasure and retention of personal data.
+//!
+//! - **Export** collects every row mentioning an email address (contacts,
+//!   customers and their quotes, bookings, attachments, invoices, credit
+//!   notes, payments, emails sent and audit log entries) into one
+//!   machine-readable document.
+//! - **Erasure** anonymises personal fields in place. Quotes, bookings,
+//!   invoices and payments are kept with their amounts so the accounts still
+//!   add up.
+//!   Signed quote acceptances are kept unchanged as evidence of the
+//!   contract.
+//! - **Retention** strips IP addresses and user agents from contact
+//!   submissions once they are older than the configured period.
+//!
+//! Every export and erasure is recorded in the audit log.
+//!
+//! ## Structures
+//!
+//! - [`SubjectAccessExport`] - Everything held about a data subject
+//! - [`ErasureReport`] - Rows anonymised by an erasure
+//! - [`GdprBmc`] - Business Model Controller for GDPR operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::gdpr::GdprBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn handle_request(mm: &ModelManager) -> Result<(), Box<dyn std::error::Error>> {
+//!     let export = GdprBmc::export(mm, "john@example.com", "admin").await?;
+//!     println!("{}", serde_json::to_string_pretty(&export)?);
+//!
+//!     let report = GdprBmc::erase(mm, "john@example.com", "admin").await?;
+//!     println!("Anonymised {} customers", report.customers);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::model::attachment::{Attachment, AttachmentBmc};
+use crate::model::audit_log::{AuditLogBmc, AuditLogEntry, AuditLogForCreate};
+use crate::model::booking::{Booking, BOOKING_COLUMNS};
+use crate::model::contact::Contact;
+use crate::model::customer::Customer;
+use crate::model::email_log::EmailLog;
+use crate::model::invoice::{Invoice, INVOICE_COLUMNS};
+use crate::model::payment::Payment;
+use crate::model::quote::{Quote, QUOTE_COLUMNS};
+use crate::model::quote_acceptance::{QuoteAcceptance, QuoteAcceptanceBmc};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use tracing::{info, instrument};
+use utoipa::ToSchema;
+
+/// Placeholder name written over erased names.
+pub const ERASED_NAME: &str = "Erased";
+
+/// Domain used for placeholder emails written over erased addresses.
+///
+/// `.invalid` is reserved (RFC 2606) so these can never receive mail.
+pub const ERASED_EMAIL_DOMAIN: &str = "erased.invalid";
+
+/// Everything held about a data subject.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SubjectAccessExport {
+    /// Email address the export was requested for
+    pub email: String,
+    /// When the export was generated
+    pub generated_at: OffsetDateTime,
+    /// Contact form submissions
+    pub contacts: Vec<Contact>,
+    /// Customer records
+    pub customers: Vec<Customer>,
+    /// Quotes for those customers
+    pub quotes: Vec<Quote>,
+    /// Signed acceptances of those quotes
+    pub quote_acceptances: Vec<QuoteAcceptance>,
+    /// Bookings for those customers
+    pub bookings: Vec<Booking>,
+    /// Files attached to those quotes and bookings (metadata only)
+    pub attachments: Vec<Attachment>,
+    /// Invoices and credit notes for those customers
+    pub invoices: Vec<Invoice>,
+    /// Payments made by those customers
+    pub payments: Vec<Payment>,
+    /// Emails sent to or about the subject
+    pub emails: Vec<EmailLog>,
+    /// Audit log entries concerning the subject
+    pub audit_log: Vec<AuditLogEntry>,
+}
+
+/// Number of rows anonymised by an erasure.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ErasureReport {
+    /// Contact form submissions anonymised
+    pub contacts: u64,
+    /// Customer records anonymised
+    pub customers: u64,
+    /// Bookings with notes and reviews removed
+    pub bookings: u64,
+    /// Quotes with customer notes removed
+    pub quotes: u64,
+    /// Email log entries anonymised
+    pub emails: u64,
+    /// Audit log entries detached from the subject
+    pub audit_log: u64,
+}
+
+/// Business Model Controller for GDPR operations.
+pub struct GdprBmc;
+
+impl GdprBmc {
+    /// Collects everything held about an email address.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `email` - Data subject's email address (case-insensitive)
+    /// * `actor` - Who requested the export, for the audit log
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if `email` is not an email address.
+    #[instrument(skip(mm))]
+    pub async fn export(
+        mm: &ModelManager,
+        email: &str,
+        actor: &str,
+    ) -> Result<SubjectAccessExport> {
+        let email = validate_email(email)?;
+        let db = mm.dbx().db();
+
+        let contacts: Vec<Contact> = sqlx::query_as(
+            r#"
+            SELECT id, name, email, subject, message, submitted_at, ip_address, user_agent
+            FROM contact_submissions
+            WHERE lower(email) = lower($1)
+            ORDER BY submitted_at ASC
+            "#,
+        )
+        .bind(email)
+        .fetch_all(db)
+        .await?;
+
+        let customers: Vec<Customer> = sqlx::query_as(
+            r#"
+            SELECT id, name, email, phone, addresses, notes, tags, created_at, updated_at
+            FROM customers
+            WHERE lower(email) = lower($1)
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(email)
+        .fetch_all(db)
+        .await?;
+        let customer_ids: Vec<i32> = customers.iter().map(|c| c.id).collect();
+
+        let quotes: Vec<Quote> = sqlx::query_as(&format!(
+            "SELECT {QUOTE_COLUMNS} FROM quotes WHERE customer_id = ANY($1) ORDER BY id ASC"
+        ))
+        .bind(&customer_ids)
+        .fetch_all(db)
+        .await?;
+        let quote_ids: Vec<i32> = quotes.iter().map(|q| q.id).collect();
+        let quote_acceptances = QuoteAcceptanceBmc::list_for_quotes(mm, &quote_ids).await?;
+
+        let bookings: Vec<Booking> = sqlx::query_as(&format!(
+            "SELECT {BOOKING_COLUMNS} FROM bookings WHERE customer_id = ANY($1) ORDER BY id ASC"
+        ))
+        .bind(&customer_ids)
+        .fetch_all(db)
+        .await?;
+        let booking_ids: Vec<i32> = bookings.iter().map(|b| b.id).collect();
+        let attachments = AttachmentBmc::list_for_owners(mm, &booking_ids, &quote_ids).await?;
+
+        let invoices: Vec<Invoice> = sqlx::query_as(&format!(
+            "SELECT {INVOICE_COLUMNS} FROM invoices WHERE customer_id = ANY($1) ORDER BY id ASC"
+        ))
+        .bind(&customer_ids)
+        .fetch_all(db)
+        .await?;
+
+        let payments: Vec<Payment> = sqlx::query_as(
+            r#"
+            SELECT id, customer_id, quote_id, booking_id, invoice_id, amount_cents, method,
+                   reference, status, paid_at
+            FROM payments
+            WHERE customer_id = ANY($1)
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(&customer_ids)
+        .fetch_all(db)
+        .await?;
+
+        let emails: Vec<EmailLog> = sqlx::query_as(
+            r#"
+            SELECT id, customer_id, to_email, subject, kind, related_email, status, sent_at
+            FROM email_log
+            WHERE customer_id = ANY($2)
+               OR lower(to_email) = lower($1)
+               OR lower(related_email) = lower($1)
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(email)
+        .bind(&customer_ids)
+        .fetch_all(db)
+        .await?;
+
+        let audit_log = AuditLogBmc::list_by_subject(mm, email).await?;
+
+        let export = SubjectAccessExport {
+            email: email.to_string(),
+            generated_at: OffsetDateTime::now_utc(),
+            contacts,
+            customers,
+            quotes,
+            quote_acceptances,
+            bookings,
+            attachments,
+            invoices,
+            payments,
+            emails,
+            audit_log,
+        };
+
+        let _ = AuditLogBmc::create(
+            mm,
+            AuditLogForCreate {
+                action: "gdpr_export".to_string(),
+                actor: actor.to_string(),
+                subject_email: Some(email.to_string()),
+                details: Some(serde_json::json!({
+                    "contacts": export.contacts.len(),
+                    "customers": export.customers.len(),
+                    "quotes": export.quotes.len(),
+                    "quote_acceptances": export.quote_acceptances.len(),
+                    "bookings": export.bookings.len(),
+                    "attachments": export.attachments.len(),
+                    "invoices": export.invoices.len(),
+                    "payments": export.payments.len(),
+                    "emails": export.emails.len(),
+                })),
+            },
+        )
+        .await?;
+
+        Ok(export)
+    }
+
+    /// Anonymises every personal field held about an email address.
+    ///
+    /// Names, emails, phone numbers, addresses, free-text notes, reviews,
+    /// IP addresses and user agents are overwritten or cleared. Quote line
+    /// items and amounts, booking records and payments are kept intact.
+    ///
+    /// Runs in a single transaction: either everything is anonymised or
+    /// nothing is.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `email` - Data subject's email address (case-insensitive)
+    /// * `actor` - Who requested the erasure, for the audit log
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if `email` is not an email address.
+    #[instrument(skip(mm))]
+    pub async fn erase(mm: &ModelManager, email: &str, actor: &str) -> Result<ErasureReport> {
+        let email = validate_email(email)?;
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        let customer_ids: Vec<i32> = dbx
+            .fetch_all(
+                sqlx::query_as::<_, (i32,)>(
+                    "SELECT id FROM customers WHERE lower(email) = lower($1)",
+                )
+                .bind(email),
+            )
+            .await?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect();
+
+        let contacts = dbx
+            .execute(
+                sqlx::query(
+                    r#"
+                    UPDATE contact_submissions
+                    SET name = $2,
+                        email = 'erased-contact-' || id || '@' || $3,
+                        subject = NULL,
+                        message = '',
+                        ip_address = NULL,
+                        user_agent = NULL
+                    WHERE lower(email) = lower($1)
+                    "#,
+                )
+                .bind(email)
+                .bind(ERASED_NAME)
+                .bind(ERASED_EMAIL_DOMAIN),
+            )
+            .await?;
+
+        let customers = dbx
+            .execute(
+                sqlx::query(
+                    r#"
+                    UPDATE customers
+                    SET name = $2,
+                        email = 'erased-customer-' || id || '@' || $3,
+                        phone = NULL,
+                        addresses = NULL,
+                        notes = NULL,
+                        tags = NULL,
+                        updated_at = CURRENT_TIMESTAMP
+                    WHERE id = ANY($1)
+                    "#,
+                )
+                .bind(&customer_ids)
+                .bind(ERASED_NAME)
+                .bind(ERASED_EMAIL_DOMAIN),
+            )
+            .await?;
+
+        let bookings = dbx
+            .execute(
+                sqlx::query(
+                    r#"
+                    UPDATE bookings
+                    SET notes = NULL,
+                        customer_review = NULL,
+                        updated_at = CURRENT_TIMESTAMP
+                    WHERE customer_id = ANY($1)
+                    "#,
+                )
+                .bind(&customer_ids),
+            )
+            .await?;
+
+        let quotes = dbx
+            .execute(
+                sqlx::query(
+                    r#"
+                    UPDATE quotes
+                    SET customer_notes = NULL,
+                        updated_at = CURRENT_TIMESTAMP
+                    WHERE customer_id = ANY($1)
+                    "#,
+                )
+                .bind(&customer_ids),
+            )
+            .await?;
+
+        let emails = dbx
+            .execute(
+                sqlx::query(
+                    r#"
+                    UPDATE email_log
+                    SET to_email = CASE WHEN lower(to_email) = lower($1)
+                                        THEN 'erased-email-' || id || '@' || $3
+                                        ELSE to_email END,
+                        related_email = CASE WHEN lower(related_email) = lower($1)
+                                             THEN NULL
+                                             ELSE related_email END
+                    WHERE customer_id = ANY($2)
+                       OR lower(to_email) = lower($1)
+                       OR lower(related_email) = lower($1)
+                    "#,
+                )
+                .bind(email)
+                .bind(&customer_ids)
+                .bind(ERASED_EMAIL_DOMAIN),
+            )
+            .await?;
+
+        let audit_log = dbx
+            .execute(
+                sqlx::query(
+                    "UPDATE audit_log SET subject_email = NULL WHERE lower(subject_email) = lower($1)",
+                )
+                .bind(email),
+            )
+            .await?;
+
+        let report = ErasureReport {
+            contacts,
+            customers,
+            bookings,
+            quotes,
+            emails,
+            audit_log,
+        };
+
+        let _ = AuditLogBmc::create(
+            &mm,
+            AuditLogForCreate {
+                action: "gdpr_erasure".to_string(),
+                actor: actor.to_string(),
+                subject_email: None,
+                details: Some(serde_json::json!({
+                    "customer_ids": customer_ids,
+                    "report": report,
+                })),
+            },
+        )
+        .await?;
+
+        dbx.commit_txn().await?;
+
+        info!(
+            customers = report.customers,
+            contacts = report.contacts,
+            "GDPR erasure completed"
+        );
+        Ok(report)
+    }
+
+    /// Strips IP addresses and user agents from contact submissions older
+    /// than `retention_days`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `retention_days` - How long to keep submission metadata
+    ///
+    /// # Returns
+    ///
+    /// The number of submissions purged.
+    #[instrument(skip(mm))]
+    pub async fn purge_contact_metadata(mm: &ModelManager, retention_days: u32) -> Result<u64> {
+        let purged = sqlx::query(
+            r#"
+            UPDATE contact_submissions
+            SET ip_address = NULL, user_agent = NULL
+            WHERE submitted_at < CURRENT_TIMESTAMP - make_interval(days => $1)
+              AND (ip_address IS NOT NULL OR user_agent IS NOT NULL)
+            "#,
+        )
+        .bind(retention_days as i32)
+        .execute(mm.dbx().db())
+        .await?
+        .rows_affected();
+
+        if purged > 0 {
+            let _ = AuditLogBmc::create(
+                mm,
+                AuditLogForCreate {
+                    action: "retention_purge".to_string(),
+                    actor: "system".to_string(),
+                    subject_email: None,
+                    details: Some(serde_json::json!({
+                        "table": "contact_submissions",
+                        "retention_days": retention_days,
+                        "rows": purged,
+                    })),
+                },
+            )
+            .await?;
+        }
+
+        Ok(purged)
+    }
+}
+
+/// Trims an email address and checks it looks like one.
+fn validate_email(email: &str) -> Result<&str> {
+    let email = email.trim();
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && !domain.is_empty() => Ok(email),
+        _ => Err(Error::ValidationError(
+            "A valid email address is required".into(),
+        )),
+    }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::booking::{BookingBmc, BookingForCreate};
+    use crate::model::contact::{ContactBmc, ContactForCreate};
+    use crate::model::customer::{CustomerBmc, CustomerForCreate};
+    use crate::model::invoice::{InvoiceBmc, InvoiceSettings};
+    use crate::model::quote::QuoteItem;
+
+    #[test]
+    fn test_validate_email() {
+        assert_eq!(
+            validate_email(" john@example.com ").ok(),
+            Some("john@example.com")
+        );
+        assert!(validate_email("john").is_err());
+        assert!(validate_email("@example.com").is_err());
+        assert!(validate_email("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gdpr_export_and_erase() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let email = format!("gdpr_{}@example.com", uuid::Uuid::new_v4());
+        let customer_id = CustomerBmc::create(
+            &mm,
+            CustomerForCreate {
+                name: "Test GDPR Customer".to_string(),
+                email: Some(email.clone()),
+                phone: Some("+44 7700 900456".to_string()),
+                notes: Some("Side gate code 1234".to_string()),
+            },
+        )
+        .await?;
+        let contact_id = ContactBmc::create(
+            &mm,
+            ContactForCreate {
+                name: "Test GDPR Customer".to_string(),
+                email: email.to_uppercase(),
+                subject: None,
+                message: "Please call me back".to_string(),
+                ip_address: Some("127.0.0.1".to_string()),
+                user_agent: Some("test-agent".to_string()),
+            },
+        )
+        .await?;
+        let booking_id = BookingBmc::create(
+            &mm,
+            BookingForCreate {
+                customer_id: Some(customer_id),
+                service_type: "test_gdpr_invoice".to_string(),
+                scheduled_date: None,
+                scheduled_time: None,
+                notes: None,
+            },
+        )
+        .await?;
+        BookingBmc::complete(&mm, booking_id, 60).await?;
+        let settings = InvoiceSettings {
+            vat_registered: false,
+            vat_rate_bp: 0,
+            payment_terms_days: 14,
+        };
+        let items = vec![QuoteItem {
+            description: "Hang a door".to_string(),
+            quantity: 1,
+            unit_price: 6000,
+            discount: None,
+            vat_rate_bp: None,
+        }];
+        let invoice_id =
+            InvoiceBmc::generate_from_booking(&mm, booking_id, Some(items), &settings).await?;
+        let _ = InvoiceBmc::issue(&mm, invoice_id, &settings).await?;
+        let credit_note_id =
+            InvoiceBmc::create_credit_note(&mm, invoice_id, 1000, "Goodwill").await?;
+
+        // Execute
+        let export = GdprBmc::export(&mm, &email, "test").await?;
+        let report = GdprBmc::erase(&mm, &email, "test").await?;
+
+        // Check
+        assert_eq!(export.customers.len(), 1);
+        assert_eq!(export.contacts.len(), 1);
+        let invoice_ids: Vec<i32> = export.invoices.iter().map(|i| i.id).collect();
+        assert_eq!(invoice_ids, [invoice_id, credit_note_id]);
+        assert_eq!(export.invoices[1].kind, "credit_note");
+        assert_eq!(report.customers, 1);
+        assert_eq!(report.contacts, 1);
+
+        let customer = CustomerBmc::get(&mm, customer_id).await?;
+        assert_eq!(customer.name, ERASED_NAME);
+        assert!(customer.phone.is_none());
+        assert!(customer.notes.is_none());
+        let contact = ContactBmc::get(&mm, contact_id).await?;
+        assert!(contact.ip_address.is_none());
+        assert!(contact.email.ends_with(ERASED_EMAIL_DOMAIN));
+
+        let after = GdprBmc::export(&mm, &email, "test").await?;
+        assert!(after.customers.is_empty());
+        assert!(after.contacts.is_empty());
+
+        // Cleanup
+        ContactBmc::delete(&mm, contact_id).await?;
+        BookingBmc::delete(&mm, booking_id).await?;
+        CustomerBmc::delete(&mm, customer_id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,1125 @@
+//! # Invoice Model
+//!
+//! Invoices and credit notes generated from completed bookings.
+//!
+//! ## Lifecycle
+//!
+//! ```text
+//! draft ──issue──▶ issued ──(due date passes)──▶ overdue
+//!   │                │                              │
+//!   │                └───────(fully paid)───────────┴──▶ paid
+//!   └──void──▶ void ◀──void (nothing paid or credited)──┘
+//! ```
+//!
+//! - Drafts have no number. Numbers (`INV-000001`, credit notes
+//!   `CN-000001`) are taken when a document is issued, inside the same
+//!   transaction, so issued numbers are sequential with no gaps.
+//! - Line items come from the booking's accepted quote, or are supplied
+//!   explicitly when the job was not quoted.
+//! - Totals come from [`crate::pricing::price`], the same function quotes
+//!   use, so an invoice for a quoted job matches the quote to the cent.
+//! - VAT is only charged when the business is VAT registered; the mode,
+//!   default rate and per-rate breakdown are stored on each invoice so
+//!   later config changes never alter issued documents.
+//! - Payments may be partial; an invoice is paid once payments and credit
+//!   notes cover its total.
+//!
+//! ## Structures
+//!
+//! - [`Invoice`] - Complete invoice or credit note record from database
+//! - [`InvoiceSettings`] - VAT mode, rate and payment terms
+//! - [`InvoicePaymentForCreate`] - Data required to record a payment
+//! - [`InvoiceReminder`] - An overdue invoice due a reminder
+//! - [`InvoiceBmc`] - Business Model Controller for invoice operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::invoice::{InvoiceBmc, InvoiceSettings};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn bill(mm: &ModelManager, booking_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+//!     let settings = InvoiceSettings::from_config();
+//!     let id = InvoiceBmc::generate_from_booking(mm, booking_id, None, &settings).await?;
+//!     let number = InvoiceBmc::issue(mm, id, &settings).await?;
+//!     println!("Issued {number}");
+//!     Ok(())
+//! }
+//! ```
+
+use crate::core_config;
+use crate::model::material::MaterialBmc;
+use crate::model::quote::{Quote, QuoteItem, QUOTE_COLUMNS};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::pricing::{self, Discount, VatBand, VatSettings};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Columns selected for [`Invoice`].
+pub(crate) const INVOICE_COLUMNS: &str =
+    "id, number, kind, credited_invoice_id, customer_id, booking_id, \
+     quote_id, items, subtotal_cents, discount_cents, vat_registered, vat_rate_bp, vat_cents, \
+     vat_breakdown, total_cents, paid_cents, credited_cents, status, notes, issue_date, due_date, \
+     reminder_count, last_reminder_at, created_at, updated_at";
+
+/// Complete invoice or credit note record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Invoice {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Document number (INV-000001 / CN-000001), None while draft
+    pub number: Option<String>,
+    /// Document kind: invoice, credit_note
+    pub kind: String,
+    /// Invoice this credit note is against
+    pub credited_invoice_id: Option<i32>,
+    /// Customer ID (foreign key)
+    pub customer_id: Option<i32>,
+    /// Booking the invoice bills for
+    pub booking_id: Option<i32>,
+    /// Quote the line items came from
+    pub quote_id: Option<i32>,
+    /// JSON array of line items
+    pub items: serde_json::Value,
+    /// Sum of line items in cents
+    pub subtotal_cents: i32,
+    /// Discount in cents
+    pub discount_cents: i32,
+    /// Whether VAT was charged
+    pub vat_registered: bool,
+    /// Default VAT rate in basis points (2000 = 20%)
+    pub vat_rate_bp: i32,
+    /// VAT in cents
+    pub vat_cents: i32,
+    /// VAT by rate, as [`VatBand`]s
+    pub vat_breakdown: serde_json::Value,
+    /// Total payable in cents (net + VAT)
+    pub total_cents: i32,
+    /// Amount paid so far in cents
+    pub paid_cents: i32,
+    /// Amount credited by credit notes in cents
+    pub credited_cents: i32,
+    /// Current status: draft, issued, paid, overdue, void
+    pub status: String,
+    /// Notes printed on the document (credit note reason)
+    pub notes: Option<String>,
+    /// Date issued
+    pub issue_date: Option<time::Date>,
+    /// Date payment is due
+    pub due_date: Option<time::Date>,
+    /// Number of overdue reminders sent
+    pub reminder_count: i32,
+    /// When the last overdue reminder was sent
+    pub last_reminder_at: Option<OffsetDateTime>,
+    /// When the invoice was created
+    pub created_at: Option<OffsetDateTime>,
+    /// When the invoice was last updated
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl Invoice {
+    /// Amount still owed in cents.
+    #[must_use]
+    pub fn balance_cents(&self) -> i32 {
+        self.total_cents - self.paid_cents - self.credited_cents
+    }
+}
+
+/// VAT mode, rate and payment terms applied to new invoices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvoiceSettings {
+    /// Whether to charge VAT
+    pub vat_registered: bool,
+    /// VAT rate in basis points (2000 = 20%)
+    pub vat_rate_bp: i32,
+    /// Days between issue and due date
+    pub payment_terms_days: i32,
+}
+
+impl InvoiceSettings {
+    /// Loads settings from `VAT_REGISTERED`, `VAT_RATE_BP` and
+    /// `INVOICE_PAYMENT_TERMS_DAYS`.
+    #[must_use]
+    pub fn from_config() -> Self {
+        let config = core_config();
+        Self {
+            vat_registered: config.VAT_REGISTERED,
+            vat_rate_bp: config.VAT_RATE_BP,
+            payment_terms_days: config.INVOICE_PAYMENT_TERMS_DAYS,
+        }
+    }
+
+    /// Effective VAT rate: zero when not VAT registered.
+    #[must_use]
+    pub fn effective_rate_bp(&self) -> i32 {
+        self.vat().rate_for(None)
+    }
+
+    /// VAT settings for [`pricing::price`].
+    #[must_use]
+    pub fn vat(&self) -> VatSettings {
+        VatSettings {
+            registered: self.vat_registered,
+            default_rate_bp: self.vat_rate_bp,
+        }
+    }
+}
+
+/// Data required to record a payment against an invoice.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct InvoicePaymentForCreate {
+    /// Amount in cents (may be less than the balance)
+    pub amount_cents: i32,
+    /// Payment method: card, bank_transfer, cash
+    pub method: String,
+    /// External reference (Stripe PaymentIntent ID, bank reference)
+    pub reference: Option<String>,
+}
+
+/// An overdue invoice due a reminder, with the customer to send it to.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct InvoiceReminder {
+    /// Invoice ID
+    pub id: i32,
+    /// Invoice number
+    pub number: String,
+    /// Customer ID
+    pub customer_id: i32,
+    /// Customer name
+    pub customer_name: String,
+    /// Customer email
+    pub customer_email: String,
+    /// Amount still owed in cents
+    pub balance_cents: i32,
+    /// Date payment was due
+    pub due_date: time::Date,
+    /// Number of reminders already sent
+    pub reminder_count: i32,
+}
+
+/// Business Model Controller for invoice operations.
+pub struct InvoiceBmc;
+
+impl InvoiceBmc {
+    /// Generates a draft invoice for a completed booking.
+    ///
+    /// Line items are taken from `items` when given, otherwise from the
+    /// booking's accepted quote (including its discount): the shared items
+    /// plus the option and add-ons the customer chose. Billable materials
+    /// recorded on the booking are appended either way, so a job billed
+    /// for materials alone needs neither.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `booking_id` - Completed booking to bill
+    /// * `items` - Explicit line items (for jobs that were not quoted)
+    /// * `settings` - VAT mode and rate
+    ///
+    /// # Returns
+    ///
+    /// The ID of the new draft invoice.
+    ///
+    /// # Errors
+    ///
+    /// - `EntityNotFound` if the booking does not exist
+    /// - `ValidationError` if the booking is not completed, already has a
+    ///   live invoice (including one generated concurrently), or there are
+    ///   no line items
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm, items, settings))]
+    pub async fn generate_from_booking(
+        mm: &ModelManager,
+        booking_id: i32,
+        items: Option<Vec<QuoteItem>>,
+        settings: &InvoiceSettings,
+    ) -> Result<i32> {
+        let booking: Option<(Option<i32>, String, Option<i32>)> =
+            sqlx::query_as("SELECT customer_id, status, quote_id FROM bookings WHERE id = $1")
+                .bind(booking_id)
+                .fetch_optional(mm.dbx().db())
+                .await?;
+        let Some((customer_id, status, booking_quote_id)) = booking else {
+            return Err(Error::EntityNotFound {
+                entity: "Booking",
+                id: booking_id as i64,
+            });
+        };
+        if status != "completed" {
+            return Err(Error::ValidationError(
+                format!("Only completed bookings can be invoiced (booking is {status})").into(),
+            ));
+        }
+
+        let (already_invoiced,): (bool,) = sqlx::query_as(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM invoices
+                WHERE booking_id = $1 AND kind = 'invoice' AND status <> 'void'
+            )
+            "#,
+        )
+        .bind(booking_id)
+        .fetch_one(mm.dbx().db())
+        .await?;
+        if already_invoiced {
+            return Err(Error::ValidationError(
+                "Booking has already been invoiced".into(),
+            ));
+        }
+
+        let quote: Option<Quote> = sqlx::query_as(&format!(
+            r#"
+            SELECT {QUOTE_COLUMNS}
+            FROM quotes
+            WHERE (id = $1 OR booking_id = $2) AND status = 'accepted'
+            ORDER BY accepted_at DESC NULLS LAST
+            LIMIT 1
+            "#
+        ))
+        .bind(booking_quote_id)
+        .bind(booking_id)
+        .fetch_optional(mm.dbx().db())
+        .await?;
+
+        let materials = MaterialBmc::invoice_items(mm, booking_id).await?;
+        let (quote_id, mut items, quote_discount_cents) = match (items, quote) {
+            (Some(items), quote) => (quote.map(|q| q.id), items, 0),
+            (None, Some(quote)) => (
+                Some(quote.id),
+                quote.selected_items()?,
+                quote.discount_cents,
+            ),
+            (None, None) if !materials.is_empty() => (None, Vec::new(), 0),
+            (None, None) => {
+                return Err(Error::ValidationError(
+                    "Booking has no accepted quote; line items are required".into(),
+                ))
+            }
+        };
+        items.extend(materials);
+        if items.is_empty() {
+            return Err(Error::ValidationError(
+                "An invoice needs at least one line item".into(),
+            ));
+        }
+
+        // The quote's discount covers line discounts, its own discount and
+        // any promo code; whatever the lines don't account for is carried
+        // over as one fixed order discount so the invoice matches the quote.
+        let vat = settings.vat();
+        let line_discount_cents = pricing::price(&items, &[], &vat)?.line_discount_cents;
+        let order_discount = Discount::Fixed {
+            amount_cents: (quote_discount_cents - line_discount_cents).max(0),
+        };
+        let totals = pricing::price(&items, &[order_discount], &vat)?;
+        let items_json = serde_json::to_value(&items)
+            .map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))?;
+        let vat_json = serde_json::to_value(&totals.vat_bands)
+            .map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))?;
+
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO invoices (kind, customer_id, booking_id, quote_id, items,
+                                  subtotal_cents, discount_cents, vat_registered, vat_rate_bp,
+                                  vat_cents, vat_breakdown, total_cents, status)
+            VALUES ('invoice', $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, 'draft')
+            RETURNING id
+            "#,
+        )
+        .bind(customer_id)
+        .bind(booking_id)
+        .bind(quote_id)
+        .bind(&items_json)
+        .bind(totals.subtotal_cents)
+        .bind(totals.discount_cents)
+        .bind(settings.vat_registered)
+        .bind(settings.effective_rate_bp())
+        .bind(totals.vat_cents)
+        .bind(&vat_json)
+        .bind(totals.total_cents)
+        .fetch_one(mm.dbx().db())
+        .await
+        .map_err(live_invoice_conflict)?;
+
+        Ok(row.0)
+    }
+
+    /// Gets an invoice or credit note by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Invoice ID to retrieve
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the invoice does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<Invoice> {
+        let invoice = mm
+            .dbx()
+            .fetch_optional(
+                sqlx::query_as::<_, Invoice>(&format!(
+                    "SELECT {INVOICE_COLUMNS} FROM invoices WHERE id = $1"
+                ))
+                .bind(id),
+            )
+            .await?;
+
+        invoice.ok_or(Error::EntityNotFound {
+            entity: "Invoice",
+            id: id as i64,
+        })
+    }
+
+    /// Lists invoices and credit notes, newest first.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager) -> Result<Vec<Invoice>> {
+        let invoices = sqlx::query_as(&format!(
+            "SELECT {INVOICE_COLUMNS} FROM invoices ORDER BY created_at DESC"
+        ))
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(invoices)
+    }
+
+    /// Lists invoices and credit notes for a specific customer.
+    #[instrument(skip(mm))]
+    pub async fn list_by_customer(mm: &ModelManager, customer_id: i32) -> Result<Vec<Invoice>> {
+        let invoices = sqlx::query_as(&format!(
+            "SELECT {INVOICE_COLUMNS} FROM invoices WHERE customer_id = $1 ORDER BY created_at DESC"
+        ))
+        .bind(customer_id)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(invoices)
+    }
+
+    /// Issues a draft invoice: assigns the next invoice number and sets the
+    /// issue and due dates.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Draft invoice to issue
+    /// * `settings` - Payment terms
+    ///
+    /// # Returns
+    ///
+    /// The assigned invoice number.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the invoice is not a draft.
+    #[instrument(skip(mm, settings))]
+    pub async fn issue(mm: &ModelManager, id: i32, settings: &InvoiceSettings) -> Result<String> {
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        let invoice = Self::get_for_update(&mm, id).await?;
+        if invoice.status != "draft" {
+            return Err(Error::ValidationError(
+                format!("Only drafts can be issued (invoice is {})", invoice.status).into(),
+            ));
+        }
+
+        let number = next_number(&mm, "invoice").await?;
+        dbx.execute(
+            sqlx::query(
+                r#"
+                UPDATE invoices
+                SET number = $2,
+                    status = 'issued',
+                    issue_date = CURRENT_DATE,
+                    due_date = CURRENT_DATE + $3
+                WHERE id = $1
+                "#,
+            )
+            .bind(id)
+            .bind(&number)
+            .bind(settings.payment_terms_days),
+        )
+        .await?;
+
+        dbx.commit_txn().await?;
+
+        Ok(number)
+    }
+
+    /// Records a (possibly partial) payment against an issued invoice.
+    ///
+    /// The invoice becomes `paid` once payments and credits cover its total.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Invoice being paid
+    /// * `payment` - Amount and method
+    ///
+    /// # Returns
+    ///
+    /// The ID of the new payment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the invoice is not awaiting payment or
+    /// the amount is not between 1 cent and the outstanding balance.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm))]
+    pub async fn record_payment(
+        mm: &ModelManager,
+        id: i32,
+        payment: InvoicePaymentForCreate,
+    ) -> Result<i32> {
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        let invoice = Self::get_for_update(&mm, id).await?;
+        if invoice.kind != "invoice" || !matches!(invoice.status.as_str(), "issued" | "overdue") {
+            return Err(Error::ValidationError(
+                format!(
+                    "Payments can only be recorded against issued invoices (invoice is {})",
+                    invoice.status
+                )
+                .into(),
+            ));
+        }
+        if payment.amount_cents <= 0 || payment.amount_cents > invoice.balance_cents() {
+            return Err(Error::ValidationError(
+                format!(
+                    "Payment must be between 1 and {} cents",
+                    invoice.balance_cents()
+                )
+                .into(),
+            ));
+        }
+
+        let (payment_id,): (i32,) = dbx
+            .fetch_one(
+                sqlx::query_as(
+                    r#"
+                    INSERT INTO payments (customer_id, booking_id, invoice_id, amount_cents,
+                                          method, reference, status)
+                    VALUES ($1, $2, $3, $4, $5, $6, 'succeeded')
+                    RETURNING id
+                    "#,
+                )
+                .bind(invoice.customer_id)
+                .bind(invoice.booking_id)
+                .bind(id)
+                .bind(payment.amount_cents)
+                .bind(&payment.method)
+                .bind(&payment.reference),
+            )
+            .await?;
+
+        dbx.execute(
+            sqlx::query(
+                r#"
+                UPDATE invoices
+                SET paid_cents = paid_cents + $2,
+                    status = CASE WHEN total_cents - paid_cents - $2 - credited_cents <= 0
+                                  THEN 'paid' ELSE status END
+                WHERE id = $1
+                "#,
+            )
+            .bind(id)
+            .bind(payment.amount_cents),
+        )
+        .await?;
+
+        dbx.commit_txn().await?;
+
+        Ok(payment_id)
+    }
+
+    /// Issues a credit note against an invoice.
+    ///
+    /// `amount_cents` is the gross amount credited; for VAT-registered
+    /// invoices it is split into net and VAT at the invoice's rate. Only the
+    /// outstanding balance can be credited, since money already paid has to
+    /// be refunded rather than credited; an invoice whose balance is credited
+    /// in full becomes `paid`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `invoice_id` - Invoice being credited
+    /// * `amount_cents` - Gross amount to credit
+    /// * `reason` - Reason printed on the credit note
+    ///
+    /// # Returns
+    ///
+    /// The ID of the new credit note.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the invoice has not been issued, has no
+    /// outstanding balance, or the amount exceeds it.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm))]
+    pub async fn create_credit_note(
+        mm: &ModelManager,
+        invoice_id: i32,
+        amount_cents: i32,
+        reason: &str,
+    ) -> Result<i32> {
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        let invoice = Self::get_for_update(&mm, invoice_id).await?;
+        if invoice.kind != "invoice"
+            || !matches!(invoice.status.as_str(), "issued" | "overdue" | "paid")
+        {
+            return Err(Error::ValidationError(
+                format!(
+                    "Credit notes can only be raised against issued invoices (invoice is {})",
+                    invoice.status
+                )
+                .into(),
+            ));
+        }
+        let creditable = invoice.balance_cents();
+        if creditable <= 0 {
+            return Err(Error::ValidationError(
+                "The invoice has no outstanding balance to credit; refund the payment instead"
+                    .into(),
+            ));
+        }
+        if amount_cents <= 0 || amount_cents > creditable {
+            return Err(Error::ValidationError(
+                format!("Credit must be between 1 and {creditable} cents").into(),
+            ));
+        }
+
+        // Credit VAT in the same proportions as the invoice, band by band
+        let net = proportion(
+            amount_cents,
+            invoice.total_cents - invoice.vat_cents,
+            invoice.total_cents,
+        );
+        let invoice_bands: Vec<VatBand> =
+            serde_json::from_value(invoice.vat_breakdown.clone()).unwrap_or_default();
+        let band_nets = pricing::allocate(
+            i64::from(net),
+            &invoice_bands
+                .iter()
+                .map(|b| i64::from(b.net_cents))
+                .collect::<Vec<_>>(),
+        );
+        let band_vats = pricing::allocate(
+            i64::from(amount_cents - net),
+            &invoice_bands
+                .iter()
+                .map(|b| i64::from(b.vat_cents))
+                .collect::<Vec<_>>(),
+        );
+        let credit_bands: Vec<VatBand> = invoice_bands
+            .iter()
+            .zip(band_nets.into_iter().zip(band_vats))
+            .map(|(band, (net_cents, vat_cents))| VatBand {
+                rate_bp: band.rate_bp,
+                net_cents: net_cents as i32,
+                vat_cents: vat_cents as i32,
+            })
+            .collect();
+        let vat_json = serde_json::to_value(&credit_bands)
+            .map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))?;
+        let items = serde_json::to_value(vec![QuoteItem {
+            description: format!(
+                "Credit against {}",
+                invoice.number.as_deref().unwrap_or_default()
+            ),
+            quantity: 1,
+            unit_price: net,
+            discount: None,
+            vat_rate_bp: None,
+        }])
+        .map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))?;
+
+        let number = next_number(&mm, "credit_note").await?;
+        let (credit_note_id,): (i32,) = dbx
+            .fetch_one(
+                sqlx::query_as(
+                    r#"
+                    INSERT INTO invoices (number, kind, credited_invoice_id, customer_id,
+                                          booking_id, items, subtotal_cents, vat_registered,
+                                          vat_rate_bp, vat_cents, vat_breakdown, total_cents,
+                                          status, notes, issue_date)
+                    VALUES ($1, 'credit_note', $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+                            'issued', $12, CURRENT_DATE)
+                    RETURNING id
+                    "#,
+                )
+                .bind(&number)
+                .bind(invoice_id)
+                .bind(invoice.customer_id)
+                .bind(invoice.booking_id)
+                .bind(&items)
+                .bind(net)
+                .bind(invoice.vat_registered)
+                .bind(invoice.vat_rate_bp)
+                .bind(amount_cents - net)
+                .bind(&vat_json)
+                .bind(amount_cents)
+                .bind(reason),
+            )
+            .await?;
+
+        dbx.execute(
+            sqlx::query(
+                r#"
+                UPDATE invoices
+                SET credited_cents = credited_cents + $2,
+                    status = CASE WHEN total_cents - paid_cents - credited_cents - $2 <= 0
+                                  THEN 'paid' ELSE status END
+                WHERE id = $1
+                "#,
+            )
+            .bind(invoice_id)
+            .bind(amount_cents),
+        )
+        .await?;
+
+        dbx.commit_txn().await?;
+
+        Ok(credit_note_id)
+    }
+
+    /// Voids an invoice that has nothing paid or credited against it.
+    ///
+    /// Issued invoices keep their number, so the sequence stays gap-free.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the invoice is paid, already void, or
+    /// has payments or credit notes against it.
+    #[instrument(skip(mm))]
+    pub async fn void(mm: &ModelManager, id: i32) -> Result<()> {
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        // Locked so a payment or credit can't land between the check and
+        // the update
+        let invoice = Self::get_for_update(&mm, id).await?;
+        if invoice.kind != "invoice"
+            || !matches!(invoice.status.as_str(), "draft" | "issued" | "overdue")
+            || invoice.paid_cents > 0
+            || invoice.credited_cents > 0
+        {
+            return Err(Error::ValidationError(
+                "Only invoices with nothing paid or credited can be voided; raise a credit note instead"
+                    .into(),
+            ));
+        }
+
+        dbx.execute(sqlx::query("UPDATE invoices SET status = 'void' WHERE id = $1").bind(id))
+            .await?;
+
+        dbx.commit_txn().await?;
+
+        Ok(())
+    }
+
+    /// Marks issued invoices past their due date as overdue.
+    ///
+    /// # Returns
+    ///
+    /// The number of invoices that became overdue.
+    #[instrument(skip(mm))]
+    pub async fn mark_overdue(mm: &ModelManager) -> Result<u64> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE invoices
+            SET status = 'overdue'
+            WHERE kind = 'invoice' AND status = 'issued' AND due_date < CURRENT_DATE
+            "#,
+        )
+        .execute(mm.dbx().db())
+        .await?
+        .rows_affected();
+
+        Ok(updated)
+    }
+
+    /// Lists overdue invoices that have not had a reminder in the last
+    /// `interval_days` days and whose customer has an email address.
+    #[instrument(skip(mm))]
+    pub async fn list_reminders_due(
+        mm: &ModelManager,
+        interval_days: i32,
+    ) -> Result<Vec<InvoiceReminder>> {
+        let reminders = sqlx::query_as(
+            r#"
+            SELECT i.id, i.number, c.id AS customer_id, c.name AS customer_name,
+                   c.email AS customer_email,
+                   i.total_cents - i.paid_cents - i.credited_cents AS balance_cents,
+                   i.due_date, i.reminder_count
+            FROM invoices i
+            JOIN customers c ON c.id = i.customer_id
+            WHERE i.kind = 'invoice' AND i.status = 'overdue'
+              AND i.number IS NOT NULL AND i.due_date IS NOT NULL
+              AND c.email IS NOT NULL
+              AND (i.last_reminder_at IS NULL
+                   OR i.last_reminder_at < CURRENT_TIMESTAMP - make_interval(days => $1))
+            ORDER BY i.due_date ASC
+            "#,
+        )
+        .bind(interval_days)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(reminders)
+    }
+
+    /// Records that an overdue reminder was sent.
+    #[instrument(skip(mm))]
+    pub async fn mark_reminded(mm: &ModelManager, id: i32) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE invoices
+            SET reminder_count = reminder_count + 1, last_reminder_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes a draft invoice. Issued documents can only be voided.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the invoice has been issued.
+    #[instrument(skip(mm))]
+    pub async fn delete_draft(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM invoices WHERE id = $1 AND status = 'draft'")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            // Distinguish "missing" from "not a draft"
+            Self::get(mm, id).await?;
+            return Err(Error::ValidationError(
+                "Issued invoices cannot be deleted; void them instead".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Locks an invoice row for the rest of the transaction.
+    async fn get_for_update(mm: &ModelManager, id: i32) -> Result<Invoice> {
+        let invoice = mm
+            .dbx()
+            .fetch_optional(
+                sqlx::query_as::<_, Invoice>(&format!(
+                    "SELECT {INVOICE_COLUMNS} FROM invoices WHERE id = $1 FOR UPDATE"
+                ))
+                .bind(id),
+            )
+            .await?;
+
+        invoice.ok_or(Error::EntityNotFound {
+            entity: "Invoice",
+            id: id as i64,
+        })
+    }
+}
+
+/// The index allowing one live invoice per booking.
+const LIVE_BOOKING_INDEX: &str = "idx_invoices_live_booking";
+
+/// Maps an insert refused by [`LIVE_BOOKING_INDEX`], when another request
+/// invoiced the booking first, to the error the up-front check gives.
+fn live_invoice_conflict(err: sqlx::Error) -> Error {
+    match &err {
+        sqlx::Error::Database(db) if db.constraint() == Some(LIVE_BOOKING_INDEX) => {
+            Error::ValidationError("Booking has already been invoiced".into())
+        }
+        _ => err.into(),
+    }
+}
+
+// region:    --- Numbering & VAT Helpers
+
+/// Takes the next number from a document sequence.
+///
+/// Must run inside the issuing transaction: the counter row stays locked
+/// until commit, and a rollback returns the number to the sequence.
+async fn next_number(mm: &ModelManager, kind: &'static str) -> Result<String> {
+    let (n,): (i32,) = mm
+        .dbx()
+        .fetch_one(
+            sqlx::query_as(
+                r#"
+                UPDATE document_counters
+                SET next_number = next_number + 1
+                WHERE kind = $1
+                RETURNING next_number - 1
+                "#,
+            )
+            .bind(kind),
+        )
+        .await?;
+
+    Ok(format_number(kind, n))
+}
+
+/// Formats a document number: `INV-000042`, `CN-000007`.
+fn format_number(kind: &str, n: i32) -> String {
+    let prefix = if kind == "credit_note" { "CN" } else { "INV" };
+    format!("{prefix}-{n:06}")
+}
+
+/// `amount × part / whole`, rounded half up. Used to split a VAT-inclusive
+/// credit into net and VAT in the invoice's proportions.
+fn proportion(amount: i32, part: i32, whole: i32) -> i32 {
+    if whole <= 0 {
+        return amount;
+    }
+    let whole = i64::from(whole);
+    let scaled = (i64::from(amount) * i64::from(part) + whole / 2) / whole;
+    scaled as i32
+}
+
+// endregion: --- Numbering & VAT Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::booking::{BookingBmc, BookingForCreate};
+
+    const VAT_20: InvoiceSettings = InvoiceSettings {
+        vat_registered: true,
+        vat_rate_bp: 2000,
+        payment_terms_days: 14,
+    };
+
+    fn test_items() -> Vec<QuoteItem> {
+        vec![
+            QuoteItem {
+                description: "Call-out fee".to_string(),
+                quantity: 1,
+                unit_price: 3000,
+                discount: None,
+                vat_rate_bp: None,
+            },
+            QuoteItem {
+                description: "Labour (1 hour)".to_string(),
+                quantity: 2,
+                unit_price: 4525,
+                discount: None,
+                vat_rate_bp: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number("invoice", 42), "INV-000042");
+        assert_eq!(format_number("credit_note", 7), "CN-000007");
+    }
+
+    #[test]
+    fn test_vat_settings_effective_rate() {
+        let settings = InvoiceSettings {
+            vat_registered: false,
+            ..VAT_20
+        };
+
+        assert_eq!(VAT_20.effective_rate_bp(), 2000);
+        assert_eq!(settings.effective_rate_bp(), 0);
+    }
+
+    #[test]
+    fn test_proportion_rounding() {
+        assert_eq!(proportion(1200, 10_000, 12_000), 1000);
+        assert_eq!(proportion(1000, 1000, 1000), 1000);
+        assert_eq!(proportion(1, 1, 2), 1); // 0.5 rounds up
+        assert_eq!(proportion(500, 0, 0), 500);
+    }
+
+    #[tokio::test]
+    async fn test_invoice_err_booking_not_completed() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let booking_id = BookingBmc::create(
+            &mm,
+            BookingForCreate {
+                customer_id: None,
+                service_type: "test_invoice_pending".to_string(),
+                scheduled_date: None,
+                scheduled_time: None,
+                notes: None,
+            },
+        )
+        .await?;
+
+        // Execute
+        let res =
+            InvoiceBmc::generate_from_booking(&mm, booking_id, Some(test_items()), &VAT_20).await;
+
+        // Check
+        assert!(matches!(res, Err(Error::ValidationError(_))));
+
+        // Cleanup
+        BookingBmc::delete(&mm, booking_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invoice_issue_and_partial_payments() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let booking_id = BookingBmc::create(
+            &mm,
+            BookingForCreate {
+                customer_id: None,
+                service_type: "test_invoice_flow".to_string(),
+                scheduled_date: None,
+                scheduled_time: None,
+                notes: None,
+            },
+        )
+        .await?;
+        BookingBmc::complete(&mm, booking_id, 90).await?;
+        let id =
+            InvoiceBmc::generate_from_booking(&mm, booking_id, Some(test_items()), &VAT_20).await?;
+
+        // Execute
+        let number = InvoiceBmc::issue(&mm, id, &VAT_20).await?;
+        let payment = |amount_cents| InvoicePaymentForCreate {
+            amount_cents,
+            method: "bank_transfer".to_string(),
+            reference: None,
+        };
+        let _ = InvoiceBmc::record_payment(&mm, id, payment(5000)).await?;
+        let partly_paid = InvoiceBmc::get(&mm, id).await?;
+        let _ = InvoiceBmc::record_payment(&mm, id, payment(partly_paid.balance_cents())).await?;
+        let paid = InvoiceBmc::get(&mm, id).await?;
+
+        // Check
+        assert!(number.starts_with("INV-"));
+        assert_eq!(partly_paid.status, "issued");
+        assert_eq!(paid.status, "paid");
+        assert_eq!(paid.balance_cents(), 0);
+        assert!(InvoiceBmc::void(&mm, id).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invoice_err_second_live_invoice() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let booking_id = BookingBmc::create(
+            &mm,
+            BookingForCreate {
+                customer_id: None,
+                service_type: "test_invoice_twice".to_string(),
+                scheduled_date: None,
+                scheduled_time: None,
+                notes: None,
+            },
+        )
+        .await?;
+        BookingBmc::complete(&mm, booking_id, 60).await?;
+        let id =
+            InvoiceBmc::generate_from_booking(&mm, booking_id, Some(test_items()), &VAT_20).await?;
+
+        // Execute
+        let again =
+            InvoiceBmc::generate_from_booking(&mm, booking_id, Some(test_items()), &VAT_20).await;
+        // As if a concurrent request had passed the up-front check
+        let raced = sqlx::query(
+            "INSERT INTO invoices (kind, booking_id, subtotal_cents, total_cents) \
+             VALUES ('invoice', $1, 100, 100)",
+        )
+        .bind(booking_id)
+        .execute(mm.dbx().db())
+        .await
+        .map_err(live_invoice_conflict);
+        InvoiceBmc::void(&mm, id).await?;
+        let after_void =
+            InvoiceBmc::generate_from_booking(&mm, booking_id, Some(test_items()), &VAT_20).await;
+
+        // Check
+        assert!(matches!(again, Err(Error::ValidationError(_))));
+        assert!(matches!(raced, Err(Error::ValidationError(_))));
+        assert!(after_void.is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invoice_credit_note_err_paid() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let booking_id = BookingBmc::create(
+            &mm,
+            BookingForCreate {
+                customer_id: None,
+                service_type: "test_invoice_credit".to_string(),
+                scheduled_date: None,
+                scheduled_time: None,
+                notes: None,
+            },
+        )
+        .await?;
+        BookingBmc::complete(&mm, booking_id, 60).await?;
+        let id =
+            InvoiceBmc::generate_from_booking(&mm, booking_id, Some(test_items()), &VAT_20).await?;
+        let _ = InvoiceBmc::issue(&mm, id, &VAT_20).await?;
+        let payment = InvoicePaymentForCreate {
+            amount_cents: 5000,
+            method: "bank_transfer".to_string(),
+            reference: None,
+        };
+        let _ = InvoiceBmc::record_payment(&mm, id, payment).await?;
+        let balance = InvoiceBmc::get(&mm, id).await?.balance_cents();
+
+        // Execute
+        let over = InvoiceBmc::create_credit_note(&mm, id, balance + 1, "Goodwill").await;
+        let credit_note_id = InvoiceBmc::create_credit_note(&mm, id, balance, "Goodwill").await?;
+        let settled = InvoiceBmc::get(&mm, id).await?;
+        let paid_off = InvoiceBmc::create_credit_note(&mm, id, 1, "Goodwill").await;
+
+        // Check
+        assert!(matches!(over, Err(Error::ValidationError(_))));
+        assert_eq!(
+            InvoiceBmc::get(&mm, credit_note_id).await?.total_cents,
+            balance
+        );
+        assert_eq!(settled.status, "paid");
+        assert_eq!(
+            settled.paid_cents + settled.credited_cents,
+            settled.total_cents
+        );
+        assert!(matches!(paid_off, Err(Error::ValidationError(_))));
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
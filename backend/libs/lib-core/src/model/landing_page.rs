@@ -0,0 +1,545 @@
+//! # Landing Page Model
+//!
+//! Area and service-in-town landing pages, assembled from
+//! [`crate::model::area`] and [`crate::model::service`].
+//!
+//! Every published area has an `/areas/{slug}` page. A
+//! `/services/{service}/{town}` page exists only once someone has written
+//! an introduction for that service in that town: pages that differ only
+//! by the town's name are duplicate content, so introductions must be at
+//! least [`MIN_INTRO_WORDS`] long and not near-copies of another
+//! introduction or of the area's or service's own description.
+//!
+//! ## Structures
+//!
+//! - [`ServiceAreaIntro`] - An introduction for a service in an area
+//! - [`LandingPageBmc`] - Business Model Controller for landing pages
+//!
+//! The page types themselves ([`AreaPage`], [`ServiceAreaPage`]) live in
+//! the `shared` crate so the Leptos site can render them.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::landing_page::LandingPageBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn show(mm: &ModelManager) -> Result<(), Box<dyn std::error::Error>> {
+//!     let page = LandingPageBmc::service_area_page(mm, "plumbing", "coventry").await?;
+//!     println!("{} in {}: {}", page.service.name, page.area.name, page.intro);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::content;
+use crate::model::area::{Area, AreaBmc};
+use crate::model::service::{Service, ServiceBmc};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::text::is_near_duplicate;
+use serde::{Deserialize, Serialize};
+use shared::{AreaPage, AreaService, PageLink, ServiceAreaPage, SitemapUrl, Testimonial};
+use sqlx::FromRow;
+use std::collections::HashMap;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Fewest words a service-in-town introduction may have.
+pub const MIN_INTRO_WORDS: usize = 20;
+
+/// An introduction for a service in an area.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct ServiceAreaIntro {
+    /// Service ID
+    pub service_id: i32,
+    /// Area ID
+    pub area_id: i32,
+    /// Copy unique to this service in this town
+    pub intro: String,
+    /// When the introduction was last changed
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+/// Business Model Controller for landing pages.
+pub struct LandingPageBmc;
+
+impl LandingPageBmc {
+    /// Writes or replaces the introduction for a service in an area, which
+    /// publishes its page.
+    ///
+    /// # Errors
+    ///
+    /// - `EntityNotFound` if the service or area does not exist
+    /// - `ValidationError` if the introduction is too short, or too like
+    ///   another introduction or the service's or area's description
+    #[instrument(skip(mm, intro))]
+    pub async fn set_intro(
+        mm: &ModelManager,
+        service_id: i32,
+        area_id: i32,
+        intro: &str,
+    ) -> Result<()> {
+        let service = ServiceBmc::get(mm, service_id).await?;
+        let area = AreaBmc::get(mm, area_id).await?;
+        let others: Vec<ServiceAreaIntro> = Self::list(mm)
+            .await?
+            .into_iter()
+            .filter(|i| (i.service_id, i.area_id) != (service_id, area_id))
+            .collect();
+        check_intro(intro, &service, &area, &others)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO service_area_pages (service_id, area_id, intro)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (service_id, area_id) DO UPDATE SET intro = EXCLUDED.intro
+            "#,
+        )
+        .bind(service_id)
+        .bind(area_id)
+        .bind(intro.trim())
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes the introduction for a service in an area, which takes its
+    /// page down.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if there is no introduction.
+    #[instrument(skip(mm))]
+    pub async fn remove_intro(mm: &ModelManager, service_id: i32, area_id: i32) -> Result<()> {
+        let rows_affected =
+            sqlx::query("DELETE FROM service_area_pages WHERE service_id = $1 AND area_id = $2")
+                .bind(service_id)
+                .bind(area_id)
+                .execute(mm.dbx().db())
+                .await?
+                .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "ServiceAreaIntro",
+                id: service_id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Lists every introduction, published or not.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager) -> Result<Vec<ServiceAreaIntro>> {
+        let intros = sqlx::query_as(
+            "SELECT service_id, area_id, intro, updated_at FROM service_area_pages \
+             ORDER BY area_id, service_id",
+        )
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(intros)
+    }
+
+    /// Assembles the page for a published area.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SlugNotFound` if no active area has the slug.
+    #[instrument(skip(mm))]
+    pub async fn area_page(mm: &ModelManager, slug: &str) -> Result<AreaPage> {
+        let area = AreaBmc::get_by_slug(mm, slug).await?;
+        let local: Vec<i32> =
+            sqlx::query_scalar("SELECT service_id FROM service_area_pages WHERE area_id = $1")
+                .bind(area.id)
+                .fetch_all(mm.dbx().db())
+                .await?;
+        let services = ServiceBmc::list(mm, true)
+            .await?
+            .into_iter()
+            .map(|s| AreaService {
+                local_page: local.contains(&s.id),
+                slug: s.slug,
+                name: s.name,
+                summary: s.summary,
+            })
+            .collect();
+        let other_areas = AreaBmc::list(mm, true)
+            .await?
+            .into_iter()
+            .filter(|a| a.id != area.id)
+            .map(|a| PageLink {
+                slug: a.slug,
+                name: a.name,
+            })
+            .collect();
+
+        Ok(AreaPage {
+            testimonial: testimonial(&area),
+            slug: area.slug,
+            name: area.name,
+            description: area.description,
+            postcodes: area.postcodes,
+            travel_time: area.travel_time,
+            services,
+            other_areas,
+        })
+    }
+
+    /// Assembles the page for a service in a town.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SlugNotFound` if the service or area is not published, or
+    /// nobody has written an introduction for the pair.
+    #[instrument(skip(mm))]
+    pub async fn service_area_page(
+        mm: &ModelManager,
+        service_slug: &str,
+        area_slug: &str,
+    ) -> Result<ServiceAreaPage> {
+        let service = ServiceBmc::get_by_slug(mm, service_slug).await?;
+        let area = AreaBmc::get_by_slug(mm, area_slug).await?;
+        let intro: String = sqlx::query_scalar(
+            "SELECT intro FROM service_area_pages WHERE service_id = $1 AND area_id = $2",
+        )
+        .bind(service.id)
+        .bind(area.id)
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or_else(|| Error::SlugNotFound {
+            entity: "ServiceAreaPage",
+            slug: format!("{service_slug}/{area_slug}"),
+        })?;
+
+        let price: Option<(i32, i32)> = sqlx::query_as(
+            "SELECT base_low_cents, base_high_cents FROM service_rates \
+             WHERE service_type = $1 AND active",
+        )
+        .bind(&service.service_type)
+        .fetch_optional(mm.dbx().db())
+        .await?;
+        let other_services = links(
+            mm,
+            r#"
+            SELECT s.slug, s.name FROM service_area_pages p
+            JOIN services s ON s.id = p.service_id
+            WHERE p.area_id = $1 AND p.service_id <> $2 AND s.active
+            ORDER BY s.sort_order, s.name
+            "#,
+            area.id,
+            service.id,
+        )
+        .await?;
+        let other_areas = links(
+            mm,
+            r#"
+            SELECT a.slug, a.name FROM service_area_pages p
+            JOIN service_areas a ON a.id = p.area_id
+            WHERE p.service_id = $1 AND p.area_id <> $2 AND a.active
+            ORDER BY a.sort_order, a.name
+            "#,
+            service.id,
+            area.id,
+        )
+        .await?;
+
+        Ok(ServiceAreaPage {
+            testimonial: testimonial(&area),
+            service: PageLink {
+                slug: service.slug,
+                name: service.name,
+            },
+            area: PageLink {
+                slug: area.slug,
+                name: area.name,
+            },
+            intro,
+            description: content::render(&service.description).html,
+            price_from_cents: price.map(|p| p.0),
+            price_to_cents: price.map(|p| p.1),
+            postcodes: area.postcodes,
+            travel_time: area.travel_time,
+            other_services,
+            other_areas,
+        })
+    }
+
+    /// Every published landing page for the sitemap, areas first, with
+    /// paths relative to the site root (e.g. `/areas/coventry`,
+    /// `/services/plumbing/coventry`). A page was last modified when its
+    /// area, service or introduction last changed, and a service page lists
+    /// the images in the service's description.
+    #[instrument(skip(mm))]
+    pub async fn sitemap_urls(mm: &ModelManager) -> Result<Vec<SitemapUrl>> {
+        let mut urls: Vec<SitemapUrl> = AreaBmc::list(mm, true)
+            .await?
+            .into_iter()
+            .map(|a| SitemapUrl {
+                path: format!("/areas/{}", a.slug),
+                lastmod: a.updated_at.or(a.created_at).and_then(rfc3339),
+                images: Vec::new(),
+            })
+            .collect();
+        let pages: Vec<(String, String, String, Option<OffsetDateTime>)> = sqlx::query_as(
+            r#"
+            SELECT s.slug, a.slug, s.description,
+                GREATEST(s.updated_at, a.updated_at, p.updated_at)
+            FROM service_area_pages p
+            JOIN services s ON s.id = p.service_id
+            JOIN service_areas a ON a.id = p.area_id
+            WHERE s.active AND a.active
+            ORDER BY s.sort_order, s.name, a.sort_order, a.name
+            "#,
+        )
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        let mut images: HashMap<String, Vec<String>> = HashMap::new();
+        for (service, area, description, updated_at) in pages {
+            let service_images = images
+                .entry(service.clone())
+                .or_insert_with(|| content::render(&description).images);
+            urls.push(SitemapUrl {
+                path: format!("/services/{service}/{area}"),
+                lastmod: updated_at.and_then(rfc3339),
+                images: service_images.clone(),
+            });
+        }
+
+        Ok(urls)
+    }
+}
+
+// region:    --- Landing Page Helpers
+
+fn rfc3339(at: OffsetDateTime) -> Option<String> {
+    at.format(&Rfc3339).ok()
+}
+
+fn testimonial(area: &Area) -> Option<Testimonial> {
+    Some(Testimonial {
+        quote: area.testimonial_quote.clone()?,
+        author: area.testimonial_author.clone()?,
+    })
+}
+
+/// Runs a `(slug, name)` query with two ID parameters.
+async fn links(mm: &ModelManager, sql: &str, a: i32, b: i32) -> Result<Vec<PageLink>> {
+    let rows: Vec<(String, String)> = sqlx::query_as(sql)
+        .bind(a)
+        .bind(b)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(slug, name)| PageLink { slug, name })
+        .collect())
+}
+
+/// Checks an introduction is long enough and says something new.
+fn check_intro(
+    intro: &str,
+    service: &Service,
+    area: &Area,
+    others: &[ServiceAreaIntro],
+) -> Result<()> {
+    let words = intro.split_whitespace().count();
+    if words < MIN_INTRO_WORDS {
+        return Err(Error::ValidationError(
+            format!(
+                "Introduction has {words} words; write at least {MIN_INTRO_WORDS} about {} in {}",
+                service.name, area.name
+            )
+            .into(),
+        ));
+    }
+    if is_near_duplicate(intro, &service.description) || is_near_duplicate(intro, &area.description)
+    {
+        return Err(Error::ValidationError(
+            "Introduction repeats the service or area description; say something new".into(),
+        ));
+    }
+    if others
+        .iter()
+        .any(|other| is_near_duplicate(intro, &other.intro))
+    {
+        return Err(Error::ValidationError(
+            "Introduction is too like another page's; search engines would treat them as \
+             duplicates"
+                .into(),
+        ));
+    }
+
+    Ok(())
+}
+
+// endregion: --- Landing Page Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::area::AreaForCreate;
+    use crate::model::service::ServiceForCreate;
+
+    const PLUMBING_INTRO: &str = "Leamington's Regency terraces hide some creative plumbing: \
+        we trace leaks under suspended floors, swap seized stopcocks and replace the old lead \
+        runs that still turn up around Clarendon Square.";
+
+    /// `text` with each word tagged with the test's `suffix`, so fixtures
+    /// left by other runs, or by tests running alongside, never count as
+    /// duplicates of this run's.
+    fn fx_unique(text: &str, suffix: &str) -> String {
+        text.split_whitespace()
+            .map(|word| format!("{suffix}{word}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[tokio::test]
+    async fn test_intro_publishes_page() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let suffix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let area_id = AreaBmc::create(
+            &mm,
+            AreaForCreate {
+                slug: Some(format!("leamington-{suffix}")),
+                name: "Leamington Spa".to_string(),
+                description: fx_unique("Spa town south of Coventry.", &suffix),
+                postcodes: vec![],
+                travel_time: None,
+                testimonial_quote: Some("Tidy and quick.".to_string()),
+                testimonial_author: Some("Ann, CV32".to_string()),
+                sort_order: 999,
+                active: true,
+            },
+        )
+        .await?;
+        let service_id = ServiceBmc::create(
+            &mm,
+            ServiceForCreate {
+                slug: Some(format!("leak-repair-{suffix}")),
+                name: "Leak Repair".to_string(),
+                summary: "Leaks found and fixed".to_string(),
+                description: fx_unique("We find and fix leaks.", &suffix),
+                service_type: None,
+                sort_order: 999,
+                active: true,
+            },
+        )
+        .await?;
+        let (service_slug, area_slug) = (
+            format!("leak-repair-{suffix}"),
+            format!("leamington-{suffix}"),
+        );
+
+        // Execute
+        let before = LandingPageBmc::service_area_page(&mm, &service_slug, &area_slug).await;
+        let too_short = LandingPageBmc::set_intro(&mm, service_id, area_id, "We fix leaks.").await;
+        let intro = fx_unique(PLUMBING_INTRO, &suffix);
+        LandingPageBmc::set_intro(&mm, service_id, area_id, &intro).await?;
+        let page = LandingPageBmc::service_area_page(&mm, &service_slug, &area_slug).await?;
+        let area_page = LandingPageBmc::area_page(&mm, &area_slug).await?;
+        let urls = LandingPageBmc::sitemap_urls(&mm).await?;
+
+        // Check
+        assert!(matches!(before, Err(Error::SlugNotFound { .. })));
+        assert!(too_short.is_err());
+        assert_eq!(page.intro, intro);
+        assert_eq!(
+            page.testimonial.map(|t| t.author).as_deref(),
+            Some("Ann, CV32")
+        );
+        assert!(area_page
+            .services
+            .iter()
+            .any(|s| s.slug == service_slug && s.local_page));
+        let service_url = urls
+            .iter()
+            .find(|url| url.path == format!("/services/{service_slug}/{area_slug}"));
+        assert!(urls
+            .iter()
+            .any(|url| url.path == format!("/areas/{area_slug}")));
+        assert!(service_url.is_some_and(|url| url.lastmod.is_some()));
+
+        // Cleanup
+        ServiceBmc::delete(&mm, service_id).await?;
+        AreaBmc::delete(&mm, area_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_intro_refused() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let suffix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let mut area_ids = Vec::new();
+        for (town, about) in [
+            ("Warwick", "Castle, racecourse and narrow medieval streets"),
+            ("Kenilworth", "Commuter favourite with a ruined abbey"),
+        ] {
+            let id = AreaBmc::create(
+                &mm,
+                AreaForCreate {
+                    slug: Some(format!("{}-{suffix}", town.to_lowercase())),
+                    name: town.to_string(),
+                    description: fx_unique(about, &suffix),
+                    postcodes: vec![],
+                    travel_time: None,
+                    testimonial_quote: None,
+                    testimonial_author: None,
+                    sort_order: 999,
+                    active: true,
+                },
+            )
+            .await?;
+            area_ids.push(id);
+        }
+        let service_id = ServiceBmc::create(
+            &mm,
+            ServiceForCreate {
+                slug: Some(format!("pipework-{suffix}")),
+                name: "Pipework".to_string(),
+                summary: "Pipes replaced".to_string(),
+                description: fx_unique("New pipework.", &suffix),
+                service_type: None,
+                sort_order: 999,
+                active: true,
+            },
+        )
+        .await?;
+
+        // Execute
+        let intro = fx_unique(PLUMBING_INTRO, &suffix);
+        LandingPageBmc::set_intro(&mm, service_id, area_ids[0], &intro).await?;
+        let town_swapped = intro.replace("Leamington", "Kenilworth");
+        let duplicate =
+            LandingPageBmc::set_intro(&mm, service_id, area_ids[1], &town_swapped).await;
+        // Rewriting the same page is not a duplicate of itself
+        let rewrite = LandingPageBmc::set_intro(&mm, service_id, area_ids[0], &intro).await;
+
+        // Check
+        assert!(duplicate.is_err());
+        assert!(rewrite.is_ok());
+
+        // Cleanup
+        ServiceBmc::delete(&mm, service_id).await?;
+        for id in area_ids {
+            AreaBmc::delete(&mm, id).await?;
+        }
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,368 @@
+//! # Margin Model
+//!
+//! What completed jobs earned against what they cost.
+//!
+//! For each completed booking the report sets what was charged against
+//! labour and materials:
+//!
+//! - **Revenue** - net (ex-VAT) value of the booking's issued invoices,
+//!   less any credit notes raised against them. Drafts and voided
+//!   invoices are ignored, so an uninvoiced job shows no revenue.
+//! - **Labour** - the job's actual duration (falling back to the
+//!   estimate) at `LABOUR_COST_PER_HOUR_CENTS`.
+//! - **Materials** - the recorded cost of every material line on the
+//!   booking, billable or not.
+//!
+//! Jobs are then rolled up per service type and overall.
+//!
+//! ## Structures
+//!
+//! - [`MarginFilter`] - Date range and service type to report on
+//! - [`JobMargin`] - Margin on one completed job
+//! - [`ServiceMargin`] - Margins rolled up for a service type
+//! - [`MarginReport`] - Jobs, per-service rollups and totals
+//! - [`MarginBmc`] - Business Model Controller for margin reports
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::margin::{MarginBmc, MarginFilter};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn last_month(mm: &ModelManager) -> Result<(), Box<dyn std::error::Error>> {
+//!     let report = MarginBmc::report(
+//!         mm,
+//!         MarginFilter {
+//!             from: Some("2026-09-01".to_string()),
+//!             to: Some("2026-09-30".to_string()),
+//!             service_type: None,
+//!         },
+//!     )
+//!     .await?;
+//!     for service in &report.services {
+//!         println!("{}: {} cents", service.service_type, service.margin_cents);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::core_config;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use time::format_description::well_known::Iso8601;
+use time::{Date, OffsetDateTime};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Date range and service type to report on. Dates are `YYYY-MM-DD` and
+/// inclusive, and apply to when the job was completed.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct MarginFilter {
+    /// First completion date to include
+    pub from: Option<String>,
+    /// Last completion date to include
+    pub to: Option<String>,
+    /// Only jobs of this service type
+    pub service_type: Option<String>,
+}
+
+/// Margin on one completed job. All amounts are in cents, ex-VAT.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JobMargin {
+    /// Booking ID
+    pub booking_id: i32,
+    /// Service type of the booking
+    pub service_type: String,
+    /// When the job was completed
+    pub completed_at: Option<OffsetDateTime>,
+    /// Minutes of labour costed
+    pub labour_minutes: i32,
+    /// Net invoiced, less credit notes
+    pub revenue_cents: i64,
+    /// Labour minutes at the configured hourly cost
+    pub labour_cost_cents: i64,
+    /// Recorded cost of materials used
+    pub material_cost_cents: i64,
+    /// Revenue less labour and materials
+    pub margin_cents: i64,
+    /// Margin as basis points of revenue (`None` without revenue)
+    pub margin_bp: Option<i64>,
+}
+
+/// Margins rolled up for a service type (or for every job, in
+/// [`MarginReport::totals`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ServiceMargin {
+    /// Service type (`all` in the totals)
+    pub service_type: String,
+    /// Number of jobs
+    pub jobs: i64,
+    /// Minutes of labour
+    pub labour_minutes: i64,
+    /// Net invoiced, less credit notes
+    pub revenue_cents: i64,
+    /// Labour cost
+    pub labour_cost_cents: i64,
+    /// Material cost
+    pub material_cost_cents: i64,
+    /// Revenue less labour and materials
+    pub margin_cents: i64,
+    /// Margin as basis points of revenue (`None` without revenue)
+    pub margin_bp: Option<i64>,
+}
+
+/// Margin report over a range of completed jobs.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MarginReport {
+    /// Hourly labour cost the report was run with, in cents
+    pub labour_cost_per_hour_cents: i64,
+    /// Jobs, most recently completed first
+    pub jobs: Vec<JobMargin>,
+    /// Rollups per service type, by service type
+    pub services: Vec<ServiceMargin>,
+    /// Rollup of every job
+    pub totals: ServiceMargin,
+}
+
+/// Costs and revenue of one job as read from the database.
+#[derive(Debug, FromRow)]
+struct JobCosts {
+    booking_id: i32,
+    service_type: String,
+    completed_at: Option<OffsetDateTime>,
+    labour_minutes: i32,
+    revenue_cents: i64,
+    material_cost_cents: i64,
+}
+
+/// Business Model Controller for margin reports.
+pub struct MarginBmc;
+
+impl MarginBmc {
+    /// Reports margins on completed jobs.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `filter` - Completion date range and service type
+    ///
+    /// # Returns
+    ///
+    /// Each matching job's margin, with rollups per service type and in
+    /// total.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if a date is not a valid `YYYY-MM-DD` date.
+    #[instrument(skip(mm))]
+    pub async fn report(mm: &ModelManager, filter: MarginFilter) -> Result<MarginReport> {
+        let from = parse_date("from", filter.from.as_deref())?;
+        let to = parse_date("to", filter.to.as_deref())?;
+
+        let rows: Vec<JobCosts> = sqlx::query_as(
+            r#"
+            SELECT b.id AS booking_id,
+                   b.service_type,
+                   b.completed_at,
+                   COALESCE(b.actual_duration, b.estimated_duration, 0) AS labour_minutes,
+                   COALESCE((
+                       SELECT SUM(CASE WHEN i.kind = 'credit_note'
+                                       THEN -(i.total_cents - i.vat_cents)
+                                       ELSE i.total_cents - i.vat_cents END)
+                       FROM invoices i
+                       WHERE i.booking_id = b.id
+                         AND ((i.kind = 'invoice' AND i.status IN ('issued', 'overdue', 'paid'))
+                              OR (i.kind = 'credit_note' AND i.status <> 'void'))
+                   ), 0)::BIGINT AS revenue_cents,
+                   COALESCE((
+                       SELECT SUM(m.quantity::BIGINT * m.unit_cost_cents)
+                       FROM booking_materials m
+                       WHERE m.booking_id = b.id
+                   ), 0)::BIGINT AS material_cost_cents
+            FROM bookings b
+            WHERE b.status = 'completed'
+              AND ($1::DATE IS NULL OR b.completed_at::DATE >= $1)
+              AND ($2::DATE IS NULL OR b.completed_at::DATE <= $2)
+              AND ($3::TEXT IS NULL OR b.service_type = $3)
+            ORDER BY b.completed_at DESC NULLS LAST, b.id DESC
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(&filter.service_type)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        let rate = core_config().LABOUR_COST_PER_HOUR_CENTS;
+        let jobs: Vec<JobMargin> = rows.into_iter().map(|row| job_margin(row, rate)).collect();
+        let (services, totals) = summarise(&jobs);
+
+        Ok(MarginReport {
+            labour_cost_per_hour_cents: rate,
+            jobs,
+            services,
+            totals,
+        })
+    }
+}
+
+// region:    --- Margin Helpers
+
+/// Parses an optional `YYYY-MM-DD` query value; blank means not given.
+pub(crate) fn parse_date(field: &str, value: Option<&str>) -> Result<Option<Date>> {
+    value
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| {
+            Date::parse(v.trim(), &Iso8601::DATE).map_err(|_| {
+                Error::ValidationError(format!("{field} must be a YYYY-MM-DD date").into())
+            })
+        })
+        .transpose()
+}
+
+/// Cost of `minutes` at `rate_per_hour`, rounded to the nearest cent.
+fn labour_cost(minutes: i64, rate_per_hour: i64) -> i64 {
+    (minutes * rate_per_hour + 30) / 60
+}
+
+fn margin_bp(margin_cents: i64, revenue_cents: i64) -> Option<i64> {
+    (revenue_cents > 0).then(|| margin_cents * 10_000 / revenue_cents)
+}
+
+fn job_margin(row: JobCosts, rate_per_hour: i64) -> JobMargin {
+    let labour_cost_cents = labour_cost(i64::from(row.labour_minutes), rate_per_hour);
+    let margin_cents = row.revenue_cents - labour_cost_cents - row.material_cost_cents;
+
+    JobMargin {
+        booking_id: row.booking_id,
+        service_type: row.service_type,
+        completed_at: row.completed_at,
+        labour_minutes: row.labour_minutes,
+        revenue_cents: row.revenue_cents,
+        labour_cost_cents,
+        material_cost_cents: row.material_cost_cents,
+        margin_cents,
+        margin_bp: margin_bp(margin_cents, row.revenue_cents),
+    }
+}
+
+fn add_job(rollup: &mut ServiceMargin, job: &JobMargin) {
+    rollup.jobs += 1;
+    rollup.labour_minutes += i64::from(job.labour_minutes);
+    rollup.revenue_cents += job.revenue_cents;
+    rollup.labour_cost_cents += job.labour_cost_cents;
+    rollup.material_cost_cents += job.material_cost_cents;
+    rollup.margin_cents += job.margin_cents;
+    rollup.margin_bp = margin_bp(rollup.margin_cents, rollup.revenue_cents);
+}
+
+/// Rolls jobs up per service type (sorted by service type) and overall.
+fn summarise(jobs: &[JobMargin]) -> (Vec<ServiceMargin>, ServiceMargin) {
+    let mut services: BTreeMap<&str, ServiceMargin> = BTreeMap::new();
+    let mut totals = ServiceMargin {
+        service_type: "all".to_string(),
+        ..Default::default()
+    };
+
+    for job in jobs {
+        let service = services
+            .entry(&job.service_type)
+            .or_insert_with(|| ServiceMargin {
+                service_type: job.service_type.clone(),
+                ..Default::default()
+            });
+        add_job(service, job);
+        add_job(&mut totals, job);
+    }
+
+    (services.into_values().collect(), totals)
+}
+
+// endregion: --- Margin Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fx_job(service_type: &str, minutes: i32, revenue: i64, materials: i64) -> JobMargin {
+        job_margin(
+            JobCosts {
+                booking_id: 1,
+                service_type: service_type.to_string(),
+                completed_at: None,
+                labour_minutes: minutes,
+                revenue_cents: revenue,
+                material_cost_cents: materials,
+            },
+            2500,
+        )
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert!(parse_date("from", None).unwrap().is_none());
+        assert!(parse_date("from", Some("")).unwrap().is_none());
+        assert_eq!(
+            parse_date("from", Some("2026-09-01")).unwrap(),
+            Some(Date::from_calendar_date(2026, time::Month::September, 1).unwrap())
+        );
+        assert!(matches!(
+            parse_date("to", Some("01/09/2026")),
+            Err(Error::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_labour_cost_rounds_to_nearest_cent() {
+        assert_eq!(labour_cost(60, 2500), 2500);
+        assert_eq!(labour_cost(1, 2500), 42); // 41.67
+        assert_eq!(labour_cost(1, 90), 2); // 1.5 rounds up
+        assert_eq!(labour_cost(0, 2500), 0);
+    }
+
+    #[test]
+    fn test_job_margin() {
+        let job = fx_job("plumbing", 90, 10_000, 1_250);
+
+        assert_eq!(job.labour_cost_cents, 3_750);
+        assert_eq!(job.margin_cents, 5_000);
+        assert_eq!(job.margin_bp, Some(5_000));
+
+        let uninvoiced = fx_job("plumbing", 60, 0, 500);
+        assert_eq!(uninvoiced.margin_cents, -3_000);
+        assert_eq!(uninvoiced.margin_bp, None);
+    }
+
+    #[test]
+    fn test_summarise_groups_by_service_type() {
+        let jobs = vec![
+            fx_job("plumbing", 60, 10_000, 1_000),
+            fx_job("electrical", 120, 20_000, 0),
+            fx_job("plumbing", 30, 5_000, 250),
+        ];
+
+        let (services, totals) = summarise(&jobs);
+
+        assert_eq!(services.len(), 2);
+        assert_eq!(services[0].service_type, "electrical");
+        assert_eq!(services[1].service_type, "plumbing");
+        assert_eq!(services[1].jobs, 2);
+        assert_eq!(services[1].labour_minutes, 90);
+        assert_eq!(services[1].revenue_cents, 15_000);
+        assert_eq!(services[1].margin_cents, 15_000 - 3_750 - 1_250);
+        assert_eq!(services[1].margin_bp, Some(6_666));
+        assert_eq!(totals.jobs, 3);
+        assert_eq!(totals.revenue_cents, 35_000);
+        assert_eq!(
+            totals.margin_cents,
+            services.iter().map(|s| s.margin_cents).sum::<i64>()
+        );
+    }
+}
+
+// endregion: --- Tests
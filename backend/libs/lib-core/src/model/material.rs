@@ -0,0 +1,712 @@
+//! # Material Model
+//!
+//! A catalogue of materials and parts, and what each booking used.
+//!
+//! Catalogue entries carry what an item costs us and what we charge for
+//! it. Recording usage on a booking copies the entry's description, unit
+//! and prices onto the usage line, so changing the catalogue never alters
+//! a finished job. Lines can also be entered ad hoc, without a catalogue
+//! entry, for one-off purchases.
+//!
+//! ## Billing
+//!
+//! Billable lines become line items on the booking's invoice (see
+//! [`crate::model::invoice::InvoiceBmc::generate_from_booking`]). Mark a
+//! line as not billable when the accepted quote already charges for it;
+//! its cost still counts against the job in the margin report. Usage is
+//! locked once the booking has a live invoice.
+//!
+//! ## Structures
+//!
+//! - [`Material`] - Catalogue entry from database
+//! - [`MaterialForCreate`] - Data required to add a catalogue entry
+//! - [`MaterialForUpdate`] - Data for updating a catalogue entry
+//! - [`BookingMaterial`] - Material used on a booking
+//! - [`BookingMaterialForCreate`] - Data required to record usage
+//! - [`MaterialBmc`] - Business Model Controller for materials and usage
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::material::{BookingMaterialForCreate, MaterialBmc, MaterialForCreate};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn fit_trap(mm: &ModelManager, job: i32) -> Result<(), Box<dyn std::error::Error>> {
+//!     let material_id = MaterialBmc::create(
+//!         mm,
+//!         MaterialForCreate {
+//!             name: "40mm bottle trap".to_string(),
+//!             supplier: Some("Screwfix".to_string()),
+//!             unit: None,
+//!             cost_cents: 450,
+//!             sell_cents: 900,
+//!             vat_rate_bp: None,
+//!         },
+//!     )
+//!     .await?;
+//!     let _ = MaterialBmc::add_to_booking(
+//!         mm,
+//!         job,
+//!         BookingMaterialForCreate {
+//!             material_id: Some(material_id),
+//!             quantity: 1,
+//!             ..Default::default()
+//!         },
+//!     )
+//!     .await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::model::quote::QuoteItem;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Columns selected for [`Material`].
+const MATERIAL_COLUMNS: &str = "id, name, supplier, unit, cost_cents, sell_cents, vat_rate_bp, \
+     active, created_at, updated_at";
+
+/// Columns selected for [`BookingMaterial`].
+const BOOKING_MATERIAL_COLUMNS: &str = "id, booking_id, material_id, description, unit, \
+     quantity, unit_cost_cents, unit_price_cents, vat_rate_bp, billable, created_at";
+
+/// Catalogue entry from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Material {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Name, e.g. "15mm copper pipe"
+    pub name: String,
+    /// Where it is bought
+    pub supplier: Option<String>,
+    /// Unit it is sold in: each, m, box, litre, ...
+    pub unit: String,
+    /// What one unit costs us, in cents
+    pub cost_cents: i32,
+    /// What we charge for one unit, in cents (net of VAT)
+    pub sell_cents: i32,
+    /// VAT rate in basis points (`None` = the default rate)
+    pub vat_rate_bp: Option<i32>,
+    /// Whether the entry is offered when recording usage
+    pub active: bool,
+    /// When the entry was created
+    pub created_at: Option<OffsetDateTime>,
+    /// When the entry was last updated
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+/// Data required to add a catalogue entry.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct MaterialForCreate {
+    /// Name
+    pub name: String,
+    /// Supplier
+    pub supplier: Option<String>,
+    /// Unit (default `each`)
+    pub unit: Option<String>,
+    /// Cost per unit in cents
+    pub cost_cents: i32,
+    /// Sell price per unit in cents
+    pub sell_cents: i32,
+    /// VAT rate in basis points
+    pub vat_rate_bp: Option<i32>,
+}
+
+/// Data for updating a catalogue entry. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct MaterialForUpdate {
+    /// New name
+    pub name: Option<String>,
+    /// New supplier
+    pub supplier: Option<String>,
+    /// New unit
+    pub unit: Option<String>,
+    /// New cost per unit in cents
+    pub cost_cents: Option<i32>,
+    /// New sell price per unit in cents
+    pub sell_cents: Option<i32>,
+    /// New VAT rate in basis points
+    pub vat_rate_bp: Option<i32>,
+    /// Offer or retire the entry
+    pub active: Option<bool>,
+}
+
+/// Material used on a booking.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct BookingMaterial {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Booking the material was used on
+    pub booking_id: i32,
+    /// Catalogue entry, if it came from the catalogue
+    pub material_id: Option<i32>,
+    /// Description printed on the invoice
+    pub description: String,
+    /// Unit the quantity is in
+    pub unit: String,
+    /// Units used
+    pub quantity: i32,
+    /// Cost per unit in cents, as at the time of use
+    pub unit_cost_cents: i32,
+    /// Charge per unit in cents, as at the time of use
+    pub unit_price_cents: i32,
+    /// VAT rate in basis points (`None` = the default rate)
+    pub vat_rate_bp: Option<i32>,
+    /// Whether the line is added to the invoice
+    pub billable: bool,
+    /// When the usage was recorded
+    pub created_at: Option<OffsetDateTime>,
+}
+
+impl BookingMaterial {
+    /// Total cost of the line in cents.
+    #[must_use]
+    pub fn cost_cents(&self) -> i64 {
+        i64::from(self.quantity) * i64::from(self.unit_cost_cents)
+    }
+
+    /// The line as an invoice line item.
+    #[must_use]
+    pub fn to_item(&self) -> QuoteItem {
+        let description = if self.unit == "each" {
+            self.description.clone()
+        } else {
+            format!("{} ({})", self.description, self.unit)
+        };
+
+        QuoteItem {
+            description,
+            quantity: self.quantity,
+            unit_price: self.unit_price_cents,
+            discount: None,
+            vat_rate_bp: self.vat_rate_bp,
+        }
+    }
+}
+
+/// Data required to record material used on a booking.
+///
+/// With a `material_id`, omitted fields come from the catalogue entry;
+/// without one, `description`, `unit_cost_cents` and `unit_price_cents`
+/// are required.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct BookingMaterialForCreate {
+    /// Catalogue entry used
+    pub material_id: Option<i32>,
+    /// Description (defaults to the entry's name)
+    pub description: Option<String>,
+    /// Unit (defaults to the entry's unit, or `each`)
+    pub unit: Option<String>,
+    /// Units used
+    pub quantity: i32,
+    /// Cost per unit in cents (defaults to the entry's cost)
+    pub unit_cost_cents: Option<i32>,
+    /// Charge per unit in cents (defaults to the entry's sell price)
+    pub unit_price_cents: Option<i32>,
+    /// VAT rate in basis points (defaults to the entry's rate)
+    pub vat_rate_bp: Option<i32>,
+    /// Add the line to the invoice (default true)
+    pub billable: Option<bool>,
+}
+
+/// Business Model Controller for materials and their use on bookings.
+pub struct MaterialBmc;
+
+impl MaterialBmc {
+    /// Adds a catalogue entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `material` - Entry to insert
+    ///
+    /// # Returns
+    ///
+    /// The auto-generated ID of the new entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for a blank name or negative prices.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm), fields(name = %material.name))]
+    pub async fn create(mm: &ModelManager, material: MaterialForCreate) -> Result<i32> {
+        validate_name(&material.name)?;
+        validate_prices(
+            Some(material.cost_cents),
+            Some(material.sell_cents),
+            material.vat_rate_bp,
+        )?;
+
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO materials (name, supplier, unit, cost_cents, sell_cents, vat_rate_bp)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(material.name.trim())
+        .bind(&material.supplier)
+        .bind(normalize_unit(material.unit.as_deref()))
+        .bind(material.cost_cents)
+        .bind(material.sell_cents)
+        .bind(material.vat_rate_bp)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Gets a catalogue entry by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the entry does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<Material> {
+        sqlx::query_as(&format!(
+            "SELECT {MATERIAL_COLUMNS} FROM materials WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "Material",
+            id: id as i64,
+        })
+    }
+
+    /// Lists catalogue entries by name, optionally only active ones.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, active_only: bool) -> Result<Vec<Material>> {
+        let materials = sqlx::query_as(&format!(
+            "SELECT {MATERIAL_COLUMNS} FROM materials \
+             WHERE active OR NOT $1 ORDER BY name, supplier NULLS FIRST"
+        ))
+        .bind(active_only)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(materials)
+    }
+
+    /// Updates a catalogue entry. Usage already recorded keeps its prices.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the entry does not exist, or
+    /// `ValidationError` for a blank name or negative prices.
+    #[instrument(skip(mm))]
+    pub async fn update(mm: &ModelManager, id: i32, data: MaterialForUpdate) -> Result<()> {
+        if let Some(name) = &data.name {
+            validate_name(name)?;
+        }
+        validate_prices(data.cost_cents, data.sell_cents, data.vat_rate_bp)?;
+
+        let rows_affected = sqlx::query(
+            r#"
+            UPDATE materials
+            SET name = COALESCE($2, name),
+                supplier = COALESCE($3, supplier),
+                unit = COALESCE($4, unit),
+                cost_cents = COALESCE($5, cost_cents),
+                sell_cents = COALESCE($6, sell_cents),
+                vat_rate_bp = COALESCE($7, vat_rate_bp),
+                active = COALESCE($8, active)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(data.name.as_deref().map(str::trim))
+        .bind(&data.supplier)
+        .bind(data.unit.as_deref().map(|u| normalize_unit(Some(u))))
+        .bind(data.cost_cents)
+        .bind(data.sell_cents)
+        .bind(data.vat_rate_bp)
+        .bind(data.active)
+        .execute(mm.dbx().db())
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "Material",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Deletes a catalogue entry. Usage lines keep their own copy of the
+    /// description and prices.
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM materials WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "Material",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Records material used on a booking.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `booking_id` - Booking the material was used on
+    /// * `usage` - Catalogue entry and/or explicit details, and quantity
+    ///
+    /// # Returns
+    ///
+    /// The ID of the new usage line.
+    ///
+    /// # Errors
+    ///
+    /// - `EntityNotFound` if the booking or catalogue entry does not exist
+    /// - `ValidationError` if the quantity is not positive, ad hoc details
+    ///   are missing, or the booking has already been invoiced
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm))]
+    pub async fn add_to_booking(
+        mm: &ModelManager,
+        booking_id: i32,
+        usage: BookingMaterialForCreate,
+    ) -> Result<i32> {
+        if usage.quantity <= 0 {
+            return Err(Error::ValidationError("Quantity must be at least 1".into()));
+        }
+        validate_prices(
+            usage.unit_cost_cents,
+            usage.unit_price_cents,
+            usage.vat_rate_bp,
+        )?;
+        ensure_not_invoiced(mm, booking_id).await?;
+
+        let material = match usage.material_id {
+            Some(id) => Some(Self::get(mm, id).await?),
+            None => None,
+        };
+        let missing = |field: &str| {
+            Error::ValidationError(
+                format!("{field} is required for materials not in the catalogue").into(),
+            )
+        };
+        let description = match (usage.description, &material) {
+            (Some(description), _) if !description.trim().is_empty() => {
+                description.trim().to_string()
+            }
+            (_, Some(material)) => material.name.clone(),
+            (_, None) => return Err(missing("description")),
+        };
+        let unit = match (usage.unit.as_deref(), &material) {
+            (Some(unit), _) => normalize_unit(Some(unit)),
+            (None, Some(material)) => material.unit.clone(),
+            (None, None) => normalize_unit(None),
+        };
+        let unit_cost_cents = usage
+            .unit_cost_cents
+            .or(material.as_ref().map(|m| m.cost_cents))
+            .ok_or_else(|| missing("unit_cost_cents"))?;
+        let unit_price_cents = usage
+            .unit_price_cents
+            .or(material.as_ref().map(|m| m.sell_cents))
+            .ok_or_else(|| missing("unit_price_cents"))?;
+        let vat_rate_bp = usage
+            .vat_rate_bp
+            .or(material.as_ref().and_then(|m| m.vat_rate_bp));
+
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO booking_materials (booking_id, material_id, description, unit, quantity,
+                                           unit_cost_cents, unit_price_cents, vat_rate_bp,
+                                           billable)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(booking_id)
+        .bind(usage.material_id)
+        .bind(&description)
+        .bind(&unit)
+        .bind(usage.quantity)
+        .bind(unit_cost_cents)
+        .bind(unit_price_cents)
+        .bind(vat_rate_bp)
+        .bind(usage.billable.unwrap_or(true))
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Lists the materials used on a booking, in the order recorded.
+    #[instrument(skip(mm))]
+    pub async fn list_for_booking(
+        mm: &ModelManager,
+        booking_id: i32,
+    ) -> Result<Vec<BookingMaterial>> {
+        let lines = sqlx::query_as(&format!(
+            "SELECT {BOOKING_MATERIAL_COLUMNS} FROM booking_materials \
+             WHERE booking_id = $1 ORDER BY id ASC"
+        ))
+        .bind(booking_id)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(lines)
+    }
+
+    /// Removes a usage line from a booking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the line is not on the booking, or
+    /// `ValidationError` if the booking has already been invoiced.
+    #[instrument(skip(mm))]
+    pub async fn remove_from_booking(mm: &ModelManager, booking_id: i32, id: i32) -> Result<()> {
+        ensure_not_invoiced(mm, booking_id).await?;
+
+        let rows_affected =
+            sqlx::query("DELETE FROM booking_materials WHERE id = $1 AND booking_id = $2")
+                .bind(id)
+                .bind(booking_id)
+                .execute(mm.dbx().db())
+                .await?
+                .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "BookingMaterial",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Invoice line items for a booking's billable materials.
+    #[instrument(skip(mm))]
+    pub async fn invoice_items(mm: &ModelManager, booking_id: i32) -> Result<Vec<QuoteItem>> {
+        Ok(Self::list_for_booking(mm, booking_id)
+            .await?
+            .iter()
+            .filter(|line| line.billable)
+            .map(BookingMaterial::to_item)
+            .collect())
+    }
+}
+
+// region:    --- Material Helpers
+
+/// Usage is part of the bill, so it is frozen once the booking has an
+/// invoice that has not been voided.
+async fn ensure_not_invoiced(mm: &ModelManager, booking_id: i32) -> Result<()> {
+    let booking: Option<(bool,)> = sqlx::query_as(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM invoices
+            WHERE booking_id = b.id AND kind = 'invoice' AND status <> 'void'
+        )
+        FROM bookings b
+        WHERE b.id = $1
+        "#,
+    )
+    .bind(booking_id)
+    .fetch_optional(mm.dbx().db())
+    .await?;
+
+    match booking {
+        None => Err(Error::EntityNotFound {
+            entity: "Booking",
+            id: booking_id as i64,
+        }),
+        Some((true,)) => Err(Error::ValidationError(
+            "Booking has already been invoiced; void the invoice to change its materials".into(),
+        )),
+        Some((false,)) => Ok(()),
+    }
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(Error::ValidationError("Material name is required".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_prices(cost: Option<i32>, price: Option<i32>, vat_rate_bp: Option<i32>) -> Result<()> {
+    if cost.is_some_and(|c| c < 0) || price.is_some_and(|p| p < 0) {
+        return Err(Error::ValidationError(
+            "Costs and prices cannot be negative".into(),
+        ));
+    }
+    if vat_rate_bp.is_some_and(|rate| !(0..=10_000).contains(&rate)) {
+        return Err(Error::ValidationError(
+            "VAT rate must be between 0% and 100%".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Trimmed, lower-case unit; blank means `each`.
+fn normalize_unit(unit: Option<&str>) -> String {
+    match unit.map(str::trim) {
+        Some(unit) if !unit.is_empty() => unit.to_lowercase(),
+        _ => "each".to_string(),
+    }
+}
+
+// endregion: --- Material Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::booking::{BookingBmc, BookingForCreate};
+
+    fn fx_line(unit: &str) -> BookingMaterial {
+        BookingMaterial {
+            id: 1,
+            booking_id: 1,
+            material_id: None,
+            description: "15mm copper pipe".to_string(),
+            unit: unit.to_string(),
+            quantity: 3,
+            unit_cost_cents: 250,
+            unit_price_cents: 400,
+            vat_rate_bp: Some(500),
+            billable: true,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_booking_material_to_item() {
+        let item = fx_line("m").to_item();
+
+        assert_eq!(item.description, "15mm copper pipe (m)");
+        assert_eq!(item.quantity, 3);
+        assert_eq!(item.unit_price, 400);
+        assert_eq!(item.vat_rate_bp, Some(500));
+        assert_eq!(fx_line("each").to_item().description, "15mm copper pipe");
+        assert_eq!(fx_line("m").cost_cents(), 750);
+    }
+
+    #[test]
+    fn test_normalize_unit() {
+        assert_eq!(normalize_unit(None), "each");
+        assert_eq!(normalize_unit(Some("  ")), "each");
+        assert_eq!(normalize_unit(Some(" Litre ")), "litre");
+    }
+
+    #[tokio::test]
+    async fn test_material_usage_flow() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let material_id = MaterialBmc::create(
+            &mm,
+            MaterialForCreate {
+                name: "Test bottle trap".to_string(),
+                supplier: Some("Test supplier".to_string()),
+                unit: None,
+                cost_cents: 450,
+                sell_cents: 900,
+                vat_rate_bp: None,
+            },
+        )
+        .await?;
+        let booking_id = BookingBmc::create(
+            &mm,
+            BookingForCreate {
+                customer_id: None,
+                service_type: "test_materials".to_string(),
+                scheduled_date: None,
+                scheduled_time: None,
+                notes: None,
+            },
+        )
+        .await?;
+
+        // Execute
+        let _ = MaterialBmc::add_to_booking(
+            &mm,
+            booking_id,
+            BookingMaterialForCreate {
+                material_id: Some(material_id),
+                quantity: 2,
+                ..Default::default()
+            },
+        )
+        .await?;
+        // Price changes after use do not touch the recorded line
+        MaterialBmc::update(
+            &mm,
+            material_id,
+            MaterialForUpdate {
+                sell_cents: Some(1200),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let _ = MaterialBmc::add_to_booking(
+            &mm,
+            booking_id,
+            BookingMaterialForCreate {
+                description: Some("Silicone".to_string()),
+                quantity: 1,
+                unit_cost_cents: Some(600),
+                unit_price_cents: Some(0),
+                billable: Some(false),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let lines = MaterialBmc::list_for_booking(&mm, booking_id).await?;
+        let items = MaterialBmc::invoice_items(&mm, booking_id).await?;
+
+        // Check
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].description, "Test bottle trap");
+        assert_eq!(lines[0].unit_price_cents, 900);
+        assert_eq!(lines[1].material_id, None);
+        assert_eq!(items.len(), 1, "only billable lines are invoiced");
+        assert_eq!(items[0].quantity, 2);
+        let missing_price = MaterialBmc::add_to_booking(
+            &mm,
+            booking_id,
+            BookingMaterialForCreate {
+                description: Some("Mystery part".to_string()),
+                quantity: 1,
+                ..Default::default()
+            },
+        )
+        .await;
+        assert!(matches!(missing_price, Err(Error::ValidationError(_))));
+
+        // Cleanup
+        BookingBmc::delete(&mm, booking_id).await?;
+        MaterialBmc::delete(&mm, material_id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
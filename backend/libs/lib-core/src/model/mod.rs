@@ -4,59 +4,155 @@
 //!
 //! ## Available BMCs
 //!
+//! - [`area::AreaBmc`] - Towns we cover, each with a landing page
+//! - [`attachment::AttachmentBmc`] - Photos and documents attached to bookings and quotes
+//! - [`audit_log::AuditLogBmc`] - Audit trail of sensitive admin operations
 //! - [`contact::ContactBmc`] - Contact form submissions
+//! - [`blog_post::BlogPostBmc`] - Blog posts, drafted and scheduled
 //! - [`booking::BookingBmc`] - Job bookings/appointments
 //! - [`customer::CustomerBmc`] - Customer CRM records
+//! - [`document::DocumentBmc`] - Quote and invoice PDFs
+//! - [`email_log::EmailLogBmc`] - Record of emails sent
+//! - [`gdpr::GdprBmc`] - Subject access exports, erasure and retention
+//! - [`invoice::InvoiceBmc`] - Invoices, credit notes and invoice payments
+//! - [`landing_page::LandingPageBmc`] - Area and service-in-town landing pages
+//! - [`margin::MarginBmc`] - Per-job and per-service margin reports
+//! - [`material::MaterialBmc`] - Materials catalogue and per-booking usage
+//! - [`onboarding::OnboardingBmc`] - New clients' onboarding drafts, published as tenants
+//! - [`payment::PaymentBmc`] - Payments received
+//! - [`postcode_district::PostcodeDistrictBmc`] - Postcode district locations and service coverage
+//! - [`pricing_rule::PricingRuleBmc`] - Instant quote service ranges and pricing rules
+//! - [`promo_code::PromoCodeBmc`] - Promo codes and their redemptions
 //! - [`quote::QuoteBmc`] - Itemized quotes
+//! - [`quote_acceptance::QuoteAcceptanceBmc`] - Signed, immutable records of quote acceptance
+//! - [`quote_revision::QuoteRevisionBmc`] - Immutable quote revisions and what changed
+//! - [`quote_template::QuoteTemplateBmc`] - Reusable quote templates with variables
+//! - [`route_plan::RoutePlanBmc`] - Daily technician routes and their GeoJSON
+//! - [`search::SearchBmc`] - Full-text and fuzzy search across customers, bookings and quotes
+//! - [`service::ServiceBmc`] - Services shown on landing pages
+//! - [`technician::TechnicianBmc`] - Technicians, job assignment and suggested assignees
+//! - [`tenant::TenantBmc`] - Client businesses hosted from this deployment
+//! - [`time_entry::TimeEntryBmc`] - Job time tracking and weekly timesheets
+//! - [`timeline::TimelineBmc`] - Per-customer activity timeline
+//! - [`user::UserBmc`] - Users and the salts of their admin tokens
 
 // region:    --- Modules
 
+pub mod area;
+pub mod attachment;
+pub mod audit_log;
 mod base;
+pub mod blog_post;
 pub mod booking;
 pub mod contact;
 pub mod customer;
+pub mod document;
+pub mod email_log;
 mod error;
+pub mod gdpr;
+pub mod invoice;
+pub mod landing_page;
+pub mod margin;
+pub mod material;
+pub mod onboarding;
 pub mod pagination;
+pub mod payment;
+pub mod postcode_district;
+pub mod pricing_rule;
+pub mod promo_code;
 pub mod query_log;
 pub mod quote;
+pub mod quote_acceptance;
+pub mod quote_revision;
+pub mod quote_template;
+pub mod route_plan;
+pub mod search;
+pub mod service;
 mod store;
+pub mod technician;
+pub mod tenant;
+pub mod time_entry;
+pub mod timeline;
 pub mod transaction;
+pub mod user;
 
 pub use self::error::{Error, Result};
 
+use crate::core_config;
 use crate::model::store::dbx::Dbx;
-use crate::model::store::new_db_pool;
+use crate::model::store::{new_tenant_pool, TenantPools};
+use crate::model::tenant::DEFAULT_TENANT_ID;
+use std::sync::{Arc, Mutex, PoisonError};
 
 // endregion: --- Modules
 
 // region:    --- ModelManager
 
 /// Central resource manager for the application
+///
+/// Each ModelManager works for one tenant: its queries run on that
+/// tenant's connections, which the database limits to the tenant's rows.
 #[derive(Clone)]
 pub struct ModelManager {
     dbx: Dbx,
+    tenant_id: i32,
+    pools: Arc<Mutex<TenantPools>>,
 }
 
 impl ModelManager {
-    /// Create a new ModelManager with database connection
+    /// Create a new ModelManager with database connection, working for the
+    /// default tenant
     pub async fn new() -> Result<Self> {
-        let db_pool = new_db_pool()
+        let db_pool = new_tenant_pool(DEFAULT_TENANT_ID)
+            .map_err(|ex| Error::CantCreateModelManagerProvider(ex.to_string()))?;
+        sqlx::query("SELECT 1")
+            .execute(&db_pool)
             .await
             .map_err(|ex| Error::CantCreateModelManagerProvider(ex.to_string()))?;
+        let pools = TenantPools::new(db_pool.clone(), core_config().TENANT_POOL_MAX);
+        let dbx = Dbx::new(db_pool, false)?;
+        Ok(ModelManager {
+            dbx,
+            tenant_id: DEFAULT_TENANT_ID,
+            pools: Arc::new(Mutex::new(pools)),
+        })
+    }
+
+    /// Create a ModelManager working for another tenant, sharing this one's
+    /// connection pools
+    pub fn for_tenant(&self, tenant_id: i32) -> Result<ModelManager> {
+        let db_pool = self
+            .pools
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(tenant_id)?;
         let dbx = Dbx::new(db_pool, false)?;
-        Ok(ModelManager { dbx })
+        Ok(ModelManager {
+            dbx,
+            tenant_id,
+            pools: self.pools.clone(),
+        })
     }
 
     /// Create a new ModelManager with transaction support
     pub fn new_with_txn(&self) -> Result<ModelManager> {
         let dbx = Dbx::new(self.dbx.db().clone(), true)?;
-        Ok(ModelManager { dbx })
+        Ok(ModelManager {
+            dbx,
+            tenant_id: self.tenant_id,
+            pools: self.pools.clone(),
+        })
     }
 
     /// Get the database executor
     pub fn dbx(&self) -> &Dbx {
         &self.dbx
     }
+
+    /// The tenant this ModelManager works for
+    pub fn tenant_id(&self) -> i32 {
+        self.tenant_id
+    }
 }
 
 // endregion: --- ModelManager
@@ -0,0 +1,634 @@
+//! # Onboarding Model
+//!
+//! New clients' business details, as submitted through the onboarding
+//! wizard. A submission is stored as a draft [`OnboardingForm`]; an admin
+//! reviews it, corrects it if needed, and publishes it.
+//!
+//! Publishing creates the client's tenant (see [`crate::model::tenant`])
+//! with its contact details, opening hours, accreditations and
+//! testimonials, then sets up the site as that tenant: a priced service for
+//! each service offered and an area for the postcodes covered. The tenant
+//! stays suspended until all of that is in place. If a step fails, fix the
+//! draft and publish again; the tenant created by the first attempt is
+//! reused, and services and areas already set up are kept.
+//!
+//! ## Structures
+//!
+//! - [`OnboardingDraft`] - Draft record from database
+//! - [`OnboardingForPublish`] - Where and how to publish a draft
+//! - [`OnboardingBmc`] - Business Model Controller for onboarding
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::onboarding::{OnboardingBmc, OnboardingForPublish};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn go_live(mm: &ModelManager, draft_id: i32) -> lib_core::model::Result<i32> {
+//!     let publish = OnboardingForPublish {
+//!         slug: None,
+//!         domains: vec!["jonesplumbing.co.uk".to_string()],
+//!         primary_color: Some("#0f766e".to_string()),
+//!         accent_color: None,
+//!     };
+//!     OnboardingBmc::publish(mm, draft_id, publish).await
+//! }
+//! ```
+
+use crate::model::area::{AreaBmc, AreaForCreate};
+use crate::model::postcode_district::PostcodeDistrictBmc;
+use crate::model::pricing_rule::PricingRuleBmc;
+use crate::model::service::{ServiceBmc, ServiceForCreate};
+use crate::model::tenant::{TenantBmc, TenantForCreate, TenantForUpdate};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::text::slugify;
+use serde::{Deserialize, Serialize};
+use shared::{OnboardingForm, ServiceRate, Validate};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Allowed values of [`OnboardingDraft::status`].
+pub const ONBOARDING_STATUSES: &[&str] = &["submitted", "published"];
+
+/// Select list for [`OnboardingDraft`].
+const ONBOARDING_SELECT: &str = "SELECT id, form, status, published_tenant_id, published_at, \
+     created_at, updated_at FROM onboarding_drafts";
+
+/// Longest service key the pricing catalog takes.
+const SERVICE_TYPE_MAX_LEN: usize = 50;
+
+/// Longest one-line service summary.
+const SUMMARY_MAX_LEN: usize = 160;
+
+/// Onboarding draft record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct OnboardingDraft {
+    /// Draft ID
+    pub id: i32,
+    /// The submitted [`OnboardingForm`]
+    pub form: serde_json::Value,
+    /// `submitted` or `published`
+    pub status: String,
+    /// Tenant created for the client, once publishing has started
+    pub published_tenant_id: Option<i32>,
+    /// When the site went live
+    pub published_at: Option<OffsetDateTime>,
+    /// When the form was submitted
+    pub created_at: Option<OffsetDateTime>,
+    /// When the draft was last changed
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl OnboardingDraft {
+    /// The client's details.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored form is malformed.
+    pub fn onboarding_form(&self) -> Result<OnboardingForm> {
+        serde_json::from_value(self.form.clone())
+            .map_err(|e| Error::ValidationError(format!("Malformed onboarding form: {e}").into()))
+    }
+}
+
+/// Where and how to publish a draft.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct OnboardingForPublish {
+    /// Tenant short name (default: from the business name)
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Hosts to serve the site on
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// Main brand colour, `#rrggbb`
+    #[serde(default)]
+    pub primary_color: Option<String>,
+    /// Highlight colour, `#rrggbb`
+    #[serde(default)]
+    pub accent_color: Option<String>,
+}
+
+/// Business Model Controller for onboarding drafts.
+pub struct OnboardingBmc;
+
+impl OnboardingBmc {
+    /// Stores a submitted onboarding form as a draft.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `form` - The client's details
+    ///
+    /// # Returns
+    ///
+    /// The new draft's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the form is invalid (see
+    /// [`OnboardingForm::validate`]).
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm, form), fields(business_name = %form.business_name))]
+    pub async fn create(mm: &ModelManager, form: OnboardingForm) -> Result<i32> {
+        form.validate().map_err(Error::ValidationError)?;
+
+        let (id,): (i32,) =
+            sqlx::query_as("INSERT INTO onboarding_drafts (form) VALUES ($1) RETURNING id")
+                .bind(to_json(&form)?)
+                .fetch_one(mm.dbx().db())
+                .await?;
+
+        Ok(id)
+    }
+
+    /// Gets a draft by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the draft does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<OnboardingDraft> {
+        sqlx::query_as(&format!("{ONBOARDING_SELECT} WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "OnboardingDraft",
+                id: id as i64,
+            })
+    }
+
+    /// Lists drafts, newest first, optionally only those with a status.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for an unknown status.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, status: Option<&str>) -> Result<Vec<OnboardingDraft>> {
+        if let Some(status) = status.filter(|s| !ONBOARDING_STATUSES.contains(s)) {
+            return Err(Error::ValidationError(
+                format!("Unknown onboarding status {status}").into(),
+            ));
+        }
+
+        let drafts = sqlx::query_as(&format!(
+            "{ONBOARDING_SELECT} WHERE $1::TEXT IS NULL OR status = $1 ORDER BY id DESC"
+        ))
+        .bind(status)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(drafts)
+    }
+
+    /// Replaces a draft's form, e.g. with the admin's corrections.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the draft does not exist, or
+    /// `ValidationError` if the form is invalid or the draft has been
+    /// published.
+    #[instrument(skip(mm, form))]
+    pub async fn update(mm: &ModelManager, id: i32, form: OnboardingForm) -> Result<()> {
+        form.validate().map_err(Error::ValidationError)?;
+
+        let rows_affected = sqlx::query(
+            "UPDATE onboarding_drafts SET form = $2 WHERE id = $1 AND status = 'submitted'",
+        )
+        .bind(id)
+        .bind(to_json(&form)?)
+        .execute(mm.dbx().db())
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            Self::get(mm, id).await?;
+            return Err(Error::ValidationError(
+                format!("Onboarding draft {id} is already published").into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Publishes a draft as a live site.
+    ///
+    /// Creates the client's tenant (suspended), sets up their services,
+    /// prices and area as that tenant, then serves the site. Coverage is
+    /// measured from the middle of the postcode districts the client
+    /// covers, where their locations are known.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Draft to publish
+    /// * `publish` - Tenant slug, domains and colours
+    ///
+    /// # Returns
+    ///
+    /// The new tenant's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the draft does not exist, and
+    /// `ValidationError` if it is already published, or as for
+    /// [`TenantBmc::create`], [`ServiceBmc::create`] and
+    /// [`AreaBmc::create`]. `slug`, `domains` and colours are ignored when
+    /// an earlier attempt already created the tenant; change them on the
+    /// tenant instead.
+    #[instrument(skip(mm, publish))]
+    pub async fn publish(mm: &ModelManager, id: i32, publish: OnboardingForPublish) -> Result<i32> {
+        let draft = Self::get(mm, id).await?;
+        if draft.status == "published" {
+            return Err(Error::ValidationError(
+                format!("Onboarding draft {id} is already published").into(),
+            ));
+        }
+        let form = draft.onboarding_form()?;
+
+        let tenant_id = match draft.published_tenant_id {
+            Some(tenant_id) => tenant_id,
+            None => {
+                let centre = postcodes_centre(mm, &form.postcodes).await?;
+                let tenant_id =
+                    TenantBmc::create(mm, tenant_for_create(&form, publish, centre)).await?;
+                sqlx::query("UPDATE onboarding_drafts SET published_tenant_id = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(tenant_id)
+                    .execute(mm.dbx().db())
+                    .await?;
+                tenant_id
+            }
+        };
+
+        set_up_site(&mm.for_tenant(tenant_id)?, &form).await?;
+
+        TenantBmc::update(
+            mm,
+            tenant_id,
+            TenantForUpdate {
+                active: Some(true),
+                ..Default::default()
+            },
+        )
+        .await?;
+        sqlx::query(
+            "UPDATE onboarding_drafts SET status = 'published', published_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(id)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(tenant_id)
+    }
+}
+
+// region:    --- Onboarding Helpers
+
+/// The tenant a form describes, suspended until its site is set up.
+fn tenant_for_create(
+    form: &OnboardingForm,
+    publish: OnboardingForPublish,
+    centre: Option<(f64, f64)>,
+) -> TenantForCreate {
+    TenantForCreate {
+        slug: publish.slug,
+        business_name: form.business_name.trim().to_string(),
+        domains: publish.domains,
+        phone: Some(form.phone.trim().to_string()),
+        email: Some(form.email.trim().to_string()),
+        tagline: Some(format!("{} in {}", form.trade.trim(), form.town.trim())),
+        primary_color: publish.primary_color,
+        accent_color: publish.accent_color,
+        logo_url: form
+            .logo_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string),
+        centre_latitude: centre.map(|(lat, _)| lat),
+        centre_longitude: centre.map(|(_, lng)| lng),
+        trade: Some(form.trade.trim().to_string()),
+        opening_hours: form.opening_hours.clone(),
+        accreditations: form
+            .accreditations
+            .iter()
+            .map(|a| a.trim().to_string())
+            .collect(),
+        testimonials: form.testimonials.clone(),
+        noindex: false,
+        active: false,
+    }
+}
+
+/// Average location of the districts whose locations are known.
+async fn postcodes_centre(mm: &ModelManager, postcodes: &[String]) -> Result<Option<(f64, f64)>> {
+    let mut points = Vec::with_capacity(postcodes.len());
+    for postcode in postcodes {
+        if let Some(district) = PostcodeDistrictBmc::find(mm, postcode).await? {
+            points.push((district.latitude, district.longitude));
+        }
+    }
+    if points.is_empty() {
+        return Ok(None);
+    }
+
+    let n = points.len() as f64;
+    let (lat, lng) = points
+        .iter()
+        .fold((0.0, 0.0), |(lat, lng), (p_lat, p_lng)| {
+            (lat + p_lat, lng + p_lng)
+        });
+    Ok(Some((lat / n, lng / n)))
+}
+
+/// Adds the form's prices, services and area, skipping any already set up
+/// by an earlier attempt. `mm` must be the client's tenant.
+async fn set_up_site(mm: &ModelManager, form: &OnboardingForm) -> Result<()> {
+    let existing_services = ServiceBmc::list(mm, false).await?;
+    for (i, offered) in form.services.iter().enumerate() {
+        let service_type = service_type_for(&offered.name);
+        let sort_order = (i as i32 + 1) * 10;
+        PricingRuleBmc::save_service(
+            mm,
+            ServiceRate {
+                service_type: service_type.clone(),
+                label: offered.name.trim().to_string(),
+                base_low_cents: offered.price_from_cents,
+                base_high_cents: offered.price_to_cents,
+                sort_order,
+                active: true,
+            },
+        )
+        .await?;
+
+        let slug = slugify(&offered.name);
+        if existing_services.iter().any(|s| s.slug == slug) {
+            continue;
+        }
+        ServiceBmc::create(
+            mm,
+            ServiceForCreate {
+                slug: Some(slug),
+                name: offered.name.trim().to_string(),
+                summary: summary(&offered.description),
+                description: offered.description.trim().to_string(),
+                service_type: Some(service_type),
+                sort_order,
+                active: true,
+            },
+        )
+        .await?;
+    }
+
+    let area_slug = slugify(&form.town);
+    if !AreaBmc::list(mm, false)
+        .await?
+        .iter()
+        .any(|a| a.slug == area_slug)
+    {
+        let testimonial = form.testimonials.first();
+        AreaBmc::create(
+            mm,
+            AreaForCreate {
+                slug: Some(area_slug),
+                name: form.town.trim().to_string(),
+                description: area_description(form),
+                postcodes: form.postcodes.clone(),
+                travel_time: None,
+                testimonial_quote: testimonial.map(|t| t.quote.trim().to_string()),
+                testimonial_author: testimonial.map(|t| t.author.trim().to_string()),
+                sort_order: 10,
+                active: true,
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Pricing catalog key for a service name, within the catalog's length.
+fn service_type_for(name: &str) -> String {
+    let slug = slugify(name);
+    let mut end = slug.len().min(SERVICE_TYPE_MAX_LEN);
+    while !slug.is_char_boundary(end) {
+        end -= 1;
+    }
+    slug[..end].trim_end_matches('-').to_string()
+}
+
+/// First sentence of a description, shortened at a word to fit a card.
+fn summary(description: &str) -> String {
+    let description = description.trim();
+    let sentence = description
+        .split_inclusive(['.', '!', '?'])
+        .next()
+        .unwrap_or(description)
+        .trim();
+    if sentence.chars().count() <= SUMMARY_MAX_LEN {
+        return sentence.to_string();
+    }
+
+    let mut summary = String::new();
+    for word in sentence.split_whitespace() {
+        if summary.chars().count() + word.chars().count() + 1 > SUMMARY_MAX_LEN - 3 {
+            break;
+        }
+        if !summary.is_empty() {
+            summary.push(' ');
+        }
+        summary.push_str(word);
+    }
+    summary.push_str("...");
+    summary
+}
+
+/// About the client working in their town, for its area page.
+fn area_description(form: &OnboardingForm) -> String {
+    format!(
+        "{} is a local {} working across {} and the {} postcode districts.",
+        form.business_name.trim(),
+        form.trade.trim().to_lowercase(),
+        form.town.trim(),
+        form.postcodes
+            .iter()
+            .map(|p| p.trim().to_ascii_uppercase())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<serde_json::Value> {
+    serde_json::to_value(value).map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))
+}
+
+// endregion: --- Onboarding Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use shared::{OnboardingService, OpeningHours, Testimonial};
+
+    fn fx_form(business_name: &str) -> OnboardingForm {
+        OnboardingForm {
+            business_name: business_name.to_string(),
+            trade: "Plumber".to_string(),
+            contact_name: "Gareth Jones".to_string(),
+            email: "gareth@example.com".to_string(),
+            phone: "07700 900123".to_string(),
+            town: "Rugby".to_string(),
+            services: vec![
+                OnboardingService {
+                    name: "Boiler Servicing".to_string(),
+                    description: "Annual boiler service and gas safety check. Certificate \
+                                  emailed the same day."
+                        .to_string(),
+                    price_from_cents: 6000,
+                    price_to_cents: 9000,
+                },
+                OnboardingService {
+                    name: "Leak Repairs".to_string(),
+                    description: "Dripping taps, weeping joints and burst pipes traced and \
+                                  fixed."
+                        .to_string(),
+                    price_from_cents: 5000,
+                    price_to_cents: 15000,
+                },
+            ],
+            postcodes: vec!["CV21".to_string(), "CV22".to_string()],
+            logo_url: None,
+            opening_hours: vec![OpeningHours {
+                days: "Monday - Friday".to_string(),
+                opens: "08:00".to_string(),
+                closes: "17:30".to_string(),
+            }],
+            accreditations: vec!["Gas Safe Registered".to_string()],
+            testimonials: vec![Testimonial {
+                quote: "Boiler sorted the same afternoon.".to_string(),
+                author: "Sue, Hillmorton".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_service_type_for() {
+        assert_eq!(service_type_for("Boiler Servicing"), "boiler-servicing");
+        let long = service_type_for(&"Gutter Cleaning And ".repeat(5));
+        assert!(long.len() <= SERVICE_TYPE_MAX_LEN);
+        assert!(!long.ends_with('-'));
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            summary(" Annual boiler service. Certificate emailed. "),
+            "Annual boiler service."
+        );
+        let long = summary(&"word ".repeat(60));
+        assert!(long.chars().count() <= SUMMARY_MAX_LEN);
+        assert!(long.ends_with("..."));
+    }
+
+    #[test]
+    fn test_area_description() {
+        assert_eq!(
+            area_description(&fx_form("Jones Plumbing")),
+            "Jones Plumbing is a local plumber working across Rugby and the CV21, CV22 \
+             postcode districts."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_update_publish() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let suffix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let mut invalid = fx_form("Jones Plumbing");
+        invalid.postcodes.clear();
+
+        // Execute
+        let rejected = OnboardingBmc::create(&mm, invalid).await;
+        let id = OnboardingBmc::create(&mm, fx_form("Jones Plumbing")).await?;
+        OnboardingBmc::update(&mm, id, fx_form(&format!("Jones Plumbing {suffix}"))).await?;
+        let tenant_id = OnboardingBmc::publish(
+            &mm,
+            id,
+            OnboardingForPublish {
+                slug: Some(format!("jones-{suffix}")),
+                domains: vec![format!("jones-{suffix}.example.com")],
+                primary_color: None,
+                accent_color: None,
+            },
+        )
+        .await?;
+        let draft = OnboardingBmc::get(&mm, id).await?;
+        let republished = OnboardingBmc::publish(
+            &mm,
+            id,
+            OnboardingForPublish {
+                slug: None,
+                domains: vec![],
+                primary_color: None,
+                accent_color: None,
+            },
+        )
+        .await;
+        let edited = OnboardingBmc::update(&mm, id, fx_form("Jones")).await;
+        let tenant = TenantBmc::get(&mm, tenant_id).await?;
+        let tenant_mm = mm.for_tenant(tenant_id)?;
+        let services = ServiceBmc::list(&tenant_mm, false).await?;
+        let areas = AreaBmc::list(&tenant_mm, false).await?;
+        let catalog = PricingRuleBmc::catalog(&tenant_mm, false).await?;
+
+        // Check
+        assert!(rejected.is_err(), "Postcodes are required");
+        assert_eq!(draft.status, "published");
+        assert_eq!(draft.published_tenant_id, Some(tenant_id));
+        assert!(republished.is_err(), "A draft is published once");
+        assert!(edited.is_err(), "Published drafts are read-only");
+        assert!(tenant.active);
+        assert_eq!(tenant.business_name, format!("Jones Plumbing {suffix}"));
+        assert_eq!(tenant.accreditations, ["Gas Safe Registered"]);
+        assert_eq!(tenant.testimonial_list()?.len(), 1);
+        assert_eq!(services.len(), 2);
+        assert_eq!(
+            services[0].summary,
+            "Annual boiler service and gas safety check."
+        );
+        assert_eq!(areas.len(), 1);
+        assert_eq!(areas[0].postcodes, ["CV21", "CV22"]);
+        assert_eq!(catalog.services.len(), 2);
+
+        // Cleanup
+        for table in [
+            "services",
+            "service_areas",
+            "service_rates",
+            "document_counters",
+        ] {
+            sqlx::query(&format!("DELETE FROM {table}"))
+                .execute(tenant_mm.dbx().db())
+                .await?;
+        }
+        sqlx::query("DELETE FROM onboarding_drafts WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?;
+        sqlx::query("DELETE FROM tenants WHERE id = $1")
+            .bind(tenant_id)
+            .execute(mm.dbx().db())
+            .await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
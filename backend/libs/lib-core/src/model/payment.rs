@@ -0,0 +1,170 @@
+//! # Payment Model
+//!
+//! This module defines payments received from customers against quotes
+//! and bookings.
+//!
+//! ## Structures
+//!
+//! - [`Payment`] - Complete payment record from database
+//! - [`PaymentForCreate`] - Data required to record a payment
+//! - [`PaymentBmc`] - Business Model Controller for payment operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::payment::{PaymentBmc, PaymentForCreate};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn record_payment(mm: &ModelManager) -> Result<i32, Box<dyn std::error::Error>> {
+//!     let payment = PaymentForCreate {
+//!         customer_id: Some(1),
+//!         quote_id: None,
+//!         booking_id: Some(1),
+//!         invoice_id: None,
+//!         amount_cents: 7500,
+//!         method: "card".to_string(),
+//!         reference: Some("pi_123".to_string()),
+//!     };
+//!     let id = PaymentBmc::create(mm, payment).await?;
+//!     Ok(id)
+//! }
+//! ```
+
+use crate::model::ModelManager;
+use crate::model::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Complete payment record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Payment {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Customer ID (foreign key)
+    pub customer_id: Option<i32>,
+    /// Quote ID the payment is for
+    pub quote_id: Option<i32>,
+    /// Booking ID the payment is for
+    pub booking_id: Option<i32>,
+    /// Invoice ID the payment is for
+    pub invoice_id: Option<i32>,
+    /// Amount in cents
+    pub amount_cents: i32,
+    /// Payment method: card, bank_transfer, cash
+    pub method: String,
+    /// External reference (Stripe PaymentIntent ID, bank reference)
+    pub reference: Option<String>,
+    /// Payment status: succeeded, refunded, failed
+    pub status: String,
+    /// When the payment was received
+    pub paid_at: Option<OffsetDateTime>,
+}
+
+/// Data required to record a payment.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct PaymentForCreate {
+    /// Customer ID (optional)
+    pub customer_id: Option<i32>,
+    /// Quote ID the payment is for
+    pub quote_id: Option<i32>,
+    /// Booking ID the payment is for
+    pub booking_id: Option<i32>,
+    /// Invoice ID the payment is for
+    pub invoice_id: Option<i32>,
+    /// Amount in cents
+    pub amount_cents: i32,
+    /// Payment method: card, bank_transfer, cash
+    pub method: String,
+    /// External reference (Stripe PaymentIntent ID, bank reference)
+    pub reference: Option<String>,
+}
+
+/// Business Model Controller for payment operations.
+pub struct PaymentBmc;
+
+impl PaymentBmc {
+    /// Records a successful payment.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `payment` - Payment data to insert
+    ///
+    /// # Returns
+    ///
+    /// The auto-generated ID of the new payment.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm), fields(amount_cents = payment.amount_cents))]
+    pub async fn create(mm: &ModelManager, payment: PaymentForCreate) -> Result<i32> {
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO payments (customer_id, quote_id, booking_id, invoice_id, amount_cents,
+                                  method, reference, status)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 'succeeded')
+            RETURNING id
+            "#,
+        )
+        .bind(payment.customer_id)
+        .bind(payment.quote_id)
+        .bind(payment.booking_id)
+        .bind(payment.invoice_id)
+        .bind(payment.amount_cents)
+        .bind(&payment.method)
+        .bind(&payment.reference)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Lists payments for a specific customer, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `customer_id` - Customer to list payments for
+    #[instrument(skip(mm))]
+    pub async fn list_by_customer(mm: &ModelManager, customer_id: i32) -> Result<Vec<Payment>> {
+        let payments = sqlx::query_as(
+            r#"
+            SELECT id, customer_id, quote_id, booking_id, invoice_id, amount_cents, method,
+                   reference, status, paid_at
+            FROM payments
+            WHERE customer_id = $1
+            ORDER BY paid_at DESC
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(payments)
+    }
+
+    /// Deletes a payment.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Payment ID to delete
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM payments WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(crate::model::Error::EntityNotFound {
+                entity: "Payment",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+}
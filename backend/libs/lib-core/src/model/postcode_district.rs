@@ -0,0 +1,312 @@
+//! # Postcode District Model
+//!
+//! Where postcode districts are, and whether we cover them.
+//!
+//! District centres are imported offline from an open postcode dataset
+//! (see [`crate::postcode::parse_districts_csv`]). Coverage is measured as
+//! the straight-line distance from the tenant's service centre (default:
+//! `SERVICE_CENTRE_LATITUDE`/`SERVICE_CENTRE_LONGITUDE`) to the centre of
+//! the customer's district, and banded by `TRAVEL_BANDS_KM`: band 1 is
+//! within the first distance, band 2 within the second, and so on. Band
+//! numbers are the `distance_zone` values instant quotes are priced by.
+//! Beyond the last band, or in a district we have no location for, we do
+//! not cover.
+//!
+//! ## Structures
+//!
+//! - [`PostcodeDistrict`] - A district's location from the database
+//! - [`PostcodeDistrictBmc`] - Business Model Controller for districts
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::postcode_district::PostcodeDistrictBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn check(mm: &ModelManager) -> Result<(), Box<dyn std::error::Error>> {
+//!     let coverage = PostcodeDistrictBmc::coverage(mm, "CV1 1AA").await?;
+//!     println!("{}", coverage.message);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::core_config;
+use crate::model::tenant::TenantBmc;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::postcode::{self, DistrictLocation};
+use crate::routing::{haversine_km, Point};
+use serde::{Deserialize, Serialize};
+use shared::Coverage;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// A postcode district's location.
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct PostcodeDistrict {
+    /// Outcode, e.g. `CV1`
+    pub outcode: String,
+    /// Latitude of the district's centre
+    pub latitude: f64,
+    /// Longitude of the district's centre
+    pub longitude: f64,
+    /// Town or local authority
+    pub district: Option<String>,
+    /// When the location was last imported
+    pub imported_at: OffsetDateTime,
+}
+
+/// Business Model Controller for postcode districts and coverage.
+pub struct PostcodeDistrictBmc;
+
+impl PostcodeDistrictBmc {
+    /// Imports district locations, replacing any already held for the same
+    /// outcodes.
+    ///
+    /// # Returns
+    ///
+    /// The number of districts written.
+    #[instrument(skip(mm, districts), fields(count = districts.len()))]
+    pub async fn import(mm: &ModelManager, districts: &[DistrictLocation]) -> Result<u64> {
+        let outcodes: Vec<&str> = districts.iter().map(|d| d.outcode.as_str()).collect();
+        let latitudes: Vec<f64> = districts.iter().map(|d| d.latitude).collect();
+        let longitudes: Vec<f64> = districts.iter().map(|d| d.longitude).collect();
+        let names: Vec<Option<&str>> = districts.iter().map(|d| d.district.as_deref()).collect();
+
+        let rows_affected = sqlx::query(
+            r#"
+            INSERT INTO postcode_districts (outcode, latitude, longitude, district)
+            SELECT * FROM UNNEST($1::TEXT[], $2::FLOAT8[], $3::FLOAT8[], $4::TEXT[])
+            ON CONFLICT (outcode) DO UPDATE
+            SET latitude = EXCLUDED.latitude,
+                longitude = EXCLUDED.longitude,
+                district = COALESCE(EXCLUDED.district, postcode_districts.district),
+                imported_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(&outcodes)
+        .bind(&latitudes)
+        .bind(&longitudes)
+        .bind(&names)
+        .execute(mm.dbx().db())
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected)
+    }
+
+    /// Finds the district a full or partial postcode is in.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the postcode is malformed.
+    #[instrument(skip(mm))]
+    pub async fn find(mm: &ModelManager, postcode: &str) -> Result<Option<PostcodeDistrict>> {
+        let outcode = outcode(postcode)?;
+        let district = sqlx::query_as(
+            "SELECT outcode, latitude, longitude, district, imported_at \
+             FROM postcode_districts WHERE outcode = $1",
+        )
+        .bind(&outcode)
+        .fetch_optional(mm.dbx().db())
+        .await?;
+
+        Ok(district)
+    }
+
+    /// Answers "do you cover me?" for a full or partial postcode.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the postcode is malformed.
+    #[instrument(skip(mm))]
+    pub async fn coverage(mm: &ModelManager, postcode: &str) -> Result<Coverage> {
+        let district = Self::find(mm, postcode).await?;
+        let config = core_config();
+        let tenant = TenantBmc::get(mm, mm.tenant_id()).await?;
+        let centre = Point {
+            lat: tenant
+                .centre_latitude
+                .unwrap_or(config.SERVICE_CENTRE_LATITUDE),
+            lng: tenant
+                .centre_longitude
+                .unwrap_or(config.SERVICE_CENTRE_LONGITUDE),
+        };
+
+        Ok(assess(
+            postcode,
+            district.as_ref(),
+            centre,
+            &config.TRAVEL_BANDS_KM,
+        ))
+    }
+}
+
+// region:    --- Postcode District Helpers
+
+/// Outcode of a full postcode or district; areas alone are too vague.
+fn outcode(postcode: &str) -> Result<String> {
+    postcode::outward(postcode)
+        .filter(|o| o.chars().any(|c| c.is_ascii_digit()))
+        .ok_or_else(|| Error::ValidationError(format!("{postcode} is not a UK postcode").into()))
+}
+
+/// Travel band for a distance: 1 within the first edge, and so on.
+fn travel_band(distance_km: f64, bands_km: &[f64]) -> Option<i32> {
+    bands_km
+        .iter()
+        .position(|&edge| distance_km <= edge)
+        .and_then(|i| i32::try_from(i + 1).ok())
+}
+
+fn assess(
+    postcode: &str,
+    district: Option<&PostcodeDistrict>,
+    centre: Point,
+    bands_km: &[f64],
+) -> Coverage {
+    let canonical = postcode::normalize(postcode)
+        .or_else(|| postcode::outward(postcode))
+        .unwrap_or_else(|| postcode.trim().to_uppercase());
+    let outcode = outcode(postcode).unwrap_or_else(|_| canonical.clone());
+
+    let Some(district) = district else {
+        return Coverage {
+            message: format!(
+                "We don't have {outcode} on our map yet - get in touch and we'll let you know"
+            ),
+            postcode: canonical,
+            outcode,
+            district: None,
+            covered: false,
+            distance_km: None,
+            travel_band: None,
+        };
+    };
+
+    let distance = haversine_km(
+        centre,
+        Point {
+            lat: district.latitude,
+            lng: district.longitude,
+        },
+    );
+    let distance_km = (distance * 10.0).round() / 10.0;
+    let band = travel_band(distance, bands_km);
+    let place = district.district.as_deref().unwrap_or(&district.outcode);
+    let message = match band {
+        Some(1) => format!("Good news - we cover {place}"),
+        Some(_) => format!("We cover {place}; a travel charge may apply"),
+        None => format!("Sorry, {place} is outside the area we cover"),
+    };
+
+    Coverage {
+        postcode: canonical,
+        outcode,
+        district: district.district.clone(),
+        covered: band.is_some(),
+        distance_km: Some(distance_km),
+        travel_band: band,
+        message,
+    }
+}
+
+// endregion: --- Postcode District Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+
+    const COVENTRY: Point = Point {
+        lat: 52.4081,
+        lng: -1.5106,
+    };
+
+    fn fx_district(outcode: &str, lat: f64, lng: f64) -> PostcodeDistrict {
+        PostcodeDistrict {
+            outcode: outcode.to_string(),
+            latitude: lat,
+            longitude: lng,
+            district: Some("Testville".to_string()),
+            imported_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_travel_band() {
+        let bands = [10.0, 20.0, 30.0];
+
+        assert_eq!(travel_band(0.0, &bands), Some(1));
+        assert_eq!(travel_band(10.0, &bands), Some(1));
+        assert_eq!(travel_band(10.1, &bands), Some(2));
+        assert_eq!(travel_band(29.9, &bands), Some(3));
+        assert_eq!(travel_band(30.1, &bands), None);
+        assert_eq!(travel_band(1.0, &[]), None);
+    }
+
+    #[test]
+    fn test_assess() {
+        let bands = [10.0, 20.0, 30.0];
+        // About 3 km and 50 km from the centre
+        let near = fx_district("CV2", 52.42, -1.47);
+        let far = fx_district("NN1", 52.24, -0.89);
+
+        let covered = assess("cv2 1aa", Some(&near), COVENTRY, &bands);
+        let outside = assess("NN1", Some(&far), COVENTRY, &bands);
+        let unknown = assess("ZZ9", None, COVENTRY, &bands);
+
+        assert!(covered.covered);
+        assert_eq!(covered.postcode, "CV2 1AA");
+        assert_eq!(covered.outcode, "CV2");
+        assert_eq!(covered.travel_band, Some(1));
+        assert!(!outside.covered);
+        assert!(outside.distance_km.unwrap() > 30.0);
+        assert!(!unknown.covered);
+        assert_eq!(unknown.distance_km, None);
+    }
+
+    #[test]
+    fn test_outcode() {
+        assert_eq!(outcode("cv1 1aa").unwrap(), "CV1");
+        assert_eq!(outcode("b33").unwrap(), "B33");
+        assert!(outcode("CV").is_err());
+        assert!(outcode("nowhere").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_and_coverage() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let districts = vec![DistrictLocation {
+            outcode: "ZZ98".to_string(),
+            latitude: 52.41,
+            longitude: -1.51,
+            district: Some("Test District".to_string()),
+        }];
+
+        // Execute
+        let imported = PostcodeDistrictBmc::import(&mm, &districts).await?;
+        let reimported = PostcodeDistrictBmc::import(&mm, &districts).await?;
+        let found = PostcodeDistrictBmc::find(&mm, "zz98 1aa").await?;
+
+        // Check
+        assert_eq!((imported, reimported), (1, 1));
+        let found = found.expect("district should be imported");
+        assert_eq!(found.district.as_deref(), Some("Test District"));
+        assert!(PostcodeDistrictBmc::find(&mm, "ZZ97").await?.is_none());
+
+        // Cleanup
+        sqlx::query("DELETE FROM postcode_districts WHERE outcode = 'ZZ98'")
+            .execute(mm.dbx().db())
+            .await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
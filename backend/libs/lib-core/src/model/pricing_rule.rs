@@ -0,0 +1,485 @@
+//! # Pricing Rule Model
+//!
+//! The instant quote catalog: base price ranges per service and the rules
+//! (urgency, job size, out-of-hours, weekend, distance zone) that adjust
+//! them. Admins edit both here; [`crate::pricing::estimate`] evaluates
+//! them.
+//!
+//! ## Structures
+//!
+//! - [`PricingRuleForCreate`] - Data required to create or replace a rule
+//! - [`PricingRuleBmc`] - Business Model Controller for the pricing catalog
+//!
+//! The catalog types themselves ([`PricingCatalog`], [`PricingRule`],
+//! [`ServiceRate`]) live in the `shared` crate so the Leptos calculator can
+//! use them too.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::pricing_rule::{PricingRuleBmc, PricingRuleForCreate};
+//! use lib_core::model::ModelManager;
+//! use shared::{RuleAdjustment, RuleCondition};
+//!
+//! async fn bank_holiday_uplift(mm: &ModelManager) -> Result<i32, Box<dyn std::error::Error>> {
+//!     let rule = PricingRuleForCreate {
+//!         label: "Out-of-hours electrical".to_string(),
+//!         condition: RuleCondition::OutOfHours,
+//!         service_type: Some("electrical".to_string()),
+//!         adjustment: RuleAdjustment::Percent { basis_points: 5000 },
+//!         cap_cents: Some(7500),
+//!         priority: 30,
+//!         active: true,
+//!     };
+//!     let id = PricingRuleBmc::create(mm, rule).await?;
+//!     Ok(id)
+//! }
+//! ```
+
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use shared::{PricingCatalog, PricingRule, RuleAdjustment, RuleCondition, ServiceRate};
+use sqlx::FromRow;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Data required to create a rule, or to replace one on update.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct PricingRuleForCreate {
+    /// Label shown to customers
+    pub label: String,
+    /// When the rule applies
+    pub condition: RuleCondition,
+    /// Only for this service (any service if `None`)
+    #[serde(default)]
+    pub service_type: Option<String>,
+    /// Price change
+    pub adjustment: RuleAdjustment,
+    /// Largest change the rule can make, in cents
+    #[serde(default)]
+    pub cap_cents: Option<i32>,
+    /// Evaluation order (lowest first)
+    #[serde(default = "default_priority")]
+    pub priority: i32,
+    /// Whether the rule is in use
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_priority() -> i32 {
+    100
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Rule as stored: condition and adjustment flattened into columns.
+#[derive(Debug, FromRow)]
+struct PricingRuleRow {
+    id: i32,
+    label: String,
+    condition_kind: String,
+    condition_value: Option<String>,
+    service_type: Option<String>,
+    adjustment_kind: String,
+    adjustment_value: i32,
+    cap_cents: Option<i32>,
+    priority: i32,
+    active: bool,
+}
+
+#[derive(Debug, FromRow)]
+struct ServiceRateRow {
+    service_type: String,
+    label: String,
+    base_low_cents: i32,
+    base_high_cents: i32,
+    sort_order: i32,
+    active: bool,
+}
+
+/// Business Model Controller for the pricing catalog.
+pub struct PricingRuleBmc;
+
+impl PricingRuleBmc {
+    /// Loads services (in display order) and rules (in evaluation order).
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `active_only` - Leave out inactive services and rules
+    #[instrument(skip(mm))]
+    pub async fn catalog(mm: &ModelManager, active_only: bool) -> Result<PricingCatalog> {
+        let services: Vec<ServiceRateRow> = sqlx::query_as(
+            r#"
+            SELECT service_type, label, base_low_cents, base_high_cents, sort_order, active
+            FROM service_rates
+            WHERE active OR NOT $1
+            ORDER BY sort_order, service_type
+            "#,
+        )
+        .bind(active_only)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        let rules: Vec<PricingRuleRow> = sqlx::query_as(&format!(
+            "SELECT {PRICING_RULE_COLUMNS} FROM pricing_rules \
+             WHERE active OR NOT $1 ORDER BY priority, id"
+        ))
+        .bind(active_only)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(PricingCatalog {
+            services: services.into_iter().map(service_from_row).collect(),
+            rules: rules
+                .into_iter()
+                .map(rule_from_row)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Creates a pricing rule.
+    ///
+    /// # Returns
+    ///
+    /// The auto-generated ID of the new rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for an empty label or condition value, a
+    /// negative cap or a reduction of more than 100%.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm), fields(label = %rule.label))]
+    pub async fn create(mm: &ModelManager, rule: PricingRuleForCreate) -> Result<i32> {
+        validate_rule(&rule)?;
+        let (condition_kind, condition_value) = condition_columns(&rule.condition);
+        let (adjustment_kind, adjustment_value) = adjustment_columns(rule.adjustment);
+
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO pricing_rules (label, condition_kind, condition_value, service_type,
+                                       adjustment_kind, adjustment_value, cap_cents, priority,
+                                       active)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(rule.label.trim())
+        .bind(condition_kind)
+        .bind(condition_value)
+        .bind(&rule.service_type)
+        .bind(adjustment_kind)
+        .bind(adjustment_value)
+        .bind(rule.cap_cents)
+        .bind(rule.priority)
+        .bind(rule.active)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Gets a pricing rule by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the rule does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<PricingRule> {
+        let row: PricingRuleRow = sqlx::query_as(&format!(
+            "SELECT {PRICING_RULE_COLUMNS} FROM pricing_rules WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "PricingRule",
+            id: id as i64,
+        })?;
+
+        rule_from_row(row)
+    }
+
+    /// Replaces a pricing rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the rule does not exist, or
+    /// `ValidationError` as for [`Self::create`].
+    #[instrument(skip(mm), fields(label = %rule.label))]
+    pub async fn update(mm: &ModelManager, id: i32, rule: PricingRuleForCreate) -> Result<()> {
+        validate_rule(&rule)?;
+        let (condition_kind, condition_value) = condition_columns(&rule.condition);
+        let (adjustment_kind, adjustment_value) = adjustment_columns(rule.adjustment);
+
+        let rows_affected = sqlx::query(
+            r#"
+            UPDATE pricing_rules
+            SET label = $2, condition_kind = $3, condition_value = $4, service_type = $5,
+                adjustment_kind = $6, adjustment_value = $7, cap_cents = $8, priority = $9,
+                active = $10
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(rule.label.trim())
+        .bind(condition_kind)
+        .bind(condition_value)
+        .bind(&rule.service_type)
+        .bind(adjustment_kind)
+        .bind(adjustment_value)
+        .bind(rule.cap_cents)
+        .bind(rule.priority)
+        .bind(rule.active)
+        .execute(mm.dbx().db())
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "PricingRule",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Deletes a pricing rule.
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM pricing_rules WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "PricingRule",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Creates a service's base range, or replaces it if the service exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for an empty key or label, or a range that
+    /// is negative or upside down.
+    #[instrument(skip(mm), fields(service_type = %service.service_type))]
+    pub async fn save_service(mm: &ModelManager, service: ServiceRate) -> Result<()> {
+        if service.service_type.trim().is_empty() || service.label.trim().is_empty() {
+            return Err(Error::ValidationError(
+                "Services need a key and a label".into(),
+            ));
+        }
+        if service.base_low_cents < 0 || service.base_high_cents < service.base_low_cents {
+            return Err(Error::ValidationError(
+                "Base range must be non-negative with low at most high".into(),
+            ));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO service_rates (service_type, label, base_low_cents, base_high_cents,
+                                       sort_order, active)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (tenant_id, service_type) DO UPDATE
+            SET label = EXCLUDED.label,
+                base_low_cents = EXCLUDED.base_low_cents,
+                base_high_cents = EXCLUDED.base_high_cents,
+                sort_order = EXCLUDED.sort_order,
+                active = EXCLUDED.active
+            "#,
+        )
+        .bind(service.service_type.trim())
+        .bind(service.label.trim())
+        .bind(service.base_low_cents)
+        .bind(service.base_high_cents)
+        .bind(service.sort_order)
+        .bind(service.active)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(())
+    }
+}
+
+const PRICING_RULE_COLUMNS: &str = "id, label, condition_kind, condition_value, service_type, \
+                                    adjustment_kind, adjustment_value, cap_cents, priority, active";
+
+// region:    --- Pricing Rule Helpers
+
+fn validate_rule(rule: &PricingRuleForCreate) -> Result<()> {
+    if rule.label.trim().is_empty() {
+        return Err(Error::ValidationError("Rule label cannot be empty".into()));
+    }
+    if condition_columns(&rule.condition)
+        .1
+        .is_some_and(|value| value.trim().is_empty())
+    {
+        return Err(Error::ValidationError(
+            "Rule condition needs a value".into(),
+        ));
+    }
+    if rule.cap_cents.is_some_and(|cap| cap < 0) {
+        return Err(Error::ValidationError("Rule cap cannot be negative".into()));
+    }
+    if let RuleAdjustment::Percent { basis_points } = rule.adjustment {
+        if basis_points < -10_000 {
+            return Err(Error::ValidationError(
+                "Rules cannot take off more than 100%".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn condition_columns(condition: &RuleCondition) -> (&'static str, Option<&str>) {
+    match condition {
+        RuleCondition::Urgency(value) => ("urgency", Some(value)),
+        RuleCondition::JobSize(value) => ("job_size", Some(value)),
+        RuleCondition::OutOfHours => ("out_of_hours", None),
+        RuleCondition::Weekend => ("weekend", None),
+        RuleCondition::DistanceZone(value) => ("distance_zone", Some(value)),
+    }
+}
+
+fn adjustment_columns(adjustment: RuleAdjustment) -> (&'static str, i32) {
+    match adjustment {
+        RuleAdjustment::Fixed { amount_cents } => ("fixed", amount_cents),
+        RuleAdjustment::Percent { basis_points } => ("percent", basis_points),
+    }
+}
+
+fn rule_from_row(row: PricingRuleRow) -> Result<PricingRule> {
+    let value = || row.condition_value.clone().unwrap_or_default();
+    let condition = match row.condition_kind.as_str() {
+        "urgency" => RuleCondition::Urgency(value()),
+        "job_size" => RuleCondition::JobSize(value()),
+        "out_of_hours" => RuleCondition::OutOfHours,
+        "weekend" => RuleCondition::Weekend,
+        "distance_zone" => RuleCondition::DistanceZone(value()),
+        other => {
+            return Err(Error::ValidationError(
+                format!("Unknown rule condition '{other}'").into(),
+            ))
+        }
+    };
+    let adjustment = match row.adjustment_kind.as_str() {
+        "percent" => RuleAdjustment::Percent {
+            basis_points: row.adjustment_value,
+        },
+        _ => RuleAdjustment::Fixed {
+            amount_cents: row.adjustment_value,
+        },
+    };
+
+    Ok(PricingRule {
+        id: row.id,
+        label: row.label,
+        condition,
+        service_type: row.service_type,
+        adjustment,
+        cap_cents: row.cap_cents,
+        priority: row.priority,
+        active: row.active,
+    })
+}
+
+fn service_from_row(row: ServiceRateRow) -> ServiceRate {
+    ServiceRate {
+        service_type: row.service_type,
+        label: row.label,
+        base_low_cents: row.base_low_cents,
+        base_high_cents: row.base_high_cents,
+        sort_order: row.sort_order,
+        active: row.active,
+    }
+}
+
+// endregion: --- Pricing Rule Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+
+    fn fx_rule() -> PricingRuleForCreate {
+        PricingRuleForCreate {
+            label: "Test zone 9".to_string(),
+            condition: RuleCondition::DistanceZone("9".to_string()),
+            service_type: Some("plumbing".to_string()),
+            adjustment: RuleAdjustment::Fixed { amount_cents: 4200 },
+            cap_cents: None,
+            priority: 999,
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_validate_rule_err() {
+        let mut rule = fx_rule();
+        rule.condition = RuleCondition::Urgency(" ".to_string());
+        assert!(validate_rule(&rule).is_err());
+
+        let mut rule = fx_rule();
+        rule.adjustment = RuleAdjustment::Percent {
+            basis_points: -10_001,
+        };
+        assert!(validate_rule(&rule).is_err());
+
+        let mut rule = fx_rule();
+        rule.cap_cents = Some(-1);
+        assert!(validate_rule(&rule).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pricing_rule_crud_and_catalog_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+
+        // -- Exec
+        let id = PricingRuleBmc::create(&mm, fx_rule()).await?;
+        let catalog = PricingRuleBmc::catalog(&mm, true).await?;
+
+        // -- Check
+        assert!(catalog
+            .services
+            .iter()
+            .any(|s| s.service_type == "plumbing"));
+        let rule = catalog.rules.iter().find(|r| r.id == id).unwrap();
+        assert_eq!(rule.condition, RuleCondition::DistanceZone("9".to_string()));
+        assert_eq!(
+            rule.adjustment,
+            RuleAdjustment::Fixed { amount_cents: 4200 }
+        );
+
+        // -- Exec: deactivate
+        let mut update = fx_rule();
+        update.active = false;
+        PricingRuleBmc::update(&mm, id, update).await?;
+
+        // -- Check
+        let catalog = PricingRuleBmc::catalog(&mm, true).await?;
+        assert!(catalog.rules.iter().all(|r| r.id != id));
+        assert!(!PricingRuleBmc::get(&mm, id).await?.active);
+
+        // -- Cleanup
+        PricingRuleBmc::delete(&mm, id).await?;
+        assert!(PricingRuleBmc::get(&mm, id).await.is_err());
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
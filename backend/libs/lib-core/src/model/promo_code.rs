@@ -0,0 +1,507 @@
+//! # Promo Code Model
+//!
+//! Promo codes give a fixed or percentage discount off a quote, within a
+//! validity window, up to a usage limit and optionally only for certain
+//! services.
+//!
+//! ## Structures
+//!
+//! - [`PromoCode`] - Complete promo code record from database
+//! - [`PromoCodeForCreate`] - Data required to create a new promo code
+//! - [`PromoCodeBmc`] - Business Model Controller for promo code operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::promo_code::{PromoCodeBmc, PromoCodeForCreate};
+//! use lib_core::model::ModelManager;
+//! use lib_core::pricing::Discount;
+//!
+//! async fn spring_offer(mm: &ModelManager) -> Result<i32, Box<dyn std::error::Error>> {
+//!     let promo = PromoCodeForCreate {
+//!         code: "spring10".to_string(),
+//!         description: Some("10% off spring garden jobs".to_string()),
+//!         discount: Discount::Percent { basis_points: 1000 },
+//!         valid_from: Some("2026-03-01".to_string()),
+//!         valid_until: Some("2026-05-31".to_string()),
+//!         max_uses: Some(50),
+//!         min_subtotal_cents: None,
+//!         service_types: vec!["garden".to_string()],
+//!     };
+//!     let id = PromoCodeBmc::create(mm, promo).await?;
+//!     Ok(id)
+//! }
+//! ```
+
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::pricing::Discount;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Complete promo code record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct PromoCode {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Code customers enter, stored upper-case
+    pub code: String,
+    /// Internal description
+    pub description: Option<String>,
+    /// Discount kind: fixed, percent
+    pub discount_kind: String,
+    /// Cents for fixed discounts, basis points for percentages
+    pub discount_value: i32,
+    /// First day the code can be used
+    pub valid_from: Option<time::Date>,
+    /// Last day the code can be used
+    pub valid_until: Option<time::Date>,
+    /// Maximum number of redemptions (unlimited if `None`)
+    pub max_uses: Option<i32>,
+    /// Redemptions so far
+    pub uses: i32,
+    /// Minimum quote value before the code applies, in cents
+    pub min_subtotal_cents: i32,
+    /// Services the code applies to (any if empty)
+    pub service_types: Vec<String>,
+    /// Whether the code can still be used
+    pub active: bool,
+    /// When the code was created
+    pub created_at: Option<OffsetDateTime>,
+    /// When the code was last updated
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl PromoCode {
+    /// The discount this code gives.
+    #[must_use]
+    pub fn discount(&self) -> Discount {
+        match self.discount_kind.as_str() {
+            "percent" => Discount::Percent {
+                basis_points: self.discount_value,
+            },
+            _ => Discount::Fixed {
+                amount_cents: self.discount_value,
+            },
+        }
+    }
+
+    /// Checks the code can be applied to a quote for `service_type` worth
+    /// `subtotal_cents` (after line discounts) on `today`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` explaining why the code cannot be used.
+    pub fn check(
+        &self,
+        service_type: Option<&str>,
+        subtotal_cents: i32,
+        today: time::Date,
+    ) -> Result<Discount> {
+        let reject = |reason: &str| {
+            Err(Error::ValidationError(
+                format!("Promo code {} {reason}", self.code).into(),
+            ))
+        };
+
+        if !self.active {
+            return reject("is no longer active");
+        }
+        if self.valid_from.is_some_and(|from| today < from) {
+            return reject("is not valid yet");
+        }
+        if self.valid_until.is_some_and(|until| today > until) {
+            return reject("has expired");
+        }
+        if self.max_uses.is_some_and(|max| self.uses >= max) {
+            return reject("has reached its usage limit");
+        }
+        if subtotal_cents < self.min_subtotal_cents {
+            return reject("does not apply to a quote this size");
+        }
+        if !self.service_types.is_empty()
+            && !service_type.is_some_and(|s| self.service_types.iter().any(|t| t == s))
+        {
+            return reject("does not apply to this service");
+        }
+
+        Ok(self.discount())
+    }
+}
+
+/// Data required to create a new promo code.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct PromoCodeForCreate {
+    /// Code customers enter (case-insensitive)
+    pub code: String,
+    /// Internal description
+    pub description: Option<String>,
+    /// Discount the code gives
+    pub discount: Discount,
+    /// First day the code can be used (YYYY-MM-DD)
+    pub valid_from: Option<String>,
+    /// Last day the code can be used (YYYY-MM-DD)
+    pub valid_until: Option<String>,
+    /// Maximum number of redemptions
+    pub max_uses: Option<i32>,
+    /// Minimum quote value in cents
+    pub min_subtotal_cents: Option<i32>,
+    /// Services the code applies to (any if empty)
+    #[serde(default)]
+    pub service_types: Vec<String>,
+}
+
+/// Business Model Controller for promo code operations.
+pub struct PromoCodeBmc;
+
+impl PromoCodeBmc {
+    /// Creates a new promo code.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `promo` - Promo code data to insert
+    ///
+    /// # Returns
+    ///
+    /// The auto-generated ID of the new promo code.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for an empty code or invalid discount, or
+    /// a database error if the code already exists.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm), fields(code = %promo.code))]
+    pub async fn create(mm: &ModelManager, promo: PromoCodeForCreate) -> Result<i32> {
+        let code = normalize(&promo.code);
+        if code.is_empty() {
+            return Err(Error::ValidationError("Promo code cannot be empty".into()));
+        }
+        promo.discount.validate()?;
+        let (kind, value) = match promo.discount {
+            Discount::Fixed { amount_cents } => ("fixed", amount_cents),
+            Discount::Percent { basis_points } => ("percent", basis_points),
+        };
+
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO promo_codes (code, description, discount_kind, discount_value,
+                                     valid_from, valid_until, max_uses, min_subtotal_cents,
+                                     service_types)
+            VALUES ($1, $2, $3, $4, $5::DATE, $6::DATE, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(&code)
+        .bind(&promo.description)
+        .bind(kind)
+        .bind(value)
+        .bind(&promo.valid_from)
+        .bind(&promo.valid_until)
+        .bind(promo.max_uses)
+        .bind(promo.min_subtotal_cents.unwrap_or(0))
+        .bind(&promo.service_types)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Gets a promo code by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the promo code does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<PromoCode> {
+        mm.dbx()
+            .fetch_optional(
+                sqlx::query_as(&format!(
+                    "SELECT {PROMO_CODE_COLUMNS} FROM promo_codes WHERE id = $1"
+                ))
+                .bind(id),
+            )
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "PromoCode",
+                id: id as i64,
+            })
+    }
+
+    /// Looks up a promo code as entered by a customer (case-insensitive).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if no such code exists.
+    #[instrument(skip(mm))]
+    pub async fn get_by_code(mm: &ModelManager, code: &str) -> Result<PromoCode> {
+        sqlx::query_as(&format!(
+            "SELECT {PROMO_CODE_COLUMNS} FROM promo_codes WHERE code = $1"
+        ))
+        .bind(normalize(code))
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or_else(|| Error::ValidationError(format!("Unknown promo code {code}").into()))
+    }
+
+    /// Lists all promo codes, newest first.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager) -> Result<Vec<PromoCode>> {
+        let promos = sqlx::query_as(&format!(
+            "SELECT {PROMO_CODE_COLUMNS} FROM promo_codes ORDER BY created_at DESC"
+        ))
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(promos)
+    }
+
+    /// Checks a code against a prospective quote without redeeming it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the code is unknown or cannot be used.
+    #[instrument(skip(mm))]
+    pub async fn validate(
+        mm: &ModelManager,
+        code: &str,
+        service_type: Option<&str>,
+        subtotal_cents: i32,
+    ) -> Result<PromoCode> {
+        let promo = Self::get_by_code(mm, code).await?;
+        promo.check(
+            service_type,
+            subtotal_cents,
+            OffsetDateTime::now_utc().date(),
+        )?;
+
+        Ok(promo)
+    }
+
+    /// Stops a promo code from being used again.
+    #[instrument(skip(mm))]
+    pub async fn deactivate(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("UPDATE promo_codes SET active = FALSE WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "PromoCode",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Deletes a promo code and its redemption history.
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM promo_codes WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "PromoCode",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Locks a code for redemption inside the caller's transaction.
+    ///
+    /// Pair with [`Self::record_redemption`] in the same transaction so
+    /// concurrent quotes cannot exceed `max_uses`.
+    pub(crate) async fn lock_by_code(mm: &ModelManager, code: &str) -> Result<PromoCode> {
+        mm.dbx()
+            .fetch_optional(
+                sqlx::query_as(&format!(
+                    "SELECT {PROMO_CODE_COLUMNS} FROM promo_codes WHERE code = $1 FOR UPDATE"
+                ))
+                .bind(normalize(code)),
+            )
+            .await?
+            .ok_or_else(|| Error::ValidationError(format!("Unknown promo code {code}").into()))
+    }
+
+    /// Counts a redemption against a code locked with [`Self::lock_by_code`].
+    pub(crate) async fn record_redemption(
+        mm: &ModelManager,
+        promo_code_id: i32,
+        quote_id: i32,
+        discount_cents: i32,
+    ) -> Result<()> {
+        let dbx = mm.dbx();
+        dbx.execute(
+            sqlx::query("UPDATE promo_codes SET uses = uses + 1 WHERE id = $1").bind(promo_code_id),
+        )
+        .await?;
+        dbx.execute(
+            sqlx::query(
+                r#"
+                INSERT INTO promo_redemptions (promo_code_id, quote_id, discount_cents)
+                VALUES ($1, $2, $3)
+                "#,
+            )
+            .bind(promo_code_id)
+            .bind(quote_id)
+            .bind(discount_cents),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Updates the discount recorded for a quote's redemption after the
+    /// quote is repriced.
+    pub(crate) async fn update_redemption(
+        mm: &ModelManager,
+        promo_code_id: i32,
+        quote_id: i32,
+        discount_cents: i32,
+    ) -> Result<()> {
+        mm.dbx()
+            .execute(
+                sqlx::query(
+                    r#"
+                    UPDATE promo_redemptions SET discount_cents = $3
+                    WHERE promo_code_id = $1 AND quote_id = $2
+                    "#,
+                )
+                .bind(promo_code_id)
+                .bind(quote_id)
+                .bind(discount_cents),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+const PROMO_CODE_COLUMNS: &str =
+    "id, code, description, discount_kind, discount_value, valid_from, \
+                       valid_until, max_uses, uses, min_subtotal_cents, service_types, active, \
+                       created_at, updated_at";
+
+/// Codes are matched case-insensitively and ignoring surrounding spaces.
+fn normalize(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use time::Month;
+
+    fn date(year: i32, month: Month, day: u8) -> time::Date {
+        time::Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    fn fx_promo() -> PromoCode {
+        PromoCode {
+            id: 1,
+            code: "SPRING10".to_string(),
+            description: None,
+            discount_kind: "percent".to_string(),
+            discount_value: 1000,
+            valid_from: Some(date(2026, Month::March, 1)),
+            valid_until: Some(date(2026, Month::May, 31)),
+            max_uses: Some(2),
+            uses: 0,
+            min_subtotal_cents: 5000,
+            service_types: vec!["garden".to_string()],
+            active: true,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_promo_check_ok() {
+        let promo = fx_promo();
+
+        let discount = promo.check(Some("garden"), 5000, date(2026, Month::May, 31));
+
+        assert_eq!(
+            discount.ok(),
+            Some(Discount::Percent { basis_points: 1000 })
+        );
+    }
+
+    #[test]
+    fn test_promo_check_err_rules() {
+        let promo = fx_promo();
+        let in_window = date(2026, Month::April, 1);
+
+        assert!(promo
+            .check(Some("garden"), 5000, date(2026, Month::February, 28))
+            .is_err());
+        assert!(promo
+            .check(Some("garden"), 5000, date(2026, Month::June, 1))
+            .is_err());
+        assert!(promo.check(Some("garden"), 4999, in_window).is_err());
+        assert!(promo.check(Some("plumbing"), 5000, in_window).is_err());
+        assert!(promo.check(None, 5000, in_window).is_err());
+
+        let used_up = PromoCode {
+            uses: 2,
+            ..fx_promo()
+        };
+        assert!(used_up.check(Some("garden"), 5000, in_window).is_err());
+
+        let inactive = PromoCode {
+            active: false,
+            ..fx_promo()
+        };
+        assert!(inactive.check(Some("garden"), 5000, in_window).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_promo_create_get_by_code_ok() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let id = PromoCodeBmc::create(
+            &mm,
+            PromoCodeForCreate {
+                code: " test_promo_create ".to_string(),
+                description: None,
+                discount: Discount::Fixed { amount_cents: 1500 },
+                valid_from: None,
+                valid_until: Some("2099-12-31".to_string()),
+                max_uses: None,
+                min_subtotal_cents: None,
+                service_types: Vec::new(),
+            },
+        )
+        .await?;
+
+        // Execute
+        let promo = PromoCodeBmc::get_by_code(&mm, "Test_Promo_Create").await?;
+
+        // Check
+        assert_eq!(promo.id, id);
+        assert_eq!(promo.code, "TEST_PROMO_CREATE");
+        assert_eq!(promo.discount(), Discount::Fixed { amount_cents: 1500 });
+
+        // Cleanup
+        PromoCodeBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
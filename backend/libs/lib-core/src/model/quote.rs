@@ -6,9 +6,20 @@
 //!
 //! - [`Quote`] - Complete quote record from database
 //! - [`QuoteItem`] - Line item in a quote
+//! - [`QuoteOption`] - A named alternative, e.g. repair or replace
+//! - [`QuoteSelection`] - The option and add-ons a customer chose
 //! - [`QuoteForCreate`] - Data required to create a new quote
+//! - [`QuoteForUpdate`] - Changes to an existing quote (new revision once sent)
 //! - [`QuoteBmc`] - Business Model Controller for quote operations
 //!
+//! ## Options and add-ons
+//!
+//! A quote can offer [`QuoteOption`]s, each adding its own line items to
+//! the quote's shared items, and optional add-ons the customer can tick.
+//! Until the quote is accepted its totals are for the first option with no
+//! add-ons; accepting it records a [`QuoteSelection`] and reprices the
+//! quote for that selection, which the booking and invoice then follow.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
@@ -24,17 +35,29 @@
 //!                 description: "Call-out fee".to_string(),
 //!                 quantity: 1,
 //!                 unit_price: 3000,
+//!                 discount: None,
+//!                 vat_rate_bp: None,
 //!             },
 //!         ],
 //!         valid_days: Some(30),
+//!         service_type: None,
+//!         discount: None,
+//!         promo_code: None,
+//!         notes: None,
+//!         template_id: None,
+//!         options: vec![],
+//!         add_ons: vec![],
 //!     };
 //!     let id = QuoteBmc::create(mm, quote).await?;
 //!     Ok(id)
 //! }
 //! ```
 
+use crate::model::promo_code::PromoCodeBmc;
+use crate::model::quote_revision::{self, QuoteRevision, QuoteRevisionBmc};
 use crate::model::ModelManager;
-use crate::model::Result;
+use crate::model::{Error, Result};
+use crate::pricing::{self, Discount, PriceBreakdown, VatSettings};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use time::OffsetDateTime;
@@ -50,6 +73,52 @@ pub struct QuoteItem {
     pub quantity: i32,
     /// Unit price in cents
     pub unit_price: i32,
+    /// Discount on this line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discount: Option<Discount>,
+    /// VAT rate for this line in basis points (defaults to `VAT_RATE_BP`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vat_rate_bp: Option<i32>,
+}
+
+/// A named alternative within a quote, e.g. "Repair tap" or "Replace
+/// mixer".
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuoteOption {
+    /// Name shown to the customer
+    pub name: String,
+    /// What the option involves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Line items for this option, on top of the quote's shared items
+    pub items: Vec<QuoteItem>,
+    /// Total including VAT with the shared items and no add-ons, in cents.
+    /// Set when the quote is priced; ignored on input.
+    #[serde(default)]
+    pub total_cents: i32,
+}
+
+/// The option and add-ons a customer chose when accepting a quote.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct QuoteSelection {
+    /// Index of the chosen option (required when the quote has options)
+    #[serde(default)]
+    pub option: Option<usize>,
+    /// Indexes of the add-ons ticked
+    #[serde(default)]
+    pub add_ons: Vec<usize>,
+}
+
+impl QuoteSelection {
+    /// What a quote is priced as before the customer chooses: the first
+    /// option, if any, and no add-ons.
+    #[must_use]
+    pub fn default_for(options: &[QuoteOption]) -> Self {
+        Self {
+            option: (!options.is_empty()).then_some(0),
+            add_ons: Vec::new(),
+        }
+    }
 }
 
 /// Complete quote record from the database.
@@ -61,14 +130,38 @@ pub struct Quote {
     pub customer_id: Option<i32>,
     /// Quote title
     pub title: String,
-    /// JSON array of line items
+    /// JSON array of line items shared by every option
     pub items: serde_json::Value,
+    /// JSON array of [`QuoteOption`]s to choose between
+    pub options: serde_json::Value,
+    /// JSON array of optional add-on [`QuoteItem`]s
+    pub add_ons: serde_json::Value,
+    /// [`QuoteSelection`] made when the quote was accepted
+    pub selection: Option<serde_json::Value>,
     /// Subtotal in cents
     pub subtotal_cents: i32,
-    /// Discount in cents
+    /// All discounts (line, quote and promo) in cents
     pub discount_cents: i32,
-    /// Total in cents
+    /// VAT in cents
+    pub vat_cents: i32,
+    /// VAT by rate, as [`VatBand`]s
+    pub vat_breakdown: serde_json::Value,
+    /// Total including VAT in cents
     pub total_cents: i32,
+    /// Service the quote is for, used by promo code restrictions
+    pub service_type: Option<String>,
+    /// Quote-level [`Discount`]
+    pub discount: Option<serde_json::Value>,
+    /// Promo code applied to the quote
+    pub promo_code_id: Option<i32>,
+    /// Notes printed on the quote
+    pub notes: Option<String>,
+    /// Template the quote was created from
+    pub template_id: Option<i32>,
+    /// Current revision, bumped each time a sent quote is edited
+    pub revision: i32,
+    /// Revision the customer accepted
+    pub accepted_revision: Option<i32>,
     /// Quote expiry date
     pub valid_until: Option<time::Date>,
     /// Current status: draft, sent, viewed, accepted, rejected, expired
@@ -85,6 +178,55 @@ pub struct Quote {
     pub updated_at: Option<OffsetDateTime>,
 }
 
+impl Quote {
+    /// Options to choose between (empty if there is no choice).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stored options are malformed.
+    pub fn quote_options(&self) -> Result<Vec<QuoteOption>> {
+        from_json(&self.options)
+    }
+
+    /// Optional add-ons.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stored add-ons are malformed.
+    pub fn quote_add_ons(&self) -> Result<Vec<QuoteItem>> {
+        from_json(&self.add_ons)
+    }
+
+    /// The customer's selection once accepted, otherwise the default one
+    /// the totals are for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stored options or selection are malformed.
+    pub fn quote_selection(&self) -> Result<QuoteSelection> {
+        match &self.selection {
+            Some(selection) => from_json(selection),
+            None => Ok(QuoteSelection::default_for(&self.quote_options()?)),
+        }
+    }
+
+    /// Line items for the work the totals are for: the shared items, the
+    /// selected option's items and the ticked add-ons.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stored items are malformed or the selection
+    /// does not match the options.
+    pub fn selected_items(&self) -> Result<Vec<QuoteItem>> {
+        select_items(
+            &from_json::<Vec<QuoteItem>>(&self.items)?,
+            &self.quote_options()?,
+            &self.quote_add_ons()?,
+            &self.quote_selection()?,
+        )
+    }
+}
+
 /// Data required to create a new quote.
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 pub struct QuoteForCreate {
@@ -96,6 +238,48 @@ pub struct QuoteForCreate {
     pub items: Vec<QuoteItem>,
     /// Days until expiry (default 30)
     pub valid_days: Option<i32>,
+    /// Service the quote is for
+    #[serde(default)]
+    pub service_type: Option<String>,
+    /// Quote-level discount, applied after line discounts
+    #[serde(default)]
+    pub discount: Option<Discount>,
+    /// Promo code to redeem, applied after the quote discount
+    #[serde(default)]
+    pub promo_code: Option<String>,
+    /// Notes printed on the quote
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Template the quote was created from
+    #[serde(default)]
+    pub template_id: Option<i32>,
+    /// Alternatives to choose between, each on top of `items`
+    #[serde(default)]
+    pub options: Vec<QuoteOption>,
+    /// Optional add-ons the customer can tick
+    #[serde(default)]
+    pub add_ons: Vec<QuoteItem>,
+}
+
+/// Changes to an existing quote. Fields left `None` keep their value.
+///
+/// Editing a sent quote creates a new revision; see [`QuoteBmc::update`].
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct QuoteForUpdate {
+    /// New title
+    pub title: Option<String>,
+    /// Replacement shared line items
+    pub items: Option<Vec<QuoteItem>>,
+    /// Replacement options (an empty list removes the choice)
+    pub options: Option<Vec<QuoteOption>>,
+    /// Replacement add-ons
+    pub add_ons: Option<Vec<QuoteItem>>,
+    /// New expiry, in days from today
+    pub valid_days: Option<i32>,
+    /// Replacement quote-level discount (a zero discount removes it)
+    pub discount: Option<Discount>,
+    /// New notes
+    pub notes: Option<String>,
 }
 
 /// Business Model Controller for quote operations.
@@ -104,6 +288,12 @@ pub struct QuoteBmc;
 impl QuoteBmc {
     /// Creates a new quote in the database.
     ///
+    /// Totals come from [`pricing::price`]: line discounts, then the quote
+    /// discount, then the promo code, then VAT per line rate. A promo code
+    /// is redeemed in the same transaction, so its usage limit holds under
+    /// concurrent quotes. With options, the totals are for the first option
+    /// and each option carries its own total.
+    ///
     /// # Arguments
     ///
     /// * `mm` - Model manager for database access
@@ -112,37 +302,229 @@ impl QuoteBmc {
     /// # Returns
     ///
     /// The auto-generated ID of the new quote.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for invalid prices or discounts, or a promo
+    /// code that is unknown or cannot be used for this quote.
     #[must_use = "the returned ID should be used or logged"]
     #[instrument(skip(mm), fields(title = %quote.title))]
     pub async fn create(mm: &ModelManager, quote: QuoteForCreate) -> Result<i32> {
-        // Calculate totals
-        let subtotal: i32 = quote.items.iter().map(|i| i.quantity * i.unit_price).sum();
-        let total = subtotal; // No discount for now
-
-        // Serialize items to JSON
-        let items_json = serde_json::to_value(&quote.items)
-            .map_err(|e| crate::model::Error::Sqlx(sqlx::Error::Decode(Box::new(e))))?;
+        let vat = VatSettings::from_config();
+        let mut order_discounts: Vec<Discount> = quote.discount.into_iter().collect();
+        let mut options = quote.options;
+        validate_options(&options)?;
+        let priced_items = select_items(
+            &quote.items,
+            &options,
+            &quote.add_ons,
+            &QuoteSelection::default_for(&options),
+        )?;
+
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        // Promo codes are checked against the price before the promo itself
+        let promo = match quote.promo_code.as_deref() {
+            Some(code) => {
+                let promo = PromoCodeBmc::lock_by_code(&mm, code).await?;
+                let before = pricing::price(&priced_items, &order_discounts, &vat)?;
+                let discount = promo.check(
+                    quote.service_type.as_deref(),
+                    before.net_cents,
+                    OffsetDateTime::now_utc().date(),
+                )?;
+                order_discounts.push(discount);
+                Some(promo)
+            }
+            None => None,
+        };
+        let breakdown = price_options(&quote.items, &mut options, &order_discounts, &vat)?;
 
-        // Calculate valid_until date
+        let items_json = to_json(&quote.items)?;
+        let vat_json = to_json(&breakdown.vat_bands)?;
+        let discount_json = quote.discount.as_ref().map(to_json).transpose()?;
         let valid_days = quote.valid_days.unwrap_or(30);
 
-        let row: (i32,) = sqlx::query_as(
-            r#"
-            INSERT INTO quotes (customer_id, title, items, subtotal_cents, total_cents, valid_until, status)
-            VALUES ($1, $2, $3, $4, $5, CURRENT_DATE + $6, 'draft')
-            RETURNING id
-            "#,
-        )
-        .bind(quote.customer_id)
-        .bind(&quote.title)
-        .bind(&items_json)
-        .bind(subtotal)
-        .bind(total)
-        .bind(valid_days)
-        .fetch_one(mm.dbx().db())
-        .await?;
+        let (id,): (i32,) = dbx
+            .fetch_one(
+                sqlx::query_as(
+                    r#"
+                    INSERT INTO quotes (customer_id, title, items, subtotal_cents, discount_cents,
+                                        vat_cents, vat_breakdown, total_cents, service_type,
+                                        discount, promo_code_id, notes, template_id,
+                                        valid_until, options, add_ons, status)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13,
+                            CURRENT_DATE + $14, $15, $16, 'draft')
+                    RETURNING id
+                    "#,
+                )
+                .bind(quote.customer_id)
+                .bind(&quote.title)
+                .bind(&items_json)
+                .bind(breakdown.subtotal_cents)
+                .bind(breakdown.discount_cents)
+                .bind(breakdown.vat_cents)
+                .bind(&vat_json)
+                .bind(breakdown.total_cents)
+                .bind(&quote.service_type)
+                .bind(&discount_json)
+                .bind(promo.as_ref().map(|p| p.id))
+                .bind(&quote.notes)
+                .bind(quote.template_id)
+                .bind(valid_days)
+                .bind(to_json(&options)?)
+                .bind(to_json(&quote.add_ons)?),
+            )
+            .await?;
+
+        if let Some(promo) = promo {
+            let promo_cents = breakdown.order_discount_cents.last().copied().unwrap_or(0);
+            PromoCodeBmc::record_redemption(&mm, promo.id, id, promo_cents).await?;
+        }
+
+        dbx.commit_txn().await?;
 
-        Ok(row.0)
+        Ok(id)
+    }
+
+    /// Edits a quote and reprices it.
+    ///
+    /// A draft is changed in place. A sent or viewed quote gets a new
+    /// revision: the previous one stays as the customer saw it, the new one
+    /// records what changed, and the quote goes back to `sent`. An edit that
+    /// changes nothing leaves the quote alone. A promo code already on the
+    /// quote keeps applying.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Quote to edit
+    /// * `changes` - Fields to change
+    ///
+    /// # Returns
+    ///
+    /// The quote as it now stands.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the quote does not exist, or
+    /// `ValidationError` for invalid prices or discounts, or a quote that
+    /// has already been accepted, rejected or has expired.
+    #[instrument(skip(mm, changes))]
+    pub async fn update(mm: &ModelManager, id: i32, changes: QuoteForUpdate) -> Result<Quote> {
+        let vat = VatSettings::from_config();
+
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        let current: Quote = dbx
+            .fetch_optional(
+                sqlx::query_as(&format!(
+                    "SELECT {QUOTE_COLUMNS} FROM quotes WHERE id = $1 FOR UPDATE"
+                ))
+                .bind(id),
+            )
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "Quote",
+                id: id as i64,
+            })?;
+        let revise = match current.status.as_str() {
+            "draft" => false,
+            "sent" | "viewed" => true,
+            other => {
+                return Err(Error::ValidationError(
+                    format!("This quote is {other} and can no longer be changed").into(),
+                ))
+            }
+        };
+
+        let items = match changes.items {
+            Some(items) => items,
+            None => from_json(&current.items)?,
+        };
+        let mut options = match changes.options {
+            Some(options) => options,
+            None => current.quote_options()?,
+        };
+        let add_ons = match changes.add_ons {
+            Some(add_ons) => add_ons,
+            None => current.quote_add_ons()?,
+        };
+        validate_options(&options)?;
+        let discount = match changes.discount {
+            Some(discount) => Some(discount),
+            None => current.discount.as_ref().map(from_json).transpose()?,
+        };
+        let mut order_discounts: Vec<Discount> = discount.into_iter().collect();
+        if let Some(promo_code_id) = current.promo_code_id {
+            order_discounts.push(PromoCodeBmc::get(&mm, promo_code_id).await?.discount());
+        }
+        let breakdown = price_options(&items, &mut options, &order_discounts, &vat)?;
+
+        let updated: Quote = dbx
+            .fetch_one(
+                sqlx::query_as(&format!(
+                    r#"
+                    UPDATE quotes
+                    SET title = $2, items = $3, subtotal_cents = $4, discount_cents = $5,
+                        vat_cents = $6, vat_breakdown = $7, total_cents = $8, discount = $9,
+                        notes = $10,
+                        valid_until = COALESCE(CURRENT_DATE + $11::INTEGER, valid_until),
+                        revision = revision + $12::INTEGER,
+                        status = CASE WHEN $12 = 1 THEN 'sent' ELSE status END,
+                        options = $13, add_ons = $14,
+                        updated_at = CURRENT_TIMESTAMP
+                    WHERE id = $1
+                    RETURNING {QUOTE_COLUMNS}
+                    "#
+                ))
+                .bind(id)
+                .bind(changes.title.as_ref().unwrap_or(&current.title))
+                .bind(to_json(&items)?)
+                .bind(breakdown.subtotal_cents)
+                .bind(breakdown.discount_cents)
+                .bind(breakdown.vat_cents)
+                .bind(to_json(&breakdown.vat_bands)?)
+                .bind(breakdown.total_cents)
+                .bind(discount.as_ref().map(to_json).transpose()?)
+                .bind(changes.notes.as_ref().or(current.notes.as_ref()))
+                .bind(changes.valid_days)
+                .bind(i32::from(revise))
+                .bind(to_json(&options)?)
+                .bind(to_json(&add_ons)?),
+            )
+            .await?;
+
+        if let Some(promo_code_id) = current.promo_code_id {
+            let promo_cents = breakdown.order_discount_cents.last().copied().unwrap_or(0);
+            PromoCodeBmc::update_redemption(&mm, promo_code_id, id, promo_cents).await?;
+        }
+
+        if revise {
+            let previous = match QuoteRevisionBmc::get(&mm, id, current.revision).await {
+                Ok(previous) => previous,
+                // Sent before revisions were recorded
+                Err(Error::EntityNotFound { .. }) => {
+                    QuoteRevisionBmc::snapshot(&mm, &current, None).await?;
+                    QuoteRevision::from_quote(&current)
+                }
+                Err(e) => return Err(e),
+            };
+            let changes = quote_revision::diff(&previous, &QuoteRevision::from_quote(&updated))?;
+            if changes.is_empty() {
+                // Dropping the transaction rolls the revision bump back
+                return Ok(current);
+            }
+            QuoteRevisionBmc::snapshot(&mm, &updated, Some(&changes)).await?;
+        }
+
+        dbx.commit_txn().await?;
+
+        Ok(updated)
     }
 
     /// Gets a quote by ID.
@@ -153,34 +535,22 @@ impl QuoteBmc {
     /// * `id` - Quote ID to retrieve
     #[instrument(skip(mm))]
     pub async fn get(mm: &ModelManager, id: i32) -> Result<Quote> {
-        let quote = sqlx::query_as::<_, Quote>(
-            r#"
-            SELECT id, customer_id, title, items, subtotal_cents, discount_cents, total_cents,
-                   valid_until, status, customer_notes, accepted_at, booking_id,
-                   created_at, updated_at
-            FROM quotes
-            WHERE id = $1
-            "#,
-        )
-        .bind(id)
-        .fetch_one(mm.dbx().db())
-        .await?;
-
-        Ok(quote)
+        sqlx::query_as(&format!("SELECT {QUOTE_COLUMNS} FROM quotes WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "Quote",
+                id: id as i64,
+            })
     }
 
     /// Lists all quotes, ordered by creation date (newest first).
     #[instrument(skip(mm))]
     pub async fn list(mm: &ModelManager) -> Result<Vec<Quote>> {
-        let quotes = sqlx::query_as(
-            r#"
-            SELECT id, customer_id, title, items, subtotal_cents, discount_cents, total_cents,
-                   valid_until, status, customer_notes, accepted_at, booking_id,
-                   created_at, updated_at
-            FROM quotes
-            ORDER BY created_at DESC
-            "#,
-        )
+        let quotes = sqlx::query_as(&format!(
+            "SELECT {QUOTE_COLUMNS} FROM quotes ORDER BY created_at DESC"
+        ))
         .fetch_all(mm.dbx().db())
         .await?;
 
@@ -190,16 +560,9 @@ impl QuoteBmc {
     /// Lists quotes by status.
     #[instrument(skip(mm))]
     pub async fn list_by_status(mm: &ModelManager, status: &str) -> Result<Vec<Quote>> {
-        let quotes = sqlx::query_as(
-            r#"
-            SELECT id, customer_id, title, items, subtotal_cents, discount_cents, total_cents,
-                   valid_until, status, customer_notes, accepted_at, booking_id,
-                   created_at, updated_at
-            FROM quotes
-            WHERE status = $1
-            ORDER BY created_at DESC
-            "#,
-        )
+        let quotes = sqlx::query_as(&format!(
+            "SELECT {QUOTE_COLUMNS} FROM quotes WHERE status = $1 ORDER BY created_at DESC"
+        ))
         .bind(status)
         .fetch_all(mm.dbx().db())
         .await?;
@@ -210,16 +573,9 @@ impl QuoteBmc {
     /// Lists quotes for a specific customer.
     #[instrument(skip(mm))]
     pub async fn list_by_customer(mm: &ModelManager, customer_id: i32) -> Result<Vec<Quote>> {
-        let quotes = sqlx::query_as(
-            r#"
-            SELECT id, customer_id, title, items, subtotal_cents, discount_cents, total_cents,
-                   valid_until, status, customer_notes, accepted_at, booking_id,
-                   created_at, updated_at
-            FROM quotes
-            WHERE customer_id = $1
-            ORDER BY created_at DESC
-            "#,
-        )
+        let quotes = sqlx::query_as(&format!(
+            "SELECT {QUOTE_COLUMNS} FROM quotes WHERE customer_id = $1 ORDER BY created_at DESC"
+        ))
         .bind(customer_id)
         .fetch_all(mm.dbx().db())
         .await?;
@@ -253,36 +609,108 @@ impl QuoteBmc {
         Ok(())
     }
 
-    /// Marks a quote as sent.
+    /// Marks a quote as sent and records the revision the customer is
+    /// being sent, if not recorded already.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the quote does not exist, or
+    /// `ValidationError` if it has already been answered or has expired.
     #[instrument(skip(mm))]
     pub async fn send(mm: &ModelManager, id: i32) -> Result<()> {
-        Self::update_status(mm, id, "sent").await
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        let quote: Option<Quote> = dbx
+            .fetch_optional(
+                sqlx::query_as(&format!(
+                    r#"
+                    UPDATE quotes
+                    SET status = 'sent', updated_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND status IN ('draft', 'sent', 'viewed')
+                    RETURNING {QUOTE_COLUMNS}
+                    "#
+                ))
+                .bind(id),
+            )
+            .await?;
+        let Some(quote) = quote else {
+            // Distinguish "missing" from "already answered"
+            let status: Option<(String,)> = dbx
+                .fetch_optional(sqlx::query_as("SELECT status FROM quotes WHERE id = $1").bind(id))
+                .await?;
+            return Err(match status {
+                Some((status,)) => Error::ValidationError(
+                    format!("Quote can no longer be sent (quote is {status})").into(),
+                ),
+                None => Error::EntityNotFound {
+                    entity: "Quote",
+                    id: id as i64,
+                },
+            });
+        };
+        QuoteRevisionBmc::snapshot(&mm, &quote, None).await?;
+
+        dbx.commit_txn().await?;
+
+        Ok(())
+    }
+
+    /// Marks a sent quote as viewed by the customer.
+    #[instrument(skip(mm))]
+    pub async fn mark_viewed(mm: &ModelManager, id: i32) -> Result<()> {
+        sqlx::query("UPDATE quotes SET status = 'viewed' WHERE id = $1 AND status = 'sent'")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?;
+
+        Ok(())
     }
 
     /// Accepts a quote and optionally creates a booking.
+    ///
+    /// `revision` is the revision the customer was shown. Acceptance fails
+    /// if the quote has been revised since, so a customer can never accept
+    /// terms they did not see.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the quote does not exist, or
+    /// `ValidationError` if `revision` is out of date or the quote is not
+    /// waiting for a response.
     #[instrument(skip(mm))]
     pub async fn accept(
         mm: &ModelManager,
         id: i32,
+        revision: i32,
         booking_id: Option<i32>,
         customer_notes: Option<&str>,
     ) -> Result<()> {
-        sqlx::query(
+        let rows_affected = sqlx::query(
             r#"
             UPDATE quotes
             SET status = 'accepted',
                 accepted_at = CURRENT_TIMESTAMP,
-                booking_id = $2,
-                customer_notes = $3,
+                accepted_revision = $2,
+                booking_id = $3,
+                customer_notes = $4,
                 updated_at = CURRENT_TIMESTAMP
-            WHERE id = $1
+            WHERE id = $1 AND revision = $2 AND status IN ('sent', 'viewed')
             "#,
         )
         .bind(id)
+        .bind(revision)
         .bind(booking_id)
         .bind(customer_notes)
         .execute(mm.dbx().db())
-        .await?;
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            let quote = Self::get(mm, id).await?;
+            return Err(not_acceptable(&quote, revision));
+        }
 
         Ok(())
     }
@@ -327,12 +755,191 @@ impl QuoteBmc {
     }
 }
 
+pub(crate) const QUOTE_COLUMNS: &str =
+    "id, customer_id, title, items, options, add_ons, selection, subtotal_cents, \
+     discount_cents, vat_cents, vat_breakdown, total_cents, service_type, discount, \
+     promo_code_id, notes, template_id, revision, accepted_revision, valid_until, status, \
+     customer_notes, accepted_at, booking_id, created_at, updated_at";
+
+// region:    --- Quote Helpers
+
+/// Shared items plus the selected option's items and add-ons, in that
+/// order.
+///
+/// # Errors
+///
+/// Returns `ValidationError` if no option is chosen when there are options
+/// to choose from, or an option or add-on index is out of range or
+/// repeated.
+pub fn select_items(
+    items: &[QuoteItem],
+    options: &[QuoteOption],
+    add_ons: &[QuoteItem],
+    selection: &QuoteSelection,
+) -> Result<Vec<QuoteItem>> {
+    let mut selected = items.to_vec();
+
+    match (options.is_empty(), selection.option) {
+        (true, None) => {}
+        (true, Some(_)) => {
+            return Err(Error::ValidationError(
+                "This quote has no options to choose from".into(),
+            ))
+        }
+        (false, None) => {
+            return Err(Error::ValidationError(
+                "Please choose one of the options".into(),
+            ))
+        }
+        (false, Some(i)) => {
+            let option = options
+                .get(i)
+                .ok_or(Error::ValidationError("Unknown option".into()))?;
+            selected.extend(option.items.iter().cloned());
+        }
+    }
+
+    for (n, &i) in selection.add_ons.iter().enumerate() {
+        if selection.add_ons[..n].contains(&i) {
+            return Err(Error::ValidationError(
+                "Each add-on can only be chosen once".into(),
+            ));
+        }
+        let add_on = add_ons
+            .get(i)
+            .ok_or(Error::ValidationError("Unknown add-on".into()))?;
+        selected.push(add_on.clone());
+    }
+
+    Ok(selected)
+}
+
+/// One line describing a selection, e.g. `Replace mixer, with Isolation
+/// valve`. Empty when there was nothing to choose.
+#[must_use]
+pub fn describe_selection(
+    options: &[QuoteOption],
+    add_ons: &[QuoteItem],
+    selection: &QuoteSelection,
+) -> String {
+    let option = selection
+        .option
+        .and_then(|i| options.get(i))
+        .map(|o| o.name.clone());
+    let extras: Vec<&str> = selection
+        .add_ons
+        .iter()
+        .filter_map(|&i| add_ons.get(i))
+        .map(|a| a.description.as_str())
+        .collect();
+
+    match (option, extras.is_empty()) {
+        (Some(option), true) => option,
+        (Some(option), false) => format!("{option}, with {}", extras.join(", ")),
+        (None, false) => format!("With {}", extras.join(", ")),
+        (None, true) => String::new(),
+    }
+}
+
+/// Prices the default selection and sets each option's own total.
+fn price_options(
+    items: &[QuoteItem],
+    options: &mut [QuoteOption],
+    order_discounts: &[Discount],
+    vat: &VatSettings,
+) -> Result<PriceBreakdown> {
+    for option in options.iter_mut() {
+        let mut option_items = items.to_vec();
+        option_items.extend(option.items.iter().cloned());
+        option.total_cents = pricing::price(&option_items, order_discounts, vat)?.total_cents;
+    }
+
+    let default_items = select_items(items, options, &[], &QuoteSelection::default_for(options))?;
+    pricing::price(&default_items, order_discounts, vat)
+}
+
+/// Prices `quote` for `selection` with the quote's own discount and promo
+/// code, as it would stand if the customer accepted that selection. The
+/// promo code is read through `mm`'s transaction, if it has one.
+///
+/// # Errors
+///
+/// Returns `ValidationError` if the selection does not match the quote's
+/// options and add-ons.
+pub(crate) async fn price_selection(
+    mm: &ModelManager,
+    quote: &Quote,
+    selection: &QuoteSelection,
+) -> Result<PriceBreakdown> {
+    let items = select_items(
+        &from_json::<Vec<QuoteItem>>(&quote.items)?,
+        &quote.quote_options()?,
+        &quote.quote_add_ons()?,
+        selection,
+    )?;
+    let mut order_discounts: Vec<Discount> = quote
+        .discount
+        .as_ref()
+        .map(from_json)
+        .transpose()?
+        .into_iter()
+        .collect();
+    if let Some(promo_code_id) = quote.promo_code_id {
+        order_discounts.push(PromoCodeBmc::get(mm, promo_code_id).await?.discount());
+    }
+
+    pricing::price(&items, &order_discounts, &VatSettings::from_config())
+}
+
+fn validate_options(options: &[QuoteOption]) -> Result<()> {
+    for option in options {
+        if option.name.trim().is_empty() {
+            return Err(Error::ValidationError("Every option needs a name".into()));
+        }
+        if options.iter().filter(|o| o.name == option.name).count() > 1 {
+            return Err(Error::ValidationError(
+                format!("There are two options called \"{}\"", option.name).into(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn to_json<T: Serialize>(value: &T) -> Result<serde_json::Value> {
+    serde_json::to_value(value).map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(value: &serde_json::Value) -> Result<T> {
+    serde_json::from_value(value.clone()).map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))
+}
+
+/// Why `quote` cannot be accepted at `revision`.
+pub(crate) fn not_acceptable(quote: &Quote, revision: i32) -> Error {
+    if quote.revision != revision {
+        Error::ValidationError(
+            format!(
+                "This quote was revised after you viewed it; please review revision {}",
+                quote.revision
+            )
+            .into(),
+        )
+    } else {
+        Error::ValidationError(
+            format!("This quote is {} and cannot be accepted", quote.status).into(),
+        )
+    }
+}
+
+// endregion: --- Quote Helpers
+
 // region:    --- Tests
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::_dev_utils;
+    use crate::model::promo_code::PromoCodeForCreate;
 
     fn test_items() -> Vec<QuoteItem> {
         vec![
@@ -340,11 +947,15 @@ mod tests {
                 description: "Call-out fee".to_string(),
                 quantity: 1,
                 unit_price: 3000,
+                discount: None,
+                vat_rate_bp: None,
             },
             QuoteItem {
                 description: "Labour (1 hour)".to_string(),
                 quantity: 1,
                 unit_price: 4500,
+                discount: None,
+                vat_rate_bp: None,
             },
         ]
     }
@@ -360,6 +971,13 @@ mod tests {
             title: "Test Quote Create".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
 
         let id = QuoteBmc::create(&mm, quote).await?;
@@ -373,6 +991,153 @@ mod tests {
         Ok(())
     }
 
+    fn test_options() -> (Vec<QuoteOption>, Vec<QuoteItem>) {
+        let item = |description: &str, unit_price| QuoteItem {
+            description: description.to_string(),
+            quantity: 1,
+            unit_price,
+            discount: None,
+            vat_rate_bp: None,
+        };
+        let options = vec![
+            QuoteOption {
+                name: "Repair tap".to_string(),
+                description: None,
+                items: vec![item("Washer", 500)],
+                total_cents: 0,
+            },
+            QuoteOption {
+                name: "Replace mixer".to_string(),
+                description: Some("Includes a new mixer tap".to_string()),
+                items: vec![item("Mixer tap", 9000)],
+                total_cents: 0,
+            },
+        ];
+        (options, vec![item("Isolation valve", 1500)])
+    }
+
+    #[test]
+    fn test_select_items() -> Result<()> {
+        let (options, add_ons) = test_options();
+        let select = |option, add_ons_chosen: Vec<usize>| {
+            select_items(
+                &test_items(),
+                &options,
+                &add_ons,
+                &QuoteSelection {
+                    option,
+                    add_ons: add_ons_chosen,
+                },
+            )
+        };
+
+        let chosen = select(Some(1), vec![0])?;
+        let descriptions: Vec<&str> = chosen.iter().map(|i| i.description.as_str()).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "Call-out fee",
+                "Labour (1 hour)",
+                "Mixer tap",
+                "Isolation valve"
+            ]
+        );
+        assert!(select(None, vec![]).is_err(), "An option must be chosen");
+        assert!(select(Some(2), vec![]).is_err());
+        assert!(select(Some(0), vec![1]).is_err());
+        assert!(select(Some(0), vec![0, 0]).is_err());
+        assert_eq!(
+            select_items(&test_items(), &[], &[], &QuoteSelection::default())?.len(),
+            2
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe_selection() {
+        let (options, add_ons) = test_options();
+        let describe = |option, add_ons_chosen: Vec<usize>| {
+            describe_selection(
+                &options,
+                &add_ons,
+                &QuoteSelection {
+                    option,
+                    add_ons: add_ons_chosen,
+                },
+            )
+        };
+
+        assert_eq!(describe(Some(0), vec![]), "Repair tap");
+        assert_eq!(
+            describe(Some(1), vec![0]),
+            "Replace mixer, with Isolation valve"
+        );
+        assert_eq!(describe(None, vec![]), "");
+    }
+
+    #[tokio::test]
+    async fn test_quote_create_with_options() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let (options, add_ons) = test_options();
+
+        // Execute
+        let duplicate = QuoteBmc::create(
+            &mm,
+            QuoteForCreate {
+                customer_id: None,
+                title: "test_quote_create_with_options duplicate".to_string(),
+                items: test_items(),
+                valid_days: Some(30),
+                service_type: None,
+                discount: None,
+                promo_code: None,
+                notes: None,
+                template_id: None,
+                options: vec![options[0].clone(), options[0].clone()],
+                add_ons: vec![],
+            },
+        )
+        .await;
+        let id = QuoteBmc::create(
+            &mm,
+            QuoteForCreate {
+                customer_id: None,
+                title: "test_quote_create_with_options".to_string(),
+                items: test_items(),
+                valid_days: Some(30),
+                service_type: None,
+                discount: None,
+                promo_code: None,
+                notes: None,
+                template_id: None,
+                options,
+                add_ons,
+            },
+        )
+        .await?;
+
+        // Check
+        assert!(duplicate.is_err(), "Option names must be unique");
+        let quote = QuoteBmc::get(&mm, id).await?;
+        let options = quote.quote_options()?;
+        assert_eq!(quote.subtotal_cents, 3000 + 4500 + 500);
+        assert_eq!(quote.total_cents, options[0].total_cents);
+        assert!(options[1].total_cents > options[0].total_cents);
+        assert_eq!(quote.quote_add_ons()?.len(), 1);
+        assert_eq!(
+            quote.quote_selection()?,
+            QuoteSelection::default_for(&options)
+        );
+        assert_eq!(quote.selected_items()?.len(), 3);
+
+        // Cleanup
+        QuoteBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_quote_get_ok() -> Result<()> {
         // Setup
@@ -384,6 +1149,13 @@ mod tests {
             title: "Test Quote Get".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
         let id = QuoteBmc::create(&mm, quote).await?;
 
@@ -395,7 +1167,7 @@ mod tests {
         assert_eq!(quote.title, "Test Quote Get");
         assert_eq!(quote.status, "draft");
         assert_eq!(quote.subtotal_cents, 7500); // 3000 + 4500
-        assert_eq!(quote.total_cents, 7500);
+        assert_eq!(quote.total_cents, 7500 + quote.vat_cents);
 
         // Cleanup
         QuoteBmc::delete(&mm, id).await?;
@@ -418,6 +1190,61 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_quote_create_discounts_and_promo_ok() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let promo_id = PromoCodeBmc::create(
+            &mm,
+            PromoCodeForCreate {
+                code: "test_quote_promo".to_string(),
+                description: None,
+                discount: Discount::Percent { basis_points: 1000 },
+                valid_from: None,
+                valid_until: None,
+                max_uses: Some(1),
+                min_subtotal_cents: None,
+                service_types: vec!["plumbing".to_string()],
+            },
+        )
+        .await?;
+        let mut items = test_items();
+        items[0].discount = Some(Discount::Fixed { amount_cents: 1000 });
+        let fx_quote = |title: &str| QuoteForCreate {
+            customer_id: None,
+            title: title.to_string(),
+            items: items.clone(),
+            valid_days: Some(30),
+            service_type: Some("plumbing".to_string()),
+            discount: Some(Discount::Fixed { amount_cents: 500 }),
+            promo_code: Some("Test_Quote_Promo".to_string()),
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
+        };
+
+        // Execute
+        let id = QuoteBmc::create(&mm, fx_quote("Test Promo 1")).await?;
+        let second = QuoteBmc::create(&mm, fx_quote("Test Promo 2")).await;
+
+        // Check
+        let quote = QuoteBmc::get(&mm, id).await?;
+        // 7500 - 1000 line - 500 quote = 6000, then 10% promo = 600
+        assert_eq!(quote.subtotal_cents, 7500);
+        assert_eq!(quote.discount_cents, 2100);
+        assert_eq!(quote.total_cents, 5400 + quote.vat_cents);
+        assert_eq!(quote.promo_code_id, Some(promo_id));
+        assert!(second.is_err(), "Promo code usage limit should be enforced");
+        assert_eq!(PromoCodeBmc::get(&mm, promo_id).await?.uses, 1);
+
+        // Cleanup
+        QuoteBmc::delete(&mm, id).await?;
+        PromoCodeBmc::delete(&mm, promo_id).await?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_quote_list_ok() -> Result<()> {
         // Setup
@@ -429,12 +1256,26 @@ mod tests {
             title: "Test List Quote 1".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
         let quote2 = QuoteForCreate {
             customer_id: None,
             title: "Test List Quote 2".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
 
         let id1 = QuoteBmc::create(&mm, quote1).await?;
@@ -464,6 +1305,13 @@ mod tests {
             title: "Test Status Filter".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
         let id = QuoteBmc::create(&mm, quote).await?;
 
@@ -493,6 +1341,13 @@ mod tests {
             title: "Test Update Status".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
         let id = QuoteBmc::create(&mm, quote).await?;
 
@@ -520,6 +1375,13 @@ mod tests {
             title: "Test Send".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
         let id = QuoteBmc::create(&mm, quote).await?;
 
@@ -529,6 +1391,16 @@ mod tests {
         // Check
         let quote = QuoteBmc::get(&mm, id).await?;
         assert_eq!(quote.status, "sent");
+        QuoteBmc::update_status(&mm, id, "rejected").await?;
+        assert!(matches!(
+            QuoteBmc::send(&mm, id).await,
+            Err(Error::ValidationError(_))
+        ));
+        assert_eq!(QuoteBmc::get(&mm, id).await?.status, "rejected");
+        assert!(matches!(
+            QuoteBmc::send(&mm, i32::MAX).await,
+            Err(Error::EntityNotFound { .. })
+        ));
 
         // Cleanup
         QuoteBmc::delete(&mm, id).await?;
@@ -547,15 +1419,24 @@ mod tests {
             title: "Test Accept".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
         let id = QuoteBmc::create(&mm, quote).await?;
+        QuoteBmc::send(&mm, id).await?;
 
         // Execute
-        QuoteBmc::accept(&mm, id, None, Some("Looks good!")).await?;
+        QuoteBmc::accept(&mm, id, 1, None, Some("Looks good!")).await?;
 
         // Check
         let quote = QuoteBmc::get(&mm, id).await?;
         assert_eq!(quote.status, "accepted");
+        assert_eq!(quote.accepted_revision, Some(1));
         assert!(quote.accepted_at.is_some());
         assert_eq!(quote.customer_notes, Some("Looks good!".to_string()));
 
@@ -565,6 +1446,62 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_quote_update_sent_creates_revision() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let quote = QuoteForCreate {
+            customer_id: None,
+            title: "Test Revision".to_string(),
+            items: test_items(),
+            valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
+        };
+        let id = QuoteBmc::create(&mm, quote).await?;
+        QuoteBmc::send(&mm, id).await?;
+
+        // Execute
+        let mut items = test_items();
+        items[1].quantity = 2;
+        let updated = QuoteBmc::update(
+            &mm,
+            id,
+            QuoteForUpdate {
+                items: Some(items),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        // Check
+        assert_eq!(updated.revision, 2);
+        assert_eq!(updated.subtotal_cents, 12000);
+        let revisions = QuoteRevisionBmc::list(&mm, id).await?;
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].subtotal_cents, 7500, "Old revision is kept");
+        let changes = revisions[1].quote_changes().unwrap();
+        assert_eq!(changes.changed[0].after.quantity, 2);
+
+        // Check - accepting the superseded revision fails
+        assert!(QuoteBmc::accept(&mm, id, 1, None, None).await.is_err());
+        QuoteBmc::accept(&mm, id, 2, None, None).await?;
+
+        // Check - accepted quotes can no longer be edited
+        let res = QuoteBmc::update(&mm, id, QuoteForUpdate::default()).await;
+        assert!(res.is_err());
+
+        // Cleanup
+        QuoteBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_quote_reject() -> Result<()> {
         // Setup
@@ -576,6 +1513,13 @@ mod tests {
             title: "Test Reject".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
         let id = QuoteBmc::create(&mm, quote).await?;
 
@@ -604,6 +1548,13 @@ mod tests {
             title: "Test Delete".to_string(),
             items: test_items(),
             valid_days: Some(30),
+            service_type: None,
+            discount: None,
+            promo_code: None,
+            notes: None,
+            template_id: None,
+            options: vec![],
+            add_ons: vec![],
         };
         let id = QuoteBmc::create(&mm, quote).await?;
 
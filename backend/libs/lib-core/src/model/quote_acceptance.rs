@@ -0,0 +1,595 @@
+//! # Quote Acceptance Model
+//!
+//! Evidence that a customer accepted a quote, kept as an e-signature
+//! record: the signer's typed name, an optional drawn signature, when and
+//! from where they accepted, and a SHA-256 hash of the exact revision they
+//! agreed to (see [`QuoteRevision::content_hash`]).
+//!
+//! Records are immutable once written. They are printed on the accepted
+//! quote's PDF and included in the acceptance confirmation email.
+//!
+//! Accepting also records which option and add-ons the customer chose
+//! (see [`QuoteSelection`]), reprices the quote for that selection and
+//! opens a pending booking for the work.
+//!
+//! ## Structures
+//!
+//! - [`QuoteAcceptance`] - Stored acceptance record
+//! - [`QuoteAcceptanceForCreate`] - What the customer submitted, plus request details
+//! - [`QuoteAcceptanceBmc`] - Business Model Controller for acceptances
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::quote_acceptance::{QuoteAcceptanceBmc, QuoteAcceptanceForCreate};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn sign(mm: &ModelManager, quote_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+//!     let acceptance = QuoteAcceptanceBmc::record(
+//!         mm,
+//!         quote_id,
+//!         QuoteAcceptanceForCreate {
+//!             revision: 1,
+//!             signer_name: "Jane Smith".to_string(),
+//!             signature_image: None,
+//!             customer_notes: None,
+//!             ip_address: Some("203.0.113.7".to_string()),
+//!             user_agent: Some("Mozilla/5.0".to_string()),
+//!             selection: Default::default(),
+//!         },
+//!     )
+//!     .await?;
+//!     println!("Accepted revision {} ({})", acceptance.revision, acceptance.content_hash);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::document::pdf::JpegImage;
+use crate::model::promo_code::PromoCodeBmc;
+use crate::model::quote::{
+    describe_selection, not_acceptable, price_selection, to_json, Quote, QuoteSelection,
+    QUOTE_COLUMNS,
+};
+use crate::model::quote_revision::{QuoteRevision, QuoteRevisionBmc};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use lib_utils::b64::b64_decode;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Largest drawn signature accepted, in bytes of JPEG.
+const MAX_SIGNATURE_BYTES: usize = 200 * 1024;
+
+/// Longest user agent stored; anything after is cut off.
+const MAX_USER_AGENT_CHARS: usize = 512;
+
+/// Prefix of a drawn signature sent as a data URL.
+const SIGNATURE_DATA_URL_PREFIX: &str = "data:image/jpeg;base64,";
+
+/// Stored record of a customer accepting a quote.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct QuoteAcceptance {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Quote accepted
+    pub quote_id: i32,
+    /// Revision accepted
+    pub revision: i32,
+    /// Name the customer typed to sign
+    pub signer_name: String,
+    /// Drawn signature as a JPEG, if one was given
+    #[serde(skip)]
+    pub signature_jpeg: Option<Vec<u8>>,
+    /// IP address the acceptance came from
+    pub ip_address: Option<String>,
+    /// Browser user agent
+    pub user_agent: Option<String>,
+    /// Hex SHA-256 of the accepted revision's content
+    pub content_hash: String,
+    /// Notes the customer left when accepting
+    pub customer_notes: Option<String>,
+    /// [`QuoteSelection`] the customer chose
+    pub selection: serde_json::Value,
+    /// When the quote was accepted
+    pub accepted_at: OffsetDateTime,
+}
+
+impl QuoteAcceptance {
+    /// Whether `revision` is the content this acceptance was signed for.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the revision's items are malformed.
+    pub fn matches(&self, revision: &QuoteRevision) -> Result<bool> {
+        Ok(revision.quote_id == self.quote_id
+            && revision.revision == self.revision
+            && revision.content_hash()? == self.content_hash)
+    }
+
+    /// The option and add-ons the customer chose.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored selection is malformed.
+    pub fn quote_selection(&self) -> Result<QuoteSelection> {
+        serde_json::from_value(self.selection.clone())
+            .map_err(|e| Error::ValidationError(format!("Malformed selection: {e}").into()))
+    }
+}
+
+/// Data required to record an acceptance.
+#[derive(Debug, Clone)]
+pub struct QuoteAcceptanceForCreate {
+    /// Revision the customer was shown
+    pub revision: i32,
+    /// Name the customer typed to sign
+    pub signer_name: String,
+    /// Drawn signature as a `data:image/jpeg;base64,...` URL
+    pub signature_image: Option<String>,
+    /// Notes the customer left when accepting
+    pub customer_notes: Option<String>,
+    /// IP address of the request
+    pub ip_address: Option<String>,
+    /// `User-Agent` header of the request
+    pub user_agent: Option<String>,
+    /// Option and add-ons chosen (empty for a quote without choices)
+    pub selection: QuoteSelection,
+}
+
+/// Business Model Controller for quote acceptances.
+pub struct QuoteAcceptanceBmc;
+
+impl QuoteAcceptanceBmc {
+    /// Accepts a quote and records who signed for which content.
+    ///
+    /// In one transaction: checks the quote is waiting for a response at
+    /// `acceptance.revision` and the selection matches its options, hashes
+    /// that revision, reprices the quote for the selection (when it has
+    /// options or add-ons), marks it accepted, opens a pending booking if
+    /// it has none and stores the record.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `quote_id` - Quote being accepted
+    /// * `acceptance` - Signer details and request metadata
+    ///
+    /// # Returns
+    ///
+    /// The stored acceptance record.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the quote does not exist, or
+    /// `ValidationError` if the name is missing, the signature is not a
+    /// JPEG data URL, the selection is missing or unknown, the revision is
+    /// out of date or the quote is not waiting for a response.
+    #[instrument(skip(mm, acceptance), fields(revision = acceptance.revision))]
+    pub async fn record(
+        mm: &ModelManager,
+        quote_id: i32,
+        acceptance: QuoteAcceptanceForCreate,
+    ) -> Result<QuoteAcceptance> {
+        let signer_name = acceptance.signer_name.trim();
+        if signer_name.is_empty() {
+            return Err(Error::ValidationError(
+                "Please type your full name to sign".into(),
+            ));
+        }
+        if signer_name.chars().count() > 255 {
+            return Err(Error::ValidationError("Name is too long".into()));
+        }
+        let signature = acceptance
+            .signature_image
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .map(decode_signature)
+            .transpose()?;
+        let user_agent = acceptance
+            .user_agent
+            .map(|ua| ua.chars().take(MAX_USER_AGENT_CHARS).collect::<String>());
+
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        let quote: Quote = dbx
+            .fetch_optional(
+                sqlx::query_as(&format!(
+                    "SELECT {QUOTE_COLUMNS} FROM quotes WHERE id = $1 FOR UPDATE"
+                ))
+                .bind(quote_id),
+            )
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "Quote",
+                id: quote_id as i64,
+            })?;
+        if quote.revision != acceptance.revision
+            || !matches!(quote.status.as_str(), "sent" | "viewed")
+        {
+            return Err(not_acceptable(&quote, acceptance.revision));
+        }
+        // Reads go through the transaction too: with the quote row locked,
+        // a second pool connection could wait on this one
+        let selection = &acceptance.selection;
+        let breakdown = price_selection(&mm, &quote, selection).await?;
+        let content_hash = QuoteRevisionBmc::get(&mm, quote_id, acceptance.revision)
+            .await?
+            .content_hash()?;
+
+        let options = quote.quote_options()?;
+        let add_ons = quote.quote_add_ons()?;
+        if !options.is_empty() || !add_ons.is_empty() {
+            dbx.execute(
+                sqlx::query(
+                    r#"
+                    UPDATE quotes
+                    SET subtotal_cents = $2, discount_cents = $3, vat_cents = $4,
+                        vat_breakdown = $5, total_cents = $6
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(quote_id)
+                .bind(breakdown.subtotal_cents)
+                .bind(breakdown.discount_cents)
+                .bind(breakdown.vat_cents)
+                .bind(to_json(&breakdown.vat_bands)?)
+                .bind(breakdown.total_cents),
+            )
+            .await?;
+            if let Some(promo_code_id) = quote.promo_code_id {
+                let promo_cents = breakdown.order_discount_cents.last().copied().unwrap_or(0);
+                PromoCodeBmc::update_redemption(&mm, promo_code_id, quote_id, promo_cents).await?;
+            }
+        }
+
+        let booking_id = match quote.booking_id {
+            Some(booking_id) => booking_id,
+            None => {
+                let chosen = describe_selection(&options, &add_ons, selection);
+                let mut notes = format!("Accepted quote Q-{quote_id:06}");
+                if !chosen.is_empty() {
+                    notes = format!("{notes}: {chosen}");
+                }
+                let (booking_id,): (i32,) = dbx
+                    .fetch_one(
+                        sqlx::query_as(
+                            r#"
+                            INSERT INTO bookings (customer_id, service_type, quote_id, notes, status)
+                            VALUES ($1, $2, $3, $4, 'pending')
+                            RETURNING id
+                            "#,
+                        )
+                        .bind(quote.customer_id)
+                        .bind(quote.service_type.as_deref().unwrap_or("general"))
+                        .bind(quote_id)
+                        .bind(notes),
+                    )
+                    .await?;
+                booking_id
+            }
+        };
+
+        dbx.execute(
+            sqlx::query(
+                r#"
+                UPDATE quotes
+                SET status = 'accepted',
+                    accepted_at = CURRENT_TIMESTAMP,
+                    accepted_revision = $2,
+                    customer_notes = $3,
+                    selection = $4,
+                    booking_id = $5,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $1
+                "#,
+            )
+            .bind(quote_id)
+            .bind(acceptance.revision)
+            .bind(&acceptance.customer_notes)
+            .bind(to_json(selection)?)
+            .bind(booking_id),
+        )
+        .await?;
+
+        let record = dbx
+            .fetch_one(
+                sqlx::query_as(&format!(
+                    r#"
+                    INSERT INTO quote_acceptances (quote_id, revision, signer_name,
+                                                   signature_jpeg, ip_address, user_agent,
+                                                   content_hash, customer_notes, selection,
+                                                   accepted_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, CURRENT_TIMESTAMP)
+                    RETURNING {QUOTE_ACCEPTANCE_COLUMNS}
+                    "#
+                ))
+                .bind(quote_id)
+                .bind(acceptance.revision)
+                .bind(signer_name)
+                .bind(signature)
+                .bind(&acceptance.ip_address)
+                .bind(user_agent)
+                .bind(content_hash)
+                .bind(&acceptance.customer_notes)
+                .bind(to_json(selection)?),
+            )
+            .await?;
+
+        dbx.commit_txn().await?;
+
+        Ok(record)
+    }
+
+    /// Gets the acceptance record for a quote.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` (with the quote ID) if the quote has not
+    /// been signed for.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, quote_id: i32) -> Result<QuoteAcceptance> {
+        Self::get_for_quote(mm, quote_id)
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "QuoteAcceptance",
+                id: quote_id as i64,
+            })
+    }
+
+    /// Gets the acceptance record for a quote, if it has one.
+    #[instrument(skip(mm))]
+    pub async fn get_for_quote(
+        mm: &ModelManager,
+        quote_id: i32,
+    ) -> Result<Option<QuoteAcceptance>> {
+        let acceptance = sqlx::query_as(&format!(
+            "SELECT {QUOTE_ACCEPTANCE_COLUMNS} FROM quote_acceptances WHERE quote_id = $1"
+        ))
+        .bind(quote_id)
+        .fetch_optional(mm.dbx().db())
+        .await?;
+
+        Ok(acceptance)
+    }
+
+    /// Lists the acceptance records for a set of quotes, oldest first.
+    #[instrument(skip(mm))]
+    pub async fn list_for_quotes(
+        mm: &ModelManager,
+        quote_ids: &[i32],
+    ) -> Result<Vec<QuoteAcceptance>> {
+        let acceptances = sqlx::query_as(&format!(
+            "SELECT {QUOTE_ACCEPTANCE_COLUMNS} FROM quote_acceptances \
+             WHERE quote_id = ANY($1) ORDER BY id"
+        ))
+        .bind(quote_ids)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(acceptances)
+    }
+}
+
+const QUOTE_ACCEPTANCE_COLUMNS: &str = "id, quote_id, revision, signer_name, signature_jpeg, \
+                                        ip_address, user_agent, content_hash, customer_notes, \
+                                        selection, accepted_at";
+
+// region:    --- Quote Acceptance Helpers
+
+/// Decodes a drawn signature sent as a JPEG data URL.
+fn decode_signature(data_url: &str) -> Result<Vec<u8>> {
+    let invalid = || Error::ValidationError("Signature must be a JPEG image".into());
+
+    let encoded = data_url
+        .strip_prefix(SIGNATURE_DATA_URL_PREFIX)
+        .ok_or_else(invalid)?;
+    // Base64 is 4 characters per 3 bytes
+    if encoded.len() / 4 * 3 > MAX_SIGNATURE_BYTES {
+        return Err(Error::ValidationError(
+            "Signature image is too large".into(),
+        ));
+    }
+    let bytes = b64_decode(encoded).map_err(|_| invalid())?;
+
+    JpegImage::from_bytes(bytes)
+        .map(JpegImage::into_bytes)
+        .ok_or_else(invalid)
+}
+
+// endregion: --- Quote Acceptance Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::booking::BookingBmc;
+    use crate::model::quote::{QuoteBmc, QuoteForCreate, QuoteItem, QuoteOption};
+
+    /// 2x3 px JPEG header (SOI, empty APP0, SOF0), as a data URL.
+    const SIGNATURE: &str = "data:image/jpeg;base64,/9j/4AAC/8AAEQgAAwACAw==";
+
+    fn fx_acceptance(revision: i32, signer_name: &str) -> QuoteAcceptanceForCreate {
+        QuoteAcceptanceForCreate {
+            revision,
+            signer_name: signer_name.to_string(),
+            signature_image: Some(SIGNATURE.to_string()),
+            customer_notes: Some("Mornings are best".to_string()),
+            ip_address: Some("203.0.113.7".to_string()),
+            user_agent: Some("Mozilla/5.0 (test)".to_string()),
+            selection: QuoteSelection::default(),
+        }
+    }
+
+    fn fx_item(description: &str, unit_price: i32) -> QuoteItem {
+        QuoteItem {
+            description: description.to_string(),
+            quantity: 1,
+            unit_price,
+            discount: None,
+            vat_rate_bp: None,
+        }
+    }
+
+    #[test]
+    fn test_decode_signature() {
+        assert!(decode_signature(SIGNATURE).is_ok());
+        assert!(decode_signature("data:image/png;base64,iVBORw0KGgo=").is_err());
+        assert!(decode_signature("data:image/jpeg;base64,not base64").is_err());
+        // Valid base64, but not a JPEG
+        assert!(decode_signature("data:image/jpeg;base64,aGVsbG8=").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_quote_acceptance_record_ok() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let id = QuoteBmc::create(
+            &mm,
+            QuoteForCreate {
+                customer_id: None,
+                title: "test_quote_acceptance_record_ok".to_string(),
+                items: vec![QuoteItem {
+                    description: "Labour".to_string(),
+                    quantity: 1,
+                    unit_price: 4500,
+                    discount: None,
+                    vat_rate_bp: None,
+                }],
+                valid_days: Some(30),
+                service_type: None,
+                discount: None,
+                promo_code: None,
+                notes: None,
+                template_id: None,
+                options: vec![],
+                add_ons: vec![],
+            },
+        )
+        .await?;
+        QuoteBmc::send(&mm, id).await?;
+
+        // Execute
+        let missing_name = QuoteAcceptanceBmc::record(&mm, id, fx_acceptance(1, "  ")).await;
+        let stale = QuoteAcceptanceBmc::record(&mm, id, fx_acceptance(2, "Jane Smith")).await;
+        let acceptance =
+            QuoteAcceptanceBmc::record(&mm, id, fx_acceptance(1, " Jane Smith ")).await?;
+        let again = QuoteAcceptanceBmc::record(&mm, id, fx_acceptance(1, "Jane Smith")).await;
+
+        // Check
+        assert!(missing_name.is_err());
+        assert!(stale.is_err());
+        assert!(again.is_err(), "A quote can only be accepted once");
+        assert_eq!(acceptance.signer_name, "Jane Smith");
+        assert!(acceptance.signature_jpeg.is_some());
+        let revision = QuoteRevisionBmc::get(&mm, id, 1).await?;
+        assert!(acceptance.matches(&revision)?);
+        let quote = QuoteBmc::get(&mm, id).await?;
+        assert_eq!(quote.status, "accepted");
+        assert_eq!(quote.accepted_revision, Some(1));
+        let stored = QuoteAcceptanceBmc::get(&mm, id).await?;
+        assert_eq!(stored.content_hash, acceptance.content_hash);
+        assert_eq!(stored.accepted_at, acceptance.accepted_at);
+        let booking_id = quote.booking_id.expect("acceptance opens a booking");
+        let booking = BookingBmc::get(&mm, booking_id).await?;
+        assert_eq!(booking.status, "pending");
+        assert_eq!(booking.quote_id, Some(id));
+
+        // Cleanup - acceptances hold their quote until removed explicitly
+        sqlx::query("DELETE FROM quote_acceptances WHERE quote_id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?;
+        QuoteBmc::delete(&mm, id).await?;
+        BookingBmc::delete(&mm, booking_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_quote_acceptance_record_selection() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let option = |name: &str, price| QuoteOption {
+            name: name.to_string(),
+            description: None,
+            items: vec![fx_item(name, price)],
+            total_cents: 0,
+        };
+        let id = QuoteBmc::create(
+            &mm,
+            QuoteForCreate {
+                customer_id: None,
+                title: "test_quote_acceptance_record_selection".to_string(),
+                items: vec![fx_item("Call-out fee", 3000)],
+                valid_days: Some(30),
+                service_type: Some("plumbing".to_string()),
+                discount: None,
+                promo_code: None,
+                notes: None,
+                template_id: None,
+                options: vec![option("Repair tap", 2000), option("Replace mixer", 9000)],
+                add_ons: vec![fx_item("Isolation valve", 1500)],
+            },
+        )
+        .await?;
+        QuoteBmc::send(&mm, id).await?;
+        let default_total = QuoteBmc::get(&mm, id).await?.total_cents;
+        let select = |option, add_ons| QuoteAcceptanceForCreate {
+            selection: QuoteSelection { option, add_ons },
+            ..fx_acceptance(1, "Jane Smith")
+        };
+
+        // Execute
+        let no_option = QuoteAcceptanceBmc::record(&mm, id, select(None, vec![])).await;
+        let unknown = QuoteAcceptanceBmc::record(&mm, id, select(Some(2), vec![])).await;
+        let acceptance = QuoteAcceptanceBmc::record(&mm, id, select(Some(1), vec![0])).await?;
+
+        // Check
+        assert!(no_option.is_err());
+        assert!(unknown.is_err());
+        assert_eq!(
+            acceptance.quote_selection()?,
+            QuoteSelection {
+                option: Some(1),
+                add_ons: vec![0]
+            }
+        );
+        let quote = QuoteBmc::get(&mm, id).await?;
+        let descriptions: Vec<String> = quote
+            .selected_items()?
+            .into_iter()
+            .map(|item| item.description)
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec!["Call-out fee", "Replace mixer", "Isolation valve"]
+        );
+        assert!(quote.total_cents > default_total);
+        let booking_id = quote.booking_id.expect("acceptance opens a booking");
+        let booking = BookingBmc::get(&mm, booking_id).await?;
+        assert_eq!(booking.service_type, "plumbing");
+        assert_eq!(
+            booking.notes.as_deref(),
+            Some(format!("Accepted quote Q-{id:06}: Replace mixer, with Isolation valve").as_str())
+        );
+
+        // Cleanup
+        sqlx::query("DELETE FROM quote_acceptances WHERE quote_id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?;
+        QuoteBmc::delete(&mm, id).await?;
+        BookingBmc::delete(&mm, booking_id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,676 @@
+//! # Quote Revision Model
+//!
+//! Immutable snapshots of a quote as the customer saw it. The first
+//! revision is taken when a quote is sent; every edit after that adds a new
+//! revision along with a [`QuoteChanges`] diff against the one before, so
+//! the customer can see what changed and acceptance can name the exact
+//! revision accepted.
+//!
+//! ## Structures
+//!
+//! - [`QuoteRevision`] - Snapshot of a quote at one revision
+//! - [`QuoteChanges`] - Line item and total differences between revisions
+//! - [`QuoteRevisionBmc`] - Business Model Controller for reading revisions
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::quote_revision::QuoteRevisionBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn what_changed(mm: &ModelManager, quote_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+//!     let latest = QuoteRevisionBmc::latest(mm, quote_id).await?;
+//!     for line in latest.change_summary() {
+//!         println!("{line}");
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::document::{format_date, format_money};
+use crate::model::quote::{Quote, QuoteItem, QuoteOption};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Snapshot of a quote at one revision.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct QuoteRevision {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Quote this is a revision of
+    pub quote_id: i32,
+    /// Revision number, starting at 1
+    pub revision: i32,
+    /// Quote title
+    pub title: String,
+    /// JSON array of line items shared by every option
+    pub items: serde_json::Value,
+    /// JSON array of [`QuoteOption`]s
+    pub options: serde_json::Value,
+    /// JSON array of optional add-on items
+    pub add_ons: serde_json::Value,
+    /// Subtotal in cents
+    pub subtotal_cents: i32,
+    /// All discounts in cents
+    pub discount_cents: i32,
+    /// VAT in cents
+    pub vat_cents: i32,
+    /// VAT by rate
+    pub vat_breakdown: serde_json::Value,
+    /// Total including VAT in cents
+    pub total_cents: i32,
+    /// Notes printed on the quote
+    pub notes: Option<String>,
+    /// Quote expiry date
+    pub valid_until: Option<time::Date>,
+    /// [`QuoteChanges`] from the previous revision (`None` for the first)
+    pub changes: Option<serde_json::Value>,
+    /// When the revision was made
+    pub created_at: Option<OffsetDateTime>,
+}
+
+impl QuoteRevision {
+    /// Snapshot of a quote's current content (not yet stored).
+    pub(crate) fn from_quote(quote: &Quote) -> Self {
+        Self {
+            id: 0,
+            quote_id: quote.id,
+            revision: quote.revision,
+            title: quote.title.clone(),
+            items: quote.items.clone(),
+            options: quote.options.clone(),
+            add_ons: quote.add_ons.clone(),
+            subtotal_cents: quote.subtotal_cents,
+            discount_cents: quote.discount_cents,
+            vat_cents: quote.vat_cents,
+            vat_breakdown: quote.vat_breakdown.clone(),
+            total_cents: quote.total_cents,
+            notes: quote.notes.clone(),
+            valid_until: quote.valid_until,
+            changes: None,
+            created_at: None,
+        }
+    }
+
+    /// Line items in this revision.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored items are malformed.
+    pub fn quote_items(&self) -> Result<Vec<QuoteItem>> {
+        serde_json::from_value(self.items.clone())
+            .map_err(|e| Error::ValidationError(format!("Malformed line items: {e}").into()))
+    }
+
+    /// Options in this revision.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored options are malformed.
+    pub fn quote_options(&self) -> Result<Vec<QuoteOption>> {
+        serde_json::from_value(self.options.clone())
+            .map_err(|e| Error::ValidationError(format!("Malformed options: {e}").into()))
+    }
+
+    /// Add-ons in this revision.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored add-ons are malformed.
+    pub fn quote_add_ons(&self) -> Result<Vec<QuoteItem>> {
+        serde_json::from_value(self.add_ons.clone())
+            .map_err(|e| Error::ValidationError(format!("Malformed add-ons: {e}").into()))
+    }
+
+    /// What changed since the previous revision, if this is not the first.
+    #[must_use]
+    pub fn quote_changes(&self) -> Option<QuoteChanges> {
+        self.changes
+            .as_ref()
+            .and_then(|changes| serde_json::from_value(changes.clone()).ok())
+    }
+
+    /// Customer-facing "what changed" lines (empty for the first revision).
+    #[must_use]
+    pub fn change_summary(&self) -> Vec<String> {
+        self.quote_changes()
+            .map(|changes| changes.summary())
+            .unwrap_or_default()
+    }
+
+    /// Hex SHA-256 of what the customer is agreeing to: quote and revision
+    /// number, title, line items, options, add-ons, totals, VAT, notes and
+    /// expiry.
+    ///
+    /// Recorded when the quote is accepted, so the accepted terms can be
+    /// proven later by hashing the stored revision again.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored items are malformed.
+    pub fn content_hash(&self) -> Result<String> {
+        let content = HashedContent {
+            quote_id: self.quote_id,
+            revision: self.revision,
+            title: &self.title,
+            items: self.quote_items()?,
+            options: self.quote_options()?,
+            add_ons: self.quote_add_ons()?,
+            subtotal_cents: self.subtotal_cents,
+            discount_cents: self.discount_cents,
+            vat_cents: self.vat_cents,
+            vat_breakdown: &self.vat_breakdown,
+            total_cents: self.total_cents,
+            notes: self.notes.as_deref(),
+            valid_until: self.valid_until.map(|date| date.to_string()),
+        };
+        let json = serde_json::to_vec(&content)
+            .map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))?;
+
+        Ok(Sha256::digest(&json)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect())
+    }
+}
+
+/// Fields covered by [`QuoteRevision::content_hash`], in a fixed order.
+/// Object keys inside the JSON values serialise sorted, so the encoding is
+/// canonical. Options and add-ons are left out when there are none, so
+/// hashes of quotes without them are unchanged.
+#[derive(Serialize)]
+struct HashedContent<'a> {
+    quote_id: i32,
+    revision: i32,
+    title: &'a str,
+    items: Vec<QuoteItem>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    options: Vec<QuoteOption>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    add_ons: Vec<QuoteItem>,
+    subtotal_cents: i32,
+    discount_cents: i32,
+    vat_cents: i32,
+    vat_breakdown: &'a serde_json::Value,
+    total_cents: i32,
+    notes: Option<&'a str>,
+    valid_until: Option<String>,
+}
+
+/// A line item whose quantity, price or discount changed.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ItemChange {
+    /// The line as it was
+    pub before: QuoteItem,
+    /// The line as it is now
+    pub after: QuoteItem,
+}
+
+/// A total that changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TotalChange {
+    /// Subtotal, Discount, VAT or Total
+    pub label: String,
+    /// Previous amount in cents
+    pub before_cents: i32,
+    /// New amount in cents
+    pub after_cents: i32,
+}
+
+/// Differences between two revisions of a quote.
+///
+/// Lines are matched by description, so renaming a line shows as one line
+/// removed and another added.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct QuoteChanges {
+    /// Revision these changes are relative to
+    pub from_revision: i32,
+    /// Lines that are new
+    pub added: Vec<QuoteItem>,
+    /// Lines that were taken off
+    pub removed: Vec<QuoteItem>,
+    /// Lines with a different quantity, price, discount or VAT rate
+    pub changed: Vec<ItemChange>,
+    /// Totals that moved
+    pub totals: Vec<TotalChange>,
+    /// Option, add-on, title, notes or expiry changes, as sentences
+    pub other: Vec<String>,
+}
+
+impl QuoteChanges {
+    /// Whether the two revisions are the same.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.totals.is_empty()
+            && self.other.is_empty()
+    }
+
+    /// One line per change, for showing to the customer.
+    #[must_use]
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for item in &self.added {
+            lines.push(format!("Added: {}", describe_item(item)));
+        }
+        for item in &self.removed {
+            lines.push(format!("Removed: {}", describe_item(item)));
+        }
+        for change in &self.changed {
+            lines.push(format!(
+                "Changed: {} (was {})",
+                describe_item(&change.after),
+                terms(&change.before)
+            ));
+        }
+        lines.extend(self.other.iter().cloned());
+        for total in &self.totals {
+            lines.push(format!(
+                "{}: {} → {}",
+                total.label,
+                format_money(i64::from(total.before_cents)),
+                format_money(i64::from(total.after_cents))
+            ));
+        }
+        lines
+    }
+}
+
+/// Compares two revisions of the same quote.
+///
+/// # Errors
+///
+/// Returns `ValidationError` if either revision's items are malformed.
+pub fn diff(before: &QuoteRevision, after: &QuoteRevision) -> Result<QuoteChanges> {
+    let mut unmatched = before.quote_items()?;
+    let mut changes = QuoteChanges {
+        from_revision: before.revision,
+        ..Default::default()
+    };
+
+    for item in after.quote_items()? {
+        match unmatched
+            .iter()
+            .position(|old| old.description == item.description)
+        {
+            Some(i) => {
+                let old = unmatched.remove(i);
+                if !same_terms(&old, &item) {
+                    changes.changed.push(ItemChange {
+                        before: old,
+                        after: item,
+                    });
+                }
+            }
+            None => changes.added.push(item),
+        }
+    }
+    changes.removed = unmatched;
+
+    for (label, old, new) in [
+        ("Subtotal", before.subtotal_cents, after.subtotal_cents),
+        ("Discount", before.discount_cents, after.discount_cents),
+        ("VAT", before.vat_cents, after.vat_cents),
+        ("Total", before.total_cents, after.total_cents),
+    ] {
+        if old != new {
+            changes.totals.push(TotalChange {
+                label: label.to_string(),
+                before_cents: old,
+                after_cents: new,
+            });
+        }
+    }
+
+    let (old_options, new_options) = (before.quote_options()?, after.quote_options()?);
+    for option in &new_options {
+        match old_options.iter().find(|old| old.name == option.name) {
+            None => changes.other.push(format!(
+                "New option: {} ({})",
+                option.name,
+                format_money(i64::from(option.total_cents))
+            )),
+            Some(old) if !same_items(&old.items, &option.items) => changes.other.push(format!(
+                "Option \"{}\" changed: {} → {}",
+                option.name,
+                format_money(i64::from(old.total_cents)),
+                format_money(i64::from(option.total_cents))
+            )),
+            Some(_) => {}
+        }
+    }
+    for old in &old_options {
+        if !new_options.iter().any(|option| option.name == old.name) {
+            changes
+                .other
+                .push(format!("Option \"{}\" withdrawn", old.name));
+        }
+    }
+    if !same_items(&before.quote_add_ons()?, &after.quote_add_ons()?) {
+        changes.other.push("Optional extras updated".to_string());
+    }
+
+    if before.title != after.title {
+        changes
+            .other
+            .push(format!("Title changed to \"{}\"", after.title));
+    }
+    if before.notes != after.notes {
+        changes.other.push("Notes updated".to_string());
+    }
+    if before.valid_until != after.valid_until {
+        if let Some(date) = after.valid_until {
+            changes
+                .other
+                .push(format!("Now valid until {}", format_date(date)));
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Business Model Controller for reading quote revisions.
+///
+/// Revisions are written by [`crate::model::quote::QuoteBmc`] when a quote
+/// is sent or edited, and never updated.
+pub struct QuoteRevisionBmc;
+
+impl QuoteRevisionBmc {
+    /// Lists a quote's revisions, oldest first.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, quote_id: i32) -> Result<Vec<QuoteRevision>> {
+        let revisions = mm
+            .dbx()
+            .fetch_all(
+                sqlx::query_as(&format!(
+                    "SELECT {QUOTE_REVISION_COLUMNS} FROM quote_revisions \
+                     WHERE quote_id = $1 ORDER BY revision"
+                ))
+                .bind(quote_id),
+            )
+            .await?;
+
+        Ok(revisions)
+    }
+
+    /// Gets one revision of a quote.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` (with the quote ID) if there is no such
+    /// revision.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, quote_id: i32, revision: i32) -> Result<QuoteRevision> {
+        mm.dbx()
+            .fetch_optional(
+                sqlx::query_as(&format!(
+                    "SELECT {QUOTE_REVISION_COLUMNS} FROM quote_revisions \
+                     WHERE quote_id = $1 AND revision = $2"
+                ))
+                .bind(quote_id)
+                .bind(revision),
+            )
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "QuoteRevision",
+                id: quote_id as i64,
+            })
+    }
+
+    /// Gets the newest revision of a quote.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the quote has never been sent.
+    #[instrument(skip(mm))]
+    pub async fn latest(mm: &ModelManager, quote_id: i32) -> Result<QuoteRevision> {
+        mm.dbx()
+            .fetch_optional(
+                sqlx::query_as(&format!(
+                    "SELECT {QUOTE_REVISION_COLUMNS} FROM quote_revisions \
+                     WHERE quote_id = $1 ORDER BY revision DESC LIMIT 1"
+                ))
+                .bind(quote_id),
+            )
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "QuoteRevision",
+                id: quote_id as i64,
+            })
+    }
+
+    /// Stores the quote's current content as revision `quote.revision`,
+    /// with the `changes` since the previous revision. Does nothing if that
+    /// revision already exists. Runs in the caller's transaction.
+    pub(crate) async fn snapshot(
+        mm: &ModelManager,
+        quote: &Quote,
+        changes: Option<&QuoteChanges>,
+    ) -> Result<()> {
+        let snapshot = QuoteRevision::from_quote(quote);
+        let changes = changes
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))?;
+
+        mm.dbx()
+            .execute(
+                sqlx::query(
+                    r#"
+                    INSERT INTO quote_revisions (quote_id, revision, title, items,
+                                                 subtotal_cents, discount_cents, vat_cents,
+                                                 vat_breakdown, total_cents, notes,
+                                                 valid_until, changes, options, add_ons)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                    ON CONFLICT (quote_id, revision) DO NOTHING
+                    "#,
+                )
+                .bind(snapshot.quote_id)
+                .bind(snapshot.revision)
+                .bind(&snapshot.title)
+                .bind(&snapshot.items)
+                .bind(snapshot.subtotal_cents)
+                .bind(snapshot.discount_cents)
+                .bind(snapshot.vat_cents)
+                .bind(&snapshot.vat_breakdown)
+                .bind(snapshot.total_cents)
+                .bind(&snapshot.notes)
+                .bind(snapshot.valid_until)
+                .bind(&changes)
+                .bind(&snapshot.options)
+                .bind(&snapshot.add_ons),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+const QUOTE_REVISION_COLUMNS: &str = "id, quote_id, revision, title, items, options, add_ons, \
+                                      subtotal_cents, discount_cents, vat_cents, vat_breakdown, \
+                                      total_cents, notes, valid_until, changes, created_at";
+
+// region:    --- Quote Revision Helpers
+
+fn same_terms(a: &QuoteItem, b: &QuoteItem) -> bool {
+    a.quantity == b.quantity
+        && a.unit_price == b.unit_price
+        && a.discount == b.discount
+        && a.vat_rate_bp == b.vat_rate_bp
+}
+
+fn same_items(a: &[QuoteItem], b: &[QuoteItem]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.description == b.description && same_terms(a, b))
+}
+
+/// `Labour (1 hour), 2 × £45.00`
+fn describe_item(item: &QuoteItem) -> String {
+    format!("{}, {}", item.description, terms(item))
+}
+
+/// `2 × £45.00`
+fn terms(item: &QuoteItem) -> String {
+    format!(
+        "{} × {}",
+        item.quantity,
+        format_money(i64::from(item.unit_price))
+    )
+}
+
+// endregion: --- Quote Revision Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fx_revision(revision: i32, items: serde_json::Value, total_cents: i32) -> QuoteRevision {
+        QuoteRevision {
+            id: revision,
+            quote_id: 1,
+            revision,
+            title: "Bathroom tap".to_string(),
+            items,
+            options: json!([]),
+            add_ons: json!([]),
+            subtotal_cents: total_cents,
+            discount_cents: 0,
+            vat_cents: 0,
+            vat_breakdown: json!([]),
+            total_cents,
+            notes: None,
+            valid_until: None,
+            changes: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_items_and_totals() -> Result<()> {
+        // -- Setup & Fixtures
+        let before = fx_revision(
+            1,
+            json!([
+                {"description": "Call-out fee", "quantity": 1, "unit_price": 3000},
+                {"description": "Labour", "quantity": 1, "unit_price": 4500},
+                {"description": "Washer", "quantity": 1, "unit_price": 200}
+            ]),
+            7700,
+        );
+        let after = fx_revision(
+            2,
+            json!([
+                {"description": "Call-out fee", "quantity": 1, "unit_price": 3000},
+                {"description": "Labour", "quantity": 2, "unit_price": 4500},
+                {"description": "New tap", "quantity": 1, "unit_price": 3500}
+            ]),
+            15500,
+        );
+
+        // -- Exec
+        let changes = diff(&before, &after)?;
+
+        // -- Check
+        assert_eq!(changes.from_revision, 1);
+        assert_eq!(changes.added[0].description, "New tap");
+        assert_eq!(changes.removed[0].description, "Washer");
+        assert_eq!(changes.changed.len(), 1);
+        assert_eq!(changes.changed[0].after.quantity, 2);
+        assert_eq!(changes.totals.len(), 2); // Subtotal and Total
+        assert_eq!(
+            changes.summary(),
+            vec![
+                "Added: New tap, 1 × £35.00",
+                "Removed: Washer, 1 × £2.00",
+                "Changed: Labour, 2 × £45.00 (was 1 × £45.00)",
+                "Subtotal: £77.00 → £155.00",
+                "Total: £77.00 → £155.00",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_identical_is_empty() -> Result<()> {
+        let items = json!([{"description": "Labour", "quantity": 1, "unit_price": 4500}]);
+        let changes = diff(
+            &fx_revision(1, items.clone(), 4500),
+            &fx_revision(2, items, 4500),
+        )?;
+
+        assert!(changes.is_empty());
+        assert!(changes.summary().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_options() -> Result<()> {
+        let items = json!([{"description": "Call-out fee", "quantity": 1, "unit_price": 3000}]);
+        let mut before = fx_revision(1, items.clone(), 3000);
+        before.options = json!([
+            {"name": "Repair tap", "items": [{"description": "Washer", "quantity": 1, "unit_price": 200}], "total_cents": 3200},
+            {"name": "Replace tap", "items": [{"description": "New tap", "quantity": 1, "unit_price": 3500}], "total_cents": 6500}
+        ]);
+        let mut after = fx_revision(2, items, 3000);
+        after.options = json!([
+            {"name": "Repair tap", "items": [{"description": "Washer", "quantity": 2, "unit_price": 200}], "total_cents": 3400},
+            {"name": "Replace mixer", "items": [{"description": "Mixer", "quantity": 1, "unit_price": 9000}], "total_cents": 12000}
+        ]);
+        after.add_ons =
+            json!([{"description": "Isolation valve", "quantity": 1, "unit_price": 1500}]);
+
+        let changes = diff(&before, &after)?;
+
+        assert_eq!(
+            changes.other,
+            vec![
+                "Option \"Repair tap\" changed: £32.00 → £34.00",
+                "New option: Replace mixer (£120.00)",
+                "Option \"Replace tap\" withdrawn",
+                "Optional extras updated",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_hash_covers_content() -> Result<()> {
+        let items = json!([{"description": "Labour", "quantity": 1, "unit_price": 4500}]);
+        let revision = fx_revision(1, items.clone(), 4500);
+        let hash = revision.content_hash()?;
+
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(hash, fx_revision(1, items.clone(), 4500).content_hash()?);
+        // Storage metadata is not part of the content
+        let mut stored = fx_revision(1, items.clone(), 4500);
+        stored.id = 99;
+        assert_eq!(hash, stored.content_hash()?);
+        // Any change to the terms changes the hash
+        assert_ne!(hash, fx_revision(2, items.clone(), 4500).content_hash()?);
+        assert_ne!(hash, fx_revision(1, items.clone(), 4600).content_hash()?);
+        let mut with_add_on = fx_revision(1, items.clone(), 4500);
+        with_add_on.add_ons = items;
+        assert_ne!(hash, with_add_on.content_hash()?);
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,673 @@
+//! # Quote Template Model
+//!
+//! Reusable quotes for common jobs. A template's items may take their
+//! quantity from a variable (hours of labour, number of doors) that the
+//! admin fills in when creating a quote from it.
+//!
+//! ## Variables
+//!
+//! Each [`TemplateVariable`] has a default and optional bounds. An item with
+//! a `quantity_variable` has its quantity multiplied by that variable's
+//! value, and `{name}` placeholders in item descriptions are replaced with
+//! the value:
+//!
+//! ```text
+//! {"description": "Labour ({hours} hours)", "quantity": 1,
+//!  "quantity_variable": "hours", "unit_price": 4500}
+//!   + hours = 3  ─▶  Labour (3 hours) × 3 @ £45.00
+//! ```
+//!
+//! ## Structures
+//!
+//! - [`QuoteTemplate`] - Complete template record from database
+//! - [`TemplateItem`] - Line item in a template
+//! - [`TemplateVariable`] - Value filled in when creating a quote
+//! - [`QuoteTemplateForCreate`] - Data required to create a new template
+//! - [`QuoteTemplateForUpdate`] - Data for updating an existing template
+//! - [`QuoteFromTemplate`] - Data for creating a quote from a template
+//! - [`QuoteTemplateBmc`] - Business Model Controller for template operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::quote_template::{QuoteFromTemplate, QuoteTemplateBmc};
+//! use lib_core::model::ModelManager;
+//! use std::collections::HashMap;
+//!
+//! async fn quote_tap_repair(mm: &ModelManager) -> Result<i32, Box<dyn std::error::Error>> {
+//!     let request = QuoteFromTemplate {
+//!         customer_id: Some(1),
+//!         title: None,
+//!         values: HashMap::from([("hours".to_string(), 2)]),
+//!         valid_days: None,
+//!         notes: None,
+//!         discount: None,
+//!         promo_code: None,
+//!     };
+//!     let quote_id = QuoteTemplateBmc::create_quote(mm, 1, request).await?;
+//!     Ok(quote_id)
+//! }
+//! ```
+
+use crate::model::quote::{QuoteBmc, QuoteForCreate, QuoteItem};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::pricing::Discount;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Columns selected for [`QuoteTemplate`].
+const QUOTE_TEMPLATE_COLUMNS: &str = "id, name, service_type, items, variables, valid_days, \
+     notes, active, created_at, updated_at";
+
+/// Complete quote template record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct QuoteTemplate {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Template name, e.g. "Leaky Tap Repair"
+    pub name: String,
+    /// Service the template is for
+    pub service_type: String,
+    /// JSON array of [`TemplateItem`]s
+    pub items: serde_json::Value,
+    /// JSON array of [`TemplateVariable`]s
+    pub variables: serde_json::Value,
+    /// Default days until quotes from this template expire
+    pub valid_days: i32,
+    /// Notes copied onto quotes
+    pub notes: Option<String>,
+    /// Whether the template is offered
+    pub active: bool,
+    /// When the template was created
+    pub created_at: Option<OffsetDateTime>,
+    /// When the template was last updated
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+/// Line item in a template.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TemplateItem {
+    /// Description, may contain `{variable}` placeholders
+    pub description: String,
+    /// Quantity (multiplied by `quantity_variable` when set)
+    pub quantity: i32,
+    /// Variable the quantity scales with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_variable: Option<String>,
+    /// Unit price in cents
+    pub unit_price: i32,
+    /// Discount on this line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discount: Option<Discount>,
+    /// VAT rate for this line in basis points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vat_rate_bp: Option<i32>,
+}
+
+/// A value the admin fills in when creating a quote from a template.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TemplateVariable {
+    /// Key used in `quantity_variable` and `{placeholders}`
+    pub name: String,
+    /// Label shown in the admin form
+    pub label: String,
+    /// Value used when none is given
+    pub default: i32,
+    /// Smallest allowed value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<i32>,
+    /// Largest allowed value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<i32>,
+}
+
+impl QuoteTemplate {
+    /// Template line items.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored items are malformed.
+    pub fn template_items(&self) -> Result<Vec<TemplateItem>> {
+        parse(&self.items, "items")
+    }
+
+    /// Template variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored variables are malformed.
+    pub fn template_variables(&self) -> Result<Vec<TemplateVariable>> {
+        parse(&self.variables, "variables")
+    }
+
+    /// Builds quote line items with the given variable values.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for unknown variables or values outside a
+    /// variable's bounds.
+    pub fn instantiate(&self, values: &HashMap<String, i32>) -> Result<Vec<QuoteItem>> {
+        instantiate(&self.template_items()?, &self.template_variables()?, values)
+    }
+}
+
+/// Data required to create a new quote template.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct QuoteTemplateForCreate {
+    /// Template name
+    pub name: String,
+    /// Service the template is for
+    pub service_type: String,
+    /// Line items
+    pub items: Vec<TemplateItem>,
+    /// Variables used by the items
+    #[serde(default)]
+    pub variables: Vec<TemplateVariable>,
+    /// Days until quotes expire (default 30)
+    pub valid_days: Option<i32>,
+    /// Notes copied onto quotes
+    pub notes: Option<String>,
+}
+
+/// Data for updating an existing quote template. Omitted fields are left
+/// unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct QuoteTemplateForUpdate {
+    /// New name
+    pub name: Option<String>,
+    /// New service type
+    pub service_type: Option<String>,
+    /// New line items
+    pub items: Option<Vec<TemplateItem>>,
+    /// New variables
+    pub variables: Option<Vec<TemplateVariable>>,
+    /// New default validity in days
+    pub valid_days: Option<i32>,
+    /// New notes
+    pub notes: Option<String>,
+    /// Offer or retire the template
+    pub active: Option<bool>,
+}
+
+/// Data for creating a draft quote from a template.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct QuoteFromTemplate {
+    /// Customer the quote is for
+    pub customer_id: Option<i32>,
+    /// Quote title (defaults to the template name)
+    pub title: Option<String>,
+    /// Variable values; missing variables use their defaults
+    #[serde(default)]
+    pub values: HashMap<String, i32>,
+    /// Days until expiry (defaults to the template's)
+    pub valid_days: Option<i32>,
+    /// Notes (defaults to the template's)
+    pub notes: Option<String>,
+    /// Quote-level discount
+    pub discount: Option<Discount>,
+    /// Promo code to redeem
+    pub promo_code: Option<String>,
+}
+
+/// Business Model Controller for quote template operations.
+pub struct QuoteTemplateBmc;
+
+impl QuoteTemplateBmc {
+    /// Creates a new quote template.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `template` - Template data to insert
+    ///
+    /// # Returns
+    ///
+    /// The auto-generated ID of the new template.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the items refer to undeclared variables.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm), fields(name = %template.name))]
+    pub async fn create(mm: &ModelManager, template: QuoteTemplateForCreate) -> Result<i32> {
+        validate(&template.items, &template.variables)?;
+
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO quote_templates (name, service_type, items, variables, valid_days, notes)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(&template.name)
+        .bind(&template.service_type)
+        .bind(to_json(&template.items)?)
+        .bind(to_json(&template.variables)?)
+        .bind(template.valid_days.unwrap_or(30))
+        .bind(&template.notes)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Gets a quote template by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the template does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<QuoteTemplate> {
+        sqlx::query_as(&format!(
+            "SELECT {QUOTE_TEMPLATE_COLUMNS} FROM quote_templates WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "QuoteTemplate",
+            id: id as i64,
+        })
+    }
+
+    /// Lists templates by name, optionally only active ones.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, active_only: bool) -> Result<Vec<QuoteTemplate>> {
+        let templates = sqlx::query_as(&format!(
+            "SELECT {QUOTE_TEMPLATE_COLUMNS} FROM quote_templates \
+             WHERE active OR NOT $1 ORDER BY service_type, name"
+        ))
+        .bind(active_only)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(templates)
+    }
+
+    /// Updates a quote template.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the template does not exist, or
+    /// `ValidationError` if the resulting items refer to undeclared
+    /// variables.
+    #[instrument(skip(mm))]
+    pub async fn update(mm: &ModelManager, id: i32, data: QuoteTemplateForUpdate) -> Result<()> {
+        let current = Self::get(mm, id).await?;
+        let items = match &data.items {
+            Some(items) => items.clone(),
+            None => current.template_items()?,
+        };
+        let variables = match &data.variables {
+            Some(variables) => variables.clone(),
+            None => current.template_variables()?,
+        };
+        validate(&items, &variables)?;
+
+        sqlx::query(
+            r#"
+            UPDATE quote_templates
+            SET name = COALESCE($2, name),
+                service_type = COALESCE($3, service_type),
+                items = $4,
+                variables = $5,
+                valid_days = COALESCE($6, valid_days),
+                notes = COALESCE($7, notes),
+                active = COALESCE($8, active)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(&data.name)
+        .bind(&data.service_type)
+        .bind(to_json(&items)?)
+        .bind(to_json(&variables)?)
+        .bind(data.valid_days)
+        .bind(&data.notes)
+        .bind(data.active)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes a quote template. Quotes created from it keep their items.
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM quote_templates WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "QuoteTemplate",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Creates a draft quote from a template.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `id` - Template to use
+    /// * `request` - Customer, variable values and overrides
+    ///
+    /// # Returns
+    ///
+    /// The ID of the new draft quote.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the template does not exist, or
+    /// `ValidationError` if it is inactive, a variable value is invalid or
+    /// the promo code cannot be used.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm))]
+    pub async fn create_quote(
+        mm: &ModelManager,
+        id: i32,
+        request: QuoteFromTemplate,
+    ) -> Result<i32> {
+        let template = Self::get(mm, id).await?;
+        if !template.active {
+            return Err(Error::ValidationError(
+                format!("Template '{}' is no longer active", template.name).into(),
+            ));
+        }
+        let items = template.instantiate(&request.values)?;
+
+        QuoteBmc::create(
+            mm,
+            QuoteForCreate {
+                customer_id: request.customer_id,
+                title: request.title.unwrap_or_else(|| template.name.clone()),
+                items,
+                valid_days: Some(request.valid_days.unwrap_or(template.valid_days)),
+                service_type: Some(template.service_type),
+                discount: request.discount,
+                promo_code: request.promo_code,
+                notes: request.notes.or(template.notes),
+                template_id: Some(template.id),
+                options: vec![],
+                add_ons: vec![],
+            },
+        )
+        .await
+    }
+}
+
+// region:    --- Template Helpers
+
+/// Fills in variable values and builds quote line items.
+fn instantiate(
+    items: &[TemplateItem],
+    variables: &[TemplateVariable],
+    values: &HashMap<String, i32>,
+) -> Result<Vec<QuoteItem>> {
+    if let Some(unknown) = values
+        .keys()
+        .find(|name| !variables.iter().any(|v| &v.name == *name))
+    {
+        return Err(Error::ValidationError(
+            format!("Unknown template variable '{unknown}'").into(),
+        ));
+    }
+
+    let mut resolved = HashMap::with_capacity(variables.len());
+    for variable in variables {
+        let value = values
+            .get(&variable.name)
+            .copied()
+            .unwrap_or(variable.default);
+        if variable.min.is_some_and(|min| value < min)
+            || variable.max.is_some_and(|max| value > max)
+        {
+            return Err(Error::ValidationError(
+                format!(
+                    "{} must be between {} and {}",
+                    variable.label,
+                    variable.min.map_or("-".to_string(), |v| v.to_string()),
+                    variable.max.map_or("-".to_string(), |v| v.to_string()),
+                )
+                .into(),
+            ));
+        }
+        resolved.insert(variable.name.as_str(), value);
+    }
+
+    items
+        .iter()
+        .map(|item| {
+            let quantity = match &item.quantity_variable {
+                Some(name) => {
+                    let value = resolved.get(name.as_str()).copied().ok_or_else(|| {
+                        Error::ValidationError(format!("Unknown template variable '{name}'").into())
+                    })?;
+                    item.quantity.checked_mul(value).ok_or_else(|| {
+                        Error::ValidationError("Template quantity is too large".into())
+                    })?
+                }
+                None => item.quantity,
+            };
+            let description = resolved
+                .iter()
+                .fold(item.description.clone(), |text, (name, value)| {
+                    text.replace(&format!("{{{name}}}"), &value.to_string())
+                });
+
+            Ok(QuoteItem {
+                description,
+                quantity,
+                unit_price: item.unit_price,
+                discount: item.discount,
+                vat_rate_bp: item.vat_rate_bp,
+            })
+        })
+        .collect()
+}
+
+/// Checks items only refer to declared variables and names are unique.
+fn validate(items: &[TemplateItem], variables: &[TemplateVariable]) -> Result<()> {
+    for (i, variable) in variables.iter().enumerate() {
+        if variables[..i].iter().any(|v| v.name == variable.name) {
+            return Err(Error::ValidationError(
+                format!("Template variable '{}' is declared twice", variable.name).into(),
+            ));
+        }
+    }
+    for item in items {
+        if let Some(name) = &item.quantity_variable {
+            if !variables.iter().any(|v| &v.name == name) {
+                return Err(Error::ValidationError(
+                    format!("Template variable '{name}' is not declared").into(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn parse<T: serde::de::DeserializeOwned>(value: &serde_json::Value, what: &str) -> Result<T> {
+    serde_json::from_value(value.clone())
+        .map_err(|e| Error::ValidationError(format!("Malformed template {what}: {e}").into()))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<serde_json::Value> {
+    serde_json::to_value(value).map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))
+}
+
+// endregion: --- Template Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+
+    fn fx_items() -> Vec<TemplateItem> {
+        vec![
+            TemplateItem {
+                description: "Call-out fee".to_string(),
+                quantity: 1,
+                quantity_variable: None,
+                unit_price: 3000,
+                discount: None,
+                vat_rate_bp: None,
+            },
+            TemplateItem {
+                description: "Labour ({hours} hours)".to_string(),
+                quantity: 1,
+                quantity_variable: Some("hours".to_string()),
+                unit_price: 4500,
+                discount: None,
+                vat_rate_bp: None,
+            },
+        ]
+    }
+
+    fn fx_variables() -> Vec<TemplateVariable> {
+        vec![TemplateVariable {
+            name: "hours".to_string(),
+            label: "Hours of labour".to_string(),
+            default: 1,
+            min: Some(1),
+            max: Some(8),
+        }]
+    }
+
+    #[test]
+    fn test_instantiate_defaults_and_values() {
+        let defaults = instantiate(&fx_items(), &fx_variables(), &HashMap::new()).unwrap();
+        let three = instantiate(
+            &fx_items(),
+            &fx_variables(),
+            &HashMap::from([("hours".to_string(), 3)]),
+        )
+        .unwrap();
+
+        assert_eq!(defaults[1].quantity, 1);
+        assert_eq!(defaults[1].description, "Labour (1 hours)");
+        assert_eq!(three[0].quantity, 1);
+        assert_eq!(three[1].quantity, 3);
+        assert_eq!(three[1].description, "Labour (3 hours)");
+    }
+
+    #[test]
+    fn test_instantiate_err_invalid_values() {
+        let out_of_range = HashMap::from([("hours".to_string(), 9)]);
+        let unknown = HashMap::from([("doors".to_string(), 1)]);
+
+        assert!(instantiate(&fx_items(), &fx_variables(), &out_of_range).is_err());
+        assert!(instantiate(&fx_items(), &fx_variables(), &unknown).is_err());
+    }
+
+    #[test]
+    fn test_validate_err_undeclared_variable() {
+        assert!(validate(&fx_items(), &[]).is_err());
+        assert!(validate(&fx_items(), &fx_variables()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_template_create_quote_ok() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let template_id = QuoteTemplateBmc::create(
+            &mm,
+            QuoteTemplateForCreate {
+                name: "test_template_create_quote_ok".to_string(),
+                service_type: "plumbing".to_string(),
+                items: fx_items(),
+                variables: fx_variables(),
+                valid_days: Some(14),
+                notes: Some("Price includes disposal of old parts".to_string()),
+            },
+        )
+        .await?;
+
+        // Execute
+        let quote_id = QuoteTemplateBmc::create_quote(
+            &mm,
+            template_id,
+            QuoteFromTemplate {
+                values: HashMap::from([("hours".to_string(), 2)]),
+                ..QuoteFromTemplate::default()
+            },
+        )
+        .await?;
+
+        // Check
+        let quote = QuoteBmc::get(&mm, quote_id).await?;
+        assert_eq!(quote.title, "test_template_create_quote_ok");
+        assert_eq!(quote.subtotal_cents, 3000 + 2 * 4500);
+        assert_eq!(quote.template_id, Some(template_id));
+        assert_eq!(
+            quote.notes.as_deref(),
+            Some("Price includes disposal of old parts")
+        );
+        assert_eq!(quote.service_type.as_deref(), Some("plumbing"));
+
+        // Cleanup
+        QuoteBmc::delete(&mm, quote_id).await?;
+        QuoteTemplateBmc::delete(&mm, template_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_template_update_ok() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let id = QuoteTemplateBmc::create(
+            &mm,
+            QuoteTemplateForCreate {
+                name: "test_template_update_ok".to_string(),
+                service_type: "plumbing".to_string(),
+                items: fx_items(),
+                variables: fx_variables(),
+                valid_days: None,
+                notes: None,
+            },
+        )
+        .await?;
+
+        // Execute
+        QuoteTemplateBmc::update(
+            &mm,
+            id,
+            QuoteTemplateForUpdate {
+                valid_days: Some(7),
+                active: Some(false),
+                ..QuoteTemplateForUpdate::default()
+            },
+        )
+        .await?;
+
+        // Check
+        let template = QuoteTemplateBmc::get(&mm, id).await?;
+        assert_eq!(template.valid_days, 7);
+        assert!(!template.active);
+        assert_eq!(template.template_items()?, fx_items());
+        let res = QuoteTemplateBmc::create_quote(&mm, id, QuoteFromTemplate::default()).await;
+        assert!(res.is_err(), "Inactive templates should not create quotes");
+
+        // Cleanup
+        QuoteTemplateBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
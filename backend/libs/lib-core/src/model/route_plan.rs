@@ -0,0 +1,518 @@
+//! # Route Plan Model
+//!
+//! Each technician's jobs for a day, in the order to drive them.
+//!
+//! A technician's route covers the live (not cancelled) bookings they are
+//! assigned to on the day. Jobs with a scheduled time are appointments and
+//! are kept; the rest are fitted around them to keep driving short (see
+//! [`crate::routing`]). The day starts at the technician's base if they
+//! have one, otherwise at the first job. Jobs without site coordinates
+//! cannot be placed and are listed as unrouted.
+//!
+//! Routes can be rendered as a GeoJSON `FeatureCollection` for the map:
+//! a point per base and stop, a line per route, and a geometry-less
+//! feature per unrouted job.
+//!
+//! ## Structures
+//!
+//! - [`RouteStop`] - A job in visiting order, with estimated times
+//! - [`TechnicianRoute`] - One technician's route for the day
+//! - [`RoutePlanBmc`] - Business Model Controller for route planning
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::route_plan::{to_geojson, RoutePlanBmc};
+//! use lib_core::model::ModelManager;
+//!
+//! async fn map(mm: &ModelManager) -> Result<(), Box<dyn std::error::Error>> {
+//!     let routes = RoutePlanBmc::plan_day(mm, "2026-11-02", None, None).await?;
+//!     println!("{}", to_geojson(&routes));
+//!     Ok(())
+//! }
+//! ```
+
+use crate::model::booking::parse_time;
+use crate::model::margin::parse_date;
+use crate::model::technician::{TechnicianBmc, DEFAULT_JOB_MINUTES};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::routing::{self, Point, Stop};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::FromRow;
+use time::{Date, Time};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// When the working day starts if the caller does not say.
+pub const DEFAULT_DAY_START: &str = "08:00";
+
+/// A job in visiting order, with estimated times (`HH:MM`, running past
+/// `24:00` if the day overruns).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RouteStop {
+    /// Position in the route, from 1
+    pub sequence: i32,
+    /// Booking ID
+    pub booking_id: i32,
+    /// Service type
+    pub service_type: String,
+    /// Site postcode
+    pub postcode: Option<String>,
+    /// Site latitude
+    pub latitude: f64,
+    /// Site longitude
+    pub longitude: f64,
+    /// Appointment time given to the customer, if any
+    pub appointment: Option<String>,
+    /// Estimated arrival
+    pub arrival: String,
+    /// When work starts (after waiting for an appointment)
+    pub start: String,
+    /// When the technician leaves
+    pub departure: String,
+    /// Road distance from the previous stop or base, km
+    pub drive_km: f64,
+    /// Minutes late for the appointment
+    pub late_minutes: i32,
+}
+
+/// One technician's route for a day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TechnicianRoute {
+    /// Technician's user ID
+    pub user_id: i32,
+    /// Technician's display name
+    pub display_name: String,
+    /// Day planned
+    pub date: Date,
+    /// Latitude of the base the day starts from
+    pub base_latitude: Option<f64>,
+    /// Longitude of the base the day starts from
+    pub base_longitude: Option<f64>,
+    /// Jobs in visiting order
+    pub stops: Vec<RouteStop>,
+    /// Jobs that could not be placed for lack of coordinates
+    pub unrouted: Vec<i32>,
+    /// Estimated road distance, km
+    pub distance_km: f64,
+    /// Estimated driving, minutes
+    pub drive_minutes: i32,
+    /// Total lateness across appointments, minutes
+    pub late_minutes: i32,
+}
+
+/// A technician's job on the day, as read for planning.
+#[derive(Debug, FromRow)]
+struct DayJob {
+    user_id: i32,
+    display_name: String,
+    base_latitude: Option<f64>,
+    base_longitude: Option<f64>,
+    booking_id: i32,
+    service_type: String,
+    postcode: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    scheduled_time: Option<Time>,
+    duration_minutes: i32,
+}
+
+/// Business Model Controller for route planning.
+pub struct RoutePlanBmc;
+
+impl RoutePlanBmc {
+    /// Plans the day's route for one technician, or for every technician
+    /// with jobs that day.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `date` - Day to plan (`YYYY-MM-DD`)
+    /// * `user_id` - Only this technician
+    /// * `day_start` - When the day starts (`HH:MM`, default
+    ///   [`DEFAULT_DAY_START`])
+    ///
+    /// # Returns
+    ///
+    /// Routes by technician name. A single technician is returned even on
+    /// a day without jobs.
+    ///
+    /// # Errors
+    ///
+    /// - `ValidationError` for a missing or malformed date or start time
+    /// - `EntityNotFound` if `user_id` is not a technician
+    #[instrument(skip(mm))]
+    pub async fn plan_day(
+        mm: &ModelManager,
+        date: &str,
+        user_id: Option<i32>,
+        day_start: Option<&str>,
+    ) -> Result<Vec<TechnicianRoute>> {
+        let date = parse_date("date", Some(date))?
+            .ok_or_else(|| Error::ValidationError("date is required".into()))?;
+        let day_start = day_start.unwrap_or(DEFAULT_DAY_START);
+        let day_start = parse_time(day_start)
+            .map(minute_of_day)
+            .ok_or_else(|| Error::ValidationError("start must be an HH:MM time".into()))?;
+
+        let jobs: Vec<DayJob> = sqlx::query_as(
+            r#"
+            SELECT a.user_id, t.display_name, t.base_latitude, t.base_longitude,
+                   b.id AS booking_id, b.service_type, b.postcode, b.latitude, b.longitude,
+                   b.scheduled_time, COALESCE(b.estimated_duration, $3) AS duration_minutes
+            FROM booking_assignments a
+            JOIN technicians t ON t.user_id = a.user_id
+            JOIN bookings b ON b.id = a.booking_id
+            WHERE b.scheduled_date = $1
+              AND b.status <> 'cancelled'
+              AND ($2::INTEGER IS NULL OR a.user_id = $2)
+            ORDER BY t.display_name, a.user_id, b.scheduled_time ASC NULLS LAST, b.id
+            "#,
+        )
+        .bind(date)
+        .bind(user_id)
+        .bind(DEFAULT_JOB_MINUTES)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        if let Some(user_id) = user_id.filter(|_| jobs.is_empty()) {
+            let technician = TechnicianBmc::get(mm, user_id).await?;
+            return Ok(vec![TechnicianRoute {
+                user_id,
+                display_name: technician.display_name,
+                date,
+                base_latitude: technician.base_latitude,
+                base_longitude: technician.base_longitude,
+                stops: Vec::new(),
+                unrouted: Vec::new(),
+                distance_km: 0.0,
+                drive_minutes: 0,
+                late_minutes: 0,
+            }]);
+        }
+
+        let routes = jobs
+            .chunk_by(|a, b| a.user_id == b.user_id)
+            .map(|jobs| plan_route(date, day_start, jobs))
+            .collect();
+
+        Ok(routes)
+    }
+}
+
+/// Renders routes as a GeoJSON `FeatureCollection`.
+///
+/// Every feature carries `kind` (`base`, `stop`, `route` or `unrouted`)
+/// and `user_id`; stops also carry their [`RouteStop`] fields.
+#[must_use]
+pub fn to_geojson(routes: &[TechnicianRoute]) -> Value {
+    let mut features = Vec::new();
+
+    for route in routes {
+        let base = route.base_latitude.zip(route.base_longitude);
+        let mut line: Vec<[f64; 2]> = Vec::with_capacity(route.stops.len() + 1);
+
+        if let Some((lat, lng)) = base {
+            line.push([lng, lat]);
+            features.push(json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": [lng, lat] },
+                "properties": {
+                    "kind": "base",
+                    "user_id": route.user_id,
+                    "display_name": route.display_name,
+                },
+            }));
+        }
+        for stop in &route.stops {
+            line.push([stop.longitude, stop.latitude]);
+            let mut properties = serde_json::to_value(stop).unwrap_or_else(|_| json!({}));
+            properties["kind"] = json!("stop");
+            properties["user_id"] = json!(route.user_id);
+            features.push(json!({
+                "type": "Feature",
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [stop.longitude, stop.latitude],
+                },
+                "properties": properties,
+            }));
+        }
+        if line.len() > 1 {
+            features.push(json!({
+                "type": "Feature",
+                "geometry": { "type": "LineString", "coordinates": line },
+                "properties": {
+                    "kind": "route",
+                    "user_id": route.user_id,
+                    "display_name": route.display_name,
+                    "date": route.date.to_string(),
+                    "distance_km": route.distance_km,
+                    "drive_minutes": route.drive_minutes,
+                    "late_minutes": route.late_minutes,
+                },
+            }));
+        }
+        for booking_id in &route.unrouted {
+            features.push(json!({
+                "type": "Feature",
+                "geometry": null,
+                "properties": {
+                    "kind": "unrouted",
+                    "user_id": route.user_id,
+                    "booking_id": booking_id,
+                },
+            }));
+        }
+    }
+
+    json!({ "type": "FeatureCollection", "features": features })
+}
+
+// region:    --- Route Plan Helpers
+
+/// Plans one technician's jobs, all sharing a `user_id`.
+fn plan_route(date: Date, day_start: i32, jobs: &[DayJob]) -> TechnicianRoute {
+    let first = &jobs[0];
+    let base = first
+        .base_latitude
+        .zip(first.base_longitude)
+        .map(|(lat, lng)| Point { lat, lng });
+    let (placed, unplaced): (Vec<&DayJob>, Vec<&DayJob>) = jobs
+        .iter()
+        .partition(|job| job.latitude.is_some() && job.longitude.is_some());
+
+    let stops: Vec<Stop> = placed
+        .iter()
+        .map(|job| Stop {
+            id: job.booking_id,
+            location: Point {
+                lat: job.latitude.unwrap_or_default(),
+                lng: job.longitude.unwrap_or_default(),
+            },
+            fixed_at: job.scheduled_time.map(minute_of_day),
+            duration: job.duration_minutes,
+        })
+        .collect();
+    let plan = routing::plan(base, day_start, &stops);
+
+    let route_stops = plan
+        .visits
+        .iter()
+        .zip(1..)
+        .filter_map(|(visit, sequence)| {
+            let job = placed.iter().find(|job| job.booking_id == visit.id)?;
+            Some(RouteStop {
+                sequence,
+                booking_id: job.booking_id,
+                service_type: job.service_type.clone(),
+                postcode: job.postcode.clone(),
+                latitude: job.latitude.unwrap_or_default(),
+                longitude: job.longitude.unwrap_or_default(),
+                appointment: job.scheduled_time.map(|t| clock(minute_of_day(t))),
+                arrival: clock(visit.arrive),
+                start: clock(visit.start),
+                departure: clock(visit.depart),
+                drive_km: round_km(visit.drive_km),
+                late_minutes: visit.late_minutes,
+            })
+        })
+        .collect();
+
+    TechnicianRoute {
+        user_id: first.user_id,
+        display_name: first.display_name.clone(),
+        date,
+        base_latitude: first.base_latitude,
+        base_longitude: first.base_longitude,
+        stops: route_stops,
+        unrouted: unplaced.iter().map(|job| job.booking_id).collect(),
+        distance_km: round_km(plan.distance_km),
+        drive_minutes: plan.drive_minutes,
+        late_minutes: plan.late_minutes,
+    }
+}
+
+fn minute_of_day(time: Time) -> i32 {
+    i32::from(time.hour()) * 60 + i32::from(time.minute())
+}
+
+/// `HH:MM` for minutes since midnight.
+fn clock(minutes: i32) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Kilometres to one decimal place.
+fn round_km(km: f64) -> f64 {
+    (km * 10.0).round() / 10.0
+}
+
+// endregion: --- Route Plan Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::booking::{BookingBmc, BookingForCreate, BookingForUpdate};
+    use crate::model::technician::TechnicianForCreate;
+
+    fn fx_job(booking_id: i32, lng: Option<f64>, scheduled_time: Option<Time>) -> DayJob {
+        DayJob {
+            user_id: 7,
+            display_name: "Test Tech".to_string(),
+            base_latitude: Some(52.4),
+            base_longitude: Some(-1.5),
+            booking_id,
+            service_type: "plumbing".to_string(),
+            postcode: None,
+            latitude: lng.map(|_| 52.4),
+            longitude: lng,
+            scheduled_time,
+            duration_minutes: 60,
+        }
+    }
+
+    fn oct(day: u8) -> Date {
+        Date::from_calendar_date(2026, time::Month::October, day).unwrap()
+    }
+
+    #[test]
+    fn test_plan_route() {
+        let jobs = vec![
+            fx_job(1, Some(-1.3), None),
+            fx_job(2, Some(-1.4), None),
+            fx_job(3, None, None),
+        ];
+
+        let route = plan_route(oct(20), 8 * 60, &jobs);
+
+        assert_eq!(
+            route.stops.iter().map(|s| s.booking_id).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(route.stops[0].sequence, 1);
+        assert_eq!(route.stops[0].appointment, None);
+        assert_eq!(route.unrouted, vec![3]);
+        assert!(route.distance_km > 0.0);
+    }
+
+    #[test]
+    fn test_to_geojson() {
+        let jobs = vec![fx_job(1, Some(-1.3), None), fx_job(2, None, None)];
+        let route = plan_route(oct(20), 8 * 60, &jobs);
+
+        let geojson = to_geojson(&[route]);
+
+        assert_eq!(geojson["type"], "FeatureCollection");
+        let kinds: Vec<&str> = geojson["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["properties"]["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, vec!["base", "stop", "route", "unrouted"]);
+        let stop = &geojson["features"][1];
+        assert_eq!(stop["geometry"]["coordinates"][0], -1.3);
+        assert_eq!(stop["properties"]["booking_id"], 1);
+        assert_eq!(
+            geojson["features"][2]["geometry"]["coordinates"][0][0],
+            -1.5
+        );
+    }
+
+    #[test]
+    fn test_clock() {
+        assert_eq!(clock(8 * 60 + 5), "08:05");
+        assert_eq!(clock(25 * 60), "25:00");
+    }
+
+    #[tokio::test]
+    async fn test_plan_day_orders_assigned_jobs() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let (user_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO users (username, email, pwd_hash, token_salt) \
+             VALUES ('test_route_tech', 'test_route_tech@example.com', 'x', gen_random_uuid()) \
+             RETURNING id",
+        )
+        .fetch_one(mm.dbx().db())
+        .await?;
+        let _ = TechnicianBmc::create(
+            &mm,
+            TechnicianForCreate {
+                user_id,
+                display_name: "Test Route Tech".to_string(),
+                phone: None,
+                skills: Vec::new(),
+                areas: Vec::new(),
+                base_latitude: Some(52.4),
+                base_longitude: Some(-1.5),
+            },
+        )
+        .await?;
+        let mut bookings = Vec::new();
+        for (lng, time) in [(-1.2, Some("08:45")), (-1.4, None), (-1.3, None)] {
+            let id = BookingBmc::create(
+                &mm,
+                BookingForCreate {
+                    customer_id: None,
+                    service_type: "test_route".to_string(),
+                    scheduled_date: None,
+                    scheduled_time: None,
+                    notes: None,
+                },
+            )
+            .await?;
+            BookingBmc::update(
+                &mm,
+                id,
+                BookingForUpdate {
+                    scheduled_date: Some("2026-11-03".to_string()),
+                    scheduled_time: time.map(str::to_string),
+                    estimated_duration: Some(30),
+                    latitude: Some(52.4),
+                    longitude: Some(lng),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            bookings.push(id);
+        }
+        TechnicianBmc::assign(&mm, bookings[0], &[user_id], true).await?;
+        TechnicianBmc::assign(&mm, bookings[1], &[user_id], true).await?;
+        TechnicianBmc::assign(&mm, bookings[2], &[user_id], true).await?;
+
+        // Execute
+        let routes = RoutePlanBmc::plan_day(&mm, "2026-11-03", Some(user_id), None).await?;
+        let bad_date = RoutePlanBmc::plan_day(&mm, "03/11/2026", Some(user_id), None).await;
+
+        // Check
+        assert_eq!(routes.len(), 1);
+        let route = &routes[0];
+        assert_eq!(
+            route.stops.iter().map(|s| s.booking_id).collect::<Vec<_>>(),
+            vec![bookings[0], bookings[2], bookings[1]],
+            "the 08:45 appointment comes first, then back towards base"
+        );
+        assert_eq!(route.stops[0].appointment.as_deref(), Some("08:45"));
+        assert_eq!(route.late_minutes, 0);
+        assert!(matches!(bad_date, Err(Error::ValidationError(_))));
+
+        // Cleanup
+        for id in bookings {
+            BookingBmc::delete(&mm, id).await?;
+        }
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(mm.dbx().db())
+            .await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,466 @@
+//! # Search Model
+//!
+//! Unified full-text and fuzzy search across customers, bookings and quotes.
+//!
+//! Backed by the generated `search_vector` columns (Postgres `tsvector`) and
+//! `pg_trgm` similarity, so partial words, phone numbers written in different
+//! formats, postcodes and small typos all find the right record.
+//!
+//! ## Structures
+//!
+//! - [`SearchHit`] - A ranked, typed search result with a highlighted snippet
+//! - [`SearchHitKind`] - The entity type a hit refers to
+//! - [`SearchBmc`] - Business Model Controller for search operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::search::SearchBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn find(mm: &ModelManager) -> Result<(), Box<dyn std::error::Error>> {
+//!     let hits = SearchBmc::search(mm, "07833 263486", 20).await?;
+//!     for hit in hits {
+//!         println!("{:?} #{} {} ({:.2})", hit.kind, hit.id, hit.title, hit.rank);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Minimum query length (in characters, after trimming).
+pub const MIN_QUERY_LEN: usize = 2;
+
+/// Maximum number of hits returned by a single search.
+pub const MAX_LIMIT: i64 = 100;
+
+/// Number of characters of context kept either side of the first match.
+const SNIPPET_CONTEXT: usize = 40;
+
+/// Entity type a search hit refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchHitKind {
+    Customer,
+    Booking,
+    Quote,
+}
+
+impl SearchHitKind {
+    fn from_db(kind: &str) -> Result<Self> {
+        match kind {
+            "customer" => Ok(Self::Customer),
+            "booking" => Ok(Self::Booking),
+            "quote" => Ok(Self::Quote),
+            other => Err(Error::ValidationError(
+                format!("Unknown search hit kind: {other}").into(),
+            )),
+        }
+    }
+}
+
+/// A ranked, typed search result.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchHit {
+    /// Entity type of the hit
+    pub kind: SearchHitKind,
+    /// Primary key of the entity
+    pub id: i32,
+    /// Display title (customer name, quote title, booking service type)
+    pub title: String,
+    /// Secondary line (contact details, status)
+    pub subtitle: Option<String>,
+    /// HTML-escaped snippet with matches wrapped in `<mark>` tags
+    pub highlight: String,
+    /// Relevance score (higher is better)
+    pub rank: f32,
+    /// When the entity was created
+    pub created_at: Option<OffsetDateTime>,
+}
+
+/// Raw row returned by the search query.
+#[derive(Debug, FromRow)]
+struct SearchRow {
+    kind: String,
+    id: i32,
+    title: String,
+    subtitle: Option<String>,
+    haystack: String,
+    rank: f32,
+    created_at: Option<OffsetDateTime>,
+}
+
+/// Business Model Controller for search operations.
+pub struct SearchBmc;
+
+impl SearchBmc {
+    /// Searches customers, bookings and quotes.
+    ///
+    /// Matches on full-text (with prefix matching on every term), trigram
+    /// similarity for typos, normalised phone digits and compacted postcodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `query` - Free-text search query
+    /// * `limit` - Maximum number of hits (clamped to 1..=[`MAX_LIMIT`])
+    ///
+    /// # Returns
+    ///
+    /// Hits ordered by descending rank.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the query is shorter than [`MIN_QUERY_LEN`].
+    #[instrument(skip(mm))]
+    pub async fn search(mm: &ModelManager, query: &str, limit: i64) -> Result<Vec<SearchHit>> {
+        let query = query.trim();
+        if query.chars().count() < MIN_QUERY_LEN {
+            return Err(Error::ValidationError(
+                format!("Search query must be at least {MIN_QUERY_LEN} characters").into(),
+            ));
+        }
+
+        let prefix_query = prefix_tsquery(query);
+        let digits = phone_digits(query);
+        let compact = compact_alnum(query);
+        let limit = limit.clamp(1, MAX_LIMIT);
+
+        let rows = sqlx::query_as::<_, SearchRow>(
+            r#"
+            WITH q AS (
+                SELECT CASE WHEN $2::text IS NULL THEN NULL::tsquery
+                            ELSE to_tsquery('simple', $2) || to_tsquery('english', $2)
+                       END AS tsq,
+                       $1::text AS raw,
+                       $3::text AS digits,
+                       $4::text AS compact
+            )
+            SELECT * FROM (
+                SELECT 'customer' AS kind, c.id, c.name AS title,
+                       NULLIF(concat_ws(' · ', c.email, c.phone), '') AS subtitle,
+                       concat_ws(' ', c.name, c.email, c.phone, c.addresses::text, c.notes) AS haystack,
+                       (coalesce(ts_rank(c.search_vector, q.tsq), 0)
+                        + greatest(similarity(c.name, q.raw),
+                                   word_similarity(q.raw, coalesce(c.email, '')),
+                                   CASE WHEN q.digits IS NOT NULL
+                                        AND regexp_replace(regexp_replace(coalesce(c.phone, ''), '[^0-9]', '', 'g'), '^(44|0)', '') LIKE '%' || q.digits || '%'
+                                        THEN 1.0 ELSE 0 END,
+                                   CASE WHEN length(q.compact) >= 3
+                                        AND strpos(upper(regexp_replace(coalesce(c.addresses::text, ''), '[^A-Za-z0-9]', '', 'g')), q.compact) > 0
+                                        THEN 0.8 ELSE 0 END))::real AS rank,
+                       c.created_at
+                FROM customers c, q
+                WHERE c.search_vector @@ q.tsq
+                   OR c.name % q.raw
+                   OR q.raw <% coalesce(c.email, '')
+                   OR (q.digits IS NOT NULL
+                       AND regexp_replace(regexp_replace(coalesce(c.phone, ''), '[^0-9]', '', 'g'), '^(44|0)', '') LIKE '%' || q.digits || '%')
+                   OR (length(q.compact) >= 3
+                       AND strpos(upper(regexp_replace(coalesce(c.addresses::text, ''), '[^A-Za-z0-9]', '', 'g')), q.compact) > 0)
+
+                UNION ALL
+
+                SELECT 'booking' AS kind, b.id, b.service_type AS title,
+                       concat_ws(' · ', b.status, b.scheduled_date::text) AS subtitle,
+                       concat_ws(' ', b.service_type, b.notes) AS haystack,
+                       (coalesce(ts_rank(b.search_vector, q.tsq), 0)
+                        + greatest(similarity(b.service_type, q.raw),
+                                   word_similarity(q.raw, coalesce(b.notes, ''))))::real AS rank,
+                       b.created_at
+                FROM bookings b, q
+                WHERE b.search_vector @@ q.tsq
+                   OR b.service_type % q.raw
+                   OR q.raw <% coalesce(b.notes, '')
+
+                UNION ALL
+
+                SELECT 'quote' AS kind, qt.id, qt.title,
+                       concat_ws(' · ', qt.status, '£' || to_char(qt.total_cents / 100.0, 'FM999999990.00')) AS subtitle,
+                       concat_ws(' ', qt.title,
+                                 (SELECT string_agg(i->>'description', ' ')
+                                  FROM jsonb_array_elements(coalesce(qt.items::jsonb, '[]'::jsonb)) AS i)) AS haystack,
+                       (coalesce(ts_rank(qt.search_vector, q.tsq), 0)
+                        + similarity(qt.title, q.raw))::real AS rank,
+                       qt.created_at
+                FROM quotes qt, q
+                WHERE qt.search_vector @@ q.tsq
+                   OR qt.title % q.raw
+            ) hits
+            ORDER BY rank DESC, created_at DESC NULLS LAST
+            LIMIT $5
+            "#,
+        )
+        .bind(query)
+        .bind(prefix_query)
+        .bind(digits)
+        .bind(compact)
+        .bind(limit)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(SearchHit {
+                    kind: SearchHitKind::from_db(&row.kind)?,
+                    id: row.id,
+                    title: row.title,
+                    subtitle: row.subtitle,
+                    highlight: highlight(&row.haystack, query),
+                    rank: row.rank,
+                    created_at: row.created_at,
+                })
+            })
+            .collect()
+    }
+}
+
+// region:    --- Query Helpers
+
+/// Splits a query into lowercase alphanumeric terms.
+fn terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Builds a `to_tsquery` expression matching every term as a prefix.
+///
+/// Returns `None` when the query has no usable terms (e.g. only punctuation).
+fn prefix_tsquery(query: &str) -> Option<String> {
+    let terms = terms(query);
+    if terms.is_empty() {
+        return None;
+    }
+    Some(
+        terms
+            .iter()
+            .map(|t| format!("{t}:*"))
+            .collect::<Vec<_>>()
+            .join(" & "),
+    )
+}
+
+/// Extracts national phone digits from a query (drops `44` / `0` prefixes).
+///
+/// Returns `None` unless the query looks like a phone number fragment:
+/// at least 4 digits and nothing but digits, spaces, `+`, `-` or brackets.
+fn phone_digits(query: &str) -> Option<String> {
+    if !query
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '+' | '-' | '(' | ')'))
+    {
+        return None;
+    }
+    let digits: String = query.chars().filter(char::is_ascii_digit).collect();
+    let national = digits
+        .strip_prefix("44")
+        .or_else(|| digits.strip_prefix('0'))
+        .unwrap_or(&digits);
+    (national.len() >= 4).then(|| national.to_string())
+}
+
+/// Uppercase alphanumerics only, so "cv1 2ab" matches "CV1 2AB".
+fn compact_alnum(query: &str) -> String {
+    query
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Escapes text for safe inclusion in HTML.
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Builds an HTML-escaped snippet around the first match with every
+/// occurrence of a query term wrapped in `<mark>`.
+///
+/// Matching is case-insensitive on whole characters; when nothing matches
+/// literally (e.g. a typo found by trigram similarity) the start of the
+/// text is returned unmarked.
+fn highlight(text: &str, query: &str) -> String {
+    let text: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = text.iter().flat_map(|c| c.to_lowercase()).collect();
+    // Fall back to no highlighting if lowercasing changed the length
+    let lower = if lower.len() == text.len() {
+        lower
+    } else {
+        text.clone()
+    };
+    let terms: Vec<Vec<char>> = terms(query).iter().map(|t| t.chars().collect()).collect();
+
+    // Mark every matched character
+    let mut marked = vec![false; text.len()];
+    for term in &terms {
+        if term.is_empty() || term.len() > lower.len() {
+            continue;
+        }
+        for start in 0..=(lower.len() - term.len()) {
+            if lower[start..start + term.len()] == term[..] {
+                marked[start..start + term.len()]
+                    .iter_mut()
+                    .for_each(|m| *m = true);
+            }
+        }
+    }
+
+    // Window around the first match
+    let first = marked.iter().position(|m| *m).unwrap_or(0);
+    let start = first.saturating_sub(SNIPPET_CONTEXT);
+    let end = (first + SNIPPET_CONTEXT * 2).min(text.len());
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    let mut in_mark = false;
+    for i in start..end {
+        if marked[i] != in_mark {
+            out.push_str(if marked[i] { "<mark>" } else { "</mark>" });
+            in_mark = marked[i];
+        }
+        out.push_str(&escape_html(&text[i].to_string()));
+    }
+    if in_mark {
+        out.push_str("</mark>");
+    }
+    if end < text.len() {
+        out.push('…');
+    }
+    out
+}
+
+// endregion: --- Query Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::customer::{CustomerBmc, CustomerForCreate};
+
+    #[test]
+    fn test_prefix_tsquery() {
+        assert_eq!(
+            prefix_tsquery("Leaky tap!").as_deref(),
+            Some("leaky:* & tap:*")
+        );
+        assert_eq!(prefix_tsquery("o'brien").as_deref(), Some("o:* & brien:*"));
+        assert_eq!(prefix_tsquery("--"), None);
+    }
+
+    #[test]
+    fn test_phone_digits() {
+        assert_eq!(
+            phone_digits("+44 7833 263486").as_deref(),
+            Some("7833263486")
+        );
+        assert_eq!(phone_digits("07833 263486").as_deref(), Some("7833263486"));
+        assert_eq!(phone_digits("263486").as_deref(), Some("263486"));
+        assert_eq!(phone_digits("012"), None);
+        assert_eq!(phone_digits("CV1 2AB"), None);
+    }
+
+    #[test]
+    fn test_compact_alnum() {
+        assert_eq!(compact_alnum("cv1 2ab"), "CV12AB");
+    }
+
+    #[test]
+    fn test_highlight_marks_and_escapes() {
+        let snippet = highlight("John <Smith> smith@example.com", "smith");
+        assert_eq!(
+            snippet,
+            "John &lt;<mark>Smith</mark>&gt; <mark>smith</mark>@example.com"
+        );
+    }
+
+    #[test]
+    fn test_highlight_windows_long_text() {
+        let text = format!("{}needle{}", "a".repeat(100), "b".repeat(100));
+        let snippet = highlight(&text, "needle");
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.contains("<mark>needle</mark>"));
+    }
+
+    #[test]
+    fn test_highlight_no_literal_match() {
+        assert_eq!(highlight("John Smith", "jhon"), "John Smith");
+    }
+
+    #[tokio::test]
+    async fn test_search_err_query_too_short() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+
+        // Execute
+        let res = SearchBmc::search(&mm, " a ", 10).await;
+
+        // Check
+        assert!(matches!(res, Err(Error::ValidationError(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_customer_by_phone_and_typo() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let unique = uuid::Uuid::new_v4().simple().to_string();
+        let name = format!("Bartholomew Search{}", &unique[..8]);
+        let id = CustomerBmc::create(
+            &mm,
+            CustomerForCreate {
+                name: name.clone(),
+                email: Some(format!("search_{unique}@example.com")),
+                phone: Some("+44 7700 900123".to_string()),
+                notes: None,
+            },
+        )
+        .await?;
+
+        // Execute
+        let by_phone = SearchBmc::search(&mm, "07700 900123", 20).await?;
+        let by_typo =
+            SearchBmc::search(&mm, &format!("Bartholomw Search{}", &unique[..8]), 20).await?;
+
+        // Check
+        assert!(by_phone
+            .iter()
+            .any(|h| h.kind == SearchHitKind::Customer && h.id == id));
+        assert!(by_typo
+            .iter()
+            .any(|h| h.kind == SearchHitKind::Customer && h.id == id));
+
+        // Cleanup
+        CustomerBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
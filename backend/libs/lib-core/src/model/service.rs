@@ -0,0 +1,421 @@
+//! # Service Model
+//!
+//! The services we offer, as shown on landing pages.
+//!
+//! A service can be linked to a pricing catalog entry (`service_type`) so
+//! its pages show the same typical price range as the instant quote
+//! calculator. As with areas, a service's description may not be a
+//! near-copy of another's.
+//!
+//! ## Structures
+//!
+//! - [`Service`] - Service record from database
+//! - [`ServiceForCreate`] - Data required to add a service
+//! - [`ServiceForUpdate`] - Data for updating a service
+//! - [`ServiceBmc`] - Business Model Controller for services
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::service::ServiceBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn names(mm: &ModelManager) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+//!     let services = ServiceBmc::list(mm, true).await?;
+//!     Ok(services.into_iter().map(|s| s.name).collect())
+//! }
+//! ```
+
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::text::{is_near_duplicate, is_slug, slugify};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Select list for [`Service`].
+const SERVICE_SELECT: &str = "SELECT id, slug, name, summary, description, service_type, \
+     sort_order, active, created_at, updated_at FROM services";
+
+/// Service record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Service {
+    /// Service ID
+    pub id: i32,
+    /// URL slug, e.g. `plumbing`
+    pub slug: String,
+    /// Service name
+    pub name: String,
+    /// One line, for cards and meta descriptions
+    pub summary: String,
+    /// About the service, as Markdown
+    pub description: String,
+    /// Pricing catalog entry for price ranges
+    pub service_type: Option<String>,
+    /// Display order (lowest first)
+    pub sort_order: i32,
+    /// Whether the service's pages are published
+    pub active: bool,
+    /// When the service was added
+    pub created_at: Option<OffsetDateTime>,
+    /// When the service was last updated
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+/// Data required to add a service.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ServiceForCreate {
+    /// URL slug (default: from the name)
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Service name
+    pub name: String,
+    /// One-line summary
+    pub summary: String,
+    /// About the service, as Markdown
+    pub description: String,
+    /// Pricing catalog entry, e.g. `plumbing`
+    #[serde(default)]
+    pub service_type: Option<String>,
+    /// Display order (lowest first)
+    #[serde(default)]
+    pub sort_order: i32,
+    /// Whether to publish the service's pages
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Data for updating a service. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct ServiceForUpdate {
+    /// New URL slug
+    pub slug: Option<String>,
+    /// New name
+    pub name: Option<String>,
+    /// New summary
+    pub summary: Option<String>,
+    /// New description
+    pub description: Option<String>,
+    /// New pricing catalog entry
+    pub service_type: Option<String>,
+    /// New display order
+    pub sort_order: Option<i32>,
+    /// Publish or unpublish
+    pub active: Option<bool>,
+}
+
+/// Business Model Controller for services.
+pub struct ServiceBmc;
+
+impl ServiceBmc {
+    /// Adds a service.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `service` - Service to add
+    ///
+    /// # Returns
+    ///
+    /// The new service's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for a blank name, summary or description,
+    /// an invalid or taken slug, an unknown pricing catalog entry, or a
+    /// description too like another service's.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm, service), fields(name = %service.name))]
+    pub async fn create(mm: &ModelManager, service: ServiceForCreate) -> Result<i32> {
+        let slug = service
+            .slug
+            .clone()
+            .unwrap_or_else(|| slugify(&service.name));
+        let others = Self::list(mm, false).await?;
+        check_service(
+            None,
+            &slug,
+            &service.name,
+            &service.summary,
+            &service.description,
+            &others,
+        )?;
+        check_service_type(mm, service.service_type.as_deref()).await?;
+
+        let row: (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO services
+                (slug, name, summary, description, service_type, sort_order, active)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+        )
+        .bind(&slug)
+        .bind(service.name.trim())
+        .bind(service.summary.trim())
+        .bind(service.description.trim())
+        .bind(&service.service_type)
+        .bind(service.sort_order)
+        .bind(service.active)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// Gets a service by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the service does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<Service> {
+        sqlx::query_as(&format!("{SERVICE_SELECT} WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "Service",
+                id: id as i64,
+            })
+    }
+
+    /// Gets a published service by slug.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SlugNotFound` if no active service has the slug.
+    #[instrument(skip(mm))]
+    pub async fn get_by_slug(mm: &ModelManager, slug: &str) -> Result<Service> {
+        sqlx::query_as(&format!("{SERVICE_SELECT} WHERE slug = $1 AND active"))
+            .bind(slug)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or_else(|| Error::SlugNotFound {
+                entity: "Service",
+                slug: slug.to_string(),
+            })
+    }
+
+    /// Lists services in display order, optionally only published ones.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, active_only: bool) -> Result<Vec<Service>> {
+        let services = sqlx::query_as(&format!(
+            "{SERVICE_SELECT} WHERE active OR NOT $1 ORDER BY sort_order, name"
+        ))
+        .bind(active_only)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(services)
+    }
+
+    /// Updates a service.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the service does not exist, or
+    /// `ValidationError` as for [`ServiceBmc::create`].
+    #[instrument(skip(mm, data))]
+    pub async fn update(mm: &ModelManager, id: i32, data: ServiceForUpdate) -> Result<()> {
+        let current = Self::get(mm, id).await?;
+        let others = Self::list(mm, false).await?;
+        check_service(
+            Some(id),
+            data.slug.as_deref().unwrap_or(&current.slug),
+            data.name.as_deref().unwrap_or(&current.name),
+            data.summary.as_deref().unwrap_or(&current.summary),
+            data.description.as_deref().unwrap_or(&current.description),
+            &others,
+        )?;
+        check_service_type(mm, data.service_type.as_deref()).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE services
+            SET slug = COALESCE($2, slug),
+                name = COALESCE($3, name),
+                summary = COALESCE($4, summary),
+                description = COALESCE($5, description),
+                service_type = COALESCE($6, service_type),
+                sort_order = COALESCE($7, sort_order),
+                active = COALESCE($8, active)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(&data.slug)
+        .bind(data.name.as_deref().map(str::trim))
+        .bind(data.summary.as_deref().map(str::trim))
+        .bind(data.description.as_deref().map(str::trim))
+        .bind(&data.service_type)
+        .bind(data.sort_order)
+        .bind(data.active)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes a service and its area pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the service does not exist.
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM services WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "Service",
+                id: id as i64,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+// region:    --- Service Helpers
+
+/// Checks a service's fields, and that it doesn't duplicate another.
+fn check_service(
+    id: Option<i32>,
+    slug: &str,
+    name: &str,
+    summary: &str,
+    description: &str,
+    services: &[Service],
+) -> Result<()> {
+    if [name, summary, description]
+        .iter()
+        .any(|f| f.trim().is_empty())
+    {
+        return Err(Error::ValidationError(
+            "A service needs a name, a summary and a description".into(),
+        ));
+    }
+    if !is_slug(slug) {
+        return Err(Error::ValidationError(
+            format!(
+                "Slug must be lowercase words joined by hyphens, like {}",
+                slugify(name)
+            )
+            .into(),
+        ));
+    }
+
+    for other in services.iter().filter(|s| Some(s.id) != id) {
+        if other.slug == slug {
+            return Err(Error::ValidationError(
+                format!("Slug {slug} is already used by {}", other.name).into(),
+            ));
+        }
+        if is_near_duplicate(description, &other.description) {
+            return Err(Error::ValidationError(
+                format!(
+                    "Description is too like {}'s; write one about this service",
+                    other.name
+                )
+                .into(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+async fn check_service_type(mm: &ModelManager, service_type: Option<&str>) -> Result<()> {
+    let Some(service_type) = service_type else {
+        return Ok(());
+    };
+    let (exists,): (bool,) =
+        sqlx::query_as("SELECT EXISTS (SELECT 1 FROM service_rates WHERE service_type = $1)")
+            .bind(service_type)
+            .fetch_one(mm.dbx().db())
+            .await?;
+    if !exists {
+        return Err(Error::ValidationError(
+            format!("Unknown pricing service type: {service_type}").into(),
+        ));
+    }
+
+    Ok(())
+}
+
+// endregion: --- Service Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+
+    #[tokio::test]
+    async fn test_create_and_check_duplicates() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let suffix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let service = ServiceForCreate {
+            slug: None,
+            name: format!("Gutter Clearing {suffix}"),
+            summary: "Gutters and downpipes cleared".to_string(),
+            description: format!(
+                "We clear moss and leaves from gutters and downpipes and check the joints \
+                 for leaks, reference {suffix}."
+            ),
+            service_type: Some("general".to_string()),
+            sort_order: 999,
+            active: true,
+        };
+
+        // Execute
+        let id = ServiceBmc::create(&mm, service.clone()).await?;
+        let copy = ServiceBmc::create(
+            &mm,
+            ServiceForCreate {
+                slug: Some(format!("gutter-cleaning-{suffix}")),
+                ..service.clone()
+            },
+        )
+        .await;
+        let unknown_type = ServiceBmc::create(
+            &mm,
+            ServiceForCreate {
+                slug: Some(format!("roofing-{suffix}")),
+                description: "Slipped tiles and flashing repairs.".to_string(),
+                service_type: Some("no-such-type".to_string()),
+                ..service.clone()
+            },
+        )
+        .await;
+
+        // Check
+        let created = ServiceBmc::get_by_slug(&mm, &format!("gutter-clearing-{suffix}")).await?;
+        assert_eq!(created.id, id);
+        assert!(
+            copy.is_err(),
+            "Near-identical descriptions should be refused"
+        );
+        assert!(unknown_type.is_err());
+
+        // Cleanup
+        ServiceBmc::delete(&mm, id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -1,24 +1,131 @@
 //! Database connection and pooling.
 //!
-//! Provides Postgres database pool configuration.
+//! Provides Postgres database pool configuration, one pool per tenant, with
+//! only the most recently used tenants' pools kept open.
 
 // region:    --- Modules
 
 pub(in crate::model) mod dbx;
 
 use crate::core_config;
+use crate::model::tenant::DEFAULT_TENANT_ID;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::{Pool, Postgres};
+use std::collections::HashMap;
+use std::time::Duration;
 
 // endregion: --- Modules
 
 pub type Db = Pool<Postgres>;
 
-pub async fn new_db_pool() -> sqlx::Result<Db> {
-    let max_connections = if cfg!(test) { 1 } else { 5 };
+/// Role tenant connections run as. It owns no tables, so row level
+/// security applies even when the configured database user owns them.
+const TENANT_ROLE: &str = "app_tenant";
+
+/// How long an idle tenant connection is kept, so quiet sites don't hold
+/// connections.
+const TENANT_IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Creates the pool for one tenant's connections. Every connection runs as
+/// [`TENANT_ROLE`] with `app.tenant_id` set, so queries only see and write
+/// that tenant's rows. Connections are opened on first use.
+pub fn new_tenant_pool(tenant_id: i32) -> sqlx::Result<Db> {
+    let max_connections = if cfg!(test) {
+        1
+    } else {
+        core_config().TENANT_POOL_CONNECTIONS.max(1)
+    };
 
     PgPoolOptions::new()
         .max_connections(max_connections)
-        .connect(&core_config().DB_URL)
-        .await
+        .idle_timeout(TENANT_IDLE_TIMEOUT)
+        .after_connect(move |conn, _meta| {
+            Box::pin(async move {
+                sqlx::query(&format!("SET ROLE {TENANT_ROLE}"))
+                    .execute(&mut *conn)
+                    .await?;
+                sqlx::query("SELECT set_config('app.tenant_id', $1, false)")
+                    .bind(tenant_id.to_string())
+                    .execute(&mut *conn)
+                    .await?;
+                Ok(())
+            })
+        })
+        .connect_lazy(&core_config().DB_URL)
+}
+
+/// The open tenant pools, at most `max` of them. Making room for another
+/// drops the least recently used, whose connections close once requests
+/// still holding it finish. The default tenant's pool is never dropped.
+pub(in crate::model) struct TenantPools {
+    pools: HashMap<i32, (Db, u64)>,
+    max: usize,
+    clock: u64,
+}
+
+impl TenantPools {
+    pub(in crate::model) fn new(default_pool: Db, max: usize) -> Self {
+        Self {
+            pools: HashMap::from([(DEFAULT_TENANT_ID, (default_pool, 0))]),
+            max: max.max(1),
+            clock: 0,
+        }
+    }
+
+    /// The tenant's pool, created if it isn't open.
+    pub(in crate::model) fn get(&mut self, tenant_id: i32) -> sqlx::Result<Db> {
+        self.clock += 1;
+        if let Some((db_pool, last_used)) = self.pools.get_mut(&tenant_id) {
+            *last_used = self.clock;
+            return Ok(db_pool.clone());
+        }
+
+        if self.pools.len() >= self.max {
+            let least_recent = self
+                .pools
+                .iter()
+                .filter(|(id, _)| **id != DEFAULT_TENANT_ID)
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(id, _)| *id);
+            if let Some(id) = least_recent {
+                self.pools.remove(&id);
+            }
+        }
+        let db_pool = new_tenant_pool(tenant_id)?;
+        self.pools.insert(tenant_id, (db_pool.clone(), self.clock));
+
+        Ok(db_pool)
+    }
+
+    #[cfg(test)]
+    fn open(&self) -> Vec<i32> {
+        let mut ids: Vec<i32> = self.pools.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
 }
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tenant_pools_drop_least_recently_used() -> sqlx::Result<()> {
+        let mut pools = TenantPools::new(new_tenant_pool(DEFAULT_TENANT_ID)?, 3);
+
+        pools.get(2)?;
+        pools.get(3)?;
+        pools.get(2)?;
+        pools.get(4)?;
+        assert_eq!(pools.open(), vec![1, 2, 4]);
+
+        pools.get(5)?;
+        assert_eq!(pools.open(), vec![1, 4, 5]);
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,972 @@
+//! # Technician Model
+//!
+//! Technicians, the jobs they are assigned to, and who to send next.
+//!
+//! A technician is a user with a working profile: the skills they can be
+//! sent out for and the postcode areas they cover. Skills are matched
+//! against a booking's `service_type`; areas are postcode areas (`SW`) or
+//! districts (`SW1A`) matched against the booking's site postcode.
+//!
+//! ## Scheduling
+//!
+//! A booking can be assigned to several technicians. Assigning someone
+//! whose other jobs that day overlap the booking's time slot (its
+//! scheduled time plus its estimated duration, or
+//! [`DEFAULT_JOB_MINUTES`]) is refused unless forced. Jobs without a
+//! scheduled time never conflict.
+//!
+//! Suggestions rank every active technician not yet on the booking: free
+//! before clashing, skilled before not, same district before same area
+//! before elsewhere, then fewest jobs that day.
+//!
+//! ## Structures
+//!
+//! - [`Technician`] - Technician profile from database
+//! - [`TechnicianForCreate`] - Data required to make a user a technician
+//! - [`TechnicianForUpdate`] - Data for updating a profile
+//! - [`BookingAssignment`] - A technician assigned to a booking
+//! - [`ScheduleConflict`] - Another job overlapping a booking's slot
+//! - [`AreaMatch`] - How closely a technician's areas cover a job site
+//! - [`TechnicianSuggestion`] - A ranked candidate for a booking
+//! - [`TechnicianBmc`] - Business Model Controller for technicians
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::technician::TechnicianBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn send_best(mm: &ModelManager, booking_id: i32) -> lib_core::model::Result<()> {
+//!     let suggestions = TechnicianBmc::suggest(mm, booking_id).await?;
+//!     if let Some(best) = suggestions.first().filter(|s| s.conflicts.is_empty()) {
+//!         TechnicianBmc::assign(mm, booking_id, &[best.technician.user_id], false).await?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::model::booking::{validate_location, Booking, BOOKING_COLUMNS};
+use crate::model::margin::parse_date;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::postcode;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::cmp::Reverse;
+use time::{Date, OffsetDateTime, Time};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Minutes a job is assumed to take when it has no estimate.
+pub const DEFAULT_JOB_MINUTES: i32 = 60;
+
+/// Select list for [`Technician`].
+const TECHNICIAN_SELECT: &str = "SELECT t.user_id, u.username, t.display_name, t.phone, \
+     t.skills, t.areas, t.base_latitude, t.base_longitude, t.active, t.created_at, \
+     t.updated_at \
+     FROM technicians t JOIN users u ON u.id = t.user_id";
+
+/// Technician profile from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Technician {
+    /// User ID (also identifies the technician)
+    pub user_id: i32,
+    /// Login name of the user
+    pub username: String,
+    /// Name shown to staff and customers
+    pub display_name: String,
+    /// Contact number
+    pub phone: Option<String>,
+    /// Service types the technician can be sent out for
+    pub skills: Vec<String>,
+    /// Postcode areas or districts covered
+    pub areas: Vec<String>,
+    /// Latitude of the home or depot the working day starts from
+    pub base_latitude: Option<f64>,
+    /// Longitude of the home or depot the working day starts from
+    pub base_longitude: Option<f64>,
+    /// Whether the technician is taking work
+    pub active: bool,
+    /// When the profile was created
+    pub created_at: Option<OffsetDateTime>,
+    /// When the profile was last updated
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+/// Data required to make a user a technician.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct TechnicianForCreate {
+    /// Existing user to give a profile
+    pub user_id: i32,
+    /// Name shown to staff and customers
+    pub display_name: String,
+    /// Contact number
+    pub phone: Option<String>,
+    /// Service types, e.g. `plumbing`
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Postcode areas or districts, e.g. `SW` or `E14`
+    #[serde(default)]
+    pub areas: Vec<String>,
+    /// Latitude of the technician's base
+    pub base_latitude: Option<f64>,
+    /// Longitude of the technician's base
+    pub base_longitude: Option<f64>,
+}
+
+/// Data for updating a technician profile. Omitted fields are left
+/// unchanged; lists are replaced whole.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct TechnicianForUpdate {
+    /// New display name
+    pub display_name: Option<String>,
+    /// New contact number
+    pub phone: Option<String>,
+    /// New skills
+    pub skills: Option<Vec<String>>,
+    /// New areas
+    pub areas: Option<Vec<String>>,
+    /// New base latitude (set together with `base_longitude`)
+    pub base_latitude: Option<f64>,
+    /// New base longitude (set together with `base_latitude`)
+    pub base_longitude: Option<f64>,
+    /// Take on or stop taking work
+    pub active: Option<bool>,
+}
+
+/// A technician assigned to a booking.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct BookingAssignment {
+    /// Booking ID
+    pub booking_id: i32,
+    /// Technician's user ID
+    pub user_id: i32,
+    /// Technician's display name
+    pub display_name: String,
+    /// When they were assigned
+    pub assigned_at: OffsetDateTime,
+}
+
+/// Another of a technician's jobs overlapping a booking's time slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ScheduleConflict {
+    /// Technician's user ID
+    pub user_id: i32,
+    /// The overlapping booking
+    pub booking_id: i32,
+    /// Its service type
+    pub service_type: String,
+    /// Its start time
+    pub scheduled_time: Time,
+    /// Its length in minutes
+    pub duration_minutes: i32,
+}
+
+/// How closely a technician's areas cover a job site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AreaMatch {
+    /// The site's district is one of their areas
+    District,
+    /// The site's postcode area is one of their areas
+    Area,
+    /// Not covered, or the site has no postcode
+    None,
+}
+
+/// A ranked candidate for a booking.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TechnicianSuggestion {
+    /// The technician
+    pub technician: Technician,
+    /// Whether the booking's service type is one of their skills
+    pub skill_match: bool,
+    /// How closely their areas cover the job site
+    pub area_match: AreaMatch,
+    /// Their jobs overlapping the booking's slot
+    pub conflicts: Vec<ScheduleConflict>,
+    /// Their jobs on the booking's day
+    pub jobs_that_day: i64,
+}
+
+/// The slot a booking occupies, as read for scheduling.
+#[derive(Debug, FromRow)]
+struct BookingSlot {
+    service_type: String,
+    status: String,
+    scheduled_date: Option<Date>,
+    scheduled_time: Option<Time>,
+    estimated_duration: Option<i32>,
+    postcode: Option<String>,
+}
+
+/// A technician's other job on the same day.
+#[derive(Debug, FromRow)]
+struct DayJob {
+    user_id: i32,
+    booking_id: i32,
+    service_type: String,
+    scheduled_time: Option<Time>,
+    duration_minutes: i32,
+}
+
+/// Business Model Controller for technicians and job assignment.
+pub struct TechnicianBmc;
+
+impl TechnicianBmc {
+    /// Gives an existing user a technician profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `technician` - Profile to create
+    ///
+    /// # Returns
+    ///
+    /// The user ID, which identifies the technician.
+    ///
+    /// # Errors
+    ///
+    /// - `EntityNotFound` if the user does not exist
+    /// - `ValidationError` for a blank name, an invalid area or base, or a
+    ///   user who is already a technician
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm), fields(user_id = technician.user_id))]
+    pub async fn create(mm: &ModelManager, technician: TechnicianForCreate) -> Result<i32> {
+        validate_name(&technician.display_name)?;
+        let skills = normalize_skills(&technician.skills);
+        let areas = normalize_areas(&technician.areas)?;
+        validate_location(technician.base_latitude, technician.base_longitude)?;
+
+        let (user_exists, already): (bool, bool) = sqlx::query_as(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM users WHERE id = $1),
+                   EXISTS (SELECT 1 FROM technicians WHERE user_id = $1)
+            "#,
+        )
+        .bind(technician.user_id)
+        .fetch_one(mm.dbx().db())
+        .await?;
+        if !user_exists {
+            return Err(Error::EntityNotFound {
+                entity: "User",
+                id: technician.user_id as i64,
+            });
+        }
+        if already {
+            return Err(Error::ValidationError(
+                "User is already a technician".into(),
+            ));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO technicians
+                (user_id, display_name, phone, skills, areas, base_latitude, base_longitude)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(technician.user_id)
+        .bind(technician.display_name.trim())
+        .bind(&technician.phone)
+        .bind(&skills)
+        .bind(&areas)
+        .bind(technician.base_latitude)
+        .bind(technician.base_longitude)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(technician.user_id)
+    }
+
+    /// Gets a technician by user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the user is not a technician.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, user_id: i32) -> Result<Technician> {
+        sqlx::query_as(&format!("{TECHNICIAN_SELECT} WHERE t.user_id = $1"))
+            .bind(user_id)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "Technician",
+                id: user_id as i64,
+            })
+    }
+
+    /// Lists technicians by name, optionally only active ones.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, active_only: bool) -> Result<Vec<Technician>> {
+        let technicians = sqlx::query_as(&format!(
+            "{TECHNICIAN_SELECT} WHERE t.active OR NOT $1 ORDER BY t.display_name, t.user_id"
+        ))
+        .bind(active_only)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(technicians)
+    }
+
+    /// Updates a technician profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the user is not a technician, or
+    /// `ValidationError` for a blank name or an invalid area or base.
+    #[instrument(skip(mm))]
+    pub async fn update(mm: &ModelManager, user_id: i32, data: TechnicianForUpdate) -> Result<()> {
+        if let Some(name) = &data.display_name {
+            validate_name(name)?;
+        }
+        let skills = data.skills.as_deref().map(normalize_skills);
+        let areas = data.areas.as_deref().map(normalize_areas).transpose()?;
+        validate_location(data.base_latitude, data.base_longitude)?;
+
+        let rows_affected = sqlx::query(
+            r#"
+            UPDATE technicians
+            SET display_name = COALESCE($2, display_name),
+                phone = COALESCE($3, phone),
+                skills = COALESCE($4, skills),
+                areas = COALESCE($5, areas),
+                active = COALESCE($6, active),
+                base_latitude = COALESCE($7, base_latitude),
+                base_longitude = COALESCE($8, base_longitude)
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(data.display_name.as_deref().map(str::trim))
+        .bind(&data.phone)
+        .bind(&skills)
+        .bind(&areas)
+        .bind(data.active)
+        .bind(data.base_latitude)
+        .bind(data.base_longitude)
+        .execute(mm.dbx().db())
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "Technician",
+                id: user_id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Removes a technician profile and their assignments. The user and
+    /// their recorded time are kept.
+    #[instrument(skip(mm))]
+    pub async fn delete(mm: &ModelManager, user_id: i32) -> Result<()> {
+        let rows_affected = sqlx::query("DELETE FROM technicians WHERE user_id = $1")
+            .bind(user_id)
+            .execute(mm.dbx().db())
+            .await?
+            .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "Technician",
+                id: user_id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Assigns technicians to a booking.
+    ///
+    /// Technicians already on the booking are left as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `booking_id` - Booking to staff
+    /// * `user_ids` - Technicians to add
+    /// * `force` - Assign even if their schedules clash
+    ///
+    /// # Returns
+    ///
+    /// Everyone now assigned to the booking.
+    ///
+    /// # Errors
+    ///
+    /// - `EntityNotFound` if the booking or a technician does not exist
+    /// - `ValidationError` if the booking is completed or cancelled, a
+    ///   technician is inactive, or (unless forced) a schedule clashes
+    #[instrument(skip(mm))]
+    pub async fn assign(
+        mm: &ModelManager,
+        booking_id: i32,
+        user_ids: &[i32],
+        force: bool,
+    ) -> Result<Vec<BookingAssignment>> {
+        if user_ids.is_empty() {
+            return Err(Error::ValidationError(
+                "At least one technician is required".into(),
+            ));
+        }
+        let slot = booking_slot(mm, booking_id).await?;
+        if matches!(slot.status.as_str(), "completed" | "cancelled") {
+            return Err(Error::ValidationError(
+                format!("Cannot assign technicians to a {} booking", slot.status).into(),
+            ));
+        }
+        for &user_id in user_ids {
+            if !Self::get(mm, user_id).await?.active {
+                return Err(Error::ValidationError(
+                    format!("Technician {user_id} is not taking work").into(),
+                ));
+            }
+        }
+
+        if !force {
+            let day_jobs = day_jobs(mm, booking_id, &slot, user_ids).await?;
+            let conflicts = find_conflicts(&slot, &day_jobs);
+            if let Some(conflict) = conflicts.first() {
+                return Err(Error::ValidationError(
+                    format!(
+                        "Technician {} already has booking {} at {} for {} minutes",
+                        conflict.user_id,
+                        conflict.booking_id,
+                        hh_mm(conflict.scheduled_time),
+                        conflict.duration_minutes
+                    )
+                    .into(),
+                ));
+            }
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO booking_assignments (booking_id, user_id)
+            SELECT $1, UNNEST($2::INTEGER[])
+            ON CONFLICT (booking_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(booking_id)
+        .bind(user_ids)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Self::list_assignments(mm, booking_id).await
+    }
+
+    /// Removes a technician from a booking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the technician is not on the booking.
+    #[instrument(skip(mm))]
+    pub async fn unassign(mm: &ModelManager, booking_id: i32, user_id: i32) -> Result<()> {
+        let rows_affected =
+            sqlx::query("DELETE FROM booking_assignments WHERE booking_id = $1 AND user_id = $2")
+                .bind(booking_id)
+                .bind(user_id)
+                .execute(mm.dbx().db())
+                .await?
+                .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(Error::EntityNotFound {
+                entity: "BookingAssignment",
+                id: user_id as i64,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Lists the technicians assigned to a booking, in assignment order.
+    #[instrument(skip(mm))]
+    pub async fn list_assignments(
+        mm: &ModelManager,
+        booking_id: i32,
+    ) -> Result<Vec<BookingAssignment>> {
+        let assignments = sqlx::query_as(
+            r#"
+            SELECT a.booking_id, a.user_id, t.display_name, a.assigned_at
+            FROM booking_assignments a
+            JOIN technicians t ON t.user_id = a.user_id
+            WHERE a.booking_id = $1
+            ORDER BY a.assigned_at ASC, a.user_id ASC
+            "#,
+        )
+        .bind(booking_id)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(assignments)
+    }
+
+    /// Ranks active technicians not yet on a booking as candidates for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the booking does not exist.
+    #[instrument(skip(mm))]
+    pub async fn suggest(mm: &ModelManager, booking_id: i32) -> Result<Vec<TechnicianSuggestion>> {
+        let slot = booking_slot(mm, booking_id).await?;
+        let candidates: Vec<Technician> = sqlx::query_as(&format!(
+            "{TECHNICIAN_SELECT} WHERE t.active AND NOT EXISTS ( \
+                 SELECT 1 FROM booking_assignments a \
+                 WHERE a.booking_id = $1 AND a.user_id = t.user_id)"
+        ))
+        .bind(booking_id)
+        .fetch_all(mm.dbx().db())
+        .await?;
+        let user_ids: Vec<i32> = candidates.iter().map(|t| t.user_id).collect();
+        let day_jobs = day_jobs(mm, booking_id, &slot, &user_ids).await?;
+
+        Ok(rank(&slot, candidates, &day_jobs))
+    }
+
+    /// Lists a technician's bookings by schedule, optionally between two
+    /// `YYYY-MM-DD` dates (inclusive). Unscheduled jobs come last.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the user is not a technician, or
+    /// `ValidationError` for a malformed date.
+    #[instrument(skip(mm))]
+    pub async fn list_bookings(
+        mm: &ModelManager,
+        user_id: i32,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<Vec<Booking>> {
+        let from = parse_date("from", from)?;
+        let to = parse_date("to", to)?;
+        Self::get(mm, user_id).await?;
+
+        let bookings = sqlx::query_as(&format!(
+            r#"
+            SELECT {BOOKING_COLUMNS}
+            FROM bookings
+            WHERE id IN (SELECT booking_id FROM booking_assignments WHERE user_id = $1)
+              AND ($2::DATE IS NULL OR scheduled_date >= $2)
+              AND ($3::DATE IS NULL OR scheduled_date <= $3)
+            ORDER BY scheduled_date ASC NULLS LAST, scheduled_time ASC NULLS LAST, id ASC
+            "#
+        ))
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(bookings)
+    }
+}
+
+// region:    --- Technician Helpers
+
+async fn booking_slot(mm: &ModelManager, booking_id: i32) -> Result<BookingSlot> {
+    sqlx::query_as(
+        r#"
+        SELECT service_type, status, scheduled_date, scheduled_time, estimated_duration, postcode
+        FROM bookings
+        WHERE id = $1
+        "#,
+    )
+    .bind(booking_id)
+    .fetch_optional(mm.dbx().db())
+    .await?
+    .ok_or(Error::EntityNotFound {
+        entity: "Booking",
+        id: booking_id as i64,
+    })
+}
+
+/// The technicians' other live jobs on the booking's day.
+async fn day_jobs(
+    mm: &ModelManager,
+    booking_id: i32,
+    slot: &BookingSlot,
+    user_ids: &[i32],
+) -> Result<Vec<DayJob>> {
+    let Some(date) = slot.scheduled_date else {
+        return Ok(Vec::new());
+    };
+
+    let jobs = sqlx::query_as(
+        r#"
+        SELECT a.user_id, b.id AS booking_id, b.service_type, b.scheduled_time,
+               COALESCE(b.estimated_duration, $4) AS duration_minutes
+        FROM booking_assignments a
+        JOIN bookings b ON b.id = a.booking_id
+        WHERE a.user_id = ANY($1)
+          AND b.scheduled_date = $2
+          AND b.id <> $3
+          AND b.status <> 'cancelled'
+        ORDER BY b.scheduled_time ASC NULLS LAST
+        "#,
+    )
+    .bind(user_ids)
+    .bind(date)
+    .bind(booking_id)
+    .bind(DEFAULT_JOB_MINUTES)
+    .fetch_all(mm.dbx().db())
+    .await?;
+
+    Ok(jobs)
+}
+
+/// Minutes since midnight.
+fn minute_of_day(time: Time) -> i32 {
+    i32::from(time.hour()) * 60 + i32::from(time.minute())
+}
+
+fn hh_mm(time: Time) -> String {
+    format!("{:02}:{:02}", time.hour(), time.minute())
+}
+
+/// Whether two `[start, start + minutes)` slots overlap.
+fn overlaps(a: Time, a_minutes: i32, b: Time, b_minutes: i32) -> bool {
+    let (a, b) = (minute_of_day(a), minute_of_day(b));
+    a < b + b_minutes && b < a + a_minutes
+}
+
+/// Day jobs that overlap the booking's slot.
+fn find_conflicts(slot: &BookingSlot, day_jobs: &[DayJob]) -> Vec<ScheduleConflict> {
+    let Some(start) = slot.scheduled_time else {
+        return Vec::new();
+    };
+    let minutes = slot.estimated_duration.unwrap_or(DEFAULT_JOB_MINUTES);
+
+    day_jobs
+        .iter()
+        .filter_map(|job| {
+            let time = job.scheduled_time?;
+            overlaps(start, minutes, time, job.duration_minutes).then(|| ScheduleConflict {
+                user_id: job.user_id,
+                booking_id: job.booking_id,
+                service_type: job.service_type.clone(),
+                scheduled_time: time,
+                duration_minutes: job.duration_minutes,
+            })
+        })
+        .collect()
+}
+
+/// How closely `areas` cover `site`.
+fn area_match(areas: &[String], site: Option<&str>) -> AreaMatch {
+    let Some(site) = site else {
+        return AreaMatch::None;
+    };
+    let district = postcode::outward(site);
+    let area = postcode::area(site);
+
+    if district.is_some_and(|d| areas.contains(&d)) {
+        AreaMatch::District
+    } else if area.is_some_and(|a| areas.contains(&a)) {
+        AreaMatch::Area
+    } else {
+        AreaMatch::None
+    }
+}
+
+/// Scores and orders candidates for the booking's slot.
+fn rank(
+    slot: &BookingSlot,
+    candidates: Vec<Technician>,
+    day_jobs: &[DayJob],
+) -> Vec<TechnicianSuggestion> {
+    let conflicts = find_conflicts(slot, day_jobs);
+    let service = normalize_skill(&slot.service_type);
+    let mut suggestions: Vec<TechnicianSuggestion> = candidates
+        .into_iter()
+        .map(|technician| TechnicianSuggestion {
+            skill_match: technician.skills.contains(&service),
+            area_match: area_match(&technician.areas, slot.postcode.as_deref()),
+            conflicts: conflicts
+                .iter()
+                .filter(|c| c.user_id == technician.user_id)
+                .cloned()
+                .collect(),
+            jobs_that_day: day_jobs
+                .iter()
+                .filter(|j| j.user_id == technician.user_id)
+                .count() as i64,
+            technician,
+        })
+        .collect();
+
+    suggestions.sort_by_cached_key(|s| {
+        (
+            !s.conflicts.is_empty(),
+            Reverse(s.skill_match),
+            s.area_match,
+            s.jobs_that_day,
+            s.technician.display_name.to_lowercase(),
+        )
+    });
+
+    suggestions
+}
+
+/// Skills compare like service types: lower case, words joined by `_`.
+fn normalize_skill(skill: &str) -> String {
+    skill
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn normalize_skills(skills: &[String]) -> Vec<String> {
+    let mut skills: Vec<String> = skills
+        .iter()
+        .map(|s| normalize_skill(s))
+        .filter(|s| !s.is_empty())
+        .collect();
+    skills.sort();
+    skills.dedup();
+
+    skills
+}
+
+/// Areas are stored as canonical areas or districts.
+fn normalize_areas(areas: &[String]) -> Result<Vec<String>> {
+    let mut normalized = areas
+        .iter()
+        .map(|a| {
+            postcode::outward(a).ok_or_else(|| {
+                Error::ValidationError(format!("{a} is not a postcode area or district").into())
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    normalized.sort();
+    normalized.dedup();
+
+    Ok(normalized)
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(Error::ValidationError("Technician name is required".into()));
+    }
+
+    Ok(())
+}
+
+// endregion: --- Technician Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::booking::{BookingBmc, BookingForCreate, BookingForUpdate};
+
+    fn at(hour: u8, minute: u8) -> Time {
+        Time::from_hms(hour, minute, 0).unwrap()
+    }
+
+    fn fx_slot(time: Option<Time>, postcode: Option<&str>) -> BookingSlot {
+        BookingSlot {
+            service_type: "Plumbing".to_string(),
+            status: "confirmed".to_string(),
+            scheduled_date: None,
+            scheduled_time: time,
+            estimated_duration: Some(90),
+            postcode: postcode.map(str::to_string),
+        }
+    }
+
+    fn fx_job(user_id: i32, time: Option<Time>, minutes: i32) -> DayJob {
+        DayJob {
+            user_id,
+            booking_id: 100 + user_id,
+            service_type: "electrical".to_string(),
+            scheduled_time: time,
+            duration_minutes: minutes,
+        }
+    }
+
+    fn fx_technician(user_id: i32, name: &str, skills: &[&str], areas: &[&str]) -> Technician {
+        Technician {
+            user_id,
+            username: name.to_lowercase(),
+            display_name: name.to_string(),
+            phone: None,
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            areas: areas.iter().map(|a| a.to_string()).collect(),
+            base_latitude: None,
+            base_longitude: None,
+            active: true,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let slot = fx_slot(Some(at(10, 0)), None);
+        let jobs = vec![
+            fx_job(1, Some(at(9, 0)), 60),   // ends as ours starts
+            fx_job(2, Some(at(11, 0)), 30),  // inside ours
+            fx_job(3, Some(at(8, 0)), 150),  // runs into ours
+            fx_job(4, None, 60),             // no time set
+            fx_job(5, Some(at(11, 30)), 60), // starts as ours ends
+        ];
+
+        let conflicts = find_conflicts(&slot, &jobs);
+
+        assert_eq!(
+            conflicts.iter().map(|c| c.user_id).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert!(find_conflicts(&fx_slot(None, None), &jobs).is_empty());
+    }
+
+    #[test]
+    fn test_area_match() {
+        let areas = vec!["E14".to_string(), "SW".to_string()];
+
+        assert_eq!(area_match(&areas, Some("E14 5AB")), AreaMatch::District);
+        assert_eq!(area_match(&areas, Some("SW1A 1AA")), AreaMatch::Area);
+        assert_eq!(area_match(&areas, Some("E1 6AN")), AreaMatch::None);
+        assert_eq!(area_match(&areas, None), AreaMatch::None);
+    }
+
+    #[test]
+    fn test_rank_orders_by_availability_skill_area_and_load() {
+        let slot = fx_slot(Some(at(10, 0)), Some("SW1A 1AA"));
+        let candidates = vec![
+            fx_technician(1, "Busy", &["plumbing"], &["SW1A"]),
+            fx_technician(2, "Generalist", &["carpentry"], &["SW1A"]),
+            fx_technician(3, "Nearby", &["plumbing"], &["SW"]),
+            fx_technician(4, "Local", &["plumbing"], &["SW1A"]),
+            fx_technician(5, "Loaded", &["plumbing"], &["SW1A"]),
+        ];
+        let jobs = vec![
+            fx_job(1, Some(at(10, 30)), 60),
+            fx_job(5, Some(at(14, 0)), 60),
+        ];
+
+        let ranked = rank(&slot, candidates, &jobs);
+
+        assert_eq!(
+            ranked
+                .iter()
+                .map(|s| s.technician.display_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Local", "Loaded", "Nearby", "Generalist", "Busy"]
+        );
+        assert!(ranked[0].skill_match);
+        assert_eq!(ranked[2].area_match, AreaMatch::Area);
+        assert_eq!(ranked[4].conflicts.len(), 1);
+    }
+
+    #[test]
+    fn test_normalize_skills_and_areas() {
+        assert_eq!(
+            normalize_skills(&[
+                "General Repair".to_string(),
+                "plumbing".to_string(),
+                " Plumbing ".to_string()
+            ]),
+            vec!["general_repair", "plumbing"]
+        );
+        assert_eq!(
+            normalize_areas(&["sw1a".to_string(), "e14 5ab".to_string()]).unwrap(),
+            vec!["E14", "SW1A"]
+        );
+        assert!(normalize_areas(&["London".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_assign_detects_conflicts() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let (user_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO users (username, email, pwd_hash, token_salt) \
+             VALUES ('test_tech_assign', 'test_tech_assign@example.com', 'x', gen_random_uuid()) \
+             RETURNING id",
+        )
+        .fetch_one(mm.dbx().db())
+        .await?;
+        let _ = TechnicianBmc::create(
+            &mm,
+            TechnicianForCreate {
+                user_id,
+                display_name: "Test Tech".to_string(),
+                phone: None,
+                skills: vec!["test_assign".to_string()],
+                areas: vec!["SW".to_string()],
+                base_latitude: None,
+                base_longitude: None,
+            },
+        )
+        .await?;
+        let mut bookings = Vec::new();
+        for time in ["09:00", "09:30"] {
+            let id = BookingBmc::create(
+                &mm,
+                BookingForCreate {
+                    customer_id: None,
+                    service_type: "test_assign".to_string(),
+                    scheduled_date: None,
+                    scheduled_time: None,
+                    notes: None,
+                },
+            )
+            .await?;
+            BookingBmc::update(
+                &mm,
+                id,
+                BookingForUpdate {
+                    scheduled_date: Some("2026-11-02".to_string()),
+                    scheduled_time: Some(time.to_string()),
+                    postcode: Some("SW1A 1AA".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            bookings.push(id);
+        }
+
+        // Execute
+        TechnicianBmc::assign(&mm, bookings[0], &[user_id], false).await?;
+        let clash = TechnicianBmc::assign(&mm, bookings[1], &[user_id], false).await;
+        let suggestions = TechnicianBmc::suggest(&mm, bookings[1]).await?;
+        let forced = TechnicianBmc::assign(&mm, bookings[1], &[user_id], true).await?;
+        let jobs = TechnicianBmc::list_bookings(&mm, user_id, Some("2026-11-02"), None).await?;
+
+        // Check
+        assert!(matches!(clash, Err(Error::ValidationError(_))));
+        let ours = suggestions
+            .iter()
+            .find(|s| s.technician.user_id == user_id)
+            .expect("technician should be suggested");
+        assert!(ours.skill_match);
+        assert_eq!(ours.area_match, AreaMatch::Area);
+        assert_eq!(ours.conflicts.len(), 1);
+        assert_eq!(forced.len(), 1);
+        assert_eq!(
+            jobs.iter().map(|b| b.id).collect::<Vec<_>>(),
+            bookings.clone()
+        );
+
+        // Cleanup
+        for id in bookings {
+            BookingBmc::delete(&mm, id).await?;
+        }
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(mm.dbx().db())
+            .await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,775 @@
+//! # Tenant Model
+//!
+//! The client businesses hosted from this deployment. Each tenant has its
+//! own domains, contact details and branding, and owns its own customers,
+//! bookings, quotes, prices and landing pages.
+//!
+//! New clients usually arrive through onboarding (see
+//! [`crate::model::onboarding`]), which creates their tenant when an admin
+//! publishes it.
+//!
+//! Requests are matched to a tenant by host name ([`TenantBmc::resolve`]);
+//! the default tenant also serves the `DEFAULT_TENANT_HOSTS`, such as
+//! `localhost`, and other hosts are served by no tenant at all.
+//! [`ModelManager::for_tenant`] then gives a ModelManager whose queries the
+//! database limits to that tenant's rows, so the other models need no
+//! tenant filters of their own.
+//!
+//! ## Structures
+//!
+//! - [`Tenant`] - Tenant record from database
+//! - [`TenantForCreate`] - Data required to add a tenant
+//! - [`TenantForUpdate`] - Data for updating a tenant
+//! - [`TenantBmc`] - Business Model Controller for tenants
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::tenant::TenantBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn for_host(mm: &ModelManager, host: &str) -> lib_core::model::Result<ModelManager> {
+//!     let tenant = TenantBmc::resolve(mm, host).await?;
+//!     mm.for_tenant(tenant.id)
+//! }
+//! ```
+
+use crate::core_config;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use crate::text::{is_slug, slugify};
+use serde::{Deserialize, Serialize};
+use shared::{OpeningHours, Testimonial, Validate};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// The tenant owning everything that predates multi-tenancy, and serving
+/// the `DEFAULT_TENANT_HOSTS`.
+pub const DEFAULT_TENANT_ID: i32 = 1;
+
+/// Select list for [`Tenant`].
+const TENANT_SELECT: &str = "SELECT id, slug, business_name, domains, phone, email, tagline, \
+     primary_color, accent_color, logo_url, centre_latitude, centre_longitude, trade, \
+     opening_hours, accreditations, testimonials, noindex, active, created_at, updated_at \
+     FROM tenants";
+
+/// Document sequences every tenant numbers separately.
+const DOCUMENT_KINDS: [&str; 2] = ["invoice", "credit_note"];
+
+/// Tenant record from the database.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct Tenant {
+    /// Tenant ID
+    pub id: i32,
+    /// Short name, e.g. `coventry-handyman`
+    pub slug: String,
+    /// Trading name shown on the site and documents
+    pub business_name: String,
+    /// Hosts the site is served on, lowercase without `www.`
+    pub domains: Vec<String>,
+    /// Phone number customers call
+    pub phone: Option<String>,
+    /// Contact email
+    pub email: Option<String>,
+    /// One-line strapline
+    pub tagline: Option<String>,
+    /// Main brand colour, `#rrggbb`
+    pub primary_color: Option<String>,
+    /// Highlight colour, `#rrggbb`
+    pub accent_color: Option<String>,
+    /// Logo image URL
+    pub logo_url: Option<String>,
+    /// Latitude coverage distances are measured from
+    pub centre_latitude: Option<f64>,
+    /// Longitude coverage distances are measured from
+    pub centre_longitude: Option<f64>,
+    /// Trade, e.g. "Plumber"
+    pub trade: Option<String>,
+    /// JSON array of [`OpeningHours`]
+    pub opening_hours: serde_json::Value,
+    /// Trade bodies and schemes
+    pub accreditations: Vec<String>,
+    /// JSON array of [`Testimonial`]s
+    pub testimonials: serde_json::Value,
+    /// Whether search engines are asked not to index the tenant's sites
+    pub noindex: bool,
+    /// Whether the tenant's sites are served
+    pub active: bool,
+    /// When the tenant was added
+    pub created_at: Option<OffsetDateTime>,
+    /// When the tenant was last updated
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl Tenant {
+    /// The origin of the tenant's site, e.g. `https://jonesplumbing.co.uk`:
+    /// its first domain, or `PUBLIC_BASE_URL` for the default tenant and a
+    /// tenant without domains.
+    pub fn origin(&self) -> String {
+        match self.domains.first() {
+            Some(domain) if self.id != DEFAULT_TENANT_ID => format!("https://{}", domain),
+            _ => core_config()
+                .PUBLIC_BASE_URL
+                .trim_end_matches('/')
+                .to_string(),
+        }
+    }
+
+    /// When the business is open.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored hours are malformed.
+    pub fn opening_hours_list(&self) -> Result<Vec<OpeningHours>> {
+        parse(&self.opening_hours, "opening hours")
+    }
+
+    /// What customers have said.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the stored testimonials are malformed.
+    pub fn testimonial_list(&self) -> Result<Vec<Testimonial>> {
+        parse(&self.testimonials, "testimonials")
+    }
+}
+
+/// Data required to add a tenant.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct TenantForCreate {
+    /// Short name (default: from the business name)
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Trading name
+    pub business_name: String,
+    /// Hosts to serve the site on
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// Phone number
+    #[serde(default)]
+    pub phone: Option<String>,
+    /// Contact email
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Strapline
+    #[serde(default)]
+    pub tagline: Option<String>,
+    /// Main brand colour, `#rrggbb`
+    #[serde(default)]
+    pub primary_color: Option<String>,
+    /// Highlight colour, `#rrggbb`
+    #[serde(default)]
+    pub accent_color: Option<String>,
+    /// Logo image URL
+    #[serde(default)]
+    pub logo_url: Option<String>,
+    /// Latitude coverage distances are measured from
+    #[serde(default)]
+    pub centre_latitude: Option<f64>,
+    /// Longitude coverage distances are measured from
+    #[serde(default)]
+    pub centre_longitude: Option<f64>,
+    /// Trade
+    #[serde(default)]
+    pub trade: Option<String>,
+    /// When the business is open
+    #[serde(default)]
+    pub opening_hours: Vec<OpeningHours>,
+    /// Trade bodies and schemes
+    #[serde(default)]
+    pub accreditations: Vec<String>,
+    /// What customers have said
+    #[serde(default)]
+    pub testimonials: Vec<Testimonial>,
+    /// Ask search engines not to index the tenant's sites
+    #[serde(default)]
+    pub noindex: bool,
+    /// Whether to serve the tenant's sites straight away
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Data for updating a tenant. Omitted fields are left unchanged; domains
+/// are replaced whole.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct TenantForUpdate {
+    /// New trading name
+    pub business_name: Option<String>,
+    /// New hosts
+    pub domains: Option<Vec<String>>,
+    /// New phone number
+    pub phone: Option<String>,
+    /// New contact email
+    pub email: Option<String>,
+    /// New strapline
+    pub tagline: Option<String>,
+    /// New brand colour
+    pub primary_color: Option<String>,
+    /// New highlight colour
+    pub accent_color: Option<String>,
+    /// New logo URL
+    pub logo_url: Option<String>,
+    /// New centre latitude
+    pub centre_latitude: Option<f64>,
+    /// New centre longitude
+    pub centre_longitude: Option<f64>,
+    /// New trade
+    pub trade: Option<String>,
+    /// New opening hours
+    pub opening_hours: Option<Vec<OpeningHours>>,
+    /// New accreditations
+    pub accreditations: Option<Vec<String>>,
+    /// New testimonials
+    pub testimonials: Option<Vec<Testimonial>>,
+    /// Ask search engines not to index, or to index, the tenant's sites
+    pub noindex: Option<bool>,
+    /// Serve or suspend the tenant's sites
+    pub active: Option<bool>,
+}
+
+/// Business Model Controller for tenants.
+pub struct TenantBmc;
+
+impl TenantBmc {
+    /// Adds a tenant, with its own invoice and credit note numbering.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `tenant` - Tenant to add
+    ///
+    /// # Returns
+    ///
+    /// The new tenant's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for a blank business name, an invalid or
+    /// taken slug, an invalid domain or one another tenant serves, a
+    /// colour that isn't `#rrggbb`, or invalid opening hours or
+    /// testimonials.
+    #[must_use = "the returned ID should be used or logged"]
+    #[instrument(skip(mm, tenant), fields(business_name = %tenant.business_name))]
+    pub async fn create(mm: &ModelManager, tenant: TenantForCreate) -> Result<i32> {
+        let slug = tenant
+            .slug
+            .clone()
+            .unwrap_or_else(|| slugify(&tenant.business_name));
+        let domains = normalize_domains(&tenant.domains)?;
+        let others = Self::list(mm, false).await?;
+        check_tenant(
+            None,
+            &slug,
+            &tenant.business_name,
+            &domains,
+            [&tenant.primary_color, &tenant.accent_color],
+            &others,
+        )?;
+        check_profile(&tenant.opening_hours, &tenant.testimonials)?;
+
+        let (id,): (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO tenants
+                (slug, business_name, domains, phone, email, tagline, primary_color,
+                 accent_color, logo_url, centre_latitude, centre_longitude, trade,
+                 opening_hours, accreditations, testimonials, noindex, active)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            RETURNING id
+            "#,
+        )
+        .bind(&slug)
+        .bind(tenant.business_name.trim())
+        .bind(&domains)
+        .bind(&tenant.phone)
+        .bind(&tenant.email)
+        .bind(&tenant.tagline)
+        .bind(lowercase(&tenant.primary_color))
+        .bind(lowercase(&tenant.accent_color))
+        .bind(&tenant.logo_url)
+        .bind(tenant.centre_latitude)
+        .bind(tenant.centre_longitude)
+        .bind(&tenant.trade)
+        .bind(to_json(&tenant.opening_hours)?)
+        .bind(&tenant.accreditations)
+        .bind(to_json(&tenant.testimonials)?)
+        .bind(tenant.noindex)
+        .bind(tenant.active)
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        // Counters are tenant rows, so they're written as the new tenant
+        let tenant_mm = mm.for_tenant(id)?;
+        for kind in DOCUMENT_KINDS {
+            sqlx::query("INSERT INTO document_counters (kind, next_number) VALUES ($1, 1)")
+                .bind(kind)
+                .execute(tenant_mm.dbx().db())
+                .await?;
+        }
+
+        Ok(id)
+    }
+
+    /// Gets a tenant by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the tenant does not exist.
+    #[instrument(skip(mm))]
+    pub async fn get(mm: &ModelManager, id: i32) -> Result<Tenant> {
+        sqlx::query_as(&format!("{TENANT_SELECT} WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "Tenant",
+                id: id as i64,
+            })
+    }
+
+    /// Lists tenants by ID, optionally only active ones.
+    #[instrument(skip(mm))]
+    pub async fn list(mm: &ModelManager, active_only: bool) -> Result<Vec<Tenant>> {
+        let tenants = sqlx::query_as(&format!(
+            "{TENANT_SELECT} WHERE active OR NOT $1 ORDER BY id"
+        ))
+        .bind(active_only)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(tenants)
+    }
+
+    /// Finds the tenant serving a host, e.g. from a request's `Host`
+    /// header. Ports, case and a leading `www.` are ignored. The
+    /// `DEFAULT_TENANT_HOSTS` get the default tenant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SlugNotFound` if no active tenant serves the host.
+    #[instrument(skip(mm))]
+    pub async fn resolve(mm: &ModelManager, host: &str) -> Result<Tenant> {
+        let not_found = || Error::SlugNotFound {
+            entity: "Tenant",
+            slug: host.to_string(),
+        };
+        let host = normalize_host(host).ok_or_else(not_found)?;
+        let tenant = sqlx::query_as(&format!(
+            "{TENANT_SELECT} WHERE active AND $1 = ANY(domains) ORDER BY id LIMIT 1"
+        ))
+        .bind(&host)
+        .fetch_optional(mm.dbx().db())
+        .await?;
+        if let Some(tenant) = tenant {
+            return Ok(tenant);
+        }
+
+        let default_hosts = &core_config().DEFAULT_TENANT_HOSTS;
+        if default_hosts
+            .iter()
+            .any(|h| normalize_host(h).as_deref() == Some(host.as_str()))
+        {
+            return Self::get(mm, DEFAULT_TENANT_ID).await;
+        }
+
+        Err(not_found())
+    }
+
+    /// Updates a tenant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the tenant does not exist, or
+    /// `ValidationError` as for [`TenantBmc::create`]. The default tenant
+    /// can't be suspended.
+    #[instrument(skip(mm, data))]
+    pub async fn update(mm: &ModelManager, id: i32, data: TenantForUpdate) -> Result<()> {
+        let current = Self::get(mm, id).await?;
+        if id == DEFAULT_TENANT_ID && data.active == Some(false) {
+            return Err(Error::ValidationError(
+                "The default tenant can't be suspended".into(),
+            ));
+        }
+        let domains = data.domains.as_deref().map(normalize_domains).transpose()?;
+        let others = Self::list(mm, false).await?;
+        check_tenant(
+            Some(id),
+            &current.slug,
+            data.business_name
+                .as_deref()
+                .unwrap_or(&current.business_name),
+            domains.as_deref().unwrap_or(&current.domains),
+            [&data.primary_color, &data.accent_color],
+            &others,
+        )?;
+        check_profile(
+            data.opening_hours.as_deref().unwrap_or_default(),
+            data.testimonials.as_deref().unwrap_or_default(),
+        )?;
+        let opening_hours = data.opening_hours.as_ref().map(to_json).transpose()?;
+        let testimonials = data.testimonials.as_ref().map(to_json).transpose()?;
+
+        sqlx::query(
+            r#"
+            UPDATE tenants
+            SET business_name = COALESCE($2, business_name),
+                domains = COALESCE($3, domains),
+                phone = COALESCE($4, phone),
+                email = COALESCE($5, email),
+                tagline = COALESCE($6, tagline),
+                primary_color = COALESCE($7, primary_color),
+                accent_color = COALESCE($8, accent_color),
+                logo_url = COALESCE($9, logo_url),
+                centre_latitude = COALESCE($10, centre_latitude),
+                centre_longitude = COALESCE($11, centre_longitude),
+                trade = COALESCE($12, trade),
+                opening_hours = COALESCE($13, opening_hours),
+                accreditations = COALESCE($14, accreditations),
+                testimonials = COALESCE($15, testimonials),
+                noindex = COALESCE($16, noindex),
+                active = COALESCE($17, active)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(data.business_name.as_deref().map(str::trim))
+        .bind(&domains)
+        .bind(&data.phone)
+        .bind(&data.email)
+        .bind(&data.tagline)
+        .bind(lowercase(&data.primary_color))
+        .bind(lowercase(&data.accent_color))
+        .bind(&data.logo_url)
+        .bind(data.centre_latitude)
+        .bind(data.centre_longitude)
+        .bind(&data.trade)
+        .bind(opening_hours)
+        .bind(&data.accreditations)
+        .bind(testimonials)
+        .bind(data.noindex)
+        .bind(data.active)
+        .execute(mm.dbx().db())
+        .await?;
+
+        Ok(())
+    }
+}
+
+// region:    --- Tenant Helpers
+
+/// The host a tenant would list: lowercase, without port, trailing dot or
+/// `www.`. `None` for an empty host.
+fn normalize_host(host: &str) -> Option<String> {
+    let host = host.trim().to_ascii_lowercase();
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name.to_string(),
+        _ => host,
+    };
+    let host = host.trim_end_matches('.');
+    let host = host.strip_prefix("www.").unwrap_or(host);
+
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+/// Canonical domains, without repeats.
+fn normalize_domains(domains: &[String]) -> Result<Vec<String>> {
+    let mut hosts: Vec<String> = Vec::with_capacity(domains.len());
+    for domain in domains {
+        let host = normalize_host(domain)
+            .filter(|h| is_host(h))
+            .ok_or_else(|| Error::ValidationError(format!("{domain} is not a domain").into()))?;
+        if !hosts.contains(&host) {
+            hosts.push(host);
+        }
+    }
+
+    Ok(hosts)
+}
+
+/// Whether a string is a plain host name: dot-separated labels of letters,
+/// digits and inner hyphens.
+fn is_host(host: &str) -> bool {
+    host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        })
+}
+
+/// Whether a colour is `#rrggbb`.
+fn is_hex_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn lowercase(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::to_ascii_lowercase)
+}
+
+/// Checks a tenant's fields, and that it doesn't claim another tenant's
+/// slug or domains.
+fn check_tenant(
+    id: Option<i32>,
+    slug: &str,
+    business_name: &str,
+    domains: &[String],
+    colors: [&Option<String>; 2],
+    tenants: &[Tenant],
+) -> Result<()> {
+    if business_name.trim().is_empty() {
+        return Err(Error::ValidationError(
+            "A tenant needs a business name".into(),
+        ));
+    }
+    if !is_slug(slug) {
+        return Err(Error::ValidationError(
+            format!(
+                "Slug must be lowercase words joined by hyphens, like {}",
+                slugify(business_name)
+            )
+            .into(),
+        ));
+    }
+    if let Some(color) = colors.into_iter().flatten().find(|c| !is_hex_color(c)) {
+        return Err(Error::ValidationError(
+            format!("{color} is not a colour like #1e3a8a").into(),
+        ));
+    }
+
+    for other in tenants.iter().filter(|t| Some(t.id) != id) {
+        if other.slug == slug {
+            return Err(Error::ValidationError(
+                format!("Slug {slug} is already used by {}", other.business_name).into(),
+            ));
+        }
+        if let Some(domain) = domains.iter().find(|d| other.domains.contains(d)) {
+            return Err(Error::ValidationError(
+                format!("{domain} is already served for {}", other.business_name).into(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the opening hours and testimonials a site shows.
+fn check_profile(opening_hours: &[OpeningHours], testimonials: &[Testimonial]) -> Result<()> {
+    for hours in opening_hours {
+        hours.validate().map_err(Error::ValidationError)?;
+    }
+    for testimonial in testimonials {
+        testimonial.validate().map_err(Error::ValidationError)?;
+    }
+
+    Ok(())
+}
+
+fn parse<T: serde::de::DeserializeOwned>(value: &serde_json::Value, what: &str) -> Result<T> {
+    serde_json::from_value(value.clone())
+        .map_err(|e| Error::ValidationError(format!("Malformed tenant {what}: {e}").into()))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<serde_json::Value> {
+    serde_json::to_value(value).map_err(|e| Error::Sqlx(sqlx::Error::Decode(Box::new(e))))
+}
+
+// endregion: --- Tenant Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+
+    fn fx_tenant(id: i32, slug: &str, domains: &[&str]) -> Tenant {
+        Tenant {
+            id,
+            slug: slug.to_string(),
+            business_name: slug.to_uppercase(),
+            domains: domains.iter().map(|d| d.to_string()).collect(),
+            phone: None,
+            email: None,
+            tagline: None,
+            primary_color: None,
+            accent_color: None,
+            logo_url: None,
+            centre_latitude: None,
+            centre_longitude: None,
+            trade: None,
+            opening_hours: serde_json::json!([]),
+            accreditations: vec![],
+            testimonials: serde_json::json!([]),
+            noindex: false,
+            active: true,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_host() {
+        assert_eq!(
+            normalize_host("WWW.JonesPlumbing.co.uk:443").as_deref(),
+            Some("jonesplumbing.co.uk")
+        );
+        assert_eq!(
+            normalize_host("example.com.").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            normalize_host("localhost:3000").as_deref(),
+            Some("localhost")
+        );
+        assert_eq!(normalize_host("  "), None);
+    }
+
+    #[test]
+    fn test_normalize_domains() {
+        let domains = ["www.example.com", "Example.com", "shop.example.com"].map(String::from);
+
+        assert_eq!(
+            normalize_domains(&domains).unwrap(),
+            ["example.com", "shop.example.com"]
+        );
+        assert!(normalize_domains(&["https://example.com".to_string()]).is_err());
+        assert!(normalize_domains(&["-bad.example.com".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_tenant_origin() {
+        let public = core_config().PUBLIC_BASE_URL.trim_end_matches('/');
+
+        assert_eq!(
+            fx_tenant(2, "jones", &["jonesplumbing.co.uk", "jones.co.uk"]).origin(),
+            "https://jonesplumbing.co.uk"
+        );
+        assert_eq!(fx_tenant(2, "jones", &[]).origin(), public);
+        assert_eq!(
+            fx_tenant(
+                DEFAULT_TENANT_ID,
+                "coventry-handyman",
+                &["coventryhandyman.co.uk"]
+            )
+            .origin(),
+            public
+        );
+    }
+
+    #[test]
+    fn test_check_tenant() {
+        let tenants = [fx_tenant(
+            1,
+            "coventry-handyman",
+            &["coventryhandyman.co.uk"],
+        )];
+        let colour = |c: &str| Some(c.to_string());
+        let check = |id, slug, domains: &[&str], primary: &Option<String>| {
+            let domains: Vec<String> = domains.iter().map(|d| d.to_string()).collect();
+            check_tenant(id, slug, "Jones", &domains, [primary, &None], &tenants)
+        };
+
+        assert!(check(None, "jones", &["jones.co.uk"], &colour("#0F766E")).is_ok());
+        assert!(check(None, "Jones", &["jones.co.uk"], &None).is_err());
+        assert!(check(None, "coventry-handyman", &["jones.co.uk"], &None).is_err());
+        assert!(check(None, "jones", &["coventryhandyman.co.uk"], &None).is_err());
+        assert!(check(None, "jones", &[], &colour("teal")).is_err());
+        assert!(check(None, "jones", &[], &colour("#0f766")).is_err());
+        // A tenant may keep its own slug and domains
+        assert!(check(
+            Some(1),
+            "coventry-handyman",
+            &["coventryhandyman.co.uk"],
+            &None
+        )
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_resolve_update() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let suffix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let domain = format!("test-{suffix}.example.com");
+        let tenant = TenantForCreate {
+            slug: Some(format!("test-tenant-{suffix}")),
+            business_name: "Test Plumbing".to_string(),
+            domains: vec![domain.clone()],
+            phone: Some("01234 567890".to_string()),
+            email: None,
+            tagline: None,
+            primary_color: Some("#0F766E".to_string()),
+            accent_color: None,
+            logo_url: None,
+            centre_latitude: None,
+            centre_longitude: None,
+            trade: Some("Plumber".to_string()),
+            opening_hours: vec![OpeningHours {
+                days: "Monday - Friday".to_string(),
+                opens: "08:00".to_string(),
+                closes: "17:00".to_string(),
+            }],
+            accreditations: vec![],
+            testimonials: vec![],
+            noindex: false,
+            active: true,
+        };
+
+        // Execute
+        let id = TenantBmc::create(&mm, tenant.clone()).await?;
+        let duplicate = TenantBmc::create(&mm, tenant).await;
+        let resolved = TenantBmc::resolve(&mm, &format!("www.{domain}:8080")).await?;
+        let unknown = TenantBmc::resolve(&mm, "unknown.example.com").await;
+        let default = TenantBmc::resolve(&mm, "localhost:3000").await?;
+        let tenant_mm = mm.for_tenant(id)?;
+        let (visible_tenants,): (i64,) =
+            sqlx::query_as("SELECT COUNT(DISTINCT tenant_id) FROM document_counters")
+                .fetch_one(tenant_mm.dbx().db())
+                .await?;
+        TenantBmc::update(
+            &mm,
+            id,
+            TenantForUpdate {
+                active: Some(false),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let suspended = TenantBmc::resolve(&mm, &domain).await;
+
+        // Check
+        assert!(duplicate.is_err(), "Slug and domain must be unique");
+        assert_eq!(resolved.id, id);
+        assert_eq!(resolved.primary_color.as_deref(), Some("#0f766e"));
+        assert_eq!(resolved.opening_hours_list()?[0].closes, "17:00");
+        assert!(matches!(unknown, Err(Error::SlugNotFound { .. })));
+        assert_eq!(default.id, DEFAULT_TENANT_ID);
+        assert_eq!(visible_tenants, 1, "A tenant only sees its own rows");
+        assert!(
+            matches!(suspended, Err(Error::SlugNotFound { .. })),
+            "Suspended tenants serve nothing"
+        );
+
+        // Cleanup
+        sqlx::query("DELETE FROM document_counters")
+            .execute(tenant_mm.dbx().db())
+            .await?;
+        sqlx::query("DELETE FROM tenants WHERE id = $1")
+            .bind(id)
+            .execute(mm.dbx().db())
+            .await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
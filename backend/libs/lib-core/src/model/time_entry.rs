@@ -0,0 +1,733 @@
+//! # Time Entry Model
+//!
+//! Time technicians spend on jobs, and weekly timesheets built from it.
+//!
+//! Each stretch of work is one entry. Starting or resuming a job opens an
+//! entry for the technician; pausing closes it, and stopping the job closes
+//! every open entry and completes the booking. All timestamps come from the
+//! database clock, and the booking's `actual_duration` is recomputed from
+//! its closed entries each time one closes, so it always matches the
+//! recorded time.
+//!
+//! A technician can be clocked in on one job at a time. Clock actions for a
+//! technician are serialised by locking their user row, and the table's
+//! exclusion constraint rejects overlapping entries as a backstop.
+//!
+//! ## Structures
+//!
+//! - [`TimeEntry`] - One stretch of work on a booking
+//! - [`Timesheet`] - A technician's week, per entry and per day
+//! - [`TimesheetEntry`] - An entry as it falls within the week
+//! - [`TimesheetDay`] - Minutes worked on one day
+//! - [`TimeEntryBmc`] - Business Model Controller for time tracking
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::time_entry::TimeEntryBmc;
+//! use lib_core::model::ModelManager;
+//! use std::error::Error;
+//!
+//! async fn two_visits(mm: &ModelManager, job: i32, tech: i32) -> Result<(), Box<dyn Error>> {
+//!     TimeEntryBmc::start(mm, job, tech).await?;
+//!     TimeEntryBmc::pause(mm, job, tech).await?;
+//!     // ... next day
+//!     TimeEntryBmc::resume(mm, job, tech).await?;
+//!     let minutes = TimeEntryBmc::stop(mm, job).await?;
+//!     println!("Job took {minutes} minutes");
+//!     Ok(())
+//! }
+//! ```
+
+use crate::model::margin::parse_date;
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt::Write;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, Duration, OffsetDateTime};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// Columns selected for [`TimeEntry`].
+const TIME_ENTRY_COLUMNS: &str = "id, booking_id, user_id, started_at, ended_at, ended_by";
+
+/// One stretch of work by a technician on a booking.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct TimeEntry {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Booking worked on
+    pub booking_id: i32,
+    /// Technician (user) who did the work
+    pub user_id: i32,
+    /// When the clock started
+    pub started_at: OffsetDateTime,
+    /// When the clock stopped (`None` while running)
+    pub ended_at: Option<OffsetDateTime>,
+    /// What closed the entry: pause or stop
+    pub ended_by: Option<String>,
+}
+
+/// An entry as it falls within a timesheet's week.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TimesheetEntry {
+    /// Time entry ID
+    pub entry_id: i32,
+    /// Booking worked on
+    pub booking_id: i32,
+    /// Service type of the booking
+    pub service_type: String,
+    /// When the clock started
+    pub started_at: OffsetDateTime,
+    /// When the clock stopped (`None` while running)
+    pub ended_at: Option<OffsetDateTime>,
+    /// Minutes of the entry inside the week (running entries count to now)
+    pub minutes: i64,
+}
+
+/// Minutes worked on one day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TimesheetDay {
+    /// The day (UTC)
+    pub date: Date,
+    /// Minutes worked
+    pub minutes: i64,
+}
+
+/// A technician's time for one week, Monday to Sunday (UTC).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Timesheet {
+    /// Technician (user) ID
+    pub user_id: i32,
+    /// Technician's username
+    pub username: String,
+    /// Monday the week starts on
+    pub week_start: Date,
+    /// Sunday the week ends on
+    pub week_end: Date,
+    /// Entries overlapping the week, in start order
+    pub entries: Vec<TimesheetEntry>,
+    /// Minutes per day, Monday first
+    pub days: Vec<TimesheetDay>,
+    /// Minutes in the week
+    pub total_minutes: i64,
+}
+
+impl Timesheet {
+    /// The timesheet as CSV: one row per entry, then a total row.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("date,booking_id,service_type,started_at,ended_at,minutes\n");
+        for entry in &self.entries {
+            let date = entry.started_at.date().max(self.week_start);
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                date,
+                entry.booking_id,
+                csv_field(&entry.service_type),
+                rfc3339(entry.started_at),
+                entry.ended_at.map(rfc3339).unwrap_or_default(),
+                entry.minutes
+            );
+        }
+        let _ = writeln!(csv, "total,,,,,{}", self.total_minutes);
+
+        csv
+    }
+}
+
+/// Entry and booking details read for a timesheet.
+#[derive(Debug, FromRow)]
+struct TimesheetRow {
+    id: i32,
+    booking_id: i32,
+    service_type: String,
+    started_at: OffsetDateTime,
+    ended_at: Option<OffsetDateTime>,
+}
+
+/// Business Model Controller for job time tracking.
+pub struct TimeEntryBmc;
+
+impl TimeEntryBmc {
+    /// Starts the clock for a technician on a booking.
+    ///
+    /// The first start on a booking also sets its `started_at`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `booking_id` - Booking to work on
+    /// * `user_id` - Technician starting work
+    ///
+    /// # Returns
+    ///
+    /// The new, running entry.
+    ///
+    /// # Errors
+    ///
+    /// - `EntityNotFound` if the booking or user does not exist
+    /// - `ValidationError` if the booking is completed or cancelled, or the
+    ///   technician is already clocked in on a job
+    #[instrument(skip(mm))]
+    pub async fn start(mm: &ModelManager, booking_id: i32, user_id: i32) -> Result<TimeEntry> {
+        Self::clock_in(mm, booking_id, user_id, false).await
+    }
+
+    /// Restarts the clock for a technician after a pause.
+    ///
+    /// # Errors
+    ///
+    /// As [`TimeEntryBmc::start`], and `ValidationError` if the technician
+    /// has no earlier time on the booking.
+    #[instrument(skip(mm))]
+    pub async fn resume(mm: &ModelManager, booking_id: i32, user_id: i32) -> Result<TimeEntry> {
+        Self::clock_in(mm, booking_id, user_id, true).await
+    }
+
+    /// Stops the clock for a technician, leaving the job open.
+    ///
+    /// # Returns
+    ///
+    /// The closed entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the technician is not clocked in on the
+    /// booking.
+    #[instrument(skip(mm))]
+    pub async fn pause(mm: &ModelManager, booking_id: i32, user_id: i32) -> Result<TimeEntry> {
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        lock_user(&mm, user_id).await?;
+        let entry: TimeEntry = dbx
+            .fetch_optional(
+                sqlx::query_as(&format!(
+                    r#"
+                    UPDATE time_entries
+                    SET ended_at = CURRENT_TIMESTAMP, ended_by = 'pause'
+                    WHERE booking_id = $1 AND user_id = $2 AND ended_at IS NULL
+                    RETURNING {TIME_ENTRY_COLUMNS}
+                    "#
+                ))
+                .bind(booking_id)
+                .bind(user_id),
+            )
+            .await?
+            .ok_or_else(|| {
+                Error::ValidationError("Technician is not clocked in on this booking".into())
+            })?;
+        refresh_duration(&mm, booking_id).await?;
+
+        dbx.commit_txn().await?;
+
+        Ok(entry)
+    }
+
+    /// Stops the job: closes every running entry on the booking and marks
+    /// it completed, with `actual_duration` from the recorded time.
+    ///
+    /// # Returns
+    ///
+    /// The booking's total recorded minutes.
+    ///
+    /// # Errors
+    ///
+    /// - `EntityNotFound` if the booking does not exist
+    /// - `ValidationError` if the booking is completed or cancelled, or no
+    ///   time has been recorded on it
+    #[instrument(skip(mm))]
+    pub async fn stop(mm: &ModelManager, booking_id: i32) -> Result<i32> {
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        lock_open_booking(&mm, booking_id).await?;
+        dbx.execute(
+            sqlx::query(
+                r#"
+                UPDATE time_entries
+                SET ended_at = CURRENT_TIMESTAMP, ended_by = 'stop'
+                WHERE booking_id = $1 AND ended_at IS NULL
+                "#,
+            )
+            .bind(booking_id),
+        )
+        .await?;
+        let (entries,): (i64,) = dbx
+            .fetch_one(
+                sqlx::query_as("SELECT COUNT(*) FROM time_entries WHERE booking_id = $1")
+                    .bind(booking_id),
+            )
+            .await?;
+        if entries == 0 {
+            return Err(Error::ValidationError(
+                "No time has been recorded on this booking".into(),
+            ));
+        }
+        let minutes = refresh_duration(&mm, booking_id).await?;
+        dbx.execute(
+            sqlx::query(
+                r#"
+                UPDATE bookings
+                SET status = 'completed', completed_at = CURRENT_TIMESTAMP,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $1
+                "#,
+            )
+            .bind(booking_id),
+        )
+        .await?;
+
+        dbx.commit_txn().await?;
+
+        Ok(minutes)
+    }
+
+    /// Lists a booking's time entries, oldest first.
+    #[instrument(skip(mm))]
+    pub async fn list_for_booking(mm: &ModelManager, booking_id: i32) -> Result<Vec<TimeEntry>> {
+        let entries = sqlx::query_as(&format!(
+            "SELECT {TIME_ENTRY_COLUMNS} FROM time_entries \
+             WHERE booking_id = $1 ORDER BY started_at ASC, id ASC"
+        ))
+        .bind(booking_id)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Builds a technician's timesheet for a week.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `user_id` - Technician
+    /// * `week` - Any `YYYY-MM-DD` date in the week (default: this week)
+    /// * `now` - Current time, which running entries count up to
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the user does not exist, or
+    /// `ValidationError` if `week` is not a valid date.
+    #[instrument(skip(mm))]
+    pub async fn timesheet(
+        mm: &ModelManager,
+        user_id: i32,
+        week: Option<&str>,
+        now: OffsetDateTime,
+    ) -> Result<Timesheet> {
+        let day = parse_date("week", week)?.unwrap_or_else(|| now.date());
+        let week_start = day - Duration::days(i64::from(day.weekday().number_days_from_monday()));
+        let from = week_start.midnight().assume_utc();
+        let until = from + Duration::weeks(1);
+
+        let (username,): (String,) = sqlx::query_as("SELECT username FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(mm.dbx().db())
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: "User",
+                id: user_id as i64,
+            })?;
+        let rows: Vec<TimesheetRow> = sqlx::query_as(
+            r#"
+            SELECT t.id, t.booking_id, b.service_type, t.started_at, t.ended_at
+            FROM time_entries t
+            JOIN bookings b ON b.id = t.booking_id
+            WHERE t.user_id = $1
+              AND t.started_at < $3
+              AND (t.ended_at IS NULL OR t.ended_at > $2)
+            ORDER BY t.started_at ASC, t.id ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(until)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        Ok(build_timesheet(user_id, username, week_start, rows, now))
+    }
+
+    /// Opens an entry for the technician after checking they are free.
+    async fn clock_in(
+        mm: &ModelManager,
+        booking_id: i32,
+        user_id: i32,
+        resume: bool,
+    ) -> Result<TimeEntry> {
+        let mm = mm.new_with_txn()?;
+        let dbx = mm.dbx();
+        dbx.begin_txn().await?;
+
+        lock_user(&mm, user_id).await?;
+        lock_open_booking(&mm, booking_id).await?;
+        let running: Option<(i32,)> = dbx
+            .fetch_optional(
+                sqlx::query_as(
+                    "SELECT booking_id FROM time_entries WHERE user_id = $1 AND ended_at IS NULL",
+                )
+                .bind(user_id),
+            )
+            .await?;
+        if let Some((running_booking_id,)) = running {
+            return Err(Error::ValidationError(
+                format!("Technician is already clocked in on booking {running_booking_id}").into(),
+            ));
+        }
+        if resume {
+            let (worked,): (bool,) = dbx
+                .fetch_one(
+                    sqlx::query_as(
+                        "SELECT EXISTS (SELECT 1 FROM time_entries \
+                         WHERE booking_id = $1 AND user_id = $2)",
+                    )
+                    .bind(booking_id)
+                    .bind(user_id),
+                )
+                .await?;
+            if !worked {
+                return Err(Error::ValidationError(
+                    "Technician has no time on this booking to resume; start it instead".into(),
+                ));
+            }
+        }
+
+        let entry: TimeEntry = dbx
+            .fetch_one(
+                sqlx::query_as(&format!(
+                    r#"
+                    INSERT INTO time_entries (booking_id, user_id)
+                    VALUES ($1, $2)
+                    RETURNING {TIME_ENTRY_COLUMNS}
+                    "#
+                ))
+                .bind(booking_id)
+                .bind(user_id),
+            )
+            .await?;
+        dbx.execute(
+            sqlx::query(
+                r#"
+                UPDATE bookings
+                SET started_at = COALESCE(started_at, $2), updated_at = CURRENT_TIMESTAMP
+                WHERE id = $1
+                "#,
+            )
+            .bind(booking_id)
+            .bind(entry.started_at),
+        )
+        .await?;
+
+        dbx.commit_txn().await?;
+
+        Ok(entry)
+    }
+}
+
+// region:    --- Time Entry Helpers
+
+/// Locks the technician's user row so their clock actions run one at a time.
+async fn lock_user(mm: &ModelManager, user_id: i32) -> Result<()> {
+    mm.dbx()
+        .fetch_optional(
+            sqlx::query_as::<_, (i32,)>("SELECT id FROM users WHERE id = $1 FOR UPDATE")
+                .bind(user_id),
+        )
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "User",
+            id: user_id as i64,
+        })?;
+
+    Ok(())
+}
+
+/// Locks a booking that can still have time recorded against it.
+async fn lock_open_booking(mm: &ModelManager, booking_id: i32) -> Result<()> {
+    let (status,): (String,) = mm
+        .dbx()
+        .fetch_optional(
+            sqlx::query_as("SELECT status FROM bookings WHERE id = $1 FOR UPDATE").bind(booking_id),
+        )
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "Booking",
+            id: booking_id as i64,
+        })?;
+    if matches!(status.as_str(), "completed" | "cancelled") {
+        return Err(Error::ValidationError(
+            format!("Time cannot be recorded on a {status} booking").into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Sets the booking's `actual_duration` to its closed entries' total, in
+/// whole minutes, and returns it.
+async fn refresh_duration(mm: &ModelManager, booking_id: i32) -> Result<i32> {
+    let (minutes,): (i32,) = mm
+        .dbx()
+        .fetch_one(
+            sqlx::query_as(
+                r#"
+                UPDATE bookings
+                SET actual_duration = (
+                        SELECT ROUND(COALESCE(SUM(EXTRACT(EPOCH FROM ended_at - started_at)), 0)
+                                     / 60)::INTEGER
+                        FROM time_entries
+                        WHERE booking_id = $1 AND ended_at IS NOT NULL
+                    ),
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = $1
+                RETURNING actual_duration
+                "#,
+            )
+            .bind(booking_id),
+        )
+        .await?;
+
+    Ok(minutes)
+}
+
+/// Whole minutes in `seconds`, rounded to the nearest minute.
+fn to_minutes(seconds: i64) -> i64 {
+    (seconds + 30) / 60
+}
+
+/// Clips each entry to the week (running entries end at `now`) and totals
+/// the time per entry and per day.
+fn build_timesheet(
+    user_id: i32,
+    username: String,
+    week_start: Date,
+    rows: Vec<TimesheetRow>,
+    now: OffsetDateTime,
+) -> Timesheet {
+    let from = week_start.midnight().assume_utc();
+    let mut day_seconds = [0i64; 7];
+    let mut total_seconds = 0;
+
+    let entries = rows
+        .into_iter()
+        .map(|row| {
+            let start = row.started_at.max(from);
+            let end = row.ended_at.unwrap_or(now).min(from + Duration::weeks(1));
+            let mut seconds = 0;
+            for (day, day_total) in day_seconds.iter_mut().enumerate() {
+                let day_from = from + Duration::days(day as i64);
+                let day_until = day_from + Duration::days(1);
+                let overlap = (end.min(day_until) - start.max(day_from)).whole_seconds();
+                if overlap > 0 {
+                    *day_total += overlap;
+                    seconds += overlap;
+                }
+            }
+            total_seconds += seconds;
+
+            TimesheetEntry {
+                entry_id: row.id,
+                booking_id: row.booking_id,
+                service_type: row.service_type,
+                started_at: row.started_at,
+                ended_at: row.ended_at,
+                minutes: to_minutes(seconds),
+            }
+        })
+        .collect();
+
+    Timesheet {
+        user_id,
+        username,
+        week_start,
+        week_end: week_start + Duration::days(6),
+        entries,
+        days: day_seconds
+            .iter()
+            .enumerate()
+            .map(|(day, seconds)| TimesheetDay {
+                date: week_start + Duration::days(day as i64),
+                minutes: to_minutes(*seconds),
+            })
+            .collect(),
+        total_minutes: to_minutes(total_seconds),
+    }
+}
+
+fn rfc3339(at: OffsetDateTime) -> String {
+    at.format(&Rfc3339).unwrap_or_default()
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// endregion: --- Time Entry Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::booking::{BookingBmc, BookingForCreate};
+    use time::{Month, Time};
+
+    /// A time in the week of Monday 12 October 2026 (UTC).
+    fn at(day: u8, hour: u8, minute: u8) -> OffsetDateTime {
+        oct(day)
+            .with_time(Time::from_hms(hour, minute, 0).unwrap())
+            .assume_utc()
+    }
+
+    fn oct(day: u8) -> Date {
+        Date::from_calendar_date(2026, Month::October, day).unwrap()
+    }
+
+    fn fx_row(
+        id: i32,
+        started_at: OffsetDateTime,
+        ended_at: Option<OffsetDateTime>,
+    ) -> TimesheetRow {
+        TimesheetRow {
+            id,
+            booking_id: 7,
+            service_type: "plumbing".to_string(),
+            started_at,
+            ended_at,
+        }
+    }
+
+    #[test]
+    fn test_build_timesheet_splits_days_and_clips_week() {
+        let monday = oct(12);
+        let rows = vec![
+            // Started the Sunday before; only the Monday hour counts
+            fx_row(1, at(11, 23, 0), Some(at(12, 1, 0))),
+            // Runs past midnight into Wednesday
+            fx_row(2, at(13, 22, 30), Some(at(14, 0, 45))),
+            // Still running
+            fx_row(3, at(16, 9, 0), None),
+        ];
+
+        let sheet = build_timesheet(1, "tech".to_string(), monday, rows, at(16, 10, 30));
+
+        assert_eq!(sheet.week_end, oct(18));
+        assert_eq!(
+            sheet.entries.iter().map(|e| e.minutes).collect::<Vec<_>>(),
+            vec![60, 135, 90]
+        );
+        assert_eq!(
+            sheet.days.iter().map(|d| d.minutes).collect::<Vec<_>>(),
+            vec![60, 90, 45, 0, 90, 0, 0]
+        );
+        assert_eq!(sheet.total_minutes, 285);
+    }
+
+    #[test]
+    fn test_timesheet_to_csv() {
+        let sheet = Timesheet {
+            user_id: 1,
+            username: "tech".to_string(),
+            week_start: oct(12),
+            week_end: oct(18),
+            entries: vec![TimesheetEntry {
+                entry_id: 1,
+                booking_id: 7,
+                service_type: "fix, \"urgent\"".to_string(),
+                started_at: at(12, 9, 0),
+                ended_at: None,
+                minutes: 30,
+            }],
+            days: Vec::new(),
+            total_minutes: 30,
+        };
+
+        assert_eq!(
+            sheet.to_csv(),
+            "date,booking_id,service_type,started_at,ended_at,minutes\n\
+             2026-10-12,7,\"fix, \"\"urgent\"\"\",2026-10-12T09:00:00Z,,30\n\
+             total,,,,,30\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_time_entry_flow() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let (user_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO users (username, email, pwd_hash, token_salt) \
+             VALUES ('test_time_tech', 'test_time_tech@example.com', 'x', gen_random_uuid()) \
+             RETURNING id",
+        )
+        .fetch_one(mm.dbx().db())
+        .await?;
+        let mut bookings = Vec::new();
+        for _ in 0..2 {
+            bookings.push(
+                BookingBmc::create(
+                    &mm,
+                    BookingForCreate {
+                        customer_id: None,
+                        service_type: "test_time".to_string(),
+                        scheduled_date: None,
+                        scheduled_time: None,
+                        notes: None,
+                    },
+                )
+                .await?,
+            );
+        }
+
+        // Execute
+        let first = TimeEntryBmc::start(&mm, bookings[0], user_id).await?;
+        let double_booked = TimeEntryBmc::start(&mm, bookings[1], user_id).await;
+        TimeEntryBmc::pause(&mm, bookings[0], user_id).await?;
+        let fresh_resume = TimeEntryBmc::resume(&mm, bookings[1], user_id).await;
+        let second = TimeEntryBmc::resume(&mm, bookings[0], user_id).await?;
+        let minutes = TimeEntryBmc::stop(&mm, bookings[0]).await?;
+
+        // Check
+        assert!(matches!(double_booked, Err(Error::ValidationError(_))));
+        assert!(matches!(fresh_resume, Err(Error::ValidationError(_))));
+        assert_ne!(first.id, second.id);
+        let entries = TimeEntryBmc::list_for_booking(&mm, bookings[0]).await?;
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.ended_at.is_some()));
+        assert_eq!(entries[1].ended_by.as_deref(), Some("stop"));
+        let booking = BookingBmc::get(&mm, bookings[0]).await?;
+        assert_eq!(booking.status, "completed");
+        assert_eq!(booking.actual_duration, Some(minutes));
+        assert!(booking.started_at.is_some());
+        let after_stop = TimeEntryBmc::start(&mm, bookings[0], user_id).await;
+        assert!(matches!(after_stop, Err(Error::ValidationError(_))));
+        let sheet = TimeEntryBmc::timesheet(&mm, user_id, None, OffsetDateTime::now_utc()).await?;
+        assert_eq!(sheet.entries.len(), 2);
+
+        // Cleanup
+        for id in bookings {
+            BookingBmc::delete(&mm, id).await?;
+        }
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(mm.dbx().db())
+            .await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
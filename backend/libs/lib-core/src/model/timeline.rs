@@ -0,0 +1,337 @@
+//! # Customer Timeline Model
+//!
+//! Merges everything that has happened with a customer into one
+//! chronologically ordered feed, for when they call and we need the full
+//! picture at a glance.
+//!
+//! Sources:
+//! - Contact form submissions (matched on the customer's email)
+//! - Quotes, plus every quote status change
+//! - Bookings, plus every booking status change
+//! - Emails sent to or about the customer
+//! - Payments received
+//!
+//! ## Structures
+//!
+//! - [`TimelineEvent`] - A single entry in the feed
+//! - [`TimelineEventKind`] - What happened
+//! - [`TimelineBmc`] - Business Model Controller for timeline queries
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::pagination::Pagination;
+//! use lib_core::model::timeline::TimelineBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn show(mm: &ModelManager) -> Result<(), Box<dyn std::error::Error>> {
+//!     let page = TimelineBmc::list_for_customer(mm, 1, Pagination::first_page()).await?;
+//!     for event in page.items {
+//!         println!("{:?} {:?} {}", event.occurred_at, event.kind, event.summary);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::model::pagination::{PaginatedResult, Pagination};
+use crate::model::ModelManager;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+/// What a timeline event records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    ContactSubmitted,
+    QuoteCreated,
+    QuoteStatusChanged,
+    BookingCreated,
+    BookingStatusChanged,
+    EmailSent,
+    PaymentReceived,
+}
+
+impl TimelineEventKind {
+    fn from_db(kind: &str) -> Result<Self> {
+        match kind {
+            "contact_submitted" => Ok(Self::ContactSubmitted),
+            "quote_created" => Ok(Self::QuoteCreated),
+            "quote_status_changed" => Ok(Self::QuoteStatusChanged),
+            "booking_created" => Ok(Self::BookingCreated),
+            "booking_status_changed" => Ok(Self::BookingStatusChanged),
+            "email_sent" => Ok(Self::EmailSent),
+            "payment_received" => Ok(Self::PaymentReceived),
+            other => Err(Error::ValidationError(
+                format!("Unknown timeline event kind: {other}").into(),
+            )),
+        }
+    }
+}
+
+/// A single entry in a customer's timeline.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TimelineEvent {
+    /// What happened
+    pub kind: TimelineEventKind,
+    /// ID of the underlying record (contact, quote, booking, email log, payment)
+    pub entity_id: i32,
+    /// When it happened
+    pub occurred_at: OffsetDateTime,
+    /// One-line human-readable summary
+    pub summary: String,
+    /// Previous status (status changes only)
+    pub from_status: Option<String>,
+    /// Status after the event
+    pub status: Option<String>,
+    /// Amount in cents (quotes and payments)
+    pub amount_cents: Option<i32>,
+}
+
+/// Raw row returned by the timeline query.
+#[derive(Debug, FromRow)]
+struct TimelineRow {
+    kind: String,
+    entity_id: i32,
+    occurred_at: OffsetDateTime,
+    summary: String,
+    from_status: Option<String>,
+    status: Option<String>,
+    amount_cents: Option<i32>,
+}
+
+/// Every event for customer `$1` whose email is `$2`.
+const EVENTS_SQL: &str = r#"
+    SELECT 'contact_submitted' AS kind, cs.id AS entity_id,
+           cs.submitted_at::timestamptz AS occurred_at,
+           coalesce(cs.subject, 'Contact form message') AS summary,
+           NULL::varchar AS from_status, NULL::varchar AS status, NULL::int AS amount_cents
+    FROM contact_submissions cs
+    WHERE $2::text IS NOT NULL AND lower(cs.email) = lower($2)
+      AND cs.submitted_at IS NOT NULL
+
+    UNION ALL
+
+    SELECT 'quote_created', q.id, q.created_at, q.title,
+           NULL, NULL, q.total_cents
+    FROM quotes q
+    WHERE q.customer_id = $1 AND q.created_at IS NOT NULL
+
+    UNION ALL
+
+    SELECT 'quote_status_changed', q.id, sc.changed_at, q.title,
+           sc.from_status, sc.to_status, NULL
+    FROM status_changes sc
+    JOIN quotes q ON sc.entity = 'quote' AND q.id = sc.entity_id
+    WHERE q.customer_id = $1 AND sc.changed_at IS NOT NULL
+
+    UNION ALL
+
+    SELECT 'booking_created', b.id, b.created_at, b.service_type,
+           NULL, NULL, NULL
+    FROM bookings b
+    WHERE b.customer_id = $1 AND b.created_at IS NOT NULL
+
+    UNION ALL
+
+    SELECT 'booking_status_changed', b.id, sc.changed_at, b.service_type,
+           sc.from_status, sc.to_status, NULL
+    FROM status_changes sc
+    JOIN bookings b ON sc.entity = 'booking' AND b.id = sc.entity_id
+    WHERE b.customer_id = $1 AND sc.changed_at IS NOT NULL
+
+    UNION ALL
+
+    SELECT 'email_sent', e.id, e.sent_at, e.subject,
+           NULL, e.status, NULL
+    FROM email_log e
+    WHERE (e.customer_id = $1
+           OR ($2::text IS NOT NULL
+               AND (lower(e.to_email) = lower($2) OR lower(e.related_email) = lower($2))))
+      AND e.sent_at IS NOT NULL
+
+    UNION ALL
+
+    SELECT 'payment_received', p.id, p.paid_at,
+           concat_ws(' ', initcap(replace(p.method, '_', ' ')), 'payment'),
+           NULL, p.status, p.amount_cents
+    FROM payments p
+    WHERE p.customer_id = $1 AND p.paid_at IS NOT NULL
+"#;
+
+/// Business Model Controller for customer timelines.
+pub struct TimelineBmc;
+
+impl TimelineBmc {
+    /// Lists a customer's timeline, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `mm` - Model manager for database access
+    /// * `customer_id` - Customer to build the timeline for
+    /// * `pagination` - Page to return
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the customer does not exist.
+    #[instrument(skip(mm))]
+    pub async fn list_for_customer(
+        mm: &ModelManager,
+        customer_id: i32,
+        pagination: Pagination,
+    ) -> Result<PaginatedResult<TimelineEvent>> {
+        let email: Option<(Option<String>,)> =
+            sqlx::query_as("SELECT email FROM customers WHERE id = $1")
+                .bind(customer_id)
+                .fetch_optional(mm.dbx().db())
+                .await?;
+        let Some((email,)) = email else {
+            return Err(Error::EntityNotFound {
+                entity: "Customer",
+                id: customer_id as i64,
+            });
+        };
+
+        let (total,): (i64,) =
+            sqlx::query_as(&format!("SELECT count(*) FROM ({EVENTS_SQL}) events"))
+                .bind(customer_id)
+                .bind(&email)
+                .fetch_one(mm.dbx().db())
+                .await?;
+
+        let rows = sqlx::query_as::<_, TimelineRow>(&format!(
+            "SELECT * FROM ({EVENTS_SQL}) events
+             ORDER BY occurred_at DESC, kind ASC, entity_id DESC
+             LIMIT $3 OFFSET $4"
+        ))
+        .bind(customer_id)
+        .bind(&email)
+        .bind(pagination.limit() as i64)
+        .bind(pagination.offset() as i64)
+        .fetch_all(mm.dbx().db())
+        .await?;
+
+        let items = rows
+            .into_iter()
+            .map(|row| {
+                Ok(TimelineEvent {
+                    kind: TimelineEventKind::from_db(&row.kind)?,
+                    entity_id: row.entity_id,
+                    occurred_at: row.occurred_at,
+                    summary: row.summary,
+                    from_status: row.from_status,
+                    status: row.status,
+                    amount_cents: row.amount_cents,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(PaginatedResult::new(items, &pagination, total as u64))
+    }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::model::customer::{CustomerBmc, CustomerForCreate};
+    use crate::model::payment::{PaymentBmc, PaymentForCreate};
+    use crate::model::quote::{QuoteBmc, QuoteForCreate, QuoteItem};
+
+    #[tokio::test]
+    async fn test_timeline_err_customer_not_found() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let fx_id = 999999;
+
+        // Execute
+        let res = TimelineBmc::list_for_customer(&mm, fx_id, Pagination::first_page()).await;
+
+        // Check
+        assert!(matches!(res, Err(Error::EntityNotFound { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_timeline_merges_sources_newest_first() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let customer_id = CustomerBmc::create(
+            &mm,
+            CustomerForCreate {
+                name: "Test Timeline Customer".to_string(),
+                email: Some(format!("timeline_{}@example.com", uuid::Uuid::new_v4())),
+                phone: None,
+                notes: None,
+            },
+        )
+        .await?;
+        let quote_id = QuoteBmc::create(
+            &mm,
+            QuoteForCreate {
+                customer_id: Some(customer_id),
+                title: "Test Timeline Quote".to_string(),
+                items: vec![QuoteItem {
+                    description: "Call-out fee".to_string(),
+                    quantity: 1,
+                    unit_price: 3000,
+                    discount: None,
+                    vat_rate_bp: None,
+                }],
+                valid_days: Some(30),
+                service_type: None,
+                discount: None,
+                promo_code: None,
+                notes: None,
+                template_id: None,
+                options: vec![],
+                add_ons: vec![],
+            },
+        )
+        .await?;
+        QuoteBmc::send(&mm, quote_id).await?;
+        let payment_id = PaymentBmc::create(
+            &mm,
+            PaymentForCreate {
+                customer_id: Some(customer_id),
+                quote_id: Some(quote_id),
+                booking_id: None,
+                invoice_id: None,
+                amount_cents: 3000,
+                method: "card".to_string(),
+                reference: None,
+            },
+        )
+        .await?;
+
+        // Execute
+        let page =
+            TimelineBmc::list_for_customer(&mm, customer_id, Pagination::first_page()).await?;
+
+        // Check
+        let kinds: Vec<_> = page.items.iter().map(|e| e.kind).collect();
+        assert!(kinds.contains(&TimelineEventKind::QuoteCreated));
+        assert!(kinds.contains(&TimelineEventKind::QuoteStatusChanged));
+        assert!(kinds.contains(&TimelineEventKind::PaymentReceived));
+        assert_eq!(page.total_items, 3);
+        assert!(page
+            .items
+            .windows(2)
+            .all(|w| w[0].occurred_at >= w[1].occurred_at));
+
+        // Cleanup
+        PaymentBmc::delete(&mm, payment_id).await?;
+        QuoteBmc::delete(&mm, quote_id).await?;
+        CustomerBmc::delete(&mm, customer_id).await?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
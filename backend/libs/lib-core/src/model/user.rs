@@ -0,0 +1,163 @@
+//! # User Model
+//!
+//! The accounts of people working for a tenant, as far as admin access
+//! tokens need them.
+//!
+//! ## Structures
+//!
+//! - [`UserForAuth`] - A user's identity and current token salt
+//! - [`UserBmc`] - Business Model Controller for user operations
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::model::user::UserBmc;
+//! use lib_core::model::ModelManager;
+//!
+//! async fn revoke(mm: &ModelManager) -> Result<(), Box<dyn std::error::Error>> {
+//!     if let Some(user) = UserBmc::first_by_username(mm, "sam").await? {
+//!         UserBmc::reset_token_salt(mm, user.id).await?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::model::{Error, ModelManager, Result};
+use sqlx::FromRow;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Columns selected into [`UserForAuth`].
+const USER_FOR_AUTH_COLUMNS: &str = "id, username, token_salt";
+
+/// A user's identity and current token salt.
+#[derive(Debug, Clone, FromRow)]
+pub struct UserForAuth {
+    /// Auto-generated primary key
+    pub id: i32,
+    /// Login name, recorded as the actor of audited admin operations
+    pub username: String,
+    /// Salt of the user's admin tokens (NULL until one is issued)
+    pub token_salt: Option<Uuid>,
+}
+
+/// Business Model Controller for user operations.
+pub struct UserBmc;
+
+impl UserBmc {
+    /// Gets a user.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the tenant has no such user.
+    #[instrument(skip(mm))]
+    pub async fn get_for_auth(mm: &ModelManager, id: i32) -> Result<UserForAuth> {
+        sqlx::query_as(&format!(
+            "SELECT {USER_FOR_AUTH_COLUMNS} FROM users WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "User",
+            id: id as i64,
+        })
+    }
+
+    /// Finds a user by username.
+    #[instrument(skip(mm))]
+    pub async fn first_by_username(
+        mm: &ModelManager,
+        username: &str,
+    ) -> Result<Option<UserForAuth>> {
+        let user = sqlx::query_as(&format!(
+            "SELECT {USER_FOR_AUTH_COLUMNS} FROM users WHERE username = $1"
+        ))
+        .bind(username)
+        .fetch_optional(mm.dbx().db())
+        .await?;
+
+        Ok(user)
+    }
+
+    /// The user's token salt, set first if they have none.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the tenant has no such user.
+    #[instrument(skip(mm))]
+    pub async fn ensure_token_salt(mm: &ModelManager, id: i32) -> Result<Uuid> {
+        Self::set_token_salt(mm, id, "COALESCE(token_salt, gen_random_uuid())").await
+    }
+
+    /// Gives the user a new token salt, revoking every admin token they
+    /// hold.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EntityNotFound` if the tenant has no such user.
+    #[instrument(skip(mm))]
+    pub async fn reset_token_salt(mm: &ModelManager, id: i32) -> Result<Uuid> {
+        Self::set_token_salt(mm, id, "gen_random_uuid()").await
+    }
+
+    async fn set_token_salt(mm: &ModelManager, id: i32, salt: &str) -> Result<Uuid> {
+        let (token_salt,): (Uuid,) = sqlx::query_as(&format!(
+            "UPDATE users SET token_salt = {salt}, updated_at = CURRENT_TIMESTAMP \
+             WHERE id = $1 RETURNING token_salt"
+        ))
+        .bind(id)
+        .fetch_optional(mm.dbx().db())
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: "User",
+            id: id as i64,
+        })?;
+
+        Ok(token_salt)
+    }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+
+    #[tokio::test]
+    async fn test_token_salt_reset_revokes() -> Result<()> {
+        // Setup
+        let mm = _dev_utils::init_test().await;
+        let (id,): (i32,) = sqlx::query_as(
+            "INSERT INTO users (username, email) \
+             VALUES ('test_token_salt', 'test_token_salt@example.com') \
+             ON CONFLICT (tenant_id, username) DO UPDATE SET token_salt = NULL \
+             RETURNING id",
+        )
+        .fetch_one(mm.dbx().db())
+        .await?;
+
+        // Exec
+        let salt = UserBmc::ensure_token_salt(&mm, id).await?;
+        let kept = UserBmc::ensure_token_salt(&mm, id).await?;
+        let reset = UserBmc::reset_token_salt(&mm, id).await?;
+
+        // Check
+        assert_eq!(salt, kept);
+        assert_ne!(salt, reset);
+        let user = UserBmc::first_by_username(&mm, "test_token_salt")
+            .await?
+            .expect("user exists");
+        assert_eq!(user.id, id);
+        assert_eq!(user.token_salt, Some(reset));
+        assert!(matches!(
+            UserBmc::get_for_auth(&mm, -1).await,
+            Err(Error::EntityNotFound { .. })
+        ));
+
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
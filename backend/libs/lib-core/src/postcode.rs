@@ -0,0 +1,327 @@
+//! UK postcode handling.
+//!
+//! Postcodes arrive in every shape ("sw1a1aa", " SW1A  1AA "). They are
+//! stored in the canonical form - upper case, with one space before the
+//! three-character inward code - and compared by their parts:
+//!
+//! - **Outward code** (district) - `SW1A` in `SW1A 1AA`
+//! - **Area** - the leading letters, `SW`
+//!
+//! Partial postcodes such as `SW1A` or `SW` are accepted wherever an area
+//! is expected, so technicians' working areas can be a whole area or a
+//! single district.
+//!
+//! District locations come from an open postcode dataset (ONS, OS Code-Point
+//! Open or a postcode-district list) exported as CSV; [`parse_districts_csv`]
+//! reduces it to one centre per district.
+
+use std::collections::BTreeMap;
+
+/// Canonical form of a full postcode, or `None` if it is not shaped like
+/// one.
+///
+/// # Example
+///
+/// ```
+/// use lib_core::postcode::normalize;
+///
+/// assert_eq!(normalize(" sw1a1aa ").as_deref(), Some("SW1A 1AA"));
+/// assert_eq!(normalize("not a postcode"), None);
+/// ```
+#[must_use]
+pub fn normalize(postcode: &str) -> Option<String> {
+    let compact = compact(postcode);
+    if !(5..=7).contains(&compact.len()) {
+        return None;
+    }
+    let (outward, inward) = compact.split_at(compact.len() - 3);
+    let inward_ok = {
+        let b = inward.as_bytes();
+        b[0].is_ascii_digit() && b[1].is_ascii_alphabetic() && b[2].is_ascii_alphabetic()
+    };
+    if !inward_ok || !is_outward(outward) {
+        return None;
+    }
+
+    Some(format!("{outward} {inward}"))
+}
+
+/// Outward code (district) of a full or partial postcode.
+///
+/// A full postcode loses its inward code; an outward code or area is
+/// returned as given, in canonical case.
+#[must_use]
+pub fn outward(postcode: &str) -> Option<String> {
+    if let Some(full) = normalize(postcode) {
+        return full.split(' ').next().map(str::to_string);
+    }
+    let compact = compact(postcode);
+    (is_outward(&compact) || is_area(&compact)).then_some(compact)
+}
+
+/// Area (leading letters) of a full or partial postcode.
+#[must_use]
+pub fn area(postcode: &str) -> Option<String> {
+    let outward = outward(postcode)?;
+    let letters: String = outward
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect();
+
+    (!letters.is_empty()).then_some(letters)
+}
+
+/// Header names recognised for each column, in order of preference.
+const CODE_HEADERS: [&str; 6] = [
+    "outcode",
+    "postcode district",
+    "district code",
+    "postcode",
+    "pcds",
+    "pcd",
+];
+const LATITUDE_HEADERS: [&str; 2] = ["latitude", "lat"];
+const LONGITUDE_HEADERS: [&str; 3] = ["longitude", "long", "lng"];
+const NAME_HEADERS: [&str; 6] = [
+    "district",
+    "town/area",
+    "post town",
+    "town",
+    "admin_district",
+    "area",
+];
+
+/// A postcode district and where it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistrictLocation {
+    /// Outcode, e.g. `CV1`
+    pub outcode: String,
+    /// Latitude of the district's centre
+    pub latitude: f64,
+    /// Longitude of the district's centre
+    pub longitude: f64,
+    /// Town or local authority the district belongs to
+    pub district: Option<String>,
+}
+
+/// Districts read from a postcode CSV.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistrictCsv {
+    /// One entry per district, by outcode
+    pub districts: Vec<DistrictLocation>,
+    /// Rows without a usable postcode or location
+    pub skipped: usize,
+}
+
+/// Why a postcode CSV could not be read.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CsvError {
+    /// No header row
+    #[error("The postcode CSV is empty")]
+    Empty,
+
+    /// A required column is missing from the header row
+    #[error("The postcode CSV has no {0} column")]
+    MissingColumn(&'static str),
+}
+
+/// Reads district centres from a postcode CSV with a header row.
+///
+/// Columns are found by header name (case-insensitive): the postcode or
+/// outcode, latitude, longitude and optionally a town or district name.
+/// Full postcodes are grouped by district and their locations averaged.
+/// Rows with a malformed postcode or missing coordinates (terminated and
+/// non-geographic postcodes) are skipped and counted.
+///
+/// # Errors
+///
+/// Returns [`CsvError`] if there is no header row or it lacks a postcode,
+/// latitude or longitude column.
+///
+/// # Example
+///
+/// ```
+/// use lib_core::postcode::parse_districts_csv;
+///
+/// let csv = "Postcode,Latitude,Longitude,Town/Area\nCV1,52.408,-1.509,Coventry\n";
+/// let parsed = parse_districts_csv(csv).unwrap();
+///
+/// assert_eq!(parsed.districts[0].outcode, "CV1");
+/// assert_eq!(parsed.districts[0].district.as_deref(), Some("Coventry"));
+/// ```
+pub fn parse_districts_csv(csv: &str) -> Result<DistrictCsv, CsvError> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<String> = split_csv_line(lines.next().ok_or(CsvError::Empty)?)
+        .iter()
+        .map(|h| h.trim().trim_start_matches('\u{feff}').to_lowercase())
+        .collect();
+    let column = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|n| header.iter().position(|h| h == n))
+    };
+    let code = column(&CODE_HEADERS).ok_or(CsvError::MissingColumn("postcode"))?;
+    let lat = column(&LATITUDE_HEADERS).ok_or(CsvError::MissingColumn("latitude"))?;
+    let lng = column(&LONGITUDE_HEADERS).ok_or(CsvError::MissingColumn("longitude"))?;
+    let name = NAME_HEADERS
+        .iter()
+        .find_map(|n| header.iter().position(|h| h == n).filter(|&i| i != code));
+
+    // Running totals per district: (lat sum, lng sum, count, name)
+    let mut totals: BTreeMap<String, (f64, f64, u32, Option<String>)> = BTreeMap::new();
+    let mut skipped = 0;
+    for line in lines {
+        let fields = split_csv_line(line);
+        let field = |i: usize| fields.get(i).map(|f| f.trim()).unwrap_or_default();
+        let outcode = outward(field(code)).filter(|o| is_outward(o));
+        let location = field(lat)
+            .parse::<f64>()
+            .ok()
+            .zip(field(lng).parse::<f64>().ok());
+        let (Some(outcode), Some((latitude, longitude))) = (outcode, location) else {
+            skipped += 1;
+            continue;
+        };
+        if !(-90.0..=90.0).contains(&latitude)
+            || !(-180.0..=180.0).contains(&longitude)
+            || (latitude == 0.0 && longitude == 0.0)
+        {
+            skipped += 1;
+            continue;
+        }
+
+        let entry = totals.entry(outcode).or_insert((0.0, 0.0, 0, None));
+        entry.0 += latitude;
+        entry.1 += longitude;
+        entry.2 += 1;
+        if entry.3.is_none() {
+            entry.3 = name
+                .map(field)
+                .filter(|n| !n.is_empty())
+                .map(str::to_string);
+        }
+    }
+
+    let districts = totals
+        .into_iter()
+        .map(
+            |(outcode, (lat_sum, lng_sum, count, district))| DistrictLocation {
+                outcode,
+                latitude: lat_sum / f64::from(count),
+                longitude: lng_sum / f64::from(count),
+                district,
+            },
+        )
+        .collect();
+
+    Ok(DistrictCsv { districts, skipped })
+}
+
+/// Upper-cased alphanumerics only.
+fn compact(postcode: &str) -> String {
+    postcode
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// One or two letters, a digit, then an optional digit or letter.
+fn is_outward(code: &str) -> bool {
+    let letters = code.chars().take_while(char::is_ascii_alphabetic).count();
+    let rest = &code[letters..];
+
+    (1..=2).contains(&letters)
+        && (1..=2).contains(&rest.len())
+        && rest.as_bytes()[0].is_ascii_digit()
+        && rest.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// One or two letters.
+fn is_area(code: &str) -> bool {
+    (1..=2).contains(&code.len()) && code.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Splits a CSV line into fields, honouring double-quoted fields.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("sw1a1aa").as_deref(), Some("SW1A 1AA"));
+        assert_eq!(normalize(" M1  1AE ").as_deref(), Some("M1 1AE"));
+        assert_eq!(normalize("b338th").as_deref(), Some("B33 8TH"));
+        assert_eq!(normalize("SW1A"), None);
+        assert_eq!(normalize("12345"), None);
+        assert_eq!(normalize("SW1A 1A1"), None);
+    }
+
+    #[test]
+    fn test_outward_and_area() {
+        assert_eq!(outward("sw1a 1aa").as_deref(), Some("SW1A"));
+        assert_eq!(outward("sw1a").as_deref(), Some("SW1A"));
+        assert_eq!(outward("sw").as_deref(), Some("SW"));
+        assert_eq!(outward("123"), None);
+        assert_eq!(area("SW1A 1AA").as_deref(), Some("SW"));
+        assert_eq!(area("m1").as_deref(), Some("M"));
+        assert_eq!(area(""), None);
+    }
+
+    #[test]
+    fn test_parse_districts_csv_groups_full_postcodes() {
+        let csv = "\u{feff}pcds,lat,long,admin_district\n\
+                   CV1 1AA,52.40,-1.50,Coventry\n\
+                   CV1 2BB,52.42,-1.52,Coventry\n\
+                   \"B33 8TH\",52.48,-1.78,\"Birmingham, West Midlands\"\n\
+                   ZZ99 3WZ,99.999999,0.000000,\n\
+                   not a postcode,52.0,-1.0,Nowhere\n";
+
+        let parsed = parse_districts_csv(csv).unwrap();
+
+        assert_eq!(parsed.skipped, 2);
+        assert_eq!(parsed.districts.len(), 2);
+        let b33 = &parsed.districts[0];
+        assert_eq!(b33.outcode, "B33");
+        assert_eq!(b33.district.as_deref(), Some("Birmingham, West Midlands"));
+        let cv1 = &parsed.districts[1];
+        assert_eq!(cv1.outcode, "CV1");
+        assert!((cv1.latitude - 52.41).abs() < 1e-9);
+        assert!((cv1.longitude + 1.51).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_districts_csv_requires_columns() {
+        assert_eq!(parse_districts_csv(""), Err(CsvError::Empty));
+        assert_eq!(
+            parse_districts_csv("postcode,latitude\nCV1,52.4\n"),
+            Err(CsvError::MissingColumn("longitude"))
+        );
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,694 @@
+//! # Pricing
+//!
+//! The single pricing function behind quotes, invoices and the instant
+//! quote endpoint.
+//!
+//! ## Order of operations
+//!
+//! 1. Each line's gross is `quantity × unit_price`.
+//! 2. The line's own [`Discount`] comes off its gross.
+//! 3. Order-level discounts (a manual quote discount, then a promo code)
+//!    come off the running net, one after another. Each is spread across
+//!    the lines in proportion to their net, using largest remainders so the
+//!    shares add up to the discount exactly.
+//! 4. Lines are grouped by VAT rate and VAT is charged once per rate on the
+//!    band's net, as HMRC allows for invoice totals.
+//!
+//! ## Rounding
+//!
+//! All amounts are whole cents. Percentages (discounts and VAT) round half
+//! up; fixed discounts are capped so nothing goes negative.
+//!
+//! ## Instant estimates
+//!
+//! [`estimate`] turns a service's base range into an estimate range by
+//! running the catalog's [`PricingRule`]s in priority order. The instant
+//! quote endpoint then prices each end of the range with [`price`] so promo
+//! codes and VAT work exactly as they do on quotes.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use lib_core::model::quote::QuoteItem;
+//! use lib_core::pricing::{price, Discount, VatSettings};
+//!
+//! let items = vec![QuoteItem {
+//!     description: "Labour (1 hour)".to_string(),
+//!     quantity: 2,
+//!     unit_price: 4500,
+//!     discount: None,
+//!     vat_rate_bp: None,
+//! }];
+//! let vat = VatSettings { registered: true, default_rate_bp: 2000 };
+//! let breakdown = price(&items, &[Discount::Percent { basis_points: 1000 }], &vat).unwrap();
+//!
+//! assert_eq!(breakdown.net_cents, 8100);
+//! assert_eq!(breakdown.vat_cents, 1620);
+//! assert_eq!(breakdown.total_cents, 9720);
+//! ```
+
+use crate::core_config;
+use crate::model::quote::QuoteItem;
+use crate::model::{Error, Result};
+use serde::{Deserialize, Serialize};
+use shared::{
+    AppliedAdjustment, InstantQuoteRequest, PricingCatalog, PricingRule, RuleAdjustment,
+    RuleCondition,
+};
+use utoipa::ToSchema;
+
+/// Largest percentage, in basis points.
+const FULL_BP: i64 = 10_000;
+
+/// A discount on a line or a whole order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Discount {
+    /// Fixed amount off, in cents (capped at the amount discounted)
+    Fixed { amount_cents: i32 },
+    /// Percentage off, in basis points (1000 = 10%)
+    Percent { basis_points: i32 },
+}
+
+impl Discount {
+    /// Checks the discount is non-negative and at most 100%.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` for an out-of-range discount.
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            Discount::Fixed { amount_cents } if amount_cents < 0 => Err(Error::ValidationError(
+                "Fixed discounts cannot be negative".into(),
+            )),
+            Discount::Percent { basis_points } if !(0..=10_000).contains(&basis_points) => Err(
+                Error::ValidationError("Percentage discounts must be between 0% and 100%".into()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Amount taken off `base` cents.
+    #[must_use]
+    pub fn amount_off(&self, base: i64) -> i64 {
+        if base <= 0 {
+            return 0;
+        }
+        match *self {
+            Discount::Fixed { amount_cents } => i64::from(amount_cents).min(base),
+            Discount::Percent { basis_points } => percent_of(base, basis_points).min(base),
+        }
+    }
+}
+
+/// Whether VAT is charged, and at what rate when a line has none of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VatSettings {
+    /// Whether the business is VAT registered
+    pub registered: bool,
+    /// Rate for lines without their own, in basis points (2000 = 20%)
+    pub default_rate_bp: i32,
+}
+
+impl VatSettings {
+    /// Loads settings from `VAT_REGISTERED` and `VAT_RATE_BP`.
+    #[must_use]
+    pub fn from_config() -> Self {
+        let config = core_config();
+        Self {
+            registered: config.VAT_REGISTERED,
+            default_rate_bp: config.VAT_RATE_BP,
+        }
+    }
+
+    /// Rate charged on a line: its own rate, the default, or zero when not
+    /// VAT registered.
+    #[must_use]
+    pub fn rate_for(&self, line_rate_bp: Option<i32>) -> i32 {
+        if self.registered {
+            line_rate_bp.unwrap_or(self.default_rate_bp)
+        } else {
+            0
+        }
+    }
+}
+
+/// One line after discounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PricedLine {
+    /// Quantity × unit price
+    pub gross_cents: i64,
+    /// The line's own discount
+    pub line_discount_cents: i64,
+    /// This line's share of order-level discounts
+    pub order_discount_cents: i64,
+    /// Gross less all discounts
+    pub net_cents: i64,
+    /// VAT rate applied, in basis points
+    pub vat_rate_bp: i32,
+}
+
+/// VAT charged at one rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct VatBand {
+    /// Rate in basis points
+    pub rate_bp: i32,
+    /// Net amount charged at this rate, in cents
+    pub net_cents: i32,
+    /// VAT at this rate, in cents
+    pub vat_cents: i32,
+}
+
+/// Result of pricing a set of lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceBreakdown {
+    /// Per-line detail, in input order
+    pub lines: Vec<PricedLine>,
+    /// Sum of line grosses
+    pub subtotal_cents: i32,
+    /// Sum of line-level discounts
+    pub line_discount_cents: i32,
+    /// Each order-level discount actually applied, in input order
+    pub order_discount_cents: Vec<i32>,
+    /// All discounts together
+    pub discount_cents: i32,
+    /// Subtotal less discounts
+    pub net_cents: i32,
+    /// VAT by rate, lowest rate first (empty when not VAT registered)
+    pub vat_bands: Vec<VatBand>,
+    /// Total VAT
+    pub vat_cents: i32,
+    /// Net plus VAT
+    pub total_cents: i32,
+}
+
+/// Prices line items with their own discounts, order-level discounts and
+/// VAT.
+///
+/// # Errors
+///
+/// Returns `ValidationError` for negative quantities or prices, invalid
+/// discounts or VAT rates, or totals too large to store.
+pub fn price(
+    items: &[QuoteItem],
+    order_discounts: &[Discount],
+    vat: &VatSettings,
+) -> Result<PriceBreakdown> {
+    // -- Lines and their own discounts
+    let mut lines = Vec::with_capacity(items.len());
+    for item in items {
+        if item.quantity < 0 || item.unit_price < 0 {
+            return Err(Error::ValidationError(
+                format!("'{}' has a negative quantity or price", item.description).into(),
+            ));
+        }
+        if let Some(discount) = &item.discount {
+            discount.validate()?;
+        }
+        let vat_rate_bp = vat.rate_for(item.vat_rate_bp);
+        if !(0..=10_000).contains(&vat_rate_bp) {
+            return Err(Error::ValidationError(
+                format!("'{}' has an invalid VAT rate", item.description).into(),
+            ));
+        }
+
+        let gross_cents = i64::from(item.quantity) * i64::from(item.unit_price);
+        let line_discount_cents = item
+            .discount
+            .map(|d| d.amount_off(gross_cents))
+            .unwrap_or(0);
+        lines.push(PricedLine {
+            gross_cents,
+            line_discount_cents,
+            order_discount_cents: 0,
+            net_cents: gross_cents - line_discount_cents,
+            vat_rate_bp,
+        });
+    }
+
+    // -- Order-level discounts, spread across lines
+    let mut applied = Vec::with_capacity(order_discounts.len());
+    for discount in order_discounts {
+        discount.validate()?;
+        let running_net: i64 = lines.iter().map(|l| l.net_cents).sum();
+        let amount = discount.amount_off(running_net);
+        let weights: Vec<i64> = lines.iter().map(|l| l.net_cents).collect();
+        for (line, share) in lines.iter_mut().zip(allocate(amount, &weights)) {
+            line.order_discount_cents += share;
+            line.net_cents -= share;
+        }
+        applied.push(to_cents(amount)?);
+    }
+
+    // -- VAT per rate
+    let mut bands: Vec<VatBand> = Vec::new();
+    if vat.registered {
+        let mut rates: Vec<i32> = lines.iter().map(|l| l.vat_rate_bp).collect();
+        rates.sort_unstable();
+        rates.dedup();
+        for rate_bp in rates {
+            let net: i64 = lines
+                .iter()
+                .filter(|l| l.vat_rate_bp == rate_bp)
+                .map(|l| l.net_cents)
+                .sum();
+            bands.push(VatBand {
+                rate_bp,
+                net_cents: to_cents(net)?,
+                vat_cents: to_cents(percent_of(net, rate_bp))?,
+            });
+        }
+    }
+
+    let subtotal: i64 = lines.iter().map(|l| l.gross_cents).sum();
+    let line_discount: i64 = lines.iter().map(|l| l.line_discount_cents).sum();
+    let net: i64 = lines.iter().map(|l| l.net_cents).sum();
+    let vat_total: i64 = bands.iter().map(|b| i64::from(b.vat_cents)).sum();
+
+    Ok(PriceBreakdown {
+        lines,
+        subtotal_cents: to_cents(subtotal)?,
+        line_discount_cents: to_cents(line_discount)?,
+        order_discount_cents: applied,
+        discount_cents: to_cents(subtotal - net)?,
+        net_cents: to_cents(net)?,
+        vat_bands: bands,
+        vat_cents: to_cents(vat_total)?,
+        total_cents: to_cents(net + vat_total)?,
+    })
+}
+
+/// Estimate range for a job, before promo codes and VAT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Estimate {
+    /// Lower end, in cents
+    pub low_cents: i32,
+    /// Upper end, in cents
+    pub high_cents: i32,
+    /// Rules that changed the range, in the order applied
+    pub adjustments: Vec<AppliedAdjustment>,
+}
+
+/// Runs the catalog's rules over a service's base range.
+///
+/// Active rules whose condition matches the request (and whose service, if
+/// any, is the one requested) run in ascending `priority`, then `id`. Each
+/// works on the price left by the rules before it, is limited to
+/// `cap_cents` either way, and cannot take either end below zero.
+///
+/// # Errors
+///
+/// Returns `ValidationError` when the service is not in the catalog or not
+/// active.
+pub fn estimate(catalog: &PricingCatalog, request: &InstantQuoteRequest) -> Result<Estimate> {
+    let service = catalog
+        .services
+        .iter()
+        .find(|s| s.active && s.service_type == request.service_type)
+        .ok_or_else(|| {
+            Error::ValidationError(format!("Unknown service '{}'", request.service_type).into())
+        })?;
+
+    let mut rules: Vec<&PricingRule> = catalog
+        .rules
+        .iter()
+        .filter(|rule| rule.active && rule_applies(rule, request))
+        .collect();
+    rules.sort_by_key(|rule| (rule.priority, rule.id));
+
+    let mut low = i64::from(service.base_low_cents);
+    let mut high = i64::from(service.base_high_cents);
+    let mut adjustments = Vec::new();
+    for rule in rules {
+        let low_delta = rule_delta(rule, low);
+        let high_delta = rule_delta(rule, high);
+        if low_delta == 0 && high_delta == 0 {
+            continue;
+        }
+        low += low_delta;
+        high += high_delta;
+        adjustments.push(AppliedAdjustment {
+            label: rule.label.clone(),
+            low_cents: to_cents(low_delta)?,
+            high_cents: to_cents(high_delta)?,
+        });
+    }
+
+    Ok(Estimate {
+        low_cents: to_cents(low)?,
+        high_cents: to_cents(high)?,
+        adjustments,
+    })
+}
+
+fn rule_applies(rule: &PricingRule, request: &InstantQuoteRequest) -> bool {
+    if rule
+        .service_type
+        .as_ref()
+        .is_some_and(|service| *service != request.service_type)
+    {
+        return false;
+    }
+    match &rule.condition {
+        RuleCondition::Urgency(value) => request.urgency.as_deref() == Some(value.as_str()),
+        RuleCondition::JobSize(value) => request.job_size.as_deref() == Some(value.as_str()),
+        RuleCondition::OutOfHours => request.out_of_hours,
+        RuleCondition::Weekend => request.weekend,
+        RuleCondition::DistanceZone(value) => {
+            request.distance_zone.as_deref() == Some(value.as_str())
+        }
+    }
+}
+
+/// Change `rule` makes to `running` cents, after its cap and the zero floor.
+fn rule_delta(rule: &PricingRule, running: i64) -> i64 {
+    let delta = match rule.adjustment {
+        RuleAdjustment::Fixed { amount_cents } => i64::from(amount_cents),
+        RuleAdjustment::Percent { basis_points } => percent_of(running, basis_points),
+    };
+    let delta = match rule.cap_cents {
+        Some(cap) => delta.clamp(-i64::from(cap), i64::from(cap)),
+        None => delta,
+    };
+    delta.max(-running)
+}
+
+/// `base × basis_points / 10000`, rounded half up.
+fn percent_of(base: i64, basis_points: i32) -> i64 {
+    (base * i64::from(basis_points) + FULL_BP / 2).div_euclid(FULL_BP)
+}
+
+/// Splits `amount` across `weights` proportionally. Shares are floored and
+/// the leftover cents go to the largest remainders (earlier entries win
+/// ties), so the shares always sum to `amount`.
+#[must_use]
+pub fn allocate(amount: i64, weights: &[i64]) -> Vec<i64> {
+    let total: i64 = weights.iter().sum();
+    if total <= 0 || amount <= 0 {
+        return vec![0; weights.len()];
+    }
+
+    let mut shares: Vec<i64> = weights.iter().map(|w| amount * w / total).collect();
+    let mut leftover = amount - shares.iter().sum::<i64>();
+
+    let mut by_remainder: Vec<usize> = (0..weights.len()).collect();
+    by_remainder.sort_by_key(|&i| std::cmp::Reverse((amount * weights[i]) % total));
+    for i in by_remainder {
+        if leftover == 0 {
+            break;
+        }
+        shares[i] += 1;
+        leftover -= 1;
+    }
+
+    shares
+}
+
+fn to_cents(amount: i64) -> Result<i32> {
+    i32::try_from(amount).map_err(|_| Error::ValidationError("Amount is too large to price".into()))
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VAT_20: VatSettings = VatSettings {
+        registered: true,
+        default_rate_bp: 2000,
+    };
+    const NO_VAT: VatSettings = VatSettings {
+        registered: false,
+        default_rate_bp: 2000,
+    };
+
+    fn item(unit_price: i32, quantity: i32) -> QuoteItem {
+        QuoteItem {
+            description: "Item".to_string(),
+            quantity,
+            unit_price,
+            discount: None,
+            vat_rate_bp: None,
+        }
+    }
+
+    #[test]
+    fn test_price_no_discounts_no_vat() {
+        let breakdown = price(&[item(3000, 1), item(4500, 1)], &[], &NO_VAT).unwrap();
+
+        assert_eq!(breakdown.subtotal_cents, 7500);
+        assert_eq!(breakdown.discount_cents, 0);
+        assert_eq!(breakdown.vat_cents, 0);
+        assert!(breakdown.vat_bands.is_empty());
+        assert_eq!(breakdown.total_cents, 7500);
+    }
+
+    #[test]
+    fn test_price_line_discounts() {
+        let mut fixed = item(3000, 1);
+        fixed.discount = Some(Discount::Fixed { amount_cents: 500 });
+        let mut percent = item(4500, 2);
+        percent.discount = Some(Discount::Percent { basis_points: 1250 });
+
+        let breakdown = price(&[fixed, percent], &[], &NO_VAT).unwrap();
+
+        assert_eq!(breakdown.line_discount_cents, 500 + 1125);
+        assert_eq!(breakdown.net_cents, 12000 - 1625);
+    }
+
+    #[test]
+    fn test_price_fixed_discount_capped() {
+        let mut line = item(1000, 1);
+        line.discount = Some(Discount::Fixed { amount_cents: 5000 });
+
+        let breakdown = price(&[line], &[Discount::Fixed { amount_cents: 100 }], &VAT_20).unwrap();
+
+        assert_eq!(breakdown.net_cents, 0);
+        assert_eq!(breakdown.order_discount_cents, vec![0]);
+        assert_eq!(breakdown.total_cents, 0);
+    }
+
+    #[test]
+    fn test_price_order_discounts_apply_in_sequence() {
+        let breakdown = price(
+            &[item(10000, 1)],
+            &[
+                Discount::Fixed { amount_cents: 2000 },
+                Discount::Percent { basis_points: 1000 },
+            ],
+            &NO_VAT,
+        )
+        .unwrap();
+
+        assert_eq!(breakdown.order_discount_cents, vec![2000, 800]);
+        assert_eq!(breakdown.net_cents, 7200);
+    }
+
+    #[test]
+    fn test_price_order_discount_allocation_sums_exactly() {
+        let items = [item(333, 1), item(333, 1), item(334, 1)];
+
+        let breakdown = price(&items, &[Discount::Fixed { amount_cents: 100 }], &NO_VAT).unwrap();
+        let shares: Vec<i64> = breakdown
+            .lines
+            .iter()
+            .map(|l| l.order_discount_cents)
+            .collect();
+
+        assert_eq!(shares.iter().sum::<i64>(), 100);
+        assert_eq!(shares, vec![33, 33, 34]);
+    }
+
+    #[test]
+    fn test_price_vat_bands_per_rate() {
+        let mut zero_rated = item(1000, 1);
+        zero_rated.vat_rate_bp = Some(0);
+        let mut reduced = item(1001, 1);
+        reduced.vat_rate_bp = Some(500);
+
+        let breakdown = price(&[item(1003, 1), zero_rated, reduced], &[], &VAT_20).unwrap();
+
+        assert_eq!(
+            breakdown.vat_bands,
+            vec![
+                VatBand {
+                    rate_bp: 0,
+                    net_cents: 1000,
+                    vat_cents: 0
+                },
+                VatBand {
+                    rate_bp: 500,
+                    net_cents: 1001,
+                    vat_cents: 50
+                },
+                VatBand {
+                    rate_bp: 2000,
+                    net_cents: 1003,
+                    vat_cents: 201
+                },
+            ]
+        );
+        assert_eq!(breakdown.vat_cents, 251);
+        assert_eq!(breakdown.total_cents, 3004 + 251);
+    }
+
+    #[test]
+    fn test_price_vat_after_order_discount() {
+        let breakdown = price(
+            &[item(4500, 2)],
+            &[Discount::Percent { basis_points: 1000 }],
+            &VAT_20,
+        )
+        .unwrap();
+
+        assert_eq!(breakdown.net_cents, 8100);
+        assert_eq!(breakdown.vat_cents, 1620);
+        assert_eq!(breakdown.total_cents, 9720);
+    }
+
+    #[test]
+    fn test_percent_rounds_half_up() {
+        assert_eq!(percent_of(25, 2000), 5);
+        assert_eq!(percent_of(3, 2000), 1); // 0.6
+        assert_eq!(percent_of(2, 2500), 1); // 0.5
+        assert_eq!(percent_of(1, 2000), 0); // 0.2
+    }
+
+    #[test]
+    fn test_price_err_invalid_input() {
+        let mut negative = item(-1, 1);
+        assert!(price(&[negative.clone()], &[], &NO_VAT).is_err());
+
+        negative.unit_price = 100;
+        negative.discount = Some(Discount::Percent {
+            basis_points: 10_001,
+        });
+        assert!(price(&[negative], &[], &NO_VAT).is_err());
+
+        assert!(price(&[item(i32::MAX, 2)], &[], &NO_VAT).is_err());
+    }
+
+    fn catalog(rules: Vec<PricingRule>) -> PricingCatalog {
+        PricingCatalog {
+            services: vec![shared::ServiceRate {
+                service_type: "plumbing".to_string(),
+                label: "Plumbing".to_string(),
+                base_low_cents: 4500,
+                base_high_cents: 12000,
+                sort_order: 10,
+                active: true,
+            }],
+            rules,
+        }
+    }
+
+    fn rule(
+        id: i32,
+        priority: i32,
+        condition: RuleCondition,
+        adjustment: RuleAdjustment,
+    ) -> PricingRule {
+        PricingRule {
+            id,
+            label: format!("Rule {id}"),
+            condition,
+            service_type: None,
+            adjustment,
+            cap_cents: None,
+            priority,
+            active: true,
+        }
+    }
+
+    fn request() -> InstantQuoteRequest {
+        InstantQuoteRequest {
+            service_type: "plumbing".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_estimate_rules_in_priority_order_with_caps() {
+        // -- Setup: weekend +£20 runs before a 50% large-job uplift capped at £50
+        let mut large = rule(
+            1,
+            20,
+            RuleCondition::JobSize("large".to_string()),
+            RuleAdjustment::Percent { basis_points: 5000 },
+        );
+        large.cap_cents = Some(5000);
+        let weekend = rule(
+            2,
+            10,
+            RuleCondition::Weekend,
+            RuleAdjustment::Fixed { amount_cents: 2000 },
+        );
+        let catalog = catalog(vec![large, weekend]);
+        let mut request = request();
+        request.job_size = Some("large".to_string());
+        request.weekend = true;
+
+        // -- Exec
+        let estimate = estimate(&catalog, &request).unwrap();
+
+        // -- Check
+        assert_eq!(estimate.low_cents, 4500 + 2000 + 3250);
+        assert_eq!(estimate.high_cents, 12000 + 2000 + 5000);
+        assert_eq!(estimate.adjustments[0].label, "Rule 2");
+        assert_eq!(estimate.adjustments[1].low_cents, 3250);
+        assert_eq!(estimate.adjustments[1].high_cents, 5000);
+    }
+
+    #[test]
+    fn test_estimate_skips_unmatched_and_floors_at_zero() {
+        let mut electrical_only = rule(
+            1,
+            10,
+            RuleCondition::OutOfHours,
+            RuleAdjustment::Fixed { amount_cents: 999 },
+        );
+        electrical_only.service_type = Some("electrical".to_string());
+        let mut inactive = rule(
+            2,
+            10,
+            RuleCondition::OutOfHours,
+            RuleAdjustment::Fixed { amount_cents: 999 },
+        );
+        inactive.active = false;
+        let zone = rule(
+            3,
+            10,
+            RuleCondition::DistanceZone("3".to_string()),
+            RuleAdjustment::Fixed { amount_cents: 2500 },
+        );
+        let flexible = rule(
+            4,
+            20,
+            RuleCondition::Urgency("flexible".to_string()),
+            RuleAdjustment::Fixed {
+                amount_cents: -10_000,
+            },
+        );
+        let catalog = catalog(vec![electrical_only, inactive, zone, flexible]);
+        let mut request = request();
+        request.out_of_hours = true;
+        request.urgency = Some("flexible".to_string());
+
+        let estimate = estimate(&catalog, &request).unwrap();
+
+        assert_eq!(estimate.low_cents, 0);
+        assert_eq!(estimate.high_cents, 2000);
+        assert_eq!(estimate.adjustments.len(), 1);
+        assert_eq!(estimate.adjustments[0].low_cents, -4500);
+    }
+
+    #[test]
+    fn test_estimate_err_unknown_service() {
+        let mut request = request();
+        request.service_type = "roofing".to_string();
+        assert!(estimate(&catalog(vec![]), &request).is_err());
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,434 @@
+//! Daily route planning.
+//!
+//! Orders a technician's jobs for a day to keep driving short while still
+//! arriving on time for fixed appointments:
+//!
+//! 1. **Nearest neighbour** - from the base (or each job in turn, when
+//!    there is no base) drive to the closest job that still leaves time to
+//!    reach the next fixed appointment; fixed appointments are visited in
+//!    time order.
+//! 2. **Local improvement** - reverse runs of stops (2-opt) and move single
+//!    stops elsewhere while that cuts lateness or distance.
+//!
+//! Drive times are estimated from straight-line distance, scaled by
+//! [`ROAD_FACTOR`] for winding roads, at [`AVERAGE_SPEED_KMH`]. Times are
+//! minutes since midnight; arriving early for an appointment means
+//! waiting until it starts.
+
+/// Road distance per straight-line kilometre.
+pub const ROAD_FACTOR: f64 = 1.3;
+
+/// Average driving speed, km/h.
+pub const AVERAGE_SPEED_KMH: f64 = 40.0;
+
+/// Mean Earth radius, km.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Rounds of improvement before settling for the current order.
+const MAX_IMPROVEMENT_ROUNDS: usize = 50;
+
+/// A point on the map, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    /// Latitude
+    pub lat: f64,
+    /// Longitude
+    pub lng: f64,
+}
+
+/// A job to visit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stop {
+    /// Caller's ID for the job
+    pub id: i32,
+    /// Where the job is
+    pub location: Point,
+    /// Appointment time, if the customer was given one
+    pub fixed_at: Option<i32>,
+    /// Minutes on site
+    pub duration: i32,
+}
+
+/// A stop as scheduled in a plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Visit {
+    /// Caller's ID for the job
+    pub id: i32,
+    /// Estimated arrival
+    pub arrive: i32,
+    /// When work starts (after waiting for an appointment)
+    pub start: i32,
+    /// When the technician leaves
+    pub depart: i32,
+    /// Road distance from the previous stop (or base), km
+    pub drive_km: f64,
+    /// Minutes after the appointment time the technician arrives
+    pub late_minutes: i32,
+}
+
+/// A visiting order with its timings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    /// Stops in visiting order
+    pub visits: Vec<Visit>,
+    /// Total road distance, km (not counting the drive home)
+    pub distance_km: f64,
+    /// Total driving, minutes
+    pub drive_minutes: i32,
+    /// Total lateness across appointments, minutes
+    pub late_minutes: i32,
+}
+
+/// Great-circle distance between two points, km.
+#[must_use]
+pub fn haversine_km(a: Point, b: Point) -> f64 {
+    let (lat1, lat2) = (a.lat.to_radians(), b.lat.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lng = (b.lng - a.lng).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lng / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * h.sqrt().asin()
+}
+
+/// Estimated road distance between two points, km.
+#[must_use]
+pub fn road_km(a: Point, b: Point) -> f64 {
+    haversine_km(a, b) * ROAD_FACTOR
+}
+
+/// Estimated drive time between two points, whole minutes.
+#[must_use]
+pub fn drive_minutes(a: Point, b: Point) -> i32 {
+    (road_km(a, b) / AVERAGE_SPEED_KMH * 60.0).ceil() as i32
+}
+
+/// Plans a visiting order for a day.
+///
+/// # Arguments
+///
+/// * `base` - Where the day starts; `None` starts at the first job
+/// * `day_start` - When the technician sets off (or starts the first job)
+/// * `stops` - Jobs to visit
+///
+/// # Example
+///
+/// ```
+/// use lib_core::routing::{plan, Point, Stop};
+///
+/// let stop = |id, lng| Stop {
+///     id,
+///     location: Point { lat: 52.4, lng },
+///     fixed_at: None,
+///     duration: 60,
+/// };
+/// let base = Point { lat: 52.4, lng: -1.50 };
+/// let day = plan(Some(base), 8 * 60, &[stop(1, -1.40), stop(2, -1.45)]);
+///
+/// assert_eq!(day.visits.iter().map(|v| v.id).collect::<Vec<_>>(), vec![2, 1]);
+/// ```
+#[must_use]
+pub fn plan(base: Option<Point>, day_start: i32, stops: &[Stop]) -> Plan {
+    if stops.is_empty() {
+        return schedule(base, day_start, stops, &[]);
+    }
+
+    let starts: Vec<Option<usize>> = match base {
+        Some(_) => vec![None],
+        None => (0..stops.len()).map(Some).collect(),
+    };
+    let mut best: Option<(Vec<usize>, Plan)> = None;
+    for first in starts {
+        let mut order = nearest_neighbour(base, day_start, stops, first);
+        improve(base, day_start, stops, &mut order);
+        let candidate = schedule(base, day_start, stops, &order);
+        if best
+            .as_ref()
+            .is_none_or(|(_, plan)| better(&candidate, plan))
+        {
+            best = Some((order, candidate));
+        }
+    }
+
+    best.map(|(_, plan)| plan)
+        .unwrap_or_else(|| schedule(base, day_start, stops, &[]))
+}
+
+// region:    --- Routing Helpers
+
+/// Times and distances for visiting `stops` in `order`.
+fn schedule(base: Option<Point>, day_start: i32, stops: &[Stop], order: &[usize]) -> Plan {
+    let mut visits = Vec::with_capacity(order.len());
+    let mut position = base;
+    let mut clock = day_start;
+    let (mut distance_km, mut drive_total, mut late_total) = (0.0, 0, 0);
+
+    for &i in order {
+        let stop = &stops[i];
+        let (km, minutes) = match position {
+            Some(from) => (
+                road_km(from, stop.location),
+                drive_minutes(from, stop.location),
+            ),
+            None => (0.0, 0),
+        };
+        let arrive = clock + minutes;
+        let start = stop.fixed_at.map_or(arrive, |at| arrive.max(at));
+        let late = stop.fixed_at.map_or(0, |at| (arrive - at).max(0));
+        let depart = start + stop.duration;
+
+        visits.push(Visit {
+            id: stop.id,
+            arrive,
+            start,
+            depart,
+            drive_km: km,
+            late_minutes: late,
+        });
+        distance_km += km;
+        drive_total += minutes;
+        late_total += late;
+        position = Some(stop.location);
+        clock = depart;
+    }
+
+    Plan {
+        visits,
+        distance_km,
+        drive_minutes: drive_total,
+        late_minutes: late_total,
+    }
+}
+
+/// Whether `a` is a better plan than `b`: less lateness, then less driving.
+fn better(a: &Plan, b: &Plan) -> bool {
+    const EPSILON_KM: f64 = 1e-6;
+
+    a.late_minutes < b.late_minutes
+        || (a.late_minutes == b.late_minutes && a.distance_km + EPSILON_KM < b.distance_km)
+}
+
+/// Greedy order: the nearest job that keeps the next appointment.
+fn nearest_neighbour(
+    base: Option<Point>,
+    day_start: i32,
+    stops: &[Stop],
+    first: Option<usize>,
+) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..stops.len()).collect();
+    let mut order = Vec::with_capacity(stops.len());
+    let mut position = base;
+    let mut clock = day_start;
+
+    if let Some(first) = first {
+        remaining.retain(|&i| i != first);
+        let stop = &stops[first];
+        clock = stop.fixed_at.map_or(clock, |at| clock.max(at)) + stop.duration;
+        position = Some(stop.location);
+        order.push(first);
+    }
+
+    while !remaining.is_empty() {
+        // Appointments are kept in time order, so only the earliest one
+        // left is a candidate, and every detour must leave time for it.
+        let next_fixed = remaining
+            .iter()
+            .copied()
+            .filter(|&i| stops[i].fixed_at.is_some())
+            .min_by_key(|&i| stops[i].fixed_at);
+        let keeps_appointment = |i: usize| {
+            let Some(fixed) = next_fixed.filter(|&f| f != i) else {
+                return true;
+            };
+            let stop = &stops[i];
+            let arrive = clock + position.map_or(0, |p| drive_minutes(p, stop.location));
+            let depart = arrive + stop.duration;
+            let due = stops[fixed].fixed_at.unwrap_or(i32::MAX);
+
+            depart + drive_minutes(stop.location, stops[fixed].location) <= due
+        };
+        let distance = |i: usize| position.map_or(0.0, |p| road_km(p, stops[i].location));
+
+        let choice = remaining
+            .iter()
+            .copied()
+            .filter(|&i| stops[i].fixed_at.is_none() || Some(i) == next_fixed)
+            .filter(|&i| keeps_appointment(i))
+            .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+            .or(next_fixed)
+            .unwrap_or(remaining[0]);
+
+        let stop = &stops[choice];
+        let arrive = clock + position.map_or(0, |p| drive_minutes(p, stop.location));
+        clock = stop.fixed_at.map_or(arrive, |at| arrive.max(at)) + stop.duration;
+        position = Some(stop.location);
+        remaining.retain(|&i| i != choice);
+        order.push(choice);
+    }
+
+    order
+}
+
+/// Applies 2-opt reversals and single-stop moves while they help.
+fn improve(base: Option<Point>, day_start: i32, stops: &[Stop], order: &mut Vec<usize>) {
+    let n = order.len();
+    let mut current = schedule(base, day_start, stops, order);
+
+    for _ in 0..MAX_IMPROVEMENT_ROUNDS {
+        let mut improved = false;
+
+        for i in 0..n {
+            for j in i + 1..n {
+                let mut candidate = order.clone();
+                candidate[i..=j].reverse();
+                let plan = schedule(base, day_start, stops, &candidate);
+                if better(&plan, &current) {
+                    *order = candidate;
+                    current = plan;
+                    improved = true;
+                }
+            }
+        }
+
+        for from in 0..n {
+            for to in 0..n {
+                if from == to {
+                    continue;
+                }
+                let mut candidate = order.clone();
+                let stop = candidate.remove(from);
+                candidate.insert(to, stop);
+                let plan = schedule(base, day_start, stops, &candidate);
+                if better(&plan, &current) {
+                    *order = candidate;
+                    current = plan;
+                    improved = true;
+                }
+            }
+        }
+
+        if !improved {
+            break;
+        }
+    }
+}
+
+// endregion: --- Routing Helpers
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points along a line of latitude, roughly 6.8 km apart per 0.1°.
+    fn east(lng: f64) -> Point {
+        Point { lat: 52.4, lng }
+    }
+
+    fn fx_stop(id: i32, lng: f64, fixed_at: Option<i32>) -> Stop {
+        Stop {
+            id,
+            location: east(lng),
+            fixed_at,
+            duration: 60,
+        }
+    }
+
+    fn ids(plan: &Plan) -> Vec<i32> {
+        plan.visits.iter().map(|v| v.id).collect()
+    }
+
+    #[test]
+    fn test_haversine_km() {
+        let coventry = Point {
+            lat: 52.4068,
+            lng: -1.5197,
+        };
+        let birmingham = Point {
+            lat: 52.4862,
+            lng: -1.8904,
+        };
+
+        let km = haversine_km(coventry, birmingham);
+
+        assert!((km - 26.6).abs() < 0.5, "got {km}");
+        assert_eq!(haversine_km(coventry, coventry), 0.0);
+    }
+
+    #[test]
+    fn test_plan_orders_flexible_jobs_by_distance() {
+        let stops = vec![
+            fx_stop(3, -1.2, None),
+            fx_stop(1, -1.4, None),
+            fx_stop(2, -1.3, None),
+        ];
+
+        let day = plan(Some(east(-1.5)), 8 * 60, &stops);
+
+        assert_eq!(ids(&day), vec![1, 2, 3]);
+        assert_eq!(day.late_minutes, 0);
+        assert_eq!(
+            day.visits[0].arrive,
+            8 * 60 + drive_minutes(east(-1.5), east(-1.4))
+        );
+        assert_eq!(
+            day.visits[1].arrive,
+            day.visits[0].depart + drive_minutes(east(-1.4), east(-1.3))
+        );
+    }
+
+    #[test]
+    fn test_plan_keeps_fixed_appointments() {
+        // The far job is booked for 09:00, so it comes first even though the
+        // near one is on the way.
+        let stops = vec![fx_stop(1, -1.4, None), fx_stop(2, -1.2, Some(9 * 60))];
+
+        let day = plan(Some(east(-1.5)), 8 * 60, &stops);
+
+        assert_eq!(ids(&day), vec![2, 1]);
+        assert_eq!(day.late_minutes, 0);
+        assert_eq!(day.visits[0].start, 9 * 60, "waits for the appointment");
+    }
+
+    #[test]
+    fn test_plan_fits_flexible_jobs_between_appointments() {
+        let stops = vec![
+            fx_stop(1, -1.5, Some(8 * 60)),
+            fx_stop(2, -1.4, Some(12 * 60)),
+            fx_stop(3, -1.45, None),
+        ];
+
+        let day = plan(None, 8 * 60, &stops);
+
+        assert_eq!(ids(&day), vec![1, 3, 2]);
+        assert_eq!(day.late_minutes, 0);
+        assert_eq!(day.visits[2].start, 12 * 60);
+    }
+
+    #[test]
+    fn test_plan_without_base_starts_at_an_end() {
+        let stops = vec![
+            fx_stop(2, -1.4, None),
+            fx_stop(1, -1.5, None),
+            fx_stop(3, -1.3, None),
+        ];
+
+        let day = plan(None, 8 * 60, &stops);
+
+        assert!(ids(&day) == vec![1, 2, 3] || ids(&day) == vec![3, 2, 1]);
+        assert_eq!(day.visits[0].drive_km, 0.0);
+        assert_eq!(day.visits[0].arrive, 8 * 60);
+    }
+
+    #[test]
+    fn test_plan_reports_unavoidable_lateness() {
+        let stops = vec![fx_stop(1, -1.0, Some(8 * 60))];
+
+        let day = plan(Some(east(-1.5)), 8 * 60, &stops);
+
+        assert_eq!(day.late_minutes, drive_minutes(east(-1.5), east(-1.0)));
+        assert!(plan(None, 8 * 60, &[]).visits.is_empty());
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,124 @@
+//! Blob store on the local filesystem.
+//!
+//! Each key is a file under the root directory. Writes go to a temporary
+//! file that is renamed into place, so readers never see a partial file.
+
+use super::{validate_key, BlobStore, Error, Result};
+use async_trait::async_trait;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Blob store keeping each blob as a file under `root`.
+#[derive(Debug, Clone)]
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    /// Creates a store rooted at `root`. The directory is created on the
+    /// first write.
+    #[must_use]
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<()> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+
+        let tmp = path.with_file_name(format!(".{}.tmp", uuid::Uuid::new_v4()));
+        fs::write(&tmp, bytes).await?;
+        if let Err(e) = fs::rename(&tmp, &path).await {
+            let _ = fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        match fs::read(self.path(key)?).await {
+            Ok(bytes) => Ok(bytes),
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(Error::NotFound(key.to_string())),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fx_store() -> (LocalBlobStore, PathBuf) {
+        let root = std::env::temp_dir().join(format!("blob-store-{}", uuid::Uuid::new_v4()));
+        (LocalBlobStore::new(&root), root)
+    }
+
+    #[tokio::test]
+    async fn test_local_roundtrip() -> Result<()> {
+        // Setup
+        let (store, root) = fx_store();
+
+        // Execute
+        store
+            .put("bookings/1/a.jpg", b"first".to_vec(), "image/jpeg")
+            .await?;
+        store
+            .put("bookings/1/a.jpg", b"second".to_vec(), "image/jpeg")
+            .await?;
+        let read = store.get("bookings/1/a.jpg").await?;
+        store.delete("bookings/1/a.jpg").await?;
+
+        // Check
+        assert_eq!(read, b"second");
+        assert!(matches!(
+            store.get("bookings/1/a.jpg").await,
+            Err(Error::NotFound(_))
+        ));
+        assert!(store.delete("bookings/1/a.jpg").await.is_ok());
+        let leftovers = std::fs::read_dir(root.join("bookings/1"))?.count();
+        assert_eq!(leftovers, 0, "temporary files are cleaned up");
+
+        // Cleanup
+        std::fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_rejects_escaping_keys() {
+        let (store, _root) = fx_store();
+
+        for key in ["../outside.txt", "/tmp/outside.txt", "a/../../b"] {
+            assert!(matches!(
+                store.put(key, b"x".to_vec(), "text/plain").await,
+                Err(Error::InvalidKey(_))
+            ));
+            assert!(matches!(store.get(key).await, Err(Error::InvalidKey(_))));
+        }
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,202 @@
+//! # Blob Storage
+//!
+//! Bytes of uploaded files (job photos, receipts and their thumbnails).
+//! The database only keeps metadata and a storage key; the bytes live in a
+//! [`BlobStore`]:
+//!
+//! - [`local::LocalBlobStore`] - Files under a directory on this machine
+//! - [`s3::S3BlobStore`] - Any S3-compatible service (AWS S3, MinIO, R2, ...)
+//!
+//! ## Configuration
+//!
+//! - `STORAGE_BACKEND` - `local` (default) or `s3`
+//! - `STORAGE_LOCAL_PATH` - Directory for the local backend (default: `./data/attachments`)
+//! - `S3_ENDPOINT`, `S3_BUCKET`, `S3_REGION`, `S3_ACCESS_KEY_ID`,
+//!   `S3_SECRET_ACCESS_KEY` - S3 backend settings
+//!
+//! ## Keys
+//!
+//! Keys are relative, `/`-separated paths such as `bookings/42/<uuid>.jpg`.
+//! Segments may only use ASCII letters, digits, `.`, `_` and `-`, and
+//! cannot be `.` or `..`, so a key is safe as both a file path and a URL
+//! path on every backend.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lib_core::storage::blob_store;
+//!
+//! async fn roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+//!     let store = blob_store()?;
+//!     store.put("bookings/1/note.txt", b"hello".to_vec(), "text/plain").await?;
+//!     assert_eq!(store.get("bookings/1/note.txt").await?, b"hello");
+//!     store.delete("bookings/1/note.txt").await?;
+//!     Ok(())
+//! }
+//! ```
+
+pub mod local;
+pub mod s3;
+
+use crate::core_config;
+use async_trait::async_trait;
+use std::sync::OnceLock;
+use tracing::error;
+
+pub use self::local::LocalBlobStore;
+pub use self::s3::{S3BlobStore, S3Config};
+
+/// Storage operation result type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Errors from a [`BlobStore`].
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Key is empty, absolute or has a disallowed segment
+    #[error("Invalid storage key: {0}")]
+    InvalidKey(String),
+
+    /// Nothing is stored under the key
+    #[error("Blob not found: {0}")]
+    NotFound(String),
+
+    /// Local filesystem error
+    #[error("Storage I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Remote backend refused or failed the request
+    #[error("Storage backend error: {0}")]
+    Backend(String),
+
+    /// Backend settings are missing or invalid
+    #[error("Storage is not configured: {0}")]
+    Config(String),
+}
+
+/// Somewhere to keep file contents by key.
+///
+/// Writes replace whatever was stored under the key. Implementations must
+/// be safe to share between requests.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Stores `bytes` under `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] for a bad key, or a backend error if
+    /// the write fails.
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<()>;
+
+    /// Reads what is stored under `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if nothing is stored under the key.
+    async fn get(&self, key: &str) -> Result<Vec<u8>>;
+
+    /// Removes what is stored under `key`. Deleting a missing key succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] for a bad key, or a backend error if
+    /// the delete fails.
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// The configured blob store, created on first use.
+///
+/// # Errors
+///
+/// Returns [`Error::Config`] if `STORAGE_BACKEND` is unknown or the S3
+/// settings are incomplete.
+pub fn blob_store() -> Result<&'static dyn BlobStore> {
+    static INSTANCE: OnceLock<core::result::Result<Box<dyn BlobStore>, String>> = OnceLock::new();
+
+    INSTANCE
+        .get_or_init(|| {
+            from_config().map_err(|e| {
+                error!("Blob storage unavailable: {}", e);
+                e.to_string()
+            })
+        })
+        .as_ref()
+        .map(|store| store.as_ref())
+        .map_err(|e| Error::Config(e.clone()))
+}
+
+/// Checks that `key` is a safe relative path (see the module docs).
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidKey`] describing the first problem found.
+pub fn validate_key(key: &str) -> Result<()> {
+    let invalid = || Error::InvalidKey(key.to_string());
+
+    if key.is_empty() || key.len() > 512 {
+        return Err(invalid());
+    }
+    for segment in key.split('/') {
+        let allowed = segment
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'));
+        if segment.is_empty() || segment == "." || segment == ".." || !allowed {
+            return Err(invalid());
+        }
+    }
+
+    Ok(())
+}
+
+fn from_config() -> Result<Box<dyn BlobStore>> {
+    let config = core_config();
+
+    match config.STORAGE_BACKEND.as_str() {
+        "local" => Ok(Box::new(LocalBlobStore::new(&config.STORAGE_LOCAL_PATH))),
+        "s3" => {
+            let required = |name: &str, value: &Option<String>| {
+                value
+                    .clone()
+                    .ok_or_else(|| Error::Config(format!("{name} must be set for S3 storage")))
+            };
+            Ok(Box::new(S3BlobStore::new(S3Config {
+                endpoint: required("S3_ENDPOINT", &config.S3_ENDPOINT)?,
+                bucket: required("S3_BUCKET", &config.S3_BUCKET)?,
+                region: config.S3_REGION.clone(),
+                access_key_id: required("S3_ACCESS_KEY_ID", &config.S3_ACCESS_KEY_ID)?,
+                secret_access_key: required("S3_SECRET_ACCESS_KEY", &config.S3_SECRET_ACCESS_KEY)?,
+            })?))
+        }
+        other => Err(Error::Config(format!(
+            "Unknown STORAGE_BACKEND \"{other}\" (expected local or s3)"
+        ))),
+    }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key() {
+        for key in ["a", "bookings/42/3f2a.jpg", "quotes/7/receipt_thumb-1.jpg"] {
+            assert!(validate_key(key).is_ok(), "{key} should be valid");
+        }
+        for key in [
+            "",
+            "/etc/passwd",
+            "bookings//1.jpg",
+            "bookings/../secret",
+            "./a",
+            "a/",
+            "a\\b",
+            "photo 1.jpg",
+            "café.jpg",
+        ] {
+            assert!(validate_key(key).is_err(), "{key:?} should be rejected");
+        }
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,114 @@
+//! Text helpers for published pages.
+//!
+//! URL slugs, and a near-duplicate check for page copy. Search engines
+//! treat pages whose text is mostly the same as duplicate content and
+//! pick one to rank, so generated landing pages are compared with
+//! [`similarity`] before they are published.
+//!
+//! Similarity is the Jaccard index of the two texts' word shingles (runs of
+//! [`SHINGLE_WORDS`] consecutive words, case and punctuation ignored): 1.0
+//! for the same words in the same order, 0.0 for nothing in common.
+
+use std::collections::HashSet;
+
+/// Words per shingle.
+pub const SHINGLE_WORDS: usize = 3;
+
+/// Similarity at or above which two texts count as duplicates.
+pub const DUPLICATE_THRESHOLD: f64 = 0.5;
+
+/// Turns a name or title into a URL slug.
+///
+/// # Examples
+///
+/// ```
+/// use lib_core::text::slugify;
+///
+/// assert_eq!(slugify("Sutton Coldfield"), "sutton-coldfield");
+/// assert_eq!(slugify("  Painting & Decorating! "), "painting-decorating");
+/// ```
+#[must_use]
+pub fn slugify(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Whether `slug` is already a canonical slug (what [`slugify`] returns).
+#[must_use]
+pub fn is_slug(slug: &str) -> bool {
+    !slug.is_empty() && slugify(slug) == slug
+}
+
+/// Similarity of two texts, from 0.0 (nothing shared) to 1.0 (same words).
+///
+/// Texts shorter than a shingle are compared word by word.
+#[must_use]
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (shingles(a), shingles(b));
+    if a.is_empty() || b.is_empty() {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+    let shared = a.intersection(&b).count();
+
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+/// Whether two texts are similar enough to count as duplicate content.
+#[must_use]
+pub fn is_near_duplicate(a: &str, b: &str) -> bool {
+    similarity(a, b) >= DUPLICATE_THRESHOLD
+}
+
+fn shingles(text: &str) -> HashSet<String> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() < SHINGLE_WORDS {
+        return words.into_iter().collect();
+    }
+
+    words.windows(SHINGLE_WORDS).map(|w| w.join(" ")).collect()
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugs() {
+        assert_eq!(slugify("Kenilworth"), "kenilworth");
+        assert_eq!(slugify("Leamington Spa"), "leamington-spa");
+        assert_eq!(slugify("TV & Shelf Mounting"), "tv-shelf-mounting");
+        assert_eq!(slugify("---"), "");
+        assert!(is_slug("furniture-assembly"));
+        assert!(!is_slug("Furniture Assembly"));
+        assert!(!is_slug("trailing-"));
+        assert!(!is_slug(""));
+    }
+
+    #[test]
+    fn test_similarity() {
+        let original = "We fix leaking taps, blocked sinks and running toilets across \
+                        Kenilworth, usually the same week you call.";
+        let town_swapped = "We fix leaking taps, blocked sinks and running toilets across \
+                            Warwick, usually the same week you call.";
+        let rewritten = "Kenilworth's older terraces often have lead pipework; we replace \
+                         it and fit new stopcocks.";
+
+        assert_eq!(similarity(original, original), 1.0);
+        assert!(is_near_duplicate(original, town_swapped));
+        assert!(!is_near_duplicate(original, rewritten));
+        assert!(similarity(original, rewritten) < 0.1);
+        assert_eq!(similarity("Plumbing", "plumbing!"), 1.0);
+        assert_eq!(similarity("", "Plumbing"), 0.0);
+    }
+}
+
+// endregion: --- Tests
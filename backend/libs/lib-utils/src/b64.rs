@@ -1,8 +1,10 @@
 //! Base64 URL-safe encoding/decoding utilities.
 //!
 //! Uses URL-safe encoding without padding, suitable for tokens and URLs.
+//! [`b64_decode`] reads standard, padded base64 as found in `data:` URLs.
 
 use crate::URL_SAFE_NO_PAD;
+use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
 
 /// Encode bytes to URL-safe base64 string (no padding).
@@ -24,6 +26,15 @@ pub fn b64u_decode(b64u: &str) -> Result<Vec<u8>, base64::DecodeError> {
     URL_SAFE_NO_PAD.decode(b64u)
 }
 
+/// Decode standard (padded) base64 string to bytes, e.g. the payload of a
+/// `data:` URL.
+///
+/// # Errors
+/// Returns error if input is not valid base64.
+pub fn b64_decode(b64: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    STANDARD.decode(b64)
+}
+
 /// Decode URL-safe base64 string to UTF-8 string.
 ///
 /// # Errors
@@ -61,4 +72,10 @@ mod tests {
         let decoded = b64u_decode_to_string(&encoded).unwrap();
         assert_eq!(decoded, "test string");
     }
+
+    #[test]
+    fn test_b64_decode_standard() {
+        assert_eq!(b64_decode("/+8=").unwrap(), vec![0xFF, 0xEF]);
+        assert!(b64_decode("_-8").is_err());
+    }
 }
@@ -17,7 +17,8 @@
 //!
 //! ## Modules
 //!
-//! - **[`b64`]** - URL-safe base64 encoding/decoding (no padding)
+//! - **[`b64`]** - URL-safe base64 encoding/decoding (no padding), standard
+//!   base64 decoding
 //! - **[`envs`]** - Environment variable access helpers
 //! - **[`time_utils`]** - RFC3339 time formatting and parsing
 //!
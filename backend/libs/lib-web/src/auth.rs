@@ -0,0 +1,161 @@
+//! # Admin Authentication
+//!
+//! Everything under [`ADMIN_PATH_PREFIX`] needs an admin access token (see
+//! [`lib_core::auth`]) issued for the tenant serving the request's host.
+//! The token's user is handed to handlers as a [`Ctx`] request extension:
+//!
+//! ```rust,ignore
+//! async fn handler(Extension(ctx): Extension<Ctx>) { /* ctx.username() */ }
+//! ```
+
+use crate::{Error, Result, TenantMm};
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::{header, HeaderMap};
+use axum::middleware::Next;
+use axum::response::Response;
+use lib_core::auth::{self, AdminToken, TokenError};
+use lib_core::model::user::UserBmc;
+use lib_core::model::{Error as ModelError, ModelManager};
+use lib_core::Ctx;
+use time::OffsetDateTime;
+use tracing::warn;
+
+/// Paths only admins may use.
+pub const ADMIN_PATH_PREFIX: &str = "/api/admin/";
+
+/// Middleware refusing admin requests without a valid admin token, and
+/// adding the admin's [`Ctx`] to those with one. Other requests pass
+/// through untouched.
+///
+/// # Errors
+///
+/// `AuthRequired` without a bearer token, `InvalidToken` for a token that
+/// is malformed, forged, revoked or for another tenant, and `TokenExpired`.
+pub async fn mw_admin_auth(
+    State(mm): State<ModelManager>,
+    req: Request,
+    next: Next,
+) -> Result<Response> {
+    if !req.uri().path().starts_with(ADMIN_PATH_PREFIX) {
+        return Ok(next.run(req).await);
+    }
+
+    let (mut parts, body) = req.into_parts();
+    let TenantMm(tenant_mm) = TenantMm::from_request_parts(&mut parts, &mm).await?;
+    let ctx = admin_ctx(&tenant_mm, &parts.headers).await?;
+    parts.extensions.insert(ctx);
+
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
+
+/// The admin a request's token was issued to.
+async fn admin_ctx(mm: &ModelManager, headers: &HeaderMap) -> Result<Ctx> {
+    let Some(key) = auth::token_key() else {
+        warn!("ADMIN_TOKEN_KEY not set - admin endpoints refuse every request");
+        return Err(Error::AuthRequired);
+    };
+    let token = bearer_token(headers).ok_or(Error::AuthRequired)?;
+    let token = AdminToken::parse(token).map_err(|_| Error::InvalidToken)?;
+    if token.tenant_id != mm.tenant_id() {
+        return Err(Error::InvalidToken);
+    }
+
+    let user = match UserBmc::get_for_auth(mm, token.user_id).await {
+        Ok(user) => user,
+        Err(ModelError::EntityNotFound { .. }) => return Err(Error::InvalidToken),
+        Err(e) => return Err(e.into()),
+    };
+    let token_salt = user.token_salt.ok_or(Error::InvalidToken)?;
+    token
+        .verify(key, token_salt, OffsetDateTime::now_utc().unix_timestamp())
+        .map_err(|e| match e {
+            TokenError::Expired => Error::TokenExpired,
+            TokenError::Malformed | TokenError::InvalidSignature => Error::InvalidToken,
+        })?;
+
+    Ok(Ctx::new(user.id.into(), user.username))
+}
+
+/// The token in an `Authorization: Bearer` header.
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{HeaderValue, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use lib_core::_dev_utils;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_bearer_token() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(bearer_token(&headers), None);
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Basic abc"));
+        assert_eq!(bearer_token(&headers), None);
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer "));
+        assert_eq!(bearer_token(&headers), None);
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer 12.1.2000.sig"),
+        );
+        assert_eq!(bearer_token(&headers), Some("12.1.2000.sig"));
+    }
+
+    #[tokio::test]
+    async fn test_admin_paths_need_token() {
+        let mm = _dev_utils::init_test().await;
+        let app = Router::new()
+            .route("/api/site", get(|| async { "site" }))
+            .route("/api/admin/search", get(|| async { "hits" }))
+            .layer(axum::middleware::from_fn_with_state(mm, mw_admin_auth));
+        let request = |uri: &str, token: Option<&'static str>| {
+            let mut request = Request::builder()
+                .uri(uri)
+                .header(header::HOST, "localhost");
+            if let Some(token) = token {
+                request = request.header(header::AUTHORIZATION, token);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let public = app
+            .clone()
+            .oneshot(request("/api/site", None))
+            .await
+            .unwrap();
+        let anonymous = app
+            .clone()
+            .oneshot(request("/api/admin/search", None))
+            .await
+            .unwrap();
+        let forged = app
+            .oneshot(request(
+                "/api/admin/search",
+                Some("Bearer 1.1.99999999999.AAAA"),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(public.status(), StatusCode::OK);
+        assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(forged.status(), StatusCode::UNAUTHORIZED);
+    }
+}
+
+// endregion: --- Tests
@@ -22,6 +22,7 @@
 //! | InsufficientPermissions | 403 | User lacks required permissions |
 //! | NotAMember | 403 | User not member of resource |
 //! | EntityNotFound | 404 | Resource doesn't exist |
+//! | SlugNotFound | 404 | No resource at that slug |
 //! | TradesmanAlreadyExists | 409 | Username taken |
 //! | InvalidData | 400 | Input validation failed |
 //! | Other | 500 | Unexpected error |
@@ -165,7 +166,9 @@ impl Error {
             Error::ValidationError(_) | Error::PasswordError(_) => StatusCode::BAD_REQUEST,
 
             Error::Model(model_err) => match model_err {
-                ModelError::EntityNotFound { .. } => StatusCode::NOT_FOUND,
+                ModelError::EntityNotFound { .. } | ModelError::SlugNotFound { .. } => {
+                    StatusCode::NOT_FOUND
+                }
                 ModelError::UserAlreadyExists { .. } | ModelError::UniqueViolation { .. } => {
                     StatusCode::CONFLICT
                 }
@@ -220,6 +223,11 @@ impl IntoResponse for Error {
                         "Resource not found",
                         Some(format!("{} with id {} not found", entity, id).into()),
                     ),
+                    ModelError::SlugNotFound { entity, slug } => (
+                        StatusCode::NOT_FOUND,
+                        "Resource not found",
+                        Some(format!("{} '{}' not found", entity, slug).into()),
+                    ),
                     ModelError::UserAlreadyExists { username } => (
                         StatusCode::CONFLICT,
                         "User already exists",
@@ -330,6 +338,16 @@ mod tests {
         assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn test_slug_not_found_status() {
+        let model_err = ModelError::SlugNotFound {
+            entity: "Area",
+            slug: "atlantis".into(),
+        };
+        let err = Error::Model(model_err);
+        assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+    }
+
     #[test]
     fn test_user_already_exists_status() {
         let model_err = ModelError::UserAlreadyExists {
@@ -2,9 +2,18 @@
 //!
 //! Custom Axum extractors for common web patterns.
 use crate::Error;
-use axum::extract::{FromRequest, Json, Request};
+use axum::extract::{ConnectInfo, FromRequest, FromRequestParts, Json, Request};
+use axum::http::request::Parts;
+use axum::http::{header, HeaderMap};
+use lib_core::cache::{Cache, CacheConfig};
+use lib_core::core_config;
+use lib_core::model::tenant::TenantBmc;
+use lib_core::model::ModelManager;
 use serde::de::DeserializeOwned;
 use shared::validation::Validate;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+use std::time::Duration;
 
 /// Extractor that validates the JSON payload using the `shared::validation::Validate` trait.
 ///
@@ -37,11 +46,116 @@ impl<T> std::ops::Deref for ValidatedJson<T> {
     }
 }
 
+/// How long a host's tenant is remembered. Domain changes take effect
+/// within this time.
+const TENANT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Extractor giving a [`ModelManager`] for the tenant serving the request's
+/// host, so every query the handler makes is limited to that tenant's rows.
+///
+/// The host comes from `X-Forwarded-Host` when one of the `TRUSTED_PROXIES`
+/// (such as the site's SSR server) set it, otherwise from `Host`. Requests
+/// for hosts no tenant serves are refused with 404 Not Found.
+pub struct TenantMm(pub ModelManager);
+
+impl FromRequestParts<ModelManager> for TenantMm {
+    type Rejection = Error;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        mm: &ModelManager,
+    ) -> Result<Self, Self::Rejection> {
+        static TENANTS: OnceLock<Cache<String, i32>> = OnceLock::new();
+        let tenants = TENANTS.get_or_init(|| {
+            Cache::new(CacheConfig {
+                default_ttl: TENANT_CACHE_TTL,
+                max_entries: 1000,
+            })
+        });
+
+        let host = request_host(&parts.headers, from_trusted_proxy(parts)).unwrap_or_default();
+        let tenant_id = match tenants.get(&host) {
+            Some(id) => id,
+            None => {
+                let id = TenantBmc::resolve(mm, &host).await?.id;
+                tenants.set(host, id);
+                id
+            }
+        };
+
+        Ok(TenantMm(mm.for_tenant(tenant_id)?))
+    }
+}
+
+impl std::ops::Deref for TenantMm {
+    type Target = ModelManager;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Whether the request came straight from one of the `TRUSTED_PROXIES`.
+/// Requests without a known peer address are not trusted.
+fn from_trusted_proxy(parts: &Parts) -> bool {
+    parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .is_some_and(|ConnectInfo(peer)| is_trusted_proxy(peer.ip()))
+}
+
+fn is_trusted_proxy(ip: IpAddr) -> bool {
+    core_config().TRUSTED_PROXIES.contains(&ip.to_canonical())
+}
+
+/// The address a request was made from.
+///
+/// `X-Forwarded-For` is only read when the connecting peer is one of the
+/// `TRUSTED_PROXIES`, and then the right-most address that isn't a trusted
+/// proxy is taken: entries further left were written by the client and
+/// can't be relied on. Anyone else gets their own peer address.
+pub fn client_ip(headers: &HeaderMap, peer: SocketAddr) -> IpAddr {
+    let peer = peer.ip().to_canonical();
+    if !is_trusted_proxy(peer) {
+        return peer;
+    }
+
+    headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .flat_map(|value| value.to_str().unwrap_or_default().split(','))
+        .map(|entry| {
+            entry
+                .trim()
+                .parse::<IpAddr>()
+                .ok()
+                .map(|ip| ip.to_canonical())
+        })
+        .rev()
+        // An unreadable entry ends the chain of proxies we can vouch for
+        .find(|ip| ip.is_none_or(|ip| !is_trusted_proxy(ip)))
+        .flatten()
+        .unwrap_or(peer)
+}
+
+/// The host a request was made to: the `X-Forwarded-Host` of a trusted
+/// proxy, otherwise `Host`.
+fn request_host(headers: &HeaderMap, trusted_proxy: bool) -> Option<String> {
+    trusted_proxy
+        .then(|| headers.get("x-forwarded-host"))
+        .flatten()
+        .or_else(|| headers.get(header::HOST))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(|host| host.trim().to_ascii_lowercase())
+}
+
 // region:    --- Tests
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::http::HeaderValue;
     use shared::ContactForm;
 
     #[test]
@@ -71,6 +185,81 @@ mod tests {
         // Access inner value via .0
         assert_eq!(validated.0.name, "Jane Doe");
     }
+
+    #[test]
+    fn test_request_host() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(request_host(&headers, true), None);
+
+        headers.insert(header::HOST, HeaderValue::from_static("API.example.com"));
+        assert_eq!(
+            request_host(&headers, true).as_deref(),
+            Some("api.example.com")
+        );
+
+        headers.insert(
+            "x-forwarded-host",
+            HeaderValue::from_static("jonesplumbing.co.uk, proxy.internal"),
+        );
+        assert_eq!(
+            request_host(&headers, true).as_deref(),
+            Some("jonesplumbing.co.uk")
+        );
+        // Anyone else's X-Forwarded-Host is ignored
+        assert_eq!(
+            request_host(&headers, false).as_deref(),
+            Some("api.example.com")
+        );
+    }
+
+    #[test]
+    fn test_from_trusted_proxy() {
+        let parts = |peer: Option<&str>| {
+            let mut request = Request::builder();
+            if let Some(peer) = peer {
+                request = request.extension(ConnectInfo(peer.parse::<SocketAddr>().unwrap()));
+            }
+            request.body(()).unwrap().into_parts().0
+        };
+
+        assert!(from_trusted_proxy(&parts(Some("127.0.0.1:40000"))));
+        assert!(from_trusted_proxy(&parts(Some("[::ffff:127.0.0.1]:40000"))));
+        assert!(!from_trusted_proxy(&parts(Some("203.0.113.9:40000"))));
+        assert!(!from_trusted_proxy(&parts(None)));
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let headers = |forwarded_for: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-forwarded-for", HeaderValue::from_static(forwarded_for));
+            headers
+        };
+        let ip = |ip: &str| ip.parse::<IpAddr>().unwrap();
+        let proxy: SocketAddr = "127.0.0.1:40000".parse().unwrap();
+        let visitor: SocketAddr = "203.0.113.9:40000".parse().unwrap();
+
+        // A faked header from an untrusted peer is ignored
+        assert_eq!(
+            client_ip(&headers("198.51.100.1"), visitor),
+            ip("203.0.113.9")
+        );
+        assert_eq!(client_ip(&HeaderMap::new(), proxy), ip("127.0.0.1"));
+        // Behind a proxy, the right-most address the proxy didn't add
+        // itself wins over anything the client sent
+        assert_eq!(
+            client_ip(&headers("198.51.100.1, 203.0.113.9"), proxy),
+            ip("203.0.113.9")
+        );
+        assert_eq!(
+            client_ip(&headers("198.51.100.1, 203.0.113.9, ::1"), proxy),
+            ip("203.0.113.9")
+        );
+        assert_eq!(
+            client_ip(&headers("198.51.100.1, not-an-ip"), proxy),
+            ip("127.0.0.1")
+        );
+    }
 }
 
 // endregion: --- Tests
@@ -8,18 +8,22 @@
 //! - **[`Error`]** - Unified error type for web operations
 //! - **[`Result<T>`]** - Web operation result type
 //! - **[`ValidatedJson`]** - Auto-validating JSON extractor
+//! - **[`TenantMm`]** - ModelManager for the tenant serving the request's host
+//! - **[`mw_admin_auth`]** - Admin token check for everything under `/api/admin/`
 //!
 //! ## Error Handling Pattern
 //!
 //! Errors from lib-core (database, validation, etc.) are automatically
 //! converted to appropriate HTTP status codes:
-//! - `ModelError::EntityNotFound` → 404 Not Found
+//! - `ModelError::EntityNotFound`, `ModelError::SlugNotFound` → 404 Not Found
 //! - `ModelError::InvalidData` → 400 Bad Request
 //! - Everything else → 500 Internal Server Error
 
+pub mod auth;
 pub mod error;
 pub mod extractors;
 
 // Re-export commonly used types
+pub use auth::mw_admin_auth;
 pub use error::{Error, Result};
-pub use extractors::ValidatedJson;
+pub use extractors::{client_ip, TenantMm, ValidatedJson};
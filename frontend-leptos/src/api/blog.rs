@@ -0,0 +1,24 @@
+//! Blog API client.
+//!
+//! Posts are written and scheduled in the backend, which only returns the
+//! ones that are live. Works on both server (SSR) and client (WASM).
+
+use super::client::send;
+use shared::{ApiResponse, BlogPost};
+
+/// Fetch the live posts, newest first, optionally in one category.
+pub async fn fetch_posts(category: Option<&str>) -> Result<Vec<BlogPost>, String> {
+    let path = match category {
+        Some(category) => format!("/api/blog?category={}", category),
+        None => "/api/blog".to_string(),
+    };
+    let response: ApiResponse<Vec<BlogPost>> = send("GET", &path, None).await?;
+    response.data.ok_or(response.message)
+}
+
+/// Fetch a live post by slug, e.g. `local-seo-guide`.
+pub async fn fetch_post(slug: &str) -> Result<BlogPost, String> {
+    let path = format!("/api/blog/{}", slug);
+    let response: ApiResponse<BlogPost> = send("GET", &path, None).await?;
+    response.data.ok_or(response.message)
+}
@@ -0,0 +1,112 @@
+//! HTTP transport shared by the API clients.
+//!
+//! Works on both server (SSR) and client (WASM); server-side requests carry
+//! the page's host so the backend serves the right site.
+
+use serde::de::DeserializeOwned;
+
+/// Send a JSON request to the backend and decode the JSON response.
+pub(crate) async fn send<T: DeserializeOwned>(
+    method: &str,
+    path: &str,
+    body: Option<String>,
+) -> Result<T, String> {
+    #[cfg(feature = "ssr")]
+    {
+        use reqwest::{Client, Method};
+        let api_url =
+            std::env::var("API_URL").unwrap_or_else(|_| "http://127.0.0.1:8080".to_string());
+        let method = Method::from_bytes(method.as_bytes()).map_err(|e| e.to_string())?;
+        let mut request = Client::new().request(method, format!("{}{}", api_url, path));
+        // The backend serves the tenant for the host the page was asked for
+        if let Some(host) = page_host() {
+            request = request.header("X-Forwarded-Host", host);
+        }
+        if let Some(body) = body {
+            request = request
+                .header("Content-Type", "application/json")
+                .body(body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {}", e))?;
+
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| format!("Network error: {}", e))?;
+        if !status.is_success() {
+            return Err(error_message(status.as_u16(), text));
+        }
+        serde_json::from_str(&text).map_err(|e| format!("Invalid response: {}", e))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        use wasm_bindgen::JsCast;
+        use web_sys::{Request, RequestInit, RequestMode, Response};
+
+        let opts = RequestInit::new();
+        opts.set_method(method);
+        opts.set_mode(RequestMode::Cors);
+        if let Some(body) = &body {
+            opts.set_body(&wasm_bindgen::JsValue::from_str(body));
+        }
+
+        let request = Request::new_with_str_and_init(path, &opts)
+            .map_err(|_| "Failed to create request".to_string())?;
+        if body.is_some() {
+            request
+                .headers()
+                .set("Content-Type", "application/json")
+                .map_err(|_| "Failed to set headers".to_string())?;
+        }
+
+        let window = web_sys::window().ok_or("No window object")?;
+        let resp_value = wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request))
+            .await
+            .map_err(|_| "Network error".to_string())?;
+        let resp: Response = resp_value
+            .dyn_into()
+            .map_err(|_| "Invalid response".to_string())?;
+
+        let text = wasm_bindgen_futures::JsFuture::from(
+            resp.text().map_err(|_| "Invalid response".to_string())?,
+        )
+        .await
+        .map_err(|_| "Network error".to_string())?
+        .as_string()
+        .unwrap_or_default();
+        if !resp.ok() {
+            return Err(error_message(resp.status(), text));
+        }
+        serde_json::from_str(&text).map_err(|e| format!("Invalid response: {}", e))
+    }
+}
+
+/// Host the page being rendered was requested on (see
+/// [`request_host`](super::site::request_host)).
+#[cfg(feature = "ssr")]
+fn page_host() -> Option<String> {
+    use axum::http::request::Parts;
+    use leptos::prelude::use_context;
+
+    let parts = use_context::<Parts>()?;
+    super::site::request_host(&parts.headers).map(str::to_string)
+}
+
+/// The backend sends validation failures (unknown promo code, service no
+/// longer offered, postcode we don't cover) as plain text, or as JSON with a
+/// `detail`; show those, and a generic message for anything else.
+fn error_message(status: u16, body: String) -> String {
+    if status == 400 && !body.is_empty() {
+        serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|json| json["detail"].as_str().map(str::to_string))
+            .unwrap_or(body)
+    } else {
+        format!("Request failed (status: {}). Please try again.", status)
+    }
+}
@@ -0,0 +1,22 @@
+//! Landing page API client.
+//!
+//! Area and service-in-town pages are assembled by the backend from the
+//! areas and services in the database. Works on both server (SSR) and
+//! client (WASM).
+
+use super::client::send;
+use shared::{ApiResponse, AreaPage, ServiceAreaPage};
+
+/// Fetch the page for an area, e.g. `coventry`.
+pub async fn fetch_area_page(area: &str) -> Result<AreaPage, String> {
+    let path = format!("/api/areas/{}", area);
+    let response: ApiResponse<AreaPage> = send("GET", &path, None).await?;
+    response.data.ok_or(response.message)
+}
+
+/// Fetch the page for a service in a town, e.g. `plumbing` in `coventry`.
+pub async fn fetch_service_area_page(service: &str, town: &str) -> Result<ServiceAreaPage, String> {
+    let path = format!("/api/services/{}/{}", service, town);
+    let response: ApiResponse<ServiceAreaPage> = send("GET", &path, None).await?;
+    response.data.ok_or(response.message)
+}
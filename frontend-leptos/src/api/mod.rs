@@ -2,4 +2,10 @@
 //!
 //! HTTP client functions for backend API communication.
 
+pub mod blog;
+mod client;
 pub mod contact;
+pub mod landing;
+pub mod onboarding;
+pub mod pricing;
+pub mod site;
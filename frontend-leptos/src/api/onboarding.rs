@@ -0,0 +1,16 @@
+//! Client onboarding API client.
+//!
+//! New clients send us their business details from the onboarding wizard.
+//! Works on both server (SSR) and client (WASM).
+
+use super::client::send;
+use shared::{ApiResponse, OnboardingForm, OnboardingReceipt, Validate};
+
+/// Submit a new client's details for review.
+pub async fn submit_onboarding(form: &OnboardingForm) -> Result<OnboardingReceipt, String> {
+    form.validate()?;
+    let body = serde_json::to_string(form).map_err(|e| format!("Serialization error: {}", e))?;
+    let response: ApiResponse<OnboardingReceipt> =
+        send("POST", "/api/onboarding", Some(body)).await?;
+    response.data.ok_or(response.message)
+}
@@ -0,0 +1,31 @@
+//! Pricing API client for the instant quote calculator.
+//!
+//! The calculator reads its services and options from the backend pricing
+//! catalog and asks the backend for each estimate, so prices are only ever
+//! defined in the database. The travel zone comes from the customer's
+//! postcode via the coverage endpoint.
+
+use super::client::send;
+use shared::{ApiResponse, Coverage, InstantQuoteRequest, InstantQuoteResponse, PricingCatalog};
+
+/// Fetch the active services and pricing rules.
+pub async fn fetch_catalog() -> Result<PricingCatalog, String> {
+    send("GET", "/api/quote/catalog", None).await
+}
+
+/// Ask the backend for an estimate for a job.
+pub async fn instant_quote(request: &InstantQuoteRequest) -> Result<InstantQuoteResponse, String> {
+    let body = serde_json::to_string(request).map_err(|e| format!("Serialization error: {}", e))?;
+    send("POST", "/api/quote/instant", Some(body)).await
+}
+
+/// Check whether we cover a postcode, and which travel zone it is in.
+pub async fn check_coverage(postcode: &str) -> Result<Coverage, String> {
+    let compact: String = postcode
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect();
+    let path = format!("/api/coverage?postcode={}", compact);
+    let response: ApiResponse<Coverage> = send("GET", &path, None).await?;
+    response.data.ok_or(response.message)
+}
@@ -0,0 +1,73 @@
+//! Site configuration API client.
+//!
+//! The backend picks the business a site is for from the host it was
+//! requested on. Works on both server (SSR) and client (WASM).
+
+use super::client::send;
+use shared::{ApiResponse, SiteConfig};
+
+/// Fetch the business, branding and navigation for this site.
+pub async fn fetch_site() -> Result<SiteConfig, String> {
+    let response: ApiResponse<SiteConfig> = send("GET", "/api/site", None).await?;
+    response.data.ok_or(response.message)
+}
+
+/// The host a request was made to: `X-Forwarded-Host` when behind a
+/// proxy, otherwise `Host`.
+#[cfg(feature = "ssr")]
+pub fn request_host(headers: &axum::http::HeaderMap) -> Option<&str> {
+    headers
+        .get("x-forwarded-host")
+        .or_else(|| headers.get(axum::http::header::HOST))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+}
+
+/// How long a host's site configuration is reused by [`site_for_host`].
+#[cfg(feature = "ssr")]
+const SITE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// The site configuration for `host`, for server middleware that runs
+/// outside a page. Lookups are reused for a minute; `None` if the backend
+/// can't be reached.
+#[cfg(feature = "ssr")]
+pub async fn site_for_host(host: &str) -> Option<SiteConfig> {
+    use std::collections::HashMap;
+    use std::sync::{Mutex, OnceLock};
+    use std::time::Instant;
+
+    static CACHE: OnceLock<Mutex<HashMap<String, (Instant, SiteConfig)>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+
+    if let Some((at, site)) = cache.lock().ok().and_then(|c| c.get(host).cloned()) {
+        if at.elapsed() < SITE_TTL {
+            return Some(site);
+        }
+    }
+
+    let api_url = std::env::var("API_URL").unwrap_or_else(|_| "http://127.0.0.1:8080".to_string());
+    let site = reqwest::Client::new()
+        .get(format!("{}/api/site", api_url))
+        .header("x-forwarded-host", host)
+        .send()
+        .await;
+    let site = match site {
+        Ok(response) => match response.json::<ApiResponse<SiteConfig>>().await {
+            Ok(body) => body.data?,
+            Err(e) => {
+                log::warn!("Site lookup for {} failed: {}", host, e);
+                return None;
+            }
+        },
+        Err(e) => {
+            log::warn!("Site lookup for {} failed: {}", host, e);
+            return None;
+        }
+    };
+
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(host.to_string(), (Instant::now(), site.clone()));
+    }
+    Some(site)
+}
@@ -3,9 +3,11 @@
 //! Provides components for injecting SEO metadata into page `<head>`.
 //! Uses `PageMetadata` from shared crate for consistency.
 
+use crate::pages::examples::handyman_app::site::use_site;
 use leptos::prelude::*;
-use leptos_meta::{Meta, Script, Title};
-use shared::PageMetadata;
+use leptos_meta::{Link, Meta, Script, Title};
+use serde_json::{json, Value};
+use shared::{BlogFeed, PageMetadata, SiteConfig};
 
 /// SEO Head component.
 ///
@@ -16,32 +18,52 @@ pub fn SeoHead(
     /// Page metadata (title, description, etc.)
     #[prop(into)]
     metadata: PageMetadata,
+    /// Blog feeds to advertise for feed readers to discover
+    #[prop(optional)]
+    feeds: Vec<BlogFeed>,
 ) -> impl IntoView {
     let title = metadata.title;
     let description = metadata.description;
-    let image = metadata
-        .og_image
-        .unwrap_or_else(|| "https://xftradesman.com/og-image.jpg".to_string());
-    let url = metadata
-        .canonical_url
-        .unwrap_or_else(|| "https://xftradesman.com".to_string());
+    // Pages without their own URL or image leave them out rather than
+    // claim another site's; feed links are relative, so each site
+    // advertises its own feeds.
+    let canonical = metadata.canonical_url.map(|url| {
+        view! {
+            <link rel="canonical" href=url.clone()/>
+            <Meta property="og:url" content=url/>
+        }
+    });
+    let image = metadata.og_image.map(|image| {
+        view! {
+            <Meta property="og:image" content=image.clone()/>
+            <Meta name="twitter:image" content=image/>
+        }
+    });
+    let feed_links = feeds
+        .into_iter()
+        .map(|feed| {
+            view! {
+                <Link rel="alternate" type_="application/rss+xml" title=feed.title() href=feed.rss_path()/>
+                <Link rel="alternate" type_="application/atom+xml" title=feed.title() href=feed.atom_path()/>
+            }
+        })
+        .collect_view();
 
     view! {
         <Title text=title.clone()/>
         <Meta name="description" content=description.clone()/>
         <Meta name="viewport" content="width=device-width, initial-scale=1"/>
-        <link rel="canonical" href=url.clone()/>
+        {canonical}
+        {feed_links}
 
         <Meta property="og:title" content=title.clone()/>
         <Meta property="og:description" content=description.clone()/>
-        <Meta property="og:image" content=image.clone()/>
-        <Meta property="og:url" content=url.clone()/>
         <Meta property="og:type" content="website"/>
 
         <Meta name="twitter:card" content="summary_large_image"/>
         <Meta name="twitter:title" content=title/>
         <Meta name="twitter:description" content=description/>
-        <Meta name="twitter:image" content=image/>
+        {image}
     }
 }
 
@@ -93,102 +115,154 @@ pub fn LocalBusinessSchema() -> impl IntoView {
 /// Handyman LocalBusiness Schema.org structured data.
 ///
 /// Specifically for the handyman-coventry site with full service details.
+/// Other businesses using the site template get their own name, contact
+/// details, areas and services; the example's address, location and
+/// ratings are only claimed for the example itself.
 #[component]
 pub fn HandymanLocalBusinessSchema() -> impl IntoView {
+    let site = use_site();
+
     view! {
         <Script type_="application/ld+json">
-            {r#"{
-                "@context": "https://schema.org",
-                "@type": "HomeAndConstructionBusiness",
-                "name": "XF Tradesmen - Coventry Handyman",
-                "description": "Professional handyman services in Coventry and surrounding areas. Plumbing, electrical, carpentry, furniture assembly, and general repairs.",
-                "url": "https://xftradesman.com/handyman-coventry",
-                "telephone": "+44-7833-263486",
-                "email": "hello@xftradesman.com",
-                "address": {
-                    "@type": "PostalAddress",
-                    "streetAddress": "Coventry",
-                    "addressLocality": "Coventry",
-                    "addressRegion": "West Midlands",
-                    "postalCode": "CV1",
-                    "addressCountry": "GB"
-                },
-                "geo": {
-                    "@type": "GeoCoordinates",
-                    "latitude": 52.4068,
-                    "longitude": -1.5197
-                },
-                "areaServed": [
-                    {"@type": "City", "name": "Coventry"},
-                    {"@type": "City", "name": "Birmingham"},
-                    {"@type": "City", "name": "Solihull"},
-                    {"@type": "City", "name": "Warwick"},
-                    {"@type": "City", "name": "Leamington Spa"},
-                    {"@type": "City", "name": "Nuneaton"},
-                    {"@type": "City", "name": "Rugby"},
-                    {"@type": "City", "name": "Kenilworth"}
-                ],
-                "openingHoursSpecification": [
-                    {
-                        "@type": "OpeningHoursSpecification",
-                        "dayOfWeek": ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday"],
-                        "opens": "08:00",
-                        "closes": "18:00"
-                    },
-                    {
-                        "@type": "OpeningHoursSpecification",
-                        "dayOfWeek": "Saturday",
-                        "opens": "09:00",
-                        "closes": "16:00"
-                    }
-                ],
-                "priceRange": "££",
-                "aggregateRating": {
-                    "@type": "AggregateRating",
-                    "ratingValue": "4.9",
-                    "reviewCount": "127",
-                    "bestRating": "5",
-                    "worstRating": "1"
-                },
-                "hasOfferCatalog": {
-                    "@type": "OfferCatalog",
-                    "name": "Handyman Services",
-                    "itemListElement": [
-                        {
-                            "@type": "Offer",
-                            "itemOffered": {
-                                "@type": "Service",
-                                "name": "Plumbing Repairs",
-                                "description": "Leaky taps, toilet repairs, shower fitting"
-                            }
-                        },
-                        {
-                            "@type": "Offer",
-                            "itemOffered": {
-                                "@type": "Service",
-                                "name": "Electrical Work",
-                                "description": "Light fitting, socket installation, repairs"
-                            }
-                        },
-                        {
-                            "@type": "Offer",
-                            "itemOffered": {
-                                "@type": "Service",
-                                "name": "Furniture Assembly",
-                                "description": "IKEA, flatpack, office furniture"
-                            }
-                        },
-                        {
-                            "@type": "Offer",
-                            "itemOffered": {
-                                "@type": "Service",
-                                "name": "Carpentry",
-                                "description": "Doors, shelving, skirting boards"
-                            }
-                        }
-                    ]
-                }
-            }"#}
+            {move || site.with(handyman_schema)}
         </Script>
     }
 }
+
+const HANDYMAN_SCHEMA: &str = r#"{
+    "@context": "https://schema.org",
+    "@type": "HomeAndConstructionBusiness",
+    "name": "XF Tradesmen - Coventry Handyman",
+    "description": "Professional handyman services in Coventry and surrounding areas. Plumbing, electrical, carpentry, furniture assembly, and general repairs.",
+    "url": "https://xftradesman.com/handyman-coventry",
+    "telephone": "+44-7833-263486",
+    "email": "hello@xftradesman.com",
+    "address": {
+        "@type": "PostalAddress",
+        "streetAddress": "Coventry",
+        "addressLocality": "Coventry",
+        "addressRegion": "West Midlands",
+        "postalCode": "CV1",
+        "addressCountry": "GB"
+    },
+    "geo": {
+        "@type": "GeoCoordinates",
+        "latitude": 52.4068,
+        "longitude": -1.5197
+    },
+    "areaServed": [
+        {"@type": "City", "name": "Coventry"},
+        {"@type": "City", "name": "Birmingham"},
+        {"@type": "City", "name": "Solihull"},
+        {"@type": "City", "name": "Warwick"},
+        {"@type": "City", "name": "Leamington Spa"},
+        {"@type": "City", "name": "Nuneaton"},
+        {"@type": "City", "name": "Rugby"},
+        {"@type": "City", "name": "Kenilworth"}
+    ],
+    "openingHoursSpecification": [
+        {
+            "@type": "OpeningHoursSpecification",
+            "dayOfWeek": ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday"],
+            "opens": "08:00",
+            "closes": "18:00"
+        },
+        {
+            "@type": "OpeningHoursSpecification",
+            "dayOfWeek": "Saturday",
+            "opens": "09:00",
+            "closes": "16:00"
+        }
+    ],
+    "priceRange": "££",
+    "aggregateRating": {
+        "@type": "AggregateRating",
+        "ratingValue": "4.9",
+        "reviewCount": "127",
+        "bestRating": "5",
+        "worstRating": "1"
+    },
+    "hasOfferCatalog": {
+        "@type": "OfferCatalog",
+        "name": "Handyman Services",
+        "itemListElement": [
+            {
+                "@type": "Offer",
+                "itemOffered": {
+                    "@type": "Service",
+                    "name": "Plumbing Repairs",
+                    "description": "Leaky taps, toilet repairs, shower fitting"
+                }
+            },
+            {
+                "@type": "Offer",
+                "itemOffered": {
+                    "@type": "Service",
+                    "name": "Electrical Work",
+                    "description": "Light fitting, socket installation, repairs"
+                }
+            },
+            {
+                "@type": "Offer",
+                "itemOffered": {
+                    "@type": "Service",
+                    "name": "Furniture Assembly",
+                    "description": "IKEA, flatpack, office furniture"
+                }
+            },
+            {
+                "@type": "Offer",
+                "itemOffered": {
+                    "@type": "Service",
+                    "name": "Carpentry",
+                    "description": "Doors, shelving, skirting boards"
+                }
+            }
+        ]
+    }
+}"#;
+
+fn handyman_schema(site: &SiteConfig) -> String {
+    let mut schema: Value =
+        serde_json::from_str(HANDYMAN_SCHEMA).expect("HANDYMAN_SCHEMA is valid JSON");
+    if !site.client_site {
+        return schema.to_string();
+    }
+
+    schema["name"] = json!(site.business_name);
+    schema["description"] = json!(site.tagline.clone().unwrap_or_default());
+    schema["telephone"] = json!(site
+        .tel_href()
+        .map(|tel| tel.trim_start_matches("tel:").to_string()));
+    schema["email"] = json!(site.email);
+    schema["areaServed"] = site
+        .areas
+        .iter()
+        .map(|area| json!({"@type": "City", "name": area.name}))
+        .collect();
+    if !site.services.is_empty() {
+        schema["hasOfferCatalog"]["itemListElement"] = site
+            .services
+            .iter()
+            .map(|service| {
+                json!({
+                    "@type": "Offer",
+                    "itemOffered": {"@type": "Service", "name": service.name}
+                })
+            })
+            .collect();
+    }
+    if let Some(object) = schema.as_object_mut() {
+        for key in [
+            "url",
+            "address",
+            "geo",
+            "aggregateRating",
+            "openingHoursSpecification",
+        ] {
+            object.remove(key);
+        }
+    }
+
+    schema.to_string()
+}
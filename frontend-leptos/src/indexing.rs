@@ -0,0 +1,165 @@
+//! Search engine indexing controls.
+//!
+//! Only production is indexed. Unless `DEPLOY_ENV` is `production`, as on
+//! staging, previews and local runs, robots.txt disallows everything and
+//! every response carries `X-Robots-Tag: noindex`. Production treats the
+//! hosts of a tenant set to noindex the same way, and sends the header on
+//! the [`NOINDEX_ROUTES`](crate::NOINDEX_ROUTES) everywhere.
+//!
+//! A host whose site the backend can't confirm is indexable is kept out too,
+//! so a noindex tenant isn't exposed while the backend is unreachable.
+//!
+//! Each tenant's robots.txt points crawlers at the sitemap on its own
+//! origin (see [`site_origin`]); the platform's site uses the canonical
+//! origin, `PUBLIC_BASE_URL`.
+
+use crate::api::site::{request_host, site_for_host};
+use crate::is_noindex_path;
+use axum::extract::Request;
+use axum::http::{header, HeaderMap, HeaderValue};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use shared::SiteConfig;
+
+/// The canonical origin when `PUBLIC_BASE_URL` isn't set.
+const DEFAULT_ORIGIN: &str = "https://xftradesman.com";
+
+/// Paths crawlers are kept out of: the API proxy serves data, not pages.
+const DISALLOWED_PATHS: &[&str] = &["/api/"];
+
+/// Whether this deployment is production, and so may be indexed.
+pub fn is_production() -> bool {
+    std::env::var("DEPLOY_ENV").is_ok_and(|env| env.eq_ignore_ascii_case("production"))
+}
+
+/// The site's canonical origin, e.g. `https://xftradesman.com`.
+pub fn origin() -> String {
+    std::env::var("PUBLIC_BASE_URL")
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_else(|_| DEFAULT_ORIGIN.to_string())
+}
+
+/// The origin of the site on `host`: a client business's own domain, or
+/// the canonical [`origin`] for the platform's site and when the site
+/// isn't known.
+pub fn site_origin(host: &str, site: Option<&SiteConfig>) -> String {
+    match site {
+        Some(site) if site.client_site && !host.is_empty() => format!("https://{}", host),
+        _ => origin(),
+    }
+}
+
+/// Handler for /robots.txt
+pub async fn robots_handler(headers: HeaderMap) -> Response {
+    let host = request_host(&headers).unwrap_or_default();
+    let site = site_for_host(host).await;
+    let indexable = is_production() && may_index(site.as_ref());
+    let robots = robots_txt(indexable, &site_origin(host, site.as_ref()));
+
+    ([(header::CONTENT_TYPE, "text/plain")], robots).into_response()
+}
+
+/// Middleware adding `X-Robots-Tag: noindex` to responses search engines
+/// shouldn't index.
+pub async fn x_robots_tag_middleware(req: Request, next: Next) -> Response {
+    let noindex = is_noindex_path(req.uri().path())
+        || !is_indexable(request_host(req.headers()).unwrap_or_default()).await;
+
+    let mut response = next.run(req).await;
+    if noindex {
+        response
+            .headers_mut()
+            .insert("x-robots-tag", HeaderValue::from_static("noindex"));
+    }
+    response
+}
+
+// region:    --- Indexing Helpers
+
+/// Whether the site on `host` may be indexed.
+async fn is_indexable(host: &str) -> bool {
+    is_production() && may_index(site_for_host(host).await.as_ref())
+}
+
+/// Whether a site may be indexed; `None`, a site the backend couldn't
+/// supply, may not.
+fn may_index(site: Option<&SiteConfig>) -> bool {
+    site.is_some_and(|site| !site.noindex)
+}
+
+/// robots.txt for an `indexable` site, or one keeping every crawler out.
+fn robots_txt(indexable: bool, origin: &str) -> String {
+    if !indexable {
+        return "User-agent: *\nDisallow: /\n".to_string();
+    }
+
+    let mut robots = String::from("User-agent: *\nAllow: /\n");
+    for path in DISALLOWED_PATHS {
+        robots.push_str(&format!("Disallow: {}\n", path));
+    }
+    robots.push_str(&format!("\nSitemap: {}/sitemap.xml\n", origin));
+    robots
+}
+
+// endregion: --- Indexing Helpers
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robots_txt() {
+        let robots = robots_txt(true, "https://xftradesman.com");
+
+        assert!(robots.contains("Allow: /\n"));
+        assert!(robots.contains("Disallow: /api/\n"));
+        assert!(robots.ends_with("Sitemap: https://xftradesman.com/sitemap.xml\n"));
+        assert_eq!(
+            robots_txt(false, "https://xftradesman.com"),
+            "User-agent: *\nDisallow: /\n"
+        );
+    }
+
+    #[test]
+    fn test_may_index() {
+        let noindex = SiteConfig {
+            noindex: true,
+            ..Default::default()
+        };
+
+        assert!(may_index(Some(&SiteConfig::default())));
+        assert!(!may_index(Some(&noindex)));
+        // The backend being unreachable keeps the site out
+        assert!(!may_index(None));
+        assert_eq!(
+            robots_txt(may_index(None), "https://jonesplumbing.co.uk"),
+            "User-agent: *\nDisallow: /\n"
+        );
+    }
+
+    #[test]
+    fn test_site_origin() {
+        let client = SiteConfig {
+            client_site: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            site_origin("jonesplumbing.co.uk", Some(&client)),
+            "https://jonesplumbing.co.uk"
+        );
+        assert_eq!(
+            site_origin("xftradesman.com", Some(&SiteConfig::default())),
+            origin()
+        );
+        assert_eq!(site_origin("jonesplumbing.co.uk", None), origin());
+    }
+
+    #[test]
+    fn test_is_noindex_path() {
+        assert!(is_noindex_path("/onboarding"));
+        assert!(is_noindex_path("/handyman-coventry/admin/customers"));
+        assert!(!is_noindex_path("/handyman-coventry/administration"));
+        assert!(!is_noindex_path("/handyman-coventry"));
+    }
+}
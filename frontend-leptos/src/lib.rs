@@ -7,6 +7,8 @@
 //! - `pages`: Route page components
 //! - `components`: Reusable UI components  
 //! - `api`: Backend API client functions
+//! - `indexing`: robots.txt and `X-Robots-Tag`, by environment and tenant
+//! - `sitemap`: The sitemap, served by the SSR server
 
 // Increase recursion limit for complex Leptos view hierarchies
 #![recursion_limit = "1024"]
@@ -15,18 +17,24 @@ use leptos::prelude::*;
 use leptos_meta::*;
 use leptos_router::{
     components::{Outlet, ParentRoute, Route, Router, Routes},
+    hooks::use_location,
     path,
 };
 
 pub mod api;
 mod components;
+#[cfg(feature = "ssr")]
+pub mod indexing;
 mod pages;
+#[cfg(feature = "ssr")]
+pub mod sitemap;
 
 // Main pages
 use crate::pages::main::About;
 use crate::pages::main::Contact;
 use crate::pages::main::Home;
 use crate::pages::main::Industries;
+use crate::pages::main::Onboarding;
 use crate::pages::main::Packages;
 use crate::pages::main::Pricing;
 
@@ -63,6 +71,7 @@ use crate::pages::examples::handyman_app::pages::content::{
 use crate::pages::examples::handyman_app::pages::main::HandymanAreaPage;
 use crate::pages::examples::handyman_app::pages::main::HandymanFeatures;
 use crate::pages::examples::handyman_app::pages::main::HandymanHome;
+use crate::pages::examples::handyman_app::pages::main::HandymanServiceAreaPage;
 use crate::pages::examples::handyman_app::pages::main::HandymanServiceDetail;
 use crate::pages::examples::handyman_app::pages::main::HandymanServiceMap;
 use crate::pages::examples::handyman_app::pages::main::HandymanServices;
@@ -83,6 +92,29 @@ fn MainLayout() -> impl IntoView {
     }
 }
 
+/// Routes of [`App`] search engines are asked not to index, with the routes
+/// under them. They're left out of the sitemap and served with a `noindex`
+/// robots meta tag and `X-Robots-Tag` header.
+pub const NOINDEX_ROUTES: &[&str] = &["/onboarding", "/handyman-coventry/admin"];
+
+/// Whether `path` is one of the [`NOINDEX_ROUTES`] or under one.
+pub fn is_noindex_path(path: &str) -> bool {
+    NOINDEX_ROUTES.iter().any(|route| {
+        path.strip_prefix(route)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+/// Robots meta tag for the [`NOINDEX_ROUTES`].
+#[component]
+fn NoindexMeta() -> impl IntoView {
+    let location = use_location();
+    move || {
+        is_noindex_path(&location.pathname.get())
+            .then(|| view! { <Meta name="robots" content="noindex"/> })
+    }
+}
+
 #[component]
 pub fn App() -> impl IntoView {
     // Provides context that manages stylesheets, titles, meta tags, etc.
@@ -99,6 +131,7 @@ pub fn App() -> impl IntoView {
         <Link href="https://fonts.googleapis.com/css2?family=Inter:wght@300;400;500;600;700&family=Outfit:wght@400;500;600;700;800;900&display=swap" rel="stylesheet"/>
 
         <Router>
+            <NoindexMeta/>
             <Routes fallback=NotFound>
                 // Main Site Routes
                 <ParentRoute path=path!("/") view=MainLayout>
@@ -107,6 +140,7 @@ pub fn App() -> impl IntoView {
                     <Route path=path!("/packages") view=Packages/>
                     <Route path=path!("/about") view=About/>
                     <Route path=path!("/contact") view=Contact/>
+                    <Route path=path!("/onboarding") view=Onboarding/>
                     <Route path=path!("/coventry") view=Coventry/>
 
                     <Route path=path!("/blog") view=BlogIndex/>
@@ -125,6 +159,7 @@ pub fn App() -> impl IntoView {
                     <Route path=path!("/services/plumbing") view=PlumbingRepairs/>
                     <Route path=path!("/services/mounting") view=MountingInstallation/>
                     <Route path=path!("/services/:slug") view=HandymanServiceDetail/>
+                    <Route path=path!("/services/:service/:town") view=HandymanServiceAreaPage/>
                     <Route path=path!("/faq") view=HandymanFaq/>
                     <Route path=path!("/features") view=HandymanFeatures/>
                     <Route path=path!("/testimonials") view=HandymanTestimonials/>
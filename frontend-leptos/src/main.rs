@@ -35,9 +35,17 @@ async fn main() {
     // Build Axum router with Leptos integration
     let app = Router::new()
         .route("/health", get(|| async { "OK" })) // Health check for Fly.io
-        .route("/sitemap.xml", get(sitemap_handler))
-        .route("/robots.txt", get(robots_handler))
+        .route(
+            "/robots.txt",
+            get(frontend_leptos::indexing::robots_handler),
+        )
+        // Blog feeds are built by the API
+        .route("/blog/rss.xml", get(proxy_handler))
+        .route("/blog/atom.xml", get(proxy_handler))
+        .route("/blog/category/{category}/rss.xml", get(proxy_handler))
+        .route("/blog/category/{category}/atom.xml", get(proxy_handler))
         .route("/api/{*fn_name}", any(proxy_handler)) // Proxy API requests
+        .merge(frontend_leptos::sitemap::routes(&routes))
         .leptos_routes(&leptos_options, routes, {
             let leptos_options = leptos_options.clone();
             move || shell(leptos_options.clone())
@@ -49,150 +57,32 @@ async fn main() {
             axum::http::header::STRICT_TRANSPORT_SECURITY,
             HeaderValue::from_static("max-age=31536000; includeSubDomains"),
         ))
+        // Keep staging, noindex tenants and private pages out of search results
+        .layer(axum::middleware::from_fn(
+            frontend_leptos::indexing::x_robots_tag_middleware,
+        ))
+        // Client sites open on their own home page
+        .layer(axum::middleware::from_fn(client_site_root_middleware))
         // Canonical Redirect (www -> non-www)
         .layer(axum::middleware::from_fn(canonical_redirect_middleware));
 
     // Start the server
     log::info!("listening on http://{}", &addr);
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app.into_make_service())
-        .await
-        .unwrap();
-}
-
-/// Handler for /robots.txt - served directly from frontend
-#[cfg(feature = "ssr")]
-async fn robots_handler() -> axum::response::Response {
-    use axum::http::header;
-
-    let robots = r#"# XFTradesman Robots.txt
-User-agent: *
-Allow: /
-
-# Block admin and API endpoints
-Disallow: /admin/
-Disallow: /api/admin/
-Disallow: /handyman-coventry/admin/
-
-Sitemap: https://xftradesmen.fly.dev/sitemap.xml
-Crawl-delay: 1
-"#;
-
-    axum::response::Response::builder()
-        .header(header::CONTENT_TYPE, "text/plain")
-        .body(axum::body::Body::from(robots))
-        .unwrap_or_else(|_| axum::response::Response::default())
-}
-
-/// Handler for /sitemap.xml - served directly from frontend
-#[cfg(feature = "ssr")]
-async fn sitemap_handler() -> axum::response::Response {
-    use axum::http::header;
-
-    let base_url = "https://xftradesmen.fly.dev";
-
-    // Main XFTradesman pages
-    let main_routes = [
-        ("", "1.0", "weekly"),
-        ("/pricing", "0.8", "monthly"),
-        ("/packages", "0.8", "monthly"),
-        ("/about", "0.7", "monthly"),
-        ("/contact", "0.7", "monthly"),
-        ("/coventry", "0.6", "monthly"),
-        ("/blog", "0.7", "weekly"),
-        ("/industries", "0.6", "monthly"),
-        ("/terms", "0.3", "yearly"),
-        ("/faq", "0.6", "monthly"),
-        ("/service-agreement", "0.3", "yearly"),
-    ];
-
-    // Handyman Coventry example pages
-    let handyman_routes = [
-        ("/handyman-coventry", "0.9", "weekly"),
-        ("/handyman-coventry/services", "0.8", "weekly"),
-        (
-            "/handyman-coventry/services/furniture-assembly",
-            "0.7",
-            "monthly",
-        ),
-        ("/handyman-coventry/services/plumbing", "0.7", "monthly"),
-        ("/handyman-coventry/services/mounting", "0.7", "monthly"),
-        ("/handyman-coventry/faq", "0.6", "monthly"),
-        ("/handyman-coventry/features", "0.6", "monthly"),
-        ("/handyman-coventry/testimonials", "0.7", "weekly"),
-        ("/handyman-coventry/service-area", "0.6", "monthly"),
-        ("/handyman-coventry/booking", "0.8", "monthly"),
-        ("/handyman-coventry/quote", "0.8", "monthly"),
-        ("/handyman-coventry/about", "0.6", "monthly"),
-        ("/handyman-coventry/contact", "0.7", "monthly"),
-        ("/handyman-coventry/blog", "0.6", "weekly"),
-        ("/handyman-coventry/emergency", "0.7", "monthly"),
-        ("/handyman-coventry/privacy", "0.3", "yearly"),
-        ("/handyman-coventry/terms", "0.3", "yearly"),
-    ];
-
-    let blog_posts = [
-        "/blog/why-tradesmen-need-websites",
-        "/blog/local-seo-guide",
-        "/blog/building-trust-online",
-    ];
-
-    let mut url_entries = String::new();
-
-    // Add main routes
-    for (route, priority, changefreq) in main_routes {
-        url_entries.push_str(&format!(
-            r#"  <url>
-    <loc>{}{}</loc>
-    <changefreq>{}</changefreq>
-    <priority>{}</priority>
-  </url>
-"#,
-            base_url, route, changefreq, priority
-        ));
-    }
-
-    // Add handyman routes
-    for (route, priority, changefreq) in handyman_routes {
-        url_entries.push_str(&format!(
-            r#"  <url>
-    <loc>{}{}</loc>
-    <changefreq>{}</changefreq>
-    <priority>{}</priority>
-  </url>
-"#,
-            base_url, route, changefreq, priority
-        ));
-    }
-
-    // Add blog posts
-    for post in blog_posts {
-        url_entries.push_str(&format!(
-            r#"  <url>
-    <loc>{}{}</loc>
-    <changefreq>monthly</changefreq>
-    <priority>0.7</priority>
-  </url>
-"#,
-            base_url, post
-        ));
-    }
-
-    let sitemap = format!(
-        r#"<?xml version="1.0" encoding="UTF-8"?>
-<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
-{}</urlset>"#,
-        url_entries
-    );
-
-    axum::response::Response::builder()
-        .header(header::CONTENT_TYPE, "application/xml")
-        .body(axum::body::Body::from(sitemap))
-        .unwrap_or_else(|_| axum::response::Response::default())
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
 
 #[cfg(feature = "ssr")]
-async fn proxy_handler(req: axum::extract::Request) -> axum::response::Response {
+async fn proxy_handler(
+    axum::extract::ConnectInfo(peer): axum::extract::ConnectInfo<std::net::SocketAddr>,
+    req: axum::extract::Request,
+) -> axum::response::Response {
+    use axum::http::HeaderValue;
     use axum::response::IntoResponse;
 
     // The backend API URL - usually running on localhost:3001 in production
@@ -213,7 +103,22 @@ async fn proxy_handler(req: axum::extract::Request) -> axum::response::Response
     // Reconstruct the request to the backend
     let (parts, body) = req.into_parts();
 
-    let req_builder = client.request(parts.method, &uri).headers(parts.headers);
+    // The backend picks the tenant from the host the visitor asked for
+    let mut headers = parts.headers;
+    if !headers.contains_key("x-forwarded-host") {
+        if let Some(host) = headers.get(axum::http::header::HOST).cloned() {
+            headers.insert("x-forwarded-host", host);
+        }
+    }
+    // ...and records who is asking from the connection, never from what the
+    // visitor claims
+    headers.insert(
+        "x-forwarded-for",
+        HeaderValue::from_str(&peer.ip().to_canonical().to_string())
+            .expect("an IP address is a valid header value"),
+    );
+
+    let req_builder = client.request(parts.method, &uri).headers(headers);
 
     // Axum body to Reqwest body conversion is complex, simpler to stream bytes
     // For now, let's just forward as is if possible, or read bytes
@@ -338,3 +243,47 @@ async fn canonical_redirect_middleware(
 
     next.run(req).await
 }
+
+/// Middleware sending `/` to the handyman site on a client business's own
+/// domain, where the XFTradesman home page means nothing to visitors.
+#[cfg(feature = "ssr")]
+async fn client_site_root_middleware(
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::http::header;
+    use axum::response::IntoResponse;
+
+    if req.uri().path() != "/" {
+        return next.run(req).await;
+    }
+
+    let host = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    if let Some(site_path) = client_site_path(&host).await {
+        let query = req
+            .uri()
+            .query()
+            .map(|q| format!("?{}", q))
+            .unwrap_or_default();
+        return axum::response::Redirect::temporary(&format!("{}{}", site_path, query))
+            .into_response();
+    }
+
+    next.run(req).await
+}
+
+/// Where the site is for a `host` belonging to a client business rather
+/// than the platform. Treated as the platform when the backend can't be
+/// reached.
+#[cfg(feature = "ssr")]
+async fn client_site_path(host: &str) -> Option<String> {
+    frontend_leptos::api::site::site_for_host(host)
+        .await
+        .filter(|site| site.client_site)
+        .map(|site| site.site_path)
+}
@@ -1,13 +1,16 @@
 //! Blog index page.
 //!
-//! Lists all blog posts with SEO optimization.
+//! Lists the live blog posts with SEO optimization. Posts are fetched from
+//! the backend during SSR, so a scheduled post appears once it goes live.
 
-use super::data::{get_all_posts, BlogPost};
+use super::data::{category_color, category_icon};
+use super::BLOG_ORIGIN;
+use crate::api::blog::fetch_posts;
 use crate::components::seo::SeoHead;
 use crate::components::ui::{Button, ButtonVariant};
 use leptos::prelude::*;
 use leptos_meta::Script;
-use shared::PageMetadata;
+use shared::{BlogFeed, BlogPost, PageMetadata, BLOG_PATH};
 
 fn blog_list_json_ld(posts: &[BlogPost]) -> String {
     let post_items: Vec<String> = posts
@@ -27,8 +30,8 @@ fn blog_list_json_ld(posts: &[BlogPost]) -> String {
             }}"#,
                 p.title,
                 p.description,
-                p.canonical_url(),
-                p.published_date,
+                p.canonical_url(BLOG_ORIGIN),
+                p.published_at,
                 p.author
             )
         })
@@ -54,17 +57,15 @@ fn blog_list_json_ld(posts: &[BlogPost]) -> String {
 
 #[component]
 pub fn BlogIndex() -> impl IntoView {
-    let posts = get_all_posts();
-    let json_ld = blog_list_json_ld(&posts);
+    let posts = Resource::new(|| (), |_| fetch_posts(None));
 
     view! {
         <SeoHead metadata=PageMetadata {
             title: "Marketing Tips for Tradesmen | XF Tradesmen Blog".to_string(),
             description: "Expert marketing guides, SEO strategies, and website tips for UK tradesmen. Learn how to grow your business and get more leads online.".to_string(),
-            canonical_url: Some("https://xftradesman.com/blog".to_string()),
+            canonical_url: Some(format!("{}{}", BLOG_ORIGIN, BLOG_PATH)),
             og_image: None,
-        }/>
-        <Script type_="application/ld+json">{json_ld}</Script>
+        } feeds=vec![BlogFeed::ALL]/>
 
         <div class="space-y-0 overflow-x-hidden">
             // Hero
@@ -91,58 +92,17 @@ pub fn BlogIndex() -> impl IntoView {
                          <h2 class="text-2xl font-bold text-white tracking-widest uppercase font-mono">"Latest Transmissions"</h2>
                     </div>
 
-                    <div class="grid md:grid-cols-2 lg:grid-cols-3 gap-8">
-                        {posts.into_iter().map(|post| {
-                            let slug = post.slug.to_string();
-                            let title = post.title.to_string();
-                            let excerpt = post.description.to_string();
-                            let category_str = post.category.as_str().to_string();
-                            let category_bg = post.category.color_class().to_string();
-                            let icon_color = post.category.icon_class().to_string();
-                            let date = post.published_date.to_string();
-                            let read_time = post.read_time.to_string();
-
-                            view! {
-                                <article class="card-deep overflow-hidden group p-0 border-void-highlight hover:border-brand/50 transition-all duration-300">
-                                    <div class="h-56 bg-void relative overflow-hidden group-hover:opacity-90 transition">
-                                         <div class="absolute inset-0 bg-cyber-grid opacity-30"></div>
-                                         <div class="absolute inset-0 flex items-center justify-center">
-                                            <div class={format!("w-16 h-16 rounded-full flex items-center justify-center group-hover:scale-110 transition-transform duration-500 {}", icon_color)}>
-                                                <svg class="w-8 h-8" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M19 20H5a2 2 0 01-2-2V6a2 2 0 012-2h10a2 2 0 012 2v1m2 13a2 2 0 01-2-2V7m2 13a2 2 0 002-2V9a2 2 0 00-2-2h-2m-4-3H9M7 16h6M7 8h6v4H7V8z"/>
-                                                </svg>
-                                            </div>
-                                         </div>
-                                    </div>
-                                    <div class="p-8 relative">
-                                        <div class="flex justify-between items-center mb-4">
-                                            <span class={format!("inline-block px-2 py-1 rounded text-[10px] font-mono font-bold uppercase tracking-wider {}", category_bg)}>
-                                                {category_str}
-                                            </span>
-                                            <div class="flex items-center gap-2 text-gray-500 text-xs font-mono">
-                                                <span>{date.clone()}</span>
-                                                <span>"·"</span>
-                                                <span>{read_time.clone()}</span>
-                                            </div>
-                                        </div>
-
-                                        <h3 class="text-xl font-bold text-white mb-4 leading-tight group-hover:text-brand-light transition font-heading">
-                                            <a href={format!("/blog/{}", slug.clone())}>{title}</a>
-                                        </h3>
-                                        <p class="text-gray-400 text-sm mb-6 line-clamp-3 leading-relaxed">
-                                            {excerpt}
-                                        </p>
-                                        <a href={format!("/blog/{}", slug)} class="text-white hover:text-brand-light text-xs font-bold uppercase tracking-widest flex items-center gap-2 group/link">
-                                            "Read Analysis"
-                                            <svg class="w-4 h-4 group-hover/link:translate-x-1 transition-transform" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M17 8l4 4m0 0l-4 4m4-4H3"/>
-                                            </svg>
-                                        </a>
-                                    </div>
-                                </article>
+                    <Suspense fallback=|| view! {
+                        <p class="text-gray-500 font-mono text-sm">"Loading..."</p>
+                    }>
+                        {move || posts.get().map(|result| match result {
+                            Ok(posts) if !posts.is_empty() => posts_grid(posts).into_any(),
+                            _ => view! {
+                                <p class="text-gray-400">"No articles yet. Check back soon."</p>
                             }
-                        }).collect::<Vec<_>>()}
-                    </div>
+                            .into_any(),
+                        })}
+                    </Suspense>
                 </div>
             </section>
 
@@ -161,3 +121,64 @@ pub fn BlogIndex() -> impl IntoView {
         </div>
     }
 }
+
+/// The post cards, with the blog's structured data.
+fn posts_grid(posts: Vec<BlogPost>) -> impl IntoView {
+    let json_ld = blog_list_json_ld(&posts);
+
+    view! {
+        <Script type_="application/ld+json">{json_ld}</Script>
+        <div class="grid md:grid-cols-2 lg:grid-cols-3 gap-8">
+            {posts.into_iter().map(|post| {
+                let slug = post.slug.clone();
+                let title = post.title.clone();
+                let excerpt = post.description.clone();
+                let category_str = post.category_name().to_string();
+                let category_bg = category_color(&post.category);
+                let icon_color = category_icon(&post.category);
+                let date = post.published_date();
+                let read_time = post.read_time();
+
+                view! {
+                    <article class="card-deep overflow-hidden group p-0 border-void-highlight hover:border-brand/50 transition-all duration-300">
+                        <div class="h-56 bg-void relative overflow-hidden group-hover:opacity-90 transition">
+                             <div class="absolute inset-0 bg-cyber-grid opacity-30"></div>
+                             <div class="absolute inset-0 flex items-center justify-center">
+                                <div class={format!("w-16 h-16 rounded-full flex items-center justify-center group-hover:scale-110 transition-transform duration-500 {}", icon_color)}>
+                                    <svg class="w-8 h-8" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M19 20H5a2 2 0 01-2-2V6a2 2 0 012-2h10a2 2 0 012 2v1m2 13a2 2 0 01-2-2V7m2 13a2 2 0 002-2V9a2 2 0 00-2-2h-2m-4-3H9M7 16h6M7 8h6v4H7V8z"/>
+                                    </svg>
+                                </div>
+                             </div>
+                        </div>
+                        <div class="p-8 relative">
+                            <div class="flex justify-between items-center mb-4">
+                                <span class={format!("inline-block px-2 py-1 rounded text-[10px] font-mono font-bold uppercase tracking-wider {}", category_bg)}>
+                                    {category_str}
+                                </span>
+                                <div class="flex items-center gap-2 text-gray-500 text-xs font-mono">
+                                    <span>{date.clone()}</span>
+                                    <span>"·"</span>
+                                    <span>{read_time.clone()}</span>
+                                </div>
+                            </div>
+
+                            <h3 class="text-xl font-bold text-white mb-4 leading-tight group-hover:text-brand-light transition font-heading">
+                                <a href={format!("/blog/{}", slug.clone())}>{title}</a>
+                            </h3>
+                            <p class="text-gray-400 text-sm mb-6 line-clamp-3 leading-relaxed">
+                                {excerpt}
+                            </p>
+                            <a href={format!("/blog/{}", slug)} class="text-white hover:text-brand-light text-xs font-bold uppercase tracking-widest flex items-center gap-2 group/link">
+                                "Read Analysis"
+                                <svg class="w-4 h-4 group-hover/link:translate-x-1 transition-transform" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M17 8l4 4m0 0l-4 4m4-4H3"/>
+                                </svg>
+                            </a>
+                        </div>
+                    </article>
+                }
+            }).collect::<Vec<_>>()}
+        </div>
+    }
+}
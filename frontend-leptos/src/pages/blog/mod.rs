@@ -3,3 +3,6 @@
 pub mod article;
 pub mod data;
 pub mod index;
+
+/// The platform site the blog's pages are canonical on.
+const BLOG_ORIGIN: &str = "https://xftradesman.com";
@@ -1,22 +1,51 @@
 //! Dedicated layout for the Handyman Example Site.
+//!
+//! The same layout renders every client's site: the business name, contact
+//! details, navigation and colours come from the site configuration for
+//! the request's host (see [`crate::pages::examples::handyman_app::site`]).
 
 // use crate::pages::examples::handyman_app::components::CtaButton;
 use crate::components::seo::HandymanLocalBusinessSchema;
+use crate::pages::examples::handyman_app::site::{
+    clock, phone, provide_site, tel_href, theme, use_site,
+};
 use leptos::prelude::*;
 use leptos_router::components::{Outlet, A};
+use shared::SiteConfig;
 
 #[component]
 pub fn HandymanLayout() -> impl IntoView {
+    let config = provide_site();
+    let site = use_site();
+    let mailto = move || site.with(|s| format!("mailto:{}", s.email.clone().unwrap_or_default()));
+    let email = move || site.with(|s| s.email.clone().unwrap_or_default());
+    let located_in = move || {
+        site.with(|s| {
+            s.areas
+                .first()
+                .map(|area| format!("{}, UK", area.name))
+                .unwrap_or_else(|| "United Kingdom".to_string())
+        })
+    };
+
     view! {
+        <Suspense fallback=|| ()>
+        {move || config.get().map(|_| ())}
+
         // SEO structured data for all handyman pages
         <HandymanLocalBusinessSchema />
 
-        <div class="handyman-theme font-sans antialiased text-gray-900 bg-slate-50 min-h-screen flex flex-col selection:bg-yellow-400 selection:text-blue-900">
-            // Demo Site Banner
-            <div class="bg-gradient-to-r from-purple-600 to-indigo-600 text-white py-2 px-4 text-center text-sm">
-                <span class="mr-2">"This is an example site showcasing XFTradesmen capabilities."</span>
-                <A href="/" {..} class="underline font-bold hover:text-purple-200 transition">"Back to XFTradesmen"</A>
-            </div>
+        <div
+            class=move || format!("handyman-theme{} font-sans antialiased text-gray-900 bg-slate-50 min-h-screen flex flex-col selection:bg-yellow-400 selection:text-blue-900", site.with(theme).0)
+            style=move || site.with(theme).1
+        >
+            // Demo Site Banner (the platform's own example only)
+            <Show when=move || !site.with(|s| s.client_site)>
+                <div class="bg-gradient-to-r from-purple-600 to-indigo-600 text-white py-2 px-4 text-center text-sm">
+                    <span class="mr-2">"This is an example site showcasing XFTradesmen capabilities."</span>
+                    <A href="/" {..} class="underline font-bold hover:text-purple-200 transition">"Back to XFTradesmen"</A>
+                </div>
+            </Show>
 
 
             // Header Container
@@ -26,11 +55,7 @@ pub fn HandymanLayout() -> impl IntoView {
                     <div class="w-full flex flex-col md:flex-row justify-between items-center gap-0">
                         // Logo
                         <A href="/handyman-coventry" {..} class="flex items-center gap-3 group mr-auto">
-                             <div class="w-12 h-12 bg-gradient-to-br from-yellow-400 to-yellow-600 rounded-lg flex items-center justify-center text-blue-900 font-black text-2xl shadow-lg group-hover:scale-105 transition">"H"</div>
-                             <div class="flex flex-col">
-                                <span class="text-2xl font-black tracking-tight leading-none text-slate-900">"HANDLE"</span>
-                                <span class="text-2xl font-black tracking-tight leading-none text-blue-900">"MAN"</span>
-                             </div>
+                            {move || site.with(|s| site_logo(s, false))}
                         </A>
 
                         // Contact Widgets (Hidden on small mobile)
@@ -42,7 +67,7 @@ pub fn HandymanLayout() -> impl IntoView {
                                 </div>
                                 <div>
                                     <div class="text-xs text-gray-500 font-medium uppercase tracking-wide">"Call Today"</div>
-                                    <A href="tel:02476123456" {..} class="text-lg font-bold text-slate-900 hover:text-blue-600 transition">"024 7612 3456"</A>
+                                    <a href=tel_href(site) class="text-lg font-bold text-slate-900 hover:text-blue-600 transition">{phone(site)}</a>
                                 </div>
                             </div>
 
@@ -53,7 +78,7 @@ pub fn HandymanLayout() -> impl IntoView {
                                 </div>
                                 <div>
                                     <div class="text-xs text-gray-500 font-medium uppercase tracking-wide">"Send a Message"</div>
-                                    <A href="mailto:hello@coventryhandyman.co.uk" {..} class="text-lg font-bold text-slate-900 hover:text-blue-600 transition">"Click To Email"</A>
+                                    <a href=mailto class="text-lg font-bold text-slate-900 hover:text-blue-600 transition">"Click To Email"</a>
                                 </div>
                             </div>
 
@@ -64,7 +89,7 @@ pub fn HandymanLayout() -> impl IntoView {
                                 </div>
                                 <div>
                                     <div class="text-xs text-gray-500 font-medium uppercase tracking-wide">"Located In"</div>
-                                    <div class="text-lg font-bold text-slate-900">"Coventry, England"</div>
+                                    <div class="text-lg font-bold text-slate-900">{located_in}</div>
                                 </div>
                             </div>
                         </div>
@@ -99,9 +124,9 @@ pub fn HandymanLayout() -> impl IntoView {
                                     <svg class="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M19 9l-7 7-7-7"/></svg>
                                 </button>
                                 <div class="absolute left-0 top-full w-64 bg-white text-slate-800 shadow-xl rounded-b-lg overflow-hidden invisible group-hover:visible opacity-0 group-hover:opacity-100 transition-all duration-200 z-50 border-t-2 border-yellow-400">
-                                    <A href="/handyman-coventry/services/furniture-assembly" {..} class="block px-6 py-3 text-sm hover:bg-gray-50 hover:text-blue-900 border-b border-gray-100">"Furniture Assembly"</A>
-                                    <A href="/handyman-coventry/services/plumbing" {..} class="block px-6 py-3 text-sm hover:bg-gray-50 hover:text-blue-900 border-b border-gray-100">"Plumbing Repairs"</A>
-                                    <A href="/handyman-coventry/services/mounting" {..} class="block px-6 py-3 text-sm hover:bg-gray-50 hover:text-blue-900 border-b border-gray-100">"Mounting & Installation"</A>
+                                    {move || site.with(service_links).into_iter().map(|(href, name)| view! {
+                                        <A href={href} {..} class="block px-6 py-3 text-sm hover:bg-gray-50 hover:text-blue-900 border-b border-gray-100">{name}</A>
+                                    }).collect_view()}
                                     <A href="/handyman-coventry/services" {..} class="block px-6 py-3 text-sm hover:bg-gray-50 hover:text-blue-900 bg-gray-50 font-bold border-t border-gray-100">"View All Services"</A>
                                 </div>
                              </div>
@@ -144,14 +169,13 @@ pub fn HandymanLayout() -> impl IntoView {
                         // Column 1: Brand (4 cols)
                         <div class="md:col-span-4 space-y-6">
                              <div class="flex items-center gap-3">
-                                <div class="w-10 h-10 bg-gradient-to-br from-yellow-400 to-yellow-600 rounded flex items-center justify-center text-blue-900 font-black text-xl">"H"</div>
-                                <div class="flex flex-col">
-                                    <span class="text-xl font-black tracking-tight leading-none text-white">"HANDLE"</span>
-                                    <span class="text-xl font-black tracking-tight leading-none text-blue-500">"MAN"</span>
-                                </div>
+                                {move || site.with(|s| site_logo(s, true))}
                              </div>
                             <p class="text-base leading-relaxed text-slate-400">
-                                "XFTradesmen provides patented handles and lock systems that transform sliding doors into safer, easier, and more enjoyable features of your home."
+                                {move || site.with(|s| match (&s.tagline, s.client_site) {
+                                    (Some(tagline), true) => tagline.clone(),
+                                    _ => "XFTradesmen provides patented handles and lock systems that transform sliding doors into safer, easier, and more enjoyable features of your home.".to_string(),
+                                })}
                             </p>
                              <div class="flex gap-4 pt-4">
                                 <A href="#" {..} class="w-10 h-10 bg-white/5 rounded-full flex items-center justify-center hover:bg-white/10 transition" aria-label="Facebook">
@@ -170,12 +194,18 @@ pub fn HandymanLayout() -> impl IntoView {
                         <div class="md:col-span-4 pl-0 md:pl-12 space-y-6">
                             <div>
                                 <h4 class="text-xs font-bold text-slate-500 uppercase tracking-widest mb-1">"Phone Number"</h4>
-                                <A href="tel:02476123456" {..} class="text-2xl font-bold text-white hover:text-blue-500 transition">"(024) 7612-3456"</A>
+                                <a href=tel_href(site) class="text-2xl font-bold text-white hover:text-blue-500 transition">{phone(site)}</a>
                             </div>
                              <div>
                                 <h4 class="text-xs font-bold text-slate-500 uppercase tracking-widest mb-1">"Office Hours"</h4>
-                                <p class="text-lg text-white font-bold">"Mon - Sat"</p>
-                                <p class="text-lg text-white">"8:00 am - 6:00 pm"</p>
+                                {move || site.with(|s| if s.opening_hours.is_empty() {
+                                    view! { <p class="text-lg text-white font-bold">"Call to arrange"</p> }.into_any()
+                                } else {
+                                    s.opening_hours.iter().map(|hours| view! {
+                                        <p class="text-lg text-white font-bold">{hours.days.clone()}</p>
+                                        <p class="text-lg text-white">{format!("{} - {}", clock(&hours.opens), clock(&hours.closes))}</p>
+                                    }).collect_view().into_any()
+                                })}
                             </div>
                         </div>
 
@@ -183,12 +213,11 @@ pub fn HandymanLayout() -> impl IntoView {
                         <div class="md:col-span-4 space-y-6">
                              <div>
                                 <h4 class="text-xs font-bold text-slate-500 uppercase tracking-widest mb-1">"Email Address"</h4>
-                                <A href="mailto:info@handyman-coventry.co.uk" {..} class="text-2xl font-bold text-white hover:text-blue-500 transition">"info@handyman-coventry.co.uk"</A>
+                                <a href=mailto class="text-2xl font-bold text-white hover:text-blue-500 transition">{email}</a>
                             </div>
                              <div>
                                 <h4 class="text-xs font-bold text-slate-500 uppercase tracking-widest mb-1">"Office Address"</h4>
-                                <p class="text-lg text-white font-bold">"Coventry, CV1 1AA"</p>
-                                <p class="text-lg text-white">"United Kingdom"</p>
+                                <p class="text-lg text-white font-bold">{located_in}</p>
                             </div>
 
 
@@ -208,7 +237,67 @@ pub fn HandymanLayout() -> impl IntoView {
                 </div>
             </footer>
         </div>
+        </Suspense>
+    }
+}
+
+/// The client's logo, or their initial and business name.
+fn site_logo(site: &SiteConfig, dark: bool) -> AnyView {
+    if let Some(logo_url) = &site.logo_url {
+        return view! {
+            <img src=logo_url.clone() alt=site.business_name.clone() class="h-12 w-auto object-contain"/>
+        }
+        .into_any();
+    }
+
+    let initial = site
+        .business_name
+        .chars()
+        .next()
+        .unwrap_or('H')
+        .to_uppercase()
+        .to_string();
+    let name_class = if dark {
+        "text-xl font-black tracking-tight leading-none text-white uppercase"
+    } else {
+        "text-2xl font-black tracking-tight leading-none text-slate-900 uppercase"
+    };
+    view! {
+        <div class="w-12 h-12 bg-gradient-to-br from-yellow-400 to-yellow-600 rounded-lg flex items-center justify-center text-blue-900 font-black text-2xl shadow-lg group-hover:scale-105 transition">{initial}</div>
+        <span class=name_class>{site.business_name.clone()}</span>
     }
+    .into_any()
+}
+
+/// Service menu entries: the site's published services, or the example's
+/// own service pages before any are set up.
+fn service_links(site: &SiteConfig) -> Vec<(String, String)> {
+    if site.services.is_empty() {
+        return [
+            ("furniture-assembly", "Furniture Assembly"),
+            ("plumbing", "Plumbing Repairs"),
+            ("mounting", "Mounting & Installation"),
+        ]
+        .into_iter()
+        .map(|(slug, name)| {
+            (
+                format!("/handyman-coventry/services/{}", slug),
+                name.to_string(),
+            )
+        })
+        .collect();
+    }
+
+    site.services
+        .iter()
+        .take(6)
+        .map(|s| {
+            (
+                format!("/handyman-coventry/services/{}", s.slug),
+                s.name.clone(),
+            )
+        })
+        .collect()
 }
 
 /// Mobile menu button with hamburger icon and slide-out menu
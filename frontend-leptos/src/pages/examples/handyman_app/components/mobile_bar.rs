@@ -2,16 +2,19 @@
 //!
 //! Shows at bottom of screen on mobile devices only.
 
+use crate::pages::examples::handyman_app::site::{tel_href, use_site};
 use leptos::prelude::*;
 
 /// Sticky bottom bar for mobile devices with call and book buttons
 #[component]
 pub fn MobileStickyBar() -> impl IntoView {
+    let site = use_site();
+
     view! {
         // This bar only shows on mobile (< md breakpoint)
         <div class="fixed bottom-0 left-0 right-0 z-50 md:hidden bg-blue-900 border-t border-blue-800 p-3 flex gap-3">
             <a
-                href="tel:+447833263486"
+                href=tel_href(site)
                 class="flex-1 flex items-center justify-center gap-2 py-3 bg-white text-blue-900 font-bold rounded-lg"
             >
                 <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
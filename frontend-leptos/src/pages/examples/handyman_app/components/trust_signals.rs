@@ -2,6 +2,7 @@
 //!
 //! Displays badges and trust indicators to build customer confidence.
 
+use crate::pages::examples::handyman_app::site::{area_list, home_area, use_site};
 use leptos::prelude::*;
 
 /// Trust bar component displaying key trust signals.
@@ -9,6 +10,8 @@ use leptos::prelude::*;
 /// Shows rating, insurance status, DBS check, and locality.
 #[component]
 pub fn TrustBar() -> impl IntoView {
+    let site = use_site();
+
     view! {
         <div class="bg-gradient-to-r from-blue-900 to-blue-800 py-3 px-4 flex flex-wrap items-center justify-center gap-4 md:gap-8 text-white text-sm font-medium shadow-md">
             <span class="flex items-center gap-2">
@@ -34,7 +37,7 @@ pub fn TrustBar() -> impl IntoView {
                     <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M17.657 16.657L13.414 20.9a1.998 1.998 0 01-2.827 0l-4.244-4.243a8 8 0 1111.314 0z"/>
                     <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 11a3 3 0 11-6 0 3 3 0 016 0z"/>
                 </svg>
-                "Local to Coventry"
+                {move || format!("Local to {}", site.with(home_area))}
             </span>
         </div>
     }
@@ -43,6 +46,8 @@ pub fn TrustBar() -> impl IntoView {
 /// Full trust signals section with certifications.
 #[component]
 pub fn TrustSignals() -> impl IntoView {
+    let site = use_site();
+
     view! {
         <section class="py-16 px-6 bg-gradient-to-b from-slate-100 to-white">
             <div class="max-w-6xl mx-auto">
@@ -90,8 +95,8 @@ pub fn TrustSignals() -> impl IntoView {
                                 <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 11a3 3 0 11-6 0 3 3 0 016 0z"/>
                             </svg>
                         </div>
-                        <h3 class="font-bold text-lg text-slate-900 mb-2">"Local to Coventry"</h3>
-                        <p class="text-slate-600 text-sm">"Based in Coventry, serving the West Midlands"</p>
+                        <h3 class="font-bold text-lg text-slate-900 mb-2">{move || format!("Local to {}", site.with(home_area))}</h3>
+                        <p class="text-slate-600 text-sm">{move || format!("Serving {} and the surrounding areas", site.with(area_list))}</p>
                     </div>
                 </div>
 
@@ -1,2 +1,3 @@
 pub mod components;
 pub mod pages;
+pub mod site;
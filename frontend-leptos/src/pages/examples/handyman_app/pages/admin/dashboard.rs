@@ -2,6 +2,7 @@
 //!
 //! Shows overview of jobs, revenue, and quick actions.
 
+use crate::pages::examples::handyman_app::site::{page_title, use_site};
 use leptos::prelude::*;
 use leptos_meta::Title;
 
@@ -68,11 +69,12 @@ fn get_mock_jobs() -> Vec<Job> {
 
 #[component]
 pub fn AdminDashboard() -> impl IntoView {
+    let site = use_site();
     let stats = get_mock_stats();
     let jobs = get_mock_jobs();
 
     view! {
-        <Title text="Dashboard | Coventry Handyman Admin"/>
+        <Title text=page_title(site, "Admin Dashboard")/>
 
         <div class="min-h-screen bg-slate-100">
             // Top bar
@@ -81,7 +83,7 @@ pub fn AdminDashboard() -> impl IntoView {
                     <div class="w-10 h-10 bg-yellow-500 rounded-lg flex items-center justify-center text-blue-900 font-black text-xl">"H"</div>
                     <div>
                         <div class="font-bold">"Admin Dashboard"</div>
-                        <div class="text-sm text-blue-200">"Coventry Handyman"</div>
+                        <div class="text-sm text-blue-200">{move || site.with(|s| s.business_name.clone())}</div>
                     </div>
                 </div>
                 <div class="flex items-center gap-4">
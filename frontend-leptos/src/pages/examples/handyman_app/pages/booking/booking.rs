@@ -1,12 +1,41 @@
 //! Handyman Booking Integration
+//!
+//! The job's postcode must be one we cover before the booking can be
+//! reviewed.
 
+use crate::api::pricing::check_coverage;
 use crate::pages::examples::handyman_app::components::{GlassCard, SectionTitle};
 use leptos::prelude::*;
+use leptos::task::spawn_local;
 
 #[component]
 pub fn HandymanBooking() -> impl IntoView {
     let (step, set_step) = signal(1);
     // 1 = Service, 2 = Details, 3 = Confirmation
+    let (postcode, set_postcode) = signal(String::new());
+    let (coverage_error, set_coverage_error) = signal(Option::<String>::None);
+    let (checking, set_checking) = signal(false);
+
+    // Only move on to review once the postcode is confirmed as covered
+    let review = move |_| {
+        let postcode = postcode.get_untracked();
+        if postcode.trim().is_empty() {
+            set_coverage_error.set(Some("Please enter the job's postcode".to_string()));
+            return;
+        }
+        set_checking.set(true);
+        spawn_local(async move {
+            match check_coverage(&postcode).await {
+                Ok(found) if found.covered => {
+                    set_coverage_error.set(None);
+                    set_step.set(3);
+                }
+                Ok(found) => set_coverage_error.set(Some(found.message)),
+                Err(err) => set_coverage_error.set(Some(err)),
+            }
+            set_checking.set(false);
+        });
+    };
 
     view! {
         <div class="bg-slate-50 min-h-screen py-20 px-6">
@@ -51,6 +80,20 @@ pub fn HandymanBooking() -> impl IntoView {
                                     <div class="space-y-2">
                                         <label class="block text-sm font-bold text-gray-700">"Phone Number"</label>
                                         <input type="tel" class="w-full p-3 border border-gray-300 rounded-lg focus:ring-2 focus:ring-blue-900 focus:border-transparent outline-none transition" placeholder="07123 456789"/>
+                                    </div>
+                                    <div class="space-y-2 md:col-span-2">
+                                        <label class="block text-sm font-bold text-gray-700">"Job Postcode"</label>
+                                        <input
+                                            type="text"
+                                            class="w-full p-3 border border-gray-300 rounded-lg focus:ring-2 focus:ring-blue-900 focus:border-transparent outline-none transition uppercase"
+                                            placeholder="CV1 1AA"
+                                            autocomplete="postal-code"
+                                            prop:value=move || postcode.get()
+                                            on:input=move |ev| set_postcode.set(event_target_value(&ev))
+                                        />
+                                        {move || coverage_error.get().map(|err| view! {
+                                            <p class="text-sm text-red-700">{err}</p>
+                                        })}
                                     </div>
                                      <div class="space-y-2 md:col-span-2">
                                         <label class="block text-sm font-bold text-gray-700">"Job Description"</label>
@@ -59,7 +102,13 @@ pub fn HandymanBooking() -> impl IntoView {
                                 </div>
                                 <div class="flex justify-between pt-6">
                                     <button on:click=move |_| set_step.set(1) class="text-gray-500 hover:text-blue-900 font-bold">"Back"</button>
-                                    <button on:click=move |_| set_step.set(3) class="bg-blue-900 text-white px-8 py-3 rounded-lg font-bold hover:bg-blue-800 transition shadow-lg">"Review Booking"</button>
+                                    <button
+                                        on:click=review
+                                        disabled=move || checking.get()
+                                        class="bg-blue-900 text-white px-8 py-3 rounded-lg font-bold hover:bg-blue-800 transition shadow-lg disabled:opacity-50"
+                                    >
+                                        {move || if checking.get() { "Checking postcode..." } else { "Review Booking" }}
+                                    </button>
                                 </div>
                             </div>
                         }.into_any(),
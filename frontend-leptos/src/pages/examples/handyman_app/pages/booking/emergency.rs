@@ -1,12 +1,17 @@
 //! Emergency services page with prominent CTAs.
 
+use crate::pages::examples::handyman_app::site::{
+    area_list, page_title, phone, tel_href, use_site,
+};
 use leptos::prelude::*;
 use leptos_meta::Title;
 
 #[component]
 pub fn HandymanEmergency() -> impl IntoView {
+    let site = use_site();
+
     view! {
-        <Title text="Emergency Handyman Services | Coventry"/>
+        <Title text=page_title(site, "Emergency Handyman Services")/>
 
         <div class="min-h-screen bg-slate-50">
             // Hero with big phone number
@@ -18,18 +23,18 @@ pub fn HandymanEmergency() -> impl IntoView {
                     </div>
                     <h1 class="text-4xl md:text-5xl font-black mb-4">"Emergency Handyman"</h1>
                     <p class="text-xl text-red-100 mb-8">
-                        "Same-day service for urgent repairs in Coventry and surrounding areas."
+                        {move || format!("Same-day service for urgent repairs in {} and surrounding areas.", site.with(area_list))}
                     </p>
 
                     // Big phone button
                     <a
-                        href="tel:+447833263486"
+                        href=tel_href(site)
                         class="inline-flex items-center gap-3 px-8 py-5 bg-white text-red-600 text-2xl font-black rounded-xl hover:bg-red-50 transition shadow-xl"
                     >
                         <svg class="w-8 h-8" fill="none" stroke="currentColor" viewBox="0 0 24 24">
                             <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M3 5a2 2 0 012-2h3.28a1 1 0 01.948.684l1.498 4.493a1 1 0 01-.502 1.21l-2.257 1.13a11.042 11.042 0 005.516 5.516l1.13-2.257a1 1 0 011.21-.502l4.493 1.498a1 1 0 01.684.949V19a2 2 0 01-2 2h-1C9.716 21 3 14.284 3 6V5z"/>
                         </svg>
-                        "07833 263486"
+                        {phone(site)}
                     </a>
                 </div>
             </section>
@@ -1,88 +1,181 @@
 //! Instant Quote Calculator page.
 //!
-//! Allows customers to get an instant estimate for their job.
+//! Allows customers to get an instant estimate for their job. Services,
+//! options and prices come from the backend pricing catalog, and every
+//! estimate is calculated by the backend, so the page never disagrees with
+//! the quote a customer is later sent. The job's postcode is checked
+//! against our coverage before any estimate is shown, and sets its travel
+//! zone.
 
+use crate::api::pricing::{check_coverage, fetch_catalog, instant_quote};
+use crate::pages::examples::handyman_app::site::{page_title, tel_href, use_site};
 use leptos::prelude::*;
+use leptos::task::spawn_local;
 use leptos_meta::Title;
+use shared::{
+    Coverage, InstantQuoteRequest, InstantQuoteResponse, PricingCatalog, PricingRule,
+    RuleAdjustment, RuleCondition,
+};
 
-/// Service category for quote calculation
-#[derive(Clone, Debug, PartialEq)]
-struct ServiceOption {
-    value: &'static str,
-    label: &'static str,
-    base_low: i32,
-    base_high: i32,
+/// Formats cents as pounds, dropping the pence when there are none.
+fn pounds(cents: i32) -> String {
+    if cents % 100 == 0 {
+        format!("£{}", cents / 100)
+    } else {
+        format!("£{:.2}", cents as f64 / 100.0)
+    }
+}
+
+/// Short price hint for an option, e.g. "+£15" or "+25%", from the first
+/// active rule for that option. Empty when no rule applies.
+fn option_hint(catalog: &PricingCatalog, service: &str, condition: &RuleCondition) -> String {
+    catalog
+        .rules
+        .iter()
+        .find(|rule| {
+            rule.active
+                && rule.condition == *condition
+                && rule.service_type.as_deref().is_none_or(|s| s == service)
+        })
+        .map(adjustment_hint)
+        .unwrap_or_default()
+}
+
+/// A rule's price change, e.g. "+£15", "-£10" or "+25%".
+fn adjustment_hint(rule: &PricingRule) -> String {
+    match rule.adjustment {
+        RuleAdjustment::Fixed { amount_cents } if amount_cents < 0 => {
+            format!("-{}", pounds(-amount_cents))
+        }
+        RuleAdjustment::Fixed { amount_cents } => format!("+{}", pounds(amount_cents)),
+        RuleAdjustment::Percent { basis_points } => {
+            format!("{:+}%", basis_points as f64 / 100.0)
+        }
+    }
 }
 
-const SERVICES: &[ServiceOption] = &[
-    ServiceOption {
-        value: "plumbing",
-        label: "Plumbing",
-        base_low: 45,
-        base_high: 120,
-    },
-    ServiceOption {
-        value: "electrical",
-        label: "Electrical",
-        base_low: 50,
-        base_high: 150,
-    },
-    ServiceOption {
-        value: "carpentry",
-        label: "Carpentry",
-        base_low: 60,
-        base_high: 180,
-    },
-    ServiceOption {
-        value: "assembly",
-        label: "Furniture Assembly",
-        base_low: 35,
-        base_high: 85,
-    },
-    ServiceOption {
-        value: "painting",
-        label: "Painting & Decorating",
-        base_low: 80,
-        base_high: 250,
-    },
-    ServiceOption {
-        value: "general",
-        label: "General Repairs",
-        base_low: 40,
-        base_high: 100,
-    },
+const URGENCY_OPTIONS: &[(&str, &str, &str)] = &[
+    ("same_day", "Same Day", "border-yellow-500 bg-yellow-50"),
+    (
+        "within_3_days",
+        "Within 3 Days",
+        "border-blue-500 bg-blue-50",
+    ),
+    ("flexible", "Flexible", "border-green-500 bg-green-50"),
+];
+
+const JOB_SIZES: &[(&str, &str)] = &[
+    ("small", "Small (under an hour)"),
+    ("medium", "Medium (half a day)"),
+    ("large", "Large (a day or more)"),
 ];
 
 #[component]
 pub fn HandymanQuote() -> impl IntoView {
+    let site = use_site();
+
+    // Pricing catalog from the backend
+    let (catalog, set_catalog) = signal(PricingCatalog::default());
+    let (catalog_error, set_catalog_error) = signal(Option::<String>::None);
+
     // Form state
-    let (service, set_service) = signal("plumbing".to_string());
+    let (service, set_service) = signal(String::new());
     let (urgency, set_urgency) = signal("within_3_days".to_string());
+    let (job_size, set_job_size) = signal("medium".to_string());
+    let (out_of_hours, set_out_of_hours) = signal(false);
+    let (weekend, set_weekend) = signal(false);
+    let (postcode, set_postcode) = signal(String::new());
+    let (coverage, set_coverage) = signal(Option::<Result<Coverage, String>>::None);
     let (description, set_description) = signal(String::new());
 
-    // Calculate estimates based on selections
-    let estimate = Memo::new(move |_| {
-        let svc = service.get();
-        let urg = urgency.get();
+    // Latest estimate from the backend
+    let (estimate, set_estimate) = signal(Option::<InstantQuoteResponse>::None);
+    let (estimate_error, set_estimate_error) = signal(Option::<String>::None);
+    let latest_request = StoredValue::new(0u32);
+    let latest_postcode = StoredValue::new(0u32);
 
-        let (base_low, base_high) = SERVICES
-            .iter()
-            .find(|s| s.value == svc)
-            .map(|s| (s.base_low, s.base_high))
-            .unwrap_or((40, 100));
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match fetch_catalog().await {
+                Ok(loaded) => {
+                    if let Some(first) = loaded.services.first() {
+                        set_service.set(first.service_type.clone());
+                    }
+                    set_catalog.set(loaded);
+                }
+                Err(err) => set_catalog_error.set(Some(err)),
+            }
+        });
+    });
 
-        let urgency_modifier = match urg.as_str() {
-            "same_day" => 15,
-            "within_3_days" => 0,
-            "flexible" => -5,
-            _ => 0,
-        };
+    // Check coverage whenever the postcode changes, keeping only the newest
+    // answer.
+    Effect::new(move |_| {
+        let postcode = postcode.get();
+        latest_postcode.update_value(|n| *n += 1);
+        if postcode.trim().is_empty() {
+            set_coverage.set(None);
+            return;
+        }
+        let this_postcode = latest_postcode.get_value();
+        spawn_local(async move {
+            let result = check_coverage(&postcode).await;
+            if latest_postcode.get_value() == this_postcode {
+                set_coverage.set(Some(result));
+            }
+        });
+    });
 
-        (base_low + urgency_modifier, base_high + urgency_modifier)
+    // Re-estimate whenever the job details change. Only the newest response
+    // is shown, in case replies arrive out of order. Postcodes we don't
+    // cover get no estimate at all; the backend prices a covered one by its
+    // travel band.
+    Effect::new(move |_| {
+        let covered_postcode = match coverage.get() {
+            Some(Ok(found)) if found.covered => Some(found.postcode),
+            Some(_) => {
+                set_estimate.set(None);
+                return;
+            }
+            None => None,
+        };
+        let request = InstantQuoteRequest {
+            service_type: service.get(),
+            urgency: Some(urgency.get()),
+            job_size: Some(job_size.get()),
+            out_of_hours: out_of_hours.get(),
+            weekend: weekend.get(),
+            distance_zone: Some("1".to_string()),
+            postcode: covered_postcode,
+            description: Some(description.get_untracked()).filter(|d| !d.trim().is_empty()),
+            ..Default::default()
+        };
+        if request.service_type.is_empty() {
+            return;
+        }
+        latest_request.update_value(|n| *n += 1);
+        let this_request = latest_request.get_value();
+        spawn_local(async move {
+            let result = instant_quote(&request).await;
+            if latest_request.get_value() != this_request {
+                return;
+            }
+            match result {
+                Ok(response) => {
+                    set_estimate.set(Some(response));
+                    set_estimate_error.set(None);
+                }
+                Err(err) => set_estimate_error.set(Some(err)),
+            }
+        });
     });
 
+    let hint = move |condition: RuleCondition| {
+        catalog.with(|catalog| option_hint(catalog, &service.get(), &condition))
+    };
+
     view! {
-        <Title text="Get a Quote | Coventry Handyman"/>
+        <Title text=page_title(site, "Get a Quote")/>
 
         <div class="min-h-screen bg-gradient-to-b from-slate-50 to-white">
             // Hero
@@ -100,6 +193,10 @@ pub fn HandymanQuote() -> impl IntoView {
                 <div class="max-w-2xl mx-auto">
                     <div class="bg-white rounded-2xl shadow-xl border border-slate-100 overflow-hidden">
                         <div class="p-8 space-y-6">
+                            {move || catalog_error.get().map(|err| view! {
+                                <div class="p-4 rounded-lg bg-red-50 text-red-700 text-sm">{err}</div>
+                            })}
+
                             // Service Type
                             <div>
                                 <label class="block text-sm font-bold text-slate-700 mb-2">
@@ -111,9 +208,10 @@ pub fn HandymanQuote() -> impl IntoView {
                                         set_service.set(event_target_value(&ev));
                                     }
                                 >
-                                    {SERVICES.iter().map(|svc| {
+                                    {move || catalog.get().services.into_iter().map(|svc| {
+                                        let selected = svc.service_type == service.get_untracked();
                                         view! {
-                                            <option value=svc.value selected=svc.value == "plumbing">
+                                            <option value=svc.service_type selected=selected>
                                                 {svc.label}
                                             </option>
                                         }
@@ -143,50 +241,107 @@ pub fn HandymanQuote() -> impl IntoView {
                                     "How urgent is this?"
                                 </label>
                                 <div class="grid grid-cols-3 gap-3">
-                                    <button
-                                        class=move || format!(
-                                            "p-4 rounded-lg border-2 text-center transition {}",
-                                            if urgency.get() == "same_day" {
-                                                "border-yellow-500 bg-yellow-50"
-                                            } else {
-                                                "border-slate-200 hover:border-slate-300"
-                                            }
-                                        )
-                                        on:click=move |_| set_urgency.set("same_day".to_string())
-                                    >
-                                        <div class="font-bold text-slate-900">"Same Day"</div>
-                                        <div class="text-sm text-slate-500">"+£15"</div>
-                                    </button>
-                                    <button
-                                        class=move || format!(
-                                            "p-4 rounded-lg border-2 text-center transition {}",
-                                            if urgency.get() == "within_3_days" {
-                                                "border-blue-500 bg-blue-50"
-                                            } else {
-                                                "border-slate-200 hover:border-slate-300"
-                                            }
-                                        )
-                                        on:click=move |_| set_urgency.set("within_3_days".to_string())
+                                    {URGENCY_OPTIONS.iter().map(|&(value, label, active_class)| {
+                                        view! {
+                                            <button
+                                                class=move || format!(
+                                                    "p-4 rounded-lg border-2 text-center transition {}",
+                                                    if urgency.get() == value {
+                                                        active_class
+                                                    } else {
+                                                        "border-slate-200 hover:border-slate-300"
+                                                    }
+                                                )
+                                                on:click=move |_| set_urgency.set(value.to_string())
+                                            >
+                                                <div class="font-bold text-slate-900">{label}</div>
+                                                <div class="text-sm text-slate-500">
+                                                    {move || {
+                                                        let hint = hint(RuleCondition::Urgency(value.to_string()));
+                                                        if hint.is_empty() { "Standard".to_string() } else { hint }
+                                                    }}
+                                                </div>
+                                            </button>
+                                        }
+                                    }).collect_view()}
+                                </div>
+                            </div>
+
+                            // Job Size and Location
+                            <div class="grid md:grid-cols-2 gap-4">
+                                <div>
+                                    <label class="block text-sm font-bold text-slate-700 mb-2">
+                                        "How big is the job?"
+                                    </label>
+                                    <select
+                                        class="w-full px-4 py-3 rounded-lg border border-slate-300 focus:border-blue-500 focus:ring-2 focus:ring-blue-500/20 transition"
+                                        on:change=move |ev| set_job_size.set(event_target_value(&ev))
                                     >
-                                        <div class="font-bold text-slate-900">"Within 3 Days"</div>
-                                        <div class="text-sm text-slate-500">"Standard"</div>
-                                    </button>
-                                    <button
-                                        class=move || format!(
-                                            "p-4 rounded-lg border-2 text-center transition {}",
-                                            if urgency.get() == "flexible" {
-                                                "border-green-500 bg-green-50"
-                                            } else {
-                                                "border-slate-200 hover:border-slate-300"
+                                        {JOB_SIZES.iter().map(|&(value, label)| view! {
+                                            <option value=value selected=value == "medium">
+                                                {move || {
+                                                    let hint = hint(RuleCondition::JobSize(value.to_string()));
+                                                    if hint.is_empty() { label.to_string() } else { format!("{label} {hint}") }
+                                                }}
+                                            </option>
+                                        }).collect_view()}
+                                    </select>
+                                </div>
+                                <div>
+                                    <label class="block text-sm font-bold text-slate-700 mb-2">
+                                        "Where is the job?"
+                                    </label>
+                                    <input
+                                        type="text"
+                                        class="w-full px-4 py-3 rounded-lg border border-slate-300 focus:border-blue-500 focus:ring-2 focus:ring-blue-500/20 transition uppercase"
+                                        placeholder="Postcode, e.g. CV1 1AA"
+                                        autocomplete="postal-code"
+                                        prop:value=move || postcode.get()
+                                        on:change=move |ev| set_postcode.set(event_target_value(&ev))
+                                    />
+                                    {move || coverage.get().map(|result| match result {
+                                        Ok(found) => {
+                                            let class = if found.covered { "text-green-700" } else { "text-red-700" };
+                                            let zone_hint = found
+                                                .distance_zone()
+                                                .map(|zone| hint(RuleCondition::DistanceZone(zone)))
+                                                .unwrap_or_default();
+                                            view! {
+                                                <p class=format!("mt-2 text-sm {class}")>
+                                                    {format!("{} {}", found.message, zone_hint).trim().to_string()}
+                                                </p>
                                             }
-                                        )
-                                        on:click=move |_| set_urgency.set("flexible".to_string())
-                                    >
-                                        <div class="font-bold text-slate-900">"Flexible"</div>
-                                        <div class="text-sm text-green-600">"-£5"</div>
-                                    </button>
+                                            .into_any()
+                                        }
+                                        Err(err) => view! {
+                                            <p class="mt-2 text-sm text-red-700">{err}</p>
+                                        }
+                                        .into_any(),
+                                    })}
                                 </div>
                             </div>
+
+                            // Timing
+                            <div class="flex flex-wrap gap-6 text-slate-700">
+                                <label class="flex items-center gap-2">
+                                    <input
+                                        type="checkbox"
+                                        prop:checked=move || out_of_hours.get()
+                                        on:change=move |ev| set_out_of_hours.set(event_target_checked(&ev))
+                                    />
+                                    "Evenings / out of hours "
+                                    <span class="text-sm text-slate-500">{move || hint(RuleCondition::OutOfHours)}</span>
+                                </label>
+                                <label class="flex items-center gap-2">
+                                    <input
+                                        type="checkbox"
+                                        prop:checked=move || weekend.get()
+                                        on:change=move |ev| set_weekend.set(event_target_checked(&ev))
+                                    />
+                                    "Weekend "
+                                    <span class="text-sm text-slate-500">{move || hint(RuleCondition::Weekend)}</span>
+                                </label>
+                            </div>
                         </div>
 
                         // Estimate Display
@@ -194,13 +349,32 @@ pub fn HandymanQuote() -> impl IntoView {
                             <div class="text-center">
                                 <div class="text-sm uppercase tracking-wide text-blue-200 mb-2">"Estimated Cost"</div>
                                 <div class="text-5xl font-black mb-2">
-                                    {move || {
-                                        let (low, high) = estimate.get();
-                                        format!("£{} - £{}", low, high)
+                                    {move || match (estimate.get(), coverage.get()) {
+                                        (_, Some(Ok(found))) if !found.covered => "Outside our area".to_string(),
+                                        (Some(est), _) => format!("{} - {}", pounds(est.estimate_low), pounds(est.estimate_high)),
+                                        (None, _) => "...".to_string(),
                                     }}
                                 </div>
+                                <ul class="text-blue-100 text-sm mb-2 space-y-1">
+                                    {move || estimate.get().map(|est| est.adjustments.into_iter().map(|adj| {
+                                        let amount = if adj.low_cents == adj.high_cents {
+                                            pounds(adj.low_cents.abs())
+                                        } else {
+                                            format!("{} - {}", pounds(adj.low_cents.abs()), pounds(adj.high_cents.abs()))
+                                        };
+                                        let sign = if adj.high_cents < 0 { "-" } else { "+" };
+                                        view! { <li>{format!("{}: {}{}", adj.label, sign, amount)}</li> }
+                                    }).collect_view())}
+                                </ul>
+                                {move || estimate_error.get().map(|err| view! {
+                                    <div class="text-yellow-300 text-sm mb-2">{err}</div>
+                                })}
                                 <div class="text-blue-200 text-sm">
-                                    "Final price depends on job complexity and materials needed"
+                                    {move || if estimate.get().is_some_and(|est| est.vat_included) {
+                                        "Includes VAT. Final price depends on job complexity and materials needed"
+                                    } else {
+                                        "Final price depends on job complexity and materials needed"
+                                    }}
                                 </div>
                             </div>
                         </div>
@@ -214,7 +388,7 @@ pub fn HandymanQuote() -> impl IntoView {
                                 "Book Online Now"
                             </a>
                             <a
-                                href="tel:+447833263486"
+                                href=tel_href(site)
                                 class="block text-center px-6 py-4 bg-white border-2 border-blue-900 text-blue-900 rounded-lg font-bold text-lg hover:bg-blue-50 transition"
                             >
                                 "Call to Discuss"
@@ -252,37 +426,37 @@ pub fn HandymanQuote() -> impl IntoView {
                     <h2 class="text-3xl font-bold text-center text-slate-900 mb-12">"Pricing Guide"</h2>
                     <div class="grid md:grid-cols-2 gap-6">
                         <div class="bg-white rounded-xl p-6 shadow-sm">
-                            <h3 class="font-bold text-lg text-slate-900 mb-4">"Standard Rates"</h3>
+                            <h3 class="font-bold text-lg text-slate-900 mb-4">"Typical Prices"</h3>
                             <ul class="space-y-3 text-slate-600">
-                                <li class="flex justify-between">
-                                    <span>"Call-out fee"</span>
-                                    <span class="font-bold text-slate-900">"£30"</span>
-                                </li>
-                                <li class="flex justify-between">
-                                    <span>"Hourly rate"</span>
-                                    <span class="font-bold text-slate-900">"£45/hr"</span>
-                                </li>
-                                <li class="flex justify-between">
-                                    <span>"Materials markup"</span>
-                                    <span class="font-bold text-slate-900">"Cost + 15%"</span>
-                                </li>
+                                {move || catalog.get().services.into_iter().map(|svc| view! {
+                                    <li class="flex justify-between">
+                                        <span>{svc.label}</span>
+                                        <span class="font-bold text-slate-900">
+                                            {format!("{} - {}", pounds(svc.base_low_cents), pounds(svc.base_high_cents))}
+                                        </span>
+                                    </li>
+                                }).collect_view()}
                             </ul>
                         </div>
                         <div class="bg-white rounded-xl p-6 shadow-sm">
-                            <h3 class="font-bold text-lg text-slate-900 mb-4">"Common Jobs"</h3>
+                            <h3 class="font-bold text-lg text-slate-900 mb-4">"Adjustments"</h3>
                             <ul class="space-y-3 text-slate-600">
-                                <li class="flex justify-between">
-                                    <span>"Leaky tap repair"</span>
-                                    <span class="font-bold text-slate-900">"£45 - £85"</span>
-                                </li>
-                                <li class="flex justify-between">
-                                    <span>"Light fitting"</span>
-                                    <span class="font-bold text-slate-900">"£40 - £70"</span>
-                                </li>
-                                <li class="flex justify-between">
-                                    <span>"TV wall mount"</span>
-                                    <span class="font-bold text-slate-900">"£55 - £95"</span>
-                                </li>
+                                {move || catalog.with(|catalog| catalog.rules.iter().filter(|rule| rule.active).map(|rule| {
+                                    // Service-specific rules name their service
+                                    let service = rule.service_type.as_deref().and_then(|key| {
+                                        catalog.services.iter().find(|svc| svc.service_type == key)
+                                    });
+                                    let label = match service {
+                                        Some(svc) => format!("{} ({})", rule.label, svc.label),
+                                        None => rule.label.clone(),
+                                    };
+                                    view! {
+                                        <li class="flex justify-between">
+                                            <span>{label}</span>
+                                            <span class="font-bold text-slate-900">{adjustment_hint(rule)}</span>
+                                        </li>
+                                    }
+                                }).collect_view())}
                             </ul>
                         </div>
                     </div>
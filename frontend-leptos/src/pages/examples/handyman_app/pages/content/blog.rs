@@ -2,57 +2,58 @@
 //!
 //! Displays blog posts for SEO and authority building.
 
+use crate::pages::examples::handyman_app::site::{home_area, page_title, use_site};
 use leptos::prelude::*;
 use leptos_meta::Title;
 
 #[derive(Clone, Debug, PartialEq)]
 struct BlogPost {
     slug: &'static str,
-    title: &'static str,
-    excerpt: &'static str,
+    title: String,
+    excerpt: String,
     category: &'static str,
     date: &'static str,
     read_time: &'static str,
 }
 
-fn get_blog_posts() -> Vec<BlogPost> {
+fn get_blog_posts(area: &str) -> Vec<BlogPost> {
     vec![
         BlogPost {
             slug: "handyman-cost-coventry",
-            title: "How Much Does a Handyman Cost in Coventry? (2024 Guide)",
-            excerpt: "Complete pricing guide for handyman services in Coventry. Learn what to expect to pay for common jobs and how to get the best value.",
+            title: format!("How Much Does a Handyman Cost in {}? (2024 Guide)", area),
+            excerpt: format!("Complete pricing guide for handyman services in {}. Learn what to expect to pay for common jobs and how to get the best value.", area),
             category: "Pricing",
             date: "Dec 20, 2024",
             read_time: "5 min",
         },
         BlogPost {
             slug: "winter-home-maintenance",
-            title: "10 Essential Home Maintenance Tasks Before Winter",
-            excerpt: "Prepare your home for the cold months ahead. From checking seals to bleeding radiators, here's your complete winter prep checklist.",
+            title: "10 Essential Home Maintenance Tasks Before Winter".to_string(),
+            excerpt: "Prepare your home for the cold months ahead. From checking seals to bleeding radiators, here's your complete winter prep checklist.".to_string(),
             category: "Maintenance",
             date: "Dec 15, 2024",
             read_time: "7 min",
         },
         BlogPost {
             slug: "diy-vs-professional",
-            title: "When to DIY vs Hire a Professional Handyman",
-            excerpt: "Know when to tackle a job yourself and when it's time to call in the experts. Plus, common DIY mistakes to avoid.",
+            title: "When to DIY vs Hire a Professional Handyman".to_string(),
+            excerpt: "Know when to tackle a job yourself and when it's time to call in the experts. Plus, common DIY mistakes to avoid.".to_string(),
             category: "Tips",
             date: "Dec 10, 2024",
             read_time: "6 min",
         },
         BlogPost {
             slug: "furniture-assembly-tips",
-            title: "IKEA Furniture Assembly: 7 Pro Tips for Success",
-            excerpt: "Secrets from professional assemblers to make your flatpack furniture sturdy and stress-free.",
+            title: "IKEA Furniture Assembly: 7 Pro Tips for Success".to_string(),
+            excerpt: "Secrets from professional assemblers to make your flatpack furniture sturdy and stress-free.".to_string(),
             category: "Tips",
             date: "Dec 5, 2024",
             read_time: "4 min",
         },
         BlogPost {
             slug: "finding-reliable-handyman",
-            title: "How to Find a Reliable Handyman in Coventry",
-            excerpt: "What to look for when hiring a handyman. Credentials, insurance, reviews, and red flags to watch out for.",
+            title: format!("How to Find a Reliable Handyman in {}", area),
+            excerpt: "What to look for when hiring a handyman. Credentials, insurance, reviews, and red flags to watch out for.".to_string(),
             category: "Advice",
             date: "Nov 28, 2024",
             read_time: "5 min",
@@ -62,10 +63,11 @@ fn get_blog_posts() -> Vec<BlogPost> {
 
 #[component]
 pub fn HandymanBlog() -> impl IntoView {
-    let posts = get_blog_posts();
+    let site = use_site();
+    let posts = move || site.with(|s| get_blog_posts(&home_area(s)));
 
     view! {
-        <Title text="Blog | Coventry Handyman Tips & Advice"/>
+        <Title text=page_title(site, "Blog")/>
 
         <div class="min-h-screen bg-slate-50">
             // Hero
@@ -73,7 +75,7 @@ pub fn HandymanBlog() -> impl IntoView {
                 <div class="max-w-4xl mx-auto text-center">
                     <h1 class="text-4xl md:text-5xl font-black mb-4">"Handyman Tips & Advice"</h1>
                     <p class="text-xl text-blue-200">
-                        "Expert guides, DIY tips, and home maintenance advice from Coventry's trusted handymen."
+                        {move || format!("Expert guides, DIY tips, and home maintenance advice from {}'s trusted handymen.", site.with(home_area))}
                     </p>
                 </div>
             </section>
@@ -94,10 +96,10 @@ pub fn HandymanBlog() -> impl IntoView {
                                 "Featured"
                             </span>
                             <h2 class="text-2xl font-bold text-slate-900 mb-3">
-                                "How Much Does a Handyman Cost in Coventry?"
+                                {move || format!("How Much Does a Handyman Cost in {}?", site.with(home_area))}
                             </h2>
                             <p class="text-slate-600 mb-4">
-                                "Complete pricing guide for 2024. Know exactly what to expect to pay for common handyman jobs in the Coventry area."
+                                {move || format!("Complete pricing guide for 2024. Know exactly what to expect to pay for common handyman jobs in the {} area.", site.with(home_area))}
                             </p>
                             <a href="/handyman-coventry/blog/handyman-cost-coventry" class="inline-flex items-center gap-2 text-blue-600 font-bold hover:text-blue-700">
                                 "Read Full Guide"
@@ -116,8 +118,7 @@ pub fn HandymanBlog() -> impl IntoView {
                     <h2 class="text-2xl font-bold text-slate-900 mb-8">"Latest Articles"</h2>
 
                     <div class="grid md:grid-cols-3 gap-8">
-                        {posts.iter().skip(1).map(|post| {
-                            let post = post.clone();
+                        {move || posts().into_iter().skip(1).map(|post| {
                             view! {
                                 <article class="bg-slate-50 rounded-xl overflow-hidden hover:shadow-lg transition group">
                                     <div class="h-40 bg-gradient-to-br from-blue-100 to-blue-200 flex items-center justify-center">
@@ -1,7 +1,19 @@
+use crate::pages::examples::handyman_app::site::{area_list, phone, tel_href, use_site};
 use leptos::prelude::*;
 
 #[component]
 pub fn HandymanContact() -> impl IntoView {
+    let site = use_site();
+    let mailto = move || site.with(|s| format!("mailto:{}", s.email.clone().unwrap_or_default()));
+    let location = move || {
+        site.with(|s| {
+            s.areas
+                .first()
+                .map(|a| format!("{}, UK", a.name))
+                .unwrap_or_default()
+        })
+    };
+
     view! {
         <div class="min-h-screen bg-gray-50 font-sans text-gray-800">
             // -- Header / Banner Section --
@@ -40,8 +52,8 @@ pub fn HandymanContact() -> impl IntoView {
                                 <svg class="w-6 h-6" fill="none" stroke="currentColor" viewBox="0 0 24 24"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M3 5a2 2 0 012-2h3.28a1 1 0 01.948.684l1.498 4.493a1 1 0 01-.502 1.21l-2.257 1.13a11.042 11.042 0 005.516 5.516l1.13-2.257a1 1 0 011.21-.502l4.493 1.498a1 1 0 01.684.949V19a2 2 0 01-2 2h-1C9.716 21 3 14.284 3 6V5z"></path></svg>
                             </div>
                             <h4 class="text-lg font-bold text-blue-950">"Phone"</h4>
-                            <a href="tel:02476123456" class="text-gray-600 font-medium hover:text-blue-700 decoration-blue-300 decoration-2 underline-offset-2 transition">
-                                "(024) 7612-3456"
+                            <a href=tel_href(site) class="text-gray-600 font-medium hover:text-blue-700 decoration-blue-300 decoration-2 underline-offset-2 transition">
+                                {phone(site)}
                             </a>
                         </div>
 
@@ -52,7 +64,7 @@ pub fn HandymanContact() -> impl IntoView {
                             </div>
                             <h4 class="text-lg font-bold text-blue-950">"Location"</h4>
                             <p class="text-gray-600 font-medium">
-                                "Coventry, UK"
+                                {location}
                             </p>
                         </div>
 
@@ -62,7 +74,7 @@ pub fn HandymanContact() -> impl IntoView {
                                 <svg class="w-6 h-6" fill="none" stroke="currentColor" viewBox="0 0 24 24"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M3 8l7.89 5.26a2 2 0 002.22 0L21 8M5 19h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v10a2 2 0 002 2z"></path></svg>
                             </div>
                             <h4 class="text-lg font-bold text-blue-950">"Email"</h4>
-                            <a href="mailto:info@handyman-coventry.co.uk" class="text-gray-600 font-medium hover:text-blue-700 decoration-blue-300 decoration-2 underline-offset-2 transition">
+                            <a href=mailto class="text-gray-600 font-medium hover:text-blue-700 decoration-blue-300 decoration-2 underline-offset-2 transition">
                                 "Click To Email"
                             </a>
                         </div>
@@ -150,7 +162,7 @@ pub fn HandymanContact() -> impl IntoView {
             // -- Footer / Accreditation Strip (Optional addition based on muse) --
             <div class="bg-white border-t border-gray-200 py-12">
                 <div class="max-w-7xl mx-auto px-6 text-center">
-                    <p class="text-gray-500 font-medium mb-6 uppercase tracking-widest text-sm">"Trusted by homeowners across Coventry"</p>
+                    <p class="text-gray-500 font-medium mb-6 uppercase tracking-widest text-sm">{move || format!("Trusted by homeowners across {}", site.with(area_list))}</p>
                      <div class="flex flex-wrap justify-center gap-8 md:gap-16 opacity-60 grayscale hover:grayscale-0 transition duration-500">
                         // Placeholders for trust logos - text for now
                          <span class="text-xl font-bold font-serif text-gray-400">"ReviewCentre"</span>
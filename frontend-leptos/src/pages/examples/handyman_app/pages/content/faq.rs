@@ -1,16 +1,21 @@
 use crate::components::seo::SeoHead;
+use crate::pages::examples::handyman_app::site::{area_list, home_area, use_site};
 use leptos::prelude::*;
 use shared::PageMetadata;
 
 #[component]
 pub fn HandymanFaq() -> impl IntoView {
+    let site = use_site();
+
     view! {
-        <SeoHead metadata=PageMetadata {
-            title: "Frequently Asked Questions | XFTradesmen Handyman Coventry".to_string(),
-            description: "Answers to common questions about our handyman services in Coventry. Pricing, booking, insurance, and service areas explained.".to_string(),
-             canonical_url: Some("https://handyman-coventry.co.uk/faq".to_string()),
-            og_image: None,
-        }/>
+        {move || site.with(|s| view! {
+            <SeoHead metadata=PageMetadata {
+                title: format!("Frequently Asked Questions | {}", s.business_name),
+                description: format!("Answers to common questions about our handyman services in {}. Pricing, booking, insurance, and service areas explained.", home_area(s)),
+                canonical_url: Some("https://handyman-coventry.co.uk/faq".to_string()),
+                og_image: None,
+            }/>
+        })}
 
         <div class="min-h-screen bg-gradient-to-br from-blue-900 via-slate-900 to-black text-white">
             // Hero Section
@@ -79,13 +84,13 @@ pub fn HandymanFaq() -> impl IntoView {
                  // Question 5
                  <details class="group bg-white/5 hover:bg-white/10 backdrop-blur-sm rounded-xl border border-white/10 overflow-hidden transition-all duration-200">
                     <summary class="flex justify-between items-center cursor-pointer p-6 list-none text-lg font-bold text-white group-open:text-blue-300 transition-colors">
-                        "What areas of Coventry do you cover?"
+                        "What areas do you cover?"
                          <div class="w-8 h-8 rounded-full bg-white/10 flex items-center justify-center text-blue-300 group-open:rotate-180 transition-transform duration-300">
                              <svg class="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M19 9l-7 7-7-7"/></svg>
                         </div>
                     </summary>
                     <div class="px-6 pb-6 text-blue-100 text-base leading-relaxed animate-in slide-in-from-top-2 duration-200">
-                        "We cover all of Coventry (CV1-CV6) and immediate surrounding areas like Bedworth, Kenilworth, and Meriden. If you're slightly further out, just ask!"
+                        {move || format!("We cover {} and the immediate surrounding areas. If you're slightly further out, just ask!", site.with(area_list))}
                     </div>
                 </details>
             </div>
@@ -1,12 +1,16 @@
 //! Legal pages: Privacy Policy and Terms of Service.
 
+use crate::pages::examples::handyman_app::site::{page_title, phone, use_site};
 use leptos::prelude::*;
 use leptos_meta::Title;
 
 #[component]
 pub fn PrivacyPolicy() -> impl IntoView {
+    let site = use_site();
+    let email = move || site.with(|s| s.email.clone().unwrap_or_default());
+
     view! {
-        <Title text="Privacy Policy | Coventry Handyman"/>
+        <Title text=page_title(site, "Privacy Policy")/>
 
         <div class="min-h-screen bg-slate-50 py-12 px-6">
             <article class="max-w-3xl mx-auto bg-white rounded-xl p-8 shadow-sm prose prose-slate">
@@ -45,8 +49,8 @@ pub fn PrivacyPolicy() -> impl IntoView {
                 <h2>"Contact Us"</h2>
                 <p>"For any privacy-related queries, please contact us at:"</p>
                 <p>
-                    <strong>"Email: "</strong>"hello@xftradesman.com"<br/>
-                    <strong>"Phone: "</strong>"07833 263486"
+                    <strong>"Email: "</strong>{email}<br/>
+                    <strong>"Phone: "</strong>{phone(site)}
                 </p>
             </article>
         </div>
@@ -55,8 +59,11 @@ pub fn PrivacyPolicy() -> impl IntoView {
 
 #[component]
 pub fn TermsOfService() -> impl IntoView {
+    let site = use_site();
+    let business_name = move || site.with(|s| s.business_name.clone());
+
     view! {
-        <Title text="Terms of Service | Coventry Handyman"/>
+        <Title text=page_title(site, "Terms of Service")/>
 
         <div class="min-h-screen bg-slate-50 py-12 px-6">
             <article class="max-w-3xl mx-auto bg-white rounded-xl p-8 shadow-sm prose prose-slate">
@@ -64,7 +71,7 @@ pub fn TermsOfService() -> impl IntoView {
                 <p class="text-slate-500">"Last updated: December 23, 2024"</p>
 
                 <h2>"1. Services"</h2>
-                <p>"Coventry Handyman provides general handyman services including plumbing, electrical work, carpentry, furniture assembly, and general repairs. All work is carried out by qualified professionals."</p>
+                <p>{business_name}" provides general handyman services including plumbing, electrical work, carpentry, furniture assembly, and general repairs. All work is carried out by qualified professionals."</p>
 
                 <h2>"2. Bookings"</h2>
                 <p>"By booking our services, you agree to:"</p>
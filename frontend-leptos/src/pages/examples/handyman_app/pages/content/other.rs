@@ -1,11 +1,14 @@
+use crate::pages::examples::handyman_app::site::{home_area, use_site};
 use leptos::prelude::*;
 
 #[component]
 pub fn HandymanAbout() -> impl IntoView {
+    let site = use_site();
+
     view! {
         <div class="py-24 px-6 text-center">
             <h1 class="text-4xl font-bold text-blue-900 mb-4">"About Us"</h1>
-            <p class="text-gray-600">"We are a team of dedicated trade professionals based in Coventry."</p>
+            <p class="text-gray-600">{move || format!("We are a team of dedicated trade professionals based in {}.", site.with(home_area))}</p>
         </div>
     }
 }
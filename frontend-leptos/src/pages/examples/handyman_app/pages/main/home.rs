@@ -1,19 +1,24 @@
 //! Handyman Home Page.
 
 use crate::components::seo::{LocalBusinessSchema, SeoHead};
+use crate::pages::examples::handyman_app::site::{home_area, use_site};
 use leptos::prelude::*;
 use leptos_router::components::A;
 use shared::PageMetadata;
 
 #[component]
 pub fn HandymanHome() -> impl IntoView {
+    let site = use_site();
+
     view! {
-        <SeoHead metadata=PageMetadata {
-            title: "Coventry Handyman Services | Reliable & Local Examples".to_string(),
-            description: "Meet Rick, the renowned local handyman making home repairs simple again. 10 years experience, affordable rates, attentive workmanship.".to_string(),
-            canonical_url: Some("https://xftradesman.com/handyman-coventry".to_string()),
-            og_image: Some("/images/hero_assets/hero_bg.png".to_string()),
-        }/>
+        {move || site.with(|s| view! {
+            <SeoHead metadata=PageMetadata {
+                title: format!("{} Services | Reliable & Local", s.business_name),
+                description: "Meet Rick, the renowned local handyman making home repairs simple again. 10 years experience, affordable rates, attentive workmanship.".to_string(),
+                canonical_url: Some("https://xftradesman.com/handyman-coventry".to_string()),
+                og_image: Some("/images/hero_assets/hero_bg.png".to_string()),
+            }/>
+        })}
         <LocalBusinessSchema />
 
         <div class="font-sans antialiased text-slate-900 bg-white">
@@ -137,7 +142,7 @@ pub fn HandymanHome() -> impl IntoView {
                     </div>
                     <h2 class="text-4xl font-black text-slate-900 mb-6">"Hi, I'm Rick."</h2>
                     <p class="text-xl text-slate-600 max-w-2xl mx-auto leading-relaxed mb-10">
-                        "I started this business with a simple mission directly from my garage in Coventry: to provide honest, high-quality work without the 'tradesman markup'. When you call, you speak to me. When I work, I treat your home like my own."
+                        {move || format!("I started this business with a simple mission directly from my garage in {}: to provide honest, high-quality work without the 'tradesman markup'. When you call, you speak to me. When I work, I treat your home like my own.", site.with(home_area))}
                     </p>
                 </div>
             </section>
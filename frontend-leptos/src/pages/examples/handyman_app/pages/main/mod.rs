@@ -1,8 +1,9 @@
-//! Main pages - Home, Services, Features, Areas
+//! Main pages - Home, Services, Features, Areas, Service-in-town
 
 mod areas;
 mod features;
 mod home;
+mod service_area;
 mod service_detail;
 mod servicemap;
 mod services;
@@ -10,6 +11,7 @@ mod services;
 pub use areas::*;
 pub use features::*;
 pub use home::*;
+pub use service_area::*;
 pub use service_detail::*;
 pub use servicemap::*;
 pub use services::*;
@@ -0,0 +1,199 @@
+//! Service-in-town pages for the handyman site.
+//!
+//! A page like `/services/plumbing/coventry` only exists once someone has
+//! written an introduction for that service in that town, so these pages
+//! are never the service page with the town name swapped in.
+
+use crate::api::landing::fetch_service_area_page;
+use crate::pages::examples::handyman_app::site::{page_title, use_site};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_meta::Title;
+use leptos_router::hooks::use_params_map;
+use shared::ServiceAreaPage;
+
+#[component]
+pub fn HandymanServiceAreaPage() -> impl IntoView {
+    let site = use_site();
+    let params = use_params_map();
+    let slugs = move || {
+        let params = params.get();
+        (
+            params.get("service").unwrap_or_default(),
+            params.get("town").unwrap_or_default(),
+        )
+    };
+    let (page, set_page) = signal(Option::<Result<ServiceAreaPage, String>>::None);
+
+    Effect::new(move |_| {
+        let (service, town) = slugs();
+        set_page.set(None);
+        spawn_local(async move {
+            set_page.set(Some(fetch_service_area_page(&service, &town).await));
+        });
+    });
+
+    move || match page.get() {
+        None => view! {
+            <div class="min-h-screen bg-slate-50 flex items-center justify-center">
+                <p class="text-slate-500">"Loading..."</p>
+            </div>
+        }
+        .into_any(),
+        Some(Err(_)) => {
+            let service = slugs().0;
+            view! {
+                <Title text=page_title(site, "Page Not Found")/>
+                <div class="min-h-screen bg-slate-50 flex items-center justify-center px-6">
+                    <div class="text-center">
+                        <h1 class="text-3xl font-black text-slate-900 mb-4">"We don't have a local page for that yet"</h1>
+                        <p class="text-slate-600 mb-6">"The service page has everything you need to book."</p>
+                        <a href=format!("/handyman-coventry/services/{}", service) class="inline-block px-6 py-3 bg-blue-900 text-white font-bold rounded-lg hover:bg-blue-800 transition">
+                            "View Service"
+                        </a>
+                    </div>
+                </div>
+            }
+            .into_any()
+        }
+        Some(Ok(found)) => service_area_view(found).into_any(),
+    }
+}
+
+fn service_area_view(page: ServiceAreaPage) -> impl IntoView {
+    let site = use_site();
+    let service_slug = page.service.slug.clone();
+    let area_slug = page.area.slug.clone();
+    let price = page
+        .price_from_cents
+        .zip(page.price_to_cents)
+        .map(|(from, to)| format!("£{} - £{}", from / 100, to / 100));
+
+    view! {
+        <Title text=page_title(site, format!("{} in {}", page.service.name, page.area.name))/>
+
+        <div class="min-h-screen bg-slate-50">
+            // Hero
+            <section class="bg-gradient-to-br from-blue-900 to-blue-800 text-white py-16 px-6">
+                <div class="max-w-6xl mx-auto">
+                    <a href=format!("/handyman-coventry/areas/{}", area_slug) class="text-yellow-400 hover:text-yellow-300 text-sm font-bold mb-4 inline-flex items-center gap-2">
+                        <svg class="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7"/>
+                        </svg>
+                        "All Services in "{page.area.name.clone()}
+                    </a>
+                    <h1 class="text-4xl md:text-5xl font-black mb-4">
+                        {page.service.name.clone()}" in "{page.area.name.clone()}
+                    </h1>
+                    <p class="text-xl text-blue-200 max-w-2xl">{page.intro.clone()}</p>
+                    {price.map(|price| view! {
+                        <div class="mt-8 inline-flex items-center gap-2 bg-white/10 backdrop-blur px-6 py-3 rounded-lg">
+                            <span class="text-blue-200">"Typical price range:"</span>
+                            <span class="text-2xl font-black text-yellow-400">{price}</span>
+                        </div>
+                    })}
+                </div>
+            </section>
+
+            <div class="max-w-6xl mx-auto py-12 px-6">
+                <div class="grid lg:grid-cols-3 gap-8">
+                    // Main content
+                    <div class="lg:col-span-2 space-y-8">
+                        // About the service
+                        <div class="bg-white rounded-xl p-8 shadow-sm">
+                            <h2 class="text-2xl font-bold text-slate-900 mb-4">
+                                "About Our "{page.service.name.clone()}" Service"
+                            </h2>
+                            <div class="prose prose-slate max-w-none text-slate-600 leading-relaxed" inner_html=page.description.clone()/>
+                            <a href=format!("/handyman-coventry/services/{}", service_slug) class="mt-4 inline-block text-blue-600 font-medium hover:underline">
+                                "More about "{page.service.name.clone()}
+                            </a>
+                        </div>
+
+                        // Postcodes
+                        <div class="bg-white rounded-xl p-8 shadow-sm">
+                            <h2 class="text-2xl font-bold text-slate-900 mb-4">
+                                "Postcodes We Cover in "{page.area.name.clone()}
+                            </h2>
+                            <div class="flex flex-wrap gap-2">
+                                {page.postcodes.iter().map(|pc| {
+                                    view! {
+                                        <span class="px-4 py-2 bg-blue-50 text-blue-700 rounded-lg font-medium">
+                                            {pc.clone()}
+                                        </span>
+                                    }
+                                }).collect_view()}
+                            </div>
+                            {page.travel_time.clone().map(|travel_time| view! {
+                                <p class="mt-4 text-sm text-slate-500">"Typical response time: "{travel_time}</p>
+                            })}
+                        </div>
+
+                        // Testimonial
+                        {page.testimonial.clone().map(|testimonial| view! {
+                            <div class="bg-gradient-to-br from-blue-50 to-indigo-50 rounded-xl p-8">
+                                <blockquote class="text-lg text-slate-700 italic mb-4">
+                                    "\""{testimonial.quote}"\""
+                                </blockquote>
+                                <p class="font-bold text-slate-900">{testimonial.author}</p>
+                            </div>
+                        })}
+                    </div>
+
+                    // Sidebar
+                    <aside class="space-y-6">
+                        <div class="bg-gradient-to-br from-blue-900 to-blue-800 text-white rounded-xl p-6 shadow-lg">
+                            <h3 class="text-xl font-bold mb-4">
+                                "Book "{page.service.name.clone()}" in "{page.area.name.clone()}
+                            </h3>
+                            <div class="space-y-3">
+                                <a href="/handyman-coventry/booking" class="block w-full text-center py-4 bg-yellow-500 text-blue-900 font-bold rounded-lg hover:bg-yellow-400 transition">
+                                    "Book Online"
+                                </a>
+                                <a href="/handyman-coventry/quote" class="block w-full text-center py-4 bg-white/10 backdrop-blur border border-white/20 text-white font-bold rounded-lg hover:bg-white/20 transition">
+                                    "Instant Quote"
+                                </a>
+                            </div>
+                        </div>
+
+                        // Other services in this town
+                        {(!page.other_services.is_empty()).then(|| view! {
+                            <div class="bg-white rounded-xl p-6 shadow-sm">
+                                <h3 class="font-bold text-slate-900 mb-4">
+                                    "Also in "{page.area.name.clone()}
+                                </h3>
+                                <ul class="space-y-2 text-sm">
+                                    {page.other_services.iter().map(|other| view! {
+                                        <li>
+                                            <a href=format!("/handyman-coventry/services/{}/{}", other.slug, area_slug) class="text-blue-600 hover:underline">
+                                                {other.name.clone()}
+                                            </a>
+                                        </li>
+                                    }).collect_view()}
+                                </ul>
+                            </div>
+                        })}
+
+                        // This service in other towns
+                        {(!page.other_areas.is_empty()).then(|| view! {
+                            <div class="bg-white rounded-xl p-6 shadow-sm">
+                                <h3 class="font-bold text-slate-900 mb-4">
+                                    {page.service.name.clone()}" Elsewhere"
+                                </h3>
+                                <ul class="space-y-2 text-sm">
+                                    {page.other_areas.iter().map(|other| view! {
+                                        <li>
+                                            <a href=format!("/handyman-coventry/services/{}/{}", service_slug, other.slug) class="text-blue-600 hover:underline">
+                                                {other.name.clone()}
+                                            </a>
+                                        </li>
+                                    }).collect_view()}
+                                </ul>
+                            </div>
+                        })}
+                    </aside>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -3,6 +3,7 @@
 //! Displays detailed service information with pricing guides,
 //! FAQs for rich snippets, and booking options.
 
+use crate::pages::examples::handyman_app::site::{home_area, phone, tel_href, use_site};
 use leptos::prelude::*;
 use leptos_meta::Title;
 use leptos_router::hooks::use_params_map;
@@ -26,7 +27,7 @@ fn get_service_data(slug: &str) -> ServiceData {
         "plumbing" => ServiceData {
             name: "Plumbing Services",
             slug: "plumbing",
-            description: "Expert plumbing repairs and installations in Coventry",
+            description: "Expert plumbing repairs and installations",
             long_description: "Our qualified plumbers handle everything from leaky taps to complete bathroom installations. We arrive on time, keep your home clean, and get the job done right first time.",
             whats_included: vec![
                 "Free quote on arrival",
@@ -54,7 +55,7 @@ fn get_service_data(slug: &str) -> ServiceData {
         "electrical" => ServiceData {
             name: "Electrical Services",
             slug: "electrical",
-            description: "Safe and certified electrical work in Coventry",
+            description: "Safe and certified electrical work",
             long_description: "Our electricians are fully certified and insured. From light fitting to full rewires, we ensure all work meets current regulations and is completed to the highest safety standards.",
             whats_included: vec![
                 "Certified electrical work",
@@ -82,7 +83,7 @@ fn get_service_data(slug: &str) -> ServiceData {
         "carpentry" => ServiceData {
             name: "Carpentry Services",
             slug: "carpentry",
-            description: "Quality carpentry and woodwork in Coventry",
+            description: "Quality carpentry and woodwork",
             long_description: "Our skilled carpenters create beautiful, functional woodwork. From door hanging to custom shelving, we take pride in craftsmanship and attention to detail.",
             whats_included: vec![
                 "Precision measurements",
@@ -110,7 +111,7 @@ fn get_service_data(slug: &str) -> ServiceData {
         "assembly" => ServiceData {
             name: "Furniture Assembly",
             slug: "assembly",
-            description: "Professional flatpack assembly in Coventry",
+            description: "Professional flatpack assembly",
             long_description: "Save time and frustration with our expert assembly service. We build IKEA, Wayfair, and all flatpack furniture quickly and correctly. All fixings tightened, all pieces aligned.",
             whats_included: vec![
                 "All tools provided",
@@ -138,7 +139,7 @@ fn get_service_data(slug: &str) -> ServiceData {
         _ => ServiceData {
             name: "General Handyman Services",
             slug: "general",
-            description: "Reliable general repairs in Coventry",
+            description: "Reliable general repairs",
             long_description: "Our skilled handymen can tackle almost any job around your home. From small repairs to larger projects, we bring the right tools and expertise.",
             whats_included: vec![
                 "Free assessment",
@@ -168,6 +169,7 @@ fn get_service_data(slug: &str) -> ServiceData {
 
 #[component]
 pub fn HandymanServiceDetail() -> impl IntoView {
+    let site = use_site();
     let params = use_params_map();
     let service_slug = move || params.get().get("slug").unwrap_or_default();
 
@@ -175,7 +177,7 @@ pub fn HandymanServiceDetail() -> impl IntoView {
     let service = Memo::new(move |_| get_service_data(&service_slug()));
 
     view! {
-        <Title text=move || format!("{} | Coventry Handyman", service.get().name)/>
+        <Title text=move || format!("{} | {}", service.get().name, site.with(|s| s.business_name.clone()))/>
 
         <div class="bg-slate-50 min-h-screen">
             // Hero Section
@@ -188,7 +190,7 @@ pub fn HandymanServiceDetail() -> impl IntoView {
                         "Back to Services"
                     </a>
                     <h1 class="text-4xl md:text-5xl font-black mb-4">{move || service.get().name}</h1>
-                    <p class="text-xl text-blue-200 max-w-2xl">{move || service.get().description}</p>
+                    <p class="text-xl text-blue-200 max-w-2xl">{move || format!("{} in {}", service.get().description, site.with(home_area))}</p>
 
                     // Price range badge
                     <div class="mt-8 inline-flex items-center gap-2 bg-white/10 backdrop-blur px-6 py-3 rounded-lg">
@@ -317,10 +319,10 @@ pub fn HandymanServiceDetail() -> impl IntoView {
                                     "Book Online"
                                 </a>
                                 <a
-                                    href="tel:+447833263486"
+                                    href=tel_href(site)
                                     class="block w-full text-center py-4 bg-white/10 backdrop-blur border border-white/20 text-white font-bold rounded-lg hover:bg-white/20 transition"
                                 >
-                                    "Call: 07833 263486"
+                                    "Call: "{phone(site)}
                                 </a>
                                 <a
                                     href="/handyman-coventry/quote"
@@ -335,30 +337,14 @@ pub fn HandymanServiceDetail() -> impl IntoView {
                         <div class="bg-white rounded-xl p-6 shadow-sm border border-slate-100">
                             <h3 class="font-bold text-slate-900 mb-4">"Service Areas"</h3>
                             <ul class="space-y-2 text-sm text-slate-600">
-                                <li class="flex items-center gap-2">
-                                    <svg class="w-4 h-4 text-green-500" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M5 13l4 4L19 7"/>
-                                    </svg>
-                                    "Coventry City Centre"
-                                </li>
-                                <li class="flex items-center gap-2">
-                                    <svg class="w-4 h-4 text-green-500" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M5 13l4 4L19 7"/>
-                                    </svg>
-                                    "Earlsdon, Tile Hill, Canley"
-                                </li>
-                                <li class="flex items-center gap-2">
-                                    <svg class="w-4 h-4 text-green-500" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M5 13l4 4L19 7"/>
-                                    </svg>
-                                    "Kenilworth, Warwick"
-                                </li>
-                                <li class="flex items-center gap-2">
-                                    <svg class="w-4 h-4 text-green-500" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M5 13l4 4L19 7"/>
-                                    </svg>
-                                    "Solihull, Birmingham"
-                                </li>
+                                {move || site.with(|s| s.areas.iter().map(|area| view! {
+                                    <li class="flex items-center gap-2">
+                                        <svg class="w-4 h-4 text-green-500" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M5 13l4 4L19 7"/>
+                                        </svg>
+                                        {area.name.clone()}
+                                    </li>
+                                }).collect_view())}
                             </ul>
                             <a href="/handyman-coventry/service-area" class="text-blue-600 text-sm font-medium inline-block mt-4 hover:underline">
                                 "View full service area map"
@@ -2,10 +2,13 @@
 //!
 //! Interactive 3D globe with Bing Maps satellite imagery, 3D terrain, OSM Buildings, and enhanced rendering.
 
+use crate::pages::examples::handyman_app::site::{home_area, use_site};
 use leptos::prelude::*;
 
 #[component]
 pub fn HandymanServiceMap() -> impl IntoView {
+    let site = use_site();
+
     view! {
         <>
             // Cesium CSS and JS
@@ -30,7 +33,7 @@ pub fn HandymanServiceMap() -> impl IntoView {
                             <div class="flex flex-wrap gap-4 items-center justify-between">
                                 <div class="flex flex-wrap gap-2">
                                     <span class="text-blue-200 text-sm font-bold mr-2 self-center">"Jump to:"</span>
-                                    <button id="btn-coventry" class="px-4 py-2 bg-yellow-500 text-blue-900 rounded-lg font-bold text-sm hover:bg-yellow-400 transition shadow-md">"Coventry (HQ)"</button>
+                                    <button id="btn-coventry" class="px-4 py-2 bg-yellow-500 text-blue-900 rounded-lg font-bold text-sm hover:bg-yellow-400 transition shadow-md">{move || format!("{} (HQ)", site.with(home_area))}</button>
                                     <button id="btn-birmingham" class="px-4 py-2 bg-blue-600 text-white rounded-lg font-bold text-sm hover:bg-blue-500 transition shadow-md">"Birmingham"</button>
                                     <button id="btn-warwick" class="px-4 py-2 bg-blue-600 text-white rounded-lg font-bold text-sm hover:bg-blue-500 transition shadow-md">"Warwick"</button>
                                     <button id="btn-rugby" class="px-4 py-2 bg-blue-600 text-white rounded-lg font-bold text-sm hover:bg-blue-500 transition shadow-md">"Rugby"</button>
@@ -44,7 +47,7 @@ pub fn HandymanServiceMap() -> impl IntoView {
                 // Cesium Container
                 <section class="relative px-4 pb-8">
                     <div class="max-w-6xl mx-auto">
-                        <div id="cesiumContainer" class="w-full h-[500px] md:h-[600px] rounded-2xl overflow-hidden border-2 border-blue-500/30 shadow-2xl shadow-blue-500/20"></div>
+                        <div id="cesiumContainer" data-hq=move || site.with(home_area) class="w-full h-[500px] md:h-[600px] rounded-2xl overflow-hidden border-2 border-blue-500/30 shadow-2xl shadow-blue-500/20"></div>
                     </div>
                 </section>
 
@@ -57,7 +60,7 @@ pub fn HandymanServiceMap() -> impl IntoView {
                                     <div class="w-5 h-5 rounded-full bg-yellow-400 shadow-lg shadow-yellow-400/50"></div>
                                     <h3 class="text-lg font-bold text-white">"Main Office"</h3>
                                 </div>
-                                <p class="text-blue-200 text-sm">"Coventry - Our headquarters"</p>
+                                <p class="text-blue-200 text-sm">{move || format!("{} - Our headquarters", site.with(home_area))}</p>
                             </div>
                             <div class="bg-blue-900/50 backdrop-blur-sm rounded-xl p-6 border border-blue-500/20">
                                 <div class="flex items-center gap-3 mb-4">
@@ -164,7 +167,7 @@ pub fn HandymanServiceMap() -> impl IntoView {
                             
                             // Service locations
                             var locations = [
-                                { name: 'Coventry (HQ)', lat: 52.4068, lng: -1.5197, isHQ: true, desc: 'Our main headquarters' },
+                                { name: container.dataset.hq + ' (HQ)', lat: 52.4068, lng: -1.5197, isHQ: true, desc: 'Our main headquarters' },
                                 { name: 'Birmingham', lat: 52.4862, lng: -1.8904, isHQ: false, desc: 'Full service coverage' },
                                 { name: 'Warwick', lat: 52.2852, lng: -1.5849, isHQ: false, desc: 'Historic town coverage' },
                                 { name: 'Leamington Spa', lat: 52.2852, lng: -1.5317, isHQ: false, desc: 'Regular service area' },
@@ -1,17 +1,22 @@
 use crate::components::seo::SeoHead;
+use crate::pages::examples::handyman_app::site::{home_area, use_site};
 use leptos::prelude::*;
 use leptos_router::components::A;
 use shared::PageMetadata;
 
 #[component]
 pub fn FurnitureAssembly() -> impl IntoView {
+    let site = use_site();
+
     view! {
-        <SeoHead metadata=PageMetadata {
-            title: "Furniture Assembly Services Coventry | XFTradesmen Handyman".to_string(),
-            description: "Expert flat-pack furniture assembly in Coventry. IKEA, Wayfair, Argos - we build it all perfectly. Save time and stress with our professional service.".to_string(),
-            canonical_url: Some("https://handyman-coventry.co.uk/services/furniture-assembly".to_string()),
-            og_image: Some("/images/services/furniture_assembly.png".to_string()),
-        }/>
+        {move || site.with(|s| view! {
+            <SeoHead metadata=PageMetadata {
+                title: format!("Furniture Assembly Services {} | {}", home_area(s), s.business_name),
+                description: format!("Expert flat-pack furniture assembly in {}. IKEA, Wayfair, Argos - we build it all perfectly. Save time and stress with our professional service.", home_area(s)),
+                canonical_url: Some("https://handyman-coventry.co.uk/services/furniture-assembly".to_string()),
+                og_image: Some("/images/services/furniture_assembly.png".to_string()),
+            }/>
+        })}
 
         <div class="bg-gray-50 min-h-screen">
             // Hero Section
@@ -1,17 +1,22 @@
 use crate::components::seo::SeoHead;
+use crate::pages::examples::handyman_app::site::{home_area, use_site};
 use leptos::prelude::*;
 use leptos_router::components::A;
 use shared::PageMetadata;
 
 #[component]
 pub fn MountingInstallation() -> impl IntoView {
+    let site = use_site();
+
     view! {
-        <SeoHead metadata=PageMetadata {
-            title: "TV Mounting & Wall Installation Coventry | XFTradesmen Handyman".to_string(),
-            description: "Professional TV wall mounting, shelf installation, and picture hanging in Coventry. Safe, secure, and perfectly level. Trust the experts with your walls.".to_string(),
-            canonical_url: Some("https://handyman-coventry.co.uk/services/mounting".to_string()),
-             og_image: Some("/images/services/mounting.png".to_string()),
-        }/>
+        {move || site.with(|s| view! {
+            <SeoHead metadata=PageMetadata {
+                title: format!("TV Mounting & Wall Installation {} | {}", home_area(s), s.business_name),
+                description: format!("Professional TV wall mounting, shelf installation, and picture hanging in {}. Safe, secure, and perfectly level. Trust the experts with your walls.", home_area(s)),
+                canonical_url: Some("https://handyman-coventry.co.uk/services/mounting".to_string()),
+                og_image: Some("/images/services/mounting.png".to_string()),
+            }/>
+        })}
 
         <div class="bg-gray-50 min-h-screen">
              // Hero Section
@@ -1,17 +1,22 @@
 use crate::components::seo::SeoHead;
+use crate::pages::examples::handyman_app::site::{home_area, use_site};
 use leptos::prelude::*;
 use leptos_router::components::A;
 use shared::PageMetadata;
 
 #[component]
 pub fn PlumbingRepairs() -> impl IntoView {
+    let site = use_site();
+
     view! {
-        <SeoHead metadata=PageMetadata {
-            title: "Plumbing Repairs & Maintenance Coventry | XFTradesmen Handyman".to_string(),
-            description: "Reliable plumbing repairs in Coventry. Faucet fixing, leak repairs, sink installation, and blockage clearing. Fast response for your home plumbing needs.".to_string(),
-            canonical_url: Some("https://handyman-coventry.co.uk/services/plumbing".to_string()),
-            og_image: Some("/images/services/plumbing.png".to_string()),
-        }/>
+        {move || site.with(|s| view! {
+            <SeoHead metadata=PageMetadata {
+                title: format!("Plumbing Repairs & Maintenance {} | {}", home_area(s), s.business_name),
+                description: format!("Reliable plumbing repairs in {}. Faucet fixing, leak repairs, sink installation, and blockage clearing. Fast response for your home plumbing needs.", home_area(s)),
+                canonical_url: Some("https://handyman-coventry.co.uk/services/plumbing".to_string()),
+                og_image: Some("/images/services/plumbing.png".to_string()),
+            }/>
+        })}
 
         <div class="bg-gray-50 min-h-screen">
              // Hero Section
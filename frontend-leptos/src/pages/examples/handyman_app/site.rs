@@ -0,0 +1,203 @@
+//! The business the handyman site is rendered for.
+//!
+//! One deployment serves every client's site. The layout loads the
+//! [`SiteConfig`] for the host the page was requested on, and components
+//! read it with [`use_site`] instead of hardcoding a name, phone number or
+//! colours.
+
+use crate::api::site::fetch_site;
+use leptos::prelude::*;
+use shared::{OpeningHours, PageLink, SiteConfig, Testimonial, HANDYMAN_SITE_PATH};
+
+/// The original Coventry Handyman site, shown until the configuration has
+/// loaded and if the backend can't be reached.
+pub fn default_site() -> SiteConfig {
+    SiteConfig {
+        business_name: "Coventry Handyman".to_string(),
+        phone: Some("07833 263486".to_string()),
+        email: Some("hello@coventryhandyman.co.uk".to_string()),
+        tagline: Some("Reliable repairs across Coventry and Warwickshire".to_string()),
+        trade: Some("Handyman".to_string()),
+        areas: vec![PageLink {
+            slug: "coventry".to_string(),
+            name: "Coventry".to_string(),
+        }],
+        opening_hours: vec![OpeningHours {
+            days: "Mon - Sat".to_string(),
+            opens: "08:00".to_string(),
+            closes: "18:00".to_string(),
+        }],
+        testimonials: [
+            (
+                "I cannot recommend XFTradesmen enough. From the initial quote to the final cleanup, everything was handled with professionalism. The quality of work on my new kitchen cabinets is superb.",
+                "James Davidson, Local Guide",
+            ),
+            (
+                "Fixed my leaking tap in 20 minutes. Very polite and tidy.",
+                "Sarah Jenkins, Earlsdon",
+            ),
+            (
+                "Top notch for flat pack assembly. I usually hate doing it myself, so this was a lifesaver. Good price too.",
+                "Mike Ross, ReviewCentre",
+            ),
+            (
+                "We hired them for a full day of odd jobs - hanging pictures, fixing a door handle, and resealing the bath. Got through everything on the list!",
+                "Emily Blunt, Kenilworth",
+            ),
+            (
+                "Arrived on time for an emergency call out. The door lock was jammed and he managed to fix it without damaging the door. Very impressed.",
+                "David Chen, Coventry",
+            ),
+            (
+                "A polite young man who knows his trade. Fixed my garden fence after the storm. It's solid as a rock now.",
+                "Patricia Wilson, Finham",
+            ),
+            (
+                "Great communication. Texted me when he was on his way. Did a clean job replacing the light fixtures.",
+                "Gary Thompson, Wholey",
+            ),
+            (
+                "Very happy with the painting service. The lines are crisp and they covered all the furniture properly.",
+                "Lisa Kudrow, Tile Hill",
+            ),
+            (
+                "Honest pricing. No hidden fees at the end. Will definitely use again for the next project.",
+                "Tom Hardy, Binley",
+            ),
+            (
+                "Friendly, efficient, and did a great job fixing our garden gate. Highly recommended!",
+                "Rebecca Jones, Stoke",
+            ),
+            (
+                "I had a list of small jobs that needed doing for months. He came in and sorted them all in one afternoon. Brilliant service.",
+                "Kevin Smith, Wyken",
+            ),
+            (
+                "Very professional. Cleaned up after himself which is a rarity these days. The new shelf looks perfect.",
+                "Amanda White, Coundon",
+            ),
+            (
+                "Quick response to my enquiry. Arrived on time and fixed the leak under the sink. No fuss.",
+                "Steve Brown, Radford",
+            ),
+            (
+                "Explained exactly what needed doing and how much it would cost before starting. Very trustworthy.",
+                "Natalie Green, Holbrooks",
+            ),
+            (
+                "Replaced all the door handles in my house. Quick, tidy, and a great price.",
+                "Paul Wilson, Cheylesmore",
+            ),
+        ]
+        .into_iter()
+        .map(|(quote, author)| Testimonial {
+            quote: quote.to_string(),
+            author: author.to_string(),
+        })
+        .collect(),
+        site_path: HANDYMAN_SITE_PATH.to_string(),
+        ..Default::default()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SiteContext(Signal<SiteConfig>);
+
+/// Loads this host's site configuration and provides it to the components
+/// below. Returns the resource so the caller can suspend on it.
+pub fn provide_site() -> Resource<Result<SiteConfig, String>> {
+    let config = Resource::new(|| (), |_| fetch_site());
+    let site = Signal::derive(move || {
+        config
+            .get()
+            .and_then(Result::ok)
+            .unwrap_or_else(default_site)
+    });
+    provide_context(SiteContext(site));
+
+    config
+}
+
+/// The site being rendered.
+pub fn use_site() -> Signal<SiteConfig> {
+    use_context::<SiteContext>()
+        .map(|context| context.0)
+        .unwrap_or_else(|| Signal::stored(default_site()))
+}
+
+// The helpers below return closures rather than signals so they follow the
+// configuration as it loads and render as text, attribute values or a
+// `<Title>`.
+
+/// Page title ending in the business name, e.g. "FAQ | Coventry Handyman".
+pub fn page_title(
+    site: Signal<SiteConfig>,
+    page: impl Into<String>,
+) -> impl Fn() -> String + Clone + Send + Sync + 'static {
+    let page = page.into();
+    move || site.with(|s| format!("{} | {}", page, s.business_name))
+}
+
+/// `tel:` link for the site's phone number; `#` when it has none.
+pub fn tel_href(site: Signal<SiteConfig>) -> impl Fn() -> String + Clone + Send + Sync + 'static {
+    move || site.with(|s| s.tel_href().unwrap_or_else(|| "#".to_string()))
+}
+
+/// The phone number as written; empty when the site has none.
+pub fn phone(site: Signal<SiteConfig>) -> impl Fn() -> String + Clone + Send + Sync + 'static {
+    move || site.with(|s| s.phone.clone().unwrap_or_default())
+}
+
+/// An `HH:MM` time on the 12-hour clock, e.g. "08:00" as "8:00 am".
+pub fn clock(time: &str) -> String {
+    let Some((hours, minutes)) = time.split_once(':') else {
+        return time.to_string();
+    };
+    match hours.parse::<u32>() {
+        Ok(h) if h < 24 => {
+            let suffix = if h < 12 { "am" } else { "pm" };
+            let h = if h % 12 == 0 { 12 } else { h % 12 };
+            format!("{}:{} {}", h, minutes, suffix)
+        }
+        _ => time.to_string(),
+    }
+}
+
+/// The area the business is based in, the first it lists; "your area" when
+/// it lists none.
+pub fn home_area(site: &SiteConfig) -> String {
+    site.areas
+        .first()
+        .map(|area| area.name.clone())
+        .unwrap_or_else(|| "your area".to_string())
+}
+
+/// The areas the business covers as a phrase, e.g. "Coventry, Kenilworth
+/// and Warwick".
+pub fn area_list(site: &SiteConfig) -> String {
+    match site.areas.split_last() {
+        None => home_area(site),
+        Some((last, [])) => last.name.clone(),
+        Some((last, rest)) => {
+            let rest: Vec<&str> = rest.iter().map(|area| area.name.as_str()).collect();
+            format!("{} and {}", rest.join(", "), last.name)
+        }
+    }
+}
+
+/// Classes and inline custom properties applying the client's colours over
+/// the template's (see the TENANT BRANDING rules in `input.css`).
+pub fn theme(site: &SiteConfig) -> (String, String) {
+    let mut classes = String::new();
+    let mut style = String::new();
+    if let Some(color) = &site.primary_color {
+        classes.push_str(" tenant-primary");
+        style.push_str(&format!("--brand-primary:{};", color));
+    }
+    if let Some(color) = &site.accent_color {
+        classes.push_str(" tenant-accent");
+        style.push_str(&format!("--brand-accent:{};", color));
+    }
+
+    (classes, style)
+}
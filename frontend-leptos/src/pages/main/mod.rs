@@ -4,6 +4,7 @@ mod about;
 mod contact;
 mod home;
 mod industries;
+mod onboarding;
 mod packages;
 mod pricing;
 
@@ -11,5 +12,6 @@ pub use about::*;
 pub use contact::*;
 pub use home::*;
 pub use industries::*;
+pub use onboarding::*;
 pub use packages::*;
 pub use pricing::*;
@@ -0,0 +1,551 @@
+//! Client onboarding wizard.
+//!
+//! New clients tell us about their business here instead of over email.
+//! Each step is checked with the shared onboarding validation before moving
+//! on, and the finished form is sent to the backend as a draft for us to
+//! review and publish as their site.
+
+use crate::api::onboarding::submit_onboarding;
+use crate::components::seo::SeoHead;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::validation::MAX_ONBOARDING_SERVICES;
+use shared::{
+    OnboardingForm, OnboardingService, OpeningHours, PageMetadata, Testimonial, Validate,
+};
+use std::borrow::Cow;
+
+const STEPS: &[&str] = &[
+    "Business",
+    "Services",
+    "Area & Hours",
+    "Reviews",
+    "Check & Send",
+];
+
+const INPUT_CLASS: &str = "w-full bg-black/40 border border-white/10 rounded-xl px-5 py-4 text-white focus:border-white focus:ring-1 focus:ring-white outline-none transition-all placeholder:text-gray-700 font-medium hover:bg-black/60";
+const LABEL_CLASS: &str = "block text-xs font-bold text-gray-500 mb-2 uppercase tracking-widest";
+const SMALL_BUTTON_CLASS: &str =
+    "text-xs font-bold uppercase tracking-widest text-gray-400 hover:text-white transition-colors";
+
+/// Checks the details entered on a step.
+fn validate_step(form: &OnboardingForm, step: usize) -> Result<(), Cow<'static, str>> {
+    match step {
+        0 => form.validate_business(),
+        1 => form.validate_services(),
+        2 => form.validate_area(),
+        3 => form.validate_extras(),
+        _ => form.validate(),
+    }
+}
+
+/// Parses a price in pounds, e.g. "60" or "59.99", as pence. Anything
+/// that isn't a price is zero, which validation rejects.
+fn pence(pounds: &str) -> i32 {
+    pounds
+        .trim()
+        .trim_start_matches('£')
+        .parse::<f64>()
+        .ok()
+        .filter(|p| p.is_finite() && *p >= 0.0 && *p < 1_000_000.0)
+        .map_or(0, |p| (p * 100.0).round() as i32)
+}
+
+/// Formats pence as pounds for an input, e.g. "60" or "59.99".
+fn pounds(cents: i32) -> String {
+    match cents {
+        0 => String::new(),
+        c if c % 100 == 0 => (c / 100).to_string(),
+        c => format!("{:.2}", c as f64 / 100.0),
+    }
+}
+
+/// Splits "CV21, cv22 CV23" into upper-case districts.
+fn postcode_list(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .map(str::to_uppercase)
+        .collect()
+}
+
+/// A blank form with typical weekday hours filled in.
+fn new_form() -> OnboardingForm {
+    OnboardingForm {
+        services: vec![OnboardingService::default()],
+        opening_hours: vec![OpeningHours {
+            days: "Monday - Friday".to_string(),
+            opens: "08:00".to_string(),
+            closes: "18:00".to_string(),
+        }],
+        ..Default::default()
+    }
+}
+
+/// A labelled text input bound to one field of the form.
+fn text_field(
+    form: RwSignal<OnboardingForm>,
+    label: &'static str,
+    kind: &'static str,
+    placeholder: &'static str,
+    get: fn(&OnboardingForm) -> &String,
+    set: fn(&mut OnboardingForm) -> &mut String,
+) -> impl IntoView {
+    view! {
+        <div>
+            <label class=LABEL_CLASS>{label}</label>
+            <input
+                type=kind
+                class=INPUT_CLASS
+                placeholder=placeholder
+                prop:value=move || form.with(|f| get(f).clone())
+                on:input=move |ev| form.update(|f| *set(f) = event_target_value(&ev))
+            />
+        </div>
+    }
+}
+
+#[component]
+pub fn Onboarding() -> impl IntoView {
+    let form = RwSignal::new(new_form());
+    let (step, set_step) = signal(0usize);
+    let (error_msg, set_error_msg) = signal(Option::<String>::None);
+    let (sending, set_sending) = signal(false);
+    let (receipt, set_receipt) = signal(Option::<i32>::None);
+
+    // Rows are only rebuilt when one is added or removed, so typing in a
+    // row doesn't lose focus.
+    let service_count = Memo::new(move |_| form.with(|f| f.services.len()));
+    let hours_count = Memo::new(move |_| form.with(|f| f.opening_hours.len()));
+    let testimonial_count = Memo::new(move |_| form.with(|f| f.testimonials.len()));
+
+    let next = move |_| match form.with(|f| validate_step(f, step.get_untracked())) {
+        Ok(()) => {
+            set_error_msg.set(None);
+            set_step.update(|s| *s = (*s + 1).min(STEPS.len() - 1));
+        }
+        Err(err) => set_error_msg.set(Some(err.into_owned())),
+    };
+    let back = move |_| {
+        set_error_msg.set(None);
+        set_step.update(|s| *s = s.saturating_sub(1));
+    };
+    let submit = move |_| {
+        let details = form.get_untracked();
+        set_sending.set(true);
+        set_error_msg.set(None);
+        spawn_local(async move {
+            match submit_onboarding(&details).await {
+                Ok(sent) => set_receipt.set(Some(sent.id)),
+                Err(err) => set_error_msg.set(Some(err)),
+            }
+            set_sending.set(false);
+        });
+    };
+
+    let business_step = move || {
+        view! {
+            <div class="grid md:grid-cols-2 gap-6">
+                {text_field(form, "Business Name", "text", "e.g. Jones Plumbing", |f| &f.business_name, |f| &mut f.business_name)}
+                {text_field(form, "Trade", "text", "e.g. Plumber", |f| &f.trade, |f| &mut f.trade)}
+                {text_field(form, "Your Name", "text", "Who we'll be dealing with", |f| &f.contact_name, |f| &mut f.contact_name)}
+                {text_field(form, "Main Town", "text", "e.g. Rugby", |f| &f.town, |f| &mut f.town)}
+                {text_field(form, "Email", "email", "Where customers can reach you", |f| &f.email, |f| &mut f.email)}
+                {text_field(form, "Phone", "tel", "The number customers call", |f| &f.phone, |f| &mut f.phone)}
+                <div class="md:col-span-2">
+                    <label class=LABEL_CLASS>"Logo URL (optional)"</label>
+                    <input
+                        type="url"
+                        class=INPUT_CLASS
+                        placeholder="https://... or leave blank and email it to us"
+                        prop:value=move || form.with(|f| f.logo_url.clone().unwrap_or_default())
+                        on:input=move |ev| {
+                            let url = event_target_value(&ev);
+                            form.update(|f| f.logo_url = Some(url).filter(|u| !u.trim().is_empty()));
+                        }
+                    />
+                </div>
+            </div>
+        }
+    };
+
+    let services_step = move || {
+        view! {
+            <div class="space-y-6">
+                <p class="text-gray-400">"What you offer, and what it usually costs. You can change these later."</p>
+                {move || (0..service_count.get()).map(|i| view! {
+                    <div class="bg-black/30 border border-white/10 rounded-2xl p-6 space-y-4">
+                        <div class="grid md:grid-cols-4 gap-4">
+                            <div class="md:col-span-2">
+                                <label class=LABEL_CLASS>"Service"</label>
+                                <input
+                                    type="text"
+                                    class=INPUT_CLASS
+                                    placeholder="e.g. Boiler Servicing"
+                                    prop:value=move || form.with(|f| f.services.get(i).map(|s| s.name.clone()).unwrap_or_default())
+                                    on:input=move |ev| form.update(|f| f.services[i].name = event_target_value(&ev))
+                                />
+                            </div>
+                            <div>
+                                <label class=LABEL_CLASS>"From (£)"</label>
+                                <input
+                                    type="number"
+                                    min="0"
+                                    step="0.01"
+                                    class=INPUT_CLASS
+                                    placeholder="60"
+                                    prop:value=move || form.with(|f| f.services.get(i).map(|s| pounds(s.price_from_cents)).unwrap_or_default())
+                                    on:change=move |ev| form.update(|f| f.services[i].price_from_cents = pence(&event_target_value(&ev)))
+                                />
+                            </div>
+                            <div>
+                                <label class=LABEL_CLASS>"To (£)"</label>
+                                <input
+                                    type="number"
+                                    min="0"
+                                    step="0.01"
+                                    class=INPUT_CLASS
+                                    placeholder="90"
+                                    prop:value=move || form.with(|f| f.services.get(i).map(|s| pounds(s.price_to_cents)).unwrap_or_default())
+                                    on:change=move |ev| form.update(|f| f.services[i].price_to_cents = pence(&event_target_value(&ev)))
+                                />
+                            </div>
+                        </div>
+                        <textarea
+                            class=format!("{INPUT_CLASS} h-24 resize-none")
+                            placeholder="What the service covers"
+                            prop:value=move || form.with(|f| f.services.get(i).map(|s| s.description.clone()).unwrap_or_default())
+                            on:input=move |ev| form.update(|f| f.services[i].description = event_target_value(&ev))
+                        ></textarea>
+                        <button type="button" class=SMALL_BUTTON_CLASS on:click=move |_| form.update(|f| { f.services.remove(i); })>
+                            "Remove"
+                        </button>
+                    </div>
+                }).collect_view()}
+                <button
+                    type="button"
+                    class=SMALL_BUTTON_CLASS
+                    disabled=move || service_count.get() >= MAX_ONBOARDING_SERVICES
+                    on:click=move |_| form.update(|f| f.services.push(OnboardingService::default()))
+                >
+                    "+ Add a service"
+                </button>
+            </div>
+        }
+    };
+
+    let area_step = move || {
+        view! {
+            <div class="space-y-8">
+                <div>
+                    <label class=LABEL_CLASS>"Postcode Districts You Cover"</label>
+                    <input
+                        type="text"
+                        class=format!("{INPUT_CLASS} uppercase")
+                        placeholder="e.g. CV21, CV22, CV23"
+                        prop:value=move || form.with(|f| f.postcodes.join(", "))
+                        on:change=move |ev| form.update(|f| f.postcodes = postcode_list(&event_target_value(&ev)))
+                    />
+                    <p class="mt-2 text-sm text-gray-500">"Just the first half of the postcode, separated by commas"</p>
+                </div>
+                <div class="space-y-4">
+                    <label class=LABEL_CLASS>"Opening Hours"</label>
+                    {move || (0..hours_count.get()).map(|i| view! {
+                        <div class="grid grid-cols-12 gap-3 items-center">
+                            <input
+                                type="text"
+                                class=format!("{INPUT_CLASS} col-span-12 md:col-span-6")
+                                placeholder="e.g. Monday - Friday"
+                                prop:value=move || form.with(|f| f.opening_hours.get(i).map(|h| h.days.clone()).unwrap_or_default())
+                                on:input=move |ev| form.update(|f| f.opening_hours[i].days = event_target_value(&ev))
+                            />
+                            <input
+                                type="time"
+                                class=format!("{INPUT_CLASS} col-span-5 md:col-span-2")
+                                prop:value=move || form.with(|f| f.opening_hours.get(i).map(|h| h.opens.clone()).unwrap_or_default())
+                                on:change=move |ev| form.update(|f| f.opening_hours[i].opens = event_target_value(&ev))
+                            />
+                            <input
+                                type="time"
+                                class=format!("{INPUT_CLASS} col-span-5 md:col-span-2")
+                                prop:value=move || form.with(|f| f.opening_hours.get(i).map(|h| h.closes.clone()).unwrap_or_default())
+                                on:change=move |ev| form.update(|f| f.opening_hours[i].closes = event_target_value(&ev))
+                            />
+                            <button type="button" class=format!("{SMALL_BUTTON_CLASS} col-span-2") on:click=move |_| form.update(|f| { f.opening_hours.remove(i); })>
+                                "Remove"
+                            </button>
+                        </div>
+                    }).collect_view()}
+                    <button
+                        type="button"
+                        class=SMALL_BUTTON_CLASS
+                        on:click=move |_| form.update(|f| f.opening_hours.push(OpeningHours {
+                            opens: "08:00".to_string(),
+                            closes: "18:00".to_string(),
+                            ..Default::default()
+                        }))
+                    >
+                        "+ Add hours"
+                    </button>
+                    <p class="text-sm text-gray-500">"Days you don't list show as closed"</p>
+                </div>
+            </div>
+        }
+    };
+
+    let reviews_step = move || {
+        view! {
+            <div class="space-y-8">
+                <div>
+                    <label class=LABEL_CLASS>"Accreditations (optional, one per line)"</label>
+                    <textarea
+                        class=format!("{INPUT_CLASS} h-28 resize-none")
+                        placeholder="e.g. Gas Safe Registered"
+                        prop:value=move || form.with(|f| f.accreditations.join("\n"))
+                        on:change=move |ev| {
+                            let lines = event_target_value(&ev);
+                            form.update(|f| {
+                                f.accreditations = lines
+                                    .lines()
+                                    .map(str::trim)
+                                    .filter(|a| !a.is_empty())
+                                    .map(str::to_string)
+                                    .collect();
+                            });
+                        }
+                    ></textarea>
+                </div>
+                <div class="space-y-4">
+                    <label class=LABEL_CLASS>"Testimonials (optional)"</label>
+                    {move || (0..testimonial_count.get()).map(|i| view! {
+                        <div class="bg-black/30 border border-white/10 rounded-2xl p-6 space-y-4">
+                            <textarea
+                                class=format!("{INPUT_CLASS} h-24 resize-none")
+                                placeholder="What the customer said"
+                                prop:value=move || form.with(|f| f.testimonials.get(i).map(|t| t.quote.clone()).unwrap_or_default())
+                                on:input=move |ev| form.update(|f| f.testimonials[i].quote = event_target_value(&ev))
+                            ></textarea>
+                            <input
+                                type="text"
+                                class=INPUT_CLASS
+                                placeholder="Who said it, e.g. Sue, Hillmorton"
+                                prop:value=move || form.with(|f| f.testimonials.get(i).map(|t| t.author.clone()).unwrap_or_default())
+                                on:input=move |ev| form.update(|f| f.testimonials[i].author = event_target_value(&ev))
+                            />
+                            <button type="button" class=SMALL_BUTTON_CLASS on:click=move |_| form.update(|f| { f.testimonials.remove(i); })>
+                                "Remove"
+                            </button>
+                        </div>
+                    }).collect_view()}
+                    <button
+                        type="button"
+                        class=SMALL_BUTTON_CLASS
+                        on:click=move |_| form.update(|f| f.testimonials.push(Testimonial {
+                            quote: String::new(),
+                            author: String::new(),
+                        }))
+                    >
+                        "+ Add a testimonial"
+                    </button>
+                </div>
+            </div>
+        }
+    };
+
+    let check_step = move || {
+        let f = form.get();
+        let services = f
+            .services
+            .iter()
+            .map(|s| {
+                format!(
+                    "{} (£{} - £{})",
+                    s.name.trim(),
+                    pounds(s.price_from_cents),
+                    pounds(s.price_to_cents)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let hours = f
+            .opening_hours
+            .iter()
+            .map(|h| format!("{} {}-{}", h.days.trim(), h.opens, h.closes))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let rows = [
+            (
+                "Business",
+                format!(
+                    "{}, {} in {}",
+                    f.business_name.trim(),
+                    f.trade.trim(),
+                    f.town.trim()
+                ),
+            ),
+            (
+                "Contact",
+                format!(
+                    "{}, {}, {}",
+                    f.contact_name.trim(),
+                    f.email.trim(),
+                    f.phone.trim()
+                ),
+            ),
+            ("Services", services),
+            ("Postcodes", f.postcodes.join(", ")),
+            ("Opening Hours", hours),
+            ("Accreditations", f.accreditations.join(", ")),
+            ("Testimonials", f.testimonials.len().to_string()),
+        ];
+        view! {
+            <dl class="divide-y divide-white/10">
+                {rows.into_iter().map(|(label, value)| view! {
+                    <div class="py-4 grid md:grid-cols-3 gap-2">
+                        <dt class=LABEL_CLASS>{label}</dt>
+                        <dd class="md:col-span-2 text-white font-medium">
+                            {if value.is_empty() { "-".to_string() } else { value }}
+                        </dd>
+                    </div>
+                }).collect_view()}
+            </dl>
+        }
+    };
+
+    view! {
+        <SeoHead metadata=PageMetadata {
+            title: "Set Up Your Website | XF Tradesmen".to_string(),
+            description: "Tell us about your business, services and prices and we'll build your new trade website.".to_string(),
+            canonical_url: Some("https://xftradesman.com/onboarding".to_string()),
+            og_image: None,
+        }/>
+
+        <section class="bg-black border-t border-white/10 py-24 px-6 relative font-sans min-h-screen">
+            <div class="absolute inset-0 bg-[radial-gradient(circle_at_center,_var(--tw-gradient-stops))] from-brand/10 via-black to-black opacity-50"></div>
+
+            <div class="max-w-4xl mx-auto relative z-10 w-full">
+                <div class="mb-12 text-center">
+                    <h1 class="text-5xl md:text-7xl font-black tracking-tighter mb-4 uppercase text-transparent bg-clip-text bg-gradient-to-b from-white via-white to-gray-600">
+                        "Your New Site"
+                    </h1>
+                    <p class="text-xl text-gray-400 font-medium">
+                        "Tell us about your business and we'll have your website ready to review."
+                    </p>
+                </div>
+
+                {move || match receipt.get() {
+                    Some(id) => view! {
+                        <div class="bg-white/5 border border-white/10 p-12 rounded-3xl text-center space-y-4">
+                            <h2 class="text-3xl font-black text-white">"Thanks, we've got everything"</h2>
+                            <p class="text-gray-400">
+                                "We'll review your details and email you when your site is ready. Your reference is "
+                                <span class="text-white font-bold">{format!("#{id}")}</span>
+                                "."
+                            </p>
+                        </div>
+                    }
+                    .into_any(),
+                    None => view! {
+                        // Progress
+                        <ol class="flex flex-wrap justify-center gap-3 mb-8">
+                            {STEPS.iter().enumerate().map(|(i, name)| view! {
+                                <li class=move || format!(
+                                    "px-4 py-2 rounded-full text-xs font-bold uppercase tracking-widest border {}",
+                                    if step.get() == i {
+                                        "border-brand bg-brand/20 text-white"
+                                    } else if step.get() > i {
+                                        "border-white/20 text-gray-300"
+                                    } else {
+                                        "border-white/10 text-gray-600"
+                                    }
+                                )>
+                                    {format!("{}. {}", i + 1, name)}
+                                </li>
+                            }).collect_view()}
+                        </ol>
+
+                        <div class="bg-white/5 border border-white/10 p-8 md:p-12 rounded-3xl backdrop-blur-2xl shadow-2xl space-y-8">
+                            {move || match step.get() {
+                                0 => business_step().into_any(),
+                                1 => services_step().into_any(),
+                                2 => area_step().into_any(),
+                                3 => reviews_step().into_any(),
+                                _ => check_step().into_any(),
+                            }}
+
+                            {move || error_msg.get().map(|msg| view! {
+                                <div class="bg-brand/10 border border-brand/20 text-brand-light p-4 rounded-xl font-medium">
+                                    {msg}
+                                </div>
+                            })}
+
+                            <div class="flex justify-between gap-4 pt-2">
+                                <button
+                                    type="button"
+                                    class="border border-white/20 text-white font-black uppercase tracking-widest px-8 py-4 rounded-xl hover:bg-white/10 transition-all text-sm disabled:opacity-30"
+                                    disabled=move || step.get() == 0 || sending.get()
+                                    on:click=back
+                                >
+                                    "Back"
+                                </button>
+                                {move || if step.get() + 1 < STEPS.len() {
+                                    view! {
+                                        <button
+                                            type="button"
+                                            class="border border-white/20 text-white font-black uppercase tracking-widest px-8 py-4 rounded-xl hover:bg-white hover:text-black transition-all text-sm"
+                                            on:click=next
+                                        >
+                                            "Next"
+                                        </button>
+                                    }
+                                    .into_any()
+                                } else {
+                                    view! {
+                                        <button
+                                            type="button"
+                                            class="bg-brand text-white font-black uppercase tracking-widest px-8 py-4 rounded-xl hover:bg-brand-light transition-all text-sm disabled:opacity-50"
+                                            disabled=move || sending.get()
+                                            on:click=submit
+                                        >
+                                            {move || if sending.get() { "SENDING..." } else { "SEND MY DETAILS" }}
+                                        </button>
+                                    }
+                                    .into_any()
+                                }}
+                            </div>
+                        </div>
+                    }
+                    .into_any(),
+                }}
+            </div>
+        </section>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pence() {
+        assert_eq!(pence("60"), 6000);
+        assert_eq!(pence(" £59.99 "), 5999);
+        assert_eq!(pence("abc"), 0);
+        assert_eq!(pence("-5"), 0);
+    }
+
+    #[test]
+    fn test_postcode_list() {
+        assert_eq!(
+            postcode_list("cv21, CV22  cv23,"),
+            vec!["CV21", "CV22", "CV23"]
+        );
+    }
+
+    #[test]
+    fn test_new_form_needs_business_details() {
+        let form = new_form();
+        assert!(validate_step(&form, 0).is_err());
+        assert!(validate_step(&form, 3).is_ok());
+    }
+}
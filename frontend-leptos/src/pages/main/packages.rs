@@ -111,6 +111,10 @@ pub fn Packages() -> impl IntoView {
                                 </a>
 
                                 <p class="text-center text-gray-500 text-xs mt-4">"Secure payment via Stripe. Cancel anytime."</p>
+                                <p class="text-center text-gray-500 text-xs mt-2">
+                                    "Already signed up? "
+                                    <a href="/onboarding" class="text-white underline hover:text-brand-light">"Send us your business details"</a>
+                                </p>
                             </div>
                         </div>
 
@@ -0,0 +1,184 @@
+//! The site's sitemap.
+//!
+//! `/sitemap.xml` lists every page search engines should find: the static
+//! routes in the Leptos route list, the live blog posts, and the handyman
+//! site's area and service-in-town pages. Blog posts and landing pages are
+//! last modified when their content last changed, the blog index when its
+//! newest post did, and they list the images in their content. Content is
+//! the requested host's tenant's, URLs are on that site's origin (see
+//! [`site_origin`]), and the [`NOINDEX_ROUTES`](crate::NOINDEX_ROUTES) are
+//! left out.
+//!
+//! When there are more URLs than one sitemap file may hold, `/sitemap.xml`
+//! is an index of the files at `/sitemaps/{n}.xml`.
+
+use crate::api::site::{request_host, site_for_host};
+use crate::indexing::site_origin;
+use crate::is_noindex_path;
+use axum::extract::Path;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use leptos_axum::AxumRouteListing;
+use serde::de::DeserializeOwned;
+use shared::{ApiResponse, BlogPost, Sitemap, SitemapUrl, HANDYMAN_SITE_PATH};
+use std::sync::Arc;
+
+/// `/sitemap.xml` and `/sitemaps/{n}.xml` for the app's `routes`.
+pub fn routes<S>(routes: &[AxumRouteListing]) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let paths: Arc<[String]> = static_paths(routes.iter().map(|route| route.path())).into();
+    let index_paths = paths.clone();
+
+    Router::new()
+        .route(
+            "/sitemap.xml",
+            get(move |headers: HeaderMap| sitemap_handler(index_paths.clone(), headers)),
+        )
+        .route(
+            "/sitemaps/{file}",
+            get(move |Path(file): Path<String>, headers: HeaderMap| {
+                sitemap_file_handler(paths.clone(), file, headers)
+            }),
+        )
+}
+
+/// Handler for /sitemap.xml
+async fn sitemap_handler(static_paths: Arc<[String]>, headers: HeaderMap) -> Response {
+    let host = request_host(&headers).unwrap_or_default();
+
+    xml_response(sitemap(&static_paths, host).await.xml())
+}
+
+/// Handler for /sitemaps/{n}.xml
+async fn sitemap_file_handler(
+    static_paths: Arc<[String]>,
+    file: String,
+    headers: HeaderMap,
+) -> Response {
+    let Some(page) = file.strip_suffix(".xml").and_then(|n| n.parse().ok()) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let host = request_host(&headers).unwrap_or_default();
+
+    match sitemap(&static_paths, host).await.page_xml(page) {
+        Some(xml) => xml_response(xml),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+// region:    --- Sitemap Helpers
+
+/// Every page of the site on `host`, from `static_paths` and the backend's
+/// content for that host's tenant. Content the backend can't supply is left
+/// out rather than failing the sitemap.
+async fn sitemap(static_paths: &[String], host: &str) -> Sitemap {
+    let site = site_for_host(host).await;
+    let site_path = site
+        .as_ref()
+        .map_or(HANDYMAN_SITE_PATH, |site| site.site_path.as_str());
+    let posts: Vec<BlogPost> = fetch(host, "/api/blog", "Blog posts").await;
+    let landing_pages: Vec<SitemapUrl> = fetch(host, "/api/landing-pages", "Landing pages").await;
+    let newest_post = posts
+        .iter()
+        .filter_map(|post| post.sitemap_url().lastmod)
+        .max();
+
+    let mut urls: Vec<SitemapUrl> = static_paths
+        .iter()
+        .map(|path| SitemapUrl {
+            lastmod: newest_post.clone().filter(|_| path == "/blog"),
+            ..SitemapUrl::new(path.as_str())
+        })
+        .collect();
+    urls.extend(posts.iter().map(BlogPost::sitemap_url));
+    urls.extend(landing_pages.into_iter().map(|url| SitemapUrl {
+        path: format!("{}{}", site_path, url.path),
+        ..url
+    }));
+
+    Sitemap::new(&site_origin(host, site.as_ref()), urls)
+}
+
+/// The routes without parameters, less the noindex ones.
+fn static_paths<'a>(routes: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut paths: Vec<String> = routes
+        .into_iter()
+        .filter(|path| !path.contains(['{', '*', ':']))
+        .map(|path| match path.trim_end_matches('/') {
+            "" => "/".to_string(),
+            path => path.to_string(),
+        })
+        .filter(|path| !is_noindex_path(path))
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// The data from a backend endpoint for the tenant serving `host`, or
+/// nothing if it can't be had.
+async fn fetch<T: DeserializeOwned + Default>(host: &str, path: &str, what: &str) -> T {
+    let api_url = std::env::var("API_URL").unwrap_or_else(|_| "http://127.0.0.1:8080".to_string());
+    let response = reqwest::Client::new()
+        .get(format!("{}{}", api_url, path))
+        .header("x-forwarded-host", host)
+        .send()
+        .await;
+    let response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("{} left out of sitemap: {}", what, e);
+            return T::default();
+        }
+    };
+
+    match response.json::<ApiResponse<T>>().await {
+        Ok(body) => body.data.unwrap_or_default(),
+        Err(e) => {
+            log::warn!("{} left out of sitemap: {}", what, e);
+            T::default()
+        }
+    }
+}
+
+fn xml_response(xml: String) -> Response {
+    ([(header::CONTENT_TYPE, "application/xml")], xml).into_response()
+}
+
+// endregion: --- Sitemap Helpers
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_paths() {
+        let paths = static_paths([
+            "/",
+            "/blog",
+            "/blog/{slug}",
+            "/onboarding",
+            "/handyman-coventry/",
+            "/handyman-coventry/admin",
+            "/handyman-coventry/admin/customers",
+            "/handyman-coventry/administration",
+            "/handyman-coventry/services/{service}/{town}",
+            "/blog",
+        ]);
+
+        assert_eq!(
+            paths,
+            [
+                "/",
+                "/blog",
+                "/handyman-coventry",
+                "/handyman-coventry/administration"
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+-- ============================================================================
+-- Full-text and fuzzy search across customers, bookings and quotes
+-- ============================================================================
+--
+-- Adds generated `search_vector` columns (weighted tsvectors) and pg_trgm
+-- indexes used by `SearchBmc::search` for the admin search box.
+--
+-- Weights:
+-- - A: primary label (customer name, quote title, booking service type)
+-- - B: contact details (email, phone)
+-- - C: free text (addresses, notes, quote line items)
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Bookings previously dropped the notes captured by the booking form
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS notes TEXT;
+
+-- -- Customers
+ALTER TABLE customers ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(email, '')), 'B') ||
+        setweight(to_tsvector('simple', coalesce(phone, '')), 'B') ||
+        setweight(to_tsvector('simple', coalesce(addresses::text, '')), 'C') ||
+        setweight(to_tsvector('english', coalesce(notes, '')), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_customers_search ON customers USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_customers_name_trgm ON customers USING GIN (name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_customers_email_trgm ON customers USING GIN (email gin_trgm_ops);
+-- Phone numbers are matched on national digits only so "07833 263486" finds "+44 7833 263486"
+CREATE INDEX IF NOT EXISTS idx_customers_phone_digits_trgm
+    ON customers USING GIN (
+        (regexp_replace(regexp_replace(coalesce(phone, ''), '[^0-9]', '', 'g'), '^(44|0)', '')) gin_trgm_ops
+    );
+CREATE INDEX IF NOT EXISTS idx_customers_addresses_trgm
+    ON customers USING GIN ((coalesce(addresses::text, '')) gin_trgm_ops);
+
+-- -- Bookings
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(service_type, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(notes, '')), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_bookings_search ON bookings USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_bookings_service_type_trgm ON bookings USING GIN (service_type gin_trgm_ops);
+
+-- -- Quotes
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+        setweight(jsonb_to_tsvector('english', coalesce(items::jsonb, '[]'::jsonb), '["string"]'), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_quotes_search ON quotes USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_quotes_title_trgm ON quotes USING GIN (title gin_trgm_ops);
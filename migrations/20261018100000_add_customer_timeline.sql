@@ -0,0 +1,77 @@
+-- ============================================================================
+-- Customer activity timeline
+-- ============================================================================
+--
+-- Adds the tables `TimelineBmc::list_for_customer` merges into a single feed:
+--
+-- - status_changes: quote and booking status transitions (filled by triggers)
+-- - email_log:      every email the application sends
+-- - payments:       payments received against quotes and bookings
+
+-- -- Status changes
+CREATE TABLE IF NOT EXISTS status_changes (
+    id SERIAL PRIMARY KEY,
+    entity VARCHAR(20) NOT NULL,                 -- 'quote' or 'booking'
+    entity_id INTEGER NOT NULL,
+    from_status VARCHAR(50),
+    to_status VARCHAR(50) NOT NULL,
+    changed_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_status_changes_entity ON status_changes(entity, entity_id);
+
+-- Records a status transition whenever a quote or booking status is updated
+CREATE OR REPLACE FUNCTION record_status_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status IS DISTINCT FROM OLD.status THEN
+        INSERT INTO status_changes (entity, entity_id, from_status, to_status)
+        VALUES (TG_ARGV[0], NEW.id, OLD.status, NEW.status);
+    END IF;
+    RETURN NEW;
+END;
+$$ language 'plpgsql';
+
+DROP TRIGGER IF EXISTS record_quotes_status_change ON quotes;
+CREATE TRIGGER record_quotes_status_change AFTER UPDATE OF status ON quotes
+    FOR EACH ROW EXECUTE FUNCTION record_status_change('quote');
+
+DROP TRIGGER IF EXISTS record_bookings_status_change ON bookings;
+CREATE TRIGGER record_bookings_status_change AFTER UPDATE OF status ON bookings
+    FOR EACH ROW EXECUTE FUNCTION record_status_change('booking');
+
+-- -- Email log
+CREATE TABLE IF NOT EXISTS email_log (
+    id SERIAL PRIMARY KEY,
+    customer_id INTEGER REFERENCES customers(id) ON DELETE SET NULL,
+    to_email VARCHAR(255) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    kind VARCHAR(50) NOT NULL,                   -- e.g. 'contact_notification'
+    related_email VARCHAR(255),                  -- Customer the email is about, if not the recipient
+    status VARCHAR(20) NOT NULL DEFAULT 'sent',  -- 'sent' or 'failed'
+    sent_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_log_customer ON email_log(customer_id);
+CREATE INDEX IF NOT EXISTS idx_email_log_to_email ON email_log(lower(to_email));
+CREATE INDEX IF NOT EXISTS idx_email_log_related_email ON email_log(lower(related_email));
+
+-- -- Payments
+CREATE TABLE IF NOT EXISTS payments (
+    id SERIAL PRIMARY KEY,
+    customer_id INTEGER REFERENCES customers(id) ON DELETE SET NULL,
+    quote_id INTEGER REFERENCES quotes(id) ON DELETE SET NULL,
+    booking_id INTEGER REFERENCES bookings(id) ON DELETE SET NULL,
+    amount_cents INTEGER NOT NULL,
+    method VARCHAR(50) NOT NULL,                 -- 'card', 'bank_transfer', 'cash'
+    reference VARCHAR(255),                      -- Stripe PaymentIntent ID, bank reference...
+    status VARCHAR(20) NOT NULL DEFAULT 'succeeded',
+    paid_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_payments_customer ON payments(customer_id);
+
+-- -- Timeline lookups
+CREATE INDEX IF NOT EXISTS idx_bookings_customer ON bookings(customer_id);
+CREATE INDEX IF NOT EXISTS idx_quotes_customer ON quotes(customer_id);
+CREATE INDEX IF NOT EXISTS idx_contact_submissions_email ON contact_submissions(lower(email));
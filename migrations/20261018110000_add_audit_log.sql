@@ -0,0 +1,19 @@
+-- ============================================================================
+-- Audit log
+-- ============================================================================
+--
+-- Append-only record of sensitive admin operations (subject access exports,
+-- erasures, retention purges). Included in subject access exports and
+-- anonymised by erasure like every other table holding an email address.
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id SERIAL PRIMARY KEY,
+    action VARCHAR(50) NOT NULL,                 -- e.g. 'gdpr_export', 'gdpr_erasure'
+    actor VARCHAR(255) NOT NULL,                 -- Who performed the action
+    subject_email VARCHAR(255),                  -- Data subject, NULL once erased
+    details JSONB,                               -- Action-specific details (row counts...)
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_subject_email ON audit_log(lower(subject_email));
+CREATE INDEX IF NOT EXISTS idx_audit_log_action ON audit_log(action);
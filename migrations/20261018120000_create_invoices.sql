@@ -0,0 +1,62 @@
+-- ============================================================================
+-- Invoices and credit notes
+-- ============================================================================
+--
+-- Invoices are generated from completed bookings (using the accepted quote's
+-- line items when there is one) and numbered only when issued, so issued
+-- numbers are sequential with no gaps. Credit notes live in the same table
+-- with their own sequence.
+
+-- -- Gap-free numbering
+-- One row per document sequence. Numbers are taken with
+-- `UPDATE ... RETURNING` inside the issuing transaction, so a rolled-back
+-- issue never consumes a number.
+CREATE TABLE IF NOT EXISTS document_counters (
+    kind VARCHAR(20) PRIMARY KEY,                -- 'invoice' or 'credit_note'
+    next_number INTEGER NOT NULL DEFAULT 1
+);
+
+INSERT INTO document_counters (kind, next_number) VALUES
+    ('invoice', 1),
+    ('credit_note', 1)
+ON CONFLICT (kind) DO NOTHING;
+
+-- -- Invoices
+CREATE TABLE IF NOT EXISTS invoices (
+    id SERIAL PRIMARY KEY,
+    number VARCHAR(20) UNIQUE,                   -- INV-000001 / CN-000001, NULL while draft
+    kind VARCHAR(20) NOT NULL DEFAULT 'invoice', -- 'invoice' or 'credit_note'
+    credited_invoice_id INTEGER REFERENCES invoices(id),
+    customer_id INTEGER REFERENCES customers(id) ON DELETE SET NULL,
+    booking_id INTEGER REFERENCES bookings(id) ON DELETE SET NULL,
+    quote_id INTEGER REFERENCES quotes(id) ON DELETE SET NULL,
+    items JSONB NOT NULL DEFAULT '[]',
+    subtotal_cents INTEGER NOT NULL,
+    discount_cents INTEGER NOT NULL DEFAULT 0,
+    vat_registered BOOLEAN NOT NULL DEFAULT FALSE,
+    vat_rate_bp INTEGER NOT NULL DEFAULT 0,      -- Basis points: 2000 = 20%
+    vat_cents INTEGER NOT NULL DEFAULT 0,
+    total_cents INTEGER NOT NULL,
+    paid_cents INTEGER NOT NULL DEFAULT 0,
+    credited_cents INTEGER NOT NULL DEFAULT 0,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft', -- draft, issued, paid, overdue, void
+    notes TEXT,
+    issue_date DATE,
+    due_date DATE,
+    reminder_count INTEGER NOT NULL DEFAULT 0,
+    last_reminder_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoices_customer ON invoices(customer_id);
+CREATE INDEX IF NOT EXISTS idx_invoices_booking ON invoices(booking_id);
+CREATE INDEX IF NOT EXISTS idx_invoices_status_due ON invoices(status, due_date);
+
+DROP TRIGGER IF EXISTS update_invoices_updated_at ON invoices;
+CREATE TRIGGER update_invoices_updated_at BEFORE UPDATE ON invoices
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Payments against invoices
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS invoice_id INTEGER REFERENCES invoices(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_payments_invoice ON payments(invoice_id);
@@ -0,0 +1,53 @@
+-- ============================================================================
+-- Promo codes, discounts and per-line VAT
+-- ============================================================================
+--
+-- Quote and invoice totals come from one pricing function
+-- (`lib_core::pricing`). Line discounts and VAT rates live on each item in
+-- the `items` JSONB; quote-level discounts and promo codes are stored here.
+
+-- -- Promo codes
+CREATE TABLE IF NOT EXISTS promo_codes (
+    id SERIAL PRIMARY KEY,
+    code VARCHAR(40) NOT NULL UNIQUE,            -- Stored upper-case
+    description TEXT,
+    discount_kind VARCHAR(10) NOT NULL,          -- 'fixed' (cents) or 'percent' (basis points)
+    discount_value INTEGER NOT NULL CHECK (discount_value >= 0),
+    valid_from DATE,                             -- Inclusive
+    valid_until DATE,                            -- Inclusive
+    max_uses INTEGER,                            -- NULL = unlimited
+    uses INTEGER NOT NULL DEFAULT 0,
+    min_subtotal_cents INTEGER NOT NULL DEFAULT 0,
+    service_types TEXT[] NOT NULL DEFAULT '{}',  -- Empty = any service
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT promo_codes_kind_check CHECK (discount_kind IN ('fixed', 'percent'))
+);
+
+DROP TRIGGER IF EXISTS update_promo_codes_updated_at ON promo_codes;
+CREATE TRIGGER update_promo_codes_updated_at BEFORE UPDATE ON promo_codes
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Redemptions
+-- One row per quote a code was applied to; `promo_codes.uses` is the
+-- counter enforced against `max_uses`.
+CREATE TABLE IF NOT EXISTS promo_redemptions (
+    id SERIAL PRIMARY KEY,
+    promo_code_id INTEGER NOT NULL REFERENCES promo_codes(id) ON DELETE CASCADE,
+    quote_id INTEGER REFERENCES quotes(id) ON DELETE SET NULL,
+    discount_cents INTEGER NOT NULL,
+    redeemed_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_promo_redemptions_code ON promo_redemptions(promo_code_id);
+
+-- -- Quote pricing
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS service_type VARCHAR(50);
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS discount JSONB;
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS promo_code_id INTEGER REFERENCES promo_codes(id) ON DELETE SET NULL;
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS vat_cents INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS vat_breakdown JSONB NOT NULL DEFAULT '[]';
+
+-- -- Invoice VAT by rate
+ALTER TABLE invoices ADD COLUMN IF NOT EXISTS vat_breakdown JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,59 @@
+-- ============================================================================
+-- Quote templates
+-- ============================================================================
+--
+-- Reusable quotes for common jobs. Template items may take their quantity
+-- from a variable (hours, number of doors, ...) that the admin fills in
+-- when creating a quote from the template.
+
+-- -- Templates
+CREATE TABLE IF NOT EXISTS quote_templates (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(200) NOT NULL,
+    service_type VARCHAR(50) NOT NULL,
+    items JSONB NOT NULL DEFAULT '[]',           -- [{description, quantity, quantity_variable, unit_price, ...}]
+    variables JSONB NOT NULL DEFAULT '[]',       -- [{name, label, default, min, max}]
+    valid_days INTEGER NOT NULL DEFAULT 30,
+    notes TEXT,                                  -- Copied onto quotes created from the template
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_quote_templates_service ON quote_templates(service_type);
+
+DROP TRIGGER IF EXISTS update_quote_templates_updated_at ON quote_templates;
+CREATE TRIGGER update_quote_templates_updated_at BEFORE UPDATE ON quote_templates
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Quotes created from templates
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS notes TEXT;
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS template_id INTEGER REFERENCES quote_templates(id) ON DELETE SET NULL;
+
+-- -- Starter templates (previously hardcoded in the API)
+INSERT INTO quote_templates (name, service_type, items, variables)
+SELECT name, service_type, items::jsonb, variables::jsonb
+FROM (VALUES
+    ('Leaky Tap Repair', 'plumbing',
+     '[{"description": "Call-out fee", "quantity": 1, "unit_price": 3000},
+       {"description": "Labour ({hours} hours)", "quantity": 1, "quantity_variable": "hours", "unit_price": 4500},
+       {"description": "Materials", "quantity": 1, "unit_price": 500}]',
+     '[{"name": "hours", "label": "Hours of labour", "default": 1, "min": 1, "max": 8}]'),
+    ('Light Fitting Installation', 'electrical',
+     '[{"description": "Call-out fee", "quantity": 1, "unit_price": 3000},
+       {"description": "Labour ({hours} hours)", "quantity": 1, "quantity_variable": "hours", "unit_price": 5000},
+       {"description": "Standard light fitting", "quantity": 1, "quantity_variable": "fittings", "unit_price": 2500}]',
+     '[{"name": "hours", "label": "Hours of labour", "default": 1, "min": 1, "max": 8},
+       {"name": "fittings", "label": "Number of fittings", "default": 1, "min": 1, "max": 20}]'),
+    ('IKEA Furniture Assembly (Small)', 'assembly',
+     '[{"description": "Assembly service", "quantity": 1, "quantity_variable": "items", "unit_price": 4500}]',
+     '[{"name": "items", "label": "Pieces of furniture", "default": 1, "min": 1, "max": 10}]'),
+    ('TV Wall Mount', 'general',
+     '[{"description": "TV mounting service", "quantity": 1, "unit_price": 6500}]',
+     '[]'),
+    ('Door Hanging', 'carpentry',
+     '[{"description": "Call-out fee", "quantity": 1, "unit_price": 3000},
+       {"description": "Door hanging labour", "quantity": 1, "quantity_variable": "doors", "unit_price": 7500}]',
+     '[{"name": "doors", "label": "Number of doors", "default": 1, "min": 1, "max": 10}]')
+) AS seed(name, service_type, items, variables)
+WHERE NOT EXISTS (SELECT 1 FROM quote_templates);
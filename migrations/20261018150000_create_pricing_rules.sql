@@ -0,0 +1,72 @@
+-- ============================================================================
+-- Instant quote pricing rules
+-- ============================================================================
+--
+-- Base price ranges per service and the rules that adjust them (urgency,
+-- job size, out-of-hours, weekend, distance zone). The API and the Leptos
+-- quote calculator both read these tables, so prices live in one place.
+
+-- -- Service base ranges
+CREATE TABLE IF NOT EXISTS service_rates (
+    service_type VARCHAR(50) PRIMARY KEY,
+    label VARCHAR(100) NOT NULL,
+    base_low_cents INTEGER NOT NULL CHECK (base_low_cents >= 0),
+    base_high_cents INTEGER NOT NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    CHECK (base_high_cents >= base_low_cents)
+);
+
+DROP TRIGGER IF EXISTS update_service_rates_updated_at ON service_rates;
+CREATE TRIGGER update_service_rates_updated_at BEFORE UPDATE ON service_rates
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Rules
+CREATE TABLE IF NOT EXISTS pricing_rules (
+    id SERIAL PRIMARY KEY,
+    label VARCHAR(100) NOT NULL,
+    condition_kind VARCHAR(20) NOT NULL
+        CHECK (condition_kind IN ('urgency', 'job_size', 'out_of_hours', 'weekend', 'distance_zone')),
+    condition_value VARCHAR(50),                 -- e.g. 'same_day', 'large', '2'; NULL for flags
+    service_type VARCHAR(50) REFERENCES service_rates(service_type) ON DELETE CASCADE,
+    adjustment_kind VARCHAR(10) NOT NULL CHECK (adjustment_kind IN ('fixed', 'percent')),
+    adjustment_value INTEGER NOT NULL,           -- Cents or basis points, may be negative
+    cap_cents INTEGER CHECK (cap_cents >= 0),    -- Largest change the rule may make
+    priority INTEGER NOT NULL DEFAULT 100,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_pricing_rules_priority ON pricing_rules(priority, id) WHERE active;
+
+DROP TRIGGER IF EXISTS update_pricing_rules_updated_at ON pricing_rules;
+CREATE TRIGGER update_pricing_rules_updated_at BEFORE UPDATE ON pricing_rules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Starter catalog (previously hardcoded in the API and the calculator)
+INSERT INTO service_rates (service_type, label, base_low_cents, base_high_cents, sort_order)
+VALUES
+    ('plumbing', 'Plumbing', 4500, 12000, 10),
+    ('electrical', 'Electrical', 5000, 15000, 20),
+    ('carpentry', 'Carpentry', 6000, 18000, 30),
+    ('assembly', 'Furniture Assembly', 3500, 8500, 40),
+    ('painting', 'Painting & Decorating', 8000, 25000, 50),
+    ('general', 'General Repairs', 4000, 10000, 60)
+ON CONFLICT (service_type) DO NOTHING;
+
+INSERT INTO pricing_rules
+    (label, condition_kind, condition_value, adjustment_kind, adjustment_value, cap_cents, priority)
+SELECT *
+FROM (VALUES
+    ('Larger job', 'job_size', 'large', 'percent', 5000, 10000, 10),
+    ('Same-day call-out', 'urgency', 'same_day', 'fixed', 1500, NULL::INTEGER, 20),
+    ('Flexible timing discount', 'urgency', 'flexible', 'fixed', -500, NULL::INTEGER, 20),
+    ('Out-of-hours work', 'out_of_hours', NULL, 'percent', 2500, 5000, 30),
+    ('Weekend work', 'weekend', NULL, 'fixed', 2000, NULL::INTEGER, 40),
+    ('Travel (zone 2)', 'distance_zone', '2', 'fixed', 1000, NULL::INTEGER, 50),
+    ('Travel (zone 3)', 'distance_zone', '3', 'fixed', 2500, NULL::INTEGER, 50)
+) AS seed(label, condition_kind, condition_value, adjustment_kind, adjustment_value, cap_cents, priority)
+WHERE NOT EXISTS (SELECT 1 FROM pricing_rules);
@@ -0,0 +1,52 @@
+-- ============================================================================
+-- Quote revisions
+-- ============================================================================
+--
+-- A quote is snapshotted when it is first sent. Editing it afterwards bumps
+-- `quotes.revision` and stores a new snapshot together with what changed
+-- since the previous one. Snapshots are immutable; acceptance records the
+-- revision the customer accepted.
+
+-- -- Current and accepted revision on quotes
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS revision INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS accepted_revision INTEGER;
+
+-- -- Snapshots
+CREATE TABLE IF NOT EXISTS quote_revisions (
+    id SERIAL PRIMARY KEY,
+    quote_id INTEGER NOT NULL REFERENCES quotes(id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    items JSONB NOT NULL DEFAULT '[]',
+    subtotal_cents INTEGER NOT NULL,
+    discount_cents INTEGER NOT NULL DEFAULT 0,
+    vat_cents INTEGER NOT NULL DEFAULT 0,
+    vat_breakdown JSONB NOT NULL DEFAULT '[]',
+    total_cents INTEGER NOT NULL,
+    notes TEXT,
+    valid_until DATE,
+    changes JSONB,                               -- Diff from the previous revision (NULL for the first)
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (quote_id, revision)
+);
+
+-- Revisions are a record of what the customer was shown: never edit them
+CREATE OR REPLACE FUNCTION reject_quote_revision_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'quote revisions are immutable';
+END;
+$$ language 'plpgsql';
+
+DROP TRIGGER IF EXISTS quote_revisions_immutable ON quote_revisions;
+CREATE TRIGGER quote_revisions_immutable BEFORE UPDATE ON quote_revisions
+    FOR EACH ROW EXECUTE FUNCTION reject_quote_revision_update();
+
+-- -- Snapshot quotes that customers have already seen
+INSERT INTO quote_revisions (quote_id, revision, title, items, subtotal_cents, discount_cents,
+                             vat_cents, vat_breakdown, total_cents, notes, valid_until)
+SELECT id, revision, title, items, subtotal_cents, discount_cents, vat_cents,
+       vat_breakdown, total_cents, notes, valid_until
+FROM quotes
+WHERE status <> 'draft'
+ON CONFLICT (quote_id, revision) DO NOTHING;
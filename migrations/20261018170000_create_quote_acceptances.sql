@@ -0,0 +1,35 @@
+-- ============================================================================
+-- Quote acceptances
+-- ============================================================================
+--
+-- Evidence of a customer accepting a quote: who signed (typed name and an
+-- optional drawn signature), when, from where (IP address and user agent)
+-- and a SHA-256 hash of the exact revision they accepted. Records are
+-- immutable and outlive edits to the quote; an accepted quote cannot be
+-- deleted while its acceptance exists.
+
+CREATE TABLE IF NOT EXISTS quote_acceptances (
+    id SERIAL PRIMARY KEY,
+    quote_id INTEGER NOT NULL UNIQUE REFERENCES quotes(id) ON DELETE RESTRICT,
+    revision INTEGER NOT NULL,
+    signer_name VARCHAR(255) NOT NULL,
+    signature_jpeg BYTEA,                        -- Drawn signature, if one was given
+    ip_address VARCHAR(45),                      -- Fits IPv6
+    user_agent TEXT,
+    content_hash CHAR(64) NOT NULL,              -- Hex SHA-256 of the accepted revision
+    customer_notes TEXT,
+    accepted_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (quote_id, revision) REFERENCES quote_revisions(quote_id, revision)
+);
+
+-- Acceptances are evidence: never edit them
+CREATE OR REPLACE FUNCTION reject_quote_acceptance_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'quote acceptances are immutable';
+END;
+$$ language 'plpgsql';
+
+DROP TRIGGER IF EXISTS quote_acceptances_immutable ON quote_acceptances;
+CREATE TRIGGER quote_acceptances_immutable BEFORE UPDATE ON quote_acceptances
+    FOR EACH ROW EXECUTE FUNCTION reject_quote_acceptance_update();
@@ -0,0 +1,21 @@
+-- ============================================================================
+-- Quote options and add-ons
+-- ============================================================================
+--
+-- A quote can offer alternatives ("Repair tap" vs "Replace mixer"), each
+-- with its own line items on top of the quote's shared items, plus optional
+-- add-ons the customer can tick. Acceptance records the option and add-ons
+-- chosen; the quote is repriced for that selection and the booking and
+-- invoice are built from it.
+
+-- -- Options, add-ons and the customer's selection on quotes
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS options JSONB NOT NULL DEFAULT '[]';
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS add_ons JSONB NOT NULL DEFAULT '[]';
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS selection JSONB;
+
+-- -- Revisions snapshot them with the rest of the content
+ALTER TABLE quote_revisions ADD COLUMN IF NOT EXISTS options JSONB NOT NULL DEFAULT '[]';
+ALTER TABLE quote_revisions ADD COLUMN IF NOT EXISTS add_ons JSONB NOT NULL DEFAULT '[]';
+
+-- -- Acceptances record what was chosen
+ALTER TABLE quote_acceptances ADD COLUMN IF NOT EXISTS selection JSONB NOT NULL DEFAULT '{}';
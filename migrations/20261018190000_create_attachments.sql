@@ -0,0 +1,32 @@
+-- ============================================================================
+-- Attachments
+-- ============================================================================
+--
+-- Photos and documents attached to a booking (before/after shots, receipts)
+-- or a quote (photos of the job to price). Only metadata lives here; the
+-- bytes are in the configured blob store under `storage_key`, with a JPEG
+-- preview under `thumbnail_key` for images. Uploads are stored with their
+-- metadata (EXIF, GPS) stripped, so `sha256` is of the stored file.
+
+CREATE TABLE IF NOT EXISTS attachments (
+    id SERIAL PRIMARY KEY,
+    booking_id INTEGER REFERENCES bookings(id) ON DELETE CASCADE,
+    quote_id INTEGER REFERENCES quotes(id) ON DELETE CASCADE,
+    category VARCHAR(20) NOT NULL DEFAULT 'other'
+        CHECK (category IN ('before', 'after', 'receipt', 'other')),
+    filename VARCHAR(255) NOT NULL,              -- Sanitised original filename
+    content_type VARCHAR(100) NOT NULL,          -- Sniffed, not the client's claim
+    size_bytes INTEGER NOT NULL,
+    sha256 CHAR(64) NOT NULL,                    -- Hex SHA-256 of the stored bytes
+    width INTEGER,                               -- Images only
+    height INTEGER,
+    storage_key VARCHAR(512) NOT NULL UNIQUE,
+    thumbnail_key VARCHAR(512),
+    uploaded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Belongs to exactly one booking or quote
+    CHECK ((booking_id IS NULL) <> (quote_id IS NULL))
+);
+
+-- -- Indexes for listing a job's attachments
+CREATE INDEX IF NOT EXISTS idx_attachments_booking_id ON attachments(booking_id);
+CREATE INDEX IF NOT EXISTS idx_attachments_quote_id ON attachments(quote_id);
@@ -0,0 +1,46 @@
+-- ============================================================================
+-- Materials and per-job usage
+-- ============================================================================
+--
+-- A catalogue of materials and parts with what they cost us and what we
+-- charge, and the quantities each booking used. Usage lines snapshot the
+-- catalogue's description, unit and prices when they are recorded, so later
+-- price changes never rewrite a finished job. Billable lines are added to
+-- the booking's invoice; all lines count towards the job's material cost in
+-- the margin report.
+
+-- -- Catalogue
+CREATE TABLE IF NOT EXISTS materials (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    supplier VARCHAR(255),
+    unit VARCHAR(20) NOT NULL DEFAULT 'each',    -- each, m, box, litre, ...
+    cost_cents INTEGER NOT NULL CHECK (cost_cents >= 0),
+    sell_cents INTEGER NOT NULL CHECK (sell_cents >= 0),
+    vat_rate_bp INTEGER,                         -- NULL = the default rate
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+DROP TRIGGER IF EXISTS update_materials_updated_at ON materials;
+CREATE TRIGGER update_materials_updated_at BEFORE UPDATE ON materials
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Usage per booking
+CREATE TABLE IF NOT EXISTS booking_materials (
+    id SERIAL PRIMARY KEY,
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    material_id INTEGER REFERENCES materials(id) ON DELETE SET NULL,
+    description VARCHAR(255) NOT NULL,
+    unit VARCHAR(20) NOT NULL DEFAULT 'each',
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    unit_cost_cents INTEGER NOT NULL CHECK (unit_cost_cents >= 0),
+    unit_price_cents INTEGER NOT NULL CHECK (unit_price_cents >= 0),
+    vat_rate_bp INTEGER,
+    billable BOOLEAN NOT NULL DEFAULT TRUE,      -- FALSE when the quote already covers it
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_booking_materials_booking ON booking_materials(booking_id);
+CREATE INDEX IF NOT EXISTS idx_booking_materials_material ON booking_materials(material_id);
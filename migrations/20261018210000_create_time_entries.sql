@@ -0,0 +1,35 @@
+-- ============================================================================
+-- Job time entries
+-- ============================================================================
+--
+-- Time each technician spent on a booking, one row per stretch of work.
+-- Starting or resuming a job opens an entry (`ended_at` NULL); pausing or
+-- stopping closes it, so a job split over two visits is simply several
+-- entries. Timestamps are set by the server, and a booking's
+-- `actual_duration` is recomputed from its entries whenever one closes.
+--
+-- A technician can only be on one job at a time: their entries may not
+-- overlap, and at most one can be open.
+
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+CREATE TABLE IF NOT EXISTS time_entries (
+    id SERIAL PRIMARY KEY,
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ended_at TIMESTAMP WITH TIME ZONE,           -- NULL while the clock is running
+    ended_by VARCHAR(10)                         -- pause or stop
+        CHECK (ended_by IN ('pause', 'stop')),
+    CHECK ((ended_at IS NULL) = (ended_by IS NULL)),
+    CHECK (ended_at IS NULL OR ended_at >= started_at),
+    -- No technician works two stretches at once
+    CONSTRAINT time_entries_no_overlap EXCLUDE USING gist (
+        user_id WITH =,
+        tstzrange(started_at, COALESCE(ended_at, 'infinity'::TIMESTAMPTZ)) WITH &&
+    )
+);
+
+-- -- Indexes for a job's entries and a technician's timesheet
+CREATE INDEX IF NOT EXISTS idx_time_entries_booking ON time_entries(booking_id);
+CREATE INDEX IF NOT EXISTS idx_time_entries_user_started ON time_entries(user_id, started_at);
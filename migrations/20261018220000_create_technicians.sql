@@ -0,0 +1,38 @@
+-- ============================================================================
+-- Technicians, skills and job assignment
+-- ============================================================================
+--
+-- A technician is a user with a working profile: the skills they can be
+-- sent out for (matched against `bookings.service_type`) and the postcode
+-- areas or districts they cover. Bookings get a site postcode so jobs can
+-- be matched to technicians' areas, and can be assigned to one or more
+-- technicians.
+
+-- -- Job site
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS postcode VARCHAR(10);   -- Canonical, e.g. 'SW1A 1AA'
+
+-- -- Technician profiles
+CREATE TABLE IF NOT EXISTS technicians (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    display_name VARCHAR(255) NOT NULL,
+    phone VARCHAR(50),
+    skills TEXT[] NOT NULL DEFAULT '{}',         -- Service types, e.g. {plumbing,electrical}
+    areas TEXT[] NOT NULL DEFAULT '{}',          -- Postcode areas or districts, e.g. {SW,E14}
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+DROP TRIGGER IF EXISTS update_technicians_updated_at ON technicians;
+CREATE TRIGGER update_technicians_updated_at BEFORE UPDATE ON technicians
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Assignments
+CREATE TABLE IF NOT EXISTS booking_assignments (
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES technicians(user_id) ON DELETE CASCADE,
+    assigned_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (booking_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_booking_assignments_user ON booking_assignments(user_id);
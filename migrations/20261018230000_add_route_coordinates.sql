@@ -0,0 +1,16 @@
+-- ============================================================================
+-- Coordinates for daily route planning
+-- ============================================================================
+--
+-- Route planning orders a technician's jobs for a day by driving distance,
+-- so each job site needs a location and each technician can have a base
+-- (home or depot) the day starts from. Jobs without coordinates are left
+-- out of the route and listed separately.
+
+-- -- Job site
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS latitude DOUBLE PRECISION;
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS longitude DOUBLE PRECISION;
+
+-- -- Technician base
+ALTER TABLE technicians ADD COLUMN IF NOT EXISTS base_latitude DOUBLE PRECISION;
+ALTER TABLE technicians ADD COLUMN IF NOT EXISTS base_longitude DOUBLE PRECISION;
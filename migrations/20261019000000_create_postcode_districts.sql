@@ -0,0 +1,18 @@
+-- ============================================================================
+-- Postcode districts
+-- ============================================================================
+--
+-- Where each postcode district (outcode) is, imported offline from an open
+-- postcode dataset (`api --import-postcodes <csv>`). Coverage checks measure
+-- the distance from the service centre to a district's centre, and job
+-- sites get coordinates from their district when none are given.
+
+CREATE TABLE IF NOT EXISTS postcode_districts (
+    outcode VARCHAR(4) PRIMARY KEY,              -- e.g. 'CV1'
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    district VARCHAR(255),                       -- Town or local authority
+    imported_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT postcode_districts_location_check
+        CHECK (latitude BETWEEN -90 AND 90 AND longitude BETWEEN -180 AND 180)
+);
@@ -0,0 +1,116 @@
+-- ============================================================================
+-- Area and service landing pages
+-- ============================================================================
+--
+-- The towns we cover and the services we offer, as data. The site renders
+-- an `/areas/{slug}` page for every active area, and a
+-- `/services/{service}/{town}` page for each service and area pair that
+-- has its own introduction; pairs without one have no page, so we never
+-- publish near-identical pages that differ only by town name. All of them
+-- are listed in the sitemap.
+
+-- -- Areas
+CREATE TABLE IF NOT EXISTS service_areas (
+    id SERIAL PRIMARY KEY,
+    slug VARCHAR(100) NOT NULL UNIQUE,           -- e.g. 'leamington-spa'
+    name VARCHAR(100) NOT NULL,
+    description TEXT NOT NULL,
+    postcodes TEXT[] NOT NULL DEFAULT '{}',      -- Districts, e.g. {CV31,CV32}
+    travel_time VARCHAR(50),                     -- e.g. 'Under 20 mins'
+    testimonial_quote TEXT,
+    testimonial_author VARCHAR(100),
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+DROP TRIGGER IF EXISTS update_service_areas_updated_at ON service_areas;
+CREATE TRIGGER update_service_areas_updated_at BEFORE UPDATE ON service_areas
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Services
+CREATE TABLE IF NOT EXISTS services (
+    id SERIAL PRIMARY KEY,
+    slug VARCHAR(100) NOT NULL UNIQUE,           -- e.g. 'plumbing'
+    name VARCHAR(100) NOT NULL,
+    summary VARCHAR(255) NOT NULL,               -- One line, for cards and meta descriptions
+    description TEXT NOT NULL,
+    service_type VARCHAR(50) REFERENCES service_rates(service_type) ON DELETE SET NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+DROP TRIGGER IF EXISTS update_services_updated_at ON services;
+CREATE TRIGGER update_services_updated_at BEFORE UPDATE ON services
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Service x area pages
+CREATE TABLE IF NOT EXISTS service_area_pages (
+    service_id INTEGER NOT NULL REFERENCES services(id) ON DELETE CASCADE,
+    area_id INTEGER NOT NULL REFERENCES service_areas(id) ON DELETE CASCADE,
+    intro TEXT NOT NULL,                         -- Copy unique to this service in this town
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (service_id, area_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_service_area_pages_area ON service_area_pages(area_id);
+
+DROP TRIGGER IF EXISTS update_service_area_pages_updated_at ON service_area_pages;
+CREATE TRIGGER update_service_area_pages_updated_at BEFORE UPDATE ON service_area_pages
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- -- Starter content (previously hardcoded in the Leptos area and service pages)
+INSERT INTO service_areas
+    (slug, name, description, postcodes, travel_time, testimonial_quote, testimonial_author, sort_order)
+VALUES
+    ('coventry', 'Coventry',
+     'We''re based in Coventry and serve all areas of the city, from the city centre to suburbs like Earlsdon, Tile Hill, and Canley.',
+     '{CV1,CV2,CV3,CV4,CV5,CV6}', 'Under 20 mins',
+     'Brilliant service! Fixed our leaky tap in no time. Very professional.', 'John S., CV6', 10),
+    ('birmingham', 'Birmingham',
+     'We regularly serve South Birmingham areas including Solihull borders, Hall Green, and Acocks Green. Quick access via A45.',
+     '{B26,B27,B28,B33,B34,B36}', '30-45 mins',
+     'Great handyman, arrived on time and did an excellent job. Will use again!', 'Sarah M., B27', 20),
+    ('solihull', 'Solihull',
+     'Solihull is one of our most popular service areas. We cover Shirley, Olton, Knowle, and surrounding areas.',
+     '{B90,B91,B92,B93,B94}', '25-35 mins',
+     'Second time using them, excellent as always. Highly recommend.', 'David K., B91', 30)
+ON CONFLICT (slug) DO NOTHING;
+
+INSERT INTO services (slug, name, summary, description, service_type, sort_order)
+VALUES
+    ('plumbing', 'Plumbing',
+     'Expert plumbing repairs and installations',
+     'Our qualified plumbers handle everything from leaky taps to complete bathroom installations. We arrive on time, keep your home clean, and get the job done right first time.',
+     'plumbing', 10),
+    ('electrical', 'Electrical',
+     'Safe and certified electrical work',
+     'Our electricians are fully certified and insured. From light fitting to full rewires, we ensure all work meets current regulations and is completed to the highest safety standards.',
+     'electrical', 20),
+    ('carpentry', 'Carpentry',
+     'Quality carpentry and woodwork',
+     'Our skilled carpenters create beautiful, functional woodwork. From door hanging to custom shelving, we take pride in craftsmanship and attention to detail.',
+     'carpentry', 30),
+    ('assembly', 'Furniture Assembly',
+     'Professional flatpack assembly',
+     'Save time and frustration with our expert assembly service. We build IKEA, Wayfair, and all flatpack furniture quickly and correctly. All fixings tightened, all pieces aligned.',
+     'assembly', 40)
+ON CONFLICT (slug) DO NOTHING;
+
+INSERT INTO service_area_pages (service_id, area_id, intro)
+SELECT s.id, a.id, p.intro
+FROM (VALUES
+    ('plumbing', 'coventry',
+     'Coventry''s post-war semis and Victorian terraces in Earlsdon and Chapelfields keep us busy with tired stopcocks, lead supply pipes and bathrooms from another era. We''re ten minutes from most of the city, so a dripping tap rarely waits more than a day.'),
+    ('electrical', 'coventry',
+     'From new sockets in Tile Hill kitchens to outdoor lighting for Cheylesmore gardens, our Coventry electrical work is certified, tidy and usually booked within the week.'),
+    ('assembly', 'solihull',
+     'New-build estates around Shirley and Dickens Heath mean a lot of flatpack. We assemble wardrobes, beds and home office furniture across Solihull, and take the packaging away with us.')
+) AS p(service_slug, area_slug, intro)
+JOIN services s ON s.slug = p.service_slug
+JOIN service_areas a ON a.slug = p.area_slug
+ON CONFLICT (service_id, area_id) DO NOTHING;
@@ -0,0 +1,141 @@
+-- ============================================================================
+-- Tenants: one deployment serving many tradesman sites
+-- ============================================================================
+--
+-- Each tenant is a client business with its own domains, contact details
+-- and branding. Every tenant-owned table carries a `tenant_id` and a row
+-- level security policy, and the API's connections for a tenant run as the
+-- `app_tenant` role with `app.tenant_id` set, so every query only sees and
+-- writes that tenant's rows. Tenant 1 is the original Coventry Handyman
+-- site and owns all existing data.
+--
+-- Tables created after this migration opt in with
+-- `SELECT enable_tenant_isolation('table_name');`.
+--
+-- Needs PostgreSQL 15+ (column lists on ON DELETE SET NULL), and the
+-- migrating user must be able to create roles.
+
+-- -- Tenants
+CREATE TABLE IF NOT EXISTS tenants (
+    id SERIAL PRIMARY KEY,
+    slug VARCHAR(100) NOT NULL UNIQUE,           -- e.g. 'coventry-handyman'
+    business_name VARCHAR(255) NOT NULL,
+    domains TEXT[] NOT NULL DEFAULT '{}',        -- Lowercase hosts, e.g. {jonesplumbing.co.uk}
+    phone VARCHAR(50),
+    email VARCHAR(255),
+    tagline VARCHAR(255),
+    primary_color VARCHAR(7),                    -- '#rrggbb'; NULL keeps the site template's
+    accent_color VARCHAR(7),
+    logo_url VARCHAR(512),
+    centre_latitude DOUBLE PRECISION,            -- Where coverage distances are measured from
+    centre_longitude DOUBLE PRECISION,           -- (default: SERVICE_CENTRE_* settings)
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_tenants_domains ON tenants USING GIN (domains);
+
+DROP TRIGGER IF EXISTS update_tenants_updated_at ON tenants;
+CREATE TRIGGER update_tenants_updated_at BEFORE UPDATE ON tenants
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+INSERT INTO tenants (id, slug, business_name, phone, email, tagline)
+VALUES (1, 'coventry-handyman', 'Coventry Handyman', '07833 263486',
+        'hello@coventryhandyman.co.uk', 'Reliable repairs across Coventry and Warwickshire')
+ON CONFLICT (id) DO NOTHING;
+
+SELECT setval(pg_get_serial_sequence('tenants', 'id'), GREATEST((SELECT MAX(id) FROM tenants), 1));
+
+-- -- Role the API uses for tenant connections
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'app_tenant') THEN
+        CREATE ROLE app_tenant NOLOGIN;
+    END IF;
+END
+$$;
+
+GRANT app_tenant TO CURRENT_USER;
+GRANT USAGE ON SCHEMA public TO app_tenant;
+GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA public TO app_tenant;
+GRANT USAGE, SELECT, UPDATE ON ALL SEQUENCES IN SCHEMA public TO app_tenant;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    GRANT SELECT, INSERT, UPDATE, DELETE ON TABLES TO app_tenant;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    GRANT USAGE, SELECT, UPDATE ON SEQUENCES TO app_tenant;
+
+-- -- Isolation
+CREATE OR REPLACE FUNCTION current_tenant_id() RETURNS INTEGER
+    LANGUAGE sql STABLE
+AS $$
+    SELECT NULLIF(current_setting('app.tenant_id', true), '')::INTEGER
+$$;
+
+-- Adds `tenant_id` (existing rows go to tenant 1, new rows to the current
+-- tenant) and a policy limiting the table to the current tenant's rows.
+CREATE OR REPLACE FUNCTION enable_tenant_isolation(tbl REGCLASS) RETURNS VOID
+    LANGUAGE plpgsql
+AS $$
+BEGIN
+    EXECUTE format(
+        'ALTER TABLE %s ADD COLUMN IF NOT EXISTS tenant_id INTEGER NOT NULL DEFAULT 1 REFERENCES tenants(id)',
+        tbl);
+    EXECUTE format('ALTER TABLE %s ALTER COLUMN tenant_id SET DEFAULT current_tenant_id()', tbl);
+    EXECUTE format('CREATE INDEX IF NOT EXISTS %I ON %s (tenant_id)',
+        'idx_' || replace(tbl::TEXT, '"', '') || '_tenant', tbl);
+    EXECUTE format('ALTER TABLE %s ENABLE ROW LEVEL SECURITY', tbl);
+    EXECUTE format('DROP POLICY IF EXISTS tenant_isolation ON %s', tbl);
+    EXECUTE format(
+        'CREATE POLICY tenant_isolation ON %s USING (tenant_id = current_tenant_id()) '
+        'WITH CHECK (tenant_id = current_tenant_id())',
+        tbl);
+END
+$$;
+
+SELECT enable_tenant_isolation(t)
+FROM unnest(ARRAY[
+    'attachments', 'audit_log', 'booking_assignments', 'booking_materials', 'bookings',
+    'contact_submissions', 'customers', 'document_counters', 'email_log', 'invoices',
+    'materials', 'payments', 'pricing_rules', 'promo_codes', 'promo_redemptions',
+    'quote_acceptances', 'quote_revisions', 'quote_templates', 'quotes',
+    'service_area_pages', 'service_areas', 'service_rates', 'services', 'status_changes',
+    'technicians', 'time_entries', 'users'
+]::REGCLASS[]) AS t;
+
+-- -- Uniqueness is per tenant
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_username_key;
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_email_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_tenant_username ON users(tenant_id, username);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_tenant_email ON users(tenant_id, email);
+
+ALTER TABLE customers DROP CONSTRAINT IF EXISTS customers_email_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_customers_tenant_email ON customers(tenant_id, email);
+
+ALTER TABLE promo_codes DROP CONSTRAINT IF EXISTS promo_codes_code_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_promo_codes_tenant_code ON promo_codes(tenant_id, code);
+
+ALTER TABLE invoices DROP CONSTRAINT IF EXISTS invoices_number_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_invoices_tenant_number ON invoices(tenant_id, number);
+
+ALTER TABLE services DROP CONSTRAINT IF EXISTS services_slug_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_services_tenant_slug ON services(tenant_id, slug);
+
+ALTER TABLE service_areas DROP CONSTRAINT IF EXISTS service_areas_slug_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_service_areas_tenant_slug ON service_areas(tenant_id, slug);
+
+ALTER TABLE document_counters DROP CONSTRAINT IF EXISTS document_counters_pkey;
+ALTER TABLE document_counters ADD PRIMARY KEY (tenant_id, kind);
+
+-- Each tenant prices its own services, so rules and landing page services
+-- reference a rate within the same tenant.
+ALTER TABLE pricing_rules DROP CONSTRAINT IF EXISTS pricing_rules_service_type_fkey;
+ALTER TABLE services DROP CONSTRAINT IF EXISTS services_service_type_fkey;
+ALTER TABLE service_rates DROP CONSTRAINT IF EXISTS service_rates_pkey;
+ALTER TABLE service_rates ADD PRIMARY KEY (tenant_id, service_type);
+ALTER TABLE pricing_rules ADD CONSTRAINT pricing_rules_service_type_fkey
+    FOREIGN KEY (tenant_id, service_type) REFERENCES service_rates(tenant_id, service_type)
+    ON DELETE CASCADE;
+ALTER TABLE services ADD CONSTRAINT services_service_type_fkey
+    FOREIGN KEY (tenant_id, service_type) REFERENCES service_rates(tenant_id, service_type)
+    ON DELETE SET NULL (service_type);
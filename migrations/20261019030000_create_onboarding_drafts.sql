@@ -0,0 +1,39 @@
+-- ============================================================================
+-- Client onboarding
+-- ============================================================================
+--
+-- New clients fill in the onboarding wizard instead of emailing us their
+-- details. Each submission is kept as a draft until an admin reviews it and
+-- publishes it, which creates the client's tenant with its services, area
+-- and prices. Drafts belong to the platform's own tenant.
+
+-- -- What a client's site shows beyond contact details and branding
+ALTER TABLE tenants ADD COLUMN IF NOT EXISTS trade VARCHAR(100);
+ALTER TABLE tenants ADD COLUMN IF NOT EXISTS opening_hours JSONB NOT NULL DEFAULT '[]';
+ALTER TABLE tenants ADD COLUMN IF NOT EXISTS accreditations TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE tenants ADD COLUMN IF NOT EXISTS testimonials JSONB NOT NULL DEFAULT '[]';
+
+UPDATE tenants
+SET trade = 'Handyman',
+    opening_hours = '[{"days": "Monday - Saturday", "opens": "08:00", "closes": "18:00"}]'
+WHERE id = 1 AND trade IS NULL;
+
+-- -- Drafts
+CREATE TABLE IF NOT EXISTS onboarding_drafts (
+    id SERIAL PRIMARY KEY,
+    form JSONB NOT NULL,                         -- shared::OnboardingForm
+    status VARCHAR(20) NOT NULL DEFAULT 'submitted'
+        CHECK (status IN ('submitted', 'published')),
+    published_tenant_id INTEGER REFERENCES tenants(id) ON DELETE SET NULL,
+    published_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_onboarding_drafts_status ON onboarding_drafts(status);
+
+DROP TRIGGER IF EXISTS update_onboarding_drafts_updated_at ON onboarding_drafts;
+CREATE TRIGGER update_onboarding_drafts_updated_at BEFORE UPDATE ON onboarding_drafts
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+SELECT enable_tenant_isolation('onboarding_drafts');
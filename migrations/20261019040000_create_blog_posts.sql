@@ -0,0 +1,221 @@
+-- ============================================================================
+-- Blog posts
+-- ============================================================================
+--
+-- Posts used to be compiled into the frontend. They now live here, so they
+-- can be drafted, edited and scheduled without a redeploy: a post is shown
+-- once its status is 'published', or once it is 'scheduled' and its
+-- `publish_at` has passed.
+--
+-- A `blog_posts` table was created in production by a migration whose file
+-- was lost (see 20251205022700_create_blog_posts_table.sql). Nothing reads
+-- it and its columns are unknown, so it is kept aside as
+-- `blog_posts_legacy` rather than altered.
+
+DO $$
+BEGIN
+    IF to_regclass('blog_posts') IS NOT NULL
+        AND NOT EXISTS (
+            SELECT 1 FROM information_schema.columns
+            WHERE table_name = 'blog_posts' AND column_name = 'publish_at'
+        ) THEN
+        ALTER TABLE blog_posts RENAME TO blog_posts_legacy;
+    END IF;
+END
+$$;
+
+CREATE TABLE IF NOT EXISTS blog_posts (
+    id SERIAL PRIMARY KEY,
+    slug VARCHAR(100) NOT NULL,                  -- e.g. 'local-seo-guide'
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,                   -- For cards and meta descriptions
+    category VARCHAR(50) NOT NULL,               -- shared::BLOG_CATEGORIES slug
+    keywords TEXT[] NOT NULL DEFAULT '{}',
+    author VARCHAR(100) NOT NULL,
+    body TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft'
+        CHECK (status IN ('draft', 'scheduled', 'published')),
+    publish_at TIMESTAMP WITH TIME ZONE,         -- Required when scheduled
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    CHECK (status <> 'scheduled' OR publish_at IS NOT NULL)
+);
+
+DROP TRIGGER IF EXISTS update_blog_posts_updated_at ON blog_posts;
+CREATE TRIGGER update_blog_posts_updated_at BEFORE UPDATE ON blog_posts
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+SELECT enable_tenant_isolation('blog_posts');
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_blog_posts_tenant_slug ON blog_posts(tenant_id, slug);
+CREATE INDEX IF NOT EXISTS idx_blog_posts_publish_at ON blog_posts(publish_at DESC);
+
+-- -- The posts that were compiled into the frontend, for the platform's own site
+INSERT INTO blog_posts
+    (tenant_id, slug, title, description, category, keywords, author, status, publish_at, body)
+VALUES
+    (1, 'why-tradesmen-need-websites',
+     'Why Tradesmen with Websites Earn 40% More',
+     'New 2024 statistics reveal the massive earnings gap between tradesmen with professional websites and those without. Learn why digital presence is no longer optional.',
+     'revenue-data', ARRAY['tradesman website', 'tradesman earnings', 'website ROI', 'digital presence'],
+     'XF Tradesmen', 'published', '2024-12-15 09:00:00+00',
+     $body$<p class="lead text-xl text-gray-300 mb-8 font-light leading-relaxed">
+    In 2024, the data is clear: tradesmen with professional websites are earning significantly more than their competitors who rely solely on word-of-mouth. Here's why making the digital leap is no longer optional.
+</p>
+
+<h2>The Statistics Don't Lie</h2>
+<p>
+    According to recent industry research, 70% of homeowners now search online before hiring any tradesperson. If you don't have a website, you're invisible to this massive market segment. But it goes beyond visibility—tradesmen with websites report earning up to 40% more annually than those without.
+</p>
+
+<h3>Why the Earnings Gap Exists</h3>
+<p>
+    The difference isn't just about being found online. It's about the perception of professionalism and trustworthiness that a well-designed website creates. When potential clients see a quality website, they immediately assume:
+</p>
+<ul>
+    <li><strong>Established Business:</strong> You're not a fly-by-night operator</li>
+    <li><strong>Professional Standards:</strong> If you invest in your online presence, you invest in your work</li>
+    <li><strong>Legitimate Operation:</strong> Easy to contact, easy to verify, easy to trust</li>
+</ul>
+
+<h2>24/7 Lead Generation</h2>
+<p>
+    Your website works while you sleep. Unlike traditional advertising that stops when you stop paying, your website is constantly available to capture leads. A potential customer searching for "electrician near me" at 11 PM can find you, see your work, and send an inquiry—all while you're resting for tomorrow's jobs.
+</p>
+
+<blockquote>
+    "Since launching my website, I've gone from scrambling for work to having a 3-week waiting list. The quality of leads has completely changed—clients come to me already sold on my services."
+    <cite>— Mark T., Electrician, Birmingham</cite>
+</blockquote>
+
+<h2>Showcase Your Best Work</h2>
+<p>
+    A portfolio of your completed projects is worth more than any advertisement. When potential clients can see the quality of your work through photos and testimonials, they're pre-sold before they even call.
+</p>
+
+<h2>The ROI is Undeniable</h2>
+<p>
+    Consider the numbers: A professional tradesman website costs a fraction of what a single new client is worth. If your average job is worth £500 and your website brings in just one new client per month, that's a 10x return on your investment in the first year alone.
+</p>
+
+<h3>Take Action Today</h3>
+<p>
+    The tradesmen who are winning in 2024 all have one thing in common: a professional online presence. Every day without a website is potential revenue walking straight to your competitors.
+</p>$body$),
+    (1, 'local-seo-guide',
+     'The Ultimate Guide to Local SEO for Plumbers',
+     'Learn how to rank #1 in your local area and get your phone ringing with high-quality leads. Complete local SEO strategy for trade businesses.',
+     'local-seo', ARRAY['local SEO', 'plumber SEO', 'Google Business Profile', 'local search ranking'],
+     'XF Tradesmen', 'published', '2024-12-10 09:00:00+00',
+     $body$<p class="lead text-xl text-gray-300 mb-8 font-light leading-relaxed">
+    Want to dominate your local search results and get your phone ringing with qualified leads? This comprehensive guide covers everything plumbers and tradesmen need to know about local SEO in 2024.
+</p>
+
+<h2>What is Local SEO and Why Does It Matter?</h2>
+<p>
+    Local SEO is the practice of optimizing your online presence to attract customers from local searches. When someone types "plumber near me" or "emergency plumber Coventry" into Google, local SEO determines whether your business shows up at the top—or gets buried on page five.
+</p>
+
+<h2>Step 1: Claim and Optimize Your Google Business Profile</h2>
+<p>
+    Your Google Business Profile (formerly Google My Business) is the single most important factor in local search rankings. Here's how to optimize it:
+</p>
+<ul>
+    <li><strong>Complete Every Section:</strong> Fill out 100% of your profile—business hours, services, service areas, attributes</li>
+    <li><strong>Choose the Right Categories:</strong> Select "Plumber" as your primary category, add relevant secondary categories</li>
+    <li><strong>Add Quality Photos:</strong> Upload photos of your work, your van, yourself in uniform—at least 10 high-quality images</li>
+    <li><strong>Write a Compelling Description:</strong> Use keywords naturally while describing your services</li>
+</ul>
+
+<h2>Step 2: Build Local Citations</h2>
+<p>
+    Citations are mentions of your business name, address, and phone number (NAP) on other websites. Consistency is crucial—your NAP must be identical everywhere it appears.
+</p>
+<h3>Priority Citation Sources for UK Tradesmen:</h3>
+<ul>
+    <li>Yell.com</li>
+    <li>Checkatrade</li>
+    <li>Trustatrader</li>
+    <li>MyBuilder</li>
+    <li>Thomson Local</li>
+</ul>
+
+<h2>Step 3: Generate and Manage Reviews</h2>
+<p>
+    Reviews are the lifeblood of local SEO. Google uses review quantity, quality, and recency as ranking factors.
+</p>
+
+<blockquote>
+    "I went from 3 reviews to 47 in six months by simply asking every satisfied customer. My Google ranking went from page 2 to the top 3, and my monthly leads tripled."
+    <cite>— Dave P., Plumber, Leeds</cite>
+</blockquote>
+
+<h2>Step 4: Optimize Your Website for Local Search</h2>
+<p>
+    Your website should reinforce your local relevance to search engines:
+</p>
+<ul>
+    <li><strong>Location Pages:</strong> Create dedicated pages for each area you serve</li>
+    <li><strong>Local Keywords:</strong> Include city and area names in titles, headings, and content</li>
+    <li><strong>Schema Markup:</strong> Add LocalBusiness structured data</li>
+</ul>$body$),
+    (1, 'building-trust-online',
+     'How to Build Instant Trust with Potential Clients',
+     'Your website is your first impression. Discover the 5 proven trust signals that convert visitors into paying customers.',
+     'trust-protocols', ARRAY['trust signals', 'website credibility', 'online reputation', 'customer trust'],
+     'XF Tradesmen', 'published', '2024-12-05 09:00:00+00',
+     $body$<p class="lead text-xl text-gray-300 mb-8 font-light leading-relaxed">
+    In the trades, trust is everything. Before a customer lets you into their home, they need to believe you're reliable, skilled, and honest. Here are the five proven trust signals that convert website visitors into loyal customers.
+</p>
+
+<h2>Why Online Trust Matters More Than Ever</h2>
+<p>
+    Research shows that 88% of consumers trust online reviews as much as personal recommendations. Your website is often the first (and sometimes only) impression potential clients have of your business.
+</p>
+
+<h2>Trust Signal #1: Professional Testimonials</h2>
+<p>
+    Nothing builds trust faster than hearing from satisfied customers. But not all testimonials are created equal:
+</p>
+<ul>
+    <li><strong>Use Full Names:</strong> "John S." is good, "John Smith, Coventry" is better</li>
+    <li><strong>Include Specifics:</strong> "Great work on my bathroom refit" beats "Good service"</li>
+    <li><strong>Add Photos:</strong> Before/after photos with testimonials are incredibly powerful</li>
+</ul>
+
+<h2>Trust Signal #2: Credentials and Certifications</h2>
+<p>
+    Display your qualifications prominently. This includes:
+</p>
+<ul>
+    <li>Trade certifications (Gas Safe, NICEIC, etc.)</li>
+    <li>Professional association memberships</li>
+    <li>Insurance documentation</li>
+    <li>Years of experience</li>
+</ul>
+
+<blockquote>
+    "The moment I added my Gas Safe registration number and insurance details prominently on my website, my conversion rate jumped 25%."
+    <cite>— Steve M., Gas Engineer, Manchester</cite>
+</blockquote>
+
+<h2>Trust Signal #3: Portfolio of Completed Work</h2>
+<p>
+    Show, don't just tell. A gallery of your completed projects demonstrates quality, range, and consistency.
+</p>
+
+<h2>Trust Signal #4: Clear Contact Information</h2>
+<p>
+    Nothing screams "trustworthy" like making yourself easy to reach:
+</p>
+<ul>
+    <li><strong>Phone Number:</strong> Prominently displayed, preferably in the header</li>
+    <li><strong>Email Address:</strong> Professional email (name@yourbusiness.com)</li>
+    <li><strong>Physical Address:</strong> Even if you work from home, include your service area</li>
+</ul>
+
+<h2>Trust Signal #5: Professional Website Design</h2>
+<p>
+    Your website design itself is a trust signal. Modern, clean layouts suggest a modern, professional business.
+</p>$body$)
+ON CONFLICT DO NOTHING;
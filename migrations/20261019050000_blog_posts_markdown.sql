@@ -0,0 +1,135 @@
+-- ============================================================================
+-- Blog post bodies as Markdown
+-- ============================================================================
+--
+-- Post bodies are now written in Markdown and rendered to sanitised HTML by
+-- lib_core::content, which also gives headings their anchors and builds the
+-- table of contents. The three seeded posts were copied in as HTML; this
+-- rewrites them as Markdown. A post is only rewritten while it still holds
+-- the seeded HTML, so one already edited from the admin API is left alone.
+
+UPDATE blog_posts SET body = $body$In 2024, the data is clear: tradesmen with professional websites are earning significantly more than their competitors who rely solely on word-of-mouth. Here's why making the digital leap is no longer optional.
+
+## The Statistics Don't Lie
+
+According to recent industry research, 70% of homeowners now search online before hiring any tradesperson. If you don't have a website, you're invisible to this massive market segment. But it goes beyond visibility—tradesmen with websites report earning up to 40% more annually than those without.
+
+### Why the Earnings Gap Exists
+
+The difference isn't just about being found online. It's about the perception of professionalism and trustworthiness that a well-designed website creates. When potential clients see a quality website, they immediately assume:
+
+- **Established Business:** You're not a fly-by-night operator
+- **Professional Standards:** If you invest in your online presence, you invest in your work
+- **Legitimate Operation:** Easy to contact, easy to verify, easy to trust
+
+## 24/7 Lead Generation
+
+Your website works while you sleep. Unlike traditional advertising that stops when you stop paying, your website is constantly available to capture leads. A potential customer searching for "electrician near me" at 11 PM can find you, see your work, and send an inquiry—all while you're resting for tomorrow's jobs.
+
+> "Since launching my website, I've gone from scrambling for work to having a 3-week waiting list. The quality of leads has completely changed—clients come to me already sold on my services."
+>
+> — Mark T., Electrician, Birmingham
+
+## Showcase Your Best Work
+
+A portfolio of your completed projects is worth more than any advertisement. When potential clients can see the quality of your work through photos and testimonials, they're pre-sold before they even call.
+
+## The ROI is Undeniable
+
+Consider the numbers: A professional tradesman website costs a fraction of what a single new client is worth. If your average job is worth £500 and your website brings in just one new client per month, that's a 10x return on your investment in the first year alone.
+
+### Take Action Today
+
+The tradesmen who are winning in 2024 all have one thing in common: a professional online presence. Every day without a website is potential revenue walking straight to your competitors.
+$body$
+WHERE tenant_id = 1 AND slug = 'why-tradesmen-need-websites' AND body LIKE '<p class="lead%';
+
+UPDATE blog_posts SET body = $body$Want to dominate your local search results and get your phone ringing with qualified leads? This comprehensive guide covers everything plumbers and tradesmen need to know about local SEO in 2024.
+
+## What is Local SEO and Why Does It Matter?
+
+Local SEO is the practice of optimizing your online presence to attract customers from local searches. When someone types "plumber near me" or "emergency plumber Coventry" into Google, local SEO determines whether your business shows up at the top—or gets buried on page five.
+
+## Step 1: Claim and Optimize Your Google Business Profile
+
+Your Google Business Profile (formerly Google My Business) is the single most important factor in local search rankings. Here's how to optimize it:
+
+- **Complete Every Section:** Fill out 100% of your profile—business hours, services, service areas, attributes
+- **Choose the Right Categories:** Select "Plumber" as your primary category, add relevant secondary categories
+- **Add Quality Photos:** Upload photos of your work, your van, yourself in uniform—at least 10 high-quality images
+- **Write a Compelling Description:** Use keywords naturally while describing your services
+
+## Step 2: Build Local Citations
+
+Citations are mentions of your business name, address, and phone number (NAP) on other websites. Consistency is crucial—your NAP must be identical everywhere it appears.
+
+### Priority Citation Sources for UK Tradesmen:
+
+- Yell.com
+- Checkatrade
+- Trustatrader
+- MyBuilder
+- Thomson Local
+
+## Step 3: Generate and Manage Reviews
+
+Reviews are the lifeblood of local SEO. Google uses review quantity, quality, and recency as ranking factors.
+
+> "I went from 3 reviews to 47 in six months by simply asking every satisfied customer. My Google ranking went from page 2 to the top 3, and my monthly leads tripled."
+>
+> — Dave P., Plumber, Leeds
+
+## Step 4: Optimize Your Website for Local Search
+
+Your website should reinforce your local relevance to search engines:
+
+- **Location Pages:** Create dedicated pages for each area you serve
+- **Local Keywords:** Include city and area names in titles, headings, and content
+- **Schema Markup:** Add LocalBusiness structured data
+$body$
+WHERE tenant_id = 1 AND slug = 'local-seo-guide' AND body LIKE '<p class="lead%';
+
+UPDATE blog_posts SET body = $body$In the trades, trust is everything. Before a customer lets you into their home, they need to believe you're reliable, skilled, and honest. Here are the five proven trust signals that convert website visitors into loyal customers.
+
+## Why Online Trust Matters More Than Ever
+
+Research shows that 88% of consumers trust online reviews as much as personal recommendations. Your website is often the first (and sometimes only) impression potential clients have of your business.
+
+## Trust Signal #1: Professional Testimonials
+
+Nothing builds trust faster than hearing from satisfied customers. But not all testimonials are created equal:
+
+- **Use Full Names:** "John S." is good, "John Smith, Coventry" is better
+- **Include Specifics:** "Great work on my bathroom refit" beats "Good service"
+- **Add Photos:** Before/after photos with testimonials are incredibly powerful
+
+## Trust Signal #2: Credentials and Certifications
+
+Display your qualifications prominently. This includes:
+
+- Trade certifications (Gas Safe, NICEIC, etc.)
+- Professional association memberships
+- Insurance documentation
+- Years of experience
+
+> "The moment I added my Gas Safe registration number and insurance details prominently on my website, my conversion rate jumped 25%."
+>
+> — Steve M., Gas Engineer, Manchester
+
+## Trust Signal #3: Portfolio of Completed Work
+
+Show, don't just tell. A gallery of your completed projects demonstrates quality, range, and consistency.
+
+## Trust Signal #4: Clear Contact Information
+
+Nothing screams "trustworthy" like making yourself easy to reach:
+
+- **Phone Number:** Prominently displayed, preferably in the header
+- **Email Address:** Professional email (name@yourbusiness.com)
+- **Physical Address:** Even if you work from home, include your service area
+
+## Trust Signal #5: Professional Website Design
+
+Your website design itself is a trust signal. Modern, clean layouts suggest a modern, professional business.
+$body$
+WHERE tenant_id = 1 AND slug = 'building-trust-online' AND body LIKE '<p class="lead%';
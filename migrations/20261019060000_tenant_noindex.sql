@@ -0,0 +1,10 @@
+-- ============================================================================
+-- Tenant indexing
+-- ============================================================================
+--
+-- A tenant's sites can ask search engines not to index them, e.g. while a
+-- client's site is being reviewed before launch. The Leptos site then serves
+-- a disallow-all robots.txt and `X-Robots-Tag: noindex` on the tenant's
+-- hosts, as it does everywhere outside production.
+
+ALTER TABLE tenants ADD COLUMN IF NOT EXISTS noindex BOOLEAN NOT NULL DEFAULT FALSE;
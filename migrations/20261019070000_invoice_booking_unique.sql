@@ -0,0 +1,15 @@
+-- ============================================================================
+-- One live invoice per booking
+-- ============================================================================
+--
+-- Invoice generation checks a booking hasn't already been invoiced before
+-- inserting, but two requests for the same booking can both pass the check.
+-- The database now refuses a second invoice for a booking unless the first
+-- was voided. Credit notes share the booking and are not limited.
+--
+-- Fails if a booking already has more than one live invoice; void the
+-- duplicates first.
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_invoices_live_booking
+    ON invoices(tenant_id, booking_id)
+    WHERE kind = 'invoice' AND status <> 'void';
@@ -49,5 +49,11 @@ pub mod validation;
 pub use error::{SharedError, SharedResult};
 pub use metadata::{PageMetadata, FULL_BUSINESS_DESCRIPTION};
 pub use newtypes::{Email, NonEmptyString, PhoneNumber, PositiveInt, PriceCents};
-pub use types::{ApiResponse, ContactForm, Product, ProductImage, ProductWithImages};
+pub use types::{
+    ApiResponse, AppliedAdjustment, AreaPage, AreaService, BlogFeed, BlogPost, ContactForm,
+    Coverage, InstantQuoteRequest, InstantQuoteResponse, OnboardingForm, OnboardingReceipt,
+    OnboardingService, OpeningHours, PageLink, PricingCatalog, PricingRule, Product, ProductImage,
+    ProductWithImages, RuleAdjustment, RuleCondition, ServiceAreaPage, ServiceRate, SiteConfig,
+    Sitemap, SitemapUrl, Testimonial, TocEntry, BLOG_CATEGORIES, BLOG_PATH, HANDYMAN_SITE_PATH,
+};
 pub use validation::Validate;
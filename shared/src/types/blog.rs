@@ -0,0 +1,289 @@
+//! Blog post types
+//!
+//! Posts are written and scheduled in the backend; the site renders the
+//! published ones from these types, and the backend syndicates them as
+//! RSS and Atom [`BlogFeed`]s.
+
+use crate::types::sitemap::SitemapUrl;
+use serde::{Deserialize, Serialize};
+
+/// Where the blog lives on a site.
+pub const BLOG_PATH: &str = "/blog";
+
+/// The blog's name, for feeds and structured data.
+pub const BLOG_TITLE: &str = "XF Tradesmen Blog";
+
+/// Blog categories as `(slug, name)`, in display order.
+pub const BLOG_CATEGORIES: &[(&str, &str)] = &[
+    ("revenue-data", "Revenue Data"),
+    ("local-seo", "Local SEO"),
+    ("trust-protocols", "Trust Protocols"),
+];
+
+/// A published blog post.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct BlogPost {
+    /// URL slug, e.g. `local-seo-guide`
+    pub slug: String,
+    /// Headline
+    pub title: String,
+    /// Summary for cards and meta descriptions
+    pub description: String,
+    /// Category slug, one of [`BLOG_CATEGORIES`]
+    pub category: String,
+    /// Search keywords
+    pub keywords: Vec<String>,
+    /// Who wrote it
+    pub author: String,
+    /// Article body as sanitised HTML, rendered from the post's Markdown
+    pub content: String,
+    /// The body's section headings, for a table of contents
+    pub toc: Vec<TocEntry>,
+    /// Estimated reading time in minutes
+    pub read_minutes: u32,
+    /// URLs of the images in the body
+    pub images: Vec<String>,
+    /// When it was (or goes) live, RFC 3339
+    pub published_at: String,
+    /// When it was last edited, RFC 3339
+    pub updated_at: Option<String>,
+}
+
+impl BlogPost {
+    /// The post's path, e.g. `/blog/local-seo-guide`.
+    pub fn path(&self) -> String {
+        format!("{}/{}", BLOG_PATH, self.slug)
+    }
+
+    /// The post's URL on the site at `origin`, e.g. `https://xftradesman.com`.
+    pub fn canonical_url(&self, origin: &str) -> String {
+        format!("{}{}", origin, self.path())
+    }
+
+    /// The category's display name, e.g. "Local SEO".
+    pub fn category_name(&self) -> &str {
+        BLOG_CATEGORIES
+            .iter()
+            .find(|(slug, _)| *slug == self.category)
+            .map_or(self.category.as_str(), |(_, name)| name)
+    }
+
+    /// Publication date for display, e.g. "December 15, 2024".
+    pub fn published_date(&self) -> String {
+        long_date(&self.published_at).unwrap_or_else(|| self.published_at.clone())
+    }
+
+    /// Estimated reading time, e.g. "5 min".
+    pub fn read_time(&self) -> String {
+        format!("{} min", self.read_minutes)
+    }
+
+    /// The post's sitemap entry, last modified when it was last edited or
+    /// went live, whichever is later.
+    pub fn sitemap_url(&self) -> SitemapUrl {
+        let lastmod = match &self.updated_at {
+            Some(updated_at) if *updated_at > self.published_at => updated_at,
+            _ => &self.published_at,
+        };
+        SitemapUrl {
+            path: self.path(),
+            lastmod: Some(lastmod.clone()).filter(|lastmod| !lastmod.is_empty()),
+            images: self.images.clone(),
+        }
+    }
+}
+
+/// A heading in a post's table of contents.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct TocEntry {
+    /// The heading's anchor, e.g. `pricing` for `#pricing`
+    pub id: String,
+    /// Heading text
+    pub title: String,
+    /// Heading level: 2 for a section, 3 for a subsection
+    pub level: u8,
+}
+
+/// The blog's RSS and Atom feeds, for every post or for one category.
+///
+/// Every site serves its own feeds, so the URLs are built on a site's
+/// origin; the paths suit links within a page.
+///
+/// # Example
+///
+/// ```rust
+/// use shared::BlogFeed;
+///
+/// let feed = BlogFeed::for_category("local-seo").unwrap();
+///
+/// assert_eq!(feed.title(), "XF Tradesmen Blog: Local SEO");
+/// assert_eq!(
+///     feed.rss_url("https://jonesplumbing.co.uk"),
+///     "https://jonesplumbing.co.uk/blog/category/local-seo/rss.xml"
+/// );
+/// assert_eq!(BlogFeed::ALL.atom_path(), "/blog/atom.xml");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlogFeed {
+    /// `(slug, name)` from [`BLOG_CATEGORIES`], or `None` for every post
+    category: Option<(&'static str, &'static str)>,
+}
+
+impl BlogFeed {
+    /// Every post.
+    pub const ALL: Self = Self { category: None };
+
+    /// The posts in a category, if it is one of [`BLOG_CATEGORIES`].
+    pub fn for_category(slug: &str) -> Option<Self> {
+        BLOG_CATEGORIES
+            .iter()
+            .find(|(category, _)| *category == slug)
+            .map(|&category| Self {
+                category: Some(category),
+            })
+    }
+
+    /// The category slug, or `None` for every post.
+    pub fn category(&self) -> Option<&'static str> {
+        self.category.map(|(slug, _)| slug)
+    }
+
+    /// Feed title, e.g. "XF Tradesmen Blog: Local SEO".
+    pub fn title(&self) -> String {
+        match self.category {
+            Some((_, name)) => format!("{}: {}", BLOG_TITLE, name),
+            None => BLOG_TITLE.to_string(),
+        }
+    }
+
+    /// The RSS 2.0 feed's path.
+    pub fn rss_path(&self) -> String {
+        format!("{}/rss.xml", self.path())
+    }
+
+    /// The Atom feed's path.
+    pub fn atom_path(&self) -> String {
+        format!("{}/atom.xml", self.path())
+    }
+
+    /// The RSS 2.0 feed's URL on the site at `origin`.
+    pub fn rss_url(&self, origin: &str) -> String {
+        format!("{}{}", origin, self.rss_path())
+    }
+
+    /// The Atom feed's URL on the site at `origin`.
+    pub fn atom_url(&self, origin: &str) -> String {
+        format!("{}{}", origin, self.atom_path())
+    }
+
+    fn path(&self) -> String {
+        match self.category {
+            Some((slug, _)) => format!("{}/category/{}", BLOG_PATH, slug),
+            None => BLOG_PATH.to_string(),
+        }
+    }
+}
+
+// region:    --- Blog Helpers
+
+/// "2024-12-15T09:00:00Z" as "December 15, 2024".
+fn long_date(rfc3339: &str) -> Option<String> {
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    let date = rfc3339.get(..10)?;
+    let mut parts = date.split('-');
+    let year: u32 = parts.next()?.parse().ok()?;
+    let month: usize = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+
+    Some(format!(
+        "{} {}, {}",
+        MONTHS.get(month.checked_sub(1)?)?,
+        day,
+        year
+    ))
+}
+
+// endregion: --- Blog Helpers
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fx_post() -> BlogPost {
+        BlogPost {
+            slug: "local-seo-guide".to_string(),
+            title: "Local SEO".to_string(),
+            description: "A guide".to_string(),
+            category: "local-seo".to_string(),
+            keywords: vec![],
+            author: "XF Tradesmen".to_string(),
+            content: "<p>Get found.</p>".to_string(),
+            toc: vec![],
+            read_minutes: 3,
+            images: vec!["/img/map.png".to_string()],
+            published_at: "2024-12-10T09:00:00Z".to_string(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_blog_post_display() {
+        let post = fx_post();
+
+        assert_eq!(post.path(), "/blog/local-seo-guide");
+        assert_eq!(
+            post.canonical_url("https://jonesplumbing.co.uk"),
+            "https://jonesplumbing.co.uk/blog/local-seo-guide"
+        );
+        assert_eq!(post.category_name(), "Local SEO");
+        assert_eq!(post.published_date(), "December 10, 2024");
+        assert_eq!(post.read_time(), "3 min");
+    }
+
+    #[test]
+    fn test_blog_post_sitemap_url() {
+        let mut post = fx_post();
+        post.updated_at = Some("2024-12-01T09:00:00Z".to_string());
+        let url = post.sitemap_url();
+
+        assert_eq!(url.path, "/blog/local-seo-guide");
+        assert_eq!(url.lastmod.as_deref(), Some("2024-12-10T09:00:00Z"));
+        assert_eq!(url.images, ["/img/map.png"]);
+
+        post.updated_at = Some("2024-12-20T12:30:00Z".to_string());
+        assert_eq!(
+            post.sitemap_url().lastmod.as_deref(),
+            Some("2024-12-20T12:30:00Z")
+        );
+    }
+
+    #[test]
+    fn test_blog_feed() {
+        assert_eq!(BlogFeed::ALL.title(), "XF Tradesmen Blog");
+        assert_eq!(BlogFeed::ALL.rss_path(), "/blog/rss.xml");
+        assert_eq!(
+            BlogFeed::ALL.rss_url("https://xftradesman.com"),
+            "https://xftradesman.com/blog/rss.xml"
+        );
+        assert_eq!(
+            BlogFeed::for_category("trust-protocols")
+                .unwrap()
+                .atom_url("https://jonesplumbing.co.uk"),
+            "https://jonesplumbing.co.uk/blog/category/trust-protocols/atom.xml"
+        );
+        assert_eq!(BlogFeed::for_category("unknown"), None);
+    }
+}
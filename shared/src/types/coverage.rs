@@ -0,0 +1,53 @@
+//! Service coverage types
+//!
+//! The answer to "do you cover me?" for a postcode. The API works it out
+//! from imported postcode districts; the booking and quote forms show it
+//! and refuse postcodes we don't cover.
+
+use serde::{Deserialize, Serialize};
+
+/// Whether, and how far, we travel to a postcode.
+///
+/// # Example
+///
+/// ```rust
+/// use shared::Coverage;
+///
+/// let coverage = Coverage {
+///     postcode: "CV1 1AA".to_string(),
+///     outcode: "CV1".to_string(),
+///     district: Some("Coventry".to_string()),
+///     covered: true,
+///     distance_km: Some(0.4),
+///     travel_band: Some(1),
+///     message: "Good news - we cover Coventry".to_string(),
+/// };
+///
+/// assert_eq!(coverage.distance_zone().as_deref(), Some("1"));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+pub struct Coverage {
+    /// The postcode asked about, in canonical form
+    pub postcode: String,
+    /// Its district (outward code)
+    pub outcode: String,
+    /// Town or local authority, if known
+    pub district: Option<String>,
+    /// Whether we travel there
+    pub covered: bool,
+    /// Straight-line distance from the service centre in km (unknown
+    /// districts have none)
+    pub distance_km: Option<f64>,
+    /// Travel band, from 1; none when not covered
+    pub travel_band: Option<i32>,
+    /// Answer to show the customer
+    pub message: String,
+}
+
+impl Coverage {
+    /// The instant quote `distance_zone` for this postcode.
+    #[must_use]
+    pub fn distance_zone(&self) -> Option<String> {
+        self.travel_band.map(|band| band.to_string())
+    }
+}
@@ -0,0 +1,111 @@
+//! Area and service landing page types
+//!
+//! The towns we cover and the services we offer live in the database; the
+//! API assembles each landing page from them and the Leptos site renders
+//! it, so adding a town needs no code change.
+
+use serde::{Deserialize, Serialize};
+
+/// Link to another landing page.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct PageLink {
+    /// URL slug, e.g. `solihull`
+    pub slug: String,
+    /// Link text
+    pub name: String,
+}
+
+/// A customer's words about a job in the area.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct Testimonial {
+    /// What they said
+    pub quote: String,
+    /// Who said it, e.g. "John S., CV6"
+    pub author: String,
+}
+
+/// A service as offered in one area.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct AreaService {
+    /// Service slug, e.g. `plumbing`
+    pub slug: String,
+    /// Service name
+    pub name: String,
+    /// One-line summary
+    pub summary: String,
+    /// Whether the service has its own page for this area
+    /// (`/services/{service}/{area}`); otherwise link to the service page
+    pub local_page: bool,
+}
+
+/// Everything shown on an `/areas/{slug}` page.
+///
+/// # Example
+///
+/// ```rust
+/// use shared::{AreaPage, AreaService};
+///
+/// let page = AreaPage {
+///     slug: "kenilworth".to_string(),
+///     name: "Kenilworth".to_string(),
+///     description: "Castle town a short hop down the A46.".to_string(),
+///     postcodes: vec!["CV8".to_string()],
+///     travel_time: Some("15-20 mins".to_string()),
+///     testimonial: None,
+///     services: vec![AreaService {
+///         slug: "plumbing".to_string(),
+///         name: "Plumbing".to_string(),
+///         summary: "Expert plumbing repairs".to_string(),
+///         local_page: true,
+///     }],
+///     other_areas: vec![],
+/// };
+///
+/// assert!(page.services.iter().any(|s| s.local_page));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct AreaPage {
+    /// URL slug
+    pub slug: String,
+    /// Town name
+    pub name: String,
+    /// About working in the area
+    pub description: String,
+    /// Postcode districts covered
+    pub postcodes: Vec<String>,
+    /// Typical travel time, e.g. "Under 20 mins"
+    pub travel_time: Option<String>,
+    /// Local testimonial
+    pub testimonial: Option<Testimonial>,
+    /// Services offered
+    pub services: Vec<AreaService>,
+    /// Other areas we cover
+    pub other_areas: Vec<PageLink>,
+}
+
+/// Everything shown on a `/services/{service}/{town}` page.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct ServiceAreaPage {
+    /// The service
+    pub service: PageLink,
+    /// The town
+    pub area: PageLink,
+    /// Copy written for this service in this town
+    pub intro: String,
+    /// About the service in general, as sanitised HTML
+    pub description: String,
+    /// Typical price range from the pricing catalog, in cents
+    pub price_from_cents: Option<i32>,
+    /// Upper end of the typical price range, in cents
+    pub price_to_cents: Option<i32>,
+    /// Postcode districts covered in the town
+    pub postcodes: Vec<String>,
+    /// Typical travel time
+    pub travel_time: Option<String>,
+    /// Local testimonial
+    pub testimonial: Option<Testimonial>,
+    /// Other services with a page in this town
+    pub other_services: Vec<PageLink>,
+    /// Other towns with a page for this service
+    pub other_areas: Vec<PageLink>,
+}
@@ -5,22 +5,53 @@
 //!
 //! ## Modules
 //! - `api` - Generic API response wrapper
+//! - `blog` - Published blog posts and their feeds
 //! - `contact` - Contact form submission data
+//! - `coverage` - Service coverage by postcode
+//! - `landing` - Area and service landing pages
+//! - `onboarding` - A new client's business details for their site
+//! - `pricing` - Instant quote catalog, requests and estimates
 //! - `product` - Product catalog and image data
+//! - `site` - The business a site is for, and its branding
+//! - `sitemap` - Sitemap pages and the XML written for them
 //!
 //! ## Types
 //! - [`ApiResponse<T>`] - Generic response wrapper for all API endpoints
+//! - [`BlogPost`] - A published blog post, with its [`TocEntry`] headings
 //! - [`ContactForm`] - Contact form submission data
+//! - [`Coverage`] - Whether we cover a postcode, and its travel band
+//! - [`AreaPage`], [`ServiceAreaPage`] - Area and service-in-town landing pages
+//! - [`OnboardingForm`] - Business, services, coverage and reviews for a new site
+//! - [`PricingCatalog`] - Service price ranges and pricing rules
 //! - [`Product`] - Product for catalog display
 //! - [`ProductImage`] - Product image metadata
+//! - [`SiteConfig`] - Business name, contact details and colours for a site
+//! - [`Sitemap`] - The site's [`SitemapUrl`]s as a sitemap, or a sitemap index
 
 pub mod api;
+pub mod blog;
 pub mod contact;
+pub mod coverage;
+pub mod landing;
+pub mod onboarding;
+pub mod pricing;
 pub mod product;
+pub mod site;
+pub mod sitemap;
 
 pub use api::ApiResponse;
+pub use blog::{BlogFeed, BlogPost, TocEntry, BLOG_CATEGORIES, BLOG_PATH, BLOG_TITLE};
 pub use contact::ContactForm;
+pub use coverage::Coverage;
+pub use landing::{AreaPage, AreaService, PageLink, ServiceAreaPage, Testimonial};
+pub use onboarding::{OnboardingForm, OnboardingReceipt, OnboardingService, OpeningHours};
+pub use pricing::{
+    AppliedAdjustment, InstantQuoteRequest, InstantQuoteResponse, PricingCatalog, PricingRule,
+    RuleAdjustment, RuleCondition, ServiceRate,
+};
 pub use product::{Product, ProductImage, ProductWithImages};
+pub use site::{SiteConfig, HANDYMAN_SITE_PATH};
+pub use sitemap::{Sitemap, SitemapUrl, SITEMAP_MAX_URLS};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,72 @@
+//! Client onboarding types
+//!
+//! What a new client tells us about their business in the onboarding
+//! wizard. A submitted [`OnboardingForm`] is kept as a draft until an admin
+//! reviews it and publishes it as the client's site.
+
+use super::landing::Testimonial;
+use serde::{Deserialize, Serialize};
+
+/// Everything needed to set up a client's site.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+pub struct OnboardingForm {
+    /// Trading name, e.g. "Jones Plumbing"
+    pub business_name: String,
+    /// Trade, e.g. "Plumber"
+    pub trade: String,
+    /// Who we're dealing with
+    pub contact_name: String,
+    /// Email customers and we use
+    pub email: String,
+    /// Phone number customers call
+    pub phone: String,
+    /// Main town worked in, e.g. "Rugby"
+    pub town: String,
+    /// Services offered, with prices
+    pub services: Vec<OnboardingService>,
+    /// Postcode districts covered, e.g. `CV21`
+    pub postcodes: Vec<String>,
+    /// Logo image URL (`https://`)
+    #[serde(default)]
+    pub logo_url: Option<String>,
+    /// When the business is open; days not listed are closed
+    #[serde(default)]
+    pub opening_hours: Vec<OpeningHours>,
+    /// Trade bodies and schemes, e.g. "Gas Safe Registered"
+    #[serde(default)]
+    pub accreditations: Vec<String>,
+    /// What customers have said
+    #[serde(default)]
+    pub testimonials: Vec<Testimonial>,
+}
+
+/// A service a client offers, and what it usually costs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct OnboardingService {
+    /// Service name, e.g. "Boiler Servicing"
+    pub name: String,
+    /// What the service covers
+    pub description: String,
+    /// Typical lowest price in pence
+    pub price_from_cents: i32,
+    /// Typical highest price in pence
+    pub price_to_cents: i32,
+}
+
+/// Opening times for one day or a run of days.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct OpeningHours {
+    /// Days, e.g. "Monday - Friday"
+    pub days: String,
+    /// Opening time, `HH:MM`
+    pub opens: String,
+    /// Closing time, `HH:MM`
+    pub closes: String,
+}
+
+/// A submitted onboarding form's reference.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct OnboardingReceipt {
+    /// Draft ID, for quoting in emails
+    pub id: i32,
+}
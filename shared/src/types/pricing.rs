@@ -0,0 +1,160 @@
+//! Instant quote pricing types
+//!
+//! The pricing catalog (base ranges per service and the rules that adjust
+//! them) lives in the database and is served by the API. The Leptos quote
+//! calculator reads the same catalog and asks the API for estimates, so
+//! prices are defined in exactly one place.
+
+use serde::{Deserialize, Serialize};
+
+/// Base price range for a service.
+///
+/// # Example
+///
+/// ```rust
+/// use shared::ServiceRate;
+///
+/// let plumbing = ServiceRate {
+///     service_type: "plumbing".to_string(),
+///     label: "Plumbing".to_string(),
+///     base_low_cents: 4500,
+///     base_high_cents: 12000,
+///     sort_order: 10,
+///     active: true,
+/// };
+///
+/// assert!(plumbing.base_low_cents <= plumbing.base_high_cents);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct ServiceRate {
+    /// Service key, e.g. `plumbing`
+    pub service_type: String,
+    /// Name shown to customers
+    pub label: String,
+    /// Lower end of the typical price, in cents
+    pub base_low_cents: i32,
+    /// Upper end of the typical price, in cents
+    pub base_high_cents: i32,
+    /// Display order (lowest first)
+    pub sort_order: i32,
+    /// Whether the service is offered in the calculator
+    pub active: bool,
+}
+
+/// When a pricing rule applies.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum RuleCondition {
+    /// Requested urgency: `same_day`, `within_3_days`, `flexible`
+    Urgency(String),
+    /// Job size: `small`, `medium`, `large`
+    JobSize(String),
+    /// Work outside normal hours
+    OutOfHours,
+    /// Work on a Saturday or Sunday
+    Weekend,
+    /// Travel zone, e.g. `1` (local) to `3` (edge of area)
+    DistanceZone(String),
+}
+
+/// How a pricing rule changes the running price.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleAdjustment {
+    /// Add (or with a negative amount, take off) a fixed amount in cents
+    Fixed { amount_cents: i32 },
+    /// Add a percentage of the running price, in basis points (may be negative)
+    Percent { basis_points: i32 },
+}
+
+/// A rule that adjusts the estimate when its condition matches.
+///
+/// Rules run in ascending `priority`, each on the price left by the rules
+/// before it. `cap_cents` limits the size of a single rule's adjustment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct PricingRule {
+    /// Rule ID
+    pub id: i32,
+    /// Label shown to customers, e.g. "Same-day call-out"
+    pub label: String,
+    /// When the rule applies
+    pub condition: RuleCondition,
+    /// Only for this service (any service if `None`)
+    pub service_type: Option<String>,
+    /// Price change
+    pub adjustment: RuleAdjustment,
+    /// Largest change this rule can make, in cents
+    pub cap_cents: Option<i32>,
+    /// Evaluation order (lowest first)
+    pub priority: i32,
+    /// Whether the rule is in use
+    pub active: bool,
+}
+
+/// Services and rules used by the instant quote calculator.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct PricingCatalog {
+    /// Services, in display order
+    pub services: Vec<ServiceRate>,
+    /// Rules, in evaluation order
+    pub rules: Vec<PricingRule>,
+}
+
+/// Details of a job for an instant estimate.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct InstantQuoteRequest {
+    /// Service key from the catalog
+    pub service_type: String,
+    /// Free-text description of the job
+    #[serde(default)]
+    pub description: Option<String>,
+    /// `same_day`, `within_3_days` or `flexible`
+    #[serde(default)]
+    pub urgency: Option<String>,
+    /// `small`, `medium` or `large`
+    #[serde(default)]
+    pub job_size: Option<String>,
+    /// Work needed outside normal hours
+    #[serde(default)]
+    pub out_of_hours: bool,
+    /// Work needed at the weekend
+    #[serde(default)]
+    pub weekend: bool,
+    /// Travel zone
+    #[serde(default)]
+    pub distance_zone: Option<String>,
+    /// Job postcode; when given, its travel band sets the travel zone
+    #[serde(default)]
+    pub postcode: Option<String>,
+    /// Promo code to apply
+    #[serde(default)]
+    pub promo_code: Option<String>,
+}
+
+/// A rule that changed an estimate.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct AppliedAdjustment {
+    /// Rule label
+    pub label: String,
+    /// Change to the low end, in cents
+    pub low_cents: i32,
+    /// Change to the high end, in cents
+    pub high_cents: i32,
+}
+
+/// Instant estimate for a job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct InstantQuoteResponse {
+    /// Lower end of the estimate, in cents
+    pub estimate_low: i32,
+    /// Upper end of the estimate, in cents
+    pub estimate_high: i32,
+    /// Rules that changed the estimate, in the order applied
+    pub adjustments: Vec<AppliedAdjustment>,
+    /// Whether the estimate includes VAT
+    pub vat_included: bool,
+    /// Promo code applied, normalised
+    pub promo_code: Option<String>,
+    /// Summary for display
+    pub message: String,
+}
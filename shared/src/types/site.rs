@@ -0,0 +1,111 @@
+//! Site configuration types
+//!
+//! One deployment serves several tradesman sites; the API picks the tenant
+//! by host name and the Leptos site renders its name, contact details and
+//! colours from a [`SiteConfig`].
+
+use super::landing::{PageLink, Testimonial};
+use super::onboarding::OpeningHours;
+use serde::{Deserialize, Serialize};
+
+/// Where the Leptos app serves the handyman site template.
+pub const HANDYMAN_SITE_PATH: &str = "/handyman-coventry";
+
+/// The business a site is for, and how it looks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+pub struct SiteConfig {
+    /// Trading name, e.g. "Coventry Handyman"
+    pub business_name: String,
+    /// Phone number as displayed, e.g. "07833 263486"
+    pub phone: Option<String>,
+    /// Contact email
+    pub email: Option<String>,
+    /// One-line strapline
+    pub tagline: Option<String>,
+    /// Main brand colour, `#rrggbb`; `None` keeps the template's
+    pub primary_color: Option<String>,
+    /// Highlight colour, `#rrggbb`; `None` keeps the template's
+    pub accent_color: Option<String>,
+    /// Logo image URL
+    pub logo_url: Option<String>,
+    /// Published services, for navigation
+    pub services: Vec<PageLink>,
+    /// Published areas, for navigation
+    pub areas: Vec<PageLink>,
+    /// Whether this is a client's site on its own domain, rather than the
+    /// example on the platform's site
+    #[serde(default)]
+    pub client_site: bool,
+    /// Trade, e.g. "Plumber"
+    #[serde(default)]
+    pub trade: Option<String>,
+    /// When the business is open
+    #[serde(default)]
+    pub opening_hours: Vec<OpeningHours>,
+    /// Trade bodies and schemes
+    #[serde(default)]
+    pub accreditations: Vec<String>,
+    /// What customers have said
+    #[serde(default)]
+    pub testimonials: Vec<Testimonial>,
+    /// Whether search engines are asked not to index the site
+    #[serde(default)]
+    pub noindex: bool,
+    /// Path the site's pages are under, e.g. `/handyman-coventry`
+    #[serde(default = "default_site_path")]
+    pub site_path: String,
+}
+
+fn default_site_path() -> String {
+    HANDYMAN_SITE_PATH.to_string()
+}
+
+impl SiteConfig {
+    /// `tel:` link for the phone number, in international form for UK
+    /// numbers: "07833 263486" becomes `tel:+447833263486`.
+    pub fn tel_href(&self) -> Option<String> {
+        let digits: String = self
+            .phone
+            .as_deref()?
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == '+')
+            .collect();
+        if digits.is_empty() {
+            return None;
+        }
+
+        let number = match digits.strip_prefix('0') {
+            Some(national) if !digits.starts_with("00") => format!("+44{national}"),
+            _ => digits,
+        };
+        Some(format!("tel:{number}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fx_site(phone: Option<&str>) -> SiteConfig {
+        SiteConfig {
+            business_name: "Test".to_string(),
+            phone: phone.map(str::to_string),
+            client_site: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_tel_href() {
+        assert_eq!(
+            fx_site(Some("07833 263486")).tel_href().as_deref(),
+            Some("tel:+447833263486")
+        );
+        assert_eq!(
+            fx_site(Some("+44 24 7612 3456")).tel_href().as_deref(),
+            Some("tel:+442476123456")
+        );
+        assert_eq!(fx_site(Some("n/a")).tel_href(), None);
+        assert_eq!(fx_site(None).tel_href(), None);
+    }
+}
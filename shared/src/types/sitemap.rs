@@ -0,0 +1,241 @@
+//! Sitemap types
+//!
+//! The site's pages are collected as [`SitemapUrl`]s and written out by
+//! [`Sitemap`] as sitemap XML. The protocol allows [`SITEMAP_MAX_URLS`]
+//! URLs per file, so a larger site is split into numbered files, each at
+//! [`Sitemap::page_url`], listed by a sitemap index.
+//!
+//! Pages carry a `lastmod` when their content has a timestamp; search
+//! engines ignore `priority` and `changefreq`, so neither is written.
+
+use serde::{Deserialize, Serialize};
+
+/// Most URLs in one sitemap file.
+pub const SITEMAP_MAX_URLS: usize = 50_000;
+
+/// A page for the sitemap.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct SitemapUrl {
+    /// Path from the site root, e.g. `/blog/local-seo-guide`
+    pub path: String,
+    /// When the page's content last changed, RFC 3339
+    pub lastmod: Option<String>,
+    /// Images on the page, absolute or from the site root
+    pub images: Vec<String>,
+}
+
+impl SitemapUrl {
+    /// A page with no timestamp or images.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            lastmod: None,
+            images: Vec::new(),
+        }
+    }
+}
+
+/// A site's sitemap.
+///
+/// # Examples
+///
+/// ```
+/// use shared::{Sitemap, SitemapUrl};
+///
+/// let sitemap = Sitemap::new("https://xftradesman.com", vec![SitemapUrl::new("/about")]);
+///
+/// assert!(!sitemap.is_index());
+/// assert!(sitemap.xml().contains("<loc>https://xftradesman.com/about</loc>"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sitemap {
+    base_url: String,
+    urls: Vec<SitemapUrl>,
+}
+
+impl Sitemap {
+    /// The sitemap of `urls` on the site at `base_url`, e.g.
+    /// `https://xftradesman.com`. Paths listed twice are kept once.
+    pub fn new(base_url: &str, mut urls: Vec<SitemapUrl>) -> Self {
+        let mut seen = std::collections::HashSet::new();
+        urls.retain(|url| seen.insert(url.path.clone()));
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            urls,
+        }
+    }
+
+    /// How many sitemap files the URLs fill.
+    pub fn pages(&self) -> usize {
+        self.urls.len().div_ceil(SITEMAP_MAX_URLS).max(1)
+    }
+
+    /// Whether there are too many URLs for one file, so `/sitemap.xml` is
+    /// an index of [`Sitemap::page_url`]s.
+    pub fn is_index(&self) -> bool {
+        self.pages() > 1
+    }
+
+    /// The URL of sitemap file `page`, counting from 1.
+    pub fn page_url(&self, page: usize) -> String {
+        format!("{}/sitemaps/{}.xml", self.base_url, page)
+    }
+
+    /// `/sitemap.xml`: every URL, or an index when they fill several files.
+    pub fn xml(&self) -> String {
+        if self.is_index() {
+            self.index_xml()
+        } else {
+            urlset_xml(&self.base_url, &self.urls)
+        }
+    }
+
+    /// Sitemap file `page`, counting from 1, if there is one.
+    pub fn page_xml(&self, page: usize) -> Option<String> {
+        let urls = self
+            .urls
+            .chunks(SITEMAP_MAX_URLS)
+            .nth(page.checked_sub(1)?)?;
+        Some(urlset_xml(&self.base_url, urls))
+    }
+
+    fn index_xml(&self) -> String {
+        let mut entries = String::new();
+        for (i, urls) in self.urls.chunks(SITEMAP_MAX_URLS).enumerate() {
+            entries.push_str(&format!(
+                "  <sitemap>\n    <loc>{}</loc>\n{}  </sitemap>\n",
+                escape_xml(&self.page_url(i + 1)),
+                lastmod_xml(urls.iter().filter_map(|url| url.lastmod.as_deref()).max())
+            ));
+        }
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+{}</sitemapindex>
+"#,
+            entries
+        )
+    }
+}
+
+// region:    --- Sitemap Helpers
+
+fn urlset_xml(base_url: &str, urls: &[SitemapUrl]) -> String {
+    let mut entries = String::new();
+    for url in urls {
+        let mut images = String::new();
+        for image in &url.images {
+            images.push_str(&format!(
+                "    <image:image>\n      <image:loc>{}</image:loc>\n    </image:image>\n",
+                escape_xml(&absolute(base_url, image))
+            ));
+        }
+        entries.push_str(&format!(
+            "  <url>\n    <loc>{}</loc>\n{}{}  </url>\n",
+            escape_xml(&absolute(base_url, &url.path)),
+            lastmod_xml(url.lastmod.as_deref()),
+            images
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:image="http://www.google.com/schemas/sitemap-image/1.1">
+{}</urlset>
+"#,
+        entries
+    )
+}
+
+fn lastmod_xml(lastmod: Option<&str>) -> String {
+    lastmod
+        .map(|lastmod| format!("    <lastmod>{}</lastmod>\n", escape_xml(lastmod)))
+        .unwrap_or_default()
+}
+
+/// `url` as an absolute URL on the site at `base_url`.
+fn absolute(base_url: &str, url: &str) -> String {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else if url == "/" {
+        base_url.to_string()
+    } else {
+        format!("{}{}", base_url, url)
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// endregion: --- Sitemap Helpers
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_URL: &str = "https://xftradesman.com";
+
+    #[test]
+    fn test_sitemap_urlset() {
+        let sitemap = Sitemap::new(
+            "https://xftradesman.com/",
+            vec![
+                SitemapUrl::new("/"),
+                SitemapUrl {
+                    path: "/blog/local-seo-guide".to_string(),
+                    lastmod: Some("2024-12-20T12:30:00Z".to_string()),
+                    images: vec![
+                        "/img/van.jpg".to_string(),
+                        "https://cdn.example.com/a&b.jpg".to_string(),
+                    ],
+                },
+                SitemapUrl::new("/"),
+            ],
+        );
+        let content = sitemap.xml();
+
+        assert!(!sitemap.is_index());
+        assert_eq!(content.matches("<url>").count(), 2);
+        assert!(content.contains("<loc>https://xftradesman.com</loc>"));
+        assert!(content.contains("<loc>https://xftradesman.com/blog/local-seo-guide</loc>"));
+        assert!(content.contains("<lastmod>2024-12-20T12:30:00Z</lastmod>"));
+        assert!(content.contains("<image:loc>https://xftradesman.com/img/van.jpg</image:loc>"));
+        assert!(content.contains("<image:loc>https://cdn.example.com/a&amp;b.jpg</image:loc>"));
+        assert_eq!(sitemap.page_xml(1), Some(content));
+        assert_eq!(sitemap.page_xml(2), None);
+    }
+
+    #[test]
+    fn test_sitemap_index() {
+        let urls = (0..SITEMAP_MAX_URLS + 1)
+            .map(|i| SitemapUrl {
+                path: format!("/page-{}", i),
+                lastmod: Some(format!("2024-12-{:02}T00:00:00Z", i % 28 + 1)),
+                images: Vec::new(),
+            })
+            .collect();
+        let sitemap = Sitemap::new(BASE_URL, urls);
+        let index = sitemap.xml();
+
+        assert!(sitemap.is_index());
+        assert_eq!(sitemap.pages(), 2);
+        assert!(index.contains("<sitemapindex"));
+        assert!(index.contains("<loc>https://xftradesman.com/sitemaps/1.xml</loc>"));
+        assert!(index.contains("<loc>https://xftradesman.com/sitemaps/2.xml</loc>"));
+        assert!(index.contains("<lastmod>2024-12-28T00:00:00Z</lastmod>"));
+        assert_eq!(
+            sitemap.page_xml(1).unwrap().matches("<url>").count(),
+            SITEMAP_MAX_URLS
+        );
+        assert!(sitemap.page_xml(2).unwrap().contains("/page-50000</loc>"));
+        assert_eq!(sitemap.page_xml(0), None);
+        assert_eq!(sitemap.page_xml(3), None);
+    }
+}
@@ -22,10 +22,17 @@
 //! # }
 //! ```
 
-use crate::types::{ContactForm, Product, ProductImage};
+use crate::newtypes::{Email, PhoneNumber};
+use crate::types::{
+    ContactForm, OnboardingForm, OnboardingService, OpeningHours, Product, ProductImage,
+    Testimonial,
+};
 
 use std::borrow::Cow;
 
+/// Most services a client can list during onboarding.
+pub const MAX_ONBOARDING_SERVICES: usize = 20;
+
 /// Trait for validating data structures.
 ///
 /// All input types (especially those coming from external sources like forms or APIs)
@@ -159,6 +166,252 @@ impl Validate for ProductImage {
     }
 }
 
+impl Validate for OnboardingForm {
+    /// Validates a client's onboarding details, one wizard step at a time.
+    ///
+    /// # Validation Rules
+    ///
+    /// See [`OnboardingForm::validate_business`],
+    /// [`OnboardingForm::validate_services`], [`OnboardingForm::validate_area`]
+    /// and [`OnboardingForm::validate_extras`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error message for the first validation failure encountered.
+    fn validate(&self) -> Result<(), Cow<'static, str>> {
+        self.validate_business()?;
+        self.validate_services()?;
+        self.validate_area()?;
+        self.validate_extras()
+    }
+}
+
+impl OnboardingForm {
+    /// Validates the business details.
+    ///
+    /// # Validation Rules
+    ///
+    /// - Business name, trade, contact name and town: must not be empty
+    /// - Email and phone: must be valid (see [`Email`] and [`PhoneNumber`])
+    /// - Logo URL: `https://` when given
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error message for the first validation failure encountered.
+    pub fn validate_business(&self) -> Result<(), Cow<'static, str>> {
+        for (value, label) in [
+            (&self.business_name, "Business name"),
+            (&self.trade, "Trade"),
+            (&self.contact_name, "Contact name"),
+            (&self.town, "Town"),
+        ] {
+            if value.trim().is_empty() {
+                return Err(Cow::Owned(format!("{label} is required")));
+            }
+            if value.trim().chars().count() > 100 {
+                return Err(Cow::Owned(format!(
+                    "{label} must be at most 100 characters"
+                )));
+            }
+        }
+        Email::new(self.email.as_str())?;
+        PhoneNumber::new(self.phone.as_str())?;
+
+        if let Some(url) = self.logo_url.as_deref().map(str::trim) {
+            if !url.is_empty() && !url.starts_with("https://") {
+                return Err(Cow::Borrowed("Logo URL must start with https://"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the services offered.
+    ///
+    /// # Validation Rules
+    ///
+    /// - 1 to [`MAX_ONBOARDING_SERVICES`] services, each valid, no two with
+    ///   the same name
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error message for the first validation failure encountered.
+    pub fn validate_services(&self) -> Result<(), Cow<'static, str>> {
+        if self.services.is_empty() {
+            return Err(Cow::Borrowed("Add at least one service"));
+        }
+        if self.services.len() > MAX_ONBOARDING_SERVICES {
+            return Err(Cow::Owned(format!(
+                "At most {MAX_ONBOARDING_SERVICES} services can be listed"
+            )));
+        }
+        for (i, service) in self.services.iter().enumerate() {
+            service.validate()?;
+            let name = service.name.trim();
+            if self.services[..i]
+                .iter()
+                .any(|other| other.name.trim().eq_ignore_ascii_case(name))
+            {
+                return Err(Cow::Owned(format!("{name} is listed twice")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates where and when the business works.
+    ///
+    /// # Validation Rules
+    ///
+    /// - Postcodes: at least one, each a district such as `CV21`
+    /// - Opening hours: each valid
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error message for the first validation failure encountered.
+    pub fn validate_area(&self) -> Result<(), Cow<'static, str>> {
+        if self.postcodes.is_empty() {
+            return Err(Cow::Borrowed("Add at least one postcode district"));
+        }
+        if let Some(postcode) = self.postcodes.iter().find(|p| !is_postcode_district(p)) {
+            return Err(Cow::Owned(format!(
+                "{} is not a postcode district (like CV21)",
+                postcode.trim()
+            )));
+        }
+        for hours in &self.opening_hours {
+            hours.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Validates the accreditations and testimonials.
+    ///
+    /// # Validation Rules
+    ///
+    /// - Accreditations: 1 to 100 characters each
+    /// - Testimonials: each valid
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error message for the first validation failure encountered.
+    pub fn validate_extras(&self) -> Result<(), Cow<'static, str>> {
+        if self
+            .accreditations
+            .iter()
+            .any(|a| a.trim().is_empty() || a.trim().chars().count() > 100)
+        {
+            return Err(Cow::Borrowed(
+                "Accreditations must be between 1 and 100 characters",
+            ));
+        }
+        for testimonial in &self.testimonials {
+            testimonial.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for OnboardingService {
+    /// Validates a service offered during onboarding.
+    ///
+    /// # Validation Rules
+    ///
+    /// - Name and description: must not be empty
+    /// - Prices: not negative, and "from" at most "to"
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error message for the first validation failure encountered.
+    fn validate(&self) -> Result<(), Cow<'static, str>> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err(Cow::Borrowed("Service name is required"));
+        }
+        if self.description.trim().is_empty() {
+            return Err(Cow::Owned(format!("{name} needs a description")));
+        }
+        if self.price_from_cents < 0 || self.price_to_cents < self.price_from_cents {
+            return Err(Cow::Owned(format!(
+                "{name} needs a price range with the lowest price first"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for OpeningHours {
+    /// Validates opening times.
+    ///
+    /// # Validation Rules
+    ///
+    /// - Days: must not be empty
+    /// - Opens and closes: `HH:MM` on a 24 hour clock, opening first
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error message for the first validation failure encountered.
+    fn validate(&self) -> Result<(), Cow<'static, str>> {
+        if self.days.trim().is_empty() {
+            return Err(Cow::Borrowed("Opening hours need the days they apply to"));
+        }
+        match (minutes(&self.opens), minutes(&self.closes)) {
+            (Some(opens), Some(closes)) if opens < closes => Ok(()),
+            (Some(_), Some(_)) => Err(Cow::Owned(format!(
+                "{} must open before closing",
+                self.days.trim()
+            ))),
+            _ => Err(Cow::Owned(format!(
+                "{} needs times like 08:00 and 17:30",
+                self.days.trim()
+            ))),
+        }
+    }
+}
+
+impl Validate for Testimonial {
+    /// Validates a testimonial.
+    ///
+    /// # Validation Rules
+    ///
+    /// - Quote and author: must not be empty
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive error message for the first validation failure encountered.
+    fn validate(&self) -> Result<(), Cow<'static, str>> {
+        if self.quote.trim().is_empty() {
+            return Err(Cow::Borrowed("Testimonials need the customer's words"));
+        }
+        if self.author.trim().is_empty() {
+            return Err(Cow::Borrowed("Testimonials need who gave them"));
+        }
+        Ok(())
+    }
+}
+
+/// Whether `value` is a postcode district (outward code), e.g. `CV1`,
+/// `B27` or `SW1A`.
+fn is_postcode_district(value: &str) -> bool {
+    let value = value.trim().to_ascii_uppercase();
+    let letters = value.chars().take_while(char::is_ascii_alphabetic).count();
+    let rest = &value[letters..];
+    let mut rest_chars = rest.chars();
+
+    (1..=2).contains(&letters)
+        && (1..=2).contains(&rest.len())
+        && rest_chars.next().is_some_and(|c| c.is_ascii_digit())
+        && rest_chars.all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Minutes past midnight of an `HH:MM` time.
+fn minutes(time: &str) -> Option<u32> {
+    let (hours, mins) = time.trim().split_once(':')?;
+    if hours.len() != 2 || mins.len() != 2 {
+        return None;
+    }
+    let (hours, mins): (u32, u32) = (hours.parse().ok()?, mins.parse().ok()?);
+    (hours < 24 && mins < 60).then_some(hours * 60 + mins)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,4 +528,109 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Image URL"));
     }
+
+    fn fx_onboarding() -> OnboardingForm {
+        OnboardingForm {
+            business_name: "Jones Plumbing".to_string(),
+            trade: "Plumber".to_string(),
+            contact_name: "Gareth Jones".to_string(),
+            email: "gareth@jonesplumbing.co.uk".to_string(),
+            phone: "07700 900123".to_string(),
+            town: "Rugby".to_string(),
+            services: vec![OnboardingService {
+                name: "Boiler Servicing".to_string(),
+                description: "Annual service and safety check.".to_string(),
+                price_from_cents: 6000,
+                price_to_cents: 9000,
+            }],
+            postcodes: vec!["CV21".to_string(), "cv22".to_string()],
+            logo_url: Some("https://jonesplumbing.co.uk/logo.png".to_string()),
+            opening_hours: vec![OpeningHours {
+                days: "Monday - Friday".to_string(),
+                opens: "08:00".to_string(),
+                closes: "17:30".to_string(),
+            }],
+            accreditations: vec!["Gas Safe Registered".to_string()],
+            testimonials: vec![Testimonial {
+                quote: "Fixed our boiler the same day.".to_string(),
+                author: "Sue, Hillmorton".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_onboarding_validate_success() {
+        assert!(fx_onboarding().validate().is_ok());
+    }
+
+    #[test]
+    fn test_onboarding_validate_errors() {
+        type Change = fn(&mut OnboardingForm);
+        let cases: [(Change, &str); 9] = [
+            (|f| f.business_name = " ".to_string(), "Business name"),
+            (|f| f.email = "gareth".to_string(), "@"),
+            (|f| f.services.clear(), "at least one service"),
+            (|f| f.services.push(f.services[0].clone()), "listed twice"),
+            (|f| f.services[0].price_to_cents = 100, "price range"),
+            (
+                |f| f.postcodes = vec!["Rugby".to_string()],
+                "postcode district",
+            ),
+            (
+                |f| f.logo_url = Some("http://example.com/logo.png".to_string()),
+                "https://",
+            ),
+            (
+                |f| f.opening_hours[0].closes = "07:00".to_string(),
+                "open before",
+            ),
+            (|f| f.testimonials[0].author.clear(), "who gave"),
+        ];
+        for (change, expected) in cases {
+            let mut form = fx_onboarding();
+            change(&mut form);
+            let err = form.validate().unwrap_err();
+            assert!(err.contains(expected), "{err} should mention {expected}");
+        }
+    }
+
+    #[test]
+    fn test_onboarding_validate_steps() {
+        let full = fx_onboarding();
+        let business_only = OnboardingForm {
+            services: vec![],
+            postcodes: vec![],
+            ..full.clone()
+        };
+        assert!(business_only.validate_business().is_ok());
+        assert!(business_only.validate_services().is_err());
+        assert!(business_only.validate_area().is_err());
+        assert!(business_only.validate().is_err());
+
+        let no_business = OnboardingForm {
+            business_name: String::new(),
+            ..full
+        };
+        assert!(no_business.validate_business().is_err());
+        assert!(no_business.validate_services().is_ok());
+        assert!(no_business.validate_area().is_ok());
+        assert!(no_business.validate_extras().is_ok());
+    }
+
+    #[test]
+    fn test_is_postcode_district() {
+        for ok in ["CV1", "b27", "SW1A", " CV21 ", "M1"] {
+            assert!(is_postcode_district(ok), "{ok}");
+        }
+        for bad in ["", "CV", "123", "CV1 2AB", "CVX1"] {
+            assert!(!is_postcode_district(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_minutes() {
+        assert_eq!(minutes("08:30"), Some(510));
+        assert_eq!(minutes("24:00"), None);
+        assert_eq!(minutes("8:30"), None);
+    }
 }